@@ -0,0 +1,182 @@
+// Copyright 2026 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Guards against accidentally large rewrites.
+//!
+//! The `limits.max-changed-files` and `limits.max-changed-bytes` settings
+//! bound how much content a single operation may modify. Changes which are
+//! only the result of rebasing onto different parents aren't counted.
+
+use std::collections::HashSet;
+
+use futures::StreamExt as _;
+use jj_lib::backend::BackendResult;
+use jj_lib::config::ConfigGetError;
+use jj_lib::matchers::EverythingMatcher;
+use jj_lib::merged_tree::MergedTree;
+use jj_lib::repo::MutableRepo;
+use jj_lib::repo::Repo as _;
+use jj_lib::repo_path::RepoPathBuf;
+use jj_lib::settings::HumanByteSize;
+use jj_lib::settings::UserSettings;
+
+use crate::command_error::CommandError;
+use crate::command_error::user_error;
+use crate::diff_util::file_content_size;
+use crate::ui::Ui;
+
+/// Configured limits on the size of a single operation.
+#[derive(Clone, Debug)]
+pub struct ChangeLimits {
+    max_changed_files: Option<usize>,
+    max_changed_bytes: Option<u64>,
+    refuse: bool,
+}
+
+impl ChangeLimits {
+    /// Loads the limits from the `limits.*` settings. A limit of 0 disables
+    /// the corresponding check.
+    pub fn from_settings(settings: &UserSettings) -> Result<Self, ConfigGetError> {
+        let max_changed_files = settings.get::<usize>("limits.max-changed-files")?;
+        let HumanByteSize(max_changed_bytes) =
+            settings.get_value_with("limits.max-changed-bytes", TryInto::try_into)?;
+        let refuse = settings.get_bool("limits.refuse-large-change")?;
+        Ok(Self {
+            max_changed_files: (max_changed_files != 0).then_some(max_changed_files),
+            max_changed_bytes: (max_changed_bytes != 0).then_some(max_changed_bytes),
+            refuse,
+        })
+    }
+
+    /// Returns true if any limit is configured.
+    pub fn is_enabled(&self) -> bool {
+        self.max_changed_files.is_some() || self.max_changed_bytes.is_some()
+    }
+
+    /// Measures the commits rewritten in `mut_repo` and warns about or refuses
+    /// the change if it exceeds the limits.
+    ///
+    /// `allow_large_change` downgrades a refusal to a warning.
+    pub async fn check(
+        &self,
+        ui: &Ui,
+        mut_repo: &MutableRepo,
+        allow_large_change: bool,
+    ) -> Result<(), CommandError> {
+        if !self.is_enabled() {
+            return Ok(());
+        }
+        let size = measure_rewrites(mut_repo, self.max_changed_bytes.is_some()).await?;
+        let mut exceeded = vec![];
+        if let Some(max) = self.max_changed_files
+            && size.files > max
+        {
+            exceeded.push(format!(
+                "{files} files were modified (limit: {max})",
+                files = size.files
+            ));
+        }
+        if let Some(max) = self.max_changed_bytes
+            && size.bytes > max
+        {
+            exceeded.push(format!(
+                "{bytes} were modified (limit: {max})",
+                bytes = HumanByteSize(size.bytes),
+                max = HumanByteSize(max)
+            ));
+        }
+        if exceeded.is_empty() {
+            return Ok(());
+        }
+        let details = exceeded.join(", ");
+        if self.refuse && !allow_large_change {
+            return Err(
+                user_error(format!("Refusing to make a large change: {details}")).hinted(
+                    "Use `--allow-large-change` if this is intended, or adjust the `limits.*` \
+                     settings.",
+                ),
+            );
+        }
+        writeln!(ui.warning_default(), "Large change: {details}")?;
+        Ok(())
+    }
+}
+
+/// Amount of content modified by an operation.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct ChangeSize {
+    /// Number of modified paths.
+    pub files: usize,
+    /// Total size of the modified file contents.
+    pub bytes: u64,
+}
+
+/// Sums up the changes made by the commits rewritten in `mut_repo`.
+///
+/// Paths which changed only because the commit was rebased onto a different
+/// tree aren't counted. Commits which were rewritten again within the same
+/// transaction are counted once, by their final version.
+pub async fn measure_rewrites(
+    mut_repo: &MutableRepo,
+    count_bytes: bool,
+) -> BackendResult<ChangeSize> {
+    let store = mut_repo.store();
+    let predecessors = mut_repo.commit_predecessors();
+    let intermediate: HashSet<_> = predecessors.values().flatten().collect();
+    let mut size = ChangeSize::default();
+    for (new_id, old_ids) in predecessors {
+        let [old_id, ..] = old_ids.as_slice() else {
+            continue;
+        };
+        if intermediate.contains(new_id) {
+            continue;
+        }
+        let new_commit = store.get_commit_async(new_id).await?;
+        let old_commit = store.get_commit_async(old_id).await?;
+        let rebased_paths: HashSet<RepoPathBuf> =
+            if new_commit.parent_ids() == old_commit.parent_ids() {
+                HashSet::new()
+            } else {
+                let old_parent_tree = old_commit.parent_tree_async(mut_repo).await?;
+                let new_parent_tree = new_commit.parent_tree_async(mut_repo).await?;
+                changed_paths(&old_parent_tree, &new_parent_tree).await?
+            };
+        let mut diff_stream = old_commit
+            .tree()
+            .diff_stream(&new_commit.tree(), &EverythingMatcher);
+        while let Some(entry) = diff_stream.next().await {
+            if rebased_paths.contains(&entry.path) {
+                continue;
+            }
+            let diff = entry.values?;
+            size.files += 1;
+            if count_bytes {
+                let after = file_content_size(store, &entry.path, &diff.after).await?;
+                let before = file_content_size(store, &entry.path, &diff.before).await?;
+                size.bytes += after.max(before);
+            }
+        }
+    }
+    Ok(size)
+}
+
+async fn changed_paths(from: &MergedTree, to: &MergedTree) -> BackendResult<HashSet<RepoPathBuf>> {
+    let mut paths = HashSet::new();
+    let mut diff_stream = from.diff_stream(to, &EverythingMatcher);
+    while let Some(entry) = diff_stream.next().await {
+        entry.values?;
+        paths.insert(entry.path);
+    }
+    Ok(paths)
+}
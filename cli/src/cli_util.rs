@@ -30,6 +30,7 @@ use std::rc::Rc;
 use std::str::FromStr;
 use std::sync::Arc;
 use std::sync::LazyLock;
+use std::time::Duration;
 use std::time::SystemTime;
 
 use bstr::ByteVec as _;
@@ -73,6 +74,7 @@ use jj_lib::fileset::FilesetParseContext;
 use jj_lib::gitignore::GitIgnoreError;
 use jj_lib::gitignore::GitIgnoreFile;
 use jj_lib::id_prefix::IdPrefixContext;
+use jj_lib::local_working_copy::LocalWorkingCopy;
 use jj_lib::lock::FileLock;
 use jj_lib::matchers::Matcher;
 use jj_lib::matchers::NothingMatcher;
@@ -93,6 +95,7 @@ use jj_lib::ref_name::RemoteName;
 use jj_lib::ref_name::RemoteRefSymbol;
 use jj_lib::ref_name::WorkspaceName;
 use jj_lib::ref_name::WorkspaceNameBuf;
+use jj_lib::refs::diff_named_ref_targets;
 use jj_lib::repo::CheckOutCommitError;
 use jj_lib::repo::EditCommitError;
 use jj_lib::repo::MutableRepo;
@@ -145,13 +148,18 @@ use jj_lib::workspace::WorkspaceLoader;
 use jj_lib::workspace::WorkspaceLoaderFactory;
 use jj_lib::workspace::default_working_copy_factories;
 use jj_lib::workspace::get_working_copy_factory;
+use jj_lib::workspace_store::WorkspaceStore;
+use jj_lib::workspace_store::repair_moved_workspace;
 use pollster::FutureExt as _;
 use tracing::instrument;
 use tracing_chrome::ChromeLayerBuilder;
 use tracing_subscriber::prelude::*;
 
+use crate::change_limits::ChangeLimits;
 use crate::command_error::CommandError;
+use crate::command_error::ErrorCode;
 use crate::command_error::cli_error;
+use crate::command_error::config_error;
 use crate::command_error::config_error_with_message;
 use crate::command_error::handle_command_result;
 use crate::command_error::internal_error;
@@ -165,10 +173,13 @@ use crate::commit_templater::CommitTemplateLanguageExtension;
 use crate::complete;
 use crate::config::ConfigArgKind;
 use crate::config::ConfigEnv;
+use crate::config::ConfigWatcher;
 use crate::config::RawConfig;
 use crate::config::config_from_environment;
 use crate::config::load_aliases_map;
+use crate::config::load_fileset_aliases_map;
 use crate::config::parse_config_args;
+use crate::decorations::Decorations;
 use crate::description_util::TextEditor;
 use crate::diff_util;
 use crate::diff_util::DiffFormat;
@@ -177,11 +188,17 @@ use crate::diff_util::DiffRenderer;
 use crate::formatter::FormatRecorder;
 use crate::formatter::Formatter;
 use crate::formatter::FormatterExt as _;
+use crate::i18n;
+use crate::i18n::CatalogLoadError;
 use crate::merge_tools::DiffEditor;
 use crate::merge_tools::MergeEditor;
 use crate::merge_tools::MergeToolConfigError;
 use crate::operation_templater::OperationTemplateLanguage;
 use crate::operation_templater::OperationTemplateLanguageExtension;
+use crate::ref_filter::RefFilterAliasesMap;
+use crate::resource_limits;
+use crate::resource_limits::ResourceLimits;
+use crate::resource_limits::ResourceWatchdog;
 use crate::revset_util;
 use crate::revset_util::RevsetExpressionEvaluator;
 use crate::revset_util::parse_union_name_patterns;
@@ -193,6 +210,7 @@ use crate::templater::TemplateRenderer;
 use crate::templater::WrapTemplateProperty;
 use crate::text_util;
 use crate::ui::ColorChoice;
+use crate::ui::ErrorFormat;
 use crate::ui::Ui;
 
 const SHORT_CHANGE_ID_TEMPLATE_TEXT: &str = "format_short_change_id_with_change_offset(self)";
@@ -304,6 +322,7 @@ struct CommandHelperData {
     string_args: Vec<String>,
     matches: ArgMatches,
     global_args: GlobalArgs,
+    at_operation_from_env: bool,
     config_env: ConfigEnv,
     config_migrations: Vec<ConfigMigrationRule>,
     raw_config: RawConfig,
@@ -311,6 +330,8 @@ struct CommandHelperData {
     revset_extensions: Arc<RevsetExtensions>,
     commit_template_extensions: Vec<Arc<dyn CommitTemplateLanguageExtension>>,
     operation_template_extensions: Vec<Arc<dyn OperationTemplateLanguageExtension>>,
+    // once_cell's version provides get_or_try_init()
+    decorations: once_cell::unsync::OnceCell<Rc<Decorations>>,
     maybe_workspace_loader: Result<Box<dyn WorkspaceLoader>, CommandError>,
     store_factories: StoreFactories,
     working_copy_factories: WorkingCopyFactories,
@@ -354,6 +375,21 @@ impl CommandHelper {
         &self.data.raw_config
     }
 
+    /// Creates a watcher which reloads the config files of this command when
+    /// they are edited. Long-lived commands should use this to pick up config
+    /// changes without restarting.
+    ///
+    /// The message catalog is reloaded along with the config.
+    pub fn config_watcher(&self) -> ConfigWatcher<'_> {
+        let mut watcher = ConfigWatcher::new(
+            self.data.config_env.clone(),
+            self.data.raw_config.clone(),
+            &self.data.config_migrations,
+        );
+        watcher.add_invalidation_hook(load_message_catalog);
+        watcher
+    }
+
     /// Settings for the current command and workspace.
     ///
     /// This may be different from the settings for new workspace created by
@@ -440,6 +476,21 @@ impl CommandHelper {
         Ok(workspace_command)
     }
 
+    /// Like [`Self::workspace_helper()`], but for commands which don't change
+    /// the repo except by snapshotting the working copy.
+    ///
+    /// If another process is checking out a commit in this workspace, the
+    /// snapshot is skipped with a warning instead of waiting for it.
+    #[instrument(skip(self, ui))]
+    pub fn read_only_workspace_helper(
+        &self,
+        ui: &Ui,
+    ) -> Result<WorkspaceCommandHelper, CommandError> {
+        let (workspace_command, stats) = self.read_only_workspace_helper_with_stats(ui)?;
+        print_snapshot_stats(ui, &stats, workspace_command.env().path_converter())?;
+        Ok(workspace_command)
+    }
+
     /// Loads workspace and repo, then snapshots the working copy if allowed and
     /// returns the SnapshotStats.
     ///
@@ -450,8 +501,27 @@ impl CommandHelper {
     pub fn workspace_helper_with_stats(
         &self,
         ui: &Ui,
+    ) -> Result<(WorkspaceCommandHelper, SnapshotStats), CommandError> {
+        self.workspace_helper_with_intent(ui, WorkspaceIntent::Write)
+    }
+
+    /// Like [`Self::workspace_helper_with_stats()`], but for read-only
+    /// commands. See [`Self::read_only_workspace_helper()`].
+    #[instrument(skip(self, ui))]
+    pub fn read_only_workspace_helper_with_stats(
+        &self,
+        ui: &Ui,
+    ) -> Result<(WorkspaceCommandHelper, SnapshotStats), CommandError> {
+        self.workspace_helper_with_intent(ui, WorkspaceIntent::Read)
+    }
+
+    fn workspace_helper_with_intent(
+        &self,
+        ui: &Ui,
+        intent: WorkspaceIntent,
     ) -> Result<(WorkspaceCommandHelper, SnapshotStats), CommandError> {
         let mut workspace_command = self.workspace_helper_no_snapshot(ui)?;
+        workspace_command.intent = intent;
 
         let (workspace_command, stats) = match workspace_command.maybe_snapshot_impl(ui) {
             Ok(stats) => (workspace_command, stats),
@@ -480,7 +550,21 @@ impl CommandHelper {
         &self,
         ui: &Ui,
     ) -> Result<WorkspaceCommandHelper, CommandError> {
-        let workspace = self.load_workspace()?;
+        self.workspace_helper_no_snapshot_with_settings(ui, self.settings())
+    }
+
+    /// Like [`Self::workspace_helper_no_snapshot()`], but loads the workspace
+    /// with the given `settings`, e.g. reloaded by [`ConfigWatcher`].
+    #[instrument(skip_all)]
+    pub fn workspace_helper_no_snapshot_with_settings(
+        &self,
+        ui: &Ui,
+        settings: &UserSettings,
+    ) -> Result<WorkspaceCommandHelper, CommandError> {
+        let workspace = self.load_workspace_with_settings(settings)?;
+        if settings.get_bool("working-copy.auto-repair-path")? {
+            repair_workspace_path(ui, &workspace)?;
+        }
         let op_head = self.resolve_operation(ui, workspace.repo_loader())?;
         let repo = workspace.repo_loader().load_at(&op_head).block_on()?;
         let mut env = self.workspace_environment(ui, &workspace)?;
@@ -526,10 +610,17 @@ impl CommandHelper {
     /// Loads workspace for the current command.
     #[instrument(skip_all)]
     pub fn load_workspace(&self) -> Result<Workspace, CommandError> {
+        self.load_workspace_with_settings(&self.data.settings)
+    }
+
+    fn load_workspace_with_settings(
+        &self,
+        settings: &UserSettings,
+    ) -> Result<Workspace, CommandError> {
         let loader = self.workspace_loader()?;
         loader
             .load(
-                &self.data.settings,
+                settings,
                 &self.data.store_factories,
                 &self.data.working_copy_factories,
             )
@@ -633,9 +724,14 @@ impl CommandHelper {
                 let merged_stats = {
                     let SnapshotStats {
                         mut untracked_paths,
+                        mut mixed_eol_paths,
                     } = stale_stats;
                     untracked_paths.extend(fresh_stats.untracked_paths);
-                    SnapshotStats { untracked_paths }
+                    mixed_eol_paths.extend(fresh_stats.mixed_eol_paths);
+                    SnapshotStats {
+                        untracked_paths,
+                        mixed_eol_paths,
+                    }
                 };
                 Ok((workspace_command, merged_stats))
             }
@@ -815,8 +911,9 @@ pub struct WorkspaceCommandEnvironment {
     revsets_use_glob_by_default: bool,
     path_converter: RepoPathUiConverter,
     workspace_name: WorkspaceNameBuf,
+    repo_path: PathBuf,
     immutable_heads_expression: Arc<UserRevsetExpression>,
-    short_prefixes_expression: Option<Arc<UserRevsetExpression>>,
+    short_prefixes_expressions: Vec<Arc<UserRevsetExpression>>,
     conflict_marker_style: ConflictMarkerStyle,
 }
 
@@ -842,8 +939,9 @@ impl WorkspaceCommandEnvironment {
             revsets_use_glob_by_default: settings.get("ui.revsets-use-glob-by-default")?,
             path_converter,
             workspace_name: workspace.workspace_name().to_owned(),
+            repo_path: workspace.repo_path().to_owned(),
             immutable_heads_expression: RevsetExpression::root(),
-            short_prefixes_expression: None,
+            short_prefixes_expressions: vec![],
             conflict_marker_style: settings.get("ui.conflict-marker-style")?,
         };
         env.reload_revset_expressions(ui)?;
@@ -908,17 +1006,15 @@ impl WorkspaceCommandEnvironment {
     /// Creates fresh new context which manages cache of short commit/change ID
     /// prefixes. New context should be created per repo view (or operation.)
     pub fn new_id_prefix_context(&self) -> IdPrefixContext {
-        let context = IdPrefixContext::new(self.command.revset_extensions().clone());
-        match &self.short_prefixes_expression {
-            None => context,
-            Some(expression) => context.disambiguate_within(expression.clone()),
-        }
+        IdPrefixContext::new(self.command.revset_extensions().clone())
+            .include_hidden(self.command.global_args().include_hidden)
+            .disambiguate_within_scopes(self.short_prefixes_expressions.iter().cloned())
     }
 
     /// Updates parsed revset expressions.
     fn reload_revset_expressions(&mut self, ui: &Ui) -> Result<(), CommandError> {
         self.immutable_heads_expression = self.load_immutable_heads_expression(ui)?;
-        self.short_prefixes_expression = self.load_short_prefixes_expression(ui)?;
+        self.short_prefixes_expressions = self.load_short_prefixes_expressions(ui)?;
         Ok(())
     }
 
@@ -953,28 +1049,39 @@ impl WorkspaceCommandEnvironment {
         Ok(expression)
     }
 
-    fn load_short_prefixes_expression(
+    /// Loads the revsets to disambiguate short prefixes within, in order of
+    /// priority.
+    fn load_short_prefixes_expressions(
         &self,
         ui: &Ui,
-    ) -> Result<Option<Arc<UserRevsetExpression>>, CommandError> {
-        let revset_string = self
-            .settings
-            .get_string("revsets.short-prefixes")
-            .optional()?
-            .map_or_else(|| self.settings.get_string("revsets.log"), Ok)?;
-        if revset_string.is_empty() {
-            Ok(None)
-        } else {
-            let mut diagnostics = RevsetDiagnostics::new();
-            let expression = revset::parse(
-                &mut diagnostics,
-                &revset_string,
-                &self.revset_parse_context(),
-            )
-            .map_err(|err| config_error_with_message("Invalid `revsets.short-prefixes`", err))?;
-            print_parse_diagnostics(ui, "In `revsets.short-prefixes`", &diagnostics)?;
-            Ok(Some(expression))
+    ) -> Result<Vec<Arc<UserRevsetExpression>>, CommandError> {
+        #[derive(serde::Deserialize)]
+        #[serde(untagged)]
+        enum RevsetOrList {
+            Revset(String),
+            List(Vec<String>),
         }
+
+        let revset_strings = match self.settings.get("revsets.short-prefixes").optional()? {
+            Some(RevsetOrList::Revset(revset)) => vec![revset],
+            Some(RevsetOrList::List(revsets)) => revsets,
+            None => vec![self.settings.get_string("revsets.log")?],
+        };
+        let mut diagnostics = RevsetDiagnostics::new();
+        let expressions = revset_strings
+            .iter()
+            .filter(|revset_string| !revset_string.is_empty())
+            .map(|revset_string| {
+                revset::parse(
+                    &mut diagnostics,
+                    revset_string,
+                    &self.revset_parse_context(),
+                )
+            })
+            .try_collect()
+            .map_err(|err| config_error_with_message("Invalid `revsets.short-prefixes`", err))?;
+        print_parse_diagnostics(ui, "In `revsets.short-prefixes`", &diagnostics)?;
+        Ok(expressions)
     }
 
     /// Returns first immutable commit.
@@ -1045,12 +1152,14 @@ impl WorkspaceCommandEnvironment {
     ) -> CommitTemplateLanguage<'a> {
         CommitTemplateLanguage::new(
             repo,
+            &self.repo_path,
             &self.path_converter,
             &self.workspace_name,
             self.revset_parse_context(),
             id_prefix_context,
             self.immutable_expression(),
             self.conflict_marker_style,
+            &self.command.data.decorations,
             &self.command.data.commit_template_extensions,
         )
     }
@@ -1067,6 +1176,19 @@ pub struct GitImportExportLock {
     _lock: Option<FileLock>,
 }
 
+impl GitImportExportLock {
+    /// Atomically replaces the file at `path` with `contents` while the lock
+    /// is held, so concurrent imports don't leave a torn or stale stamp.
+    #[cfg(feature = "git")]
+    fn write_stamp(&self, path: &Path, contents: &str) -> io::Result<()> {
+        let dir = path.parent().expect("stamp path should have a parent");
+        let mut temp_file = tempfile::NamedTempFile::new_in(dir)?;
+        temp_file.write_all(contents.as_bytes())?;
+        jj_lib::file_util::persist_temp_file(temp_file, path)?;
+        Ok(())
+    }
+}
+
 /// Provides utilities for writing a command that works on a [`Workspace`]
 /// (which most commands do).
 pub struct WorkspaceCommandHelper {
@@ -1078,6 +1200,17 @@ pub struct WorkspaceCommandHelper {
     op_summary_template_text: String,
     may_update_working_copy: bool,
     working_copy_shared_with_git: bool,
+    intent: WorkspaceIntent,
+}
+
+/// How a command is going to use the workspace.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum WorkspaceIntent {
+    /// The command doesn't change the repo except by snapshotting the working
+    /// copy.
+    Read,
+    /// The command may rewrite commits or update the working copy.
+    Write,
 }
 
 enum SnapshotWorkingCopyError {
@@ -1126,6 +1259,7 @@ impl WorkspaceCommandHelper {
             op_summary_template_text,
             may_update_working_copy,
             working_copy_shared_with_git,
+            intent: WorkspaceIntent::Write,
         };
         // Parse commit_summary template early to report error before starting
         // mutable operation.
@@ -1144,8 +1278,11 @@ impl WorkspaceCommandHelper {
         if self.may_update_working_copy {
             Ok(())
         } else {
-            let hint = if self.env.command.global_args().ignore_working_copy {
+            let global_args = self.env.command.global_args();
+            let hint = if global_args.ignore_working_copy {
                 "Don't use --ignore-working-copy."
+            } else if global_args.no_snapshot_if_busy {
+                "Don't use --no-snapshot-if-busy."
             } else {
                 "Don't use --at-op."
             };
@@ -1169,6 +1306,59 @@ impl WorkspaceCommandHelper {
         Ok(GitImportExportLock { _lock: lock })
     }
 
+    /// Acquires the workspace-level lock that serializes snapshots of the
+    /// working copy across processes.
+    ///
+    /// Snapshots of other workspaces of the same repo aren't blocked, since
+    /// they write to different working copies and the operations are merged
+    /// like any other concurrent operations.
+    ///
+    /// With `--no-snapshot-if-busy`, returns `None` instead of waiting if
+    /// another process holds the lock.
+    fn lock_snapshot(&self) -> Result<Option<FileLock>, CommandError> {
+        let lock = if self.env.command.global_args().no_snapshot_if_busy {
+            self.workspace.try_lock_snapshot()
+        } else {
+            self.workspace.lock_snapshot().map(Some)
+        };
+        lock.map_err(|err| user_error_with_message("Failed to take lock for snapshot", err))
+    }
+
+    /// Acquires the lock that prevents other processes from checking out a
+    /// commit in this workspace. Returns `None` if a checkout is in progress.
+    fn try_lock_checkout(&self) -> Result<Option<FileLock>, CommandError> {
+        self.workspace
+            .try_lock_checkout()
+            .map_err(|err| user_error_with_message("Failed to take lock for checkout", err))
+    }
+
+    /// Path to the file whose modification time records when this workspace
+    /// was last snapshotted. `None` if the working copy has no state directory.
+    fn last_snapshot_marker_path(&self) -> Option<PathBuf> {
+        self.working_copy_state_path()
+            .map(|path| path.join("last_snapshot"))
+    }
+
+    /// Returns true if this workspace was snapshotted less than
+    /// `snapshot.cooldown-seconds` ago.
+    fn snapshotted_recently(&self) -> Result<bool, CommandError> {
+        let cooldown_secs: u64 = self.settings().get("snapshot.cooldown-seconds")?;
+        if cooldown_secs == 0 {
+            return Ok(false);
+        }
+        let Some(marker_path) = self.last_snapshot_marker_path() else {
+            return Ok(false);
+        };
+        let Ok(modified) = std::fs::metadata(marker_path).and_then(|metadata| metadata.modified())
+        else {
+            return Ok(false);
+        };
+        let elapsed = SystemTime::now()
+            .duration_since(modified)
+            .unwrap_or_default();
+        Ok(elapsed < Duration::from_secs(cooldown_secs))
+    }
+
     /// Note that unless you have a good reason not to do so, you should always
     /// call [`print_snapshot_stats`] with the [`SnapshotStats`] returned by
     /// this function to present possible untracked files to the user.
@@ -1178,6 +1368,47 @@ impl WorkspaceCommandHelper {
             return Ok(SnapshotStats::default());
         }
 
+        // A checkout can take a while, and read-only commands would otherwise
+        // wait for it to release the working-copy lock. Since the working copy
+        // is about to be replaced anyway, show the last snapshot instead. The
+        // lock is held until the snapshot is done, so no checkout can start
+        // in the meantime.
+        let checkout_lock = if self.intent == WorkspaceIntent::Read {
+            let Some(lock) = self.try_lock_checkout().map_err(snapshot_command_error)? else {
+                tracing::info!("Skipping snapshot because another process is checking out");
+                writeln!(
+                    ui.warning_default(),
+                    "Skipped snapshot because the working copy is being updated by another process"
+                )
+                .map_err(snapshot_command_error)?;
+                self.may_update_working_copy = false;
+                return Ok(SnapshotStats::default());
+            };
+            Some(lock)
+        } else {
+            None
+        };
+
+        // Serialize snapshots so that concurrent processes (e.g. a shell prompt
+        // and a user command) don't race to snapshot the same working copy.
+        // With --no-snapshot-if-busy, we'd rather show possibly stale state
+        // than wait, so the working copy is then treated as if
+        // --ignore-working-copy had been specified.
+        let Some(_snapshot_lock) = self.lock_snapshot().map_err(snapshot_command_error)? else {
+            tracing::info!("Skipping snapshot because another process is snapshotting");
+            self.may_update_working_copy = false;
+            return Ok(SnapshotStats::default());
+        };
+        if self.env.command.global_args().no_snapshot_if_busy
+            && self
+                .snapshotted_recently()
+                .map_err(snapshot_command_error)?
+        {
+            tracing::info!("Skipping snapshot because of snapshot.cooldown-seconds");
+            self.may_update_working_copy = false;
+            return Ok(SnapshotStats::default());
+        }
+
         // Acquire git import/export lock once for the entire import/snapshot/export
         // cycle. This prevents races with other processes during Git HEAD and
         // refs import/export.
@@ -1220,6 +1451,9 @@ impl WorkspaceCommandHelper {
         // In that situation, the ref would be conflicted anyway, so export
         // failure is okay.
         let stats = self.snapshot_working_copy(ui).block_on()?;
+        // import_git_refs() can check out a rebased working-copy commit, which
+        // takes the checkout lock again.
+        drop(checkout_lock);
 
         // import_git_refs() can rebase the working-copy commit.
         #[cfg(feature = "git")]
@@ -1227,6 +1461,20 @@ impl WorkspaceCommandHelper {
             self.import_git_refs(ui, &git_import_export_lock)
                 .map_err(snapshot_command_error)?;
         }
+
+        // The time is only needed for the cooldown. Failing to record it only
+        // disables the cooldown.
+        let cooldown_secs: u64 = self
+            .settings()
+            .get("snapshot.cooldown-seconds")
+            .map_err(snapshot_command_error)?;
+        if cooldown_secs > 0
+            && let Some(marker_path) = self.last_snapshot_marker_path()
+        {
+            std::fs::write(marker_path, b"")
+                .inspect_err(|err| tracing::warn!(?err, "Failed to record snapshot time"))
+                .ok();
+        }
         Ok(stats)
     }
 
@@ -1318,10 +1566,27 @@ impl WorkspaceCommandHelper {
         let remote_settings = self.settings().remote_settings()?;
         let import_options =
             crate::git_util::load_git_import_options(ui, &git_settings, &remote_settings)?;
+        // Skip enumerating refs if neither the Git refs nor the operation
+        // changed since the last import.
+        let stamp_path = self.workspace.repo_path().join("git_import_stamp");
+        let stamp = git::git_refs_fingerprint(self.repo().store()).map(|fingerprint| {
+            let op_id = self.repo().op_id().hex();
+            let ignored_refs = &git_settings.ignored_refs;
+            let branch_prefixes = git_settings.branch_prefixes.iter().collect_vec();
+            format!("{op_id}\n{fingerprint}\n{ignored_refs:?}\n{branch_prefixes:?}\n")
+        });
+        if stamp.is_some() && std::fs::read_to_string(&stamp_path).ok() == stamp {
+            return Ok(());
+        }
         let mut tx = self.start_transaction();
         let stats = git::import_refs(tx.repo_mut(), &import_options)?;
         crate::git_util::print_git_import_stats_summary(ui, &stats)?;
         if !tx.repo().has_changes() {
+            if let Some(stamp) = &stamp
+                && let Err(err) = git_import_export_lock.write_stamp(&stamp_path, stamp)
+            {
+                tracing::warn!(?err, "failed to write git import stamp");
+            }
             return Ok(());
         }
 
@@ -1358,6 +1623,10 @@ impl WorkspaceCommandHelper {
         self.workspace.working_copy()
     }
 
+    pub fn workspace_store(&self) -> &Arc<dyn WorkspaceStore> {
+        self.workspace.repo_loader().workspace_store()
+    }
+
     pub fn env(&self) -> &WorkspaceCommandEnvironment {
         &self.env
     }
@@ -1427,6 +1696,13 @@ to the current parents may contain changes from multiple commits.
         self.workspace.workspace_root()
     }
 
+    /// Directory where the working copy stores its state, or `None` if this
+    /// isn't a standard local-disk working copy.
+    pub fn working_copy_state_path(&self) -> Option<&Path> {
+        let local_wc: &LocalWorkingCopy = self.workspace.working_copy().downcast_ref()?;
+        Some(local_wc.state_path())
+    }
+
     pub fn workspace_name(&self) -> &WorkspaceName {
         self.workspace.workspace_name()
     }
@@ -1490,9 +1766,54 @@ to the current parents may contain changes from multiple commits.
         Ok(expression.to_matcher())
     }
 
+    /// Loads the filesets of paths to be ignored from the `.jjignore` file in
+    /// the workspace root and the `ui.excludes-file`.
+    ///
+    /// Each non-empty line of these files is a fileset expression relative to
+    /// the workspace root. Lines starting with `#` are comments.
+    pub fn ignore_matcher(&self, ui: &Ui) -> Result<Box<dyn Matcher>, CommandError> {
+        let excludes_file = self
+            .settings()
+            .get_string("ui.excludes-file")
+            .optional()?
+            .map(|path| {
+                let path = jj_lib::file_util::expand_home_path(&path);
+                self.workspace_root().join(path)
+            });
+        let jjignore_file = self.workspace_root().join(".jjignore");
+        let context = self.env.fileset_parse_context_for_config();
+        let mut expressions = vec![];
+        for path in excludes_file.iter().chain([&jjignore_file]) {
+            let text = match std::fs::read_to_string(path) {
+                Ok(text) => text,
+                Err(err) if err.kind() == io::ErrorKind::NotFound => continue,
+                Err(err) => {
+                    return Err(user_error_with_message(
+                        format!("Failed to read {}", path.display()),
+                        err,
+                    ));
+                }
+            };
+            let mut diagnostics = FilesetDiagnostics::new();
+            for line in text.lines().map(str::trim) {
+                if line.is_empty() || line.starts_with('#') {
+                    continue;
+                }
+                let expression =
+                    fileset::parse(&mut diagnostics, line, &context).map_err(|err| {
+                        user_error_with_message(format!("Failed to parse {}", path.display()), err)
+                    })?;
+                expressions.push(expression);
+            }
+            print_parse_diagnostics(ui, &format!("In {}", path.display()), &diagnostics)?;
+        }
+        Ok(FilesetExpression::union_all(expressions).to_matcher())
+    }
+
     pub fn snapshot_options_with_start_tracking_matcher<'a>(
         &self,
         start_tracking_matcher: &'a dyn Matcher,
+        ignore_matcher: &'a dyn Matcher,
     ) -> Result<SnapshotOptions<'a>, CommandError> {
         let base_ignores = self.base_ignores()?;
         let HumanByteSize(mut max_new_file_size) = self
@@ -1503,6 +1824,7 @@ to the current parents may contain changes from multiple commits.
         }
         Ok(SnapshotOptions {
             base_ignores,
+            ignore_matcher,
             progress: None,
             start_tracking_matcher,
             force_tracking_matcher: &NothingMatcher,
@@ -1694,7 +2016,12 @@ to the current parents may contain changes from multiple commits.
         let mut all_commits = IndexSet::new();
         for revision_arg in revision_args {
             let expression = self.parse_revset(ui, revision_arg)?;
-            for commit_id in expression.evaluate_to_commit_ids()? {
+            let commit_ids = expression.evaluate_to_commit_ids().map_err(|err| {
+                revset_util::format_evaluation_error(err, &expression, || {
+                    self.commit_summary_template()
+                })
+            })?;
+            for commit_id in commit_ids {
                 all_commits.insert(commit_id?);
             }
         }
@@ -1893,8 +2220,10 @@ to the current parents may contain changes from multiple commits.
         };
         let error = if &commit_id == repo.store().root_commit_id() {
             user_error(format!("The root commit {commit_id:.12} is immutable"))
+                .with_code(ErrorCode::ImmutableCommit)
         } else {
-            let mut error = user_error(format!("Commit {commit_id:.12} is immutable"));
+            let mut error = user_error(format!("Commit {commit_id:.12} is immutable"))
+                .with_code(ErrorCode::ImmutableCommit);
             let commit = repo.store().get_commit(&commit_id)?;
             error.add_formatted_hint_with(|formatter| {
                 write!(formatter, "Could not modify commit: ")?;
@@ -1942,9 +2271,12 @@ to the current parents may contain changes from multiple commits.
         let auto_tracking_matcher = self
             .auto_tracking_matcher(ui)
             .map_err(snapshot_command_error)?;
+        let ignore_matcher = self.ignore_matcher(ui).map_err(snapshot_command_error)?;
         let options = self
-            .snapshot_options_with_start_tracking_matcher(&auto_tracking_matcher)
+            .snapshot_options_with_start_tracking_matcher(&auto_tracking_matcher, &ignore_matcher)
             .map_err(snapshot_command_error)?;
+        let change_limits =
+            ChangeLimits::from_settings(self.settings()).map_err(snapshot_command_error)?;
 
         // Compare working-copy tree and operation with repo's, and reload as needed.
         let mut locked_ws = self
@@ -1971,6 +2303,7 @@ to the current parents may contain changes from multiple commits.
                 .await
                 .map_err(snapshot_command_error)?
         };
+        resource_limits::check_limits().map_err(snapshot_command_error)?;
         if new_tree.tree_ids_and_labels() != wc_commit.tree().tree_ids_and_labels() {
             let mut tx =
                 start_repo_transaction(&self.user_repo.repo, self.env.command.string_args());
@@ -1998,6 +2331,14 @@ to the current parents may contain changes from multiple commits.
                 )
                 .map_err(snapshot_command_error)?;
             }
+            change_limits
+                .check(
+                    ui,
+                    mut_repo,
+                    self.env.command.global_args().allow_large_change,
+                )
+                .await
+                .map_err(snapshot_command_error)?;
 
             #[cfg(feature = "git")]
             if self.working_copy_shared_with_git {
@@ -2046,6 +2387,9 @@ to the current parents may contain changes from multiple commits.
         locked_ws
             .finish(self.user_repo.repo.op_id().clone())
             .map_err(snapshot_command_error)?;
+        #[cfg(feature = "git")]
+        crate::git_util::record_file_write_stats(self.repo_path(), self.repo().store())
+            .map_err(snapshot_command_error)?;
         Ok(stats)
     }
 
@@ -2119,10 +2463,24 @@ to the current parents may contain changes from multiple commits.
         description: impl Into<String>,
         _git_import_export_lock: &GitImportExportLock,
     ) -> Result<(), CommandError> {
+        if self.env.command.data.at_operation_from_env {
+            return Err(
+                user_error("Cannot create a new operation while `JJ_AT_OPERATION` is set")
+                    .hinted("Unset `JJ_AT_OPERATION` to modify the repo at the latest operation."),
+            );
+        }
         let num_rebased = tx.repo_mut().rebase_descendants().block_on()?;
         if num_rebased > 0 {
             writeln!(ui.status(), "Rebased {num_rebased} descendant commits")?;
         }
+        ChangeLimits::from_settings(self.settings())?
+            .check(
+                ui,
+                tx.repo(),
+                self.env.command.global_args().allow_large_change,
+            )
+            .block_on()?;
+        resource_limits::check_limits()?;
 
         for (name, wc_commit_id) in &tx.repo().view().wc_commit_ids().clone() {
             // This can fail if trunk() bookmark gets deleted or conflicted. If
@@ -2203,7 +2561,7 @@ to the current parents may contain changes from multiple commits.
                     Err(err) => return Err(err.into()),
                 }
             }
-            let stats = jj_lib::git::export_refs(tx.repo_mut())?;
+            let stats = crate::git_util::export_git_refs(ui, tx.repo_mut())?;
             crate::git_util::print_git_export_stats(ui, &stats)?;
         }
 
@@ -2473,7 +2831,7 @@ pub fn export_working_copy_changes_to_git(
 ) -> Result<(), CommandError> {
     let repo = mut_repo.base_repo().as_ref();
     jj_lib::git::update_intent_to_add(repo, old_tree, new_tree)?;
-    let stats = jj_lib::git::export_refs(mut_repo)?;
+    let stats = crate::git_util::export_git_refs(ui, mut_repo)?;
     crate::git_util::print_git_export_stats(ui, &stats)?;
     Ok(())
 }
@@ -2581,6 +2939,9 @@ impl WorkspaceCommandTransaction<'_> {
     }
 
     pub fn finish(self, ui: &Ui, description: impl Into<String>) -> Result<(), CommandError> {
+        if self.helper.env.command.global_args().dry_run {
+            return self.preview(ui);
+        }
         if !self.tx.repo().has_changes() {
             writeln!(ui.status(), "Nothing changed.")?;
             return Ok(());
@@ -2592,6 +2953,109 @@ impl WorkspaceCommandTransaction<'_> {
             .finish_transaction(ui, self.tx, description, &git_import_export_lock)
     }
 
+    /// Prints the commits and bookmarks the transaction would change, then
+    /// discards it. Commits written by the transaction stay in the backend,
+    /// but aren't reachable from any operation.
+    fn preview(mut self, ui: &Ui) -> Result<(), CommandError> {
+        self.repo_mut().rebase_descendants().block_on()?;
+        let Some(mut formatter) = ui.dry_run_formatter() else {
+            return Ok(());
+        };
+        if !self.tx.repo().has_changes() {
+            writeln!(formatter, "Dry-run requested, nothing would change.")?;
+            return Ok(());
+        }
+        let repo = self.tx.repo();
+        let base_repo = self.tx.base_repo().as_ref();
+        let old_heads =
+            RevsetExpression::commits(base_repo.view().heads().iter().cloned().collect());
+        let new_heads = RevsetExpression::commits(repo.view().heads().iter().cloned().collect());
+        let predecessors = repo.commit_predecessors();
+
+        // Commits written by the transaction which are still visible, and
+        // whether they replace any commit of the base repo.
+        let written_expr = RevsetExpression::commits(predecessors.keys().cloned().collect())
+            .intersection(&new_heads.ancestors());
+        let mut created_commits = vec![];
+        let mut rewritten_commits = vec![];
+        let mut replaced_ids = HashSet::new();
+        for commit in written_expr.evaluate(repo)?.iter().commits(repo.store()) {
+            let commit = commit?;
+            let mut to_visit = predecessors[commit.id()].clone();
+            let mut is_rewrite = false;
+            while let Some(id) = to_visit.pop() {
+                if let Some(ids) = predecessors.get(&id) {
+                    to_visit.extend(ids.iter().cloned());
+                } else {
+                    is_rewrite = true;
+                    replaced_ids.insert(id);
+                }
+            }
+            if is_rewrite {
+                rewritten_commits.push(commit);
+            } else {
+                created_commits.push(commit);
+            }
+        }
+        let abandoned_commits: Vec<Commit> = new_heads
+            .range(&old_heads)
+            .evaluate(repo)?
+            .iter()
+            .filter_ok(|id| !replaced_ids.contains(id))
+            .commits(repo.store())
+            .try_collect()?;
+
+        // Abandoned commits are hidden in the transaction, so they are
+        // formatted as of the base repo.
+        let template = self.commit_summary_template();
+        let base_template = self.helper.commit_summary_template();
+        writeln!(
+            formatter,
+            "Dry-run requested, discarding the following changes:"
+        )?;
+        for (heading, commits, template) in [
+            ("Created commits:", &created_commits, &template),
+            ("Rewritten commits:", &rewritten_commits, &template),
+            ("Abandoned commits:", &abandoned_commits, &base_template),
+        ] {
+            if commits.is_empty() {
+                continue;
+            }
+            writeln!(formatter, "{heading}")?;
+            for commit in commits {
+                write!(formatter, "  ")?;
+                template.format(commit, formatter.as_mut())?;
+                writeln!(formatter)?;
+            }
+        }
+        let format_target = |target: &RefTarget| {
+            if target.is_absent() {
+                "(absent)".to_owned()
+            } else {
+                target.added_ids().map(short_commit_hash).join(", ")
+            }
+        };
+        let bookmark_diffs = diff_named_ref_targets(
+            base_repo.view().local_bookmarks(),
+            repo.view().local_bookmarks(),
+        )
+        .collect_vec();
+        if !bookmark_diffs.is_empty() {
+            writeln!(formatter, "Changed bookmarks:")?;
+            for (name, (old_target, new_target)) in bookmark_diffs {
+                write!(formatter, "  ")?;
+                write!(formatter.labeled("bookmark"), "{}", name.as_symbol())?;
+                writeln!(
+                    formatter,
+                    ": {} -> {}",
+                    format_target(old_target),
+                    format_target(new_target)
+                )?;
+            }
+        }
+        Ok(())
+    }
+
     /// Returns the wrapped [`Transaction`] for circumstances where
     /// finer-grained control is needed. The caller becomes responsible for
     /// finishing the `Transaction`, including rebasing descendants and updating
@@ -2665,6 +3129,26 @@ jj git init",
     }
 }
 
+/// Updates the recorded path of the `workspace` if it appears to have been
+/// moved.
+fn repair_workspace_path(ui: &Ui, workspace: &Workspace) -> Result<(), CommandError> {
+    let repaired = repair_moved_workspace(
+        workspace.repo_loader().workspace_store().as_ref(),
+        workspace.repo_path(),
+        workspace.workspace_name(),
+        workspace.workspace_root(),
+    )?;
+    if repaired.is_some() {
+        writeln!(
+            ui.status(),
+            "Updated the path of workspace {name} to {path}",
+            name = workspace.workspace_name().as_symbol(),
+            path = workspace.workspace_root().display(),
+        )?;
+    }
+    Ok(())
+}
+
 pub fn start_repo_transaction(repo: &Arc<ReadonlyRepo>, string_args: &[String]) -> Transaction {
     let mut tx = repo.start_transaction();
     // TODO: Either do better shell-escaping here or store the values in some list
@@ -2735,6 +3219,7 @@ fn handle_stale_working_copy(
                     "The working copy is stale (not updated since operation {}).",
                     short_operation_hash(&old_op_id)
                 ))
+                .with_code(ErrorCode::StaleWorkingCopy)
                 .hinted(
                     "Run `jj workspace update-stale` to update it.
 See https://docs.jj-vcs.dev/latest/working-copy/#stale-working-copy \
@@ -2759,11 +3244,13 @@ See https://docs.jj-vcs.dev/latest/working-copy/#stale-working-copy \
         }
         Err(OpStoreError::ObjectNotFound { .. }) => {
             Err(SnapshotWorkingCopyError::StaleWorkingCopy(
-                user_error("Could not read working copy's operation.").hinted(
-                    "Run `jj workspace update-stale` to recover.
+                user_error("Could not read working copy's operation.")
+                    .with_code(ErrorCode::StaleWorkingCopy)
+                    .hinted(
+                        "Run `jj workspace update-stale` to recover.
 See https://docs.jj-vcs.dev/latest/working-copy/#stale-working-copy \
-                     for more information.",
-                ),
+                         for more information.",
+                    ),
             ))
         }
         Err(e) => Err(snapshot_command_error(e)),
@@ -2970,6 +3457,17 @@ pub fn print_snapshot_stats(
     if let Some(size) = large_files_sizes.max() {
         print_large_file_hint(ui, *size, None)?;
     }
+
+    if !stats.mixed_eol_paths.is_empty() {
+        writeln!(
+            ui.warning_default(),
+            "Converted mixed line endings to LF in some files:"
+        )?;
+        let mut formatter = ui.stderr_formatter();
+        for path in &stats.mixed_eol_paths {
+            writeln!(formatter, "  {}", path_converter.format_file_path(path))?;
+        }
+    }
     Ok(())
 }
 
@@ -3126,8 +3624,7 @@ pub fn load_fileset_aliases(
     ui: &Ui,
     config: &StackedConfig,
 ) -> Result<FilesetAliasesMap, CommandError> {
-    let table_name = ConfigNamePathBuf::from_iter(["fileset-aliases"]);
-    load_aliases_map(ui, config, &table_name)
+    load_fileset_aliases_map(ui, config)
 }
 
 pub fn load_revset_aliases(
@@ -3148,6 +3645,14 @@ pub fn load_template_aliases(
     load_aliases_map(ui, config, &table_name)
 }
 
+pub fn load_bookmark_list_filter_aliases(
+    ui: &Ui,
+    config: &StackedConfig,
+) -> Result<RefFilterAliasesMap, CommandError> {
+    let table_name = ConfigNamePathBuf::from_iter(["bookmark-list-filter-aliases"]);
+    load_aliases_map(ui, config, &table_name)
+}
+
 /// Helper to reformat content of log-like commands.
 #[derive(Clone, Debug)]
 pub struct LogContentFormat {
@@ -3460,6 +3965,16 @@ pub struct GlobalArgs {
     #[arg(long, global = true)]
     pub ignore_working_copy: bool,
 
+    /// Skip the snapshot if another process is snapshotting
+    ///
+    /// Instead of waiting for the other process, the command behaves as if
+    /// `--ignore-working-copy` had been specified. The snapshot is also
+    /// skipped if the working copy was snapshotted less than
+    /// `snapshot.cooldown-seconds` ago. This is intended for shell prompts and
+    /// other background invocations that run alongside user commands.
+    #[arg(long, global = true)]
+    pub no_snapshot_if_busy: bool,
+
     /// Allow rewriting immutable commits
     ///
     /// By default, Jujutsu prevents rewriting commits in the configured set of
@@ -3471,6 +3986,37 @@ pub struct GlobalArgs {
     #[arg(long, global = true)]
     pub ignore_immutable: bool,
 
+    /// Allow operations exceeding the configured change limits
+    ///
+    /// With `limits.refuse-large-change = true`, Jujutsu refuses to snapshot
+    /// or rewrite commits if more files or bytes would be modified than
+    /// configured by `limits.max-changed-files` and `limits.max-changed-bytes`.
+    /// This option turns the refusal into a warning.
+    #[arg(long, global = true)]
+    pub allow_large_change: bool,
+
+    /// Show what the command would change, without changing the repo
+    ///
+    /// Instead of recording an operation, the command prints the commits it
+    /// would create, rewrite, or abandon, and the bookmarks it would move. Its
+    /// usual messages about the changes are omitted. `jj git push` and `jj
+    /// gerrit upload` don't push anything.
+    ///
+    /// Only `jj rebase`, `jj squash`, `jj abandon`, `jj absorb`, the `jj
+    /// bookmark` subcommands that move bookmarks, `jj git push`, and `jj gerrit
+    /// upload` support this option. Other commands fail with it.
+    #[arg(long, global = true)]
+    pub dry_run: bool,
+
+    /// Allow change IDs to refer to hidden commits
+    ///
+    /// By default, a change ID only resolves to visible commits. With this
+    /// option, a change ID which has no visible commits resolves to its hidden
+    /// commit if there's exactly one. Use `hidden()` to select all hidden
+    /// commits.
+    #[arg(long, global = true)]
+    pub include_hidden: bool,
+
     /// Operation to load the repo at
     ///
     /// Operation to load the repo at. By default, Jujutsu loads the repo at the
@@ -3493,6 +4039,9 @@ pub struct GlobalArgs {
     /// earlier operation. Doing that is equivalent to having run concurrent
     /// commands starting at the earlier operation. There's rarely a reason to
     /// do that, but it is possible.
+    ///
+    /// If this option isn't specified, the operation in the `JJ_AT_OPERATION`
+    /// environment variable is loaded. See `jj op pin` for details.
     #[arg(long, visible_alias = "at-op", global = true)]
     #[arg(add = ArgValueCandidates::new(complete::operations))]
     pub at_operation: Option<String>,
@@ -3511,6 +4060,14 @@ pub struct EarlyArgs {
     #[arg(long, value_name = "WHEN", global = true)]
     pub color: Option<ColorChoice>,
 
+    /// How to print errors
+    ///
+    /// With `json`, the error is printed to stderr as a JSON object with a
+    /// stable error `code`, the `message`, the `sources` the error was caused
+    /// by, the `hints`, and the `exit_code`.
+    #[arg(long, value_name = "FORMAT", global = true)]
+    pub error_format: Option<ErrorFormat>,
+
     /// Silence non-primary command output
     ///
     /// For example, `jj file list` will still list files, but it won't tell
@@ -3540,6 +4097,15 @@ pub struct EarlyArgs {
     /// Additional configuration files (can be repeated)
     #[arg(long, value_name = "PATH", global = true, value_hint = clap::ValueHint::FilePath)]
     pub config_file: Vec<String>,
+
+    /// Identity to use for authoring, committing, and signing
+    ///
+    /// The identity should be defined in the `[identities.<NAME>]` config
+    /// table. This overrides the `user.identity` setting. The author of
+    /// existing commits isn't changed.
+    #[arg(long, value_name = "NAME", global = true)]
+    #[arg(add = ArgValueCandidates::new(complete::identities))]
+    pub identity: Option<String>,
 }
 
 impl EarlyArgs {
@@ -3622,6 +4188,21 @@ pub fn merge_args_with<'k, 'v, T, U>(
     pos_values.into_iter().map(|(_, value)| value).collect()
 }
 
+/// Loads the message catalog selected by the `config`. A catalog which can't be
+/// loaded is reported as a warning, and English is used instead.
+fn load_message_catalog(ui: &Ui, config: &StackedConfig) -> Result<(), CommandError> {
+    match i18n::load_catalog(config) {
+        Ok(()) => Ok(()),
+        Err(CatalogLoadError::Config(err)) => Err(config_error(err)),
+        Err(err) => {
+            use std::error::Error as _;
+            writeln!(ui.warning_default(), "{err}")?;
+            print_error_sources(ui, err.source())?;
+            Ok(())
+        }
+    }
+}
+
 fn resolve_default_command(
     ui: &Ui,
     config: &StackedConfig,
@@ -3771,12 +4352,20 @@ fn parse_early_args(
     if let Some(choice) = args.color {
         layer.set_value("ui.color", choice.to_string()).unwrap();
     }
+    if let Some(format) = args.error_format {
+        layer
+            .set_value("ui.error-format", format.to_string())
+            .unwrap();
+    }
     if args.quiet.unwrap_or_default() {
         layer.set_value("ui.quiet", true).unwrap();
     }
     if args.no_pager.unwrap_or_default() {
         layer.set_value("ui.paginate", "never").unwrap();
     }
+    if let Some(identity) = &args.identity {
+        layer.set_value("user.identity", identity.as_str()).unwrap();
+    }
     if !layer.is_empty() {
         config_layers.push(layer);
     }
@@ -3936,7 +4525,7 @@ fn parse_args(app: &Command, string_args: &[String]) -> Result<(ArgMatches, Args
     Ok((matches, args))
 }
 
-fn command_name(mut matches: &ArgMatches) -> String {
+pub(crate) fn command_name(mut matches: &ArgMatches) -> String {
     let mut command = String::new();
     while let Some((subcommand, new_matches)) = matches.subcommand() {
         if !command.is_empty() {
@@ -4202,12 +4791,22 @@ impl<'a> CliRunner<'a> {
             warn_if_args_mismatch(ui, &self.app, &config, &string_args)?;
         }
 
-        let (matches, args) = parse_args(&self.app, &string_args)
+        let (matches, mut args) = parse_args(&self.app, &string_args)
             .map_err(|err| map_clap_cli_error(err, ui, &config))?;
+        // `--at-op` takes precedence over the operation pinned by `jj op pin`.
+        let mut at_operation_from_env = false;
+        if args.global_args.at_operation.is_none()
+            && let Ok(op_str) = env::var("JJ_AT_OPERATION")
+            && !op_str.is_empty()
+        {
+            args.global_args.at_operation = Some(op_str);
+            at_operation_from_env = true;
+        }
         if args.global_args.debug {
             // TODO: set up debug logging as early as possible
             self.tracing_subscription.enable_debug_logging()?;
         }
+        ui.set_dry_run(args.global_args.dry_run);
         for process_global_args_fn in self.process_global_args_fns {
             process_global_args_fn(ui, &matches)?;
         }
@@ -4235,6 +4834,7 @@ impl<'a> CliRunner<'a> {
         config = config_env.resolve_config(&raw_config)?;
         migrate_config(&mut config)?;
         ui.reset(&config)?;
+        load_message_catalog(ui, &config)?;
 
         // Print only the last migration messages to omit duplicates.
         for (source, desc) in &last_config_migration_descriptions {
@@ -4257,12 +4857,22 @@ impl<'a> CliRunner<'a> {
         }
 
         let settings = UserSettings::from_config(config)?;
+        let resource_limits = ResourceLimits::from_settings(&settings)?;
+        if resource_limits.max_memory.is_some() && !resource_limits::enable_memory_accounting() {
+            writeln!(
+                ui.warning_default(),
+                "`limits.max-memory` is ignored because this build of jj cannot measure its \
+                 memory usage."
+            )?;
+        }
+        let _watchdog = ResourceWatchdog::start(resource_limits, command_name(&matches));
         let command_helper_data = CommandHelperData {
             app: self.app,
             cwd,
             string_args,
             matches,
             global_args: args.global_args,
+            at_operation_from_env,
             config_env,
             config_migrations: self.config_migrations,
             raw_config,
@@ -4270,6 +4880,7 @@ impl<'a> CliRunner<'a> {
             revset_extensions: self.revset_extensions.into(),
             commit_template_extensions: self.commit_template_extensions,
             operation_template_extensions: self.operation_template_extensions,
+            decorations: Default::default(),
             maybe_workspace_loader,
             store_factories: self.store_factories,
             working_copy_factories: self.working_copy_factories,
@@ -4286,7 +4897,8 @@ impl<'a> CliRunner<'a> {
                 })
             },
         );
-        (dispatch_fn)(ui, &command_helper)
+        (dispatch_fn)(ui, &command_helper)?;
+        resource_limits::check_limits()
     }
 
     #[must_use]
@@ -14,9 +14,11 @@
 
 use std::error;
 use std::error::Error as _;
+use std::fmt;
 use std::io;
 use std::io::Write as _;
 use std::iter;
+use std::path::Path;
 use std::sync::Arc;
 
 use itertools::Itertools as _;
@@ -28,6 +30,7 @@ use jj_lib::config::ConfigFileSaveError;
 use jj_lib::config::ConfigGetError;
 use jj_lib::config::ConfigLoadError;
 use jj_lib::config::ConfigMigrateError;
+use jj_lib::draft_store::DraftStoreError;
 use jj_lib::dsl_util::Diagnostics;
 use jj_lib::evolution::WalkPredecessorsError;
 use jj_lib::fileset::FilePatternParseError;
@@ -41,6 +44,7 @@ use jj_lib::op_heads_store::OpHeadsStoreError;
 use jj_lib::op_store::OpStoreError;
 use jj_lib::op_walk::OpsetEvaluationError;
 use jj_lib::op_walk::OpsetResolutionError;
+use jj_lib::remote_sync_store::RemoteSyncStoreError;
 use jj_lib::repo::CheckOutCommitError;
 use jj_lib::repo::EditCommitError;
 use jj_lib::repo::RepoLoaderError;
@@ -61,6 +65,7 @@ use jj_lib::working_copy::RecoverWorkspaceError;
 use jj_lib::working_copy::ResetError;
 use jj_lib::working_copy::SnapshotError;
 use jj_lib::working_copy::WorkingCopyStateError;
+use jj_lib::working_copy_store::WorkingCopyStoreError;
 use jj_lib::workspace::WorkspaceInitError;
 use jj_lib::workspace_store::WorkspaceStoreError;
 use thiserror::Error;
@@ -73,6 +78,7 @@ use crate::diff_util::DiffRenderError;
 use crate::formatter::FormatRecorder;
 use crate::formatter::Formatter;
 use crate::formatter::FormatterExt as _;
+use crate::i18n::tr;
 use crate::merge_tools::ConflictResolveError;
 use crate::merge_tools::DiffEditError;
 use crate::merge_tools::MergeToolConfigError;
@@ -82,6 +88,7 @@ use crate::revset_util::TagNameParseError;
 use crate::revset_util::UserRevsetEvaluationError;
 use crate::template_parser::TemplateParseError;
 use crate::template_parser::TemplateParseErrorKind;
+use crate::ui::ErrorFormat;
 use crate::ui::Ui;
 
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
@@ -94,9 +101,80 @@ pub enum CommandErrorKind {
     Internal,
 }
 
+/// Stable identifier of an error, printed with `--error-format=json`.
+///
+/// Tools wrapping `jj` can match on these codes instead of the error message.
+/// Codes must not be renamed or reused once added.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum ErrorCode {
+    /// User error without a more specific code.
+    User,
+    /// Config error without a more specific code.
+    Config,
+    /// Invalid command line.
+    Cli,
+    /// Internal error without a more specific code.
+    Internal,
+    ConfigInvalid,
+    BackendFailure,
+    RevsetParse,
+    RevsetEvaluation,
+    RevsetNoSuchRevision,
+    RevsetAmbiguousId,
+    RevsetDivergentChange,
+    RevsetConflictedRef,
+    FilesetParse,
+    TemplateParse,
+    ImmutableCommit,
+    StaleWorkingCopy,
+    SnapshotFailure,
+    GitImport,
+    GitExport,
+    GitFetch,
+    GitPush,
+    GitRemote,
+}
+
+impl ErrorCode {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::User => "user",
+            Self::Config => "config",
+            Self::Cli => "cli",
+            Self::Internal => "internal",
+            Self::ConfigInvalid => "config.invalid",
+            Self::BackendFailure => "backend.failure",
+            Self::RevsetParse => "revset.parse",
+            Self::RevsetEvaluation => "revset.evaluation",
+            Self::RevsetNoSuchRevision => "revset.no-such-revision",
+            Self::RevsetAmbiguousId => "revset.ambiguous-id",
+            Self::RevsetDivergentChange => "revset.divergent-change",
+            Self::RevsetConflictedRef => "revset.conflicted-ref",
+            Self::FilesetParse => "fileset.parse",
+            Self::TemplateParse => "template.parse",
+            Self::ImmutableCommit => "commit.immutable",
+            Self::StaleWorkingCopy => "working-copy.stale",
+            Self::SnapshotFailure => "working-copy.snapshot",
+            Self::GitImport => "git.import",
+            Self::GitExport => "git.export",
+            Self::GitFetch => "git.fetch",
+            Self::GitPush => "git.push",
+            Self::GitRemote => "git.remote",
+        }
+    }
+}
+
+impl fmt::Display for ErrorCode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct CommandError {
     pub kind: CommandErrorKind,
+    /// Specific error code. If unset, a generic code for the `kind` is used.
+    pub code: Option<ErrorCode>,
     pub error: Arc<dyn error::Error + Send + Sync>,
     pub hints: Vec<ErrorHint>,
 }
@@ -108,6 +186,7 @@ impl CommandError {
     ) -> Self {
         Self {
             kind,
+            code: None,
             error: Arc::from(err.into()),
             hints: vec![],
         }
@@ -121,6 +200,22 @@ impl CommandError {
         Self::new(kind, ErrorWithMessage::new(message, source))
     }
 
+    /// Returns error with the given error `code` set.
+    pub fn with_code(mut self, code: ErrorCode) -> Self {
+        self.code = Some(code);
+        self
+    }
+
+    /// Returns the error code, falling back to a generic code for the kind.
+    pub fn code(&self) -> ErrorCode {
+        self.code.unwrap_or(match self.kind {
+            CommandErrorKind::User => ErrorCode::User,
+            CommandErrorKind::Config => ErrorCode::Config,
+            CommandErrorKind::Cli | CommandErrorKind::BrokenPipe => ErrorCode::Cli,
+            CommandErrorKind::Internal => ErrorCode::Internal,
+        })
+    }
+
     /// Returns error with the given plain-text `hint` attached.
     pub fn hinted(mut self, hint: impl Into<String>) -> Self {
         self.add_hint(hint);
@@ -229,7 +324,11 @@ fn format_similarity_hint<S: AsRef<str>>(candidates: &[S]) -> Option<String> {
         [] => None,
         names => {
             let quoted_names = names.iter().map(|s| format!("`{}`", s.as_ref())).join(", ");
-            Some(format!("Did you mean {quoted_names}?"))
+            Some(tr!(
+                "hint.did-you-mean",
+                "Did you mean {names}?",
+                names = quoted_names
+            ))
         }
     }
 }
@@ -259,7 +358,7 @@ impl From<ConfigFileSaveError> for CommandError {
 impl From<ConfigGetError> for CommandError {
     fn from(err: ConfigGetError) -> Self {
         let hint = config_get_error_hint(&err);
-        let mut cmd_err = config_error(err);
+        let mut cmd_err = config_error(err).with_code(ErrorCode::ConfigInvalid);
         cmd_err.extend_hints(hint);
         cmd_err
     }
@@ -271,9 +370,9 @@ impl From<ConfigLoadError> for CommandError {
             ConfigLoadError::Read(_) => None,
             ConfigLoadError::Parse { source_path, .. } => source_path
                 .as_ref()
-                .map(|path| format!("Check the config file: {}", path.display())),
+                .map(|path| check_config_file_hint(path)),
         };
-        let mut cmd_err = config_error(err);
+        let mut cmd_err = config_error(err).with_code(ErrorCode::ConfigInvalid);
         cmd_err.extend_hints(hint);
         cmd_err
     }
@@ -284,8 +383,8 @@ impl From<ConfigMigrateError> for CommandError {
         let hint = err
             .source_path
             .as_ref()
-            .map(|path| format!("Check the config file: {}", path.display()));
-        let mut cmd_err = config_error(err);
+            .map(|path| check_config_file_hint(path));
+        let mut cmd_err = config_error(err).with_code(ErrorCode::ConfigInvalid);
         cmd_err.extend_hints(hint);
         cmd_err
     }
@@ -293,25 +392,37 @@ impl From<ConfigMigrateError> for CommandError {
 
 impl From<RewriteRootCommit> for CommandError {
     fn from(err: RewriteRootCommit) -> Self {
-        internal_error_with_message("Attempted to rewrite the root commit", err)
+        internal_error_with_message(
+            tr!(
+                "error.rewrite-root-commit",
+                "Attempted to rewrite the root commit"
+            ),
+            err,
+        )
     }
 }
 
 impl From<EditCommitError> for CommandError {
     fn from(err: EditCommitError) -> Self {
-        internal_error_with_message("Failed to edit a commit", err)
+        internal_error_with_message(tr!("error.edit-commit", "Failed to edit a commit"), err)
     }
 }
 
 impl From<CheckOutCommitError> for CommandError {
     fn from(err: CheckOutCommitError) -> Self {
-        internal_error_with_message("Failed to check out a commit", err)
+        internal_error_with_message(
+            tr!("error.check-out-commit", "Failed to check out a commit"),
+            err,
+        )
     }
 }
 
 impl From<RenameWorkspaceError> for CommandError {
     fn from(err: RenameWorkspaceError) -> Self {
-        user_error_with_message("Failed to rename a workspace", err)
+        user_error_with_message(
+            tr!("error.rename-workspace", "Failed to rename a workspace"),
+            err,
+        )
     }
 }
 
@@ -319,53 +430,115 @@ impl From<BackendError> for CommandError {
     fn from(err: BackendError) -> Self {
         match &err {
             BackendError::Unsupported(_) => user_error(err),
-            _ => internal_error_with_message("Unexpected error from backend", err),
+            _ => internal_error_with_message(
+                tr!("error.backend", "Unexpected error from backend"),
+                err,
+            )
+            .with_code(ErrorCode::BackendFailure),
         }
     }
 }
 
 impl From<IndexError> for CommandError {
     fn from(err: IndexError) -> Self {
-        internal_error_with_message("Unexpected error from index", err)
+        internal_error_with_message(tr!("error.index", "Unexpected error from index"), err)
     }
 }
 
 impl From<OpHeadsStoreError> for CommandError {
     fn from(err: OpHeadsStoreError) -> Self {
-        internal_error_with_message("Unexpected error from operation heads store", err)
+        internal_error_with_message(
+            tr!(
+                "error.op-heads-store",
+                "Unexpected error from operation heads store"
+            ),
+            err,
+        )
+    }
+}
+
+impl From<DraftStoreError> for CommandError {
+    fn from(err: DraftStoreError) -> Self {
+        internal_error_with_message(
+            tr!("error.draft-store", "Unexpected error from draft store"),
+            err,
+        )
+    }
+}
+
+impl From<RemoteSyncStoreError> for CommandError {
+    fn from(err: RemoteSyncStoreError) -> Self {
+        internal_error_with_message(
+            tr!(
+                "error.remote-sync-store",
+                "Unexpected error from remote sync store"
+            ),
+            err,
+        )
     }
 }
 
 impl From<WorkspaceStoreError> for CommandError {
     fn from(err: WorkspaceStoreError) -> Self {
-        internal_error_with_message("Unexpected error from workspace store", err)
+        internal_error_with_message(
+            tr!(
+                "error.workspace-store",
+                "Unexpected error from workspace store"
+            ),
+            err,
+        )
+    }
+}
+
+impl From<WorkingCopyStoreError> for CommandError {
+    fn from(err: WorkingCopyStoreError) -> Self {
+        internal_error_with_message(
+            tr!(
+                "error.working-copy-store",
+                "Unexpected error from working copy store"
+            ),
+            err,
+        )
     }
 }
 
 impl From<WorkspaceInitError> for CommandError {
     fn from(err: WorkspaceInitError) -> Self {
         match err {
-            WorkspaceInitError::DestinationExists(_) => {
-                user_error("The target repo already exists")
-            }
+            WorkspaceInitError::DestinationExists(_) => user_error(tr!(
+                "error.init.destination-exists",
+                "The target repo already exists"
+            )),
             WorkspaceInitError::EncodeRepoPath(_) => user_error(err),
-            WorkspaceInitError::CheckOutCommit(err) => {
-                internal_error_with_message("Failed to check out the initial commit", err)
-            }
+            WorkspaceInitError::CheckOutCommit(err) => internal_error_with_message(
+                tr!(
+                    "error.init.check-out-commit",
+                    "Failed to check out the initial commit"
+                ),
+                err,
+            ),
             WorkspaceInitError::Path(err) => {
-                internal_error_with_message("Failed to access the repository", err)
-            }
-            WorkspaceInitError::OpHeadsStore(err) => {
-                user_error_with_message("Failed to record initial operation", err)
-            }
-            WorkspaceInitError::WorkspaceStore(err) => {
-                internal_error_with_message("Failed to record workspace path", err)
+                internal_error_with_message(access_repository_message(), err)
             }
+            WorkspaceInitError::OpHeadsStore(err) => user_error_with_message(
+                tr!(
+                    "error.init.record-operation",
+                    "Failed to record initial operation"
+                ),
+                err,
+            ),
+            WorkspaceInitError::WorkspaceStore(err) => internal_error_with_message(
+                tr!(
+                    "error.init.record-workspace-path",
+                    "Failed to record workspace path"
+                ),
+                err,
+            ),
             WorkspaceInitError::Backend(err) => {
-                user_error_with_message("Failed to access the repository", err)
+                user_error_with_message(access_repository_message(), err)
             }
             WorkspaceInitError::WorkingCopyState(err) => {
-                internal_error_with_message("Failed to access the repository", err)
+                internal_error_with_message(access_repository_message(), err)
             }
             WorkspaceInitError::SignInit(err) => user_error(err),
             WorkspaceInitError::TransactionCommit(err) => err.into(),
@@ -377,7 +550,7 @@ impl From<OpHeadResolutionError> for CommandError {
     fn from(err: OpHeadResolutionError) -> Self {
         match err {
             OpHeadResolutionError::NoHeads => {
-                internal_error_with_message("Corrupt repository", err)
+                internal_error_with_message(tr!("error.no-op-heads", "Corrupt repository"), err)
             }
         }
     }
@@ -401,25 +574,35 @@ impl From<OpsetEvaluationError> for CommandError {
 
 impl From<SnapshotError> for CommandError {
     fn from(err: SnapshotError) -> Self {
-        internal_error_with_message("Failed to snapshot the working copy", err)
+        internal_error_with_message(
+            tr!("error.snapshot", "Failed to snapshot the working copy"),
+            err,
+        )
+        .with_code(ErrorCode::SnapshotFailure)
     }
 }
 
 impl From<OpStoreError> for CommandError {
     fn from(err: OpStoreError) -> Self {
-        internal_error_with_message("Failed to load an operation", err)
+        internal_error_with_message(tr!("error.op-store", "Failed to load an operation"), err)
     }
 }
 
 impl From<RepoLoaderError> for CommandError {
     fn from(err: RepoLoaderError) -> Self {
-        internal_error_with_message("Failed to load the repo", err)
+        internal_error_with_message(tr!("error.repo-loader", "Failed to load the repo"), err)
     }
 }
 
 impl From<ResetError> for CommandError {
     fn from(err: ResetError) -> Self {
-        internal_error_with_message("Failed to reset the working copy", err)
+        internal_error_with_message(
+            tr!(
+                "error.reset-working-copy",
+                "Failed to reset the working copy"
+            ),
+            err,
+        )
     }
 }
 
@@ -442,7 +625,7 @@ impl From<WalkPredecessorsError> for CommandError {
 
 impl From<DiffEditError> for CommandError {
     fn from(err: DiffEditError) -> Self {
-        user_error_with_message("Failed to edit diff", err)
+        user_error_with_message(tr!("error.diff-edit", "Failed to edit diff"), err)
     }
 }
 
@@ -465,15 +648,20 @@ impl From<ConflictResolveError> for CommandError {
             ConflictResolveError::Io(err) => err.into(),
             _ => {
                 let hint = match &err {
-                    ConflictResolveError::ConflictTooComplicated { .. } => {
-                        Some("Edit the conflict markers manually to resolve this.".to_owned())
-                    }
-                    ConflictResolveError::ExecutableConflict { .. } => {
-                        Some("Use `jj file chmod` to update the executable bit.".to_owned())
-                    }
+                    ConflictResolveError::ConflictTooComplicated { .. } => Some(tr!(
+                        "hint.conflict-too-complicated",
+                        "Edit the conflict markers manually to resolve this."
+                    )),
+                    ConflictResolveError::ExecutableConflict { .. } => Some(tr!(
+                        "hint.executable-conflict",
+                        "Use `jj file chmod` to update the executable bit."
+                    )),
                     _ => None,
                 };
-                let mut cmd_err = user_error_with_message("Failed to resolve conflicts", err);
+                let mut cmd_err = user_error_with_message(
+                    tr!("error.conflict-resolve", "Failed to resolve conflicts"),
+                    err,
+                );
                 cmd_err.extend_hints(hint);
                 cmd_err
             }
@@ -491,13 +679,21 @@ impl From<MergeToolConfigError> for CommandError {
     fn from(err: MergeToolConfigError) -> Self {
         match &err {
             MergeToolConfigError::MergeArgsNotConfigured { tool_name } => {
-                let tool_name = tool_name.clone();
-                user_error(err).hinted(format!(
+                let hint = tr!(
+                    "hint.merge-args-not-configured",
                     "To use `{tool_name}` as a merge tool, the config \
-                     `merge-tools.{tool_name}.merge-args` must be defined (see docs for details)"
-                ))
+                     `merge-tools.{tool_name}.merge-args` must be defined (see docs for details)",
+                    tool_name = tool_name,
+                );
+                user_error(err).hinted(hint)
             }
-            _ => user_error_with_message("Failed to load tool configuration", err),
+            _ => user_error_with_message(
+                tr!(
+                    "error.merge-tool-config",
+                    "Failed to load tool configuration"
+                ),
+                err,
+            ),
         }
     }
 }
@@ -512,7 +708,12 @@ impl From<TempTextEditError> for CommandError {
     fn from(err: TempTextEditError) -> Self {
         let hint = err.path.as_ref().map(|path| {
             let name = err.name.as_deref().unwrap_or("file");
-            format!("Edited {name} is left in {path}", path = path.display())
+            tr!(
+                "hint.temp-text-edit",
+                "Edited {name} is left in {path}",
+                name = name,
+                path = path.display(),
+            )
         });
         let mut cmd_err = user_error(err);
         cmd_err.extend_hints(hint);
@@ -536,6 +737,7 @@ mod git {
     use jj_lib::git::GitRefExpansionError;
     use jj_lib::git::GitRemoteManagementError;
     use jj_lib::git::GitResetHeadError;
+    use jj_lib::git::InvalidPushRefSpecError;
     use jj_lib::git::UnexpectedGitBackendError;
 
     use super::*;
@@ -544,20 +746,26 @@ mod git {
         fn from(err: GitImportError) -> Self {
             let hint = match &err {
                 GitImportError::MissingHeadTarget { .. }
-                | GitImportError::MissingRefAncestor { .. } => Some(
+                | GitImportError::MissingRefAncestor { .. } => Some(tr!(
+                    "hint.git.partial-clone",
                     "\
 Is this Git repository a partial clone (cloned with the --filter argument)?
 jj currently does not support partial clones. To use jj with this repository, try re-cloning with \
                      the full repository contents."
-                        .to_string(),
-                ),
+                )),
                 GitImportError::Backend(_) => None,
                 GitImportError::Index(_) => None,
                 GitImportError::Git(_) => None,
                 GitImportError::UnexpectedBackend(_) => None,
             };
-            let mut cmd_err =
-                user_error_with_message("Failed to import refs from underlying Git repo", err);
+            let mut cmd_err = user_error_with_message(
+                tr!(
+                    "error.git.import",
+                    "Failed to import refs from underlying Git repo"
+                ),
+                err,
+            )
+            .with_code(ErrorCode::GitImport);
             cmd_err.extend_hints(hint);
             cmd_err
         }
@@ -565,19 +773,26 @@ jj currently does not support partial clones. To use jj with this repository, tr
 
     impl From<GitExportError> for CommandError {
         fn from(err: GitExportError) -> Self {
-            user_error_with_message("Failed to export refs to underlying Git repo", err)
+            user_error_with_message(
+                tr!(
+                    "error.git.export",
+                    "Failed to export refs to underlying Git repo"
+                ),
+                err,
+            )
+            .with_code(ErrorCode::GitExport)
         }
     }
 
     impl From<GitFetchError> for CommandError {
         fn from(err: GitFetchError) -> Self {
-            match err {
+            let cmd_err = match err {
                 GitFetchError::NoSuchRemote(_) => user_error(err),
-                GitFetchError::RemoteName(_) => {
-                    user_error(err).hinted("Run `jj git remote rename` to give a different name.")
-                }
+                GitFetchError::RemoteName(_) => user_error(err).hinted(remote_rename_hint()),
                 GitFetchError::RejectedUpdates(_) | GitFetchError::Subprocess(_) => user_error(err),
-            }
+                GitFetchError::FetchedRef(_) => internal_error(err),
+            };
+            cmd_err.with_code(ErrorCode::GitFetch)
         }
     }
 
@@ -593,35 +808,46 @@ jj currently does not support partial clones. To use jj with this repository, tr
     impl From<GitRefExpansionError> for CommandError {
         fn from(err: GitRefExpansionError) -> Self {
             match &err {
-                GitRefExpansionError::Expression(_) => user_error(err)
-                    .hinted("Specify patterns in `(positive | ...) & ~(negative | ...)` form."),
+                GitRefExpansionError::Expression(_) => user_error(err).hinted(tr!(
+                    "hint.git.ref-expression",
+                    "Specify patterns in `(positive | ...) & ~(negative | ...)` form."
+                )),
                 GitRefExpansionError::InvalidBranchPattern(_) => user_error(err),
+                GitRefExpansionError::InvalidFetchRefspec { .. } => config_error(err),
             }
         }
     }
 
     impl From<GitPushError> for CommandError {
         fn from(err: GitPushError) -> Self {
-            match err {
+            let cmd_err = match err {
                 GitPushError::NoSuchRemote(_) => user_error(err),
-                GitPushError::RemoteName(_) => {
-                    user_error(err).hinted("Run `jj git remote rename` to give a different name.")
-                }
+                GitPushError::RemoteName(_) => user_error(err).hinted(remote_rename_hint()),
                 GitPushError::Subprocess(_) => user_error(err),
                 GitPushError::UnexpectedBackend(_) => user_error(err),
-            }
+            };
+            cmd_err.with_code(ErrorCode::GitPush)
+        }
+    }
+
+    impl From<InvalidPushRefSpecError> for CommandError {
+        fn from(err: InvalidPushRefSpecError) -> Self {
+            config_error(err)
         }
     }
 
     impl From<GitRemoteManagementError> for CommandError {
         fn from(err: GitRemoteManagementError) -> Self {
-            user_error(err)
+            user_error(err).with_code(ErrorCode::GitRemote)
         }
     }
 
     impl From<GitResetHeadError> for CommandError {
         fn from(err: GitResetHeadError) -> Self {
-            user_error_with_message("Failed to reset Git HEAD state", err)
+            user_error_with_message(
+                tr!("error.git.reset-head", "Failed to reset Git HEAD state"),
+                err,
+            )
         }
     }
 
@@ -630,19 +856,33 @@ jj currently does not support partial clones. To use jj with this repository, tr
             user_error(err)
         }
     }
+
+    fn remote_rename_hint() -> String {
+        tr!(
+            "hint.git.remote-rename",
+            "Run `jj git remote rename` to give a different name."
+        )
+    }
 }
 
 impl From<RevsetEvaluationError> for CommandError {
     fn from(err: RevsetEvaluationError) -> Self {
-        user_error(err)
+        user_error(err).with_code(ErrorCode::RevsetEvaluation)
     }
 }
 
 impl From<FilesetParseError> for CommandError {
     fn from(err: FilesetParseError) -> Self {
         let hint = fileset_parse_error_hint(&err);
-        let mut cmd_err =
-            user_error_with_message(format!("Failed to parse fileset: {}", err.kind()), err);
+        let mut cmd_err = user_error_with_message(
+            tr!(
+                "error.parse-fileset",
+                "Failed to parse fileset: {kind}",
+                kind = err.kind(),
+            ),
+            err,
+        )
+        .with_code(ErrorCode::FilesetParse);
         cmd_err.extend_hints(hint);
         cmd_err
     }
@@ -663,8 +903,15 @@ impl From<RecoverWorkspaceError> for CommandError {
 impl From<RevsetParseError> for CommandError {
     fn from(err: RevsetParseError) -> Self {
         let hint = revset_parse_error_hint(&err);
-        let mut cmd_err =
-            user_error_with_message(format!("Failed to parse revset: {}", err.kind()), err);
+        let mut cmd_err = user_error_with_message(
+            tr!(
+                "error.parse-revset",
+                "Failed to parse revset: {kind}",
+                kind = err.kind(),
+            ),
+            err,
+        )
+        .with_code(ErrorCode::RevsetParse);
         cmd_err.extend_hints(hint);
         cmd_err
     }
@@ -673,7 +920,18 @@ impl From<RevsetParseError> for CommandError {
 impl From<RevsetResolutionError> for CommandError {
     fn from(err: RevsetResolutionError) -> Self {
         let hints = revset_resolution_error_hints(&err);
-        let mut cmd_err = user_error(err);
+        let code = match &err {
+            RevsetResolutionError::NoSuchRevision { .. }
+            | RevsetResolutionError::WorkspaceMissingWorkingCopy { .. }
+            | RevsetResolutionError::EmptyString => ErrorCode::RevsetNoSuchRevision,
+            RevsetResolutionError::AmbiguousCommitIdPrefix(_)
+            | RevsetResolutionError::AmbiguousChangeIdPrefix(_) => ErrorCode::RevsetAmbiguousId,
+            RevsetResolutionError::DivergentChangeId { .. } => ErrorCode::RevsetDivergentChange,
+            RevsetResolutionError::ConflictedRef { .. } => ErrorCode::RevsetConflictedRef,
+            RevsetResolutionError::Backend(_) => ErrorCode::BackendFailure,
+            RevsetResolutionError::Other(_) => ErrorCode::User,
+        };
+        let mut cmd_err = user_error(err).with_code(code);
         cmd_err.extend_hints(hints);
         cmd_err
     }
@@ -691,8 +949,15 @@ impl From<UserRevsetEvaluationError> for CommandError {
 impl From<TemplateParseError> for CommandError {
     fn from(err: TemplateParseError) -> Self {
         let hint = template_parse_error_hint(&err);
-        let mut cmd_err =
-            user_error_with_message(format!("Failed to parse template: {}", err.kind()), err);
+        let mut cmd_err = user_error_with_message(
+            tr!(
+                "error.parse-template",
+                "Failed to parse template: {kind}",
+                kind = err.kind(),
+            ),
+            err,
+        )
+        .with_code(ErrorCode::TemplateParse);
         cmd_err.extend_hints(hint);
         cmd_err
     }
@@ -715,13 +980,19 @@ impl From<clap::Error> for CommandError {
 
 impl From<WorkingCopyStateError> for CommandError {
     fn from(err: WorkingCopyStateError) -> Self {
-        internal_error_with_message("Failed to access working copy state", err)
+        internal_error_with_message(
+            tr!(
+                "error.working-copy-state",
+                "Failed to access working copy state"
+            ),
+            err,
+        )
     }
 }
 
 impl From<GitIgnoreError> for CommandError {
     fn from(err: GitIgnoreError) -> Self {
-        user_error_with_message("Failed to process .gitignore.", err)
+        user_error_with_message(tr!("error.gitignore", "Failed to process .gitignore."), err)
     }
 }
 
@@ -747,7 +1018,10 @@ impl From<FixError> for CommandError {
             FixError::RevsetEvaluation(err) => err.into(),
             FixError::Io(err) => err.into(),
             FixError::FixContent(err) => internal_error_with_message(
-                "An error occurred while attempting to fix file content",
+                tr!(
+                    "error.fix-content",
+                    "An error occurred while attempting to fix file content"
+                ),
                 err,
             ),
         }
@@ -764,7 +1038,22 @@ impl From<BisectionError> for CommandError {
 
 impl From<SecureConfigError> for CommandError {
     fn from(err: SecureConfigError) -> Self {
-        internal_error_with_message("Failed to determine the secure config for a repo", err)
+        if let SecureConfigError::AgeError(err) = err {
+            return user_error_with_message(
+                tr!(
+                    "error.secure-config-encryption",
+                    "Failed to encrypt or decrypt the config file"
+                ),
+                err,
+            );
+        }
+        internal_error_with_message(
+            tr!(
+                "error.secure-config",
+                "Failed to determine the secure config for a repo"
+            ),
+            err,
+        )
     }
 }
 
@@ -797,19 +1086,37 @@ fn find_source_parse_error_hint(err: &dyn error::Error) -> Option<String> {
     }
 }
 
-const REVSET_SYMBOL_HINT: &str = "See https://docs.jj-vcs.dev/latest/revsets/ or use `jj help -k \
-                                  revsets` for how to quote symbols.";
+fn access_repository_message() -> String {
+    tr!("error.access-repository", "Failed to access the repository")
+}
+
+fn check_config_file_hint(path: &Path) -> String {
+    tr!(
+        "hint.check-config-file",
+        "Check the config file: {path}",
+        path = path.display(),
+    )
+}
+
+fn revset_symbol_hint() -> String {
+    tr!(
+        "hint.revset-symbol",
+        "See https://docs.jj-vcs.dev/latest/revsets/ or use `jj help -k revsets` for how to quote \
+         symbols."
+    )
+}
 
 fn bookmark_name_parse_error_hint(err: &BookmarkNameParseError) -> Option<String> {
     use revset::ExpressionKind;
     match revset::parse_program(&err.input).map(|node| node.kind) {
-        Ok(ExpressionKind::RemoteSymbol(symbol)) => Some(format!(
+        Ok(ExpressionKind::RemoteSymbol(symbol)) => Some(tr!(
+            "hint.track-remote-bookmark",
             "Looks like remote bookmark. Run `jj bookmark track {name} --remote={remote}` to \
              track it.",
             name = symbol.name.as_symbol(),
-            remote = symbol.remote.as_symbol()
+            remote = symbol.remote.as_symbol(),
         )),
-        _ => Some(REVSET_SYMBOL_HINT.to_owned()),
+        _ => Some(revset_symbol_hint()),
     }
 }
 
@@ -818,20 +1125,25 @@ fn config_get_error_hint(err: &ConfigGetError) -> Option<String> {
         ConfigGetError::NotFound { .. } => None,
         ConfigGetError::Type { source_path, .. } => source_path
             .as_ref()
-            .map(|path| format!("Check the config file: {}", path.display())),
+            .map(|path| check_config_file_hint(path)),
     }
 }
 
 fn file_pattern_parse_error_hint(err: &FilePatternParseError) -> Option<String> {
     match err {
-        FilePatternParseError::InvalidKind(_) => Some(String::from(
+        FilePatternParseError::InvalidKind(_) => Some(tr!(
+            "hint.file-pattern-kind",
             "See https://docs.jj-vcs.dev/latest/filesets/#file-patterns or `jj help -k filesets` \
-             for valid prefixes.",
+             for valid prefixes."
         )),
         // Suggest root:"<path>" if input can be parsed as repo-relative path
         FilePatternParseError::UiPath(UiPathParseError::Fs(e)) => {
             RepoPathBuf::from_relative_path(&e.input).ok().map(|path| {
-                format!(r#"Consider using root:{path:?} to specify repo-relative path"#)
+                tr!(
+                    "hint.root-file-pattern",
+                    "Consider using root:{path} to specify repo-relative path",
+                    path = format!("{path:?}"),
+                )
             })
         }
         FilePatternParseError::RelativePath(_) => None,
@@ -841,9 +1153,10 @@ fn file_pattern_parse_error_hint(err: &FilePatternParseError) -> Option<String>
 
 fn fileset_parse_error_hint(err: &FilesetParseError) -> Option<String> {
     match err.kind() {
-        FilesetParseErrorKind::SyntaxError => Some(String::from(
+        FilesetParseErrorKind::SyntaxError => Some(tr!(
+            "hint.fileset-syntax",
             "See https://docs.jj-vcs.dev/latest/filesets/ or use `jj help -k filesets` for \
-             filesets syntax and how to match file paths.",
+             filesets syntax and how to match file paths."
         )),
         FilesetParseErrorKind::NoSuchFunction {
             name: _,
@@ -863,9 +1176,10 @@ fn opset_resolution_error_hint(err: &OpsetResolutionError) -> Option<String> {
         OpsetResolutionError::MultipleOperations {
             expr: _,
             candidates,
-        } => Some(format!(
-            "Try specifying one of the operations by ID: {}",
-            candidates.iter().map(short_operation_hash).join(", ")
+        } => Some(tr!(
+            "hint.multiple-operations",
+            "Try specifying one of the operations by ID: {candidates}",
+            candidates = candidates.iter().map(short_operation_hash).join(", "),
         )),
         OpsetResolutionError::EmptyOperations(_)
         | OpsetResolutionError::InvalidIdPrefix(_)
@@ -878,11 +1192,11 @@ pub(crate) fn revset_parse_error_hint(err: &RevsetParseError) -> Option<String>
     // Only for the bottom error, which is usually the root cause
     let bottom_err = iter::successors(Some(err), |e| e.origin()).last().unwrap();
     match bottom_err.kind() {
-        RevsetParseErrorKind::SyntaxError => Some(
+        RevsetParseErrorKind::SyntaxError => Some(tr!(
+            "hint.revset-syntax",
             "See https://docs.jj-vcs.dev/latest/revsets/ or use `jj help -k revsets` for revsets \
              syntax and how to quote symbols."
-                .into(),
-        ),
+        )),
         RevsetParseErrorKind::NotPrefixOperator {
             op: _,
             similar_op,
@@ -897,7 +1211,12 @@ pub(crate) fn revset_parse_error_hint(err: &RevsetParseError) -> Option<String>
             op: _,
             similar_op,
             description,
-        } => Some(format!("Did you mean `{similar_op}` for {description}?")),
+        } => Some(tr!(
+            "hint.similar-operator",
+            "Did you mean `{similar_op}` for {description}?",
+            similar_op = similar_op,
+            description = description,
+        )),
         RevsetParseErrorKind::NoSuchFunction {
             name: _,
             candidates,
@@ -910,9 +1229,10 @@ pub(crate) fn revset_parse_error_hint(err: &RevsetParseError) -> Option<String>
 
 fn revset_resolution_error_hints(err: &RevsetResolutionError) -> Vec<String> {
     let multiple_targets_hint = |targets: &[CommitId]| {
-        format!(
-            "Use commit ID to select single revision from: {}",
-            targets.iter().map(|id| format!("{id:.12}")).join(", ")
+        tr!(
+            "hint.select-commit-id",
+            "Use commit ID to select single revision from: {targets}",
+            targets = targets.iter().map(|id| format!("{id:.12}")).join(", "),
         )
     };
     match err {
@@ -924,15 +1244,23 @@ fn revset_resolution_error_hints(err: &RevsetResolutionError) -> Vec<String> {
             symbol,
             visible_targets,
         } => vec![
-            format!(
-                "Use change offset to select single revision: {}",
-                visible_targets
+            tr!(
+                "hint.divergent-change.select-offset",
+                "Use change offset to select single revision: {targets}",
+                targets = visible_targets
                     .iter()
                     .map(|(offset, _)| format!("{symbol}/{offset}"))
-                    .join(", ")
+                    .join(", "),
+            ),
+            tr!(
+                "hint.divergent-change.select-all",
+                "Use `change_id({symbol})` to select all revisions",
+                symbol = symbol,
+            ),
+            tr!(
+                "hint.divergent-change.abandon",
+                "To abandon unneeded revisions, run `jj abandon <commit_id>`"
             ),
-            format!("Use `change_id({symbol})` to select all revisions"),
-            "To abandon unneeded revisions, run `jj abandon <commit_id>`".to_owned(),
         ],
         RevsetResolutionError::ConflictedRef {
             kind: "bookmark",
@@ -940,10 +1268,16 @@ fn revset_resolution_error_hints(err: &RevsetResolutionError) -> Vec<String> {
             targets,
         } => vec![
             multiple_targets_hint(targets),
-            format!("Use `bookmarks({symbol})` to select all revisions"),
-            format!(
+            tr!(
+                "hint.conflicted-bookmark.select-all",
+                "Use `bookmarks({symbol})` to select all revisions",
+                symbol = symbol,
+            ),
+            tr!(
+                "hint.conflicted-bookmark.set",
                 "To set which revision the bookmark points to, run `jj bookmark set {symbol} -r \
-                 <REVISION>`"
+                 <REVISION>`",
+                symbol = symbol,
             ),
         ],
         RevsetResolutionError::ConflictedRef {
@@ -962,17 +1296,17 @@ fn revset_resolution_error_hints(err: &RevsetResolutionError) -> Vec<String> {
 
 fn string_pattern_parse_error_hint(err: &StringPatternParseError) -> Option<String> {
     match err {
-        StringPatternParseError::InvalidKind(_) => Some(
+        StringPatternParseError::InvalidKind(_) => Some(tr!(
+            "hint.string-pattern-kind",
             "Try prefixing with one of `exact:`, `glob:`, `regex:`, `substring:`, or one of these \
              with `-i` suffix added (e.g. `glob-i:`) for case-insensitive matching"
-                .into(),
-        ),
+        )),
         StringPatternParseError::GlobPattern(_) | StringPatternParseError::Regex(_) => None,
     }
 }
 
 fn tag_name_parse_error_hint(_: &TagNameParseError) -> Option<String> {
-    Some(REVSET_SYMBOL_HINT.to_owned())
+    Some(revset_symbol_hint())
 }
 
 fn template_parse_error_hint(err: &TemplateParseError) -> Option<String> {
@@ -1003,16 +1337,29 @@ fn try_handle_command_result(ui: &mut Ui, result: Result<(), CommandError>) -> i
     };
     let err = &cmd_err.error;
     let hints = &cmd_err.hints;
+    if ui.error_format() == ErrorFormat::Json && !is_clap_display_request(err.as_ref()) {
+        return print_error_json(ui, cmd_err);
+    }
     match cmd_err.kind {
         CommandErrorKind::User => {
-            print_error(ui, "Error: ", err, hints)?;
+            print_error(ui, &error_heading(), err, hints)?;
             Ok(1)
         }
         CommandErrorKind::Config => {
-            print_error(ui, "Config error: ", err, hints)?;
+            print_error(
+                ui,
+                &tr!("heading.config-error", "Config error: "),
+                err,
+                hints,
+            )?;
             writeln!(
                 ui.stderr_formatter().labeled("hint"),
-                "For help, see https://docs.jj-vcs.dev/latest/config/ or use `jj help -k config`."
+                "{}",
+                tr!(
+                    "hint.config-help",
+                    "For help, see https://docs.jj-vcs.dev/latest/config/ or use `jj help -k \
+                     config`."
+                )
             )?;
             Ok(1)
         }
@@ -1020,7 +1367,7 @@ fn try_handle_command_result(ui: &mut Ui, result: Result<(), CommandError>) -> i
             if let Some(err) = err.downcast_ref::<clap::Error>() {
                 handle_clap_error(ui, err, hints)
             } else {
-                print_error(ui, "Error: ", err, hints)?;
+                print_error(ui, &error_heading(), err, hints)?;
                 Ok(2)
             }
         }
@@ -1029,12 +1376,65 @@ fn try_handle_command_result(ui: &mut Ui, result: Result<(), CommandError>) -> i
             Ok(BROKEN_PIPE_EXIT_CODE)
         }
         CommandErrorKind::Internal => {
-            print_error(ui, "Internal error: ", err, hints)?;
+            print_error(
+                ui,
+                &tr!("heading.internal-error", "Internal error: "),
+                err,
+                hints,
+            )?;
             Ok(255)
         }
     }
 }
 
+/// Returns true if the error is a request to print help or version, which
+/// isn't an error from the user's point of view.
+fn is_clap_display_request(err: &(dyn error::Error + Send + Sync + 'static)) -> bool {
+    err.downcast_ref::<clap::Error>().is_some_and(|err| {
+        matches!(
+            err.kind(),
+            clap::error::ErrorKind::DisplayHelp | clap::error::ErrorKind::DisplayVersion
+        )
+    })
+}
+
+/// Prints the error as a single-line JSON object to stderr.
+fn print_error_json(ui: &Ui, cmd_err: &CommandError) -> io::Result<u8> {
+    let exit_code = match cmd_err.kind {
+        CommandErrorKind::User | CommandErrorKind::Config => 1,
+        CommandErrorKind::Cli => 2,
+        CommandErrorKind::BrokenPipe => return Ok(BROKEN_PIPE_EXIT_CODE),
+        CommandErrorKind::Internal => 255,
+    };
+    let err = &cmd_err.error;
+    let sources = iter::successors(err.source(), |&err| err.source())
+        .map(|err| err.to_string())
+        .collect_vec();
+    let hints = cmd_err
+        .hints
+        .iter()
+        .map(|hint| match hint {
+            ErrorHint::PlainText(message) => message.clone(),
+            ErrorHint::Formatted(recorded) => String::from_utf8_lossy(recorded.data())
+                .trim_end()
+                .to_owned(),
+        })
+        .collect_vec();
+    let value = serde_json::json!({
+        "code": cmd_err.code().as_str(),
+        "message": err.to_string(),
+        "sources": sources,
+        "hints": hints,
+        "exit_code": exit_code,
+    });
+    writeln!(ui.stderr(), "{value}")?;
+    Ok(exit_code)
+}
+
+fn error_heading() -> String {
+    tr!("heading.error", "Error: ")
+}
+
 fn print_error(
     ui: &Ui,
     heading: &str,
@@ -1054,10 +1454,18 @@ pub fn print_error_sources(ui: &Ui, source: Option<&dyn error::Error>) -> io::Re
     };
     let mut formatter = ui.stderr_formatter().into_labeled("error_source");
     if err.source().is_none() {
-        write!(formatter.labeled("heading"), "Caused by: ")?;
+        write!(
+            formatter.labeled("heading"),
+            "{}",
+            tr!("heading.caused-by", "Caused by: ")
+        )?;
         writeln!(formatter, "{err}")?;
     } else {
-        writeln!(formatter.labeled("heading"), "Caused by:")?;
+        writeln!(
+            formatter.labeled("heading"),
+            "{}",
+            tr!("heading.caused-by-list", "Caused by:")
+        )?;
         for (i, err) in iter::successors(Some(err), |&err| err.source()).enumerate() {
             write!(formatter.labeled("heading"), "{}: ", i + 1)?;
             writeln!(formatter, "{err}")?;
@@ -1069,7 +1477,11 @@ pub fn print_error_sources(ui: &Ui, source: Option<&dyn error::Error>) -> io::Re
 fn print_error_hints(ui: &Ui, hints: &[ErrorHint]) -> io::Result<()> {
     let mut formatter = ui.stderr_formatter().into_labeled("hint");
     for hint in hints {
-        write!(formatter.labeled("heading"), "Hint: ")?;
+        write!(
+            formatter.labeled("heading"),
+            "{}",
+            tr!("heading.hint", "Hint: ")
+        )?;
         match hint {
             ErrorHint::PlainText(message) => {
                 writeln!(formatter, "{message}")?;
@@ -14,7 +14,12 @@
 
 use std::collections::HashMap;
 use std::collections::HashSet;
+use std::fs;
 use std::io;
+use std::io::Read as _;
+use std::mem;
+use std::path::Path;
+use std::path::PathBuf;
 
 use crossterm::ExecutableCommand as _;
 use crossterm::event::Event;
@@ -29,10 +34,17 @@ use itertools::Itertools as _;
 use jj_lib::backend::CommitId;
 use jj_lib::commit::Commit;
 use jj_lib::dag_walk;
+use jj_lib::matchers::EverythingMatcher;
+use jj_lib::merge::Diff;
+use jj_lib::merge::Merge;
+use jj_lib::merged_tree::MergedTree;
 use jj_lib::repo::MutableRepo;
 use jj_lib::repo::Repo as _;
+use jj_lib::revset::RevsetContainingFn;
 use jj_lib::revset::RevsetIteratorExt as _;
 use jj_lib::rewrite::CommitRewriter;
+use jj_lib::rewrite::CommitWithSelection;
+use jj_lib::rewrite::squash_commits;
 use pollster::FutureExt as _;
 use ratatui::Terminal;
 use ratatui::layout::Constraint;
@@ -52,17 +64,40 @@ use renderdag::Renderer as _;
 use tracing::instrument;
 
 use crate::cli_util::CommandHelper;
+use crate::cli_util::DiffSelector;
 use crate::cli_util::RevisionArg;
+use crate::cli_util::WorkspaceCommandHelper;
 use crate::cli_util::short_commit_hash;
 use crate::command_error::CommandError;
 use crate::command_error::internal_error;
 use crate::command_error::user_error;
+use crate::command_error::user_error_with_message;
 use crate::complete;
+use crate::description_util::concatenate_messages;
 use crate::templater::TemplateRenderer;
 use crate::ui::Ui;
 
-/// Interactively arrange the commit graph.
+/// Interactively arrange the commit graph
+///
+/// Besides reordering revisions and changing their parents, revisions can be
+/// abandoned, squashed into their parent, or split in two. Splitting a revision
+/// opens the diff editor to select the changes for the first of the two
+/// revisions, like `jj split`.
+///
+/// With `--script`, the changes are read from a script instead of the TUI.
+/// Each line of the script is one of the following commands, where `REV` is a
+/// revision without spaces:
+///
+/// - `set-parent REV PARENT...`: Set the parents of `REV` to the given
+///   revisions. The parents may be outside the arranged revisions, but not
+///   descendants of them.
+/// - `abandon REV`: Abandon `REV`.
+/// - `squash REV`: Squash `REV` into its parent.
+/// - `keep REV`: Keep `REV`, undoing an earlier `abandon` or `squash`.
+///
+/// Empty lines and lines starting with `#` are ignored.
 #[derive(clap::Args, Clone, Debug)]
+#[command(verbatim_doc_comment)]
 pub(crate) struct ArrangeArgs {
     /// The revisions to edit.
     ///
@@ -71,6 +106,12 @@ pub(crate) struct ArrangeArgs {
     #[arg(long, short, value_name = "REVSETS")]
     #[arg(add = clap_complete::ArgValueCompleter::new(complete::revset_expression_mutable))]
     revisions: Vec<RevisionArg>,
+
+    /// Apply the commands in the given file instead of launching the TUI
+    ///
+    /// Use `-` to read the commands from stdin.
+    #[arg(long, value_name = "FILE", value_hint = clap::ValueHint::FilePath)]
+    script: Option<PathBuf>,
 }
 
 #[instrument(skip_all)]
@@ -109,48 +150,217 @@ pub(crate) fn cmd_arrange(
         .evaluate(repo.as_ref())?;
     let external_children: Vec<_> = children_revset.iter().commits(repo.store()).try_collect()?;
 
-    let revset = target_expression.evaluate(repo.as_ref())?;
+    let revset = target_expression.clone().evaluate(repo.as_ref())?;
     let commits: Vec<Commit> = revset.iter().commits(repo.store()).try_collect()?;
     if commits.is_empty() {
         writeln!(ui.status(), "No revisions to arrange.")?;
         return Ok(());
     }
 
-    // Set up the terminal
-    io::stdout().execute(EnterAlternateScreen)?;
-    enable_raw_mode()?;
-    let mut terminal = Terminal::new(CrosstermBackend::new(io::stdout()))?;
-    terminal.clear()?;
-
     let mut state = State::new(commits, external_children);
     state.update_commit_order();
 
-    let result = run_tui(
-        ui,
-        &mut terminal,
-        &workspace_command.commit_summary_template(),
-        state,
-    );
-
-    // Restore the terminal
-    disable_raw_mode()?;
-    io::stdout().execute(LeaveAlternateScreen)?;
-
-    if let Some(new_state) = result? {
-        let mut tx = workspace_command.start_transaction();
-        new_state.apply_changes(tx.repo_mut()).block_on()?;
-        tx.finish(ui, "arrange revisions")?;
-        Ok(())
+    let new_state = if let Some(script_path) = &args.script {
+        let script = read_script(command, script_path)?;
+        let descendants_revset = target_expression.descendants().evaluate(repo.as_ref())?;
+        run_script(
+            ui,
+            &workspace_command,
+            &*descendants_revset.containing_fn(),
+            &mut state,
+            &script,
+        )?;
+        state
     } else {
-        Err(user_error("Canceled by user"))
+        let diff_selector = workspace_command.diff_selector(ui, None, true)?;
+
+        // Set up the terminal
+        io::stdout().execute(EnterAlternateScreen)?;
+        enable_raw_mode()?;
+        let mut terminal = Terminal::new(CrosstermBackend::new(io::stdout()))?;
+        terminal.clear()?;
+
+        let mut select_split = |ui: &Ui, commit: &Commit| -> Result<_, CommandError> {
+            // Hand the terminal over to the diff editor
+            disable_raw_mode()?;
+            io::stdout().execute(LeaveAlternateScreen)?;
+            let selection = select_split_changes(ui, &workspace_command, &diff_selector, commit);
+            io::stdout().execute(EnterAlternateScreen)?;
+            enable_raw_mode()?;
+            selection
+        };
+        let result = run_tui(
+            ui,
+            &mut terminal,
+            &workspace_command.commit_summary_template(),
+            &mut select_split,
+            state,
+        );
+
+        // Restore the terminal
+        disable_raw_mode()?;
+        io::stdout().execute(LeaveAlternateScreen)?;
+
+        result?.ok_or_else(|| user_error("Canceled by user"))?
+    };
+
+    let mut tx = workspace_command.start_transaction();
+    new_state.apply_changes(tx.repo_mut()).block_on()?;
+    tx.finish(ui, "arrange revisions")?;
+    Ok(())
+}
+
+/// Reads the script at `path`, or from stdin if the path is `-`.
+fn read_script(command: &CommandHelper, path: &Path) -> Result<String, CommandError> {
+    if path == Path::new("-") {
+        let mut script = String::new();
+        io::stdin().read_to_string(&mut script)?;
+        return Ok(script);
+    }
+    fs::read_to_string(command.cwd().join(path)).map_err(|err| {
+        user_error_with_message(format!("Failed to read script {}", path.display()), err)
+    })
+}
+
+/// Applies the commands in the `script` to the `state`.
+fn run_script(
+    ui: &Ui,
+    workspace_command: &WorkspaceCommandHelper,
+    is_descendant: &RevsetContainingFn<'_>,
+    state: &mut State,
+    script: &str,
+) -> Result<(), CommandError> {
+    for (index, line) in script.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        run_script_line(ui, workspace_command, is_descendant, state, line)
+            .map_err(|err| err.hinted(format!("The error is in line {}: {line}", index + 1)))?;
+    }
+    Ok(())
+}
+
+fn run_script_line(
+    ui: &Ui,
+    workspace_command: &WorkspaceCommandHelper,
+    is_descendant: &RevsetContainingFn<'_>,
+    state: &mut State,
+    line: &str,
+) -> Result<(), CommandError> {
+    let resolve = |rev: &str| -> Result<CommitId, CommandError> {
+        let commit =
+            workspace_command.resolve_single_rev(ui, &RevisionArg::from(rev.to_owned()))?;
+        Ok(commit.id().clone())
+    };
+    let resolve_arranged = |rev: &str| -> Result<CommitId, CommandError> {
+        let id = resolve(rev)?;
+        if !state.commits.contains_key(&id) {
+            return Err(user_error(format!(
+                "Revision {} is not being arranged",
+                short_commit_hash(&id)
+            )));
+        }
+        Ok(id)
+    };
+    let words = line.split_whitespace().collect_vec();
+    match words.as_slice() {
+        ["set-parent", rev, parent_revs @ ..] if !parent_revs.is_empty() => {
+            let id = resolve_arranged(rev)?;
+            let parent_ids: Vec<CommitId> = parent_revs
+                .iter()
+                .map(|rev| resolve(rev))
+                .process_results(|ids| ids.unique().collect())?;
+            for parent_id in &parent_ids {
+                if !state.commits.contains_key(parent_id) && is_descendant(parent_id)? {
+                    return Err(user_error(format!(
+                        "Revision {} is a descendant of the arranged revisions",
+                        short_commit_hash(parent_id)
+                    )));
+                }
+            }
+            if !state.set_parents(&id, parent_ids) {
+                return Err(user_error(format!(
+                    "Setting the parents of revision {} would create a cycle",
+                    short_commit_hash(&id)
+                )));
+            }
+        }
+        ["abandon", rev] => {
+            let id = resolve_arranged(rev)?;
+            state.set_action(id, Action::Abandon);
+        }
+        ["squash", rev] => {
+            let id = resolve_arranged(rev)?;
+            state.set_action(id, Action::Squash);
+        }
+        ["keep", rev] => {
+            let id = resolve_arranged(rev)?;
+            state.set_action(id, Action::Keep);
+        }
+        _ => {
+            return Err(
+                user_error(format!("Invalid arrange command: {line}")).hinted(
+                    "The commands are `set-parent REV PARENT...`, `abandon REV`, `squash REV`, \
+                     and `keep REV`",
+                ),
+            );
+        }
+    }
+    Ok(())
+}
+
+/// Prompts the user to select the changes for the first of the two revisions
+/// `commit` is split into. Returns `None` if nothing or everything was
+/// selected.
+fn select_split_changes(
+    ui: &Ui,
+    workspace_command: &WorkspaceCommandHelper,
+    diff_selector: &DiffSelector,
+    commit: &Commit,
+) -> Result<Option<MergedTree>, CommandError> {
+    let format_instructions = || {
+        format!(
+            "\
+You are splitting a commit into two: {}
+
+The diff initially shows the changes in the commit you're splitting.
+
+Adjust the right side until it shows the contents you want to split into the
+new commit.
+The changes that are not selected will replace the original commit.
+",
+            workspace_command.format_commit_summary(commit)
+        )
+    };
+    let parent_tree = commit.parent_tree(workspace_command.repo().as_ref())?;
+    let selected_tree = diff_selector.select(
+        ui,
+        Diff::new(&parent_tree, &commit.tree()),
+        Diff::new(commit.parents_conflict_label()?, commit.conflict_label()),
+        &EverythingMatcher,
+        format_instructions,
+    )?;
+    if selected_tree.tree_ids() == parent_tree.tree_ids()
+        || selected_tree.tree_ids() == commit.tree_ids()
+    {
+        return Ok(None);
     }
+    Ok(Some(selected_tree))
 }
 
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
 enum Action {
     Abandon,
     Keep,
+    /// Squash the commit into its parent
+    Squash,
+    /// Split the commit in two. The changes for the first commit are in
+    /// `State::split_trees`.
+    Split,
 }
 
+#[derive(Clone)]
 struct State {
     commits: HashMap<CommitId, Commit>,
     /// Heads of the set in the order they should be added to the UI. This is
@@ -165,6 +375,11 @@ struct State {
     actions: HashMap<CommitId, Action>,
     parents: HashMap<CommitId, Vec<CommitId>>,
     external_children: HashMap<CommitId, Commit>,
+    /// The commit whose parents are being selected, if any
+    editing_parents: Option<CommitId>,
+    /// The trees with the changes selected for the first commit of each commit
+    /// to split, relative to the original parents.
+    split_trees: HashMap<CommitId, MergedTree>,
 }
 
 impl State {
@@ -221,11 +436,142 @@ impl State {
             actions,
             parents,
             external_children,
+            editing_parents: None,
+            split_trees: HashMap::new(),
+        }
+    }
+
+    /// Returns true if the commits have the same parents and actions as in
+    /// `other`, regardless of the selection.
+    fn has_same_arrangement(&self, other: &Self) -> bool {
+        self.parents == other.parents
+            && self.actions == other.actions
+            && self.split_trees.len() == other.split_trees.len()
+            && self.split_trees.iter().all(|(id, tree)| {
+                other
+                    .split_trees
+                    .get(id)
+                    .is_some_and(|other_tree| other_tree.tree_ids() == tree.tree_ids())
+            })
+    }
+
+    /// Sets the action of `id`. Use [`Self::split_commit`] to split it.
+    fn set_action(&mut self, id: CommitId, action: Action) {
+        assert_ne!(action, Action::Split);
+        self.split_trees.remove(&id);
+        self.actions.insert(id, action);
+    }
+
+    /// Marks `id` to be split, with the changes in `selected_tree` going into
+    /// the first commit.
+    fn split_commit(&mut self, id: CommitId, selected_tree: MergedTree) {
+        self.split_trees.insert(id.clone(), selected_tree);
+        self.actions.insert(id, Action::Split);
+    }
+
+    /// Returns the commit which `id` would be squashed into. That's its only
+    /// parent, or the commit that parent is squashed into in turn. Returns
+    /// `None` if the commit has multiple parents, or if the parent is outside
+    /// the set or abandoned.
+    fn squash_destination(&self, id: &CommitId) -> Option<&CommitId> {
+        let [parent_id] = self.parents.get(id).unwrap().as_slice() else {
+            return None;
+        };
+        if !self.commits.contains_key(parent_id) {
+            return None;
+        }
+        match self.actions.get(parent_id).unwrap() {
+            Action::Keep | Action::Split => Some(parent_id),
+            Action::Squash => self.squash_destination(parent_id),
+            Action::Abandon => None,
+        }
+    }
+
+    /// Update `head_order` after parents have changed. Remaining heads keep
+    /// their order, and new heads are added in the current UI order.
+    fn update_head_order(&mut self) {
+        let heads: HashSet<&CommitId> = dag_walk::heads(
+            self.current_order.iter(),
+            |id| *id,
+            |id| {
+                self.parents
+                    .get(id)
+                    .unwrap()
+                    .iter()
+                    .filter(|id| self.commits.contains_key(id))
+            },
+        );
+        let mut head_order = self
+            .head_order
+            .iter()
+            .filter(|id| heads.contains(id))
+            .cloned()
+            .collect_vec();
+        for id in &self.current_order {
+            if heads.contains(id) && !head_order.contains(id) {
+                head_order.push(id.clone());
+            }
+        }
+        self.head_order = head_order;
+    }
+
+    /// Returns true if `ancestor_id` is `id` or one of its ancestors in the
+    /// set.
+    fn is_ancestor(&self, ancestor_id: &CommitId, id: &CommitId) -> bool {
+        dag_walk::dfs(
+            [id],
+            |id| *id,
+            |id| {
+                self.parents
+                    .get(id)
+                    .unwrap()
+                    .iter()
+                    .filter(|id| self.commits.contains_key(id))
+            },
+        )
+        .any(|id| id == ancestor_id)
+    }
+
+    /// Adds `parent_id` to the parents of `id`, or removes it if it's already a
+    /// parent. Returns false if the change would remove the last parent or
+    /// create a cycle.
+    fn toggle_parent(&mut self, id: &CommitId, parent_id: &CommitId) -> bool {
+        let parents = self.parents.get(id).unwrap();
+        if parents.contains(parent_id) {
+            if parents.len() == 1 {
+                return false;
+            }
+            self.parents
+                .get_mut(id)
+                .unwrap()
+                .retain(|id| id != parent_id);
+        } else {
+            if self.is_ancestor(id, parent_id) {
+                return false;
+            }
+            self.parents.get_mut(id).unwrap().push(parent_id.clone());
+        }
+        self.update_head_order();
+        true
+    }
+
+    /// Replaces the parents of `id`. Returns false if that would create a
+    /// cycle.
+    fn set_parents(&mut self, id: &CommitId, parent_ids: Vec<CommitId>) -> bool {
+        if parent_ids.iter().any(|parent_id| {
+            self.commits.contains_key(parent_id) && self.is_ancestor(id, parent_id)
+        }) {
+            return false;
         }
+        self.parents.insert(id.clone(), parent_ids);
+        self.update_head_order();
+        true
     }
 
-    /// Update the current UI commit order after parents have changed.
+    /// Update the current UI commit order after parents have changed. The
+    /// selection stays on the same commit, which may have moved.
     fn update_commit_order(&mut self) {
+        let selected_id = self.current_order.get(self.current_selection).cloned();
         // Use the original order to get a determinisic order.
         let commit_ids: Vec<&CommitId> = dag_walk::topo_order_reverse(
             self.head_order.iter(),
@@ -241,6 +587,11 @@ impl State {
         )
         .unwrap();
         self.current_order = commit_ids.into_iter().cloned().collect();
+        if let Some(index) = selected_id
+            .and_then(|selected_id| self.current_order.iter().position(|id| *id == selected_id))
+        {
+            self.current_selection = index;
+        }
     }
 
     fn swap_commits(&mut self, a_idx: usize, b_idx: usize) {
@@ -306,6 +657,25 @@ impl State {
             |_| panic!("cycle detected"),
         )
         .unwrap();
+        // Check the commits to squash before the state is consumed below. The
+        // parent may have been abandoned after the commit was marked.
+        let mut squash_destinations: HashMap<CommitId, CommitId> = HashMap::new();
+        for (id, action) in &self.actions {
+            if *action != Action::Squash {
+                continue;
+            }
+            let destination_id = self.squash_destination(id).ok_or_else(|| {
+                user_error(format!(
+                    "Cannot squash revision {} into its parent",
+                    short_commit_hash(id)
+                ))
+                .hinted("The parent must be a single arranged revision which isn't abandoned.")
+            })?;
+            squash_destinations.insert(id.clone(), destination_id.clone());
+        }
+        // The split commits mapped to the second commit, which their children
+        // are rebased onto
+        let mut split_commit_ids: HashMap<CommitId, CommitId> = HashMap::new();
         // Rewrite the commits in the order determined above
         let mut rewritten_commits: HashMap<CommitId, Commit> = HashMap::new();
         for id in ordered_commit_ids {
@@ -314,48 +684,177 @@ impl State {
                 .remove(&id)
                 .or_else(|| self.external_children.remove(&id))
                 .unwrap();
-            let new_parents = mut_repo.new_parents(self.parents.get(&id).unwrap());
-            let rewriter = CommitRewriter::new(mut_repo, old_commit, new_parents);
-            let action = self.actions.remove(rewriter.old_commit().id()).unwrap();
+            let parent_ids = self
+                .parents
+                .get(&id)
+                .unwrap()
+                .iter()
+                .map(|parent_id| split_commit_ids.get(parent_id).unwrap_or(parent_id).clone())
+                .collect_vec();
+            let new_parents = mut_repo.new_parents(&parent_ids);
+            let action = self.actions.remove(&id).unwrap();
             match action {
-                Action::Abandon => rewriter.abandon(),
+                Action::Abandon => CommitRewriter::new(mut_repo, old_commit, new_parents).abandon(),
                 Action::Keep => {
+                    let rewriter = CommitRewriter::new(mut_repo, old_commit, new_parents);
                     if rewriter.parents_changed() {
                         let new_commit = rewriter.rebase().await?.write().await?;
                         rewritten_commits.insert(id, new_commit);
                     }
                 }
+                Action::Squash => {
+                    // The only new parent is the rewritten destination, which
+                    // may already contain other commits squashed into it.
+                    let destination = mut_repo.store().get_commit_async(&new_parents[0]).await?;
+                    let description = concatenate_messages([&destination, &old_commit]);
+                    let source = CommitWithSelection {
+                        parent_tree: old_commit.parent_tree_async(&*mut_repo).await?,
+                        selected_tree: old_commit.tree(),
+                        commit: old_commit,
+                    };
+                    let squashed = squash_commits(mut_repo, &[source], &destination, false)
+                        .await?
+                        .expect("a fully selected commit should be squashed");
+                    let new_destination = squashed
+                        .commit_builder
+                        .set_description(description)
+                        .write()
+                        .await?;
+                    // The children are rebased onto the destination even if
+                    // the commit was moved onto it.
+                    mut_repo.record_abandoned_commit_with_parents(
+                        id.clone(),
+                        [new_destination.id().clone()],
+                    );
+                    rewritten_commits.insert(squash_destinations[&id].clone(), new_destination);
+                }
+                Action::Split => {
+                    let selected_tree = self.split_trees.remove(&id).unwrap();
+                    let label = old_commit.conflict_label();
+                    let rewriter =
+                        CommitRewriter::new(mut_repo, old_commit.clone(), new_parents.clone());
+                    let full_tree = rewriter.rebase().await?.tree();
+                    // Remove the changes which weren't selected from the
+                    // rebased tree
+                    let first_tree = MergedTree::merge(Merge::from_diffs(
+                        (
+                            full_tree.clone(),
+                            format!("{label} (rebased split revision)"),
+                        ),
+                        [Diff::new(
+                            (old_commit.tree(), format!("{label} (split revision)")),
+                            (
+                                selected_tree,
+                                format!("selected changes for split (from {label})"),
+                            ),
+                        )],
+                    ))
+                    .await?;
+                    let first_commit = mut_repo
+                        .rewrite_commit(&old_commit)
+                        .set_parents(new_parents)
+                        .set_tree(first_tree)
+                        .write()
+                        .await?;
+                    // Like `jj split`, the second commit gets a new change id
+                    // and the working copy.
+                    let second_commit = mut_repo
+                        .rewrite_commit(&old_commit)
+                        .clear_rewrite_source()
+                        .generate_new_change_id()
+                        .set_parents(vec![first_commit.id().clone()])
+                        .set_tree(full_tree)
+                        .write()
+                        .await?;
+                    let workspace_names = mut_repo
+                        .view()
+                        .wc_commit_ids()
+                        .iter()
+                        .filter(|(_, wc_commit_id)| **wc_commit_id == id)
+                        .map(|(name, _)| name.clone())
+                        .collect_vec();
+                    for name in workspace_names {
+                        mut_repo.edit(name, &second_commit).await?;
+                    }
+                    split_commit_ids.insert(id.clone(), second_commit.id().clone());
+                    rewritten_commits.insert(id, first_commit);
+                }
             }
         }
         Ok(rewritten_commits)
     }
 }
 
+/// Snapshots of the state to undo and redo changes.
+#[derive(Default)]
+struct History {
+    undo_stack: Vec<State>,
+    redo_stack: Vec<State>,
+}
+
+impl History {
+    /// Records the `previous` state if the arrangement has changed since then.
+    fn record(&mut self, previous: State, current: &State) {
+        if !current.has_same_arrangement(&previous) {
+            self.undo_stack.push(previous);
+            self.redo_stack.clear();
+        }
+    }
+
+    /// Reverts the last change. Returns false if there's nothing to undo.
+    fn undo(&mut self, state: &mut State) -> bool {
+        let Some(previous) = self.undo_stack.pop() else {
+            return false;
+        };
+        self.redo_stack.push(mem::replace(state, previous));
+        true
+    }
+
+    /// Reapplies the last undone change. Returns false if there's nothing to
+    /// redo.
+    fn redo(&mut self, state: &mut State) -> bool {
+        let Some(next) = self.redo_stack.pop() else {
+            return false;
+        };
+        self.undo_stack.push(mem::replace(state, next));
+        true
+    }
+}
+
+type SelectSplitFn<'a> = dyn FnMut(&Ui, &Commit) -> Result<Option<MergedTree>, CommandError> + 'a;
+
 fn run_tui<B: ratatui::backend::Backend>(
     ui: &mut Ui,
     terminal: &mut Terminal<B>,
     template: &TemplateRenderer<Commit>,
+    select_split: &mut SelectSplitFn<'_>,
     mut state: State,
 ) -> Result<Option<State>, CommandError> {
-    let help_items = [
+    let help_line = build_help_line(&[
         ("↓/j", "down"),
         ("↑/k", "up"),
         ("⇧+↓/J", "swap down"),
         ("⇧+↑/K", "swap up"),
         ("a", "abandon"),
         ("p", "keep"),
+        ("s", "squash"),
+        ("x", "split"),
+        ("e", "edit parents"),
+        ("u", "undo"),
+        ("⌃r", "redo"),
         ("c", "confirm"),
         ("q", "quit"),
-    ];
-    let mut help_spans = Vec::new();
-    for (i, (key, desc)) in help_items.iter().enumerate() {
-        if i > 0 {
-            help_spans.push(Span::raw(" • "));
-        }
-        help_spans.push(Span::styled(*key, Style::default().fg(Color::Magenta)));
-        help_spans.push(Span::raw(format!(" {desc}")));
-    }
-    let help_line = Line::from(help_spans);
+    ]);
+    let parents_help_line = build_help_line(&[
+        ("↓/j", "down"),
+        ("↑/k", "up"),
+        ("space", "toggle parent"),
+        ("e/⏎", "done"),
+        ("u", "undo"),
+        ("⌃r", "redo"),
+        ("q", "quit"),
+    ]);
+    let mut history = History::default();
 
     loop {
         terminal
@@ -367,7 +866,11 @@ fn run_tui<B: ratatui::backend::Backend>(
                 let main_area = layout[0];
                 let help_area = layout[1];
                 render(&state, ui, template, frame, main_area);
-                frame.render_widget(&help_line, help_area);
+                if state.editing_parents.is_some() {
+                    frame.render_widget(&parents_help_line, help_area);
+                } else {
+                    frame.render_widget(&help_line, help_area);
+                }
             })
             .map_err(|e| internal_error(format!("Failed to draw TUI: {e}")))?;
 
@@ -379,13 +882,11 @@ fn run_tui<B: ratatui::backend::Backend>(
             if event.is_release() {
                 continue;
             }
+            let previous = state.clone();
             match (event.code, event.modifiers) {
                 (KeyCode::Char('q'), KeyModifiers::NONE) => {
                     return Ok(None);
                 }
-                (KeyCode::Char('c'), KeyModifiers::NONE) => {
-                    return Ok(Some(state));
-                }
                 (KeyCode::Down | KeyCode::Char('j'), KeyModifiers::NONE) => {
                     if state.current_selection + 1 < state.commits.len() {
                         state.current_selection += 1;
@@ -396,13 +897,68 @@ fn run_tui<B: ratatui::backend::Backend>(
                         state.current_selection -= 1;
                     }
                 }
+                // The snapshots are consistent, so the commit order doesn't need
+                // to be updated.
+                (KeyCode::Char('u'), KeyModifiers::NONE) => {
+                    history.undo(&mut state);
+                    continue;
+                }
+                (KeyCode::Char('r'), KeyModifiers::CONTROL) => {
+                    history.redo(&mut state);
+                    continue;
+                }
+                (KeyCode::Char(' '), KeyModifiers::NONE) if state.editing_parents.is_some() => {
+                    let id = state.editing_parents.clone().unwrap();
+                    let parent_id = state.current_order[state.current_selection].clone();
+                    state.toggle_parent(&id, &parent_id);
+                }
+                (KeyCode::Char('e') | KeyCode::Enter | KeyCode::Esc, KeyModifiers::NONE)
+                    if state.editing_parents.is_some() =>
+                {
+                    // Move the selection back to the edited commit
+                    let id = state.editing_parents.take().unwrap();
+                    state.current_selection = state
+                        .current_order
+                        .iter()
+                        .position(|current_id| *current_id == id)
+                        .unwrap();
+                }
+                _ if state.editing_parents.is_some() => {
+                    continue;
+                }
+                (KeyCode::Char('c'), KeyModifiers::NONE) => {
+                    return Ok(Some(state));
+                }
+                (KeyCode::Char('e'), KeyModifiers::NONE) => {
+                    let id = state.current_order[state.current_selection].clone();
+                    state.editing_parents = Some(id);
+                }
                 (KeyCode::Char('a'), KeyModifiers::NONE) => {
                     let id = state.current_order[state.current_selection].clone();
-                    state.actions.insert(id, Action::Abandon);
+                    state.set_action(id, Action::Abandon);
                 }
                 (KeyCode::Char('p'), KeyModifiers::NONE) => {
                     let id = state.current_order[state.current_selection].clone();
-                    state.actions.insert(id, Action::Keep);
+                    state.set_action(id, Action::Keep);
+                }
+                (KeyCode::Char('s'), KeyModifiers::NONE) => {
+                    let id = state.current_order[state.current_selection].clone();
+                    if state.squash_destination(&id).is_none() {
+                        continue;
+                    }
+                    state.set_action(id, Action::Squash);
+                }
+                (KeyCode::Char('x'), KeyModifiers::NONE) => {
+                    let id = state.current_order[state.current_selection].clone();
+                    let selected_tree = select_split(ui, &state.commits[&id])?;
+                    // The diff editor used the whole screen
+                    terminal
+                        .clear()
+                        .map_err(|e| internal_error(format!("Failed to clear TUI: {e}")))?;
+                    let Some(selected_tree) = selected_tree else {
+                        continue;
+                    };
+                    state.split_commit(id, selected_tree);
                 }
                 // TODO: Allow swapping up/down only within linear parts of the graph.
                 (KeyCode::Down | KeyCode::Char('J'), KeyModifiers::SHIFT) => {
@@ -420,10 +976,23 @@ fn run_tui<B: ratatui::backend::Backend>(
                 }
             }
             state.update_commit_order();
+            history.record(previous, &state);
         }
     }
 }
 
+fn build_help_line(items: &[(&'static str, &str)]) -> Line<'static> {
+    let mut help_spans = Vec::new();
+    for (i, (key, desc)) in items.iter().enumerate() {
+        if i > 0 {
+            help_spans.push(Span::raw(" • "));
+        }
+        help_spans.push(Span::styled(*key, Style::default().fg(Color::Magenta)));
+        help_spans.push(Span::raw(format!(" {desc}")));
+    }
+    Line::from(help_spans)
+}
+
 fn render(
     state: &State,
     ui: &mut Ui,
@@ -475,7 +1044,7 @@ fn render(
             .collect_vec();
         let glyph = match action {
             Action::Abandon => "×",
-            Action::Keep => "○",
+            Action::Keep | Action::Squash | Action::Split => "○",
         };
         let graph_lines = row_renderer.next_row(id, edges, glyph.to_string(), "".to_string());
         let graph_text = Text::from(graph_lines);
@@ -487,9 +1056,19 @@ fn render(
             .intersection(main_area);
         frame.render_widget(graph_text, graph_area);
 
-        let action_text = match action {
-            Action::Abandon => "abandon",
-            Action::Keep => "keep",
+        // While selecting parents, show which commits are parents of the edited
+        // commit instead of the actions.
+        let editing_parents = state
+            .editing_parents
+            .as_ref()
+            .map(|editing_id| (editing_id, state.parents.get(editing_id).unwrap()));
+        let action_text = match (editing_parents, action) {
+            (Some((editing_id, _)), _) if editing_id == id => "editing",
+            (Some((_, parents)), _) if parents.contains(id) => "parent",
+            (_, Action::Abandon) => "abandon",
+            (_, Action::Keep) => "keep",
+            (_, Action::Squash) => "squash",
+            (_, Action::Split) => "split",
         };
         frame.render_widget(Text::from(action_text), action_area);
 
@@ -507,6 +1086,8 @@ mod tests {
     use maplit::hashset;
     use testutils::CommitBuilderExt as _;
     use testutils::TestRepo;
+    use testutils::create_tree;
+    use testutils::repo_path;
 
     use super::*;
 
@@ -651,6 +1232,205 @@ mod tests {
             *state.parents.get(commit_f.id()).unwrap(),
             vec![commit_c.id().clone()],
         );
+
+        // The selection stays on D after the order is recalculated
+        state.update_commit_order();
+        assert_eq!(state.current_order[0], *commit_c.id());
+        assert_eq!(state.current_order[state.current_selection], *commit_d.id());
+    }
+
+    #[test]
+    fn test_toggle_parent() {
+        let test_repo = TestRepo::init();
+        let store = test_repo.repo.store();
+        let empty_tree = store.empty_merged_tree();
+
+        // Make C a parent of B, then linearize B onto C:
+        //                B
+        //               /|          B
+        // C B          C |          |
+        // |/     =>    |/     =>    C
+        // A            A            |
+        //                           A
+        let mut tx = test_repo.repo.start_transaction();
+        let mut create_commit = |parents| {
+            tx.repo_mut()
+                .new_commit(parents, empty_tree.clone())
+                .write_unwrap()
+        };
+        let commit_a = create_commit(vec![store.root_commit_id().clone()]);
+        let commit_b = create_commit(vec![commit_a.id().clone()]);
+        let commit_c = create_commit(vec![commit_a.id().clone()]);
+
+        let mut state = State::new(
+            vec![commit_c.clone(), commit_b.clone(), commit_a.clone()],
+            vec![],
+        );
+        state.update_commit_order();
+        assert_eq!(
+            state.head_order,
+            vec![commit_c.id().clone(), commit_b.id().clone()]
+        );
+
+        // Add C as a parent of B, so C is no longer a head
+        assert!(state.toggle_parent(commit_b.id(), commit_c.id()));
+        assert_eq!(
+            *state.parents.get(commit_b.id()).unwrap(),
+            vec![commit_a.id().clone(), commit_c.id().clone()],
+        );
+        assert_eq!(state.head_order, vec![commit_b.id().clone()]);
+
+        // Remove A from the parents of B
+        assert!(state.toggle_parent(commit_b.id(), commit_a.id()));
+        assert_eq!(
+            *state.parents.get(commit_b.id()).unwrap(),
+            vec![commit_c.id().clone()],
+        );
+        state.update_commit_order();
+        assert_eq!(
+            state.current_order,
+            vec![
+                commit_b.id().clone(),
+                commit_c.id().clone(),
+                commit_a.id().clone(),
+            ]
+        );
+
+        // The last parent can't be removed
+        assert!(!state.toggle_parent(commit_b.id(), commit_c.id()));
+        // Cycles can't be created
+        assert!(!state.toggle_parent(commit_b.id(), commit_b.id()));
+        assert!(!state.toggle_parent(commit_c.id(), commit_b.id()));
+        assert!(!state.toggle_parent(commit_a.id(), commit_b.id()));
+        assert_eq!(
+            *state.parents.get(commit_c.id()).unwrap(),
+            vec![commit_a.id().clone()],
+        );
+
+        // Removing C from the parents of B makes C a head again
+        assert!(state.toggle_parent(commit_b.id(), commit_a.id()));
+        assert!(state.toggle_parent(commit_b.id(), commit_c.id()));
+        assert_eq!(
+            state.head_order,
+            vec![commit_b.id().clone(), commit_c.id().clone()]
+        );
+    }
+
+    #[test]
+    fn test_set_parents() {
+        let test_repo = TestRepo::init();
+        let store = test_repo.repo.store();
+        let empty_tree = store.empty_merged_tree();
+
+        // C B
+        // |/
+        // A
+        let mut tx = test_repo.repo.start_transaction();
+        let mut create_commit = |parents| {
+            tx.repo_mut()
+                .new_commit(parents, empty_tree.clone())
+                .write_unwrap()
+        };
+        let commit_a = create_commit(vec![store.root_commit_id().clone()]);
+        let commit_b = create_commit(vec![commit_a.id().clone()]);
+        let commit_c = create_commit(vec![commit_a.id().clone()]);
+
+        let mut state = State::new(
+            vec![commit_c.clone(), commit_b.clone(), commit_a.clone()],
+            vec![],
+        );
+        state.update_commit_order();
+
+        // Make C a merge of B and the root commit, which is outside the set
+        assert!(state.set_parents(
+            commit_c.id(),
+            vec![commit_b.id().clone(), store.root_commit_id().clone()]
+        ));
+        assert_eq!(state.head_order, vec![commit_c.id().clone()]);
+        state.update_commit_order();
+        assert_eq!(
+            state.current_order,
+            vec![
+                commit_c.id().clone(),
+                commit_b.id().clone(),
+                commit_a.id().clone(),
+            ]
+        );
+
+        // Cycles can't be created
+        assert!(!state.set_parents(commit_a.id(), vec![commit_c.id().clone()]));
+        assert!(!state.set_parents(commit_b.id(), vec![commit_b.id().clone()]));
+        assert_eq!(
+            *state.parents.get(commit_a.id()).unwrap(),
+            vec![store.root_commit_id().clone()],
+        );
+    }
+
+    #[test]
+    fn test_history() {
+        let test_repo = TestRepo::init();
+        let store = test_repo.repo.store();
+        let empty_tree = store.empty_merged_tree();
+
+        // B
+        // |
+        // A
+        let mut tx = test_repo.repo.start_transaction();
+        let mut create_commit = |parents| {
+            tx.repo_mut()
+                .new_commit(parents, empty_tree.clone())
+                .write_unwrap()
+        };
+        let commit_a = create_commit(vec![store.root_commit_id().clone()]);
+        let commit_b = create_commit(vec![commit_a.id().clone()]);
+
+        let mut state = State::new(vec![commit_b.clone(), commit_a.clone()], vec![]);
+        state.update_commit_order();
+        let mut history = History::default();
+        assert!(!history.undo(&mut state));
+
+        // Moving the selection isn't recorded
+        let previous = state.clone();
+        state.current_selection = 1;
+        history.record(previous, &state);
+        assert!(!history.undo(&mut state));
+
+        // Swap A and B, then abandon A
+        let previous = state.clone();
+        state.swap_commits(0, 1);
+        state.update_commit_order();
+        history.record(previous, &state);
+        let previous = state.clone();
+        state.actions.insert(commit_a.id().clone(), Action::Abandon);
+        history.record(previous, &state);
+
+        // Undo both changes
+        assert!(history.undo(&mut state));
+        assert_eq!(state.actions[commit_a.id()], Action::Keep);
+        assert_eq!(state.parents[commit_a.id()], vec![commit_b.id().clone()]);
+        assert!(history.undo(&mut state));
+        assert_eq!(
+            state.parents[commit_a.id()],
+            vec![store.root_commit_id().clone()]
+        );
+        assert_eq!(
+            state.current_order,
+            vec![commit_b.id().clone(), commit_a.id().clone()]
+        );
+        assert!(!history.undo(&mut state));
+
+        // Redo the swap
+        assert!(history.redo(&mut state));
+        assert_eq!(state.parents[commit_a.id()], vec![commit_b.id().clone()]);
+
+        // A new change discards the undone abandonment
+        let previous = state.clone();
+        state.actions.insert(commit_b.id().clone(), Action::Abandon);
+        history.record(previous, &state);
+        assert!(!history.redo(&mut state));
+        assert!(history.undo(&mut state));
+        assert_eq!(state.actions[commit_a.id()], Action::Keep);
+        assert_eq!(state.actions[commit_b.id()], Action::Keep);
     }
 
     #[test]
@@ -780,4 +1560,120 @@ mod tests {
             hashset![commit_b.id().clone(), new_commit_d.id().clone()]
         );
     }
+
+    #[test]
+    fn test_apply_changes_squash() {
+        let test_repo = TestRepo::init();
+        let store = test_repo.repo.store();
+        let tree_a = create_tree(&test_repo.repo, &[(repo_path("a"), "a")]);
+        let tree_b = create_tree(
+            &test_repo.repo,
+            &[(repo_path("a"), "a"), (repo_path("b"), "b")],
+        );
+
+        // Squash B into A:
+        // c           c
+        // |           |
+        // B    =>     A+B
+        // |           |
+        // A           root
+        // |
+        // root
+        //
+        // Lowercase nodes are external to the set
+        let mut tx = test_repo.repo.start_transaction();
+        let commit_a = tx
+            .repo_mut()
+            .new_commit(vec![store.root_commit_id().clone()], tree_a)
+            .set_description("A\n")
+            .write_unwrap();
+        let commit_b = tx
+            .repo_mut()
+            .new_commit(vec![commit_a.id().clone()], tree_b.clone())
+            .set_description("B\n")
+            .write_unwrap();
+        let commit_c = tx
+            .repo_mut()
+            .new_commit(vec![commit_b.id().clone()], tree_b.clone())
+            .write_unwrap();
+
+        let mut state = State::new(
+            vec![commit_b.clone(), commit_a.clone()],
+            vec![commit_c.clone()],
+        );
+        // A can't be squashed into the root commit
+        assert_eq!(state.squash_destination(commit_a.id()), None);
+        assert_eq!(state.squash_destination(commit_b.id()), Some(commit_a.id()));
+
+        // B can't be squashed into an abandoned commit
+        state.set_action(commit_b.id().clone(), Action::Squash);
+        let mut abandoned_state = state.clone();
+        abandoned_state.set_action(commit_a.id().clone(), Action::Abandon);
+        assert!(
+            abandoned_state
+                .apply_changes(tx.repo_mut())
+                .block_on()
+                .is_err()
+        );
+
+        let rewritten = state.apply_changes(tx.repo_mut()).block_on().unwrap();
+        tx.repo_mut().rebase_descendants().block_on().unwrap();
+        let new_commit_a = rewritten.get(commit_a.id()).unwrap();
+        let new_commit_c = rewritten.get(commit_c.id()).unwrap();
+        assert_eq!(new_commit_a.parent_ids(), &[store.root_commit_id().clone()]);
+        assert_eq!(new_commit_a.tree_ids(), tree_b.tree_ids());
+        assert_eq!(new_commit_a.description(), "A\n\nB\n");
+        assert_eq!(new_commit_c.parent_ids(), &[new_commit_a.id().clone()]);
+        assert_eq!(new_commit_c.tree_ids(), tree_b.tree_ids());
+        assert_eq!(
+            *tx.repo_mut().view().heads(),
+            hashset![new_commit_c.id().clone()]
+        );
+    }
+
+    #[test]
+    fn test_apply_changes_split() {
+        let test_repo = TestRepo::init();
+        let store = test_repo.repo.store();
+        let tree_a = create_tree(&test_repo.repo, &[(repo_path("a"), "a")]);
+        let tree_ab = create_tree(
+            &test_repo.repo,
+            &[(repo_path("a"), "a"), (repo_path("b"), "b")],
+        );
+
+        // Split A, selecting the changes to file a:
+        //             c
+        // c           |
+        // |           A (b)
+        // A    =>     |
+        // |           A (a)
+        // root        |
+        //             root
+        //
+        // Lowercase nodes are external to the set
+        let mut tx = test_repo.repo.start_transaction();
+        let commit_a = tx
+            .repo_mut()
+            .new_commit(vec![store.root_commit_id().clone()], tree_ab.clone())
+            .write_unwrap();
+        let commit_c = tx
+            .repo_mut()
+            .new_commit(vec![commit_a.id().clone()], tree_ab.clone())
+            .write_unwrap();
+
+        let mut state = State::new(vec![commit_a.clone()], vec![commit_c.clone()]);
+        state.split_commit(commit_a.id().clone(), tree_a.clone());
+        let rewritten = state.apply_changes(tx.repo_mut()).block_on().unwrap();
+        tx.repo_mut().rebase_descendants().block_on().unwrap();
+        let first_commit = rewritten.get(commit_a.id()).unwrap();
+        let new_commit_c = rewritten.get(commit_c.id()).unwrap();
+        let second_commit = store.get_commit(&new_commit_c.parent_ids()[0]).unwrap();
+        assert_eq!(first_commit.parent_ids(), &[store.root_commit_id().clone()]);
+        assert_eq!(first_commit.tree_ids(), tree_a.tree_ids());
+        assert_eq!(first_commit.change_id(), commit_a.change_id());
+        assert_eq!(second_commit.parent_ids(), &[first_commit.id().clone()]);
+        assert_eq!(second_commit.tree_ids(), tree_ab.tree_ids());
+        assert_ne!(second_commit.change_id(), commit_a.change_id());
+        assert_eq!(new_commit_c.tree_ids(), tree_ab.tree_ids());
+    }
 }
@@ -0,0 +1,104 @@
+// Copyright 2026 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fs;
+use std::io;
+use std::io::Write as _;
+
+use clap_complete::ArgValueCompleter;
+use jj_lib::file_util::IoResultExt as _;
+use jj_lib::file_util::PathError;
+use jj_lib::object_id::ObjectId as _;
+use tracing::instrument;
+
+use crate::cli_util::CommandHelper;
+use crate::cli_util::RevisionArg;
+use crate::command_error::CommandError;
+use crate::command_error::user_error;
+use crate::complete;
+use crate::ui::Ui;
+
+/// Name of the file in the working-copy state directory which stores the
+/// change ID of the home change.
+const HOME_CHANGE_FILE: &str = "home_change";
+
+/// Return the working copy to the home change
+///
+/// The home change is the change you're mainly working on in a workspace.
+/// After temporarily moving the working copy elsewhere with e.g. `jj edit` or
+/// `jj new`, `jj back` edits the home change again. If the home change has
+/// been rewritten since, its latest version is edited.
+///
+/// The home change is recorded per workspace with `jj back --set`.
+#[derive(clap::Args, Clone, Debug)]
+pub(crate) struct BackArgs {
+    /// Record the given revision as the home change instead of returning to it
+    #[arg(long, value_name = "REVSET", num_args = 0..=1, default_missing_value = "@")]
+    #[arg(add = ArgValueCompleter::new(complete::revset_expression_mutable))]
+    set: Option<RevisionArg>,
+}
+
+#[instrument(skip_all)]
+pub(crate) fn cmd_back(
+    ui: &mut Ui,
+    command: &CommandHelper,
+    args: &BackArgs,
+) -> Result<(), CommandError> {
+    let mut workspace_command = command.workspace_helper(ui)?;
+    let home_change_path = workspace_command
+        .working_copy_state_path()
+        .ok_or_else(|| user_error("This command requires a standard local-disk working copy"))?
+        .join(HOME_CHANGE_FILE);
+
+    if let Some(revision) = &args.set {
+        let commit = workspace_command.resolve_single_rev(ui, revision)?;
+        fs::write(&home_change_path, commit.change_id().reverse_hex())
+            .context(&home_change_path)?;
+        if let Some(mut formatter) = ui.status_formatter() {
+            write!(formatter, "Home change set to ")?;
+            workspace_command.write_commit_summary(formatter.as_mut(), &commit)?;
+            writeln!(formatter)?;
+        }
+        return Ok(());
+    }
+
+    let home_change_hex = match fs::read_to_string(&home_change_path) {
+        Ok(content) => content.trim().to_owned(),
+        Err(err) if err.kind() == io::ErrorKind::NotFound => {
+            return Err(user_error("No home change is set for this workspace")
+                .hinted("Use `jj back --set` to record the working-copy change as home"));
+        }
+        Err(err) => {
+            return Err(PathError {
+                path: home_change_path,
+                source: err,
+            }
+            .into());
+        }
+    };
+    let home_commit = workspace_command
+        .resolve_single_rev(ui, &RevisionArg::from(home_change_hex))
+        .map_err(|err| {
+            err.hinted("Use `jj back --set <REVSET>` to record another change as home")
+        })?;
+    workspace_command.check_rewritable([home_commit.id()])?;
+    if workspace_command.get_wc_commit_id() == Some(home_commit.id()) {
+        writeln!(ui.status(), "Already editing the home change")?;
+    } else {
+        let mut tx = workspace_command.start_transaction();
+        tx.edit(&home_commit)?;
+        tx.finish(ui, format!("back to commit {}", home_commit.id().hex()))?;
+    }
+    Ok(())
+}
@@ -32,6 +32,7 @@ use crate::command_error::CommandError;
 use crate::command_error::print_parse_diagnostics;
 use crate::command_error::user_error;
 use crate::complete;
+use crate::i18n::tr;
 use crate::revset_util::parse_union_name_patterns;
 use crate::ui::Ui;
 
@@ -93,10 +94,11 @@ pub fn cmd_bookmark_advance(
         .resolve_single_rev(ui, &to)
         .map_err(|error| {
             if args.to.is_none() {
-                error.hinted(
+                error.hinted(tr!(
+                    "bookmark.advance.default-target",
                     "`revsets.bookmark-advance-to` controls the default target. You can also \
-                     specify a specific target with `--to`.",
-                )
+                     specify a specific target with `--to`."
+                ))
             } else {
                 error
             }
@@ -171,9 +173,10 @@ pub fn cmd_bookmark_advance(
             Ok(!is_ff)
         },
     )? {
-        return Err(user_error(format!(
+        return Err(user_error(tr!(
+            "bookmark.advance.backwards",
             "Refusing to advance bookmark backwards or sideways: {name}",
-            name = name.as_symbol()
+            name = name.as_symbol(),
         )));
     }
     if target_commit.is_discardable(repo.as_ref())? {
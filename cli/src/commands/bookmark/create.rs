@@ -24,6 +24,7 @@ use crate::cli_util::has_tracked_remote_bookmarks;
 use crate::command_error::CommandError;
 use crate::command_error::user_error;
 use crate::complete;
+use crate::i18n::tr;
 use crate::revset_util;
 use crate::ui::Ui;
 
@@ -58,21 +59,27 @@ pub fn cmd_bookmark_create(
     let bookmark_names = &args.names;
     for name in bookmark_names {
         if view.get_local_bookmark(name).is_present() {
-            return Err(user_error(format!(
+            return Err(user_error(tr!(
+                "bookmark.create.already-exists",
                 "Bookmark already exists: {name}",
-                name = name.as_symbol()
+                name = name.as_symbol(),
             ))
-            .hinted("Use `jj bookmark set` to update it."));
+            .hinted(tr!(
+                "bookmark.create.already-exists-hint",
+                "Use `jj bookmark set` to update it."
+            )));
         }
         if has_tracked_remote_bookmarks(repo, name) {
-            return Err(user_error(format!(
+            return Err(user_error(tr!(
+                "bookmark.create.tracked-remotes-exist",
                 "Tracked remote bookmarks exist for deleted bookmark: {name}",
-                name = name.as_symbol()
+                name = name.as_symbol(),
             ))
-            .hinted(format!(
+            .hinted(tr!(
+                "bookmark.create.tracked-remotes-exist-hint",
                 "Use `jj bookmark set` to recreate the local bookmark. Run `jj bookmark untrack \
                  {name}` to disassociate them.",
-                name = name.as_symbol()
+                name = name.as_symbol(),
             )));
         }
     }
@@ -12,25 +12,43 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::collections::HashMap;
 use std::collections::HashSet;
 use std::rc::Rc;
+use std::slice;
 
 use clap_complete::ArgValueCandidates;
 use itertools::Itertools as _;
+use jj_lib::backend::CommitId;
+use jj_lib::op_store::RemoteRefState;
+use jj_lib::op_walk;
+use jj_lib::operation::Operation;
+use jj_lib::ref_name::RefName;
+use jj_lib::ref_name::RemoteName;
 use jj_lib::repo::Repo as _;
+use jj_lib::revset::RemoteRefSymbolExpression;
 use jj_lib::revset::RevsetExpression;
 use jj_lib::str_util::StringExpression;
+use pollster::FutureExt as _;
 
 use super::warn_unmatched_local_or_remote_bookmarks;
 use crate::cli_util::CommandHelper;
 use crate::cli_util::RevisionArg;
+use crate::cli_util::WorkspaceCommandHelper;
 use crate::cli_util::default_ignored_remote_name;
+use crate::cli_util::load_bookmark_list_filter_aliases;
+use crate::cli_util::short_operation_hash;
 use crate::command_error::CommandError;
+use crate::command_error::config_error_with_message;
 use crate::commit_ref_list;
 use crate::commit_ref_list::RefFilterPredicates;
+use crate::commit_ref_list::RefListEntry;
 use crate::commit_ref_list::SortKey;
 use crate::commit_templater::CommitRef;
 use crate::complete;
+use crate::formatter::Formatter;
+use crate::formatter::FormatterExt as _;
+use crate::ref_filter;
 use crate::revset_util::parse_union_name_patterns;
 use crate::templater::TemplateRenderer;
 use crate::ui::Ui;
@@ -43,6 +61,9 @@ use crate::ui::Ui;
 /// revisions are preceded by a "-" and new target revisions are preceded by a
 /// "+".
 ///
+/// If no bookmarks are selected by the arguments, the bookmarks selected by the
+/// `ui.bookmark-list-filter` setting are listed.
+///
 /// See [`jj help -k bookmarks`] for more information.
 ///
 /// [`jj help -k bookmarks`]:
@@ -85,16 +106,42 @@ pub struct BookmarkListArgs {
     ///
     /// [string pattern syntax]:
     ///     https://docs.jj-vcs.dev/latest/revsets/#string-patterns
-    #[arg(add = ArgValueCandidates::new(complete::bookmarks))]
+    #[arg(add = ArgValueCandidates::new(complete::bookmark_patterns))]
     names: Option<Vec<String>>,
 
+    /// Show bookmarks whose local name starts with the given prefix
+    ///
+    /// Bookmark names containing `/` form namespaces. For example, `--prefix
+    /// dev/` shows the bookmarks in the `dev/` namespace.
+    #[arg(long, value_name = "PREFIX")]
+    #[arg(add = ArgValueCandidates::new(complete::bookmark_namespaces))]
+    prefix: Option<String>,
+
+    /// Show a single line with the number of bookmarks for each namespace
+    ///
+    /// Only the namespaces directly below `--prefix` (or the top-level
+    /// namespaces) are shown. Bookmarks not in any of these namespaces are
+    /// listed as usual.
+    #[arg(long)]
+    collapse: bool,
+
     /// Show bookmarks whose local targets are in the given revisions
     ///
     /// Note that `-r deleted_bookmark` will not work since `deleted_bookmark`
-    /// wouldn't have a local target.
+    /// wouldn't have a local target. Use `--points-to` to match the targets
+    /// of remote bookmarks too.
     #[arg(long, short, value_name = "REVSETS")]
     revisions: Option<Vec<RevisionArg>>,
 
+    /// Show bookmarks whose local or tracked remote targets are in the given
+    /// revisions
+    ///
+    /// Unlike `--revisions`, this also matches deleted bookmarks and bookmarks
+    /// which were moved locally if one of their tracked remote bookmarks still
+    /// points to the given revisions.
+    #[arg(long, value_name = "REVSETS")]
+    points_to: Option<Vec<RevisionArg>>,
+
     /// Render each bookmark using the given template
     ///
     /// All 0-argument methods of the [`CommitRef` type] are available as
@@ -117,8 +164,36 @@ pub struct BookmarkListArgs {
     /// the most significant.
     ///
     /// This defaults to the `ui.bookmark-list-sort-keys` setting.
-    #[arg(long, value_name = "SORT_KEY", value_enum, value_delimiter = ',')]
+    #[arg(
+        long,
+        value_name = "SORT_KEY",
+        value_parser = commit_ref_list::bookmark_sort_key_parser(),
+        value_delimiter = ','
+    )]
     sort: Vec<SortKey>,
+
+    /// Limit number of bookmarks to show
+    ///
+    /// Applied after bookmarks are sorted and `--offset` bookmarks are skipped.
+    /// A bookmark and its tracked remote bookmarks count as one.
+    #[arg(long, short = 'n')]
+    limit: Option<usize>,
+
+    /// Skip the given number of bookmarks before showing any
+    ///
+    /// Applied after bookmarks are sorted. Combined with `--limit`, this can be
+    /// used to page through bookmarks.
+    #[arg(long, default_value_t = 0)]
+    offset: usize,
+
+    /// Show where each side of a conflicted bookmark came from
+    ///
+    /// For each target of a conflicted local bookmark, shows the most recent
+    /// operation in which the bookmark pointed to that target alone, and the
+    /// remote bookmarks pointing to it. The same information is available to
+    /// templates through `conflict_sides()`.
+    #[arg(long)]
+    show_conflict_detail: bool,
 }
 
 pub fn cmd_bookmark_list(
@@ -131,12 +206,12 @@ pub fn cmd_bookmark_list(
     let view = repo.view();
 
     // Like cmd_git_push(), names and revisions are OR-ed.
-    let name_expr = match (&args.names, &args.revisions) {
-        (Some(texts), _) => parse_union_name_patterns(ui, texts)?,
-        (None, Some(_)) => StringExpression::none(),
-        (None, None) => StringExpression::all(),
+    let name_expr = match &args.names {
+        Some(texts) => parse_union_name_patterns(ui, texts)?,
+        None if args.revisions.is_some() || args.points_to.is_some() => StringExpression::none(),
+        None => StringExpression::all(),
     };
-    let matched_local_targets: HashSet<_> = if let Some(revisions) = &args.revisions {
+    let mut matched_local_targets: HashSet<_> = if let Some(revisions) = &args.revisions {
         // Match against local targets only, which is consistent with "jj git push".
         let mut expression = workspace_command.parse_union_revsets(ui, revisions)?;
         // Intersects with the set of local bookmark targets to minimize the lookup
@@ -146,6 +221,23 @@ pub fn cmd_bookmark_list(
     } else {
         HashSet::new()
     };
+    let matched_tracked_remote_targets: HashSet<_> = if let Some(revisions) = &args.points_to {
+        let mut expression = workspace_command.parse_union_revsets(ui, revisions)?;
+        let tracked_remote_bookmarks = RevsetExpression::remote_bookmarks(
+            RemoteRefSymbolExpression {
+                name: StringExpression::all(),
+                remote: StringExpression::all(),
+            },
+            Some(RemoteRefState::Tracked),
+        );
+        expression.intersect_with(
+            &RevsetExpression::bookmarks(StringExpression::all()).union(&tracked_remote_bookmarks),
+        );
+        expression.evaluate_to_commit_ids()?.try_collect()?
+    } else {
+        HashSet::new()
+    };
+    matched_local_targets.extend(matched_tracked_remote_targets.iter().cloned());
 
     let template: TemplateRenderer<Rc<CommitRef>> = {
         let language = workspace_command.commit_template_language();
@@ -175,28 +267,99 @@ pub fn cmd_bookmark_list(
         name_matcher: name_expr.to_matcher(),
         remote_matcher: remote_expr.to_matcher(),
         matched_local_targets,
+        matched_tracked_remote_targets,
         conflicted: args.conflicted,
         include_local_only: !args.tracked && args.remotes.is_none(),
         include_synced_remotes: args.tracked || args.all_remotes || args.remotes.is_some(),
         include_untracked_remotes: !args.tracked && (args.all_remotes || args.remotes.is_some()),
     };
-    let mut bookmark_list_items = commit_ref_list::collect_items(view.bookmarks(), &predicates);
+    // The configured filter only applies if the bookmarks aren't filtered by
+    // the arguments.
+    let default_filter = if args.names.is_none()
+        && args.prefix.is_none()
+        && args.revisions.is_none()
+        && args.points_to.is_none()
+        && args.remotes.is_none()
+        && !args.all_remotes
+        && !args.tracked
+        && !args.conflicted
+    {
+        let config = workspace_command.settings().config();
+        let aliases_map = load_bookmark_list_filter_aliases(ui, config)?;
+        let text = workspace_command
+            .settings()
+            .get_string("ui.bookmark-list-filter")?;
+        let filter = ref_filter::parse(&text, &aliases_map)
+            .map_err(|err| config_error_with_message("Invalid `ui.bookmark-list-filter`", err))?;
+        Some(filter)
+    } else {
+        None
+    };
+    let bookmarks = view.bookmarks().filter(|(name, targets)| {
+        args.prefix
+            .as_ref()
+            .is_none_or(|prefix| name.as_str().starts_with(prefix.as_str()))
+            && default_filter
+                .as_ref()
+                .is_none_or(|filter| filter.matches(name, targets, ignored_tracked_remote))
+    });
+    let mut bookmark_list_items = commit_ref_list::collect_items(bookmarks, &predicates);
     let sort_keys = if args.sort.is_empty() {
         workspace_command.settings().get_value_with(
             "ui.bookmark-list-sort-keys",
-            commit_ref_list::parse_sort_keys,
+            commit_ref_list::parse_bookmark_sort_keys,
         )?
     } else {
         args.sort.clone()
     };
-    commit_ref_list::sort(repo.store(), &mut bookmark_list_items, &sort_keys)?;
+    commit_ref_list::sort(repo.as_ref(), &mut bookmark_list_items, &sort_keys)?;
+    let mut entries = if args.collapse {
+        let prefix = args.prefix.as_deref().unwrap_or_default();
+        commit_ref_list::collapse_namespaces(bookmark_list_items, prefix)
+    } else {
+        bookmark_list_items
+            .into_iter()
+            .map(RefListEntry::Item)
+            .collect()
+    };
+    commit_ref_list::paginate(&mut entries, args.offset, args.limit);
+    let bookmark_list_items = entries.iter().filter_map(RefListEntry::item).collect_vec();
+    let conflict_origins = if args.show_conflict_detail {
+        let conflicted_refs = bookmark_list_items
+            .iter()
+            .map(|item| item.primary.as_ref())
+            .filter(|commit_ref| commit_ref.is_local() && commit_ref.has_conflict());
+        find_conflict_origins(repo.operation(), conflicted_refs)?
+    } else {
+        HashMap::new()
+    };
 
     ui.request_pager();
     let mut formatter = ui.stdout_formatter();
-    bookmark_list_items
-        .iter()
-        .flat_map(|item| itertools::chain([&item.primary], &item.tracked))
-        .try_for_each(|commit_ref| template.format(commit_ref, formatter.as_mut()))?;
+    for entry in &entries {
+        let item = match entry {
+            RefListEntry::Item(item) => item,
+            RefListEntry::Namespace { name, count } => {
+                let mut formatter = formatter.labeled("bookmark_list");
+                let mut formatter = formatter.labeled("namespace");
+                write!(formatter.labeled("bookmark"), "{name}")?;
+                writeln!(formatter, " ({count} bookmarks)")?;
+                continue;
+            }
+        };
+        template.format(&item.primary, formatter.as_mut())?;
+        if args.show_conflict_detail && item.primary.is_local() && item.primary.has_conflict() {
+            write_conflict_detail(
+                formatter.as_mut(),
+                &workspace_command,
+                &item.primary,
+                &conflict_origins,
+            )?;
+        }
+        for commit_ref in &item.tracked {
+            template.format(commit_ref, formatter.as_mut())?;
+        }
+    }
     drop(formatter);
 
     warn_unmatched_local_or_remote_bookmarks(ui, view, &name_expr)?;
@@ -247,3 +410,82 @@ pub fn cmd_bookmark_list(
 
     Ok(())
 }
+
+/// Maximum number of operations to look through when searching for the
+/// origins of conflicted bookmark targets.
+const CONFLICT_ORIGIN_SEARCH_LIMIT: usize = 1000;
+
+/// Finds the most recent operation in which each local bookmark pointed to
+/// each side of its conflicted target alone.
+///
+/// Only the last [`CONFLICT_ORIGIN_SEARCH_LIMIT`] operations are visited, so
+/// sides set by older operations have no origin in the returned map.
+fn find_conflict_origins<'a>(
+    current_op: &Operation,
+    conflicted_refs: impl IntoIterator<Item = &'a CommitRef>,
+) -> Result<HashMap<(String, CommitId), Operation>, CommandError> {
+    let mut pending: HashSet<(String, CommitId)> = conflicted_refs
+        .into_iter()
+        .flat_map(|commit_ref| {
+            commit_ref
+                .conflict_sides()
+                .into_iter()
+                .map(|side| (commit_ref.name().to_owned(), side.target().clone()))
+        })
+        .collect();
+    let mut origins = HashMap::new();
+    if pending.is_empty() {
+        return Ok(origins);
+    }
+    for op in
+        op_walk::walk_ancestors(slice::from_ref(current_op)).take(CONFLICT_ORIGIN_SEARCH_LIMIT)
+    {
+        let op = op?;
+        let view = op.view().block_on()?;
+        pending.retain(|(name, id)| {
+            let found = view.get_local_bookmark(RefName::new(name)).as_normal() == Some(id);
+            if found {
+                origins.insert((name.clone(), id.clone()), op.clone());
+            }
+            !found
+        });
+        if pending.is_empty() {
+            break;
+        }
+    }
+    Ok(origins)
+}
+
+fn write_conflict_detail(
+    formatter: &mut dyn Formatter,
+    workspace_command: &WorkspaceCommandHelper,
+    commit_ref: &CommitRef,
+    origins: &HashMap<(String, CommitId), Operation>,
+) -> Result<(), CommandError> {
+    let store = workspace_command.repo().store();
+    let name = RefName::new(commit_ref.name());
+    for side in commit_ref.conflict_sides() {
+        let commit = store.get_commit(side.target())?;
+        let heading = if side.is_removed() {
+            "Removed target"
+        } else {
+            "Added target"
+        };
+        write!(formatter, "  {heading}: ")?;
+        workspace_command.write_commit_summary(formatter, &commit)?;
+        writeln!(formatter)?;
+        if let Some(op) = origins.get(&(name.as_str().to_owned(), side.target().clone())) {
+            let description = op.metadata().description.lines().next().unwrap_or_default();
+            writeln!(
+                formatter,
+                "    Set by operation {}: {description}",
+                short_operation_hash(op.id())
+            )?;
+        }
+        for remote in side.remotes() {
+            let symbol = name.to_remote_symbol(RemoteName::new(remote));
+            writeln!(formatter, "    Also the target of {symbol}")?;
+        }
+    }
+    Ok(())
+}
@@ -19,6 +19,7 @@ mod forget;
 mod list;
 mod r#move;
 mod rename;
+mod resolve;
 mod set;
 mod track;
 mod untrack;
@@ -52,6 +53,8 @@ use self::r#move::BookmarkMoveArgs;
 use self::r#move::cmd_bookmark_move;
 use self::rename::BookmarkRenameArgs;
 use self::rename::cmd_bookmark_rename;
+use self::resolve::BookmarkResolveArgs;
+use self::resolve::cmd_bookmark_resolve;
 use self::set::BookmarkSetArgs;
 use self::set::cmd_bookmark_set;
 use self::track::BookmarkTrackArgs;
@@ -88,6 +91,7 @@ pub enum BookmarkCommand {
     Move(BookmarkMoveArgs),
     #[command(visible_alias("r"))]
     Rename(BookmarkRenameArgs),
+    Resolve(BookmarkResolveArgs),
     #[command(visible_alias("s"))]
     Set(BookmarkSetArgs),
     #[command(visible_alias("t"))]
@@ -95,6 +99,24 @@ pub enum BookmarkCommand {
     Untrack(BookmarkUntrackArgs),
 }
 
+impl BookmarkCommand {
+    /// Returns true if the subcommand can preview its changes with
+    /// `--dry-run`.
+    pub(crate) fn supports_dry_run(&self) -> bool {
+        match self {
+            Self::Advance(_)
+            | Self::Create(_)
+            | Self::Delete(_)
+            | Self::Forget(_)
+            | Self::Move(_)
+            | Self::Rename(_)
+            | Self::Resolve(_)
+            | Self::Set(_) => true,
+            Self::List(_) | Self::Track(_) | Self::Untrack(_) => false,
+        }
+    }
+}
+
 pub fn cmd_bookmark(
     ui: &mut Ui,
     command: &CommandHelper,
@@ -108,6 +130,7 @@ pub fn cmd_bookmark(
         BookmarkCommand::List(args) => cmd_bookmark_list(ui, command, args),
         BookmarkCommand::Move(args) => cmd_bookmark_move(ui, command, args),
         BookmarkCommand::Rename(args) => cmd_bookmark_rename(ui, command, args),
+        BookmarkCommand::Resolve(args) => cmd_bookmark_resolve(ui, command, args),
         BookmarkCommand::Set(args) => cmd_bookmark_set(ui, command, args),
         BookmarkCommand::Track(args) => cmd_bookmark_track(ui, command, args),
         BookmarkCommand::Untrack(args) => cmd_bookmark_untrack(ui, command, args),
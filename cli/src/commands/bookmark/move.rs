@@ -28,6 +28,7 @@ use crate::cli_util::RevisionArg;
 use crate::command_error::CommandError;
 use crate::command_error::user_error;
 use crate::complete;
+use crate::i18n::tr;
 use crate::revset_util::parse_union_name_patterns;
 use crate::ui::Ui;
 
@@ -122,11 +123,15 @@ pub fn cmd_bookmark_move(
             },
         )?
     {
-        return Err(user_error(format!(
+        return Err(user_error(tr!(
+            "bookmark.move.backwards",
             "Refusing to move bookmark backwards or sideways: {name}",
-            name = name.as_symbol()
+            name = name.as_symbol(),
         ))
-        .hinted("Use --allow-backwards to allow it."));
+        .hinted(tr!(
+            "bookmark.move.backwards-hint",
+            "Use --allow-backwards to allow it."
+        )));
     }
     if target_commit.is_discardable(repo.as_ref())? {
         writeln!(ui.warning_default(), "Target revision is empty.")?;
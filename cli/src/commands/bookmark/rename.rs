@@ -27,6 +27,7 @@ use crate::cli_util::default_ignored_remote_name;
 use crate::command_error::CommandError;
 use crate::command_error::user_error;
 use crate::complete;
+use crate::i18n::tr;
 use crate::revset_util;
 use crate::ui::Ui;
 
@@ -55,17 +56,19 @@ pub fn cmd_bookmark_rename(
     let old_bookmark = &args.old;
     let ref_target = view.get_local_bookmark(old_bookmark).clone();
     if ref_target.is_absent() {
-        return Err(user_error(format!(
+        return Err(user_error(tr!(
+            "bookmark.rename.no-such-bookmark",
             "No such bookmark: {old_bookmark}",
-            old_bookmark = old_bookmark.as_symbol()
+            old_bookmark = old_bookmark.as_symbol(),
         )));
     }
 
     let new_bookmark = &args.new;
     if view.get_local_bookmark(new_bookmark).is_present() {
-        return Err(user_error(format!(
+        return Err(user_error(tr!(
+            "bookmark.rename.already-exists",
             "Bookmark already exists: {new_bookmark}",
-            new_bookmark = new_bookmark.as_symbol()
+            new_bookmark = new_bookmark.as_symbol(),
         )));
     }
 
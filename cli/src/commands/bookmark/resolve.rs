@@ -0,0 +1,281 @@
+// Copyright 2026 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::io::Write as _;
+
+use clap_complete::ArgValueCandidates;
+use itertools::Itertools as _;
+use jj_lib::backend::CommitId;
+use jj_lib::commit::Commit;
+use jj_lib::object_id::ObjectId as _;
+use jj_lib::op_store::RefTarget;
+use jj_lib::ref_name::RefNameBuf;
+use jj_lib::ref_name::RemoteName;
+use jj_lib::repo::Repo as _;
+use jj_lib::rewrite::merge_commit_trees;
+use pollster::FutureExt as _;
+
+use crate::cli_util::CommandHelper;
+use crate::cli_util::RevisionArg;
+use crate::cli_util::WorkspaceCommandHelper;
+use crate::command_error::CommandError;
+use crate::command_error::internal_error;
+use crate::command_error::user_error;
+use crate::complete;
+use crate::i18n::tr;
+use crate::revset_util;
+use crate::ui::Ui;
+
+/// Resolve a conflicted bookmark
+///
+/// A bookmark becomes conflicted when concurrent operations point it to
+/// different commits. This command resolves the conflict by keeping one of the
+/// targets, or by creating a merge commit of all targets, and records the
+/// resolution as a new operation. If one of the targets is a descendant of all
+/// the others, `--merge` moves the bookmark to it instead of creating a merge
+/// commit. Use `jj bookmark list --show-conflict-detail`
+/// to see where each target came from.
+///
+/// If neither `--keep` nor `--merge` is given, you will be prompted to choose
+/// one of the targets.
+#[derive(clap::Args, Clone, Debug)]
+pub struct BookmarkResolveArgs {
+    /// The target to keep
+    ///
+    /// This can be a revision, which must be one of the targets of the
+    /// bookmark, or the name of a remote, in which case the bookmark is
+    /// pointed to the target of the corresponding remote bookmark. If the
+    /// name of a remote is also a revision, use `<bookmark>@<remote>` to
+    /// select the remote bookmark.
+    #[arg(long, value_name = "REVSET|REMOTE", conflicts_with = "merge")]
+    keep: Option<String>,
+
+    /// Create a merge commit of all targets, and point the bookmark to it
+    #[arg(long)]
+    merge: bool,
+
+    /// The conflicted bookmark to resolve
+    #[arg(value_parser = revset_util::parse_bookmark_name)]
+    #[arg(add = ArgValueCandidates::new(complete::local_bookmarks))]
+    name: RefNameBuf,
+}
+
+pub fn cmd_bookmark_resolve(
+    ui: &mut Ui,
+    command: &CommandHelper,
+    args: &BookmarkResolveArgs,
+) -> Result<(), CommandError> {
+    let mut workspace_command = command.workspace_helper(ui)?;
+    let name = &args.name;
+    let old_target = workspace_command.repo().view().get_local_bookmark(name);
+    if old_target.is_absent() {
+        return Err(user_error(tr!(
+            "bookmark.resolve.no-such-bookmark",
+            "No such bookmark: {name}",
+            name = name.as_symbol(),
+        )));
+    }
+    if !old_target.has_conflict() {
+        return Err(user_error(tr!(
+            "bookmark.resolve.not-conflicted",
+            "Bookmark {name} is not conflicted",
+            name = name.as_symbol(),
+        )));
+    }
+    let added_ids: Vec<CommitId> = old_target.added_ids().cloned().collect();
+
+    let target_id = if args.merge {
+        None
+    } else if let Some(keep) = &args.keep {
+        Some(resolve_kept_target(
+            ui,
+            &workspace_command,
+            args,
+            keep,
+            &added_ids,
+        )?)
+    } else {
+        Some(choose_target(ui, &workspace_command, args, &added_ids)?)
+    };
+
+    let mut tx = workspace_command.start_transaction();
+    let mut merged = false;
+    let target_commit = if let Some(target_id) = target_id {
+        tx.repo().store().get_commit(&target_id)?
+    } else {
+        // Targets that are ancestors of other targets don't need to be merged.
+        let heads = tx
+            .repo()
+            .index()
+            .heads(&mut added_ids.iter())
+            .map_err(internal_error)?;
+        let parent_ids = added_ids
+            .iter()
+            .filter(|id| heads.contains(id))
+            .unique()
+            .cloned()
+            .collect_vec();
+        if let [head_id] = parent_ids.as_slice() {
+            tx.repo().store().get_commit(head_id)?
+        } else {
+            let parents: Vec<Commit> = parent_ids
+                .iter()
+                .map(|id| tx.repo().store().get_commit(id))
+                .try_collect()?;
+            let merged_tree = merge_commit_trees(tx.repo(), &parents).block_on()?;
+            merged = true;
+            tx.repo_mut()
+                .new_commit(parent_ids, merged_tree)
+                .write()
+                .block_on()?
+        }
+    };
+    tx.repo_mut()
+        .set_local_bookmark_target(name, RefTarget::normal(target_commit.id().clone()));
+
+    if let Some(mut formatter) = ui.status_formatter() {
+        if merged {
+            write!(formatter, "Created merge commit ")?;
+            tx.write_commit_summary(formatter.as_mut(), &target_commit)?;
+            writeln!(formatter)?;
+        }
+        write!(formatter, "Resolved bookmark {} to ", name.as_symbol())?;
+        tx.write_commit_summary(formatter.as_mut(), &target_commit)?;
+        writeln!(formatter)?;
+    }
+
+    tx.finish(
+        ui,
+        format!(
+            "resolve conflicted bookmark {name} to commit {id}",
+            name = name.as_symbol(),
+            id = target_commit.id().hex()
+        ),
+    )?;
+    Ok(())
+}
+
+/// Resolves the `--keep` argument to a commit id, which is either the target
+/// of the remote bookmark or one of the conflicted targets.
+fn resolve_kept_target(
+    ui: &Ui,
+    workspace_command: &WorkspaceCommandHelper,
+    args: &BookmarkResolveArgs,
+    keep: &str,
+    added_ids: &[CommitId],
+) -> Result<CommitId, CommandError> {
+    let name = &args.name;
+    let view = workspace_command.repo().view();
+    let explicit_remote = keep
+        .rsplit_once('@')
+        .filter(|(bookmark, _)| *bookmark == name.as_str())
+        .map(|(_, remote)| RemoteName::new(remote))
+        .filter(|remote| view.get_remote_view(remote).is_some());
+    let remote = explicit_remote.unwrap_or_else(|| RemoteName::new(keep));
+    if view.get_remote_view(remote).is_some() {
+        let keep_arg = RevisionArg::from(keep.to_owned());
+        if explicit_remote.is_none() && workspace_command.resolve_single_rev(ui, &keep_arg).is_ok()
+        {
+            return Err(user_error(tr!(
+                "bookmark.resolve.ambiguous-keep",
+                "{keep} is both a remote and a revision",
+                keep = keep,
+            ))
+            .hinted(tr!(
+                "bookmark.resolve.ambiguous-keep-hint",
+                "Use a commit or change ID to keep the revision, or `--keep {symbol}` to keep the \
+                 remote bookmark's target.",
+                symbol = name.to_remote_symbol(remote),
+            )));
+        }
+        let symbol = name.to_remote_symbol(remote);
+        let remote_target = &view.get_remote_bookmark(symbol).target;
+        return match remote_target.as_normal() {
+            Some(id) => Ok(id.clone()),
+            None if remote_target.is_absent() => Err(user_error(tr!(
+                "bookmark.resolve.no-such-remote-bookmark",
+                "No such remote bookmark: {symbol}",
+                symbol = symbol,
+            ))),
+            None => Err(user_error(tr!(
+                "bookmark.resolve.conflicted-remote-bookmark",
+                "Remote bookmark {symbol} is conflicted",
+                symbol = symbol,
+            ))),
+        };
+    }
+    let commit = workspace_command.resolve_single_rev(ui, &RevisionArg::from(keep.to_owned()))?;
+    if !added_ids.contains(commit.id()) {
+        return Err(user_error(tr!(
+            "bookmark.resolve.not-a-target",
+            "Revision {revision} is not a target of bookmark {name}",
+            revision = keep,
+            name = name.as_symbol(),
+        ))
+        .hinted(tr!(
+            "bookmark.resolve.not-a-target-hint",
+            "Use `jj bookmark set` to point the bookmark to another revision."
+        )));
+    }
+    Ok(commit.id().clone())
+}
+
+/// Prompts the user to choose one of the conflicted targets.
+fn choose_target(
+    ui: &Ui,
+    workspace_command: &WorkspaceCommandHelper,
+    args: &BookmarkResolveArgs,
+    added_ids: &[CommitId],
+) -> Result<CommitId, CommandError> {
+    writeln!(
+        ui.stderr(),
+        "Bookmark {} is conflicted, choose the target to keep:",
+        args.name.as_symbol()
+    )?;
+    let mut formatter = ui.stderr_formatter();
+    let template = workspace_command.commit_summary_template();
+    let mut choices: Vec<String> = vec![];
+    for (i, id) in added_ids.iter().enumerate() {
+        let commit = workspace_command.repo().store().get_commit(id)?;
+        write!(formatter, "{}: ", i + 1)?;
+        template.format(&commit, formatter.as_mut())?;
+        writeln!(formatter)?;
+        choices.push((i + 1).to_string());
+    }
+    writeln!(formatter, "q: quit the prompt")?;
+    choices.push("q".to_owned());
+    drop(formatter);
+
+    let index = ui
+        .prompt_choice(
+            "enter the index of the target you want to keep",
+            &choices,
+            None,
+        )
+        .map_err(|err| {
+            let err = CommandError::from(err);
+            if Ui::can_prompt() {
+                err
+            } else {
+                err.hinted(tr!(
+                    "bookmark.resolve.non-interactive-hint",
+                    "Use --keep or --merge to resolve the bookmark without prompting."
+                ))
+            }
+        })?;
+    added_ids
+        .get(index)
+        .cloned()
+        .ok_or_else(|| user_error("Bookmark resolution canceled"))
+}
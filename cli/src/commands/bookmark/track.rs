@@ -40,6 +40,12 @@ use crate::ui::Ui;
 /// A tracked remote bookmark will be imported as a local bookmark of the same
 /// name. Changes to it will propagate to the existing local bookmark on future
 /// pulls.
+///
+/// With `--all-matching-local`, every untracked remote bookmark whose name
+/// matches an existing local bookmark is tracked. This is useful after adding a
+/// new remote. If several remotes carry the same bookmark name at different
+/// commits, the bookmark is skipped so that tracking doesn't introduce a
+/// conflict. Use `--remote` to pick the remote explicitly in that case.
 #[derive(clap::Args, Clone, Debug)]
 pub struct BookmarkTrackArgs {
     /// Bookmark names to track
@@ -49,10 +55,18 @@ pub struct BookmarkTrackArgs {
     ///
     /// [string pattern syntax]:
     ///     https://docs.jj-vcs.dev/latest/revsets/#string-patterns
-    #[arg(required = true, value_name = "BOOKMARK")]
+    #[arg(
+        required_unless_present = "all_matching_local",
+        conflicts_with = "all_matching_local",
+        value_name = "BOOKMARK"
+    )]
     #[arg(add = ArgValueCandidates::new(complete::untracked_bookmarks))]
     names: Vec<String>,
 
+    /// Track remote bookmarks of the same name as existing local bookmarks
+    #[arg(long)]
+    all_matching_local: bool,
+
     /// Remote names to track
     ///
     /// By default, the specified pattern matches remote names with glob syntax.
@@ -77,7 +91,54 @@ pub fn cmd_bookmark_track(
     let mut workspace_command = command.workspace_helper(ui)?;
     let repo = workspace_command.repo().clone();
     let view = repo.view();
-    let matched_refs = if args.remotes.is_none() && args.names.iter().all(|s| s.contains('@')) {
+    let matched_refs = if args.all_matching_local {
+        let ignored_remote = default_ignored_remote_name(repo.store());
+        let remote_expr = match &args.remotes {
+            Some(text) => parse_union_name_patterns(ui, text)?,
+            None => StringExpression::all(),
+        };
+        let remote_matcher = remote_expr.to_matcher();
+        warn_unmatched_remotes(ui, view, &remote_expr)?;
+        let mut matched_refs = Vec::new();
+        for (name, bookmark_target) in view.bookmarks() {
+            if bookmark_target.local_target.is_absent() {
+                continue;
+            }
+            let candidates = bookmark_target
+                .remote_refs
+                .iter()
+                .filter(|&&(remote, remote_ref)| {
+                    ignored_remote != Some(remote)
+                        && remote_matcher.is_match(remote.as_str())
+                        && !remote_ref.is_tracked()
+                        && remote_ref.is_present()
+                })
+                .collect_vec();
+            if candidates
+                .iter()
+                .map(|(_, remote_ref)| &remote_ref.target)
+                .all_equal()
+            {
+                matched_refs.extend(
+                    candidates
+                        .into_iter()
+                        .map(|&(remote, remote_ref)| (name.to_remote_symbol(remote), remote_ref)),
+                );
+            } else {
+                writeln!(
+                    ui.warning_default(),
+                    "Skipping bookmark {name} because its remotes point to different commits: \
+                     {remotes}",
+                    name = name.as_symbol(),
+                    remotes = candidates
+                        .iter()
+                        .map(|(remote, _)| remote.as_symbol())
+                        .join(", ")
+                )?;
+            }
+        }
+        matched_refs
+    } else if args.remotes.is_none() && args.names.iter().all(|s| s.contains('@')) {
         // TODO: Delete in jj 0.43+
         writeln!(
             ui.warning_default(),
@@ -169,7 +230,7 @@ pub fn cmd_bookmark_track(
             let commit_ref = CommitRef::local(
                 name,
                 local_target.clone(),
-                bookmark_target.remote_refs.iter().map(|x| x.1),
+                bookmark_target.remote_refs.iter().copied(),
             );
             template.format(&commit_ref, formatter.as_mut())?;
 
@@ -14,6 +14,7 @@
 
 use clap_complete::ArgValueCandidates;
 use itertools::Itertools as _;
+use jj_lib::op_store::RemoteRef;
 use jj_lib::repo::Repo as _;
 use jj_lib::str_util::StringExpression;
 
@@ -64,6 +65,13 @@ pub struct BookmarkUntrackArgs {
     // TODO: Make this skip untracked remotes
     #[arg(add = ArgValueCandidates::new(complete::git_remotes))]
     remotes: Option<Vec<String>>,
+
+    /// Also forget the last-fetched state of the untracked remote bookmarks
+    ///
+    /// The remote bookmarks will be imported again on the next fetch if they
+    /// still exist on the remote.
+    #[arg(long)]
+    delete_remote_ref_state: bool,
 }
 
 pub fn cmd_bookmark_untrack(
@@ -125,7 +133,12 @@ pub fn cmd_bookmark_untrack(
     }
     let mut tx = workspace_command.start_transaction();
     for &symbol in &symbols {
-        tx.repo_mut().untrack_remote_bookmark(symbol);
+        if args.delete_remote_ref_state {
+            tx.repo_mut()
+                .set_remote_bookmark(symbol, RemoteRef::absent());
+        } else {
+            tx.repo_mut().untrack_remote_bookmark(symbol);
+        }
     }
     if !symbols.is_empty() {
         writeln!(
@@ -26,10 +26,12 @@ use crate::cli_util::CommandHelper;
 use crate::command_error::CommandError;
 use crate::command_error::user_error;
 use crate::complete;
+use crate::description_util::add_signoff_trailer;
 use crate::description_util::add_trailers;
 use crate::description_util::description_template;
 use crate::description_util::edit_description;
 use crate::description_util::join_message_paragraphs;
+use crate::description_util::should_signoff;
 use crate::text_util::parse_author;
 use crate::ui::Ui;
 
@@ -76,6 +78,12 @@ pub(crate) struct CommitArgs {
     #[arg(long)]
     editor: bool,
 
+    /// Add a `Signed-off-by` trailer for the committer to the description
+    ///
+    /// This can be enabled by default with the `commit.signoff` setting.
+    #[arg(long)]
+    signoff: bool,
+
     /// Put these paths in the current commit
     #[arg(value_name = "FILESETS", value_hint = clap::ValueHint::AnyPath)]
     #[arg(add = ArgValueCompleter::new(complete::modified_files))]
@@ -139,6 +147,7 @@ pub(crate) fn cmd_commit(
     let diff_selector =
         workspace_command.diff_selector(ui, args.tool.as_deref(), args.interactive)?;
     let text_editor = workspace_command.text_editor()?;
+    let signoff = should_signoff(workspace_command.settings(), args.signoff)?;
     let mut tx = workspace_command.start_transaction();
     let base_tree = commit.parent_tree(tx.repo())?;
     let format_instructions = || {
@@ -193,7 +202,13 @@ new working-copy commit.
     // to an empty description would break that logic.
     let description = if !description.is_empty() || use_editor {
         commit_builder.set_description(description);
-        add_trailers(ui, &tx, &commit_builder)?
+        let description = add_trailers(ui, &tx, &commit_builder)?;
+        if signoff {
+            commit_builder.set_description(description);
+            add_signoff_trailer(&commit_builder)
+        } else {
+            description
+        }
     } else {
         description
     };
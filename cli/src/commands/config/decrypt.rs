@@ -0,0 +1,48 @@
+// Copyright 2026 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::io::Write as _;
+
+use tracing::instrument;
+
+use super::ConfigLevelArgs;
+use crate::cli_util::CommandHelper;
+use crate::command_error::CommandError;
+use crate::command_error::user_error;
+use crate::ui::Ui;
+
+/// Decrypt the repo or workspace config file
+///
+/// Stores the config file encrypted by `jj config encrypt` in plain text
+/// again.
+#[derive(clap::Args, Clone, Debug)]
+pub struct ConfigDecryptArgs {
+    #[command(flatten)]
+    level: ConfigLevelArgs,
+}
+
+#[instrument(skip_all)]
+pub fn cmd_config_decrypt(
+    ui: &mut Ui,
+    command: &CommandHelper,
+    args: &ConfigDecryptArgs,
+) -> Result<(), CommandError> {
+    let source = args.level.get_secure_source_kind()?;
+    let path = command
+        .config_env()
+        .set_secure_config_encryption(source, None)?
+        .ok_or_else(|| user_error(format!("No {source} config path found")))?;
+    writeln!(ui.status(), "Decrypted config file {}", path.display())?;
+    Ok(())
+}
@@ -12,6 +12,9 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use jj_lib::config::ConfigFile;
+use jj_lib::config::ConfigFileEncoder;
+use jj_lib::config::ConfigFileSaveError;
 use jj_lib::config::ConfigLayer;
 use tracing::instrument;
 
@@ -19,12 +22,16 @@ use super::ConfigLevelArgs;
 use crate::cli_util::CommandHelper;
 use crate::command_error::CommandError;
 use crate::command_error::print_error_sources;
+use crate::description_util::TextEditor;
 use crate::ui::Ui;
 
 /// Start an editor on a jj config file.
 ///
 /// Creates the file if it doesn't already exist regardless of what the editor
 /// does.
+///
+/// An encrypted config file is decrypted to a temporary file for editing, and
+/// encrypted again when the editor exits.
 #[derive(clap::Args, Clone, Debug)]
 pub struct ConfigEditArgs {
     #[command(flatten)]
@@ -42,6 +49,9 @@ pub fn cmd_config_edit(
     if !file.path().exists() {
         file.save()?;
     }
+    if let Some(encoder) = file.encoder() {
+        return edit_encoded_config_file(ui, &editor, &file, encoder);
+    }
 
     // Editing again and again until either of these conditions is met
     // 1. The config is OK
@@ -74,3 +84,45 @@ pub fn cmd_config_edit(
     }
     Ok(())
 }
+
+fn edit_encoded_config_file(
+    ui: &Ui,
+    editor: &TextEditor,
+    file: &ConfigFile,
+    encoder: &dyn ConfigFileEncoder,
+) -> Result<(), CommandError> {
+    writeln!(
+        ui.status(),
+        "Editing encrypted file: {}",
+        file.path().display()
+    )?;
+    let mut text = file.layer().data.to_string();
+    loop {
+        text = editor.edit_str(&text, Some(".toml"))?;
+        match ConfigLayer::parse(file.layer().source, &text) {
+            Ok(_) => {
+                encoder
+                    .write_file(file.path(), &text)
+                    .map_err(ConfigFileSaveError)?;
+                break;
+            }
+            Err(e) => {
+                writeln!(
+                    ui.warning_default(),
+                    "An error has been found inside the config:"
+                )?;
+                print_error_sources(ui, Some(&e))?;
+                let continue_editing = ui.prompt_yes_no(
+                    "Do you want to keep editing the file? If not, previous config will be \
+                     restored.",
+                    Some(true),
+                )?;
+                if !continue_editing {
+                    // The file hasn't been written yet.
+                    break;
+                }
+            }
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,68 @@
+// Copyright 2026 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::io::Write as _;
+use std::path::PathBuf;
+
+use tracing::instrument;
+
+use super::ConfigLevelArgs;
+use crate::cli_util::CommandHelper;
+use crate::command_error::CommandError;
+use crate::command_error::user_error;
+use crate::ui::Ui;
+
+/// Encrypt the repo or workspace config file
+///
+/// The config file is encrypted with [age], and is decrypted transparently
+/// when the config is loaded or modified by `jj config set` or `jj config
+/// edit`. This is useful for storing credentials such as forge tokens in the
+/// repo config. The `age` command must be in the `PATH`.
+///
+/// The file is encrypted to the recipient of the given identity file. If the
+/// identity belongs to an age plugin, the key can be kept in a hardware token
+/// or the OS keychain.
+///
+/// [age]: https://age-encryption.org/
+#[derive(clap::Args, Clone, Debug)]
+pub struct ConfigEncryptArgs {
+    #[command(flatten)]
+    level: ConfigLevelArgs,
+
+    /// The age identity file to encrypt the config file with
+    #[arg(long, value_name = "FILE", value_hint = clap::ValueHint::FilePath)]
+    age_identity: PathBuf,
+}
+
+#[instrument(skip_all)]
+pub fn cmd_config_encrypt(
+    ui: &mut Ui,
+    command: &CommandHelper,
+    args: &ConfigEncryptArgs,
+) -> Result<(), CommandError> {
+    let source = args.level.get_secure_source_kind()?;
+    let identity = command.cwd().join(&args.age_identity);
+    if !identity.is_file() {
+        return Err(user_error(format!(
+            "Identity file {} doesn't exist",
+            identity.display()
+        )));
+    }
+    let path = command
+        .config_env()
+        .set_secure_config_encryption(source, Some(&identity))?
+        .ok_or_else(|| user_error(format!("No {source} config path found")))?;
+    writeln!(ui.status(), "Encrypted config file {}", path.display())?;
+    Ok(())
+}
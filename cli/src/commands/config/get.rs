@@ -19,9 +19,14 @@ use jj_lib::config::ConfigNamePathBuf;
 use jj_lib::config::ConfigValue;
 use tracing::instrument;
 
+use super::list::config_template_language;
 use crate::cli_util::CommandHelper;
 use crate::command_error::CommandError;
+use crate::command_error::internal_error;
 use crate::complete;
+use crate::config::AnnotatedValue;
+use crate::config::resolved_config_values;
+use crate::templater::TemplateRenderer;
 use crate::ui::Ui;
 
 /// Get the value of a given config option.
@@ -33,12 +38,29 @@ use crate::ui::Ui;
 /// user.name="Martin von Zweigbergk"
 /// $ jj config get user.name
 /// Martin von Zweigbergk
+///
+/// With `--resolve-origin`, every layer that defines the option is printed
+/// instead, from the lowest precedence to the highest. Values overridden by a
+/// later layer are commented out, and each line ends with the source of the
+/// value and the path to its config file if any. For example:
+///
+/// $ jj config get --resolve-origin user.name --config user.name=Martin
+/// # user.name = "Martin von Zweigbergk" # user /home/martin/.jjconfig.toml
+/// user.name = "Martin" # cli
 #[derive(clap::Args, Clone, Debug)]
 #[command(verbatim_doc_comment)]
 pub struct ConfigGetArgs {
     #[arg(required = true)]
     #[arg(add = ArgValueCandidates::new(complete::leaf_config_keys))]
     name: ConfigNamePathBuf,
+
+    /// Show which config layers define the value, including overridden ones
+    #[arg(long)]
+    resolve_origin: bool,
+
+    /// Print the origins as a JSON array (requires `--resolve-origin`)
+    #[arg(long, requires = "resolve_origin")]
+    json: bool,
 }
 
 #[instrument(skip_all)]
@@ -48,6 +70,9 @@ pub fn cmd_config_get(
     args: &ConfigGetArgs,
 ) -> Result<(), CommandError> {
     let value = command.settings().get_value(&args.name)?;
+    if args.resolve_origin {
+        return print_origins(ui, command, args);
+    }
     let stringified = match value {
         // Remove extra formatting from a string value
         ConfigValue::String(v) => v.into_value(),
@@ -62,3 +87,27 @@ pub fn cmd_config_get(
     writeln!(ui.stdout(), "{stringified}")?;
     Ok(())
 }
+
+fn print_origins(
+    ui: &mut Ui,
+    command: &CommandHelper,
+    args: &ConfigGetArgs,
+) -> Result<(), CommandError> {
+    let annotated_values = resolved_config_values(command.settings().config(), &args.name);
+    if args.json {
+        let json = serde_json::to_string(&annotated_values).map_err(internal_error)?;
+        writeln!(ui.stdout(), "{json}")?;
+        return Ok(());
+    }
+    let template: TemplateRenderer<AnnotatedValue> = {
+        let language = config_template_language(command.settings());
+        command
+            .parse_template(ui, &language, "builtin_config_list_detailed")?
+            .labeled(["config_list"])
+    };
+    let mut formatter = ui.stdout_formatter();
+    for annotated in &annotated_values {
+        template.format(annotated, formatter.as_mut())?;
+    }
+    Ok(())
+}
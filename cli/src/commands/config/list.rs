@@ -46,6 +46,15 @@ pub struct ConfigListArgs {
     #[arg(long)]
     pub include_overridden: bool,
 
+    /// Show where each value comes from.
+    ///
+    /// Each line ends with the source of the value and the path to its config
+    /// file if any. Repo and workspace config files are stored outside of the
+    /// repo, so the path points to that location. This is the same as
+    /// `-Tbuiltin_config_list_detailed`.
+    #[arg(long, conflicts_with = "template")]
+    pub origin: bool,
+
     #[command(flatten)]
     pub level: ConfigLevelArgs,
 
@@ -84,6 +93,7 @@ pub fn cmd_config_list(
         let language = config_template_language(command.settings());
         let text = match &args.template {
             Some(value) => value.to_owned(),
+            None if args.origin => "builtin_config_list_detailed".to_owned(),
             None => command.settings().get_string("templates.config_list")?,
         };
         command
@@ -122,13 +132,13 @@ pub fn cmd_config_list(
     Ok(())
 }
 
-type ConfigTemplateLanguage = GenericTemplateLanguage<'static, AnnotatedValue>;
+pub(super) type ConfigTemplateLanguage = GenericTemplateLanguage<'static, AnnotatedValue>;
 
 generic_templater::impl_self_property_wrapper!(AnnotatedValue);
 
 // AnnotatedValue will be cloned internally in the templater. If the cloning
 // cost matters, wrap it with Rc.
-fn config_template_language(settings: &UserSettings) -> ConfigTemplateLanguage {
+pub(super) fn config_template_language(settings: &UserSettings) -> ConfigTemplateLanguage {
     let mut language = ConfigTemplateLanguage::new(settings);
     language.add_keyword("name", |self_property| {
         let out_property = self_property.map(|annotated| annotated.name.to_string());
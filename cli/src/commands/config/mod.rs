@@ -12,7 +12,9 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+mod decrypt;
 mod edit;
+mod encrypt;
 mod get;
 mod list;
 mod path;
@@ -26,8 +28,12 @@ use jj_lib::config::ConfigFile;
 use jj_lib::config::ConfigSource;
 use tracing::instrument;
 
+use self::decrypt::ConfigDecryptArgs;
+use self::decrypt::cmd_config_decrypt;
 use self::edit::ConfigEditArgs;
 use self::edit::cmd_config_edit;
+use self::encrypt::ConfigEncryptArgs;
+use self::encrypt::cmd_config_encrypt;
 use self::get::ConfigGetArgs;
 use self::get::cmd_config_get;
 use self::list::ConfigListArgs;
@@ -73,6 +79,17 @@ impl ConfigLevelArgs {
         }
     }
 
+    /// Returns the source of the repo or workspace config, which can be
+    /// encrypted.
+    fn get_secure_source_kind(&self) -> Result<ConfigSource, CommandError> {
+        match self.get_source_kind() {
+            Some(source @ (ConfigSource::Repo | ConfigSource::Workspace)) => Ok(source),
+            _ => Err(user_error(
+                "Only the repo and workspace configs can be encrypted",
+            )),
+        }
+    }
+
     fn config_paths(&self, ui: &Ui, config_env: &ConfigEnv) -> Result<Vec<PathBuf>, CommandError> {
         if self.user {
             let paths = config_env
@@ -153,8 +170,10 @@ impl ConfigLevelArgs {
 ///     https://docs.jj-vcs.dev/latest/config/
 #[derive(clap::Subcommand, Clone, Debug)]
 pub(crate) enum ConfigCommand {
+    Decrypt(ConfigDecryptArgs),
     #[command(visible_alias("e"))]
     Edit(ConfigEditArgs),
+    Encrypt(ConfigEncryptArgs),
     #[command(visible_alias("g"))]
     Get(ConfigGetArgs),
     #[command(visible_alias("l"))]
@@ -174,7 +193,9 @@ pub(crate) fn cmd_config(
     subcommand: &ConfigCommand,
 ) -> Result<(), CommandError> {
     match subcommand {
+        ConfigCommand::Decrypt(args) => cmd_config_decrypt(ui, command, args),
         ConfigCommand::Edit(args) => cmd_config_edit(ui, command, args),
+        ConfigCommand::Encrypt(args) => cmd_config_encrypt(ui, command, args),
         ConfigCommand::Get(args) => cmd_config_get(ui, command, args),
         ConfigCommand::List(args) => cmd_config_list(ui, command, args),
         ConfigCommand::Path(args) => cmd_config_path(ui, command, args),
@@ -0,0 +1,204 @@
+// Copyright 2026 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::env;
+use std::io;
+use std::io::IsTerminal as _;
+use std::io::Write;
+
+use itertools::Itertools as _;
+use jj_lib::config::ConfigSource;
+use jj_lib::repo::Repo as _;
+use jj_lib::settings::UserSettings;
+
+use crate::cli_util::CommandHelper;
+use crate::command_error::CommandError;
+use crate::ui::Ui;
+
+/// Environment variables which affect jj, besides the `JJ_*` ones
+const ENV_VARS: &[&str] = &[
+    "COLORTERM",
+    "EDITOR",
+    "HOME",
+    "NO_COLOR",
+    "PAGER",
+    "TERM",
+    "VISUAL",
+    "XDG_CONFIG_HOME",
+];
+
+/// Show information about the environment jj is running in
+///
+/// This prints the jj version, the paths of the repo and the loaded config
+/// files, the storage backends, the relevant environment variables, the Git
+/// executable, and the terminal capabilities. It's meant to be attached to bug
+/// reports.
+#[derive(clap::Args, Clone, Debug)]
+pub struct DebugEnvArgs {
+    /// Replace the home directory, user name, and host name in the report
+    #[arg(long)]
+    redact: bool,
+}
+
+pub fn cmd_debug_env(
+    ui: &mut Ui,
+    command: &CommandHelper,
+    args: &DebugEnvArgs,
+) -> Result<(), CommandError> {
+    // The report is useful outside of a workspace too, so failing to load it
+    // isn't an error.
+    let workspace_command = command.workspace_helper_no_snapshot(ui);
+    let settings = match &workspace_command {
+        Ok(workspace_command) => workspace_command.settings(),
+        Err(_) => command.settings(),
+    };
+
+    let mut report: Vec<u8> = vec![];
+    writeln!(report, "{}", command.app().render_version().trim_end())?;
+    let features = [
+        cfg!(feature = "git").then_some("git"),
+        cfg!(feature = "watchman").then_some("watchman"),
+    ];
+    writeln!(report, "Features: {}", features.iter().flatten().join(", "))?;
+
+    writeln!(report, "\nWorkspace:")?;
+    match &workspace_command {
+        Ok(workspace_command) => {
+            let repo = workspace_command.repo();
+            writeln!(
+                report,
+                "  Root: {}",
+                workspace_command.workspace_root().display()
+            )?;
+            writeln!(
+                report,
+                "  Repo: {}",
+                workspace_command.repo_path().display()
+            )?;
+            writeln!(report, "  Backend: {}", repo.store().backend().name())?;
+            writeln!(report, "  Op store: {}", repo.op_store().name())?;
+            writeln!(report, "  Op heads store: {}", repo.op_heads_store().name())?;
+            writeln!(report, "  Index store: {}", repo.index_store().name())?;
+            writeln!(
+                report,
+                "  Working copy: {}",
+                workspace_command.working_copy().name()
+            )?;
+        }
+        Err(err) => {
+            let message = err.error.to_string();
+            writeln!(report, "  Not loaded: {}", message.trim_end())?;
+        }
+    }
+
+    writeln!(report, "\nConfig files:")?;
+    for layer in settings.config().layers() {
+        let Some(path) = &layer.path else {
+            continue;
+        };
+        write!(report, "  {}: {}", layer.source, path.display())?;
+        // Repo and workspace configs are stored in a directory named after
+        // their config ID.
+        if matches!(layer.source, ConfigSource::Repo | ConfigSource::Workspace)
+            && let Some(id) = path.parent().and_then(|dir| dir.file_name())
+        {
+            write!(report, " (config ID: {})", id.to_string_lossy())?;
+        }
+        writeln!(report)?;
+    }
+
+    writeln!(report, "\nEnvironment:")?;
+    let mut vars: Vec<(String, String)> = env::vars()
+        .filter(|(name, _)| name.starts_with("JJ_") || ENV_VARS.contains(&name.as_str()))
+        .collect();
+    vars.sort();
+    for (name, value) in vars {
+        writeln!(report, "  {name}={value}")?;
+    }
+
+    writeln!(report, "\nGit:")?;
+    write_git_info(&mut report, settings)?;
+
+    writeln!(report, "\nTerminal:")?;
+    let yes_no = |b: bool| if b { "yes" } else { "no" };
+    writeln!(
+        report,
+        "  Terminal stdin/stdout/stderr: {}/{}/{}",
+        yes_no(io::stdin().is_terminal()),
+        yes_no(io::stdout().is_terminal()),
+        yes_no(io::stderr().is_terminal()),
+    )?;
+    writeln!(report, "  Width: {}", ui.term_width())?;
+    writeln!(report, "  Color: {}", yes_no(ui.color()))?;
+
+    if args.redact {
+        let redacted = redact(&String::from_utf8_lossy(&report), command);
+        ui.stdout().write_all(redacted.as_bytes())?;
+    } else {
+        ui.stdout().write_all(&report)?;
+    }
+    Ok(())
+}
+
+#[cfg(feature = "git")]
+fn write_git_info(report: &mut impl Write, settings: &UserSettings) -> Result<(), CommandError> {
+    let git_settings = jj_lib::git::GitSettings::from_settings(settings)?;
+    let executable = &git_settings.executable_path;
+    writeln!(report, "  Executable: {}", executable.display())?;
+    match std::process::Command::new(executable)
+        .arg("--version")
+        .output()
+    {
+        Ok(output) if output.status.success() => {
+            let version = String::from_utf8_lossy(&output.stdout);
+            writeln!(report, "  Version: {}", version.trim_end())?;
+        }
+        Ok(output) => writeln!(report, "  Version: unknown ({})", output.status)?,
+        Err(err) => writeln!(report, "  Version: unknown ({err})")?,
+    }
+    Ok(())
+}
+
+#[cfg(not(feature = "git"))]
+fn write_git_info(report: &mut impl Write, _settings: &UserSettings) -> Result<(), CommandError> {
+    writeln!(report, "  Not supported by this build")?;
+    Ok(())
+}
+
+/// Replaces the home directory, user name, and host name in the report.
+fn redact(report: &str, command: &CommandHelper) -> String {
+    let mut redacted = report.to_owned();
+    if let Ok(home_dir) = etcetera::home_dir() {
+        let canonical_dir = dunce::canonicalize(&home_dir).unwrap_or_else(|_| home_dir.clone());
+        for dir in [canonical_dir, home_dir] {
+            let dir = dir.to_string_lossy();
+            if !dir.is_empty() {
+                redacted = redacted.replace(dir.as_ref(), "~");
+            }
+        }
+    }
+    let settings = command.settings();
+    let names = [
+        (whoami::hostname().ok(), "[HOST]"),
+        (Some(settings.operation_hostname().to_owned()), "[HOST]"),
+        (whoami::username().ok(), "[USER]"),
+        (Some(settings.operation_username().to_owned()), "[USER]"),
+    ];
+    for (name, replacement) in names {
+        if let Some(name) = name.filter(|name| !name.is_empty()) {
+            redacted = redacted.replace(&name, replacement);
+        }
+    }
+    redacted
+}
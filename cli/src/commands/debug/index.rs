@@ -12,10 +12,16 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::error::Error as _;
 use std::fmt::Debug;
 use std::io::Write as _;
+use std::iter;
 
+use clap::Subcommand;
+use itertools::Itertools as _;
+use jj_lib::default_index::DefaultIndexStore;
 use jj_lib::default_index::DefaultReadonlyIndex;
+use jj_lib::object_id::ObjectId as _;
 use pollster::FutureExt as _;
 
 use crate::cli_util::CommandHelper;
@@ -26,13 +32,37 @@ use crate::ui::Ui;
 
 /// Show commit index stats
 #[derive(clap::Args, Clone, Debug)]
-pub struct DebugIndexArgs {}
+#[command(args_conflicts_with_subcommands = true)]
+pub struct DebugIndexArgs {
+    #[command(subcommand)]
+    command: Option<DebugIndexCommand>,
+}
+
+#[derive(Subcommand, Clone, Debug)]
+pub enum DebugIndexCommand {
+    Verify(DebugIndexVerifyArgs),
+}
+
+/// Check the commit index against the commit store
+///
+/// Checks that the index files match their checksums, and that the parents and
+/// change id of every indexed commit match the commit in the store. The index
+/// isn't rebuilt even if it's corrupt.
+#[derive(clap::Args, Clone, Debug)]
+pub struct DebugIndexVerifyArgs {}
 
 pub fn cmd_debug_index(
     ui: &mut Ui,
     command: &CommandHelper,
-    _args: &DebugIndexArgs,
+    args: &DebugIndexArgs,
 ) -> Result<(), CommandError> {
+    match &args.command {
+        Some(DebugIndexCommand::Verify(args)) => cmd_debug_index_verify(ui, command, args),
+        None => cmd_debug_index_stats(ui, command),
+    }
+}
+
+fn cmd_debug_index_stats(ui: &mut Ui, command: &CommandHelper) -> Result<(), CommandError> {
     // Resolve the operation without loading the repo, so this command won't
     // update the index.
     let workspace = command.load_workspace()?;
@@ -88,3 +118,46 @@ pub fn cmd_debug_index(
     }
     Ok(())
 }
+
+fn cmd_debug_index_verify(
+    ui: &mut Ui,
+    command: &CommandHelper,
+    _args: &DebugIndexVerifyArgs,
+) -> Result<(), CommandError> {
+    // Resolve the operation without loading the repo, which would rebuild a
+    // corrupt index.
+    let workspace = command.load_workspace()?;
+    let repo_loader = workspace.repo_loader();
+    let op = command.resolve_operation(ui, repo_loader)?;
+    let index_store = repo_loader.index_store();
+    let Some(default_index_store) = index_store.downcast_ref::<DefaultIndexStore>() else {
+        return Err(user_error(format!(
+            "Cannot verify indexes of type '{}'",
+            index_store.name()
+        )));
+    };
+    let problems = default_index_store
+        .verify_index_at_operation(&op, repo_loader.store())
+        .block_on()
+        .map_err(internal_error)?;
+    for problem in &problems {
+        let causes = iter::successors(problem.source(), |&err| err.source());
+        writeln!(
+            ui.stdout(),
+            "{}",
+            iter::once(problem.to_string())
+                .chain(causes.map(|err| err.to_string()))
+                .join(": ")
+        )?;
+    }
+    if !problems.is_empty() {
+        return Err(user_error("The index is corrupt")
+            .hinted("Run `jj debug reindex` to rebuild the index."));
+    }
+    writeln!(
+        ui.status(),
+        "The index at operation {} is valid.",
+        op.id().hex()
+    )?;
+    Ok(())
+}
@@ -13,18 +13,23 @@
 // limitations under the License.
 
 mod copy_detection;
+mod env;
 mod fileset;
 mod index;
 mod index_changed_paths;
 mod init_simple;
 mod local_working_copy;
 mod object;
+mod op_store;
 mod reindex;
 mod revset;
 mod snapshot;
 mod stacked_table;
+#[cfg(feature = "git")]
+mod stats;
 mod template;
 mod tree;
+mod watch_events;
 mod watchman;
 mod working_copy;
 
@@ -34,6 +39,8 @@ use jj_lib::working_copy::WorkingCopy;
 
 use self::copy_detection::CopyDetectionArgs;
 use self::copy_detection::cmd_debug_copy_detection;
+use self::env::DebugEnvArgs;
+use self::env::cmd_debug_env;
 use self::fileset::DebugFilesetArgs;
 use self::fileset::cmd_debug_fileset;
 use self::index::DebugIndexArgs;
@@ -46,6 +53,8 @@ use self::local_working_copy::DebugLocalWorkingCopyArgs;
 use self::local_working_copy::cmd_debug_local_working_copy;
 use self::object::DebugObjectArgs;
 use self::object::cmd_debug_object;
+use self::op_store::DebugOpStoreCommand;
+use self::op_store::cmd_debug_op_store;
 use self::reindex::DebugReindexArgs;
 use self::reindex::cmd_debug_reindex;
 use self::revset::DebugRevsetArgs;
@@ -54,10 +63,16 @@ use self::snapshot::DebugSnapshotArgs;
 use self::snapshot::cmd_debug_snapshot;
 use self::stacked_table::DebugStackedTableArgs;
 use self::stacked_table::cmd_debug_stacked_table;
+#[cfg(feature = "git")]
+use self::stats::DebugStatsArgs;
+#[cfg(feature = "git")]
+use self::stats::cmd_debug_stats;
 use self::template::DebugTemplateArgs;
 use self::template::cmd_debug_template;
 use self::tree::DebugTreeArgs;
 use self::tree::cmd_debug_tree;
+use self::watch_events::DebugWatchEventsArgs;
+use self::watch_events::cmd_debug_watch_events;
 use self::watchman::DebugWatchmanCommand;
 use self::watchman::cmd_debug_watchman;
 use self::working_copy::DebugWorkingCopyArgs;
@@ -72,6 +87,7 @@ use crate::ui::Ui;
 #[command(hide = true)]
 pub enum DebugCommand {
     CopyDetection(CopyDetectionArgs),
+    Env(DebugEnvArgs),
     Fileset(DebugFilesetArgs),
     Index(DebugIndexArgs),
     IndexChangedPaths(DebugIndexChangedPathsArgs),
@@ -79,12 +95,17 @@ pub enum DebugCommand {
     LocalWorkingCopy(DebugLocalWorkingCopyArgs),
     #[command(subcommand)]
     Object(DebugObjectArgs),
+    #[command(subcommand)]
+    OpStore(DebugOpStoreCommand),
     Reindex(DebugReindexArgs),
     Revset(DebugRevsetArgs),
     Snapshot(DebugSnapshotArgs),
     StackedTable(DebugStackedTableArgs),
+    #[cfg(feature = "git")]
+    Stats(DebugStatsArgs),
     Template(DebugTemplateArgs),
     Tree(DebugTreeArgs),
+    WatchEvents(DebugWatchEventsArgs),
     #[command(subcommand)]
     Watchman(DebugWatchmanCommand),
     WorkingCopy(DebugWorkingCopyArgs),
@@ -97,18 +118,23 @@ pub fn cmd_debug(
 ) -> Result<(), CommandError> {
     match subcommand {
         DebugCommand::CopyDetection(args) => cmd_debug_copy_detection(ui, command, args),
+        DebugCommand::Env(args) => cmd_debug_env(ui, command, args),
         DebugCommand::Fileset(args) => cmd_debug_fileset(ui, command, args),
         DebugCommand::Index(args) => cmd_debug_index(ui, command, args),
         DebugCommand::IndexChangedPaths(args) => cmd_debug_index_changed_paths(ui, command, args),
         DebugCommand::InitSimple(args) => cmd_debug_init_simple(ui, command, args),
         DebugCommand::LocalWorkingCopy(args) => cmd_debug_local_working_copy(ui, command, args),
         DebugCommand::Object(args) => cmd_debug_object(ui, command, args),
+        DebugCommand::OpStore(args) => cmd_debug_op_store(ui, command, args),
         DebugCommand::Reindex(args) => cmd_debug_reindex(ui, command, args),
         DebugCommand::Revset(args) => cmd_debug_revset(ui, command, args),
         DebugCommand::Snapshot(args) => cmd_debug_snapshot(ui, command, args),
         DebugCommand::StackedTable(args) => cmd_debug_stacked_table(ui, command, args),
+        #[cfg(feature = "git")]
+        DebugCommand::Stats(args) => cmd_debug_stats(ui, command, args),
         DebugCommand::Template(args) => cmd_debug_template(ui, command, args),
         DebugCommand::Tree(args) => cmd_debug_tree(ui, command, args),
+        DebugCommand::WatchEvents(args) => cmd_debug_watch_events(ui, command, args),
         DebugCommand::Watchman(args) => cmd_debug_watchman(ui, command, args),
         DebugCommand::WorkingCopy(args) => cmd_debug_working_copy(ui, command, args),
     }
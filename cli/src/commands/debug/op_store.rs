@@ -0,0 +1,209 @@
+// Copyright 2026 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashSet;
+use std::collections::VecDeque;
+use std::io::Write as _;
+
+use clap::Subcommand;
+use itertools::Itertools as _;
+use jj_lib::backend::BackendError;
+use jj_lib::backend::CommitId;
+use jj_lib::object_id::ObjectId as _;
+use jj_lib::op_store::OpStoreError;
+use jj_lib::op_store::Operation;
+use jj_lib::op_store::OperationId;
+use jj_lib::op_store::View;
+use jj_lib::simple_op_heads_store::SimpleOpHeadsStore;
+use pollster::FutureExt as _;
+
+use crate::cli_util::CommandHelper;
+use crate::command_error::CommandError;
+use crate::command_error::user_error;
+use crate::ui::Ui;
+
+#[derive(Subcommand, Clone, Debug)]
+pub enum DebugOpStoreCommand {
+    Verify(DebugOpStoreVerifyArgs),
+}
+
+/// Check the integrity of the operation log
+///
+/// Walks all operations reachable from the current operation heads and
+/// checks that their parent operations, their views, and the commits
+/// referenced by the views can be read.
+///
+/// Exits with an error if any problem was found.
+#[derive(clap::Args, Clone, Debug)]
+pub struct DebugOpStoreVerifyArgs {
+    /// Quarantine broken operation heads
+    ///
+    /// An operation head is broken if the operation itself, one of its
+    /// parents, its view, or a commit referenced by the view is missing. Broken
+    /// heads are moved out of the set of heads into
+    /// `.jj/repo/op_heads/quarantine`, so the repo can be loaded at the
+    /// remaining heads. Problems in older operations are only reported.
+    #[arg(long)]
+    repair: bool,
+}
+
+pub fn cmd_debug_op_store(
+    ui: &mut Ui,
+    command: &CommandHelper,
+    subcommand: &DebugOpStoreCommand,
+) -> Result<(), CommandError> {
+    match subcommand {
+        DebugOpStoreCommand::Verify(args) => cmd_debug_op_store_verify(ui, command, args),
+    }
+}
+
+fn cmd_debug_op_store_verify(
+    ui: &mut Ui,
+    command: &CommandHelper,
+    args: &DebugOpStoreVerifyArgs,
+) -> Result<(), CommandError> {
+    // Don't load the repo, which would fail if an op head is broken.
+    let workspace = command.load_workspace()?;
+    let repo_loader = workspace.repo_loader();
+    let op_store = repo_loader.op_store();
+    let store = repo_loader.store();
+    let op_head_ids = repo_loader.op_heads_store().get_op_heads().block_on()?;
+
+    let mut problems: Vec<(OperationId, String)> = vec![];
+    let mut checked_commit_ids: HashSet<CommitId> = HashSet::new();
+    let mut visited_op_ids: HashSet<OperationId> = op_head_ids.iter().cloned().collect();
+    let mut queue: VecDeque<(OperationId, Operation)> = VecDeque::new();
+    for op_id in &op_head_ids {
+        match op_store.read_operation(op_id).block_on() {
+            Ok(operation) => queue.push_back((op_id.clone(), operation)),
+            Err(OpStoreError::ObjectNotFound { .. }) => {
+                problems.push((op_id.clone(), "operation is missing".to_owned()));
+            }
+            Err(err) => return Err(err.into()),
+        }
+    }
+    while let Some((op_id, operation)) = queue.pop_front() {
+        for parent_id in &operation.parents {
+            if !visited_op_ids.insert(parent_id.clone()) {
+                continue;
+            }
+            match op_store.read_operation(parent_id).block_on() {
+                Ok(parent) => queue.push_back((parent_id.clone(), parent)),
+                Err(OpStoreError::ObjectNotFound { .. }) => {
+                    let message = format!("parent operation {} is missing", parent_id.hex());
+                    problems.push((op_id.clone(), message));
+                }
+                Err(err) => return Err(err.into()),
+            }
+        }
+        let view = match op_store.read_view(&operation.view_id).block_on() {
+            Ok(view) => view,
+            Err(OpStoreError::ObjectNotFound { .. }) => {
+                let message = format!("view {} is missing", operation.view_id.hex());
+                problems.push((op_id, message));
+                continue;
+            }
+            Err(err) => return Err(err.into()),
+        };
+        for commit_id in view_commit_ids(&view) {
+            if !checked_commit_ids.insert(commit_id.clone()) {
+                continue;
+            }
+            match store.get_commit(commit_id) {
+                Ok(_) => {}
+                Err(BackendError::ObjectNotFound { .. }) => {
+                    let message =
+                        format!("commit {} referenced by view is missing", commit_id.hex());
+                    problems.push((op_id.clone(), message));
+                }
+                Err(err) => return Err(err.into()),
+            }
+        }
+    }
+
+    for (op_id, message) in &problems {
+        writeln!(ui.stdout(), "Operation {}: {message}", op_id.hex())?;
+    }
+    writeln!(
+        ui.status(),
+        "Checked {} operations and {} commits, found {} problems.",
+        visited_op_ids.len(),
+        checked_commit_ids.len(),
+        problems.len()
+    )?;
+    if problems.is_empty() {
+        return Ok(());
+    }
+
+    let broken_head_ids: HashSet<&OperationId> = problems
+        .iter()
+        .map(|(op_id, _)| op_id)
+        .filter(|op_id| op_head_ids.contains(op_id))
+        .collect();
+    if !args.repair {
+        let err = user_error("The operation log is corrupt");
+        if broken_head_ids.is_empty() {
+            return Err(err);
+        }
+        return Err(err.hinted("Use --repair to quarantine the broken operation heads."));
+    }
+    if broken_head_ids.is_empty() {
+        return Err(user_error(
+            "The operation log is corrupt, but no operation head can be quarantined",
+        ));
+    }
+    if broken_head_ids.len() == op_head_ids.len() {
+        return Err(user_error(
+            "All operation heads are broken, refusing to quarantine them",
+        ));
+    }
+    let op_heads_store = repo_loader.op_heads_store();
+    let Some(simple_op_heads_store) = op_heads_store.downcast_ref::<SimpleOpHeadsStore>() else {
+        return Err(user_error(format!(
+            "Cannot quarantine operation heads of type '{}'",
+            op_heads_store.name()
+        )));
+    };
+    for op_id in broken_head_ids.iter().sorted() {
+        simple_op_heads_store.quarantine_op_head(op_id)?;
+        writeln!(ui.status(), "Quarantined operation head {}", op_id.hex())?;
+    }
+    if problems
+        .iter()
+        .any(|(op_id, _)| !broken_head_ids.contains(op_id))
+    {
+        return Err(user_error(
+            "Some problems were found in operations that can't be quarantined",
+        ));
+    }
+    Ok(())
+}
+
+fn view_commit_ids(view: &View) -> impl Iterator<Item = &CommitId> {
+    let ref_targets = itertools::chain!(
+        view.local_bookmarks.values(),
+        view.local_tags.values(),
+        view.remote_views.values().flat_map(|remote_view| {
+            itertools::chain(remote_view.bookmarks.values(), remote_view.tags.values())
+                .map(|remote_ref| &remote_ref.target)
+        }),
+        view.git_refs.values(),
+        [&view.git_head],
+    );
+    itertools::chain!(
+        view.head_ids.iter().sorted(),
+        view.wc_commit_ids.values(),
+        ref_targets.flat_map(|target| itertools::chain(target.added_ids(), target.removed_ids())),
+    )
+}
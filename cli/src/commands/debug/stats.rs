@@ -0,0 +1,52 @@
+// Copyright 2026 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fmt::Debug;
+use std::io::Write as _;
+
+use jj_lib::git;
+use jj_lib::repo::Repo as _;
+
+use crate::cli_util::CommandHelper;
+use crate::command_error::CommandError;
+use crate::git_util::load_file_write_stats;
+use crate::ui::Ui;
+
+/// Show statistics about the files written to the Git backend
+///
+/// The counters accumulate across all commands run in the repo. Files whose
+/// blob already exists, e.g. because an identical file was snapshotted by
+/// another workspace, are not written again.
+#[derive(clap::Args, Clone, Debug)]
+pub struct DebugStatsArgs {}
+
+pub fn cmd_debug_stats(
+    ui: &mut Ui,
+    command: &CommandHelper,
+    _args: &DebugStatsArgs,
+) -> Result<(), CommandError> {
+    let workspace_command = command.workspace_helper(ui)?;
+    let store = workspace_command.repo().store();
+    git::get_git_backend(store)?;
+    let stats = load_file_write_stats(workspace_command.repo_path(), store)?;
+    writeln!(ui.stdout(), "Git file writes:")?;
+    writeln!(ui.stdout(), "  Written blobs: {}", stats.written_files)?;
+    writeln!(
+        ui.stdout(),
+        "  Reused blobs: {} ({} bytes)",
+        stats.reused_files,
+        stats.reused_bytes
+    )?;
+    Ok(())
+}
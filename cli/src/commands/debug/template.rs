@@ -14,24 +14,74 @@
 
 use std::fmt::Debug;
 use std::io::Write as _;
+use std::rc::Rc;
+
+use clap::ValueEnum;
+use jj_lib::commit::Commit;
+use jj_lib::operation::Operation;
 
 use crate::cli_util::CommandHelper;
 use crate::command_error::CommandError;
+use crate::commit_templater::CommitRef;
 use crate::template_parser;
+use crate::templater::TemplateRenderer;
 use crate::ui::Ui;
 
 /// Parse a template
+///
+/// With `--check`, the template is type-checked against the `--context` type
+/// instead of printing the parsed tree. Template aliases are expanded, and
+/// unknown keywords, functions, and methods are reported as errors. The
+/// template is never rendered.
 #[derive(clap::Args, Clone, Debug)]
 pub struct DebugTemplateArgs {
     template: String,
+    /// Type-check the template instead of printing the parsed tree
+    #[arg(long)]
+    check: bool,
+    /// Type of the object the template is checked against
+    #[arg(long, value_enum, default_value_t = TemplateContext::Commit, requires = "check")]
+    context: TemplateContext,
+}
+
+/// Type of the object a template is evaluated for.
+#[derive(Copy, Clone, PartialEq, Debug, ValueEnum)]
+enum TemplateContext {
+    /// Templates for commits, e.g. `templates.log`
+    Commit,
+    /// Templates for bookmarks and tags, e.g. `templates.bookmark_list`
+    CommitRef,
+    /// Templates for operations, e.g. `templates.op_log`
+    Operation,
 }
 
 pub fn cmd_debug_template(
     ui: &mut Ui,
-    _command: &CommandHelper,
+    command: &CommandHelper,
     args: &DebugTemplateArgs,
 ) -> Result<(), CommandError> {
-    let node = template_parser::parse_template(&args.template)?;
-    writeln!(ui.stdout(), "{node:#?}")?;
+    if !args.check {
+        let node = template_parser::parse_template(&args.template)?;
+        writeln!(ui.stdout(), "{node:#?}")?;
+        return Ok(());
+    }
+
+    let workspace_command = command.workspace_helper_no_snapshot(ui)?;
+    match args.context {
+        TemplateContext::Commit => {
+            let _template: TemplateRenderer<Commit> =
+                workspace_command.parse_commit_template(ui, &args.template)?;
+        }
+        TemplateContext::CommitRef => {
+            let language = workspace_command.commit_template_language();
+            let _template: TemplateRenderer<Rc<CommitRef>> =
+                workspace_command.parse_template(ui, &language, &args.template)?;
+        }
+        TemplateContext::Operation => {
+            let _template: TemplateRenderer<Operation> =
+                workspace_command.parse_operation_template(ui, &args.template)?;
+        }
+    }
+    writeln!(ui.status(), "Template is valid")?;
     Ok(())
 }
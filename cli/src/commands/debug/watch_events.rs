@@ -0,0 +1,138 @@
+// Copyright 2026 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fmt::Debug;
+use std::io::Write as _;
+use std::path::Path;
+use std::path::PathBuf;
+#[cfg(feature = "watchman")]
+use std::time::Duration;
+
+use jj_lib::backend::MillisSinceEpoch;
+use jj_lib::backend::Timestamp;
+use jj_lib::fsmonitor::FsmonitorEvent;
+use jj_lib::fsmonitor::FsmonitorEventSource;
+use jj_lib::fsmonitor::FsmonitorSettings;
+use jj_lib::fsmonitor::InMemoryFsmonitor;
+#[cfg(feature = "watchman")]
+use jj_lib::fsmonitor::watchman;
+#[cfg(feature = "watchman")]
+use pollster::FutureExt as _;
+
+use crate::cli_util::CommandHelper;
+use crate::command_error::CommandError;
+use crate::command_error::internal_error;
+use crate::command_error::user_error;
+use crate::command_error::user_error_with_message;
+use crate::time_util::format_absolute_timestamp;
+use crate::ui::Ui;
+
+/// Print raw change events reported by the filesystem monitor
+///
+/// Each event is printed with the time it was reported and the path relative
+/// to the workspace root. Events are printed until interrupted, or until
+/// `--limit` events have been printed.
+#[derive(clap::Args, Clone, Debug)]
+pub struct DebugWatchEventsArgs {
+    /// Stop after printing this many events
+    #[arg(long, short = 'n')]
+    limit: Option<usize>,
+
+    /// Replay events from a file instead of the configured monitor
+    ///
+    /// Each line of the file holds a timestamp in milliseconds since the epoch
+    /// and a path, separated by a space.
+    #[arg(long, value_name = "PATH")]
+    replay: Option<PathBuf>,
+}
+
+pub fn cmd_debug_watch_events(
+    ui: &mut Ui,
+    command: &CommandHelper,
+    args: &DebugWatchEventsArgs,
+) -> Result<(), CommandError> {
+    let mut source: Box<dyn FsmonitorEventSource> = match &args.replay {
+        Some(path) => Box::new(load_replay(&command.cwd().join(path))?),
+        None => open_configured_monitor(ui, command)?,
+    };
+    let mut count = 0;
+    while let Some(events) = source
+        .next_events()
+        .map_err(|err| user_error_with_message("Failed to read filesystem monitor events", err))?
+    {
+        for event in events {
+            if args.limit.is_some_and(|limit| count >= limit) {
+                return Ok(());
+            }
+            let time = format_absolute_timestamp(&event.time).map_err(internal_error)?;
+            writeln!(ui.stdout(), "{time} {}", event.path.display())?;
+            count += 1;
+        }
+    }
+    Ok(())
+}
+
+fn load_replay(path: &Path) -> Result<InMemoryFsmonitor, CommandError> {
+    let text = std::fs::read_to_string(path).map_err(|err| {
+        user_error_with_message(format!("Failed to read {}", path.display()), err)
+    })?;
+    let events: Vec<_> = text
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(i, line)| {
+            let (millis, event_path) = line
+                .split_once(' ')
+                .and_then(|(millis, event_path)| Some((millis.parse().ok()?, event_path)))
+                .ok_or_else(|| user_error(format!("Invalid event on line {}: {line}", i + 1)))?;
+            Ok(FsmonitorEvent {
+                time: Timestamp {
+                    timestamp: MillisSinceEpoch(millis),
+                    tz_offset: 0,
+                },
+                path: PathBuf::from(event_path),
+            })
+        })
+        .collect::<Result<_, CommandError>>()?;
+    let mut monitor = InMemoryFsmonitor::new();
+    monitor.push_events(events);
+    Ok(monitor)
+}
+
+fn open_configured_monitor(
+    ui: &Ui,
+    command: &CommandHelper,
+) -> Result<Box<dyn FsmonitorEventSource>, CommandError> {
+    let workspace_command = command.workspace_helper_no_snapshot(ui)?;
+    match FsmonitorSettings::from_settings(workspace_command.settings())? {
+        #[cfg(feature = "watchman")]
+        FsmonitorSettings::Watchman(config) => {
+            let monitor = watchman::Fsmonitor::init(workspace_command.workspace_root(), &config)
+                .block_on()
+                .map_err(|err| user_error_with_message("Failed to initialize Watchman", err))?;
+            Ok(Box::new(watchman::WatchmanEventSource::new(
+                monitor,
+                Duration::from_millis(100),
+            )))
+        }
+        #[cfg(not(feature = "watchman"))]
+        FsmonitorSettings::Watchman(_) => Err(user_error(
+            "Cannot watch Watchman events because jj was not compiled with the `watchman` feature",
+        )),
+        FsmonitorSettings::Test { .. } | FsmonitorSettings::None => Err(user_error(
+            "No filesystem monitor is configured",
+        )
+        .hinted("Set `fsmonitor.backend` to enable one, or use `--replay` to replay events.")),
+    }
+}
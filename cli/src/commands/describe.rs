@@ -32,12 +32,14 @@ use crate::command_error::CommandError;
 use crate::command_error::user_error;
 use crate::complete;
 use crate::description_util::ParsedBulkEditMessage;
+use crate::description_util::add_signoff_trailer;
 use crate::description_util::add_trailers_with_template;
 use crate::description_util::description_template;
 use crate::description_util::edit_description;
 use crate::description_util::edit_multiple_descriptions;
 use crate::description_util::join_message_paragraphs;
 use crate::description_util::parse_trailers_template;
+use crate::description_util::should_signoff;
 use crate::text_util::complete_newline;
 use crate::text_util::parse_author;
 use crate::ui::Ui;
@@ -98,6 +100,12 @@ pub(crate) struct DescribeArgs {
     #[arg(long, hide = true, conflicts_with = "editor")]
     edit: bool,
 
+    /// Add a `Signed-off-by` trailer for the committer to the description
+    ///
+    /// This can be enabled by default with the `commit.signoff` setting.
+    #[arg(long)]
+    signoff: bool,
+
     // TODO: Delete in jj 0.40.0+
     /// Reset the author name, email, and timestamp
     ///
@@ -174,6 +182,7 @@ pub(crate) fn cmd_describe(
         return Ok(());
     }
     let text_editor = workspace_command.text_editor()?;
+    let signoff = should_signoff(workspace_command.settings(), args.signoff)?;
 
     let mut tx = workspace_command.start_transaction();
     let tx_description = match commits.as_slice() {
@@ -237,6 +246,16 @@ pub(crate) fn cmd_describe(
         }
     }
 
+    if signoff {
+        for commit_builder in &mut commit_builders {
+            // Same as above, don't add a trailer to an empty description.
+            if use_editor || !commit_builder.description().is_empty() {
+                let new_description = add_signoff_trailer(commit_builder);
+                commit_builder.set_description(new_description);
+            }
+        }
+    }
+
     if use_editor {
         let temp_commits: Vec<_> = iter::zip(&commits, &commit_builders)
             // Edit descriptions in topological order
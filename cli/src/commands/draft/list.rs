@@ -0,0 +1,53 @@
+// Copyright 2026 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use jj_lib::draft_store::DraftStore;
+use jj_lib::repo::Repo as _;
+use jj_lib::revset::RevsetExpression;
+
+use crate::cli_util::CommandHelper;
+use crate::command_error::CommandError;
+use crate::ui::Ui;
+
+/// List the visible revisions of draft changes
+#[derive(clap::Args, Clone, Debug)]
+pub struct DraftListArgs {}
+
+pub fn cmd_draft_list(
+    ui: &mut Ui,
+    command: &CommandHelper,
+    _args: &DraftListArgs,
+) -> Result<(), CommandError> {
+    let workspace_command = command.workspace_helper(ui)?;
+    let repo = workspace_command.repo();
+    let drafts = DraftStore::load(workspace_command.repo_path()).get_all()?;
+    let mut commit_ids = vec![];
+    for change_id in &drafts {
+        // Abandoned changes are still marked, but there's nothing to show.
+        if let Some(targets) = repo.resolve_change_id(change_id)? {
+            commit_ids.extend(targets.visible_with_offsets().map(|(_, id)| id.clone()));
+        }
+    }
+    let template = workspace_command.commit_summary_template();
+    ui.request_pager();
+    let mut formatter = ui.stdout_formatter();
+    for commit in workspace_command
+        .attach_revset_evaluator(RevsetExpression::commits(commit_ids))
+        .evaluate_to_commits()?
+    {
+        template.format(&commit?, formatter.as_mut())?;
+        writeln!(formatter)?;
+    }
+    Ok(())
+}
@@ -0,0 +1,76 @@
+// Copyright 2026 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+mod list;
+mod set;
+mod unset;
+
+use jj_lib::commit::Commit;
+use jj_lib::draft_store::DraftStore;
+
+use self::list::DraftListArgs;
+use self::list::cmd_draft_list;
+use self::set::DraftSetArgs;
+use self::set::cmd_draft_set;
+use self::unset::DraftUnsetArgs;
+use self::unset::cmd_draft_unset;
+use crate::cli_util::CommandHelper;
+use crate::cli_util::WorkspaceCommandHelper;
+use crate::command_error::CommandError;
+use crate::ui::Ui;
+
+/// Manage draft changes
+///
+/// A change marked as a draft is work in progress. `jj git push` and `jj
+/// gerrit upload` refuse to push draft changes unless `--allow-drafts` is
+/// passed.
+///
+/// The flag is attached to the change ID, so it's kept when the change is
+/// rewritten. It isn't rolled back by `jj undo`.
+#[derive(clap::Subcommand, Clone, Debug)]
+pub enum DraftCommand {
+    #[command(visible_alias("l"))]
+    List(DraftListArgs),
+    #[command(visible_alias("s"))]
+    Set(DraftSetArgs),
+    #[command(visible_alias("u"))]
+    Unset(DraftUnsetArgs),
+}
+
+pub fn cmd_draft(
+    ui: &mut Ui,
+    command: &CommandHelper,
+    subcommand: &DraftCommand,
+) -> Result<(), CommandError> {
+    match subcommand {
+        DraftCommand::List(args) => cmd_draft_list(ui, command, args),
+        DraftCommand::Set(args) => cmd_draft_set(ui, command, args),
+        DraftCommand::Unset(args) => cmd_draft_unset(ui, command, args),
+    }
+}
+
+/// Returns the `commits` which are marked as drafts.
+pub(crate) fn find_draft_commits<'a>(
+    workspace_command: &WorkspaceCommandHelper,
+    commits: impl IntoIterator<Item = &'a Commit>,
+) -> Result<Vec<&'a Commit>, CommandError> {
+    let drafts = DraftStore::load(workspace_command.repo_path()).get_all()?;
+    if drafts.is_empty() {
+        return Ok(vec![]);
+    }
+    Ok(commits
+        .into_iter()
+        .filter(|commit| drafts.contains(commit.change_id()))
+        .collect())
+}
@@ -0,0 +1,53 @@
+// Copyright 2026 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use clap_complete::ArgValueCompleter;
+use itertools::Itertools as _;
+use jj_lib::commit::Commit;
+use jj_lib::draft_store::DraftStore;
+
+use crate::cli_util::CommandHelper;
+use crate::cli_util::RevisionArg;
+use crate::command_error::CommandError;
+use crate::complete;
+use crate::ui::Ui;
+
+/// Mark changes as drafts
+#[derive(clap::Args, Clone, Debug)]
+pub struct DraftSetArgs {
+    /// The revisions to mark as drafts
+    #[arg(default_value = "@", value_name = "REVSETS")]
+    #[arg(add = ArgValueCompleter::new(complete::revset_expression_mutable))]
+    revisions: Vec<RevisionArg>,
+}
+
+pub fn cmd_draft_set(
+    ui: &mut Ui,
+    command: &CommandHelper,
+    args: &DraftSetArgs,
+) -> Result<(), CommandError> {
+    let workspace_command = command.workspace_helper(ui)?;
+    let commits: Vec<Commit> = workspace_command
+        .parse_union_revsets(ui, &args.revisions)?
+        .evaluate_to_commits()?
+        .try_collect()?;
+    let store = DraftStore::load(workspace_command.repo_path());
+    let count = store.add(commits.iter().map(|commit| commit.change_id()))?;
+    if count > 0 {
+        writeln!(ui.status(), "Marked {count} changes as drafts")?;
+    } else {
+        writeln!(ui.status(), "Nothing changed.")?;
+    }
+    Ok(())
+}
@@ -104,7 +104,7 @@ pub(crate) fn cmd_evolog(
     command: &CommandHelper,
     args: &EvologArgs,
 ) -> Result<(), CommandError> {
-    let workspace_command = command.workspace_helper(ui)?;
+    let workspace_command = command.read_only_workspace_helper(ui)?;
 
     let start_commit_ids: Vec<_> = workspace_command
         .parse_union_revsets(ui, &args.revisions)?
@@ -12,8 +12,14 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::collections::BTreeMap;
+
 use clap_complete::ArgValueCandidates;
 use clap_complete::ArgValueCompleter;
+use itertools::Itertools as _;
+use jj_lib::merge::MergedTreeValue;
+use jj_lib::merged_tree::MergedTree;
+use jj_lib::repo_path::RepoPathBuf;
 use tracing::instrument;
 
 use crate::cli_util::CommandHelper;
@@ -22,16 +28,27 @@ use crate::cli_util::print_unmatched_explicit_paths;
 use crate::command_error::CommandError;
 use crate::commit_templater::TreeEntry;
 use crate::complete;
+use crate::formatter::Formatter;
 use crate::templater::TemplateRenderer;
 use crate::ui::Ui;
 
 /// List files in a revision
+///
+/// If `--revision` is given more than once, the files in any of the revisions
+/// are listed, and each line starts with a column per revision telling which
+/// version of the file the revision has. The same letter means the same
+/// content, and `-` means the file doesn't exist in the revision. For
+/// example, `aab-` means that the first two revisions have the same version of
+/// the file, the third revision has another version, and the fourth revision
+/// doesn't have the file.
 #[derive(clap::Args, Clone, Debug)]
 pub(crate) struct FileListArgs {
     /// The revision to list files in
-    #[arg(long, short, default_value = "@", value_name = "REVSET")]
+    ///
+    /// Can be repeated to compare the files in several revisions.
+    #[arg(long = "revision", short, default_value = "@", value_name = "REVSET")]
     #[arg(add = ArgValueCompleter::new(complete::revset_expression_all))]
-    revision: RevisionArg,
+    revisions: Vec<RevisionArg>,
 
     /// Render each file entry using the given template
     ///
@@ -48,6 +65,20 @@ pub(crate) struct FileListArgs {
     #[arg(add = ArgValueCandidates::new(complete::template_aliases))]
     template: Option<String>,
 
+    /// Show the files as a tree of directories
+    ///
+    /// Each file entry is rendered with the `templates.file_list_tree`
+    /// template, which shows the file name by default.
+    #[arg(long)]
+    tree: bool,
+
+    /// Show the executable bit, size, and content ID of each file
+    ///
+    /// Each file entry is rendered with the `templates.file_list_long`
+    /// template.
+    #[arg(long, short, conflicts_with_all = ["template", "tree"])]
+    long: bool,
+
     /// Only list files matching these prefixes (instead of all files)
     #[arg(value_name = "FILESETS", value_hint = clap::ValueHint::AnyPath)]
     #[arg(add = ArgValueCompleter::new(complete::all_revision_files))]
@@ -61,14 +92,23 @@ pub(crate) fn cmd_file_list(
     args: &FileListArgs,
 ) -> Result<(), CommandError> {
     let workspace_command = command.workspace_helper(ui)?;
-    let commit = workspace_command.resolve_single_rev(ui, &args.revision)?;
-    let tree = commit.tree();
+    let trees: Vec<MergedTree> = args
+        .revisions
+        .iter()
+        .map(|revision| Ok(workspace_command.resolve_single_rev(ui, revision)?.tree()))
+        .try_collect::<_, _, CommandError>()?;
     let fileset_expression = workspace_command.parse_file_patterns(ui, &args.paths)?;
     let matcher = fileset_expression.to_matcher();
     let template: TemplateRenderer<TreeEntry> = {
         let language = workspace_command.commit_template_language();
         let text = match &args.template {
             Some(value) => value.to_owned(),
+            None if args.long => workspace_command
+                .settings()
+                .get("templates.file_list_long")?,
+            None if args.tree => workspace_command
+                .settings()
+                .get("templates.file_list_tree")?,
             None => workspace_command.settings().get("templates.file_list")?,
         };
         workspace_command
@@ -76,15 +116,139 @@ pub(crate) fn cmd_file_list(
             .labeled(["file_list"])
     };
 
+    // The value of each path in each revision
+    let mut entries: BTreeMap<RepoPathBuf, Vec<Option<MergedTreeValue>>> = BTreeMap::new();
+    for (index, tree) in trees.iter().enumerate() {
+        for (path, value) in tree.entries_matching(matcher.as_ref()) {
+            let values = entries
+                .entry(path)
+                .or_insert_with(|| vec![None; trees.len()]);
+            values[index] = Some(value?);
+        }
+    }
+    let show_versions = trees.len() > 1;
+    let entries = entries.into_iter().map(|(path, values)| {
+        let versions = show_versions.then(|| format_versions(&values));
+        let value = values.into_iter().flatten().next().unwrap();
+        (versions, TreeEntry { path, value })
+    });
+
     ui.request_pager();
     let mut formatter = ui.stdout_formatter();
-    for (path, value) in tree.entries_matching(matcher.as_ref()) {
-        let entry = TreeEntry {
-            path,
-            value: value?,
-        };
-        template.format(&entry, formatter.as_mut())?;
+    if args.tree {
+        let mut root = DirNode::default();
+        for (versions, entry) in entries {
+            root.insert(versions, entry);
+        }
+        let padding = show_versions.then(|| " ".repeat(trees.len()));
+        root.write(formatter.as_mut(), &template, padding.as_deref(), None)?;
+    } else {
+        for (versions, entry) in entries {
+            if let Some(versions) = &versions {
+                write!(formatter, "{versions} ")?;
+            }
+            template.format(&entry, formatter.as_mut())?;
+        }
     }
-    print_unmatched_explicit_paths(ui, &workspace_command, &fileset_expression, [&tree])?;
+    print_unmatched_explicit_paths(ui, &workspace_command, &fileset_expression, &trees)?;
     Ok(())
 }
+
+/// Returns a letter for each of the `values` which identifies its content, or
+/// `-` if the path is absent.
+fn format_versions(values: &[Option<MergedTreeValue>]) -> String {
+    let mut seen: Vec<&MergedTreeValue> = vec![];
+    values
+        .iter()
+        .map(|value| {
+            let Some(value) = value else {
+                return '-';
+            };
+            let index = seen.iter().position(|seen_value| *seen_value == value);
+            let index = index.unwrap_or_else(|| {
+                seen.push(value);
+                seen.len() - 1
+            });
+            // There are rarely this many versions, so they don't need to be
+            // distinguished.
+            u8::try_from(index)
+                .ok()
+                .filter(|&index| index < 26)
+                .map_or('?', |index| char::from(b'a' + index))
+        })
+        .collect()
+}
+
+/// Directory in the tree rendered by `--tree`.
+#[derive(Default)]
+struct DirNode {
+    /// Files and subdirectories in path order
+    children: Vec<(String, TreeNode)>,
+}
+
+enum TreeNode {
+    Dir(DirNode),
+    File(Option<String>, TreeEntry),
+}
+
+impl DirNode {
+    /// Inserts the `entry`. Entries must be inserted in path order.
+    fn insert(&mut self, versions: Option<String>, entry: TreeEntry) {
+        let mut dir = self;
+        let (dir_path, file_name) = entry.path.split().unwrap();
+        for component in dir_path.components() {
+            let name = component.as_internal_str();
+            let is_last_dir = matches!(
+                dir.children.last(),
+                Some((last_name, TreeNode::Dir(_))) if last_name == name
+            );
+            if !is_last_dir {
+                dir.children
+                    .push((name.to_owned(), TreeNode::Dir(Self::default())));
+            }
+            let Some((_, TreeNode::Dir(child))) = dir.children.last_mut() else {
+                unreachable!();
+            };
+            dir = child;
+        }
+        let file_name = file_name.as_internal_str().to_owned();
+        dir.children
+            .push((file_name, TreeNode::File(versions, entry)));
+    }
+
+    /// Writes the children of this directory. The `indent` is the guide lines
+    /// of the parent directories, or `None` at the top level.
+    fn write(
+        &self,
+        formatter: &mut dyn Formatter,
+        template: &TemplateRenderer<TreeEntry>,
+        padding: Option<&str>,
+        indent: Option<&str>,
+    ) -> Result<(), CommandError> {
+        for (index, (name, node)) in self.children.iter().enumerate() {
+            let is_last = index + 1 == self.children.len();
+            let (guide, child_indent) = match indent {
+                None => (String::new(), String::new()),
+                Some(indent) if is_last => (format!("{indent}└── "), format!("{indent}    ")),
+                Some(indent) => (format!("{indent}├── "), format!("{indent}│   ")),
+            };
+            match node {
+                TreeNode::Dir(dir) => {
+                    if let Some(padding) = padding {
+                        write!(formatter, "{padding} ")?;
+                    }
+                    writeln!(formatter, "{guide}{name}/")?;
+                    dir.write(formatter, template, padding, Some(&child_indent))?;
+                }
+                TreeNode::File(versions, entry) => {
+                    if let Some(versions) = versions {
+                        write!(formatter, "{versions} ")?;
+                    }
+                    write!(formatter, "{guide}")?;
+                    template.format(entry, formatter)?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
@@ -63,7 +63,9 @@ pub(crate) fn cmd_file_track(
         .parse_file_patterns(ui, &args.paths)?
         .to_matcher();
 
-    let mut options = workspace_command.snapshot_options_with_start_tracking_matcher(&matcher)?;
+    let ignore_matcher = workspace_command.ignore_matcher(ui)?;
+    let mut options = workspace_command
+        .snapshot_options_with_start_tracking_matcher(&matcher, &ignore_matcher)?;
     if args.include_ignored {
         options.force_tracking_matcher = &matcher;
     }
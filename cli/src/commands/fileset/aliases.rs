@@ -0,0 +1,56 @@
+// Copyright 2026 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::BTreeMap;
+use std::io::Write as _;
+
+use jj_lib::config::ConfigNamePathBuf;
+
+use crate::cli_util::CommandHelper;
+use crate::command_error::CommandError;
+use crate::config::FilesetAliasDefinition;
+use crate::config::load_alias_items;
+use crate::ui::Ui;
+
+/// List fileset aliases
+///
+/// Prints the aliases defined in the `fileset-aliases` config table with
+/// their descriptions. An alias can be documented by defining it as a table
+/// of `definition` and `description`. See `jj help -k filesets` for details.
+#[derive(clap::Args, Clone, Debug)]
+pub struct FilesetAliasesArgs {}
+
+pub fn cmd_fileset_aliases(
+    ui: &mut Ui,
+    command: &CommandHelper,
+    _args: &FilesetAliasesArgs,
+) -> Result<(), CommandError> {
+    let config = command.settings().config();
+    let table_name = ConfigNamePathBuf::from_iter(["fileset-aliases"]);
+    let mut aliases = BTreeMap::new();
+    load_alias_items(ui, config, &table_name, |_layer, decl, item| {
+        let defn = FilesetAliasDefinition::from_item(item)?;
+        aliases.insert(decl.to_owned(), defn);
+        Ok(())
+    })?;
+    ui.request_pager();
+    let mut formatter = ui.stdout_formatter();
+    for (decl, defn) in &aliases {
+        writeln!(formatter, "{decl} = {}", defn.definition)?;
+        for line in defn.description.iter().flat_map(|text| text.lines()) {
+            writeln!(formatter, "  {line}")?;
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,39 @@
+// Copyright 2026 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+mod aliases;
+
+use self::aliases::FilesetAliasesArgs;
+use self::aliases::cmd_fileset_aliases;
+use crate::cli_util::CommandHelper;
+use crate::command_error::CommandError;
+use crate::ui::Ui;
+
+/// Commands for working with filesets
+///
+/// See `jj help -k filesets` for the fileset language.
+#[derive(clap::Subcommand, Clone, Debug)]
+pub enum FilesetCommand {
+    Aliases(FilesetAliasesArgs),
+}
+
+pub fn cmd_fileset(
+    ui: &mut Ui,
+    command: &CommandHelper,
+    subcommand: &FilesetCommand,
+) -> Result<(), CommandError> {
+    match subcommand {
+        FilesetCommand::Aliases(args) => cmd_fileset_aliases(ui, command, args),
+    }
+}
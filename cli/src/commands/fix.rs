@@ -13,14 +13,24 @@
 // limitations under the License.
 
 use std::collections::HashMap;
+use std::fs;
+use std::fs::File;
+use std::io;
 use std::io::Write as _;
 use std::path::Path;
+use std::path::PathBuf;
 use std::process::Stdio;
+use std::time::SystemTime;
 
 use clap_complete::ArgValueCompleter;
 use itertools::Itertools as _;
 use jj_lib::backend::FileId;
 use jj_lib::commit::Commit;
+use jj_lib::content_hash::blake2b_hash;
+use jj_lib::file_util::IoResultExt as _;
+use jj_lib::file_util::PathError;
+use jj_lib::file_util::create_or_reuse_dir;
+use jj_lib::file_util::persist_content_addressed_temp_file;
 use jj_lib::fileset;
 use jj_lib::fileset::FilesetDiagnostics;
 use jj_lib::fileset::FilesetExpression;
@@ -29,13 +39,16 @@ use jj_lib::fix::FileToFix;
 use jj_lib::fix::FixError;
 use jj_lib::fix::ParallelFileFixer;
 use jj_lib::fix::fix_files;
+use jj_lib::hex_util::encode_hex;
 use jj_lib::matchers::Matcher;
+use jj_lib::object_id::ObjectId as _;
 use jj_lib::repo::Repo as _;
 use jj_lib::repo_path::RepoPathUiConverter;
 use jj_lib::revset::RevsetIteratorExt as _;
 use jj_lib::settings::UserSettings;
 use jj_lib::store::Store;
 use pollster::FutureExt as _;
+use tempfile::NamedTempFile;
 use tokio::io::AsyncReadExt as _;
 use tracing::instrument;
 
@@ -74,6 +87,15 @@ use crate::ui::Ui;
 /// reused. This means that tools used with `jj fix` must produce deterministic
 /// output.
 ///
+/// ### Caching
+///
+/// With `--incremental` (or the `fix.cache.enabled` setting), tool results are
+/// also cached across runs, keyed by the tool configuration and the file path
+/// and content. Re-running `jj fix` on a large stack will then only invoke the
+/// tools for files that changed since the last run. Since the version of a
+/// tool isn't part of the key, set `fix.tools.<name>.cache-key` to e.g. the
+/// tool version to invalidate cached results when the tool is upgraded.
+///
 /// ### Configuration
 ///
 /// See `jj help -k config` chapter `Code formatting and other file content
@@ -170,6 +192,16 @@ pub(crate) struct FixArgs {
     /// specified, all files in the repo will be fixed.
     #[arg(long)]
     include_unchanged_files: bool,
+
+    /// Reuse tool results cached by previous runs, and cache new results
+    ///
+    /// This is the default if `fix.cache.enabled` is set.
+    #[arg(long)]
+    incremental: bool,
+
+    /// Don't read or write the tool result cache
+    #[arg(long, conflicts_with = "incremental")]
+    no_cache: bool,
 }
 
 #[instrument(skip_all)]
@@ -194,6 +226,19 @@ pub(crate) fn cmd_fix(
     }
     .resolve()?;
     workspace_command.check_rewritable_expr(&target_expr)?;
+    let use_cache = !args.no_cache
+        && (args.incremental || workspace_command.settings().get_bool("fix.cache.enabled")?);
+    let cache = if use_cache {
+        let max_entries = workspace_command
+            .settings()
+            .get::<usize>("fix.cache.max-entries")?;
+        Some(FixCache::init(
+            workspace_command.repo_path().join("fix_cache"),
+            max_entries,
+        )?)
+    } else {
+        None
+    };
 
     let repo = workspace_command.repo();
 
@@ -221,6 +266,7 @@ pub(crate) fn cmd_fix(
             &workspace_root,
             &path_converter,
             &tools_config,
+            cache.as_ref(),
             store,
             file_to_fix,
         )
@@ -237,6 +283,9 @@ pub(crate) fn cmd_fix(
         &mut parallel_fixer,
     )
     .block_on()?;
+    if let Some(cache) = &cache {
+        cache.gc()?;
+    }
     writeln!(
         ui.status(),
         "Fixed {} commits of {} checked.",
@@ -256,6 +305,9 @@ pub(crate) fn cmd_fix(
 /// is simply skipped and we proceed to invoke the next tool (this is
 /// indistinguishable from succeeding with no changes).
 ///
+/// If a `cache` is given, the result is looked up there before invoking the
+/// tools, and recorded there if all tools succeeded.
+///
 /// TODO: Better error handling so we can tell the user what went wrong with
 /// each failed input.
 async fn fix_one_file(
@@ -263,15 +315,35 @@ async fn fix_one_file(
     workspace_root: &Path,
     path_converter: &RepoPathUiConverter,
     tools_config: &ToolsConfig,
+    cache: Option<&FixCache>,
     store: &Store,
     file_to_fix: &FileToFix,
 ) -> Result<Option<FileId>, FixError> {
-    let mut matching_tools = tools_config
+    let matching_tools = tools_config
         .tools
         .iter()
         .filter(|tool_config| tool_config.matcher.matches(&file_to_fix.repo_path))
-        .peekable();
-    if matching_tools.peek().is_some() {
+        .collect_vec();
+    if !matching_tools.is_empty() {
+        let cache_key = cache.map(|_| FixCache::key(&matching_tools, file_to_fix));
+        if let Some(cache) = cache
+            && let Some(key) = &cache_key
+            && let Some(cached) = cache.get(key)
+        {
+            match cached {
+                None => return Ok(None),
+                // The fixed content might have been garbage collected.
+                Some(file_id) => {
+                    if store
+                        .read_file(&file_to_fix.repo_path, &file_id)
+                        .await
+                        .is_ok()
+                    {
+                        return Ok(Some(file_id));
+                    }
+                }
+            }
+        }
         // The first matching tool gets its input from the committed file, and any
         // subsequent matching tool gets its input from the previous matching tool's
         // output.
@@ -280,29 +352,41 @@ async fn fix_one_file(
             .read_file(&file_to_fix.repo_path, &file_to_fix.file_id)
             .await?;
         read.read_to_end(&mut old_content).await?;
-        let new_content = matching_tools.fold(old_content.clone(), |prev_content, tool_config| {
+        let mut new_content = old_content.clone();
+        let mut all_succeeded = true;
+        for tool_config in &matching_tools {
             match run_tool(
                 ui,
                 workspace_root,
                 path_converter,
                 &tool_config.command,
                 file_to_fix,
-                &prev_content,
+                &new_content,
             ) {
-                Ok(next_content) => next_content,
+                Ok(next_content) => new_content = next_content,
                 // TODO: Because the stderr is passed through, this isn't always failing
                 // silently, but it should do something better will the exit code, tool
                 // name, etc.
-                Err(()) => prev_content,
+                Err(()) => all_succeeded = false,
             }
-        });
-        if new_content != old_content {
+        }
+        let new_file_id = if new_content != old_content {
             // TODO: send futures back over channel
-            let new_file_id = store
-                .write_file(&file_to_fix.repo_path, &mut new_content.as_slice())
-                .await?;
-            return Ok(Some(new_file_id));
+            Some(
+                store
+                    .write_file(&file_to_fix.repo_path, &mut new_content.as_slice())
+                    .await?,
+            )
+        } else {
+            None
+        };
+        if let Some(cache) = cache
+            && let Some(key) = &cache_key
+            && all_succeeded
+        {
+            cache.insert(key, new_file_id.as_ref())?;
         }
+        return Ok(new_file_id);
     }
     Ok(None)
 }
@@ -384,10 +468,93 @@ fn run_tool(
     }
 }
 
+/// On-disk cache of tool results.
+///
+/// Each entry is a file named by the hash of the tool configuration and the
+/// input file, which contains the id of the fixed file content, or nothing if
+/// the tools didn't change the content.
+struct FixCache {
+    dir: PathBuf,
+    max_entries: usize,
+}
+
+impl FixCache {
+    fn init(dir: PathBuf, max_entries: usize) -> Result<Self, PathError> {
+        create_or_reuse_dir(&dir).context(&dir)?;
+        Ok(Self { dir, max_entries })
+    }
+
+    /// Returns the cache key for running `tools` on `file_to_fix`.
+    fn key(tools: &[&ToolConfig], file_to_fix: &FileToFix) -> String {
+        let mut key = String::new();
+        for tool in tools {
+            key.push_str(&tool.cache_key);
+            key.push('\0');
+        }
+        key.push_str(file_to_fix.repo_path.as_internal_file_string());
+        key.push('\0');
+        key.push_str(&file_to_fix.file_id.hex());
+        encode_hex(&blake2b_hash(&key))
+    }
+
+    /// Looks up a cached result. Returns `Some(None)` if the tools were known
+    /// to leave the content unchanged.
+    fn get(&self, key: &str) -> Option<Option<FileId>> {
+        let path = self.dir.join(key);
+        let content = fs::read_to_string(&path).ok()?;
+        // Bump the modification time so recently used entries survive gc.
+        if let Ok(file) = File::options().append(true).open(&path) {
+            file.set_modified(SystemTime::now()).ok();
+        }
+        if content.is_empty() {
+            Some(None)
+        } else {
+            FileId::try_from_hex(content.trim()).map(Some)
+        }
+    }
+
+    fn insert(&self, key: &str, file_id: Option<&FileId>) -> io::Result<()> {
+        let mut temp_file = NamedTempFile::new_in(&self.dir)?;
+        if let Some(file_id) = file_id {
+            temp_file.write_all(file_id.hex().as_bytes())?;
+        }
+        persist_content_addressed_temp_file(temp_file, self.dir.join(key))?;
+        Ok(())
+    }
+
+    /// Removes the least recently used entries exceeding `max_entries`.
+    fn gc(&self) -> Result<(), PathError> {
+        let mut entries: Vec<(SystemTime, PathBuf)> = fs::read_dir(&self.dir)
+            .context(&self.dir)?
+            .filter_map(|entry| {
+                let entry = entry.ok()?;
+                let modified = entry.metadata().ok()?.modified().ok()?;
+                Some((modified, entry.path()))
+            })
+            .collect();
+        if entries.len() <= self.max_entries {
+            return Ok(());
+        }
+        entries.sort_unstable();
+        let num_to_remove = entries.len() - self.max_entries;
+        for (_, path) in entries.into_iter().take(num_to_remove) {
+            // Another process may have removed the same entry concurrently.
+            match fs::remove_file(&path) {
+                Ok(()) => {}
+                Err(err) if err.kind() == io::ErrorKind::NotFound => {}
+                Err(err) => return Err(err).context(&path),
+            }
+        }
+        Ok(())
+    }
+}
+
 /// Represents an entry in the `fix.tools` config table.
 struct ToolConfig {
     /// The command that will be run to fix a matching file.
     command: CommandNameAndArgs,
+    /// Identifies the tool configuration in the result cache.
+    cache_key: String,
     /// The matcher that determines if this tool matches a file.
     matcher: Box<dyn Matcher>,
     /// Whether the tool is enabled
@@ -411,12 +578,24 @@ struct RawToolConfig {
     patterns: Vec<String>,
     #[serde(default = "default_tool_enabled")]
     enabled: bool,
+    #[serde(default)]
+    cache_key: Option<String>,
 }
 
 fn default_tool_enabled() -> bool {
     true
 }
 
+/// Serializes the parts of the tool configuration which affect its output.
+fn tool_cache_key(tool: &RawToolConfig) -> String {
+    let (name, args) = tool.command.split_name_and_args();
+    let env = match &tool.command {
+        CommandNameAndArgs::Structured { env, .. } => env.iter().sorted().collect_vec(),
+        CommandNameAndArgs::String(_) | CommandNameAndArgs::Vec(_) => vec![],
+    };
+    format!("{name:?} {args:?} {env:?} {:?}", tool.cache_key)
+}
+
 /// Parses the `fix.tools` config table.
 ///
 /// Fails if any of the commands or patterns are obviously unusable, but does
@@ -441,8 +620,10 @@ fn get_tools_config(
                     .try_collect()?,
             );
             print_parse_diagnostics(ui, &format!("In `fix.tools.{name}`"), &diagnostics)?;
+            let cache_key = tool_cache_key(&tool);
             Ok(ToolConfig {
                 command: tool.command,
+                cache_key,
                 matcher: expression.to_matcher(),
                 enabled: tool.enabled,
             })
@@ -0,0 +1,193 @@
+// Copyright 2026 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use jj_lib::git;
+use jj_lib::git::GitFetch;
+use jj_lib::git::GitSettings;
+use jj_lib::ref_name::GitRefNameBuf;
+use jj_lib::ref_name::RemoteName;
+use jj_lib::repo::Repo as _;
+
+use crate::cli_util::CommandHelper;
+use crate::command_error::CommandError;
+use crate::command_error::user_error;
+use crate::commands::gerrit::upload::calculate_push_remote;
+use crate::git_util::GitSubprocessUi;
+use crate::git_util::load_git_import_options;
+use crate::i18n::tr;
+use crate::ui::Ui;
+
+/// Download a change from Gerrit
+///
+/// Fetches a patchset of a Gerrit change and adds its commit to the repo, so
+/// you can look at it or build on top of it. The commit keeps its `Change-Id`
+/// footer, so a new version of it uploaded with `jj gerrit upload` updates the
+/// same Gerrit change.
+#[derive(clap::Args, Clone, Debug)]
+pub struct DownloadArgs {
+    /// The change number, optionally followed by `/<patchset>`
+    ///
+    /// If no patchset is specified, the latest patchset is downloaded.
+    #[arg(value_name = "CHANGE[/PATCHSET]", value_parser = parse_change_spec)]
+    change: GerritChangeSpec,
+
+    /// The Gerrit remote to download from
+    ///
+    /// Can be configured with the `gerrit.default-remote` repository option as
+    /// well.
+    #[arg(long)]
+    remote: Option<String>,
+}
+
+/// A Gerrit change number, and optionally one of its patchsets.
+#[derive(Clone, Debug, Eq, PartialEq)]
+struct GerritChangeSpec {
+    change: u64,
+    patchset: Option<u64>,
+}
+
+impl GerritChangeSpec {
+    /// Returns the prefix of the change refs, e.g. `refs/changes/34/1234/`.
+    fn ref_prefix(&self) -> String {
+        format!("refs/changes/{:02}/{}/", self.change % 100, self.change)
+    }
+}
+
+fn parse_change_spec(s: &str) -> Result<GerritChangeSpec, String> {
+    let parse_number = |s: &str| s.parse::<u64>().ok().filter(|&n| n > 0);
+    let (change, patchset) = match s.split_once('/') {
+        Some((change, patchset)) => (change, Some(patchset)),
+        None => (s, None),
+    };
+    let invalid = || "Expected a change number, optionally followed by `/<patchset>`".to_owned();
+    Ok(GerritChangeSpec {
+        change: parse_number(change).ok_or_else(invalid)?,
+        patchset: patchset
+            .map(|patchset| parse_number(patchset).ok_or_else(invalid))
+            .transpose()?,
+    })
+}
+
+pub fn cmd_gerrit_download(
+    ui: &mut Ui,
+    command: &CommandHelper,
+    args: &DownloadArgs,
+) -> Result<(), CommandError> {
+    let mut workspace_command = command.workspace_helper(ui)?;
+    let remote = calculate_push_remote(
+        workspace_command.repo().store(),
+        command.settings(),
+        args.remote.as_deref(),
+    )?;
+    let remote_name = RemoteName::new(&remote);
+
+    let mut tx = workspace_command.start_transaction();
+    let git_settings = GitSettings::from_settings(tx.settings())?;
+    let remote_settings = tx.settings().remote_settings()?;
+    let import_options = load_git_import_options(ui, &git_settings, &remote_settings)?;
+    let mut git_fetch = GitFetch::new(
+        tx.repo_mut(),
+        git_settings.to_subprocess_options(),
+        &import_options,
+    )?;
+
+    let ref_prefix = args.change.ref_prefix();
+    let patchset = if let Some(patchset) = args.change.patchset {
+        patchset
+    } else {
+        // Besides the patchsets, there's a `meta` ref with the review data.
+        git_fetch
+            .list_remote_refs(remote_name, &format!("{ref_prefix}*"))?
+            .iter()
+            .filter_map(|(name, _)| name.as_str().strip_prefix(&ref_prefix)?.parse().ok())
+            .max()
+            .ok_or_else(|| {
+                user_error(tr!(
+                    "gerrit.download.no-such-change",
+                    "Change {change} was not found on remote '{remote}'",
+                    change = args.change.change,
+                    remote = remote,
+                ))
+            })?
+    };
+    let ref_name = GitRefNameBuf::from(format!("{ref_prefix}{patchset}"));
+    let commit_id = git_fetch
+        .fetch_commit_ref(remote_name, &ref_name, &mut GitSubprocessUi::new(ui))?
+        .ok_or_else(|| {
+            user_error(tr!(
+                "gerrit.download.no-such-patchset",
+                "Patchset {patchset} of change {change} was not found on remote '{remote}'",
+                patchset = patchset,
+                change = args.change.change,
+                remote = remote,
+            ))
+        })?;
+    drop(git_fetch);
+
+    let store = tx.repo().store().clone();
+    git::get_git_backend(&store)?.import_head_commits([&commit_id])?;
+    let commit = store.get_commit(&commit_id)?;
+    tx.repo_mut().add_head(&commit)?;
+
+    if let Some(mut formatter) = ui.status_formatter() {
+        write!(
+            formatter,
+            "Downloaded patchset {patchset} of change {change} as ",
+            change = args.change.change,
+        )?;
+        tx.write_commit_summary(formatter.as_mut(), &commit)?;
+        writeln!(formatter)?;
+    }
+    tx.finish(
+        ui,
+        format!(
+            "download patchset {patchset} of Gerrit change {change}",
+            change = args.change.change,
+        ),
+    )?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_change_spec() {
+        assert_eq!(
+            parse_change_spec("1234"),
+            Ok(GerritChangeSpec {
+                change: 1234,
+                patchset: None,
+            })
+        );
+        assert_eq!(
+            parse_change_spec("1234/5"),
+            Ok(GerritChangeSpec {
+                change: 1234,
+                patchset: Some(5),
+            })
+        );
+        assert!(parse_change_spec("").is_err());
+        assert!(parse_change_spec("0").is_err());
+        assert!(parse_change_spec("1234/").is_err());
+        assert!(parse_change_spec("1234/meta").is_err());
+        assert!(parse_change_spec("I1234").is_err());
+
+        let spec = parse_change_spec("5").unwrap();
+        assert_eq!(spec.ref_prefix(), "refs/changes/05/5/");
+        let spec = parse_change_spec("1234").unwrap();
+        assert_eq!(spec.ref_prefix(), "refs/changes/34/1234/");
+    }
+}
@@ -24,7 +24,17 @@ use crate::ui::Ui;
 /// Interact with Gerrit Code Review.
 #[derive(Subcommand, Clone, Debug)]
 pub enum GerritCommand {
-    Upload(gerrit::upload::UploadArgs),
+    Download(gerrit::download::DownloadArgs),
+    Status(gerrit::status::StatusArgs),
+    Upload(Box<gerrit::upload::UploadArgs>),
+}
+
+impl GerritCommand {
+    /// Returns true if the subcommand can preview its changes with
+    /// `--dry-run`.
+    pub(crate) fn supports_dry_run(&self) -> bool {
+        matches!(self, Self::Upload(_))
+    }
 }
 
 pub fn cmd_gerrit(
@@ -33,8 +43,13 @@ pub fn cmd_gerrit(
     subcommand: &GerritCommand,
 ) -> Result<(), CommandError> {
     match subcommand {
+        GerritCommand::Download(args) => gerrit::download::cmd_gerrit_download(ui, command, args),
+        GerritCommand::Status(args) => gerrit::status::cmd_gerrit_status(ui, command, args),
         GerritCommand::Upload(review) => gerrit::upload::cmd_gerrit_upload(ui, command, review),
     }
 }
 
+mod download;
+mod rest;
+mod status;
 mod upload;
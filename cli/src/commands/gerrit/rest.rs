@@ -0,0 +1,311 @@
+// Copyright 2026 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Minimal client of the Gerrit REST API.
+//!
+//! Requests are sent by the `curl` executable, so that the proxy and TLS
+//! settings of the system are respected without linking an HTTP stack.
+
+use std::env;
+use std::fmt::Write as _;
+use std::fs;
+use std::io::Write as _;
+use std::path::PathBuf;
+use std::process::Command;
+use std::process::Stdio;
+
+use jj_lib::config::ConfigGetResultExt as _;
+use jj_lib::settings::UserSettings;
+use serde::de::DeserializeOwned;
+
+use crate::command_error::CommandError;
+use crate::command_error::user_error;
+use crate::command_error::user_error_with_message;
+use crate::i18n::tr;
+
+/// Prefix of every JSON response, which prevents XSSI attacks.
+const MAGIC_PREFIX: &[u8] = b")]}'";
+
+/// Client of the REST API of a Gerrit server.
+#[derive(Debug)]
+pub struct GerritRestClient {
+    base_url: String,
+    credentials: Option<(String, String)>,
+}
+
+impl GerritRestClient {
+    /// Creates a client of the server at `base_url`.
+    ///
+    /// The credentials are loaded from `gerrit.http-user` and
+    /// `gerrit.http-password`, or from the entry of the host in the `.netrc`
+    /// file. If there are no credentials, the requests are anonymous.
+    pub fn new(settings: &UserSettings, base_url: &str) -> Result<Self, CommandError> {
+        let base_url = base_url.trim_end_matches('/').to_owned();
+        let user = settings.get_string("gerrit.http-user").optional()?;
+        let password = settings.get_string("gerrit.http-password").optional()?;
+        let credentials = match (user, password) {
+            (Some(user), Some(password)) => Some((user, password)),
+            (None, None) => url_host(&base_url).and_then(|host| {
+                let contents = fs::read_to_string(netrc_path()?).ok()?;
+                find_netrc_credentials(&contents, host)
+            }),
+            _ => {
+                return Err(user_error(tr!(
+                    "gerrit.rest.partial-credentials",
+                    "Both `gerrit.http-user` and `gerrit.http-password` must be set"
+                )));
+            }
+        };
+        Ok(Self {
+            base_url,
+            credentials,
+        })
+    }
+
+    /// Sends a GET request to the REST endpoint `path`, e.g. `changes/?q=..`,
+    /// and parses the JSON response.
+    pub fn get<T: DeserializeOwned>(&self, path: &str) -> Result<T, CommandError> {
+        // Authenticated requests are sent to the endpoints under `/a/`.
+        let url = if self.credentials.is_some() {
+            format!("{}/a/{path}", self.base_url)
+        } else {
+            format!("{}/{path}", self.base_url)
+        };
+        // The URL and the credentials are passed through stdin so they don't
+        // show up in the process list.
+        let mut config = format!("url = {}\n", quote_curl_config(&url));
+        if let Some((user, password)) = &self.credentials {
+            writeln!(
+                config,
+                "user = {}",
+                quote_curl_config(&format!("{user}:{password}"))
+            )
+            .unwrap();
+        }
+        let mut curl = Command::new("curl");
+        curl.args(["--silent", "--show-error", "--fail", "--location"])
+            .args(["--header", "Accept: application/json"])
+            .args(["--config", "-"])
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped());
+        tracing::info!(?curl, %url, "querying Gerrit REST API");
+        let failed = |err: Box<dyn std::error::Error + Send + Sync>| {
+            user_error_with_message(
+                tr!(
+                    "gerrit.rest.request-failed",
+                    "Failed to query Gerrit at {url}",
+                    url = url,
+                ),
+                err,
+            )
+        };
+        let mut child = curl.spawn().map_err(|err| failed(err.into()))?;
+        let mut stdin = child.stdin.take().unwrap();
+        stdin
+            .write_all(config.as_bytes())
+            .map_err(|err| failed(err.into()))?;
+        drop(stdin);
+        let output = child.wait_with_output().map_err(|err| failed(err.into()))?;
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            let message = stderr.trim();
+            let message = if message.is_empty() {
+                output.status.to_string()
+            } else {
+                message.to_owned()
+            };
+            return Err(failed(message.into()));
+        }
+        parse_response(&output.stdout).map_err(|err| failed(err.into()))
+    }
+}
+
+fn parse_response<T: DeserializeOwned>(body: &[u8]) -> serde_json::Result<T> {
+    let body = body.strip_prefix(MAGIC_PREFIX).unwrap_or(body);
+    serde_json::from_slice(body)
+}
+
+/// Percent-encodes `value` to be used in the query string of a URL.
+pub fn encode_query_value(value: &str) -> String {
+    let mut encoded = String::with_capacity(value.len());
+    for byte in value.bytes() {
+        if byte.is_ascii_alphanumeric() || b"-._~".contains(&byte) {
+            encoded.push(char::from(byte));
+        } else {
+            write!(encoded, "%{byte:02X}").unwrap();
+        }
+    }
+    encoded
+}
+
+/// Quotes `value` as a string of a curl config file. Line breaks are escaped
+/// so the value can't start a new option.
+fn quote_curl_config(value: &str) -> String {
+    let mut quoted = String::with_capacity(value.len() + 2);
+    quoted.push('"');
+    for c in value.chars() {
+        match c {
+            '\\' => quoted.push_str("\\\\"),
+            '"' => quoted.push_str("\\\""),
+            '\n' => quoted.push_str("\\n"),
+            '\r' => quoted.push_str("\\r"),
+            '\t' => quoted.push_str("\\t"),
+            _ => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+/// Returns the host name of the `url`, without user info and port.
+fn url_host(url: &str) -> Option<&str> {
+    let (_scheme, rest) = url.split_once("://")?;
+    let authority = rest.split(['/', '?', '#']).next()?;
+    let host_port = authority.rsplit_once('@').map_or(authority, |(_, h)| h);
+    let host = host_port.split(':').next()?;
+    (!host.is_empty()).then_some(host)
+}
+
+fn netrc_path() -> Option<PathBuf> {
+    if let Some(path) = env::var_os("NETRC") {
+        return Some(path.into());
+    }
+    let home_dir = etcetera::home_dir().ok()?;
+    let name = if cfg!(windows) { "_netrc" } else { ".netrc" };
+    Some(home_dir.join(name))
+}
+
+/// Looks up the login and password of the `host` in the contents of a
+/// `.netrc` file. The `default` entry is ignored, so the credentials are only
+/// sent to hosts listed explicitly.
+fn find_netrc_credentials(contents: &str, host: &str) -> Option<(String, String)> {
+    #[derive(Default)]
+    struct Entry<'a> {
+        login: Option<&'a str>,
+        password: Option<&'a str>,
+    }
+
+    let mut matched: Option<Entry> = None;
+    // Which entry the following tokens belong to
+    let mut current: Option<&mut Entry> = None;
+    let mut tokens = contents.split_whitespace();
+    while let Some(token) = tokens.next() {
+        match token {
+            "machine" => {
+                let name = tokens.next()?;
+                current = if name == host && matched.is_none() {
+                    Some(matched.insert(Entry::default()))
+                } else {
+                    None
+                };
+            }
+            "default" => current = None,
+            "login" => {
+                let value = tokens.next()?;
+                if let Some(entry) = &mut current {
+                    entry.login = Some(value);
+                }
+            }
+            "password" => {
+                let value = tokens.next()?;
+                if let Some(entry) = &mut current {
+                    entry.password = Some(value);
+                }
+            }
+            // Macro definitions run until an empty line, which can't be
+            // detected after splitting. They're rare in practice.
+            "macdef" => break,
+            _ => {}
+        }
+    }
+    let entry = matched?;
+    Some((entry.login?.to_owned(), entry.password?.to_owned()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_url_host() {
+        assert_eq!(
+            url_host("https://review.example.com"),
+            Some("review.example.com")
+        );
+        assert_eq!(
+            url_host("https://review.example.com:8443/gerrit"),
+            Some("review.example.com")
+        );
+        assert_eq!(
+            url_host("https://user@review.example.com/"),
+            Some("review.example.com")
+        );
+        assert_eq!(url_host("review.example.com"), None);
+        assert_eq!(url_host("https:///path"), None);
+    }
+
+    #[test]
+    fn test_find_netrc_credentials() {
+        let contents = "
+            machine other.example.com login other password secret0
+            machine review.example.com
+                login alice
+                password secret1
+            default login anonymous password secret2
+        ";
+        assert_eq!(
+            find_netrc_credentials(contents, "review.example.com"),
+            Some(("alice".to_owned(), "secret1".to_owned()))
+        );
+        assert_eq!(
+            find_netrc_credentials(contents, "unknown.example.com"),
+            None
+        );
+        assert_eq!(
+            find_netrc_credentials(
+                "machine review.example.com login alice",
+                "review.example.com"
+            ),
+            None
+        );
+        assert_eq!(find_netrc_credentials("", "review.example.com"), None);
+    }
+
+    #[test]
+    fn test_parse_response() {
+        let body = b")]}'\n[{\"_number\": 1}]";
+        let value: serde_json::Value = parse_response(body).unwrap();
+        assert_eq!(value, serde_json::json!([{"_number": 1}]));
+        let value: serde_json::Value = parse_response(b"[]").unwrap();
+        assert_eq!(value, serde_json::json!([]));
+        assert!(parse_response::<serde_json::Value>(b"<html>").is_err());
+    }
+
+    #[test]
+    fn test_quote_curl_config() {
+        assert_eq!(quote_curl_config("a:b"), r#""a:b""#);
+        assert_eq!(quote_curl_config(r#"a"b\c"#), r#""a\"b\\c""#);
+        assert_eq!(quote_curl_config("a\nuser = b"), r#""a\nuser = b""#);
+    }
+
+    #[test]
+    fn test_encode_query_value() {
+        assert_eq!(
+            encode_query_value("change:I0123 OR change:I4567"),
+            "change%3AI0123%20OR%20change%3AI4567"
+        );
+        assert_eq!(encode_query_value("a&o=b#c\n"), "a%26o%3Db%23c%0A");
+    }
+}
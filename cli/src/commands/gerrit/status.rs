@@ -0,0 +1,242 @@
+// Copyright 2026 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::BTreeMap;
+use std::collections::HashMap;
+
+use itertools::Itertools as _;
+use jj_lib::commit::Commit;
+use jj_lib::repo::Repo as _;
+use jj_lib::trailer::parse_description_trailers;
+
+use crate::cli_util::CommandHelper;
+use crate::cli_util::RevisionArg;
+use crate::command_error::CommandError;
+use crate::command_error::user_error;
+use crate::commands::gerrit::rest::GerritRestClient;
+use crate::commands::gerrit::rest::encode_query_value;
+use crate::commands::gerrit::upload::calculate_push_remote;
+use crate::commands::gerrit::upload::calculate_review_url;
+use crate::commit_templater::GerritStatusEntry;
+use crate::i18n::tr;
+use crate::ui::Ui;
+
+/// The revisions to show if no revisions are specified.
+const DEFAULT_REVISIONS: &str = "reachable(@, mutable())";
+
+/// Maximum number of changes to look up per request.
+const QUERY_CHUNK_SIZE: usize = 50;
+
+/// Show the review status of changes on Gerrit
+///
+/// Looks up the Gerrit change of each revision by its `Change-Id` or `Link`
+/// footer, and prints the status of the change (`NEW`, `MERGED`, or
+/// `ABANDONED`) and the votes on it. Revisions without these footers are
+/// skipped.
+///
+/// The Gerrit REST API at `gerrit.review-url` is queried with `curl`. To see
+/// private changes, set `gerrit.http-user` and `gerrit.http-password` to your
+/// Gerrit HTTP credentials, or add them to your `.netrc` file.
+///
+/// The output can be customized with the `templates.gerrit_status` template.
+#[derive(clap::Args, Clone, Debug)]
+pub struct StatusArgs {
+    /// The revisions to show the status of
+    ///
+    /// If no revisions are specified, this defaults to
+    /// `reachable(@, mutable())`.
+    #[arg(long, short, value_name = "REVSETS")]
+    revisions: Vec<RevisionArg>,
+
+    /// The Gerrit remote whose review URL is queried
+    ///
+    /// Can be configured with the `gerrit.default-remote` repository option as
+    /// well.
+    #[arg(long)]
+    remote: Option<String>,
+}
+
+/// Change returned by the `/changes/` endpoint.
+#[derive(Debug, serde::Deserialize)]
+struct ChangeInfo {
+    project: String,
+    change_id: String,
+    status: String,
+    #[serde(rename = "_number")]
+    number: i64,
+    #[serde(default)]
+    labels: BTreeMap<String, LabelInfo>,
+}
+
+#[derive(Debug, serde::Deserialize)]
+struct LabelInfo {
+    #[serde(default)]
+    all: Vec<ApprovalInfo>,
+}
+
+#[derive(Debug, serde::Deserialize)]
+struct ApprovalInfo {
+    #[serde(default)]
+    value: i64,
+}
+
+impl ChangeInfo {
+    /// Returns the votes of each label, e.g. `Code-Review+2`. The most negative
+    /// vote is shown if there is any, otherwise the most positive one.
+    fn vote_summary(&self) -> Vec<String> {
+        self.labels
+            .iter()
+            .filter_map(|(name, label)| {
+                let values = label.all.iter().map(|approval| approval.value);
+                let value = values
+                    .clone()
+                    .min()
+                    .filter(|&value| value < 0)
+                    .or_else(|| values.max().filter(|&value| value > 0))?;
+                Some(format!("{name}{value:+}"))
+            })
+            .collect()
+    }
+}
+
+pub fn cmd_gerrit_status(
+    ui: &mut Ui,
+    command: &CommandHelper,
+    args: &StatusArgs,
+) -> Result<(), CommandError> {
+    let workspace_command = command.workspace_helper(ui)?;
+    let revisions = if args.revisions.is_empty() {
+        vec![RevisionArg::from(DEFAULT_REVISIONS.to_owned())]
+    } else {
+        args.revisions.clone()
+    };
+    let commits: Vec<Commit> = workspace_command
+        .parse_union_revsets(ui, &revisions)?
+        .evaluate_to_commits()?
+        .try_collect()?;
+
+    let commits_with_ids = commits
+        .into_iter()
+        .filter_map(|commit| {
+            let change_id = gerrit_change_id(&commit)?;
+            Some((commit, change_id))
+        })
+        .collect_vec();
+    if commits_with_ids.is_empty() {
+        writeln!(
+            ui.status(),
+            "{}",
+            tr!(
+                "gerrit.status.no-changes",
+                "No revisions with a Change-Id or Link footer"
+            )
+        )?;
+        return Ok(());
+    }
+
+    let remote = calculate_push_remote(
+        workspace_command.repo().store(),
+        command.settings(),
+        args.remote.as_deref(),
+    )?;
+    // The URL of a `Link` footer isn't used since anyone can write it, and
+    // the credentials would be sent to that server.
+    let review_url = calculate_review_url(command.settings(), &remote)?.ok_or_else(|| {
+        user_error(tr!(
+            "gerrit.status.no-review-url",
+            "No review URL is configured for remote '{remote}'",
+            remote = remote,
+        ))
+        .hinted(tr!(
+            "gerrit.status.no-review-url-hint",
+            "Set `gerrit.review-url` to the URL of your Gerrit server"
+        ))
+    })?;
+    let client = GerritRestClient::new(command.settings(), &review_url)?;
+
+    let mut changes: HashMap<String, ChangeInfo> = HashMap::new();
+    let change_ids = commits_with_ids
+        .iter()
+        .map(|(_, change_id)| change_id.as_str())
+        .unique()
+        .collect_vec();
+    for chunk in change_ids.chunks(QUERY_CHUNK_SIZE) {
+        let query = chunk.iter().map(|id| format!("change:{id}")).join(" OR ");
+        let infos: Vec<ChangeInfo> = client.get(&format!(
+            "changes/?q={}&o=DETAILED_LABELS",
+            encode_query_value(&query)
+        ))?;
+        for info in infos {
+            // A change may be proposed for several branches. Keep the first.
+            changes.entry(info.change_id.clone()).or_insert(info);
+        }
+    }
+
+    let template_text = command.settings().get_string("templates.gerrit_status")?;
+    let language = workspace_command.commit_template_language();
+    let template = workspace_command.parse_template(ui, &language, &template_text)?;
+    ui.request_pager();
+    let mut formatter = ui.stdout_formatter();
+    for (commit, change_id) in commits_with_ids {
+        let info = changes.get(&change_id);
+        let entry = GerritStatusEntry {
+            commit,
+            change_id,
+            status: info.map(|info| info.status.clone()).unwrap_or_default(),
+            number: info.map(|info| info.number),
+            votes: info.map(ChangeInfo::vote_summary).unwrap_or_default(),
+            url: info.map(|info| format!("{review_url}/c/{}/+/{}", info.project, info.number)),
+        };
+        template.format(&entry, formatter.as_mut())?;
+    }
+    Ok(())
+}
+
+/// Returns the Gerrit Change-Id of the `commit` from its `Change-Id` or `Link`
+/// footer.
+fn gerrit_change_id(commit: &Commit) -> Option<String> {
+    parse_description_trailers(commit.description())
+        .into_iter()
+        .find_map(|trailer| match trailer.key.as_str() {
+            "Change-Id" => Some(trailer.value),
+            "Link" => {
+                let (_url, id) = trailer.value.rsplit_once("/id/")?;
+                Some(id.to_owned())
+            }
+            _ => None,
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_vote_summary() {
+        let info: ChangeInfo = serde_json::from_value(serde_json::json!({
+            "project": "jj",
+            "change_id": "I0123456789abcdef0123456789abcdef01234567",
+            "status": "NEW",
+            "_number": 1234,
+            "labels": {
+                "Code-Review": {"all": [{"value": 1}, {"value": 2}, {"value": 0}]},
+                "Verified": {"all": [{"value": 1}, {"value": -1}]},
+                "Commit-Queue": {"all": [{"value": 0}, {}]},
+                "Other": {},
+            },
+        }))
+        .unwrap();
+        assert_eq!(info.vote_summary(), ["Code-Review+2", "Verified-1"]);
+    }
+}
@@ -15,12 +15,16 @@
 use std::collections::HashMap;
 use std::fmt::Debug;
 use std::io::Write as _;
+use std::iter;
 use std::sync::Arc;
 
 use bstr::BStr;
 use itertools::Itertools as _;
 use jj_lib::backend::CommitId;
 use jj_lib::commit::Commit;
+use jj_lib::config::ConfigGetError;
+use jj_lib::config::ConfigGetResultExt as _;
+use jj_lib::config::ConfigNamePathBuf;
 use jj_lib::git;
 use jj_lib::git::GitRefUpdate;
 use jj_lib::git::GitSubprocessOptions;
@@ -32,6 +36,7 @@ use jj_lib::store::Store;
 use jj_lib::trailer::Trailer;
 use jj_lib::trailer::parse_description_trailers;
 use pollster::FutureExt as _;
+use serde_json::json;
 
 use crate::cli_util::CommandHelper;
 use crate::cli_util::RevisionArg;
@@ -40,8 +45,14 @@ use crate::command_error::CommandError;
 use crate::command_error::internal_error;
 use crate::command_error::user_error;
 use crate::command_error::user_error_with_message;
+use crate::commands::draft::find_draft_commits;
+use crate::commit_templater::GerritUploadEntry;
 use crate::git_util::GitSubprocessUi;
 use crate::git_util::print_push_stats;
+use crate::i18n::tr;
+use crate::push_checks::PUSH_CHECK_NAMES;
+use crate::push_checks::PushCheck;
+use crate::push_checks::PushChecks;
 use crate::ui::Ui;
 
 /// Upload changes to Gerrit for code review, or update existing changes.
@@ -90,19 +101,43 @@ pub struct UploadArgs {
     #[arg(long)]
     remote: Option<String>,
 
-    /// Do not actually push the changes to Gerrit
-    #[arg(long = "dry-run", short = 'n')]
-    dry_run: bool,
+    /// Do not actually push the changes to Gerrit (same as `--dry-run`)
+    #[arg(short = 'n')]
+    short_dry_run: bool,
+
+    /// Print a summary of the uploaded changes as a JSON object
+    ///
+    /// The object lists the remote, the target branch, and the Change-Id,
+    /// commit ID, and Gerrit URL of each uploaded change. The URL is null
+    /// unless `gerrit.review-url` is configured or the change has a `Link`
+    /// footer.
+    #[arg(long)]
+    json: bool,
+
+    /// Allow uploading commits of draft changes
+    ///
+    /// Changes can be marked as drafts with `jj draft set`.
+    #[arg(long)]
+    allow_drafts: bool,
+
+    /// Skip the push checks of this kind (can be repeated)
+    ///
+    /// Commits are checked to be non-empty and to have a description, and
+    /// against the checks configured in `git.push-checks`.
+    #[arg(long, value_name = "CHECK", value_parser = PUSH_CHECK_NAMES)]
+    skip_check: Vec<String>,
 
     // The following flags are options Gerrit supports during upload.
     // They are documented at
     // https://gerrit-review.googlesource.com/Documentation/user-upload.html
     /// Add these emails as a reviewer (can be repeated)
-    #[arg(long)]
+    ///
+    /// The reviewers listed in `gerrit.default-reviewers` are added as well.
+    #[arg(long, value_name = "EMAIL")]
     reviewer: Vec<String>,
 
     /// CC these emails on the change (can be repeated)
-    #[arg(long)]
+    #[arg(long, value_name = "EMAIL")]
     cc: Vec<String>,
 
     /// Add the following labels configured by Gerrit (can be repeated)
@@ -111,6 +146,9 @@ pub struct UploadArgs {
     /// Defaults to +1 if no value is set.
     /// Eg. --label=Commit-Queue will set the Commit-Queue label to +1.
     /// Eg. --label=Commit-Queue+2 will set it to +2.
+    ///
+    /// If `gerrit.allowed-labels` is configured, only the labels listed there
+    /// can be set.
     #[arg(long, short = 'l')]
     label: Vec<String>,
 
@@ -119,10 +157,13 @@ pub struct UploadArgs {
     /// See https://gerrit-review.googlesource.com/Documentation/intro-user.html#topics.
     /// Changes can be grouped by topic, and Gerrit can be configured to submit
     /// all changes in a topic together in a single click.
+    ///
+    /// If not specified, the topic is rendered from the uploaded commit with
+    /// the `gerrit.default-topic-template` template, if configured.
     #[arg(long)]
     topic: Option<String>,
 
-    /// Applies a hashtag to the change
+    /// Applies a hashtag to the change (can be repeated)
     ///
     /// See https://gerrit-review.googlesource.com/Documentation/intro-user.html#hashtags.
     /// Hashtags are freeform strings associated with a change, like on social
@@ -132,7 +173,7 @@ pub struct UploadArgs {
     /// used for informational grouping. Changes with the same hashtags are
     /// not necessarily submitted together.
     #[arg(long)]
-    hashtag: Option<String>,
+    hashtag: Vec<String>,
 
     /// Marks the change as WIP (work in progress)
     ///
@@ -220,7 +261,7 @@ pub enum EmailNotification {
     All,
 }
 
-fn calculate_push_remote(
+pub(super) fn calculate_push_remote(
     store: &Arc<Store>,
     settings: &UserSettings,
     remote: Option<&str>,
@@ -233,8 +274,10 @@ fn calculate_push_remote(
         if remotes.contains(BStr::new(&remote)) {
             return Ok(remote.to_string());
         }
-        return Err(user_error(format!(
+        return Err(user_error(tr!(
+            "gerrit.upload.no-such-remote",
             "The remote '{remote}' (specified via `--remote`) does not exist",
+            remote = remote,
         )));
     }
 
@@ -243,8 +286,10 @@ fn calculate_push_remote(
         if remotes.contains(BStr::new(&remote)) {
             return Ok(remote);
         }
-        return Err(user_error(format!(
+        return Err(user_error(tr!(
+            "gerrit.upload.no-such-default-remote",
             "The remote '{remote}' (configured via `gerrit.default-remote`) does not exist",
+            remote = remote,
         )));
     }
 
@@ -259,18 +304,95 @@ fn calculate_push_remote(
     }
 
     // Otherwise error out
-    Err(user_error(
-        "No remote specified, and no 'gerrit' remote was found",
-    ))
+    Err(user_error(tr!(
+        "gerrit.upload.no-remote",
+        "No remote specified, and no 'gerrit' remote was found"
+    )))
+}
+
+/// Returns the review URL of the `remote`, configured by
+/// `gerrit.remotes.<name>.review-url` or `gerrit.review-url`.
+pub(super) fn calculate_review_url(
+    settings: &UserSettings,
+    remote: &str,
+) -> Result<Option<String>, CommandError> {
+    let remote_config = GerritRemoteConfig::from_settings(settings, remote)?;
+    Ok(remote_config.review_url(settings))
+}
+
+/// Settings of a Gerrit remote loaded from `[gerrit.remotes.<name>]`.
+#[derive(Clone, Debug, Default, serde::Deserialize)]
+#[serde(default, rename_all = "kebab-case")]
+struct GerritRemoteConfig {
+    /// Target branch, overriding `gerrit.default-remote-branch`.
+    branch: Option<String>,
+    /// Review URL, overriding `gerrit.review-url`.
+    review_url: Option<String>,
+    /// Push options passed on every upload, e.g. `wip` or `topic=foo`.
+    push_options: Vec<String>,
+    /// Reviewers added on every upload.
+    reviewers: Vec<String>,
+}
+
+impl GerritRemoteConfig {
+    fn from_settings(settings: &UserSettings, remote: &str) -> Result<Self, ConfigGetError> {
+        let name = ConfigNamePathBuf::from_iter(["gerrit", "remotes", remote]);
+        Ok(settings.get::<Self>(&name).optional()?.unwrap_or_default())
+    }
+
+    /// Returns the review URL without trailing slash, falling back to
+    /// `gerrit.review-url`.
+    fn review_url(&self, settings: &UserSettings) -> Option<String> {
+        self.review_url
+            .clone()
+            .or_else(|| settings.get_string("gerrit.review-url").ok())
+            .map(|url| url.trim_end_matches('/').to_owned())
+    }
+
+    /// Returns the push options implied by the configuration.
+    fn push_options(&self) -> impl Iterator<Item = String> {
+        self.push_options
+            .iter()
+            .cloned()
+            .chain(
+                self.reviewers
+                    .iter()
+                    .map(|reviewer| format!("r={reviewer}")),
+            )
+            .flat_map(|option| ["-o".to_owned(), option])
+    }
+}
+
+/// Returns the magic ref to push to, e.g. `refs/for/main%topic=foo`.
+fn gerrit_push_ref(remote_branch: &str, topic: Option<&str>) -> String {
+    match topic {
+        // Ref options are URL-decoded by Gerrit, and separated by commas.
+        Some(topic) => {
+            let encoded: String = topic
+                .bytes()
+                .map(|b| {
+                    if b.is_ascii_alphanumeric() || b"-_.~/".contains(&b) {
+                        char::from(b).to_string()
+                    } else {
+                        format!("%{b:02X}")
+                    }
+                })
+                .collect();
+            format!("refs/for/{remote_branch}%topic={encoded}")
+        }
+        None => format!("refs/for/{remote_branch}"),
+    }
 }
 
 /// Determine what Gerrit ref and remote to use. The logic is:
 ///
 /// 1. If the user specifies `--remote-branch branch`, use that
-/// 2. If the user has 'gerrit.default-remote-branch' configured, use that
-/// 3. Otherwise, bail out
+/// 2. If the remote has `gerrit.remotes.<name>.branch` configured, use that
+/// 3. If the user has 'gerrit.default-remote-branch' configured, use that
+/// 4. Otherwise, bail out
 fn calculate_push_ref(
     settings: &UserSettings,
+    remote_config: &GerritRemoteConfig,
     remote_branch: Option<String>,
 ) -> Result<String, CommandError> {
     // case 1
@@ -279,42 +401,81 @@ fn calculate_push_ref(
     }
 
     // case 2
+    if let Some(branch) = &remote_config.branch {
+        return Ok(branch.clone());
+    }
+
+    // case 3
     if let Ok(branch) = settings.get_string("gerrit.default-remote-branch") {
         return Ok(branch);
     }
 
-    // case 3
-    Err(user_error(
+    // case 4
+    Err(user_error(tr!(
+        "gerrit.upload.no-remote-branch",
         "No target branch specified via --remote-branch, and no 'gerrit.default-remote-branch' \
-         was found",
-    ))
+         was found"
+    )))
+}
+
+fn mutually_exclusive_message(flag1: &str, flag2: &str) -> String {
+    tr!(
+        "gerrit.upload.mutually-exclusive",
+        "{flag1} and {flag2} are mutually exclusive",
+        flag1 = flag1,
+        flag2 = flag2,
+    )
+}
+
+fn specify_revision_hint() -> String {
+    tr!(
+        "gerrit.upload.specify-revision",
+        "Explicitly specify a revision to upload with `-r`"
+    )
 }
 
 fn push_options(args: &UploadArgs) -> Result<Vec<String>, CommandError> {
     for c in &args.custom {
         if !c.contains(':') {
-            return Err(user_error(format!(
-                "Custom values must be of the form 'key:value'. Got {c}"
+            return Err(user_error(tr!(
+                "gerrit.upload.invalid-custom-value",
+                "Custom values must be of the form 'key:value'. Got {value}",
+                value = c,
             )));
         }
     }
     if args.wip && args.ready {
-        return Err(user_error("--wip and --ready are mutually exclusive"));
+        return Err(user_error(mutually_exclusive_message("--wip", "--ready")));
     }
     if args.private && args.remove_private {
-        return Err(user_error(
-            "--private and --remove-private are mutually exclusive",
-        ));
+        return Err(user_error(mutually_exclusive_message(
+            "--private",
+            "--remove-private",
+        )));
     }
     if args.publish_comments && args.no_publish_comments {
-        return Err(user_error(
-            "--publish-comments and --no-publish-comments are mutually exclusive",
-        ));
+        return Err(user_error(mutually_exclusive_message(
+            "--publish-comments",
+            "--no-publish-comments",
+        )));
+    }
+    for reviewer in &args.reviewer {
+        validate_account("--reviewer", reviewer)?;
+    }
+    for cc in &args.cc {
+        validate_account("--cc", cc)?;
+    }
+    if args.hashtag.iter().any(|hashtag| hashtag.trim().is_empty()) {
+        return Err(user_error(tr!(
+            "gerrit.upload.empty-hashtag",
+            "Hashtags must not be empty"
+        )));
     }
     if args.skip_validation && !args.submit {
-        return Err(user_error(
-            "--skip-validation is only supported for --submit",
-        ));
+        return Err(user_error(tr!(
+            "gerrit.upload.skip-validation-without-submit",
+            "--skip-validation is only supported for --submit"
+        )));
     }
 
     // Note: invalid push options will be ignored rather than erroring out, so
@@ -332,14 +493,15 @@ fn push_options(args: &UploadArgs) -> Result<Vec<String>, CommandError> {
                 .to_string(),
             )
         }),
-        args.topic.clone().map(|arg| ("topic", arg)),
         args.trace.clone().map(|arg| ("trace", arg)),
-        args.hashtag.clone().map(|arg| ("hashtag", arg)),
         args.deadline.clone().map(|arg| ("deadline", arg)),
     ]
     .into_iter()
-    // TODO: In a future version, we could consider adding a list of supported
-    // labels to the config, so we can list it for the user.
+    .chain(
+        args.hashtag
+            .iter()
+            .map(|arg| Some(("hashtag", arg.clone()))),
+    )
     .chain(args.label.iter().map(|arg| Some(("label", arg.clone()))))
     .chain(
         args.custom
@@ -372,13 +534,73 @@ fn push_options(args: &UploadArgs) -> Result<Vec<String>, CommandError> {
     .collect())
 }
 
+/// Checks that a reviewer or CC can be passed to Gerrit as a push option.
+fn validate_account(source: &str, account: &str) -> Result<(), CommandError> {
+    if account.is_empty() || account.contains(|c: char| c.is_whitespace() || c == ',') {
+        return Err(user_error(tr!(
+            "gerrit.upload.invalid-account",
+            "Invalid email '{account}' in {source}",
+            account = account,
+            source = source,
+        ))
+        .hinted(tr!(
+            "gerrit.upload.invalid-account-hint",
+            "Emails must not be empty or contain whitespace or commas. Repeat the option to add \
+             several people."
+        )));
+    }
+    Ok(())
+}
+
+/// Splits a `--label` value into the label name and the vote, if any.
+fn parse_label(label: &str) -> (&str, Option<&str>) {
+    match label.rfind(['+', '-']) {
+        Some(pos)
+            if pos > 0
+                && pos + 1 < label.len()
+                && label[pos + 1..].bytes().all(|b| b.is_ascii_digit()) =>
+        {
+            (&label[..pos], Some(&label[pos..]))
+        }
+        _ => (label, None),
+    }
+}
+
+/// Checks that all `labels` are listed in `allowed_labels`.
+fn validate_labels(labels: &[String], allowed_labels: &[String]) -> Result<(), CommandError> {
+    for label in labels {
+        let (name, _vote) = parse_label(label);
+        if !allowed_labels.iter().any(|allowed| allowed == name) {
+            return Err(user_error(tr!(
+                "gerrit.upload.label-not-allowed",
+                "Label {label} is not in `gerrit.allowed-labels`",
+                label = name,
+            ))
+            .hinted(tr!(
+                "gerrit.upload.label-not-allowed-hint",
+                "Allowed labels: {labels}",
+                labels = allowed_labels.join(", "),
+            )));
+        }
+    }
+    Ok(())
+}
+
 pub fn cmd_gerrit_upload(
     ui: &mut Ui,
     command: &CommandHelper,
     args: &UploadArgs,
 ) -> Result<(), CommandError> {
+    let dry_run = args.short_dry_run || command.global_args().dry_run;
     // Do this first because the validation is cheap.
-    let push_options = push_options(args)?;
+    let mut push_options = push_options(args)?;
+    if let Some(allowed_labels) = command
+        .settings()
+        .get::<Vec<String>>("gerrit.allowed-labels")
+        .optional()?
+    {
+        validate_labels(&args.label, &allowed_labels)?;
+    }
 
     let mut workspace_command = command.workspace_helper(ui)?;
 
@@ -389,27 +611,34 @@ pub fn cmd_gerrit_upload(
             .transpose()?
         {
             None => {
-                return Err(user_error("No revision provided")
-                    .hinted("Explicitly specify a revision to upload with `-r`"));
+                return Err(
+                    user_error(tr!("gerrit.upload.no-revision", "No revision provided"))
+                        .hinted(specify_revision_hint()),
+                );
             }
             // This distinguishes between the "squash workflow" and "edit workflow".
             Some(commit) => {
                 if commit.description().is_empty() {
                     let parents = commit.parent_ids();
                     if parents.len() != 1 {
-                        return Err(user_error(
+                        return Err(user_error(tr!(
+                            "gerrit.upload.no-revision-merge",
                             "No revision provided, and @ is a merge commit with no description. \
-                             Unable to determine a suitable default commit to upload.",
-                        )
-                        .hinted("Explicitly specify a revision to upload with `-r`"));
+                             Unable to determine a suitable default commit to upload."
+                        ))
+                        .hinted(specify_revision_hint()));
+                    }
+                    if let Some(mut formatter) = ui.dry_run_formatter() {
+                        writeln!(
+                            formatter,
+                            "No revision provided and @ has no description. Defaulting to @-"
+                        )?;
                     }
-                    writeln!(
-                        ui.status(),
-                        "No revision provided and @ has no description. Defaulting to @-"
-                    )?;
                     parents.to_vec()
                 } else {
-                    writeln!(ui.status(), "No revision provided. Defaulting to @")?;
+                    if let Some(mut formatter) = ui.dry_run_formatter() {
+                        writeln!(formatter, "No revision provided. Defaulting to @")?;
+                    }
                     vec![commit.id().clone()]
                 }
             }
@@ -425,7 +654,9 @@ pub fn cmd_gerrit_upload(
             .try_collect()?
     };
     if revisions.is_empty() {
-        writeln!(ui.status(), "No revisions to upload.")?;
+        if let Some(mut formatter) = ui.dry_run_formatter() {
+            writeln!(formatter, "No revisions to upload.")?;
+        }
         return Ok(());
     }
 
@@ -443,6 +674,83 @@ pub fn cmd_gerrit_upload(
         )
         .evaluate_to_commits()?
         .try_collect()?;
+    if !args.allow_drafts {
+        let drafts = find_draft_commits(&workspace_command, &to_upload)?;
+        if !drafts.is_empty() {
+            let mut error = user_error(tr!(
+                "gerrit.upload.draft-revisions",
+                "Refusing to upload {count} revisions because they are drafts",
+                count = drafts.len(),
+            ));
+            error.add_formatted_hint_with(|formatter| {
+                writeln!(formatter, "Draft revisions:")?;
+                for commit in &drafts {
+                    write!(formatter, "  ")?;
+                    workspace_command.write_commit_summary(formatter, commit)?;
+                    writeln!(formatter)?;
+                }
+                Ok(())
+            });
+            error.add_hint(tr!(
+                "gerrit.upload.draft-revisions-hint",
+                "Unmark them with `jj draft unset`, or use --allow-drafts to upload them anyway."
+            ));
+            return Err(error);
+        }
+    }
+
+    // Immediately error and reject any commits that shouldn't be uploaded.
+    let push_checks = PushChecks::from_settings(
+        command.settings(),
+        [PushCheck::NoEmptyCommits, PushCheck::NoEmptyDescription],
+        &args.skip_check,
+    )?;
+    for commit in &to_upload {
+        let failed_checks = push_checks.run(ui, &workspace_command, commit)?;
+        let Some(failure) = failed_checks.first() else {
+            continue;
+        };
+        let change = short_change_hash(commit.change_id());
+        let error = match failure.name {
+            "no-empty-commits" => user_error(tr!(
+                "gerrit.upload.empty-revision",
+                "Refusing to upload revision {change} because it is empty",
+                change = change,
+            ))
+            .hinted(tr!(
+                "gerrit.upload.empty-revision-hint",
+                "Perhaps you squashed then ran upload? Maybe you meant to upload the parent \
+                 commit instead (eg. @-)"
+            )),
+            "no-empty-description" => user_error(tr!(
+                "gerrit.upload.no-description",
+                "Refusing to upload revision {change} because it is has no description",
+                change = change,
+            ))
+            .hinted(tr!(
+                "gerrit.upload.no-description-hint",
+                "Maybe you meant to upload the parent commit instead (eg. @-)"
+            )),
+            _ => user_error(tr!(
+                "gerrit.upload.failed-check",
+                "Refusing to upload revision {change} because {reason}",
+                change = change,
+                reason = failed_checks
+                    .iter()
+                    .map(|failure| &failure.reason)
+                    .join(" and "),
+            )),
+        };
+        return Err(error.hinted(tr!(
+            "gerrit.upload.failed-check-hint",
+            "Use {flags} to upload it anyway.",
+            flags = failed_checks
+                .iter()
+                .map(|failure| format!("--skip-check={}", failure.name))
+                .unique()
+                .join(" "),
+        )));
+    }
 
     // Note: This transaction is intentionally never finished. This way, the
     // Change-Id is never part of the commit description in jj.
@@ -464,30 +772,35 @@ pub fn cmd_gerrit_upload(
 
     let subprocess_options = GitSubprocessOptions::from_settings(command.settings())?;
     let remote = calculate_push_remote(&store, command.settings(), args.remote.as_deref())?;
-    let remote_branch = calculate_push_ref(command.settings(), args.remote_branch.clone())?;
-
-    // Immediately error and reject any commits that shouldn't be uploaded.
-    for commit in &to_upload {
-        if commit.is_empty(tx.repo_mut())? {
-            return Err(user_error(format!(
-                "Refusing to upload revision {} because it is empty",
-                short_change_hash(commit.change_id())
-            ))
-            .hinted(
-                "Perhaps you squashed then ran upload? Maybe you meant to upload the parent \
-                 commit instead (eg. @-)",
-            ));
-        }
-        if commit.description().is_empty() {
-            return Err(user_error(format!(
-                "Refusing to upload revision {} because it is has no description",
-                short_change_hash(commit.change_id())
-            ))
-            .hinted("Maybe you meant to upload the parent commit instead (eg. @-)"));
-        }
+    let remote_config = GerritRemoteConfig::from_settings(command.settings(), &remote)?;
+    let remote_branch = calculate_push_ref(
+        command.settings(),
+        &remote_config,
+        args.remote_branch.clone(),
+    )?;
+    let review_url = remote_config.review_url(command.settings());
+    push_options.extend(remote_config.push_options());
+    let default_reviewers = command
+        .settings()
+        .get::<Vec<String>>("gerrit.default-reviewers")
+        .optional()?
+        .unwrap_or_default();
+    for reviewer in &default_reviewers {
+        validate_account("`gerrit.default-reviewers`", reviewer)?;
+        push_options.extend(["-o".to_owned(), format!("r={reviewer}")]);
     }
+    let template_text = command.settings().get_string("templates.gerrit_upload")?;
+    let topic_template_text = if args.topic.is_some() {
+        None
+    } else {
+        command
+            .settings()
+            .get_string("gerrit.default-topic-template")
+            .optional()?
+    };
 
     let mut old_to_new: HashMap<CommitId, Commit> = HashMap::new();
+    let mut entries: Vec<GerritUploadEntry> = vec![];
     for original_commit in to_upload.into_iter().rev() {
         let trailers = parse_description_trailers(original_commit.description());
 
@@ -499,15 +812,18 @@ pub fn cmd_gerrit_upload(
         // There shouldn't be multiple change-ID fields. So just error out if
         // there is.
         if change_id_trailers.len() > 1 {
-            return Err(user_error(format!(
-                "Multiple Change-Id footers in revision {}",
-                short_change_hash(original_commit.change_id())
+            return Err(user_error(tr!(
+                "gerrit.upload.multiple-change-ids",
+                "Multiple Change-Id footers in revision {change}",
+                change = short_change_hash(original_commit.change_id()),
             )));
         }
 
         // The user can choose to explicitly set their own change-ID to
         // override the default change-ID based on the jj change-ID.
-        let new_description = if let Some(trailer) = change_id_trailers.first() {
+        let (new_description, gerrit_change_id, url) = if let Some(trailer) =
+            change_id_trailers.first()
+        {
             // Check the change-id format is correct, intentionally leave the
             // invalid change IDs as-is.
             if trailer.key == "Change-Id"
@@ -529,30 +845,52 @@ pub fn cmd_gerrit_upload(
                 )?;
             }
 
-            original_commit.description().to_owned()
+            let (gerrit_change_id, url) = if trailer.key == "Link" {
+                let id = trailer
+                    .value
+                    .rsplit_once("/id/")
+                    .map_or(trailer.value.as_str(), |(_url, id)| id);
+                (id.to_owned(), Some(trailer.value.clone()))
+            } else {
+                let url = review_url
+                    .as_ref()
+                    .map(|review_url| format!("{review_url}/id/{}", trailer.value));
+                (trailer.value.clone(), url)
+            };
+            (
+                original_commit.description().to_owned(),
+                gerrit_change_id,
+                url,
+            )
         } else {
             // Gerrit change id is 40 chars, jj change id is 32, so we need padding.
             // To be consistent with `format_gerrit_change_id_trailer``, we pad with
             // 6a6a6964 (hex of "jjid").
             let gerrit_change_id = format!("I{}6a6a6964", original_commit.change_id().hex());
 
-            let change_id_trailer =
-                if let Ok(review_url) = command.settings().get_string("gerrit.review-url") {
-                    format!(
-                        "Link: {}/id/{gerrit_change_id}",
-                        review_url.trim_end_matches('/'),
-                    )
-                } else {
-                    format!("Change-Id: {gerrit_change_id}")
-                };
+            let url = review_url
+                .as_ref()
+                .map(|review_url| format!("{review_url}/id/{gerrit_change_id}"));
+            let change_id_trailer = if let Some(url) = &url {
+                format!("Link: {url}")
+            } else {
+                format!("Change-Id: {gerrit_change_id}")
+            };
 
-            format!(
+            let new_description = format!(
                 "{}{}{}\n",
                 original_commit.description().trim(),
                 if trailers.is_empty() { "\n\n" } else { "\n" },
                 change_id_trailer,
-            )
+            );
+            (new_description, gerrit_change_id, url)
         };
+        entries.push(GerritUploadEntry {
+            commit: original_commit.clone(),
+            change_id: gerrit_change_id,
+            url,
+            dry_run,
+        });
 
         let new_parents = original_commit
             .parent_ids()
@@ -586,35 +924,73 @@ pub fn cmd_gerrit_upload(
         old_to_new.insert(original_commit.id().clone(), new_commit);
     }
 
-    let remote_ref = format!("refs/for/{remote_branch}");
-    writeln!(
-        ui.status(),
-        "Found {} heads to push to Gerrit (remote '{}'), target branch '{}'",
-        old_heads.len(),
-        remote,
-        remote_branch,
-    )?;
+    if let Some(mut formatter) = ui.dry_run_formatter() {
+        writeln!(
+            formatter,
+            "Found {} heads to push to Gerrit (remote '{}'), target branch '{}'",
+            old_heads.len(),
+            remote,
+            remote_branch,
+        )?;
+    }
+
+    // We have to render the old commits here, because until we finish the
+    // transaction (which we don't), the new commits are labeled as "hidden".
+    // The messages are rendered upfront since the template borrows the
+    // transaction.
+    let messages: Vec<Vec<u8>> = {
+        let workspace_helper = tx.base_workspace_helper();
+        let language = workspace_helper.commit_template_language();
+        let template = workspace_helper.parse_template(ui, &language, &template_text)?;
+        old_heads
+            .iter()
+            .map(|head| {
+                let entry = entries
+                    .iter()
+                    .find(|entry| entry.commit.id() == head)
+                    .unwrap();
+                let mut output = vec![];
+                template.format(entry, ui.new_formatter(&mut output).as_mut())?;
+                Ok::<_, CommandError>(output)
+            })
+            .try_collect()?
+    };
+    // The topic template is also rendered for the old commits, which have the
+    // bookmarks.
+    let topics: Vec<Option<String>> = if let Some(text) = &topic_template_text {
+        let template = tx.base_workspace_helper().parse_commit_template(ui, text)?;
+        old_heads
+            .iter()
+            .map(|head| {
+                let commit = store.get_commit(head)?;
+                let output = template.format_plain_text(&commit);
+                let topic = String::from_utf8(output).map_err(|err| {
+                    user_error_with_message(
+                        tr!(
+                            "gerrit.upload.invalid-topic",
+                            "Invalid character in Gerrit topic"
+                        ),
+                        err.utf8_error(),
+                    )
+                })?;
+                let topic = topic.trim();
+                Ok::<_, CommandError>((!topic.is_empty()).then(|| topic.to_owned()))
+            })
+            .try_collect()?
+    } else {
+        vec![args.topic.clone(); old_heads.len()]
+    };
 
     // NOTE (aseipp): because we are pushing everything to the same remote ref,
     // we have to loop and push each commit one at a time, even though
     // push_updates in theory supports multiple GitRefUpdates at once, because
     // we obviously can't push multiple heads to the same ref.
-    for head in &old_heads {
-        if let Some(mut formatter) = ui.status_formatter() {
-            if args.dry_run {
-                write!(formatter, "Dry-run: Would push ")?;
-            } else {
-                write!(formatter, "Pushing ")?;
-            }
-            // We have to write the old commit here, because until we finish
-            // the transaction (which we don't), the new commit is labeled as
-            // "hidden".
-            tx.base_workspace_helper()
-                .write_commit_summary(formatter.as_mut(), &store.get_commit(head).unwrap())?;
-            writeln!(formatter)?;
+    for ((head, message), topic) in iter::zip(&old_heads, &messages).zip(&topics) {
+        if let Some(mut formatter) = ui.dry_run_formatter() {
+            formatter.write_all(message)?;
         }
 
-        if args.dry_run {
+        if dry_run {
             continue;
         }
 
@@ -628,7 +1004,7 @@ pub fn cmd_gerrit_upload(
             subprocess_options.clone(),
             remote.as_ref(),
             &[GitRefUpdate {
-                qualified_name: remote_ref.clone().into(),
+                qualified_name: gerrit_push_ref(&remote_branch, topic.as_deref()).into(),
                 expected_current_target: None,
                 new_target: Some(new_commit.id().clone()),
             }],
@@ -644,15 +1020,43 @@ pub fn cmd_gerrit_upload(
             git::GitPushError::NoSuchRemote(_)
             | git::GitPushError::RemoteName(_)
             | git::GitPushError::UnexpectedBackend(_) => user_error(err),
-            git::GitPushError::Subprocess(_) => {
-                user_error_with_message("Internal git error while pushing to gerrit", err)
-            }
+            git::GitPushError::Subprocess(_) => user_error_with_message(
+                tr!(
+                    "gerrit.upload.push-subprocess",
+                    "Internal git error while pushing to gerrit"
+                ),
+                err,
+            ),
         })?;
         print_push_stats(ui, &push_stats)?;
         if !push_stats.all_ok() {
-            return Err(user_error("Failed to push all changes to gerrit"));
+            return Err(user_error(tr!(
+                "gerrit.upload.push-failed",
+                "Failed to push all changes to gerrit"
+            )));
         }
     }
+
+    if args.json {
+        let changes = entries
+            .iter()
+            .map(|entry| {
+                let new_commit = &old_to_new[entry.commit.id()];
+                json!({
+                    "change_id": entry.change_id,
+                    "commit_id": new_commit.id().hex(),
+                    "url": entry.url,
+                })
+            })
+            .collect_vec();
+        let summary = json!({
+            "remote": remote,
+            "remote_branch": remote_branch,
+            "dry_run": dry_run,
+            "changes": changes,
+        });
+        writeln!(ui.stdout(), "{summary}")?;
+    }
     Ok(())
 }
 
@@ -683,8 +1087,6 @@ mod tests {
                 "-o",
                 "notify=NONE",
                 "-o",
-                "topic=my-topic",
-                "-o",
                 "r=foo@example.com",
                 "-o",
                 "cc=bar@example.com",
@@ -706,7 +1108,7 @@ mod tests {
             push_options(&UploadArgs {
                 notify: Some(EmailNotification::All),
                 trace: Some("my-trace".to_string()),
-                hashtag: Some("my-hashtag".to_string()),
+                hashtag: vec!["my-hashtag".to_string(), "other-hashtag".to_string()],
                 deadline: Some("yesterday".to_string()),
                 label: vec!["Auto-Submit".to_string(), "Commit-Queue+2".to_string()],
                 custom: vec!["foo:bar".to_string(), "baz:quux".to_string()],
@@ -725,9 +1127,11 @@ mod tests {
                 "-o",
                 "trace=my-trace",
                 "-o",
+                "deadline=yesterday",
+                "-o",
                 "hashtag=my-hashtag",
                 "-o",
-                "deadline=yesterday",
+                "hashtag=other-hashtag",
                 "-o",
                 "label=Auto-Submit",
                 "-o",
@@ -754,4 +1158,64 @@ mod tests {
             .collect::<Vec<_>>(),
         );
     }
+
+    #[test]
+    fn test_gerrit_push_ref() {
+        assert_eq!(gerrit_push_ref("main", None), "refs/for/main");
+        assert_eq!(
+            gerrit_push_ref("main", Some("my-topic")),
+            "refs/for/main%topic=my-topic"
+        );
+        assert_eq!(
+            gerrit_push_ref("release/1.0", Some("a,b c%")),
+            "refs/for/release/1.0%topic=a%2Cb%20c%25"
+        );
+    }
+
+    #[test]
+    fn test_gerrit_remote_config_push_options() {
+        let config = GerritRemoteConfig {
+            push_options: vec!["wip".to_owned(), "topic=foo".to_owned()],
+            reviewers: vec!["foo@example.com".to_owned()],
+            ..Default::default()
+        };
+        assert_eq!(
+            config.push_options().collect_vec(),
+            ["-o", "wip", "-o", "topic=foo", "-o", "r=foo@example.com"]
+        );
+    }
+
+    #[test]
+    fn test_gerrit_validate_account() {
+        assert!(validate_account("--reviewer", "foo@example.com").is_ok());
+        assert!(validate_account("--reviewer", "foo").is_ok());
+        assert!(validate_account("--reviewer", "").is_err());
+        assert!(validate_account("--reviewer", "foo @example.com").is_err());
+        assert!(validate_account("--cc", "foo@example.com,bar@example.com").is_err());
+    }
+
+    #[test]
+    fn test_gerrit_parse_label() {
+        assert_eq!(parse_label("Verified"), ("Verified", None));
+        assert_eq!(parse_label("Code-Review"), ("Code-Review", None));
+        assert_eq!(parse_label("Code-Review+2"), ("Code-Review", Some("+2")));
+        assert_eq!(parse_label("Code-Review-1"), ("Code-Review", Some("-1")));
+        assert_eq!(parse_label("Code-Review+"), ("Code-Review+", None));
+        assert_eq!(parse_label("+1"), ("+1", None));
+    }
+
+    #[test]
+    fn test_gerrit_validate_labels() {
+        let allowed = ["Verified".to_string(), "Code-Review".to_string()];
+        assert!(validate_labels(&[], &allowed).is_ok());
+        assert!(
+            validate_labels(
+                &["Verified+1".to_string(), "Code-Review-2".to_string()],
+                &allowed
+            )
+            .is_ok()
+        );
+        assert!(validate_labels(&["Commit-Queue+2".to_string()], &allowed).is_err());
+        assert!(validate_labels(&["Verified".to_string()], &[]).is_err());
+    }
 }
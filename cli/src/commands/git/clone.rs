@@ -49,6 +49,7 @@ use crate::git_util::GitSubprocessUi;
 use crate::git_util::absolute_git_url;
 use crate::git_util::load_git_import_options;
 use crate::git_util::print_git_import_stats;
+use crate::git_util::record_remote_fetches;
 use crate::revset_util::parse_union_name_patterns;
 use crate::ui::Ui;
 
@@ -435,5 +436,6 @@ fn fetch_new_remote(
         )?;
     }
     tx.finish(ui, "fetch from git remote into empty repo")?;
+    record_remote_fetches(workspace_command, &[remote_name])?;
     Ok((working_branch.map(ToOwned::to_owned), working_is_default))
 }
@@ -12,10 +12,9 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use jj_lib::git;
-
 use crate::cli_util::CommandHelper;
 use crate::command_error::CommandError;
+use crate::git_util::export_git_refs;
 use crate::git_util::print_git_export_stats;
 use crate::ui::Ui;
 
@@ -33,7 +32,7 @@ pub fn cmd_git_export(
 ) -> Result<(), CommandError> {
     let mut workspace_command = command.workspace_helper(ui)?;
     let mut tx = workspace_command.start_transaction();
-    let stats = git::export_refs(tx.repo_mut())?;
+    let stats = export_git_refs(ui, tx.repo_mut())?;
     tx.finish(ui, "export git refs")?;
     print_git_export_stats(ui, &stats)?;
     Ok(())
@@ -42,6 +42,7 @@ use crate::complete;
 use crate::git_util::GitSubprocessUi;
 use crate::git_util::load_git_import_options;
 use crate::git_util::print_git_import_stats;
+use crate::git_util::record_remote_fetches;
 use crate::revset_util::parse_union_name_patterns;
 use crate::ui::Ui;
 
@@ -158,6 +159,7 @@ pub fn cmd_git_fetch(
     }
 
     let mut tx = workspace_command.start_transaction();
+    let remote_settings = tx.settings().remote_settings()?;
 
     let is_specific = args.branches.is_some() || args.tags.is_some();
     let common_bookmark_expr = match &args.branches {
@@ -206,13 +208,17 @@ pub fn cmd_git_fetch(
                 // TODO: disable implicit fetching and set this to "all" (#7528)
                 .unwrap_or_else(StringExpression::none);
             let ref_expr = GitFetchRefExpression { bookmark, tag };
-            let expanded = expand_fetch_refspecs(remote, ref_expr)?;
+            let mut expanded = expand_fetch_refspecs(remote, ref_expr)?;
+            if common_bookmark_expr.is_none()
+                && let Some(settings) = remote_settings.get(*remote)
+            {
+                expanded.add_custom_refspecs(remote, &settings.fetch)?;
+            }
             expansions.push((remote, expanded));
         }
     }
 
     let git_settings = GitSettings::from_settings(tx.settings())?;
-    let remote_settings = tx.settings().remote_settings()?;
     let import_options = load_git_import_options(ui, &git_settings, &remote_settings)?;
     let mut git_fetch = GitFetch::new(
         tx.repo_mut(),
@@ -242,6 +248,7 @@ pub fn cmd_git_fetch(
             matching_remotes.iter().map(|n| n.as_symbol()).join(",")
         ),
     )?;
+    record_remote_fetches(&workspace_command, &matching_remotes)?;
     Ok(())
 }
 
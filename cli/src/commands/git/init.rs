@@ -41,6 +41,7 @@ use crate::command_error::user_error_with_message;
 use crate::commands::git::maybe_add_gitignore;
 use crate::config::ConfigEnv;
 use crate::formatter::FormatterExt as _;
+use crate::git_util::export_git_refs;
 use crate::git_util::is_colocated_git_workspace;
 use crate::git_util::load_git_import_options;
 use crate::git_util::print_git_export_stats;
@@ -268,7 +269,7 @@ fn init_git_refs(
         // If git.auto-local-bookmark = true or
         // remotes.<name>.auto-track-bookmarks is set, local bookmarks could be
         // created for the imported remote branches.
-        let stats = git::export_refs(tx.repo_mut())?;
+        let stats = export_git_refs(ui, tx.repo_mut())?;
         print_git_export_stats(ui, &stats)?;
     }
     let repo = tx.commit("import git refs").block_on()?;
@@ -84,6 +84,14 @@ pub enum GitCommand {
     Root(GitRootArgs),
 }
 
+impl GitCommand {
+    /// Returns true if the subcommand can preview its changes with
+    /// `--dry-run`.
+    pub(crate) fn supports_dry_run(&self) -> bool {
+        matches!(self, Self::Push(_))
+    }
+}
+
 pub fn cmd_git(
     ui: &mut Ui,
     command: &CommandHelper,
@@ -30,6 +30,8 @@ use jj_lib::commit::CommitIteratorExt as _;
 use jj_lib::config::ConfigGetResultExt as _;
 use jj_lib::git;
 use jj_lib::git::GitBranchPushTargets;
+use jj_lib::git::GitPushRefSpecs;
+use jj_lib::git::GitPushStats;
 use jj_lib::git::GitSettings;
 use jj_lib::index::IndexResult;
 use jj_lib::op_store::RefTarget;
@@ -46,6 +48,7 @@ use jj_lib::refs::classify_bookmark_push_action;
 use jj_lib::repo::Repo;
 use jj_lib::revset::RemoteRefSymbolExpression;
 use jj_lib::revset::RevsetExpression;
+use jj_lib::settings::UserSettings;
 use jj_lib::signing::SignBehavior;
 use jj_lib::str_util::StringExpression;
 use jj_lib::view::View;
@@ -62,12 +65,21 @@ use crate::command_error::cli_error;
 use crate::command_error::cli_error_with_message;
 use crate::command_error::user_error;
 use crate::command_error::user_error_with_message;
+use crate::commands::draft::find_draft_commits;
 use crate::commands::git::get_single_remote;
+use crate::commit_templater::GitPushResultEntry;
 use crate::complete;
 use crate::formatter::Formatter;
+use crate::formatter::FormatterExt as _;
 use crate::git_util::GitSubprocessUi;
+use crate::git_util::get_bookmark_web_url;
 use crate::git_util::print_push_stats;
+use crate::git_util::record_remote_push;
+use crate::i18n::tr;
 use crate::progress::ProgressWriter;
+use crate::push_checks::PUSH_CHECK_NAMES;
+use crate::push_checks::PushCheck;
+use crate::push_checks::PushChecks;
 use crate::revset_util::parse_bookmark_name;
 use crate::revset_util::parse_union_name_patterns;
 use crate::ui::Ui;
@@ -97,6 +109,9 @@ use crate::ui::Ui;
 /// `jj git fetch --remote <remote name>` and/or resolve some [bookmark
 /// conflicts].
 ///
+/// The result of each bookmark update can be printed to stdout with the
+/// `templates.git_push_result` template, which is useful in scripts.
+///
 /// [safety checks]:
 ///     https://docs.jj-vcs.dev/latest/bookmarks/#pushing-bookmarks-safety-checks
 ///
@@ -149,6 +164,10 @@ pub struct GitPushArgs {
     /// Only tracked bookmarks can be successfully deleted on the remote. A
     /// warning will be printed if any untracked bookmarks on the remote
     /// correspond to missing local bookmarks.
+    ///
+    /// If more than `git.max-push-deletions` bookmarks would be deleted, the
+    /// deletions have to be confirmed. Bookmarks are never deleted from the
+    /// remotes listed in `git.protect-remote-deletions`.
     #[arg(long, conflicts_with = "specific")]
     deleted: bool,
 
@@ -160,9 +179,18 @@ pub struct GitPushArgs {
     allow_new: bool,
 
     /// Allow pushing commits with empty descriptions
+    ///
+    /// This is the same as `--skip-check=no-empty-description`.
     #[arg(long)]
     allow_empty_description: bool,
 
+    /// Skip the push checks of this kind (can be repeated)
+    ///
+    /// Commits are checked to have a description, and against the checks
+    /// configured in `git.push-checks`.
+    #[arg(long, value_name = "CHECK", value_parser = PUSH_CHECK_NAMES)]
+    skip_check: Vec<String>,
+
     /// Allow pushing commits that are private
     ///
     /// The set of private commits can be configured by the
@@ -171,6 +199,12 @@ pub struct GitPushArgs {
     #[arg(long)]
     allow_private: bool,
 
+    /// Allow pushing commits of draft changes
+    ///
+    /// Changes can be marked as drafts with `jj draft set`.
+    #[arg(long)]
+    allow_drafts: bool,
+
     /// Push bookmarks pointing to these commits (can be repeated)
     #[arg(long, short, value_name = "REVSETS")]
     // While `-r` will often be used with mutable revisions, immutable revisions
@@ -198,10 +232,6 @@ pub struct GitPushArgs {
     #[arg(long, value_name = "NAME=REVISION")]
     #[arg(add = ArgValueCompleter::new(complete::branch_name_equals_any_revision))]
     named: Vec<String>,
-
-    /// Only display what will change on the remote
-    #[arg(long)]
-    dry_run: bool,
 }
 
 fn make_bookmark_term(bookmark_names: &[impl fmt::Display]) -> String {
@@ -243,6 +273,10 @@ pub fn cmd_git_push(
         default_remote = get_default_push_remote(ui, &workspace_command)?;
         &default_remote
     };
+    let push_refspecs = match workspace_command.settings().remote_settings()?.get(remote) {
+        Some(settings) => GitPushRefSpecs::new(&settings.push)?,
+        None => GitPushRefSpecs::default(),
+    };
 
     let mut tx = workspace_command.start_transaction();
     let view = tx.repo().view();
@@ -340,11 +374,15 @@ pub fn cmd_git_push(
             let allow_delete = false; // doesn't matter
             match classify_bookmark_update(remote_symbol, targets, allow_new, allow_delete) {
                 Ok(Some(update)) => bookmark_updates.push((name.to_owned(), update)),
-                Ok(None) => writeln!(
-                    ui.status(),
-                    "Bookmark {remote_symbol} already matches {name}",
-                    name = name.as_symbol()
-                )?,
+                Ok(None) => {
+                    if let Some(mut formatter) = ui.dry_run_formatter() {
+                        writeln!(
+                            formatter,
+                            "Bookmark {remote_symbol} already matches {name}",
+                            name = name.as_symbol()
+                        )?;
+                    }
+                }
                 Err(reason) => return Err(reason.into()),
             }
         }
@@ -365,11 +403,15 @@ pub fn cmd_git_push(
             let allow_delete = true; // named explicitly, allow delete without --delete
             match classify_bookmark_update(remote_symbol, targets, allow_new, allow_delete) {
                 Ok(Some(update)) => bookmark_updates.push((name.to_owned(), update)),
-                Ok(None) => writeln!(
-                    ui.status(),
-                    "Bookmark {remote_symbol} already matches {name}",
-                    name = name.as_symbol()
-                )?,
+                Ok(None) => {
+                    if let Some(mut formatter) = ui.dry_run_formatter() {
+                        writeln!(
+                            formatter,
+                            "Bookmark {remote_symbol} already matches {name}",
+                            name = name.as_symbol()
+                        )?;
+                    }
+                }
                 Err(reason) => return Err(reason.into()),
             }
         }
@@ -414,9 +456,12 @@ pub fn cmd_git_push(
         );
     }
     if bookmark_updates.is_empty() {
-        writeln!(ui.status(), "Nothing changed.")?;
+        if let Some(mut formatter) = ui.dry_run_formatter() {
+            writeln!(formatter, "Nothing changed.")?;
+        }
         return Ok(());
     }
+    check_protected_remote_deletions(tx.settings(), remote, &bookmark_updates)?;
 
     let sign_behavior = if tx.settings().get_bool("git.sign-on-push")? {
         Some(SignBehavior::Own)
@@ -425,7 +470,8 @@ pub fn cmd_git_push(
     };
     let commits_to_sign =
         validate_commits_ready_to_push(ui, &bookmark_updates, remote, &tx, args, sign_behavior)?;
-    if !args.dry_run
+    let dry_run = command.global_args().dry_run;
+    if !dry_run
         && !commits_to_sign.is_empty()
         && let Some(sign_behavior) = sign_behavior
     {
@@ -452,19 +498,23 @@ pub fn cmd_git_push(
         }
     }
 
-    if let Some(mut formatter) = ui.status_formatter() {
+    if let Some(mut formatter) = ui.dry_run_formatter() {
         writeln!(
             formatter,
             "Changes to push to {remote}:",
             remote = remote.as_symbol()
         )?;
         print_commits_ready_to_push(formatter.as_mut(), tx.repo(), &bookmark_updates)?;
+        if dry_run {
+            writeln!(formatter, "Dry-run requested, not pushing.")?;
+        }
     }
 
-    if args.dry_run {
-        writeln!(ui.status(), "Dry-run requested, not pushing.")?;
+    if dry_run {
+        print_push_results(ui, &tx, remote, &bookmark_updates, None)?;
         return Ok(());
     }
+    confirm_remote_deletions(ui, tx.settings(), remote, &bookmark_updates)?;
 
     let targets = GitBranchPushTargets {
         branch_updates: bookmark_updates,
@@ -475,13 +525,16 @@ pub fn cmd_git_push(
         git_settings.to_subprocess_options(),
         remote,
         &targets,
+        &push_refspecs,
         &mut GitSubprocessUi::new(ui),
     )?;
     print_push_stats(ui, &push_stats)?;
+    print_push_results(ui, &tx, remote, &targets.branch_updates, Some(&push_stats))?;
     // TODO: On partial success, locally-created --change/--named bookmarks will
     // be committed. It's probably better to remove failed local bookmarks.
     if push_stats.all_ok() || push_stats.some_exported() {
         tx.finish(ui, tx_description)?;
+        record_remote_push(&workspace_command, remote, &push_stats)?;
     }
     if push_stats.all_ok() {
         Ok(())
@@ -490,6 +543,135 @@ pub fn cmd_git_push(
     }
 }
 
+/// Returns the names of the bookmarks which will be deleted from the remote.
+fn deleted_bookmark_names(bookmark_updates: &[(RefNameBuf, BookmarkPushUpdate)]) -> Vec<&RefName> {
+    bookmark_updates
+        .iter()
+        .filter(|(_, update)| update.new_target.is_none())
+        .map(|(name, _)| name.as_ref())
+        .collect()
+}
+
+/// Refuses to delete bookmarks from the remotes listed in
+/// `git.protect-remote-deletions`.
+fn check_protected_remote_deletions(
+    settings: &UserSettings,
+    remote: &RemoteName,
+    bookmark_updates: &[(RefNameBuf, BookmarkPushUpdate)],
+) -> Result<(), CommandError> {
+    let deleted_names = deleted_bookmark_names(bookmark_updates);
+    if deleted_names.is_empty() {
+        return Ok(());
+    }
+    let protected_remotes = settings.get::<Vec<String>>("git.protect-remote-deletions")?;
+    if protected_remotes.iter().any(|name| name == remote.as_str()) {
+        return Err(user_error(format!(
+            "Refusing to delete bookmarks from protected remote {remote}: {names}",
+            remote = remote.as_symbol(),
+            names = deleted_names.iter().map(|name| name.as_symbol()).join(", "),
+        ))
+        .hinted(format!(
+            "Remote {remote} is listed in `git.protect-remote-deletions`.",
+            remote = remote.as_symbol(),
+        )));
+    }
+    Ok(())
+}
+
+/// Asks for confirmation if more than `git.max-push-deletions` bookmarks
+/// will be deleted from the remote.
+fn confirm_remote_deletions(
+    ui: &Ui,
+    settings: &UserSettings,
+    remote: &RemoteName,
+    bookmark_updates: &[(RefNameBuf, BookmarkPushUpdate)],
+) -> Result<(), CommandError> {
+    let max_deletions = settings.get::<usize>("git.max-push-deletions")?;
+    let deleted_names = deleted_bookmark_names(bookmark_updates);
+    if max_deletions == 0 || deleted_names.len() <= max_deletions {
+        return Ok(());
+    }
+    writeln!(
+        ui.warning_default(),
+        "The following {count} remote bookmarks will be deleted:",
+        count = deleted_names.len()
+    )?;
+    let mut formatter = ui.stderr_formatter();
+    for name in &deleted_names {
+        write!(formatter, "  ")?;
+        write!(
+            formatter.labeled("bookmark"),
+            "{}",
+            name.to_remote_symbol(remote)
+        )?;
+        writeln!(formatter)?;
+    }
+    drop(formatter);
+    if !ui.prompt_yes_no("Delete these bookmarks from the remote?", Some(false))? {
+        return Err(
+            user_error("Push canceled because of the bookmark deletions").hinted(
+                "Set `git.max-push-deletions` to a larger number, or 0, to push without \
+                 confirmation.",
+            ),
+        );
+    }
+    Ok(())
+}
+
+/// Prints the result of each bookmark update with the
+/// `templates.git_push_result` template. If `push_stats` is `None`, the
+/// updates weren't pushed because of `--dry-run`.
+fn print_push_results(
+    ui: &Ui,
+    tx: &WorkspaceCommandTransaction,
+    remote: &RemoteName,
+    bookmark_updates: &[(RefNameBuf, BookmarkPushUpdate)],
+    push_stats: Option<&GitPushStats>,
+) -> Result<(), CommandError> {
+    let template_text = tx.settings().get_string("templates.git_push_result")?;
+    let language = tx.commit_template_language();
+    let template = tx
+        .base_workspace_helper()
+        .parse_template(ui, &language, &template_text)?;
+    let store = tx.repo().store();
+    let get_commit = |id: &Option<CommitId>| id.as_ref().map(|id| store.get_commit(id)).transpose();
+    let mut formatter = ui.stdout_formatter();
+    for (name, update) in bookmark_updates {
+        let git_ref = format!("refs/heads/{}", name.as_str());
+        let status = if let Some(stats) = push_stats {
+            if stats.pushed.iter().any(|r| r.as_str() == git_ref) {
+                "pushed"
+            } else if stats
+                .remote_rejected
+                .iter()
+                .any(|(r, _)| r.as_str() == git_ref)
+            {
+                "remote-rejected"
+            } else {
+                "rejected"
+            }
+        } else {
+            "dry-run"
+        };
+        // Deleted bookmarks have no page on the forge.
+        let url = if update.new_target.is_some() {
+            get_bookmark_web_url(tx.base_repo(), remote.as_str(), name.as_str())
+        } else {
+            None
+        };
+        let entry = GitPushResultEntry {
+            remote: remote.as_str().to_owned(),
+            bookmark: name.as_str().to_owned(),
+            old_target: get_commit(&update.old_target)?,
+            new_target: get_commit(&update.new_target)?,
+            status: status.to_owned(),
+            url,
+        };
+        template.format(&entry, formatter.as_mut())?;
+    }
+    Ok(())
+}
+
 /// Validates that the commits that will be pushed are ready (have authorship
 /// information, are not conflicted, etc.).
 ///
@@ -525,23 +707,55 @@ fn validate_commits_ready_to_push(
         .parse_revset(ui, &private_revset_str)?
         .evaluate()?
         .containing_fn();
+    let mut skipped_checks = args.skip_check.clone();
+    if args.allow_empty_description {
+        skipped_checks.push(PushCheck::NoEmptyDescription.name().to_owned());
+    }
+    let push_checks =
+        PushChecks::from_settings(settings, [PushCheck::NoEmptyDescription], &skipped_checks)?;
     let sign_settings = sign_behavior.map(|sign_behavior| {
         let mut sign_settings = settings.sign_settings();
         sign_settings.behavior = sign_behavior;
         sign_settings
     });
 
-    let mut commits_to_sign = vec![];
-
-    for commit in workspace_helper
+    let commits: Vec<Commit> = workspace_helper
         .attach_revset_evaluator(commits_to_push)
         .evaluate_to_commits()?
-    {
-        let commit = commit?;
-        let mut reasons = vec![];
-        if commit.description().is_empty() && !args.allow_empty_description {
-            reasons.push("it has no description");
+        .try_collect()?;
+    if !args.allow_drafts {
+        let drafts = find_draft_commits(workspace_helper, &commits)?;
+        if !drafts.is_empty() {
+            let mut error = user_error(tr!(
+                "git.push.draft-commits",
+                "Won't push {count} commits since they are drafts",
+                count = drafts.len(),
+            ));
+            error.add_formatted_hint_with(|formatter| {
+                writeln!(formatter, "Draft commits:")?;
+                for commit in &drafts {
+                    write!(formatter, "  ")?;
+                    workspace_helper.write_commit_summary(formatter, commit)?;
+                    writeln!(formatter)?;
+                }
+                Ok(())
+            });
+            error.add_hint(tr!(
+                "git.push.draft-commits-hint",
+                "Unmark them with `jj draft unset`, or use --allow-drafts to push them anyway."
+            ));
+            return Err(error);
         }
+    }
+
+    let mut commits_to_sign = vec![];
+
+    for commit in commits {
+        let failed_checks = push_checks.run(ui, workspace_helper, &commit)?;
+        let mut reasons = failed_checks
+            .iter()
+            .map(|failure| failure.reason.as_str())
+            .collect_vec();
         if commit.author().name.is_empty()
             || commit.author().email.is_empty()
             || commit.committer().name.is_empty()
@@ -567,6 +781,16 @@ fn validate_commits_ready_to_push(
                 workspace_helper.write_commit_summary(formatter, &commit)?;
                 Ok(())
             });
+            if !failed_checks.is_empty() {
+                error.add_hint(format!(
+                    "Use {} to push it anyway.",
+                    failed_checks
+                        .iter()
+                        .map(|failure| format!("--skip-check={}", failure.name))
+                        .unique()
+                        .join(" ")
+                ));
+            }
             if !args.allow_private && is_private {
                 error.add_hint(format!(
                     "Configured git.private-commits: '{private_revset_str}'",
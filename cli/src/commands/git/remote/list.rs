@@ -12,29 +12,95 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use std::io::Write as _;
-
-use bstr::BString;
+use clap_complete::ArgValueCandidates;
 use gix::Remote;
+use jj_lib::backend::Timestamp;
 use jj_lib::git;
+use jj_lib::object_id::ObjectId as _;
+use jj_lib::ref_name::RemoteName;
+use jj_lib::remote_sync_store::RemoteSyncInfo;
+use jj_lib::remote_sync_store::RemoteSyncRecord;
+use jj_lib::remote_sync_store::RemoteSyncStore;
 use jj_lib::repo::Repo as _;
+use jj_lib::settings::UserSettings;
 
 use crate::cli_util::CommandHelper;
 use crate::command_error::CommandError;
 use crate::command_error::user_error_with_message;
+use crate::complete;
+use crate::generic_templater;
+use crate::generic_templater::GenericTemplateLanguage;
+use crate::templater::TemplatePropertyError;
+use crate::templater::TemplatePropertyExt as _;
+use crate::templater::TemplateRenderer;
 use crate::ui::Ui;
 
 /// List Git remotes
 #[derive(clap::Args, Clone, Debug)]
-pub struct GitRemoteListArgs {}
+pub struct GitRemoteListArgs {
+    /// Render each remote using the given template
+    ///
+    /// The following keywords are available in the template expression:
+    ///
+    /// * `name: String`: Name of the remote.
+    /// * `url: String`: URL to fetch from.
+    /// * `push_url: String`: URL to push to.
+    /// * `fetched: Boolean`: True if the remote has been fetched from.
+    /// * `last_fetch_time: Timestamp`: Time of the last fetch.
+    /// * `last_fetch_operation: String`: ID of the operation of the last fetch.
+    /// * `last_fetch_ref_count: Integer`: Number of remote bookmarks and tags
+    ///   after the last fetch.
+    /// * `pushed: Boolean`: True if the remote has been pushed to.
+    /// * `last_push_time: Timestamp`: Time of the last push.
+    /// * `last_push_operation: String`: ID of the operation of the last push.
+    /// * `last_push_ref_count: Integer`: Number of refs pushed by the last
+    ///   push.
+    ///
+    /// Evaluating a `last_fetch_*` keyword fails if the remote has never been
+    /// fetched from, and a `last_push_*` keyword fails if the remote has never
+    /// been pushed to.
+    ///
+    /// Can be overridden by the `templates.git_remote_list` setting.
+    ///
+    /// See [`jj help -k templates`] for more information.
+    ///
+    /// [`jj help -k templates`]:
+    ///     https://docs.jj-vcs.dev/latest/templates/
+    #[arg(long, short = 'T', verbatim_doc_comment)]
+    #[arg(add = ArgValueCandidates::new(complete::template_aliases))]
+    template: Option<String>,
+}
+
+/// Remote to be rendered by the `git remote list` template.
+#[derive(Clone, Debug, serde::Serialize)]
+pub struct RemoteListEntry {
+    name: String,
+    url: String,
+    push_url: String,
+    #[serde(skip)]
+    sync_info: RemoteSyncInfo,
+}
 
 pub fn cmd_git_remote_list(
     ui: &mut Ui,
     command: &CommandHelper,
-    _args: &GitRemoteListArgs,
+    args: &GitRemoteListArgs,
 ) -> Result<(), CommandError> {
     let workspace_command = command.workspace_helper(ui)?;
+    let template: TemplateRenderer<RemoteListEntry> = {
+        let language = remote_list_template_language(command.settings());
+        let text = match &args.template {
+            Some(value) => value.to_owned(),
+            None => command.settings().get_string("templates.git_remote_list")?,
+        };
+        command
+            .parse_template(ui, &language, &text)?
+            .labeled(["git_remote_list"])
+    };
+    let mut sync_infos = RemoteSyncStore::load(workspace_command.repo_path()).get_all()?;
     let git_repo = git::get_git_repo(workspace_command.repo().store())?;
+    ui.request_pager();
+    let mut formatter = ui.stdout_formatter();
     for remote_name in git_repo.remote_names() {
         let remote = match git_repo.try_find_remote(&*remote_name) {
             Some(Ok(remote)) => remote,
@@ -46,20 +112,102 @@ pub fn cmd_git_remote_list(
             }
             None => continue, // ignore empty [remote "<name>"] section
         };
-        let fetch_url = get_url(&remote, gix::remote::Direction::Fetch);
-        let push_url = get_url(&remote, gix::remote::Direction::Push);
-        if fetch_url == push_url {
-            writeln!(ui.stdout(), "{remote_name} {fetch_url}")?;
-        } else {
-            writeln!(ui.stdout(), "{remote_name} {fetch_url} (push: {push_url})")?;
-        }
+        let name = remote_name.to_string();
+        let entry = RemoteListEntry {
+            url: get_url(&remote, gix::remote::Direction::Fetch),
+            push_url: get_url(&remote, gix::remote::Direction::Push),
+            sync_info: sync_infos
+                .remove(RemoteName::new(&name))
+                .unwrap_or_default(),
+            name,
+        };
+        template.format(&entry, formatter.as_mut())?;
     }
     Ok(())
 }
 
-fn get_url(remote: &Remote, direction: gix::remote::Direction) -> BString {
+fn get_url(remote: &Remote, direction: gix::remote::Direction) -> String {
     remote
         .url(direction)
-        .map(|url| url.to_bstring())
-        .unwrap_or_else(|| "<no URL>".into())
+        .map(|url| url.to_bstring().to_string())
+        .unwrap_or_else(|| "<no URL>".to_owned())
+}
+
+type RemoteListTemplateLanguage = GenericTemplateLanguage<'static, RemoteListEntry>;
+
+generic_templater::impl_self_property_wrapper!(RemoteListEntry);
+
+fn remote_list_template_language(settings: &UserSettings) -> RemoteListTemplateLanguage {
+    let mut language = RemoteListTemplateLanguage::new(settings);
+    language.add_keyword("name", |self_property| {
+        let out_property = self_property.map(|entry| entry.name);
+        Ok(out_property.into_dyn_wrapped())
+    });
+    language.add_keyword("url", |self_property| {
+        let out_property = self_property.map(|entry| entry.url);
+        Ok(out_property.into_dyn_wrapped())
+    });
+    language.add_keyword("push_url", |self_property| {
+        let out_property = self_property.map(|entry| entry.push_url);
+        Ok(out_property.into_dyn_wrapped())
+    });
+    language.add_keyword("fetched", |self_property| {
+        let out_property = self_property.map(|entry| entry.sync_info.last_fetch.is_some());
+        Ok(out_property.into_dyn_wrapped())
+    });
+    language.add_keyword("last_fetch_time", |self_property| {
+        let out_property = self_property.and_then(|entry| last_fetch(entry).map(record_time));
+        Ok(out_property.into_dyn_wrapped())
+    });
+    language.add_keyword("last_fetch_operation", |self_property| {
+        let out_property = self_property.and_then(|entry| last_fetch(entry).map(record_operation));
+        Ok(out_property.into_dyn_wrapped())
+    });
+    language.add_keyword("last_fetch_ref_count", |self_property| {
+        let out_property = self_property.and_then(|entry| last_fetch(entry).map(record_ref_count));
+        Ok(out_property.into_dyn_wrapped())
+    });
+    language.add_keyword("pushed", |self_property| {
+        let out_property = self_property.map(|entry| entry.sync_info.last_push.is_some());
+        Ok(out_property.into_dyn_wrapped())
+    });
+    language.add_keyword("last_push_time", |self_property| {
+        let out_property = self_property.and_then(|entry| last_push(entry).map(record_time));
+        Ok(out_property.into_dyn_wrapped())
+    });
+    language.add_keyword("last_push_operation", |self_property| {
+        let out_property = self_property.and_then(|entry| last_push(entry).map(record_operation));
+        Ok(out_property.into_dyn_wrapped())
+    });
+    language.add_keyword("last_push_ref_count", |self_property| {
+        let out_property = self_property.and_then(|entry| last_push(entry).map(record_ref_count));
+        Ok(out_property.into_dyn_wrapped())
+    });
+    language
+}
+
+fn last_fetch(entry: RemoteListEntry) -> Result<RemoteSyncRecord, TemplatePropertyError> {
+    entry
+        .sync_info
+        .last_fetch
+        .ok_or_else(|| TemplatePropertyError("Remote has never been fetched from".into()))
+}
+
+fn last_push(entry: RemoteListEntry) -> Result<RemoteSyncRecord, TemplatePropertyError> {
+    entry
+        .sync_info
+        .last_push
+        .ok_or_else(|| TemplatePropertyError("Remote has never been pushed to".into()))
+}
+
+fn record_time(record: RemoteSyncRecord) -> Timestamp {
+    record.time
+}
+
+fn record_operation(record: RemoteSyncRecord) -> String {
+    record.operation_id.hex()
+}
+
+fn record_ref_count(record: RemoteSyncRecord) -> i64 {
+    i64::try_from(record.ref_count).unwrap_or(i64::MAX)
 }
@@ -15,6 +15,7 @@
 use clap_complete::ArgValueCandidates;
 use jj_lib::git;
 use jj_lib::ref_name::RemoteNameBuf;
+use jj_lib::remote_sync_store::RemoteSyncStore;
 
 use crate::cli_util::CommandHelper;
 use crate::command_error::CommandError;
@@ -35,12 +36,13 @@ pub fn cmd_git_remote_remove(
     args: &GitRemoteRemoveArgs,
 ) -> Result<(), CommandError> {
     let mut workspace_command = command.workspace_helper(ui)?;
+    let sync_store = RemoteSyncStore::load(workspace_command.repo_path());
     let mut tx = workspace_command.start_transaction();
     git::remove_remote(tx.repo_mut(), &args.remote)?;
+    // Do not print "Nothing changed." for the remote named "git".
     if tx.repo().has_changes() {
-        tx.finish(ui, format!("remove git remote {}", args.remote.as_symbol()))
-    } else {
-        // Do not print "Nothing changed." for the remote named "git".
-        Ok(())
+        tx.finish(ui, format!("remove git remote {}", args.remote.as_symbol()))?;
     }
+    sync_store.remove_remote(&args.remote)?;
+    Ok(())
 }
@@ -15,6 +15,7 @@
 use clap_complete::ArgValueCandidates;
 use jj_lib::git;
 use jj_lib::ref_name::RemoteNameBuf;
+use jj_lib::remote_sync_store::RemoteSyncStore;
 
 use crate::cli_util::CommandHelper;
 use crate::command_error::CommandError;
@@ -38,8 +39,10 @@ pub fn cmd_git_remote_rename(
     args: &GitRemoteRenameArgs,
 ) -> Result<(), CommandError> {
     let mut workspace_command = command.workspace_helper(ui)?;
+    let sync_store = RemoteSyncStore::load(workspace_command.repo_path());
     let mut tx = workspace_command.start_transaction();
     git::rename_remote(tx.repo_mut(), &args.old, &args.new)?;
+    // Do not print "Nothing changed."
     if tx.repo().has_changes() {
         tx.finish(
             ui,
@@ -48,8 +51,8 @@ pub fn cmd_git_remote_rename(
                 old = args.old.as_symbol(),
                 new = args.new.as_symbol()
             ),
-        )
-    } else {
-        Ok(()) // Do not print "Nothing changed."
+        )?;
     }
+    sync_store.rename_remote(&args.old, &args.new)?;
+    Ok(())
 }
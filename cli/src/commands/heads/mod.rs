@@ -0,0 +1,123 @@
+// Copyright 2026 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+mod prune;
+
+use std::sync::Arc;
+
+use clap::Subcommand;
+use clap_complete::ArgValueCandidates;
+use jj_lib::revset::RemoteRefSymbolExpression;
+use jj_lib::revset::RevsetExpression;
+use jj_lib::revset::UserRevsetExpression;
+use jj_lib::str_util::StringExpression;
+
+use self::prune::HeadsPruneArgs;
+use self::prune::cmd_heads_prune;
+use crate::cli_util::CommandHelper;
+use crate::command_error::CommandError;
+use crate::complete;
+use crate::ui::Ui;
+
+/// List anonymous heads
+///
+/// Anonymous heads are visible heads that aren't pointed to by a bookmark, a
+/// remote bookmark, a tag, or a working copy. They usually are leftovers of
+/// unfinished or superseded work, which is easy to lose track of.
+///
+/// The heads are printed using the `templates.heads` template, which by
+/// default shows their age and description, and whether they are divergent.
+#[derive(clap::Args, Clone, Debug)]
+#[command(args_conflicts_with_subcommands = true)]
+pub struct HeadsArgs {
+    #[command(subcommand)]
+    command: Option<HeadsCommand>,
+
+    /// Render each head using the given template
+    ///
+    /// All 0-argument methods of the [`Commit` type] are available as keywords
+    /// in the template expression. See [`jj help -k templates`] for more
+    /// information.
+    ///
+    /// If not specified, this defaults to the `templates.heads` setting.
+    ///
+    /// [`Commit` type]:
+    ///     https://docs.jj-vcs.dev/latest/templates/#commit-type
+    ///
+    /// [`jj help -k templates`]:
+    ///     https://docs.jj-vcs.dev/latest/templates/
+    #[arg(long, short = 'T')]
+    #[arg(add = ArgValueCandidates::new(complete::template_aliases))]
+    template: Option<String>,
+}
+
+#[derive(Subcommand, Clone, Debug)]
+pub enum HeadsCommand {
+    Prune(HeadsPruneArgs),
+}
+
+pub fn cmd_heads(
+    ui: &mut Ui,
+    command: &CommandHelper,
+    args: &HeadsArgs,
+) -> Result<(), CommandError> {
+    match &args.command {
+        Some(HeadsCommand::Prune(args)) => cmd_heads_prune(ui, command, args),
+        None => cmd_heads_list(ui, command, args),
+    }
+}
+
+fn cmd_heads_list(
+    ui: &mut Ui,
+    command: &CommandHelper,
+    args: &HeadsArgs,
+) -> Result<(), CommandError> {
+    let workspace_command = command.workspace_helper(ui)?;
+    let template_text = match &args.template {
+        Some(value) => value.clone(),
+        None => workspace_command.settings().get_string("templates.heads")?,
+    };
+    let template = workspace_command.parse_commit_template(ui, &template_text)?;
+    let heads = workspace_command
+        .attach_revset_evaluator(anonymous_heads_expression())
+        .evaluate_to_commits()?;
+    ui.request_pager();
+    let mut formatter = ui.stdout_formatter();
+    for commit in heads {
+        template.format(&commit?, formatter.as_mut())?;
+    }
+    Ok(())
+}
+
+/// Revisions which keep their ancestors from being anonymous.
+fn named_heads_expression() -> Arc<UserRevsetExpression> {
+    RevsetExpression::union_all(&[
+        RevsetExpression::bookmarks(StringExpression::all()),
+        RevsetExpression::remote_bookmarks(
+            RemoteRefSymbolExpression {
+                name: StringExpression::all(),
+                remote: StringExpression::all(),
+            },
+            None,
+        ),
+        RevsetExpression::tags(StringExpression::all()),
+        RevsetExpression::working_copies(),
+        RevsetExpression::root(),
+    ])
+}
+
+/// Visible heads which aren't pointed to by any ref or working copy.
+fn anonymous_heads_expression() -> Arc<UserRevsetExpression> {
+    RevsetExpression::visible_heads().minus(&named_heads_expression())
+}
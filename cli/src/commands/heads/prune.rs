@@ -0,0 +1,142 @@
+// Copyright 2026 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::io::Write as _;
+use std::time::Duration;
+
+use itertools::Itertools as _;
+use jj_lib::backend::Timestamp;
+use jj_lib::commit::Commit;
+use jj_lib::revset::RevsetExpression;
+
+use super::anonymous_heads_expression;
+use super::named_heads_expression;
+use crate::cli_util::CommandHelper;
+use crate::cli_util::print_updated_commits;
+use crate::command_error::CommandError;
+use crate::ui::Ui;
+
+/// Abandon stale anonymous heads
+///
+/// For each anonymous head whose committer timestamp is older than the given
+/// age, you are asked whether to abandon it. The ancestors of the head which
+/// aren't reachable from any other head, bookmark, tag, or working copy are
+/// abandoned along with it. Immutable revisions are never abandoned.
+///
+/// The abandoned revisions can be restored with `jj undo`.
+#[derive(clap::Args, Clone, Debug)]
+pub struct HeadsPruneArgs {
+    /// Only consider heads older than this age
+    ///
+    /// The age is a number followed by one of the `m`, `h`, `d`, or `w`
+    /// suffixes for minutes, hours, days, or weeks, e.g. `30d`.
+    #[arg(long, value_name = "AGE", value_parser = parse_age)]
+    older_than: Duration,
+}
+
+pub fn cmd_heads_prune(
+    ui: &mut Ui,
+    command: &CommandHelper,
+    args: &HeadsPruneArgs,
+) -> Result<(), CommandError> {
+    let mut workspace_command = command.workspace_helper(ui)?;
+    let age_millis = i64::try_from(args.older_than.as_millis()).unwrap_or(i64::MAX);
+    let cutoff = Timestamp::now().timestamp.0.saturating_sub(age_millis);
+    let stale_heads: Vec<Commit> = workspace_command
+        .attach_revset_evaluator(anonymous_heads_expression())
+        .evaluate_to_commits()?
+        .filter_ok(|commit| commit.committer().timestamp.timestamp.0 < cutoff)
+        .try_collect()?;
+    if stale_heads.is_empty() {
+        writeln!(ui.status(), "No anonymous heads to prune.")?;
+        return Ok(());
+    }
+
+    let mut to_prune = vec![];
+    for commit in &stale_heads {
+        let summary = workspace_command.format_commit_summary(commit);
+        if ui.prompt_yes_no(&format!("Abandon {summary}?"), Some(false))? {
+            to_prune.push(commit.id().clone());
+        }
+    }
+    if to_prune.is_empty() {
+        writeln!(ui.status(), "No revisions to abandon.")?;
+        return Ok(());
+    }
+
+    let num_pruned = to_prune.len();
+    let pruned_expr = RevsetExpression::commits(to_prune);
+    let kept_expr = RevsetExpression::visible_heads()
+        .minus(&pruned_expr)
+        .union(&named_heads_expression());
+    let to_abandon_expr = pruned_expr
+        .ancestors()
+        .minus(&kept_expr.ancestors())
+        .minus(&workspace_command.env().immutable_expression());
+    let to_abandon: Vec<Commit> = workspace_command
+        .attach_revset_evaluator(to_abandon_expr)
+        .evaluate_to_commits()?
+        .try_collect()?;
+
+    let mut tx = workspace_command.start_transaction();
+    for commit in &to_abandon {
+        tx.repo_mut().record_abandoned_commit(commit);
+    }
+    if let Some(mut formatter) = ui.status_formatter() {
+        writeln!(formatter, "Abandoned {} commits:", to_abandon.len())?;
+        print_updated_commits(
+            formatter.as_mut(),
+            &tx.base_workspace_helper().commit_summary_template(),
+            &to_abandon,
+        )?;
+    }
+    tx.finish(ui, format!("prune {num_pruned} anonymous heads"))?;
+    Ok(())
+}
+
+/// Parses an age specified as a number with one of the `m`, `h`, `d`, or `w`
+/// suffixes.
+fn parse_age(s: &str) -> Result<Duration, String> {
+    let unit_secs = match s.chars().last() {
+        Some('m') => 60,
+        Some('h') => 3600,
+        Some('d') => 86400,
+        Some('w') => 7 * 86400,
+        _ => return Err("Expected a number with one of the `m`, `h`, `d`, or `w` suffixes".into()),
+    };
+    let number: u64 = s[..s.len() - 1]
+        .parse()
+        .map_err(|_| format!("Invalid age {s:?}, expected e.g. \"30d\""))?;
+    number
+        .checked_mul(unit_secs)
+        .map(Duration::from_secs)
+        .ok_or_else(|| "Age out of range".into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_age() {
+        assert_eq!(parse_age("30m"), Ok(Duration::from_secs(30 * 60)));
+        assert_eq!(parse_age("2h"), Ok(Duration::from_secs(2 * 3600)));
+        assert_eq!(parse_age("30d"), Ok(Duration::from_secs(30 * 86400)));
+        assert_eq!(parse_age("1w"), Ok(Duration::from_secs(7 * 86400)));
+        assert!(parse_age("30").is_err());
+        assert!(parse_age("d").is_err());
+        assert!(parse_age("-1d").is_err());
+        assert!(parse_age("30y").is_err());
+    }
+}
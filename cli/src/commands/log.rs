@@ -13,21 +13,27 @@
 // limitations under the License.
 
 use std::cmp::min;
+use std::collections::HashMap;
+use std::sync::Arc;
 
 use clap_complete::ArgValueCandidates;
 use clap_complete::ArgValueCompleter;
 use itertools::Itertools as _;
 use jj_lib::backend::CommitId;
+use jj_lib::backend::MillisSinceEpoch;
 use jj_lib::commit::Commit;
 use jj_lib::graph::GraphEdge;
 use jj_lib::graph::GraphEdgeType;
 use jj_lib::graph::TopoGroupedGraphIterator;
+use jj_lib::graph::elide_linear_graph;
 use jj_lib::graph::reverse_graph;
+use jj_lib::graph::sort_graph_by_key;
 use jj_lib::repo::Repo as _;
 use jj_lib::revset::RevsetEvaluationError;
 use jj_lib::revset::RevsetExpression;
 use jj_lib::revset::RevsetFilterPredicate;
 use jj_lib::revset::RevsetIteratorExt as _;
+use jj_lib::store::Store;
 use pollster::FutureExt as _;
 use tracing::instrument;
 
@@ -55,7 +61,9 @@ use crate::ui::Ui;
 ///     https://docs.jj-vcs.dev/latest/revsets/
 ///
 /// Spans of revisions that are not included in the graph per `--revisions` are
-/// rendered as a synthetic node labeled "(elided revisions)".
+/// rendered as a synthetic node labeled "(elided revisions)". With
+/// `--elide-linear`, linear runs of revisions are also collapsed into a
+/// synthetic node labeled with the number of elided commits.
 ///
 /// The working-copy commit is indicated by a `@` symbol in the graph.
 /// [Immutable revisions] have a `◆` symbol. Other commits have a `○` symbol.
@@ -83,8 +91,8 @@ pub(crate) struct LogArgs {
 
     /// Limit number of revisions to show
     ///
-    /// Applied after revisions are filtered and reordered topologically, but
-    /// before being reversed.
+    /// Applied after revisions are filtered and reordered, but before being
+    /// reversed.
     #[arg(long, short = 'n')]
     limit: Option<usize>,
 
@@ -92,10 +100,28 @@ pub(crate) struct LogArgs {
     #[arg(long)]
     reversed: bool,
 
+    /// The order in which revisions are shown
+    ///
+    /// Children are always shown before their parents (or after them with
+    /// `--reversed`), so the graph stays correct with each order.
+    #[arg(long, value_enum, default_value_t = LogOrder::Topo)]
+    order: LogOrder,
+
     /// Don't show the graph, show a flat list of revisions
     #[arg(long, short = 'G')]
     no_graph: bool,
 
+    /// Collapse linear runs of revisions in the graph
+    ///
+    /// Only heads, merges, forks, roots, and revisions in the
+    /// `revsets.log-elide-linear-keep` set (bookmarks, tags, conflicts, and
+    /// working-copy commits by default) are shown. Runs of other revisions
+    /// are replaced by a connector showing the number of elided commits.
+    ///
+    /// The limit set by `--limit` applies to the revisions shown.
+    #[arg(long, visible_alias = "summary-graph", conflicts_with = "no_graph")]
+    elide_linear: bool,
+
     /// Render each revision using the given template
     ///
     /// Run `jj log -T` to list the built-in templates.
@@ -127,13 +153,40 @@ pub(crate) struct LogArgs {
     diff_format: DiffFormatArgs,
 }
 
+#[derive(Clone, Copy, Debug, Eq, PartialEq, clap::ValueEnum)]
+enum LogOrder {
+    /// Group revisions by branch, keeping each branch together
+    Topo,
+    /// Show revisions with newer committer dates first
+    Date,
+    /// Show revisions with newer author dates first
+    DateAuthor,
+}
+
+impl LogOrder {
+    /// Returns the key to sort the commit by. All commits have the same key in
+    /// `topo` order.
+    fn sort_key(
+        self,
+        store: &Arc<Store>,
+        id: &CommitId,
+    ) -> Result<Option<MillisSinceEpoch>, RevsetEvaluationError> {
+        let signature = match self {
+            Self::Topo => return Ok(None),
+            Self::Date => store.get_commit(id)?.committer().clone(),
+            Self::DateAuthor => store.get_commit(id)?.author().clone(),
+        };
+        Ok(Some(signature.timestamp.timestamp))
+    }
+}
+
 #[instrument(skip_all)]
 pub(crate) fn cmd_log(
     ui: &mut Ui,
     command: &CommandHelper,
     args: &LogArgs,
 ) -> Result<(), CommandError> {
-    let workspace_command = command.workspace_helper(ui)?;
+    let workspace_command = command.read_only_workspace_helper(ui)?;
     let settings = workspace_command.settings();
 
     let fileset_expression = workspace_command.parse_file_patterns(ui, &args.paths)?;
@@ -180,6 +233,12 @@ pub(crate) fn cmd_log(
 
     let prio_revset = settings.get_string("revsets.log-graph-prioritize")?;
     let prio_revset = workspace_command.parse_revset(ui, &RevisionArg::from(prio_revset))?;
+    let keep_revset = if args.elide_linear {
+        let keep_revset = settings.get_string("revsets.log-elide-linear-keep")?;
+        Some(workspace_command.parse_revset(ui, &RevisionArg::from(keep_revset))?)
+    } else {
+        None
+    };
 
     let repo = workspace_command.repo();
     let matcher = fileset_expression.to_matcher();
@@ -215,20 +274,41 @@ pub(crate) fn cmd_log(
         if !args.no_graph {
             let mut raw_output = formatter.raw()?;
             let mut graph = get_graphlog(graph_style, raw_output.as_mut());
+            let mut elided_counts = HashMap::new();
             let iter: Box<dyn Iterator<Item = _>> = {
-                let mut forward_iter = TopoGroupedGraphIterator::new(revset.iter_graph(), |id| id);
+                let forward_iter: Box<dyn Iterator<Item = _>> = if args.order == LogOrder::Topo {
+                    let mut forward_iter =
+                        TopoGroupedGraphIterator::new(revset.iter_graph(), |id| id);
 
-                let has_commit = revset.containing_fn();
+                    let has_commit = revset.containing_fn();
 
-                for prio in prio_revset.evaluate_to_commit_ids()? {
-                    let prio = prio?;
-                    if has_commit(&prio)? {
-                        forward_iter.prioritize_branch(prio);
+                    for prio in prio_revset.evaluate_to_commit_ids()? {
+                        let prio = prio?;
+                        if has_commit(&prio)? {
+                            forward_iter.prioritize_branch(prio);
+                        }
                     }
-                }
+                    Box::new(forward_iter)
+                } else {
+                    let nodes = sort_graph_by_key(
+                        revset.iter_graph(),
+                        |id| id,
+                        |id| args.order.sort_key(store, id),
+                    )?;
+                    Box::new(nodes.into_iter().map(Ok))
+                };
 
                 // The input to TopoGroupedGraphIterator shouldn't be truncated
                 // because the prioritized commit must exist in the input set.
+                let forward_iter: Box<dyn Iterator<Item = _>> = match &keep_revset {
+                    Some(keep_revset) => {
+                        let is_kept = keep_revset.evaluate()?.containing_fn();
+                        let elided = elide_linear_graph(forward_iter, |id| id, is_kept)?;
+                        elided_counts = elided.elided_counts;
+                        Box::new(elided.nodes.into_iter().map(Ok))
+                    }
+                    None => Box::new(forward_iter),
+                };
                 let forward_iter = forward_iter.take(args.limit.unwrap_or(usize::MAX));
                 if args.reversed {
                     Box::new(reverse_graph(forward_iter, |id| id)?.into_iter().map(Ok))
@@ -255,8 +335,15 @@ pub(crate) fn cmd_log(
                             graphlog_edges.push(GraphEdge::direct((edge.target, false)));
                         }
                         GraphEdgeType::Indirect => {
-                            if use_elided_nodes {
-                                elided_targets.push(edge.target.clone());
+                            // The edge may have been reversed by --reversed.
+                            let elided_count = elided_counts
+                                .get(&(commit_id.clone(), edge.target.clone()))
+                                .or_else(|| {
+                                    elided_counts.get(&(edge.target.clone(), commit_id.clone()))
+                                })
+                                .copied();
+                            if use_elided_nodes || elided_count.is_some() {
+                                elided_targets.push((edge.target.clone(), elided_count));
                                 graphlog_edges.push(GraphEdge::direct((edge.target, true)));
                             } else {
                                 graphlog_edges.push(GraphEdge::indirect((edge.target, false)));
@@ -301,7 +388,7 @@ pub(crate) fn cmd_log(
                 // TODO: propagate errors
                 explicit_paths.retain(|&path| tree.path_value(path).unwrap().is_absent());
 
-                for elided_target in elided_targets {
+                for (elided_target, elided_count) in elided_targets {
                     let elided_key = (elided_target, true);
                     let real_key = (elided_key.0.clone(), false);
                     let edges = [GraphEdge::direct(real_key)];
@@ -309,7 +396,13 @@ pub(crate) fn cmd_log(
                     let within_graph =
                         with_content_format.sub_width(graph.width(&elided_key, &edges));
                     within_graph.write(ui.new_formatter(&mut buffer).as_mut(), |formatter| {
-                        writeln!(formatter.labeled("elided"), "(elided revisions)")
+                        match elided_count {
+                            Some(1) => writeln!(formatter.labeled("elided"), "· 1 commit ·"),
+                            Some(count) => {
+                                writeln!(formatter.labeled("elided"), "· {count} commits ·")
+                            }
+                            None => writeln!(formatter.labeled("elided"), "(elided revisions)"),
+                        }
                     })?;
                     let node_symbol = format_template(ui, &None, &node_template);
                     graph.add_node(
@@ -322,7 +415,17 @@ pub(crate) fn cmd_log(
             }
         } else {
             let iter: Box<dyn Iterator<Item = Result<CommitId, RevsetEvaluationError>>> = {
-                let forward_iter = revset.iter().take(args.limit.unwrap_or(usize::MAX));
+                let forward_iter: Box<dyn Iterator<Item = _>> = if args.order == LogOrder::Topo {
+                    Box::new(revset.iter())
+                } else {
+                    let nodes = sort_graph_by_key(
+                        revset.iter_graph(),
+                        |id| id,
+                        |id| args.order.sort_key(store, id),
+                    )?;
+                    Box::new(nodes.into_iter().map(|(id, _)| Ok(id)))
+                };
+                let forward_iter = forward_iter.take(args.limit.unwrap_or(usize::MAX));
                 if args.reversed {
                     let entries: Vec<_> = forward_iter.try_collect()?;
                     Box::new(entries.into_iter().rev().map(Ok))
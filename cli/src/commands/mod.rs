@@ -15,6 +15,7 @@
 mod abandon;
 mod absorb;
 mod arrange;
+mod back;
 #[cfg(feature = "bench")]
 mod bench;
 mod bisect;
@@ -25,15 +26,18 @@ mod debug;
 mod describe;
 mod diff;
 mod diffedit;
+mod draft;
 mod duplicate;
 mod edit;
 mod evolog;
 mod file;
+mod fileset;
 mod fix;
 #[cfg(feature = "git")]
 mod gerrit;
 #[cfg(feature = "git")]
 mod git;
+mod heads;
 mod help;
 mod interdiff;
 mod log;
@@ -43,6 +47,7 @@ mod next;
 mod operation;
 mod parallelize;
 mod prev;
+mod prompt;
 mod rebase;
 mod redo;
 mod resolve;
@@ -57,6 +62,8 @@ mod sparse;
 mod split;
 mod squash;
 mod status;
+#[cfg(feature = "git")]
+mod sync_status;
 mod tag;
 mod undo;
 mod unsign;
@@ -76,7 +83,9 @@ use tracing::instrument;
 
 use crate::cli_util::Args;
 use crate::cli_util::CommandHelper;
+use crate::cli_util::command_name;
 use crate::command_error::CommandError;
+use crate::command_error::cli_error;
 use crate::complete;
 use crate::ui::Ui;
 
@@ -95,6 +104,7 @@ enum Command {
     Abandon(abandon::AbandonArgs),
     Absorb(absorb::AbsorbArgs),
     Arrange(arrange::ArrangeArgs),
+    Back(back::BackArgs),
     #[cfg(feature = "bench")]
     #[command(subcommand)]
     Bench(bench::BenchCommand),
@@ -110,12 +120,16 @@ enum Command {
     Describe(describe::DescribeArgs),
     Diff(diff::DiffArgs),
     Diffedit(diffedit::DiffeditArgs),
+    #[command(subcommand)]
+    Draft(draft::DraftCommand),
     Duplicate(duplicate::DuplicateArgs),
     Edit(edit::EditArgs),
     #[command(alias = "obslog", visible_alias = "evolution-log")]
     Evolog(evolog::EvologArgs),
     #[command(subcommand)]
     File(file::FileCommand),
+    #[command(subcommand)]
+    Fileset(fileset::FilesetCommand),
     Fix(fix::FixArgs),
     #[cfg(feature = "git")]
     #[command(subcommand)]
@@ -123,6 +137,7 @@ enum Command {
     #[cfg(feature = "git")]
     #[command(subcommand)]
     Git(git::GitCommand),
+    Heads(heads::HeadsArgs),
     Help(help::HelpArgs),
     Interdiff(interdiff::InterdiffArgs),
     Log(log::LogArgs),
@@ -134,14 +149,13 @@ enum Command {
     Operation(operation::OperationCommand),
     Parallelize(parallelize::ParallelizeArgs),
     Prev(prev::PrevArgs),
+    Prompt(prompt::PromptArgs),
     Rebase(rebase::RebaseArgs),
     Redo(redo::RedoArgs),
     Resolve(resolve::ResolveArgs),
     Restore(restore::RestoreArgs),
     Revert(revert::RevertArgs),
     Root(root::RootArgs),
-    #[command(hide = true)]
-    // TODO: Flesh out.
     Run(run::RunArgs),
     Show(show::ShowArgs),
     Sign(sign::SignArgs),
@@ -151,6 +165,8 @@ enum Command {
     Split(split::SplitArgs),
     Squash(squash::SquashArgs),
     Status(status::StatusArgs),
+    #[cfg(feature = "git")]
+    SyncStatus(sync_status::SyncStatusArgs),
     #[command(subcommand)]
     Tag(tag::TagCommand),
     Undo(undo::UndoArgs),
@@ -162,6 +178,26 @@ enum Command {
     Workspace(workspace::WorkspaceCommand),
 }
 
+impl Command {
+    /// Returns true if the command only changes the repo through
+    /// [`WorkspaceCommandTransaction::finish()`], or checks `--dry-run`
+    /// itself.
+    ///
+    /// [`WorkspaceCommandTransaction::finish()`]:
+    ///     crate::cli_util::WorkspaceCommandTransaction::finish
+    fn supports_dry_run(&self) -> bool {
+        match self {
+            Self::Abandon(_) | Self::Absorb(_) | Self::Rebase(_) | Self::Squash(_) => true,
+            Self::Bookmark(subcommand) => subcommand.supports_dry_run(),
+            #[cfg(feature = "git")]
+            Self::Gerrit(subcommand) => subcommand.supports_dry_run(),
+            #[cfg(feature = "git")]
+            Self::Git(subcommand) => subcommand.supports_dry_run(),
+            _ => false,
+        }
+    }
+}
+
 pub fn default_app() -> clap::Command {
     Command::augment_subcommands(Args::command())
 }
@@ -169,9 +205,14 @@ pub fn default_app() -> clap::Command {
 #[instrument(skip_all)]
 pub fn run_command(ui: &mut Ui, command_helper: &CommandHelper) -> Result<(), CommandError> {
     let subcommand = Command::from_arg_matches(command_helper.matches()).unwrap();
+    if command_helper.global_args().dry_run && !subcommand.supports_dry_run() {
+        let name = command_name(command_helper.matches());
+        return Err(cli_error(format!("`jj {name}` doesn't support --dry-run")));
+    }
     match &subcommand {
         Command::Abandon(args) => abandon::cmd_abandon(ui, command_helper, args),
         Command::Absorb(args) => absorb::cmd_absorb(ui, command_helper, args),
+        Command::Back(args) => back::cmd_back(ui, command_helper, args),
         #[cfg(feature = "bench")]
         Command::Bench(args) => bench::cmd_bench(ui, command_helper, args),
         Command::Bisect(args) => bisect::cmd_bisect(ui, command_helper, args),
@@ -182,15 +223,18 @@ pub fn run_command(ui: &mut Ui, command_helper: &CommandHelper) -> Result<(), Co
         Command::Describe(args) => describe::cmd_describe(ui, command_helper, args),
         Command::Diff(args) => diff::cmd_diff(ui, command_helper, args),
         Command::Diffedit(args) => diffedit::cmd_diffedit(ui, command_helper, args),
+        Command::Draft(args) => draft::cmd_draft(ui, command_helper, args),
         Command::Duplicate(args) => duplicate::cmd_duplicate(ui, command_helper, args),
         Command::Edit(args) => edit::cmd_edit(ui, command_helper, args),
         Command::Evolog(args) => evolog::cmd_evolog(ui, command_helper, args),
         Command::File(args) => file::cmd_file(ui, command_helper, args),
+        Command::Fileset(args) => fileset::cmd_fileset(ui, command_helper, args),
         Command::Fix(args) => fix::cmd_fix(ui, command_helper, args),
         #[cfg(feature = "git")]
         Command::Gerrit(sub_args) => gerrit::cmd_gerrit(ui, command_helper, sub_args),
         #[cfg(feature = "git")]
         Command::Git(args) => git::cmd_git(ui, command_helper, args),
+        Command::Heads(args) => heads::cmd_heads(ui, command_helper, args),
         Command::Help(args) => help::cmd_help(ui, command_helper, args),
         Command::Arrange(args) => arrange::cmd_arrange(ui, command_helper, args),
         Command::Interdiff(args) => interdiff::cmd_interdiff(ui, command_helper, args),
@@ -201,6 +245,7 @@ pub fn run_command(ui: &mut Ui, command_helper: &CommandHelper) -> Result<(), Co
         Command::Operation(args) => operation::cmd_operation(ui, command_helper, args),
         Command::Parallelize(args) => parallelize::cmd_parallelize(ui, command_helper, args),
         Command::Prev(args) => prev::cmd_prev(ui, command_helper, args),
+        Command::Prompt(args) => prompt::cmd_prompt(ui, command_helper, args),
         Command::Rebase(args) => rebase::cmd_rebase(ui, command_helper, args),
         Command::Redo(args) => redo::cmd_redo(ui, command_helper, args),
         Command::Resolve(args) => resolve::cmd_resolve(ui, command_helper, args),
@@ -217,6 +262,8 @@ pub fn run_command(ui: &mut Ui, command_helper: &CommandHelper) -> Result<(), Co
         Command::Split(args) => split::cmd_split(ui, command_helper, args),
         Command::Squash(args) => squash::cmd_squash(ui, command_helper, args),
         Command::Status(args) => status::cmd_status(ui, command_helper, args),
+        #[cfg(feature = "git")]
+        Command::SyncStatus(args) => sync_status::cmd_sync_status(ui, command_helper, args),
         Command::Tag(args) => tag::cmd_tag(ui, command_helper, args),
         Command::Undo(args) => undo::cmd_undo(ui, command_helper, args),
         Command::Unsign(args) => unsign::cmd_unsign(ui, command_helper, args),
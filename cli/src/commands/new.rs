@@ -32,8 +32,10 @@ use crate::cli_util::compute_commit_location;
 use crate::cli_util::merge_args_with;
 use crate::command_error::CommandError;
 use crate::complete;
+use crate::description_util::add_signoff_trailer;
 use crate::description_util::add_trailers;
 use crate::description_util::join_message_paragraphs;
+use crate::description_util::should_signoff;
 use crate::text_util;
 use crate::ui::Ui;
 
@@ -71,6 +73,12 @@ pub(crate) struct NewArgs {
     #[arg(long = "message", short, value_name = "MESSAGE")]
     message_paragraphs: Vec<String>,
 
+    /// Add a `Signed-off-by` trailer for the committer to the description
+    ///
+    /// This can be enabled by default with the `commit.signoff` setting.
+    #[arg(long)]
+    signoff: bool,
+
     /// Do not edit the newly created change
     #[arg(long, conflicts_with = "_edit")]
     no_edit: bool,
@@ -202,6 +210,7 @@ pub(crate) fn cmd_new(
     }
 
     let parent_commit_ids_set: HashSet<CommitId> = parent_commit_ids.iter().cloned().collect();
+    let signoff = should_signoff(workspace_command.settings(), args.signoff)?;
 
     let mut tx = workspace_command.start_transaction();
     let merged_tree = merge_commit_trees(tx.repo(), &parent_commits).block_on()?;
@@ -231,6 +240,10 @@ pub(crate) fn cmd_new(
         commit_builder.set_description(&description);
         let description = add_trailers(ui, &tx, &commit_builder)?;
         commit_builder.set_description(&description);
+        if signoff {
+            let description = add_signoff_trailer(&commit_builder);
+            commit_builder.set_description(description);
+        }
     }
     let new_commit = commit_builder.write(tx.repo_mut()).block_on()?;
 
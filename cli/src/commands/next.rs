@@ -74,6 +74,13 @@ pub(crate) struct NextArgs {
     /// Jump to the next conflicted descendant
     #[arg(long, conflicts_with = "offset")]
     conflict: bool,
+
+    /// Choose the first candidate if there are multiple children instead of
+    /// prompting
+    ///
+    /// The candidates are listed in the same order as in the prompt.
+    #[arg(long)]
+    auto_first: bool,
 }
 
 impl From<&NextArgs> for MovementArgs {
@@ -83,6 +90,7 @@ impl From<&NextArgs> for MovementArgs {
             edit: val.edit,
             no_edit: val.no_edit,
             conflict: val.conflict,
+            auto_first: val.auto_first,
         }
     }
 }
@@ -12,16 +12,35 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::io;
 use std::slice;
+use std::sync::Arc;
 
+use bstr::ByteVec as _;
 use clap_complete::ArgValueCandidates;
+use futures::StreamExt as _;
 use itertools::Itertools as _;
+use jj_lib::backend::CommitId;
 use jj_lib::graph::GraphEdge;
+use jj_lib::graph::GraphNode;
 use jj_lib::graph::reverse_graph;
+use jj_lib::matchers::EverythingMatcher;
+use jj_lib::merged_tree::TreeDiffEntry;
 use jj_lib::op_store::OpStoreError;
+use jj_lib::op_store::OperationId;
 use jj_lib::op_walk;
 use jj_lib::operation::Operation;
+use jj_lib::ref_name::WorkspaceName;
+use jj_lib::ref_name::WorkspaceNameBuf;
+use jj_lib::refs::diff_named_commit_ids;
+use jj_lib::refs::diff_named_ref_targets;
+use jj_lib::repo::ReadonlyRepo;
+use jj_lib::repo::Repo as _;
 use jj_lib::repo::RepoLoader;
+use jj_lib::revset;
 use pollster::FutureExt as _;
 
 use super::diff::show_op_diff;
@@ -35,6 +54,7 @@ use crate::diff_util::DiffFormatArgs;
 use crate::diff_util::DiffRenderer;
 use crate::diff_util::diff_formats_for_log;
 use crate::formatter::Formatter;
+use crate::formatter::FormatterExt as _;
 use crate::graphlog::GraphStyle;
 use crate::graphlog::get_graphlog;
 use crate::operation_templater::OperationTemplateLanguage;
@@ -63,6 +83,21 @@ pub struct OperationLogArgs {
     #[arg(long, short = 'G')]
     no_graph: bool,
 
+    /// Show operations in separate lanes per workspace
+    ///
+    /// Each operation is placed in the lane of the workspace whose
+    /// working-copy commit was changed by the operation. Operations which
+    /// didn't change any working-copy commit share a separate lane. The nodes
+    /// of each workspace lane are colored differently.
+    #[arg(long, conflicts_with = "no_graph")]
+    graph_by_workspace: bool,
+
+    /// Only show operations which changed the working-copy commit of the given
+    /// workspace
+    #[arg(long, value_name = "NAME")]
+    #[arg(add = ArgValueCandidates::new(complete::workspaces))]
+    workspace: Option<WorkspaceNameBuf>,
+
     /// Render each operation using the given template
     ///
     /// You can specify arbitrary template expressions using the
@@ -90,6 +125,16 @@ pub struct OperationLogArgs {
     #[arg(long, short = 'p')]
     patch: bool,
 
+    /// Show a one-line summary of the impact of each operation
+    ///
+    /// The summary counts the commits added and hidden, the local bookmarks
+    /// changed, and the files changed in working-copy commits by the
+    /// operation. It's only computed for the operations shown, so it's
+    /// cheaper than `--op-diff` when looking for the operation which
+    /// introduced a problem.
+    #[arg(long)]
+    diff_stat: bool,
+
     #[command(flatten)]
     diff_format: DiffFormatArgs,
 }
@@ -206,22 +251,46 @@ fn do_op_log(
         None
     };
 
+    let impact_summarizer = args.diff_stat.then(|| OpImpactSummarizer::new(repo_loader));
+
     ui.request_pager();
     let mut formatter = ui.stdout_formatter();
     let formatter = formatter.as_mut();
-    let iter =
-        op_walk::walk_ancestors(slice::from_ref(current_op)).take(args.limit.unwrap_or(usize::MAX));
+    let iter = op_walk::walk_ancestors(slice::from_ref(current_op));
+    let iter: Box<dyn Iterator<Item = _> + '_> = if let Some(name) = &args.workspace {
+        Box::new(iter.filter_map(|op| {
+            op.and_then(|op| Ok(changes_workspace(&op, name)?.then_some(op)))
+                .transpose()
+        }))
+    } else {
+        Box::new(iter)
+    };
+    let iter = iter.take(args.limit.unwrap_or(usize::MAX));
 
     if !args.no_graph {
         let mut raw_output = formatter.raw()?;
         let mut graph = get_graphlog(graph_style, raw_output.as_mut());
-        let iter = iter.map(|op| -> Result<_, OpStoreError> {
-            let op = op?;
-            let ids = op.parent_ids();
-            let edges = ids.iter().cloned().map(GraphEdge::direct).collect();
-            Ok((op, edges))
-        });
-        let iter_nodes: Box<dyn Iterator<Item = _>> = if args.reversed {
+        let mut lane_labels = HashMap::new();
+        let iter: Box<dyn Iterator<Item = _> + '_> =
+            if args.graph_by_workspace || args.workspace.is_some() {
+                let ops: Vec<_> = iter.try_collect()?;
+                let lanes = if args.graph_by_workspace {
+                    workspace_lanes(&ops)?
+                } else {
+                    vec![None; ops.len()]
+                };
+                lane_labels = workspace_lane_labels(&ops, &lanes);
+                let nodes = link_operations_in_lanes(ops, &lanes);
+                Box::new(nodes.into_iter().map(Ok))
+            } else {
+                Box::new(iter.map(|op| -> Result<_, OpStoreError> {
+                    let op = op?;
+                    let ids = op.parent_ids();
+                    let edges = ids.iter().cloned().map(GraphEdge::direct).collect();
+                    Ok((op, edges))
+                }))
+            };
+        let iter_nodes: Box<dyn Iterator<Item = _> + '_> = if args.reversed {
             Box::new(reverse_graph(iter, Operation::id)?.into_iter().map(Ok))
         } else {
             Box::new(iter)
@@ -233,11 +302,28 @@ fn do_op_log(
             within_graph.write(ui.new_formatter(&mut buffer).as_mut(), |formatter| {
                 template.format(&op, formatter)
             })?;
+            if let Some(summarizer) = &impact_summarizer
+                && let Some(impact) = summarizer.summarize(&op)?
+            {
+                within_graph.write(ui.new_formatter(&mut buffer).as_mut(), |formatter| {
+                    impact.write(formatter)
+                })?;
+            }
             if let Some(show) = &maybe_show_op_diff {
                 let mut formatter = ui.new_formatter(&mut buffer);
                 show(ui, formatter.as_mut(), &op, &within_graph)?;
             }
-            let node_symbol = format_template(ui, &op, &op_node_template);
+            let node_symbol = if let Some(label) = lane_labels.get(op.id()) {
+                let mut output = vec![];
+                let mut formatter = ui.new_formatter(&mut output);
+                op_node_template
+                    .format(&op, formatter.labeled(label).as_mut())
+                    .expect("write() to vec backed formatter should never fail");
+                drop(formatter);
+                output.into_string_lossy()
+            } else {
+                format_template(ui, &op, &op_node_template)
+            };
             graph.add_node(
                 op.id(),
                 &edges,
@@ -246,7 +332,7 @@ fn do_op_log(
             )?;
         }
     } else {
-        let iter: Box<dyn Iterator<Item = _>> = if args.reversed {
+        let iter: Box<dyn Iterator<Item = _> + '_> = if args.reversed {
             Box::new(iter.collect_vec().into_iter().rev())
         } else {
             Box::new(iter)
@@ -254,6 +340,11 @@ fn do_op_log(
         for op in iter {
             let op = op?;
             with_content_format.write(formatter, |formatter| template.format(&op, formatter))?;
+            if let Some(summarizer) = &impact_summarizer
+                && let Some(impact) = summarizer.summarize(&op)?
+            {
+                with_content_format.write(formatter, |formatter| impact.write(formatter))?;
+            }
             if let Some(show) = &maybe_show_op_diff {
                 show(ui, formatter, &op, &with_content_format)?;
             }
@@ -262,3 +353,222 @@ fn do_op_log(
 
     Ok(())
 }
+
+/// Summary of the changes made by an operation.
+#[derive(Clone, Debug)]
+struct OpImpact {
+    added_commits: usize,
+    hidden_commits: usize,
+    changed_bookmarks: usize,
+    changed_wc_files: usize,
+}
+
+impl OpImpact {
+    fn write(&self, formatter: &mut dyn Formatter) -> io::Result<()> {
+        let counts = [
+            (self.added_commits, "commit added", "commits added"),
+            (self.hidden_commits, "commit hidden", "commits hidden"),
+            (
+                self.changed_bookmarks,
+                "bookmark changed",
+                "bookmarks changed",
+            ),
+            (
+                self.changed_wc_files,
+                "file changed in working copies",
+                "files changed in working copies",
+            ),
+        ];
+        let parts = counts
+            .iter()
+            .filter(|(count, _, _)| *count > 0)
+            .map(|(count, singular, plural)| {
+                let noun = if *count == 1 { singular } else { plural };
+                format!("{count} {noun}")
+            })
+            .collect_vec();
+        write!(formatter, "Impact: ")?;
+        if parts.is_empty() {
+            writeln!(formatter, "none")
+        } else {
+            writeln!(formatter, "{}", parts.join(", "))
+        }
+    }
+}
+
+/// Computes [`OpImpact`]s of operations.
+///
+/// The repos loaded at the last summarized operation and its parent are
+/// cached, so walking the operation log loads each repo only once.
+struct OpImpactSummarizer<'a> {
+    repo_loader: &'a RepoLoader,
+    repo_cache: RefCell<HashMap<OperationId, Arc<ReadonlyRepo>>>,
+}
+
+impl<'a> OpImpactSummarizer<'a> {
+    fn new(repo_loader: &'a RepoLoader) -> Self {
+        Self {
+            repo_loader,
+            repo_cache: RefCell::new(HashMap::new()),
+        }
+    }
+
+    /// Returns the impact of the `op`, or `None` if the `op` doesn't have
+    /// exactly one parent.
+    fn summarize(&self, op: &Operation) -> Result<Option<OpImpact>, CommandError> {
+        let parent_ops: Vec<_> = op.parents().try_collect()?;
+        // TODO: Merged repo may have newly rebased commits, which wouldn't
+        // exist in the index. (#4465)
+        let [parent_op] = parent_ops.as_slice() else {
+            return Ok(None);
+        };
+        let repo = self.load_repo(op)?;
+        let parent_repo = self.load_repo(parent_op)?;
+        let impact = compute_op_impact(&parent_repo, &repo)?;
+        *self.repo_cache.borrow_mut() = HashMap::from([
+            (op.id().clone(), repo),
+            (parent_op.id().clone(), parent_repo),
+        ]);
+        Ok(Some(impact))
+    }
+
+    fn load_repo(&self, op: &Operation) -> Result<Arc<ReadonlyRepo>, CommandError> {
+        if let Some(repo) = self.repo_cache.borrow().get(op.id()) {
+            return Ok(repo.clone());
+        }
+        Ok(self.repo_loader.load_at(op).block_on()?)
+    }
+}
+
+fn compute_op_impact(
+    from_repo: &ReadonlyRepo,
+    to_repo: &ReadonlyRepo,
+) -> Result<OpImpact, CommandError> {
+    let from_heads: Vec<CommitId> = from_repo.view().heads().iter().cloned().collect();
+    let to_heads: Vec<CommitId> = to_repo.view().heads().iter().cloned().collect();
+    // The index of the newer repo contains the commits of both repos.
+    let count_commits = |wanted: &[CommitId], unwanted: &[CommitId]| {
+        revset::walk_revs(to_repo, wanted, unwanted)?
+            .iter()
+            .process_results(|iter| iter.count())
+    };
+    let added_commits = count_commits(&to_heads, &from_heads)?;
+    let hidden_commits = count_commits(&from_heads, &to_heads)?;
+
+    let changed_bookmarks = diff_named_ref_targets(
+        from_repo.view().local_bookmarks(),
+        to_repo.view().local_bookmarks(),
+    )
+    .count();
+
+    let mut changed_wc_paths = HashSet::new();
+    let changed_working_copies = diff_named_commit_ids(
+        from_repo.view().wc_commit_ids(),
+        to_repo.view().wc_commit_ids(),
+    );
+    for (_, (from_id, to_id)) in changed_working_copies {
+        let (Some(from_id), Some(to_id)) = (from_id, to_id) else {
+            continue;
+        };
+        let from_tree = to_repo.store().get_commit(from_id)?.tree();
+        let to_tree = to_repo.store().get_commit(to_id)?.tree();
+        let paths: Vec<_> = from_tree
+            .diff_stream(&to_tree, &EverythingMatcher)
+            .map(|TreeDiffEntry { path, .. }| path)
+            .collect()
+            .block_on();
+        changed_wc_paths.extend(paths);
+    }
+
+    Ok(OpImpact {
+        added_commits,
+        hidden_commits,
+        changed_bookmarks,
+        changed_wc_files: changed_wc_paths.len(),
+    })
+}
+
+/// Returns the names of the workspaces whose working-copy commit was changed
+/// by the operation.
+fn changed_workspace_names(op: &Operation) -> Result<Vec<WorkspaceNameBuf>, OpStoreError> {
+    let view = op.view().block_on()?;
+    let parent_views: Vec<_> = op
+        .parents()
+        .map(|parent| parent?.view().block_on())
+        .try_collect()?;
+    let names = view
+        .wc_commit_ids()
+        .iter()
+        .filter(|&(name, commit_id)| {
+            parent_views
+                .iter()
+                .all(|parent| parent.get_wc_commit_id(name) != Some(commit_id))
+        })
+        .map(|(name, _)| name.clone())
+        .collect();
+    Ok(names)
+}
+
+fn changes_workspace(op: &Operation, name: &WorkspaceName) -> Result<bool, OpStoreError> {
+    let names = changed_workspace_names(op)?;
+    Ok(names.iter().any(|changed| *changed == name))
+}
+
+/// Number of distinct `workspace_lane_<n>` labels used to color the nodes of
+/// `jj op log --graph-by-workspace`.
+const WORKSPACE_LANE_LABEL_COUNT: usize = 4;
+
+/// Returns the workspace lane of each operation. Operations which didn't
+/// change any working-copy commit get no lane.
+fn workspace_lanes(ops: &[Operation]) -> Result<Vec<Option<WorkspaceNameBuf>>, OpStoreError> {
+    ops.iter()
+        .map(|op| Ok(changed_workspace_names(op)?.into_iter().next()))
+        .try_collect()
+}
+
+/// Assigns a `workspace_lane_<n>` label to each operation in a workspace lane.
+/// Labels are picked in order of the first appearance of each workspace, and
+/// are reused once all of them are taken.
+fn workspace_lane_labels(
+    ops: &[Operation],
+    lanes: &[Option<WorkspaceNameBuf>],
+) -> HashMap<OperationId, String> {
+    let mut lane_indices: HashMap<&WorkspaceName, usize> = HashMap::new();
+    ops.iter()
+        .zip(lanes)
+        .filter_map(|(op, lane)| {
+            let name = lane.as_deref()?;
+            let next_index = lane_indices.len();
+            let index = *lane_indices.entry(name).or_insert(next_index);
+            let label = format!("workspace_lane_{}", index % WORKSPACE_LANE_LABEL_COUNT);
+            Some((op.id().clone(), label))
+        })
+        .collect()
+}
+
+/// Links each operation to the closest older operation in the same lane.
+///
+/// The edge is direct if the older operation is a parent, indirect otherwise.
+fn link_operations_in_lanes(
+    ops: Vec<Operation>,
+    lanes: &[Option<WorkspaceNameBuf>],
+) -> Vec<GraphNode<Operation, OperationId>> {
+    ops.iter()
+        .enumerate()
+        .map(|(i, op)| {
+            let edges = (i + 1..ops.len())
+                .find(|&j| lanes[j] == lanes[i])
+                .map(|j| {
+                    let parent_id = ops[j].id().clone();
+                    if op.parent_ids().contains(&parent_id) {
+                        GraphEdge::direct(parent_id)
+                    } else {
+                        GraphEdge::indirect(parent_id)
+                    }
+                })
+                .into_iter()
+                .collect();
+            (op.clone(), edges)
+        })
+        .collect()
+}
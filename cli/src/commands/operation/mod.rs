@@ -16,9 +16,11 @@ mod abandon;
 mod diff;
 mod integrate;
 mod log;
+mod pin;
 mod restore;
 pub mod revert;
 mod show;
+mod tag;
 
 use abandon::OperationAbandonArgs;
 use abandon::cmd_op_abandon;
@@ -29,12 +31,16 @@ use integrate::OperationIntegrateArgs;
 use integrate::cmd_op_integrate;
 use log::OperationLogArgs;
 use log::cmd_op_log;
+use pin::OperationPinArgs;
+use pin::cmd_op_pin;
 use restore::OperationRestoreArgs;
 use restore::cmd_op_restore;
 use revert::OperationRevertArgs;
 use revert::cmd_op_revert;
 use show::OperationShowArgs;
 use show::cmd_op_show;
+use tag::OperationTagArgs;
+use tag::cmd_op_tag;
 
 use crate::cli_util::CommandHelper;
 use crate::command_error::CommandError;
@@ -53,9 +59,11 @@ pub enum OperationCommand {
     Diff(OperationDiffArgs),
     Integrate(OperationIntegrateArgs),
     Log(OperationLogArgs),
+    Pin(OperationPinArgs),
     Restore(OperationRestoreArgs),
     Revert(OperationRevertArgs),
     Show(OperationShowArgs),
+    Tag(OperationTagArgs),
     // TODO: Delete in jj 0.39.0+
     #[command(hide = true)]
     Undo(OperationRevertArgs),
@@ -71,9 +79,11 @@ pub fn cmd_operation(
         OperationCommand::Diff(args) => cmd_op_diff(ui, command, args),
         OperationCommand::Integrate(args) => cmd_op_integrate(ui, command, args),
         OperationCommand::Log(args) => cmd_op_log(ui, command, args),
+        OperationCommand::Pin(args) => cmd_op_pin(ui, command, args),
         OperationCommand::Restore(args) => cmd_op_restore(ui, command, args),
         OperationCommand::Revert(args) => cmd_op_revert(ui, command, args),
         OperationCommand::Show(args) => cmd_op_show(ui, command, args),
+        OperationCommand::Tag(args) => cmd_op_tag(ui, command, args),
         OperationCommand::Undo(args) => {
             let cmd = renamed_cmd("op undo", "op revert", cmd_op_revert);
             cmd(ui, command, args)
@@ -0,0 +1,47 @@
+// Copyright 2026 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::io::Write as _;
+
+use jj_lib::object_id::ObjectId as _;
+
+use crate::cli_util::CommandHelper;
+use crate::command_error::CommandError;
+use crate::ui::Ui;
+
+/// Print the current operation ID for use in scripts
+///
+/// A script can set the printed ID to the `JJ_AT_OPERATION` environment
+/// variable to run a series of read commands against one consistent snapshot
+/// of the repo, even while other processes create new operations. For example,
+/// run `export JJ_AT_OPERATION=$(jj op pin)` before the queries, and unset the
+/// variable afterwards.
+///
+/// The working copy is snapshotted before the ID is printed. While
+/// `JJ_AT_OPERATION` is set, the working copy is ignored as if `--at-op` were
+/// specified, and commands which would create a new operation fail. The
+/// `--at-op` option takes precedence over the environment variable.
+#[derive(clap::Args, Clone, Debug)]
+pub struct OperationPinArgs {}
+
+pub fn cmd_op_pin(
+    ui: &mut Ui,
+    command: &CommandHelper,
+    _args: &OperationPinArgs,
+) -> Result<(), CommandError> {
+    let workspace_command = command.workspace_helper(ui)?;
+    let op = workspace_command.repo().operation();
+    writeln!(ui.stdout(), "{}", op.id().hex())?;
+    Ok(())
+}
@@ -23,6 +23,7 @@ use super::DEFAULT_REVERT_WHAT;
 use super::RevertWhatToRestore;
 use super::view_with_desired_portions_restored;
 use crate::cli_util::CommandHelper;
+use crate::cli_util::short_operation_hash;
 use crate::command_error::CommandError;
 use crate::command_error::user_error;
 use crate::complete;
@@ -31,12 +32,14 @@ use crate::ui::Ui;
 /// Create a new operation that reverts an earlier operation
 ///
 /// This reverts an individual operation by applying the inverse of the
-/// operation.
+/// operation. A range of operations `A..B` can be reverted at once, which
+/// applies the inverse of all operations after `A` up to and including `B`.
 #[derive(clap::Args, Clone, Debug)]
 pub struct OperationRevertArgs {
-    /// The operation to revert
+    /// The operation or range of operations to revert
     ///
-    /// Use `jj op log` to find an operation to revert.
+    /// Use `jj op log` to find an operation to revert. A range `A..B` must not
+    /// contain merge operations, and `A` must be an ancestor of `B`.
     #[arg(default_value = "@")]
     #[arg(add = ArgValueCandidates::new(complete::operations))]
     pub(crate) operation: String, // pub for `jj undo`
@@ -52,23 +55,42 @@ fn tx_description(op: &Operation) -> String {
     format!("revert operation {}", op.id().hex())
 }
 
+fn range_tx_description(start_op: &Operation, end_op: &Operation) -> String {
+    format!(
+        "revert operations {}..{}",
+        start_op.id().hex(),
+        end_op.id().hex()
+    )
+}
+
 pub fn cmd_op_revert(
     ui: &mut Ui,
     command: &CommandHelper,
     args: &OperationRevertArgs,
 ) -> Result<(), CommandError> {
     let mut workspace_command = command.workspace_helper(ui)?;
-    let bad_op = workspace_command.resolve_single_op(&args.operation)?;
-    let parent_of_bad_op = match bad_op.parents().at_most_one() {
-        Ok(Some(parent_of_bad_op)) => parent_of_bad_op?,
-        Ok(None) => return Err(user_error("Cannot revert root operation")),
-        Err(_) => return Err(user_error("Cannot revert a merge operation")),
+    let range = args.operation.split_once("..");
+    let is_range = range.is_some();
+    let (start_op, bad_ops) = if let Some((start, end)) = range {
+        let start_op = workspace_command.resolve_single_op(start)?;
+        let end_op = workspace_command.resolve_single_op(end)?;
+        let bad_ops = operations_in_range(&start_op, &end_op)?;
+        (start_op, bad_ops)
+    } else {
+        let bad_op = workspace_command.resolve_single_op(&args.operation)?;
+        let parent_of_bad_op = match bad_op.parents().at_most_one() {
+            Ok(Some(parent_of_bad_op)) => parent_of_bad_op?,
+            Ok(None) => return Err(user_error("Cannot revert root operation")),
+            Err(_) => return Err(user_error("Cannot revert a merge operation")),
+        };
+        (parent_of_bad_op, vec![bad_op])
     };
+    let end_op = &bad_ops[0];
 
     let mut tx = workspace_command.start_transaction();
     let repo_loader = tx.base_repo().loader();
-    let bad_repo = repo_loader.load_at(&bad_op).block_on()?;
-    let parent_repo = repo_loader.load_at(&parent_of_bad_op).block_on()?;
+    let bad_repo = repo_loader.load_at(end_op).block_on()?;
+    let parent_repo = repo_loader.load_at(&start_op).block_on()?;
     tx.repo_mut().merge(&bad_repo, &parent_repo).block_on()?;
     let new_view = view_with_desired_portions_restored(
         tx.repo().view().store_view(),
@@ -76,13 +98,88 @@ pub fn cmd_op_revert(
         &args.what,
     );
     tx.repo_mut().set_view(new_view);
+
+    if is_range {
+        // Bookmarks which were also moved by later operations can't be
+        // reverted cleanly.
+        let base_view = tx.base_repo().view();
+        let conflicted_names = tx
+            .repo()
+            .view()
+            .local_bookmarks()
+            .filter(|(name, target)| {
+                target.has_conflict() && !base_view.get_local_bookmark(name).has_conflict()
+            })
+            .map(|(name, _)| name.as_symbol().to_string())
+            .collect_vec();
+        if !conflicted_names.is_empty() {
+            writeln!(
+                ui.warning_default(),
+                "Later operations also changed these bookmarks, which are now conflicted: {}",
+                conflicted_names.join(", ")
+            )?;
+            writeln!(
+                ui.hint_default(),
+                "Use `jj bookmark resolve` to resolve them, or revert the later operations too."
+            )?;
+        }
+    }
+
     if let Some(mut formatter) = ui.status_formatter() {
-        write!(formatter, "Reverted operation: ")?;
         let template = tx.base_workspace_helper().operation_summary_template();
-        template.format(&bad_op, formatter.as_mut())?;
-        writeln!(formatter)?;
+        if is_range {
+            writeln!(formatter, "Reverted {} operations:", bad_ops.len())?;
+            for op in &bad_ops {
+                write!(formatter, "  ")?;
+                template.format(op, formatter.as_mut())?;
+                writeln!(formatter)?;
+            }
+        } else {
+            write!(formatter, "Reverted operation: ")?;
+            template.format(end_op, formatter.as_mut())?;
+            writeln!(formatter)?;
+        }
     }
-    tx.finish(ui, tx_description(&bad_op))?;
+    let description = if is_range {
+        range_tx_description(&start_op, end_op)
+    } else {
+        tx_description(end_op)
+    };
+    tx.finish(ui, description)?;
 
     Ok(())
 }
+
+/// Returns the operations after `start_op` up to and including `end_op`,
+/// newest first.
+fn operations_in_range(
+    start_op: &Operation,
+    end_op: &Operation,
+) -> Result<Vec<Operation>, CommandError> {
+    let mut ops = vec![];
+    let mut op = end_op.clone();
+    while op.id() != start_op.id() {
+        let parent_op = match op.parents().at_most_one() {
+            Ok(Some(parent_op)) => parent_op?,
+            Ok(None) => {
+                return Err(user_error(format!(
+                    "Operation {} is not an ancestor of operation {}",
+                    short_operation_hash(start_op.id()),
+                    short_operation_hash(end_op.id()),
+                )));
+            }
+            Err(_) => {
+                return Err(user_error(format!(
+                    "Cannot revert a range containing merge operation {}",
+                    short_operation_hash(op.id()),
+                )));
+            }
+        };
+        ops.push(op);
+        op = parent_op;
+    }
+    if ops.is_empty() {
+        return Err(user_error("The range of operations to revert is empty"));
+    }
+    Ok(ops)
+}
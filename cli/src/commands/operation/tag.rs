@@ -0,0 +1,175 @@
+// Copyright 2026 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use clap::Subcommand;
+use clap_complete::ArgValueCandidates;
+use jj_lib::operation::Operation;
+use jj_lib::repo::Repo as _;
+use pollster::FutureExt as _;
+
+use crate::cli_util::CommandHelper;
+use crate::command_error::CommandError;
+use crate::command_error::user_error;
+use crate::complete;
+use crate::formatter::FormatterExt as _;
+use crate::ui::Ui;
+
+/// Give a name to an operation
+///
+/// The tag name can be used anywhere an operation ID is accepted, for example
+/// `jj op restore <name>` or `jj --at-op <name> log`. This makes it easy to
+/// create a save point before a risky change, without having to copy the
+/// operation ID.
+///
+/// Tags aren't recorded in the operation log, so `jj undo` doesn't affect
+/// them. Tagged operations are kept by `jj util gc` even after they are
+/// abandoned.
+#[derive(clap::Args, Clone, Debug)]
+#[command(args_conflicts_with_subcommands = true, subcommand_negates_reqs = true)]
+pub struct OperationTagArgs {
+    #[command(subcommand)]
+    command: Option<OperationTagCommand>,
+
+    /// The name of the tag to create
+    #[arg(required = true, value_parser = parse_tag_name)]
+    name: Option<String>,
+
+    /// The operation to tag
+    #[arg(long, short, default_value = "@")]
+    #[arg(add = ArgValueCandidates::new(complete::operations))]
+    operation: String,
+}
+
+#[derive(Subcommand, Clone, Debug)]
+pub enum OperationTagCommand {
+    Delete(OperationTagDeleteArgs),
+    List(OperationTagListArgs),
+}
+
+/// Delete operation tags
+///
+/// The tagged operations are not abandoned.
+#[derive(clap::Args, Clone, Debug)]
+pub struct OperationTagDeleteArgs {
+    /// The tags to delete
+    #[arg(required = true)]
+    names: Vec<String>,
+}
+
+/// List operation tags
+#[derive(clap::Args, Clone, Debug)]
+pub struct OperationTagListArgs {}
+
+pub fn cmd_op_tag(
+    ui: &mut Ui,
+    command: &CommandHelper,
+    args: &OperationTagArgs,
+) -> Result<(), CommandError> {
+    match &args.command {
+        Some(OperationTagCommand::Delete(args)) => cmd_op_tag_delete(ui, command, args),
+        Some(OperationTagCommand::List(args)) => cmd_op_tag_list(ui, command, args),
+        None => cmd_op_tag_create(ui, command, args),
+    }
+}
+
+fn cmd_op_tag_create(
+    ui: &mut Ui,
+    command: &CommandHelper,
+    args: &OperationTagArgs,
+) -> Result<(), CommandError> {
+    let workspace_command = command.workspace_helper(ui)?;
+    let name = args.name.as_ref().expect("name should be required");
+    let op = workspace_command.resolve_single_op(&args.operation)?;
+    let op_store = workspace_command.repo().op_store();
+    if op_store.get_operation_tags()?.contains_key(name) {
+        return Err(
+            user_error(format!("Operation tag {name} already exists")).hinted(format!(
+                "Use `jj op tag delete {name}` first to move the tag."
+            )),
+        );
+    }
+    op_store.set_operation_tag(name, Some(op.id()))?;
+    if let Some(mut formatter) = ui.status_formatter() {
+        write!(formatter, "Created operation tag {name} at ")?;
+        let template = workspace_command.operation_summary_template();
+        template.format(&op, formatter.as_mut())?;
+        writeln!(formatter)?;
+    }
+    Ok(())
+}
+
+fn cmd_op_tag_delete(
+    ui: &mut Ui,
+    command: &CommandHelper,
+    args: &OperationTagDeleteArgs,
+) -> Result<(), CommandError> {
+    let workspace_command = command.workspace_helper_no_snapshot(ui)?;
+    let op_store = workspace_command.repo().op_store();
+    let tags = op_store.get_operation_tags()?;
+    let mut num_deleted = 0;
+    for name in &args.names {
+        if !tags.contains_key(name) {
+            writeln!(ui.warning_default(), "No such operation tag: {name}")?;
+            continue;
+        }
+        op_store.set_operation_tag(name, None)?;
+        num_deleted += 1;
+    }
+    if num_deleted > 0 {
+        writeln!(ui.status(), "Deleted {num_deleted} operation tags.")?;
+    }
+    Ok(())
+}
+
+fn cmd_op_tag_list(
+    ui: &mut Ui,
+    command: &CommandHelper,
+    _args: &OperationTagListArgs,
+) -> Result<(), CommandError> {
+    let workspace_command = command.workspace_helper_no_snapshot(ui)?;
+    let op_store = workspace_command.repo().op_store();
+    let template = workspace_command.operation_summary_template();
+
+    ui.request_pager();
+    let mut formatter = ui.stdout_formatter();
+    for (name, op_id) in op_store.get_operation_tags()? {
+        let data = op_store.read_operation(&op_id).block_on()?;
+        let op = Operation::new(op_store.clone(), op_id, data);
+        write!(formatter.labeled("operation_tag"), "{name}")?;
+        write!(formatter, ": ")?;
+        template.format(&op, formatter.as_mut())?;
+        writeln!(formatter)?;
+    }
+    Ok(())
+}
+
+/// Rejects tag names which can't be told apart from operation expressions.
+fn parse_tag_name(name: &str) -> Result<String, String> {
+    if name.is_empty() {
+        Err("Tag name must not be empty".to_owned())
+    } else if name.starts_with(['.', '@']) || name.ends_with(['-', '+']) {
+        Err("Tag name must not start with `.` or `@`, or end with `-` or `+`".to_owned())
+    } else if name.contains(['/', '\\']) || name.contains("..") {
+        Err("Tag name must not contain `/`, `\\`, or `..`".to_owned())
+    } else if name.chars().any(char::is_whitespace) {
+        Err("Tag name must not contain whitespace".to_owned())
+    } else if name.chars().all(|c| c.is_ascii_hexdigit()) {
+        Err(
+            "Tag name must not be a hexadecimal string, which looks like an operation ID"
+                .to_owned(),
+        )
+    } else {
+        Ok(name.to_owned())
+    }
+}
@@ -70,6 +70,13 @@ pub(crate) struct PrevArgs {
     /// Jump to the previous conflicted ancestor
     #[arg(long, conflicts_with = "offset")]
     conflict: bool,
+
+    /// Choose the first candidate if there are multiple parents instead of
+    /// prompting
+    ///
+    /// The candidates are listed in the same order as in the prompt.
+    #[arg(long)]
+    auto_first: bool,
 }
 
 impl From<&PrevArgs> for MovementArgs {
@@ -79,6 +86,7 @@ impl From<&PrevArgs> for MovementArgs {
             edit: val.edit,
             no_edit: val.no_edit,
             conflict: val.conflict,
+            auto_first: val.auto_first,
         }
     }
 }
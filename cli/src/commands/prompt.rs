@@ -0,0 +1,131 @@
+// Copyright 2026 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::io::Write as _;
+use std::time::Duration;
+use std::time::Instant;
+
+use clap_complete::ArgValueCandidates;
+use itertools::Itertools as _;
+use jj_lib::repo::Repo as _;
+use pollster::FutureExt as _;
+use tracing::instrument;
+
+use crate::cli_util::CommandHelper;
+use crate::cli_util::format_template;
+use crate::command_error::CommandError;
+use crate::complete;
+use crate::ui::Ui;
+
+/// Print a short summary of the working-copy commit for shell prompts
+///
+/// Unlike other commands, `jj prompt` never snapshots the working copy and
+/// never starts a pager, so the output reflects the last snapshot taken by
+/// another command.
+///
+/// The working-copy commit is rendered with the `templates.prompt` template.
+/// If `templates.prompt_op` is set, the current operation is rendered with it
+/// and appended.
+///
+/// If the output can't be produced within `prompt.timeout-ms` milliseconds,
+/// `prompt.fallback` is printed instead. The fallback is also printed if
+/// concurrent operations need to be reconciled, since that would write to the
+/// repo.
+#[derive(clap::Args, Clone, Debug)]
+#[command(verbatim_doc_comment)]
+pub(crate) struct PromptArgs {
+    /// Render the working-copy commit using the given template
+    ///
+    /// You can specify arbitrary template expressions using the
+    /// [built-in keywords]. See [`jj help -k templates`] for more information.
+    ///
+    /// [built-in keywords]:
+    ///     https://docs.jj-vcs.dev/latest/templates/#commit-keywords
+    ///
+    /// [`jj help -k templates`]:
+    ///     https://docs.jj-vcs.dev/latest/templates/
+    #[arg(long, short = 'T')]
+    #[arg(add = ArgValueCandidates::new(complete::template_aliases))]
+    template: Option<String>,
+}
+
+#[instrument(skip_all)]
+pub(crate) fn cmd_prompt(
+    ui: &mut Ui,
+    command: &CommandHelper,
+    args: &PromptArgs,
+) -> Result<(), CommandError> {
+    let settings = command.settings();
+    let timeout_ms: u64 = settings.get("prompt.timeout-ms")?;
+    let fallback = settings.get_string("prompt.fallback")?;
+    // The timeout is checked between the steps below. A slow step can't be
+    // interrupted, but nothing is left behind if the command gives up.
+    let deadline = (timeout_ms > 0).then(|| Instant::now() + Duration::from_millis(timeout_ms));
+    let timed_out = || deadline.is_some_and(|deadline| Instant::now() > deadline);
+    let print_fallback = |ui: &Ui| -> Result<(), CommandError> {
+        writeln!(ui.stdout(), "{fallback}")?;
+        Ok(())
+    };
+
+    // Load the repo without snapshotting the working copy or reconciling
+    // divergent operations, both of which would write to the repo.
+    let workspace = command.load_workspace()?;
+    let repo_loader = workspace.repo_loader();
+    let op = if command.global_args().at_operation.is_some() {
+        command.resolve_operation(ui, repo_loader)?
+    } else {
+        let op_heads = repo_loader.op_heads_store().get_op_heads().block_on()?;
+        let [op_id] = op_heads.as_slice() else {
+            return print_fallback(ui);
+        };
+        repo_loader.load_operation(op_id).block_on()?
+    };
+    let repo = repo_loader.load_at(&op).block_on()?;
+    let workspace_command = command.for_workable_repo(ui, workspace, repo)?;
+    if timed_out() {
+        return print_fallback(ui);
+    }
+
+    let commit_template_text = match &args.template {
+        Some(value) => value.clone(),
+        None => workspace_command
+            .settings()
+            .get_string("templates.prompt")?,
+    };
+    let op_template_text = workspace_command
+        .settings()
+        .get_string("templates.prompt_op")?;
+
+    let mut segments = vec![];
+    if let Some(wc_commit_id) = workspace_command.get_wc_commit_id() {
+        let commit = workspace_command.repo().store().get_commit(wc_commit_id)?;
+        let template = workspace_command
+            .parse_commit_template(ui, &commit_template_text)?
+            .labeled(["prompt", "commit"]);
+        segments.push(format_template(ui, &commit, &template));
+    }
+    if !op_template_text.is_empty() {
+        let template = workspace_command
+            .parse_operation_template(ui, &op_template_text)?
+            .labeled(["prompt", "operation"]);
+        let op = workspace_command.repo().operation();
+        segments.push(format_template(ui, op, &template));
+    }
+    if timed_out() {
+        return print_fallback(ui);
+    }
+    let output = segments.iter().filter(|s| !s.is_empty()).join(" ");
+    writeln!(ui.stdout(), "{output}")?;
+    Ok(())
+}
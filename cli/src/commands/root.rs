@@ -13,8 +13,11 @@
 // limitations under the License.
 
 use std::io::Write as _;
+use std::path::Path;
+use std::path::PathBuf;
 
 use jj_lib::file_util;
+use jj_lib::repo::RepoLoader;
 use tracing::instrument;
 
 use crate::cli_util::CommandHelper;
@@ -23,18 +26,126 @@ use crate::command_error::user_error;
 use crate::ui::Ui;
 
 /// Show the current workspace root directory (shortcut for `jj workspace root`)
+///
+/// The other options show the paths to the internal parts of the repo instead,
+/// so that scripts don't have to guess the layout under `.jj`. `--all` shows
+/// all of them as `name: path` lines, or as a JSON object with `--json`.
 #[derive(clap::Args, Clone, Debug)]
-pub(crate) struct RootArgs {}
+#[command(group(clap::ArgGroup::new("path").args([
+    "workspace",
+    "repo",
+    "store",
+    "op_store",
+    "repo_config_dir",
+    "all",
+])))]
+pub(crate) struct RootArgs {
+    /// Show the workspace root directory (the default)
+    #[arg(long)]
+    workspace: bool,
+
+    /// Show the repo directory, which is usually `.jj/repo`
+    ///
+    /// Workspaces added by `jj workspace add` share the repo directory of the
+    /// workspace they were added from.
+    #[arg(long)]
+    repo: bool,
+
+    /// Show the directory where commits are stored
+    #[arg(long)]
+    store: bool,
+
+    /// Show the directory where operations are stored
+    #[arg(long)]
+    op_store: bool,
+
+    /// Show the directory containing the repo-specific config file
+    ///
+    /// This directory lives in the user config directory, not in the repo. See
+    /// also `jj config path --repo`.
+    #[arg(long)]
+    repo_config_dir: bool,
+
+    /// Show all paths
+    #[arg(long)]
+    all: bool,
+
+    /// Print the paths as a JSON object
+    #[arg(long, requires = "all")]
+    json: bool,
+}
 
 #[instrument(skip_all)]
 pub(crate) fn cmd_root(
     ui: &mut Ui,
     command: &CommandHelper,
-    RootArgs {}: &RootArgs,
+    args: &RootArgs,
 ) -> Result<(), CommandError> {
     let loader = command.workspace_loader()?;
-    let path_bytes = file_util::path_to_bytes(loader.workspace_root()).map_err(user_error)?;
+    let workspace_root = loader.workspace_root();
+    let repo_path = loader.repo_path();
+    let repo_config_dir = || -> Result<Option<PathBuf>, CommandError> {
+        let config_path = command.config_env().maybe_repo_config_path(ui)?;
+        Ok(config_path.and_then(|path| path.parent().map(Path::to_owned)))
+    };
+
+    if args.all {
+        let paths = [
+            ("workspace_root", Some(workspace_root.to_owned())),
+            ("repo", Some(repo_path.to_owned())),
+            ("store", Some(RepoLoader::store_path(repo_path))),
+            ("op_store", Some(RepoLoader::op_store_path(repo_path))),
+            ("repo_config_dir", repo_config_dir()?),
+        ];
+        if args.json {
+            let mut map = serde_json::Map::new();
+            for (name, path) in paths {
+                let value = match path {
+                    Some(path) => path_to_json(&path)?,
+                    None => serde_json::Value::Null,
+                };
+                map.insert(name.to_owned(), value);
+            }
+            writeln!(ui.stdout(), "{}", serde_json::Value::Object(map))?;
+        } else {
+            for (name, path) in paths {
+                let Some(path) = path else {
+                    continue;
+                };
+                write!(ui.stdout(), "{name}: ")?;
+                write_path(ui, &path)?;
+            }
+        }
+        return Ok(());
+    }
+
+    let path = if args.repo {
+        repo_path.to_owned()
+    } else if args.store {
+        RepoLoader::store_path(repo_path)
+    } else if args.op_store {
+        RepoLoader::op_store_path(repo_path)
+    } else if args.repo_config_dir {
+        repo_config_dir()?.ok_or_else(|| {
+            user_error("No repo-specific config directory exists yet")
+                .hinted("Run `jj config path --repo` to create one.")
+        })?
+    } else {
+        workspace_root.to_owned()
+    };
+    write_path(ui, &path)
+}
+
+fn write_path(ui: &Ui, path: &Path) -> Result<(), CommandError> {
+    let path_bytes = file_util::path_to_bytes(path).map_err(user_error)?;
     ui.stdout().write_all(path_bytes)?;
     writeln!(ui.stdout())?;
     Ok(())
 }
+
+fn path_to_json(path: &Path) -> Result<serde_json::Value, CommandError> {
+    let path_str = path
+        .to_str()
+        .ok_or_else(|| user_error(format!("Path is not valid UTF-8: {}", path.display())))?;
+    Ok(path_str.into())
+}
@@ -14,60 +14,388 @@
 
 //! This file contains the internal implementation of `run`.
 
+use std::collections::HashMap;
+use std::collections::hash_map::Entry;
+use std::fs;
+use std::fs::File;
+use std::io;
+use std::io::Write as _;
+use std::path::PathBuf;
+use std::process::Command;
+use std::process::ExitStatus;
+use std::process::Stdio;
+use std::sync::Mutex;
+
+use clap_complete::ArgValueCompleter;
 use itertools::Itertools as _;
+use jj_lib::commit::Commit;
+use jj_lib::default_working_copy_store::DefaultWorkingCopyStore;
+use jj_lib::file_util::IoResultExt as _;
+use jj_lib::merged_tree::MergedTree;
+use jj_lib::object_id::ObjectId as _;
+use jj_lib::op_store::OperationId;
+use jj_lib::repo::Repo as _;
+use jj_lib::working_copy::SnapshotOptions;
+use jj_lib::working_copy_store::StoredWorkingCopy;
+use jj_lib::working_copy_store::WorkingCopyStore as _;
+use jj_lib::working_copy_store::WorkingCopyStoreError;
+use pollster::FutureExt as _;
 
 use crate::cli_util::CommandHelper;
 use crate::cli_util::RevisionArg;
 use crate::command_error::CommandError;
 use crate::command_error::user_error;
+use crate::command_error::user_error_with_message;
+use crate::complete;
+use crate::config::CommandNameAndArgs;
 use crate::ui::Ui;
 
-/// (**Stub**, does not work yet) Run a command across a set of revisions.
+/// Run a command across a set of revisions
+///
+/// The command is run in a separate working copy of each revision, so the
+/// working copy of the workspace isn't touched. The `JJ_COMMIT_ID` and
+/// `JJ_CHANGE_ID` environment variables are set to the IDs of the revision.
+/// Changes made to the files by the command aren't recorded unless `--rewrite`
+/// is passed.
+///
+/// The working copies are kept in the `.jj/repo/run` directory, and are
+/// reused when the command is run again against revisions with the same
+/// contents. Changes left in a reused working copy by earlier runs are
+/// reverted before the command is run, except for ignored files, so ignored
+/// build outputs are kept around. Use `jj run --clean` to remove them.
 ///
+/// The commands are run in parallel, up to `--jobs` at a time. The output of
+/// each command is saved in the `output` directory next to its working copy,
+/// and is printed once all commands have finished, followed by a summary of
+/// the exit codes per revision.
 ///
-/// All recorded state will be persisted in the `.jj` directory, so occasionally
-/// a `jj run --clean` is needed to clean up disk space.
+/// For example, to run the tests of all your local revisions, four at a time:
 ///
-/// # Example
+/// ```shell
+/// jj run 'cargo test' -r 'trunk()..@' -j 4
+/// ```
 ///
-/// # Run pre-commit on your local work
-/// $ jj run 'pre-commit run .github/pre-commit.yaml' -r (trunk()..@) -j 4
+/// With `--rewrite`, the working copy of each revision is snapshotted after the
+/// command succeeds, and the revision is rewritten with the resulting files.
+/// Descendants are rebased onto the rewritten revisions. Revisions with the
+/// same contents share a working copy, which is reset to the revision before
+/// the command is run for the next one. For example, to format all your
+/// mutable revisions:
 ///
-/// This allows pre-commit integration and other funny stuff.
+/// ```shell
+/// jj run 'cargo fmt' -r 'mutable()' --rewrite
+/// ```
 #[derive(clap::Args, Clone, Debug)]
 #[command(verbatim_doc_comment)]
 pub struct RunArgs {
-    /// The command to run across all selected revisions.
-    shell_command: String,
+    /// The command to run across all selected revisions
+    #[arg(required_unless_present = "clean")]
+    shell_command: Option<String>,
 
-    /// The revisions to change.
+    /// The revisions to run the command against
     #[arg(long, short, default_value = "@", value_name = "REVSETS")]
+    #[arg(add = ArgValueCompleter::new(complete::revset_expression_all))]
     revisions: Vec<RevisionArg>,
 
     /// A no-op option to match the interface of `git rebase -x`.
     #[arg(short = 'x', hide = true)]
     unused_command: bool,
 
-    /// How many processes should run in parallel, uses by default all cores.
+    /// How many processes should run in parallel, uses by default all cores
     #[arg(long, short)]
     jobs: Option<usize>,
+
+    /// Rewrite the revisions with the changes made by the command
+    #[arg(long)]
+    rewrite: bool,
+
+    /// Remove all cached working copies instead of running a command
+    ///
+    /// Working copies in use by another `jj run` are kept.
+    #[arg(long, conflicts_with_all = ["shell_command", "revisions", "jobs", "rewrite"])]
+    clean: bool,
 }
 
 pub fn cmd_run(ui: &mut Ui, command: &CommandHelper, args: &RunArgs) -> Result<(), CommandError> {
-    let workspace_command = command.workspace_helper(ui)?;
-    let _resolved_commits: Vec<_> = workspace_command
+    let mut workspace_command = command.workspace_helper(ui)?;
+    let working_copy_store = DefaultWorkingCopyStore::load(
+        workspace_command.repo().store().clone(),
+        workspace_command.repo_path().join("run"),
+        workspace_command.settings(),
+    );
+    if args.clean {
+        let count = working_copy_store.clean()?;
+        writeln!(ui.status(), "Removed {count} cached working copies")?;
+        return Ok(());
+    }
+    let shell_command = CommandNameAndArgs::from(args.shell_command.as_deref().unwrap());
+
+    let commits: Vec<Commit> = workspace_command
         .parse_union_revsets(ui, &args.revisions)?
         .evaluate_to_commits()?
         .try_collect()?;
+    if args.rewrite {
+        workspace_command.check_rewritable(commits.iter().map(Commit::id))?;
+    }
+    // Reused working copies are snapshotted to reset them, so the options are
+    // needed even without `--rewrite`.
+    let auto_tracking_matcher = workspace_command.auto_tracking_matcher(ui)?;
+    let ignore_matcher = workspace_command.ignore_matcher(ui)?;
+    let snapshot_options = workspace_command.snapshot_options_with_start_tracking_matcher(
+        auto_tracking_matcher.as_ref(),
+        ignore_matcher.as_ref(),
+    )?;
     // Jobs are resolved in this order:
     // 1. Commandline argument iff > 0.
     // 2. the amount of cores available.
     // 3. a single job, if all of the above fails.
-    let _jobs = match args.jobs {
+    let jobs = match args.jobs {
         Some(0) | None => std::thread::available_parallelism().map(|t| t.into()).ok(),
         Some(jobs) => Some(jobs),
     }
     // Fallback to a single user-visible job.
     .unwrap_or(1usize);
-    Err(user_error("This is a stub, do not use"))
+
+    // The working copies are checked out one by one, and the commands are run
+    // in parallel once all of them are ready.
+    let operation_id = workspace_command.repo().op_id().clone();
+    // Revisions with the same contents share a working copy, which is locked
+    // while it's in use, so each working copy is requested only once.
+    let mut group_indices = HashMap::new();
+    let mut groups: Vec<RunGroup> = vec![];
+    for (index, commit) in commits.iter().enumerate() {
+        let group_index = match group_indices.entry(commit.tree_ids()) {
+            Entry::Occupied(entry) => *entry.get(),
+            Entry::Vacant(entry) => {
+                let stored =
+                    working_copy_store.get_or_create_working_copy(commit, &operation_id)?;
+                groups.push(RunGroup {
+                    stored,
+                    jobs: vec![],
+                });
+                *entry.insert(groups.len() - 1)
+            }
+        };
+        let group = &mut groups[group_index];
+        let mut process = shell_command.to_command();
+        process
+            .current_dir(&group.stored.path)
+            .env("JJ_COMMIT_ID", commit.id().hex())
+            .env("JJ_CHANGE_ID", commit.change_id().reverse_hex())
+            .stdin(Stdio::null());
+        let output_name = commit.id().hex();
+        group.jobs.push(RunJob {
+            index,
+            commit,
+            process,
+            stdout_path: group
+                .stored
+                .output_path
+                .join(format!("{output_name}.stdout")),
+            stderr_path: group
+                .stored
+                .output_path
+                .join(format!("{output_name}.stderr")),
+        });
+    }
+    let output_paths = groups
+        .iter()
+        .flat_map(|group| &group.jobs)
+        .sorted_by_key(|job| job.index)
+        .map(|job| (job.stdout_path.clone(), job.stderr_path.clone()))
+        .collect_vec();
+    let context = RunContext {
+        snapshot_options: &snapshot_options,
+        operation_id: &operation_id,
+        rewrite: args.rewrite,
+    };
+    let job_results = schedule_run_jobs(groups, commits.len(), jobs, &context);
+
+    let mut results = vec![];
+    let mut new_trees = HashMap::new();
+    for ((commit, (stdout_path, stderr_path)), job_result) in
+        commits.iter().zip(output_paths).zip(job_results)
+    {
+        if let Some(mut formatter) = ui.status_formatter() {
+            write!(formatter, "Ran command in ")?;
+            workspace_command.write_commit_summary(formatter.as_mut(), commit)?;
+            writeln!(formatter)?;
+        }
+        let JobResult { status, new_tree } = job_result?;
+        let status = status.map_err(|err| {
+            user_error_with_message(format!("Failed to run `{shell_command}`"), err)
+        })?;
+        ui.stdout()
+            .write_all(&fs::read(&stdout_path).context(&stdout_path)?)?;
+        ui.stderr()
+            .write_all(&fs::read(&stderr_path).context(&stderr_path)?)?;
+        if let Some(tree) = new_tree
+            && tree.tree_ids() != commit.tree_ids()
+        {
+            new_trees.insert(commit.id().clone(), tree);
+        }
+        results.push((commit, status));
+    }
+
+    if let Some(mut formatter) = ui.status_formatter() {
+        writeln!(formatter, "Exit codes:")?;
+        for (commit, status) in &results {
+            // The process may have been terminated by a signal
+            let code = status
+                .code()
+                .map_or_else(|| "-".to_owned(), |code| code.to_string());
+            write!(formatter, "{code:>5}  ")?;
+            workspace_command.write_commit_summary(formatter.as_mut(), commit)?;
+            writeln!(formatter)?;
+        }
+    }
+
+    if !new_trees.is_empty() {
+        let mut tx = workspace_command.start_transaction();
+        let mut num_rebased = 0;
+        tx.repo_mut()
+            .transform_descendants(new_trees.keys().cloned().collect(), async |rewriter| {
+                if let Some(tree) = new_trees.get(rewriter.old_commit().id()) {
+                    rewriter.reparent().set_tree(tree.clone()).write().await?;
+                } else if rewriter.parents_changed() {
+                    rewriter.rebase().await?.write().await?;
+                    num_rebased += 1;
+                }
+                Ok(())
+            })
+            .block_on()?;
+        writeln!(ui.status(), "Rewrote {} commits", new_trees.len())?;
+        if num_rebased > 0 {
+            writeln!(ui.status(), "Rebased {num_rebased} descendant commits")?;
+        }
+        tx.finish(
+            ui,
+            format!(
+                "run `{shell_command}` and rewrite {} commits",
+                new_trees.len()
+            ),
+        )?;
+    }
+
+    let failed_count = results
+        .iter()
+        .filter(|(_, status)| !status.success())
+        .count();
+    if failed_count > 0 {
+        return Err(user_error(format!(
+            "Command failed in {failed_count} of {} revisions",
+            commits.len()
+        )));
+    }
+    Ok(())
+}
+
+/// Commands to be run one after another in a shared working copy.
+struct RunGroup<'a> {
+    stored: StoredWorkingCopy,
+    jobs: Vec<RunJob<'a>>,
+}
+
+/// Command to be run in the working copy of a revision.
+struct RunJob<'a> {
+    /// Position of the revision in the list of revisions to run against.
+    index: usize,
+    commit: &'a Commit,
+    process: Command,
+    stdout_path: PathBuf,
+    stderr_path: PathBuf,
+}
+
+impl RunJob<'_> {
+    /// Runs the command, streaming its output to the output files.
+    fn run(mut self) -> io::Result<ExitStatus> {
+        let stdout = File::create(&self.stdout_path)?;
+        let stderr = File::create(&self.stderr_path)?;
+        self.process.stdout(stdout).stderr(stderr).status()
+    }
+}
+
+/// Parameters shared by all jobs.
+struct RunContext<'a> {
+    snapshot_options: &'a SnapshotOptions<'a>,
+    operation_id: &'a OperationId,
+    rewrite: bool,
+}
+
+/// Outcome of a job whose working copy could be prepared.
+struct JobResult {
+    status: io::Result<ExitStatus>,
+    /// Snapshot of the working copy after the command succeeded, if
+    /// `--rewrite` was passed.
+    new_tree: Option<MergedTree>,
+}
+
+/// Runs the jobs of the `groups`, at most `max_jobs` groups at a time, and
+/// returns the results in the order of the jobs' `index`.
+///
+/// Jobs which share a working copy (because their revisions have the same
+/// contents) are run one after another so they don't interfere with each
+/// other. The working copy is reset to the revision before each of them, so a
+/// job doesn't see the changes made by the previous one.
+fn schedule_run_jobs(
+    groups: Vec<RunGroup>,
+    num_jobs: usize,
+    max_jobs: usize,
+    context: &RunContext,
+) -> Vec<Result<JobResult, WorkingCopyStoreError>> {
+    let num_groups = groups.len();
+    let queue = Mutex::new(groups.into_iter());
+    let results = Mutex::new((0..num_jobs).map(|_| None).collect_vec());
+    std::thread::scope(|scope| {
+        for _ in 0..max_jobs.min(num_groups) {
+            scope.spawn(|| {
+                loop {
+                    let Some(group) = queue.lock().unwrap().next() else {
+                        break;
+                    };
+                    let RunGroup { mut stored, jobs } = group;
+                    // Reused working copies may contain changes from earlier
+                    // runs.
+                    let mut needs_reset = stored.reused;
+                    for job in jobs {
+                        let index = job.index;
+                        let result = run_job(&mut stored, job, needs_reset, context);
+                        needs_reset = true;
+                        results.lock().unwrap()[index] = Some(result);
+                    }
+                }
+            });
+        }
+    });
+    results
+        .into_inner()
+        .unwrap()
+        .into_iter()
+        .map(|result| result.unwrap())
+        .collect()
+}
+
+/// Runs the `job` in the `stored` working copy, resetting the working copy to
+/// the revision first if `needs_reset`.
+fn run_job(
+    stored: &mut StoredWorkingCopy,
+    job: RunJob,
+    needs_reset: bool,
+    context: &RunContext,
+) -> Result<JobResult, WorkingCopyStoreError> {
+    if needs_reset {
+        stored
+            .discard_changes(job.commit, context.snapshot_options, context.operation_id)
+            .block_on()?;
+    }
+    let status = job.run();
+    let new_tree = match &status {
+        Ok(status) if context.rewrite && status.success() => Some(
+            stored
+                .snapshot(context.snapshot_options, context.operation_id)
+                .block_on()?,
+        ),
+        _ => None,
+    };
+    Ok(JobResult { status, new_tree })
 }
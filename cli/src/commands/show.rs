@@ -69,7 +69,7 @@ pub(crate) fn cmd_show(
     command: &CommandHelper,
     args: &ShowArgs,
 ) -> Result<(), CommandError> {
-    let workspace_command = command.workspace_helper(ui)?;
+    let workspace_command = command.read_only_workspace_helper(ui)?;
     let revision_arg = args
         .revision_pos
         .as_ref()
@@ -13,11 +13,14 @@
 // limitations under the License.
 use std::collections::HashMap;
 use std::io::Write as _;
+use std::slice;
 
 use clap_complete::ArgValueCandidates;
 use clap_complete::ArgValueCompleter;
+use itertools::Itertools as _;
 use jj_lib::backend::CommitId;
 use jj_lib::commit::Commit;
+use jj_lib::fileset::FilesetExpression;
 use jj_lib::matchers::Matcher;
 use jj_lib::merge::Diff;
 use jj_lib::merge::Merge;
@@ -31,6 +34,7 @@ use jj_lib::rewrite::RebaseOptions;
 use jj_lib::rewrite::RebasedCommit;
 use jj_lib::rewrite::RewriteRefsOptions;
 use jj_lib::rewrite::move_commits;
+use jj_lib::rewrite::restore_tree;
 use pollster::FutureExt as _;
 use tracing::instrument;
 
@@ -43,10 +47,12 @@ use crate::cli_util::compute_commit_location;
 use crate::cli_util::print_unmatched_explicit_paths;
 use crate::command_error::CommandError;
 use crate::complete;
+use crate::description_util::add_signoff_trailer;
 use crate::description_util::add_trailers;
 use crate::description_util::description_template;
 use crate::description_util::edit_description;
 use crate::description_util::join_message_paragraphs;
+use crate::description_util::should_signoff;
 use crate::ui::Ui;
 
 /// Split a revision in two
@@ -103,6 +109,22 @@ use crate::ui::Ui;
 /// description, the second commit will not get a description, and you will be
 /// asked for a description only for the first commit.
 ///
+/// With `--into`, the revision is split into one commit per fileset without
+/// opening the diff editor. The changes not matching any of the filesets go
+/// into the last commit:
+///
+/// ```text
+/// L                 L'
+/// |                 |
+/// K (split)   =>    K''' (remaining)
+/// |                 |
+/// J                 K" (matching the second fileset)
+///                   |
+///                   K' (matching the first fileset)
+///                   |
+///                   J
+/// ```
+///
 /// Splitting an empty commit is not supported because the same effect can be
 /// achieved with `jj new`.
 #[derive(clap::Args, Clone, Debug)]
@@ -187,6 +209,12 @@ pub(crate) struct SplitArgs {
     #[arg(long)]
     editor: bool,
 
+    /// Add a `Signed-off-by` trailer for the committer to the description
+    ///
+    /// This can be enabled by default with the `commit.signoff` setting.
+    #[arg(long)]
+    signoff: bool,
+
     /// Split the revision into two parallel revisions instead of a parent and
     /// child
     #[arg(long, short)]
@@ -196,6 +224,31 @@ pub(crate) struct SplitArgs {
     #[arg(value_name = "FILESETS", value_hint = clap::ValueHint::AnyPath)]
     #[arg(add = ArgValueCompleter::new(complete::modified_revision_files))]
     paths: Vec<String>,
+
+    /// Split the revision into one commit per fileset (can be repeated)
+    ///
+    /// The changes matching the first fileset go into the first commit, the
+    /// other changes matching the second fileset go into the second commit,
+    /// and so on. The remaining changes go into the last commit. All commits
+    /// keep the original description, and the diff editor isn't opened.
+    #[arg(
+        long,
+        value_name = "FILESET",
+        conflicts_with_all = [
+            "interactive",
+            "tool",
+            "onto",
+            "insert_after",
+            "insert_before",
+            "message_paragraphs",
+            "editor",
+            "signoff",
+            "parallel",
+            "paths",
+        ],
+    )]
+    #[arg(add = ArgValueCompleter::new(complete::modified_revision_files))]
+    into: Vec<String>,
 }
 
 impl SplitArgs {
@@ -271,6 +324,9 @@ pub(crate) fn cmd_split(
     args: &SplitArgs,
 ) -> Result<(), CommandError> {
     let mut workspace_command = command.workspace_helper(ui)?;
+    if !args.into.is_empty() {
+        return split_by_filesets(ui, workspace_command, args);
+    }
     let ResolvedSplitArgs {
         target_commit,
         matcher,
@@ -281,6 +337,7 @@ pub(crate) fn cmd_split(
         new_child_ids,
     } = args.resolve(ui, &workspace_command)?;
     let text_editor = workspace_command.text_editor()?;
+    let signoff = should_signoff(workspace_command.settings(), args.signoff)?;
     let mut tx = workspace_command.start_transaction();
 
     // Prompt the user to select the changes they want for the first commit.
@@ -308,7 +365,13 @@ pub(crate) fn cmd_split(
         // logic.
         let description = if !description.is_empty() || use_editor {
             commit_builder.set_description(description);
-            add_trailers(ui, &tx, &commit_builder)?
+            let description = add_trailers(ui, &tx, &commit_builder)?;
+            if signoff {
+                commit_builder.set_description(description);
+                add_signoff_trailer(&commit_builder)
+            } else {
+                description
+            }
         } else {
             description
         };
@@ -375,6 +438,10 @@ pub(crate) fn cmd_split(
         let description = if show_editor {
             let new_description = add_trailers(ui, &tx, &commit_builder)?;
             commit_builder.set_description(new_description);
+            if signoff {
+                let new_description = add_signoff_trailer(&commit_builder);
+                commit_builder.set_description(new_description);
+            }
             let temp_commit = commit_builder.write_hidden().block_on()?;
             let intro = "Enter a description for the remaining changes.";
             let template = description_template(ui, &tx, intro, &temp_commit)?;
@@ -396,7 +463,13 @@ pub(crate) fn cmd_split(
             new_child_ids,
         )?
     } else {
-        rewrite_descendants(&mut tx, &target, first_commit, second_commit, parallel)?
+        rewrite_descendants(
+            &mut tx,
+            &target.commit,
+            first_commit,
+            second_commit,
+            parallel,
+        )?
     };
     if let Some(mut formatter) = ui.status_formatter() {
         if num_rebased > 0 {
@@ -412,6 +485,96 @@ pub(crate) fn cmd_split(
     Ok(())
 }
 
+/// Splits the revision into one commit per `--into` fileset, followed by a
+/// commit with the remaining changes.
+fn split_by_filesets(
+    ui: &mut Ui,
+    mut workspace_command: WorkspaceCommandHelper,
+    args: &SplitArgs,
+) -> Result<(), CommandError> {
+    let target_commit = workspace_command.resolve_single_rev(ui, &args.revision)?;
+    workspace_command.check_rewritable([target_commit.id()])?;
+    let expressions: Vec<FilesetExpression> = args
+        .into
+        .iter()
+        .map(|fileset| workspace_command.parse_file_patterns(ui, slice::from_ref(fileset)))
+        .try_collect()?;
+    let parent_tree = target_commit.parent_tree(workspace_command.repo().as_ref())?;
+    let target_tree = target_commit.tree();
+    print_unmatched_explicit_paths(
+        ui,
+        &workspace_command,
+        &FilesetExpression::union_all(expressions.clone()),
+        [&parent_tree, &target_tree],
+    )?;
+
+    let mut tx = workspace_command.start_transaction();
+    // Each commit contains the changes matching its fileset and the filesets
+    // before it, so the trees are computed from the accumulated filesets.
+    let mut trees = vec![];
+    for i in 1..=expressions.len() {
+        let matcher = FilesetExpression::union_all(expressions[..i].to_vec()).to_matcher();
+        let tree = restore_tree(
+            &target_tree,
+            &parent_tree,
+            target_commit.conflict_label(),
+            target_commit.parents_conflict_label()?,
+            matcher.as_ref(),
+        )
+        .block_on()?;
+        let previous_tree = trees.last().unwrap_or(&parent_tree);
+        if tree.tree_ids() == previous_tree.tree_ids() {
+            writeln!(
+                ui.warning_default(),
+                "No changes match the fileset {fileset}, so its revision will be empty",
+                fileset = args.into[i - 1]
+            )?;
+        }
+        trees.push(tree);
+    }
+    if trees.last().unwrap().tree_ids() == target_tree.tree_ids() {
+        writeln!(
+            ui.warning_default(),
+            "All changes match the filesets, so the last revision will be empty"
+        )?;
+    }
+    trees.push(target_tree);
+
+    let mut new_commits: Vec<Commit> = vec![];
+    for tree in trees {
+        let mut commit_builder = tx.repo_mut().rewrite_commit(&target_commit).detach();
+        commit_builder.set_tree(tree);
+        // The first commit takes the place of the original commit, like the
+        // selected changes of an interactive split.
+        if let Some(parent) = new_commits.last() {
+            commit_builder.set_parents(vec![parent.id().clone()]);
+            commit_builder.clear_rewrite_source();
+            commit_builder.generate_new_change_id();
+        }
+        new_commits.push(commit_builder.write(tx.repo_mut()).block_on()?);
+    }
+    let first_commit = new_commits.first().unwrap().clone();
+    let last_commit = new_commits.last().unwrap().clone();
+    let (_, _, num_rebased) =
+        rewrite_descendants(&mut tx, &target_commit, first_commit, last_commit, false)?;
+    if let Some(mut formatter) = ui.status_formatter() {
+        if num_rebased > 0 {
+            writeln!(formatter, "Rebased {num_rebased} descendant commits")?;
+        }
+        writeln!(formatter, "Split into {} commits:", new_commits.len())?;
+        for commit in &new_commits {
+            write!(formatter, "  ")?;
+            tx.write_commit_summary(formatter.as_mut(), commit)?;
+            writeln!(formatter)?;
+        }
+    }
+    tx.finish(
+        ui,
+        format!("split commit {} by filesets", target_commit.id().hex()),
+    )?;
+    Ok(())
+}
+
 fn move_first_commit(
     tx: &mut WorkspaceCommandTransaction,
     target: &CommitWithSelection,
@@ -482,7 +645,7 @@ fn move_first_commit(
 
 fn rewrite_descendants(
     tx: &mut WorkspaceCommandTransaction,
-    target: &CommitWithSelection,
+    target_commit: &Commit,
     first_commit: Commit,
     second_commit: Commit,
     parallel: bool,
@@ -493,11 +656,11 @@ fn rewrite_descendants(
         // moves any bookmarks pointing to the target commit to the second
         // commit.
         tx.repo_mut()
-            .set_rewritten_commit(target.commit.id().clone(), second_commit.id().clone());
+            .set_rewritten_commit(target_commit.id().clone(), second_commit.id().clone());
     }
     let mut num_rebased = 0;
     tx.repo_mut()
-        .transform_descendants(vec![target.commit.id().clone()], async |mut rewriter| {
+        .transform_descendants(vec![target_commit.id().clone()], async |mut rewriter| {
             num_rebased += 1;
             if parallel && legacy_bookmark_behavior {
                 // The old_parent is the second commit due to the rewrite above.
@@ -515,7 +678,7 @@ fn rewrite_descendants(
     // Move the working copy commit (@) to the second commit for any workspaces
     // where the target commit is the working copy commit.
     for (name, working_copy_commit) in tx.base_repo().clone().view().wc_commit_ids() {
-        if working_copy_commit == target.commit.id() {
+        if working_copy_commit == target_commit.id() {
             tx.repo_mut()
                 .edit(name.clone(), &second_commit)
                 .block_on()?;
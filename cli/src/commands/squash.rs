@@ -28,6 +28,7 @@ use jj_lib::repo::Repo as _;
 use jj_lib::rewrite;
 use jj_lib::rewrite::CommitWithSelection;
 use jj_lib::rewrite::merge_commit_trees;
+use jj_lib::settings::UserSettings;
 use pollster::FutureExt as _;
 use tracing::instrument;
 
@@ -42,6 +43,7 @@ use crate::command_error::user_error;
 use crate::complete;
 use crate::description_util::add_trailers;
 use crate::description_util::combine_messages_for_editing;
+use crate::description_util::concatenate_messages;
 use crate::description_util::description_template;
 use crate::description_util::edit_description;
 use crate::description_util::join_message_paragraphs;
@@ -68,7 +70,9 @@ use crate::ui::Ui;
 ///
 /// If the source was abandoned and both the source and destination had a
 /// non-empty description, you will be asked for the combined description. If
-/// either was empty, then the other one will be used.
+/// either was empty, then the other one will be used. Use `--message-strategy`
+/// or the `squash.message-strategy` setting to combine the descriptions
+/// differently.
 ///
 /// If a working-copy commit gets abandoned, it will be given a new, empty
 /// commit. This is true in general; it is not specific to this command.
@@ -149,9 +153,24 @@ pub(crate) struct SquashArgs {
 
     /// Use the description of the destination revision and discard the
     /// description(s) of the source revision(s)
+    ///
+    /// This is the same as `--message-strategy=keep-destination`, except
+    /// that it can be combined with `--onto`, `--insert-after`, or
+    /// `--insert-before`. The new commit is then left without a description.
     #[arg(long, short, conflicts_with = "message_paragraphs")]
     use_destination_message: bool,
 
+    /// How to combine the descriptions of the source and destination
+    /// revisions
+    ///
+    /// Defaults to the `squash.message-strategy` setting.
+    #[arg(
+        long,
+        value_name = "STRATEGY",
+        conflicts_with_all = ["message_paragraphs", "use_destination_message"]
+    )]
+    message_strategy: Option<MessageStrategy>,
+
     /// Open an editor to edit the change description
     ///
     /// Forces an editor to open when using `--message` to allow the
@@ -311,7 +330,8 @@ pub(crate) fn cmd_squash(
         tx.base_workspace_helper()
             .diff_selector(ui, args.tool.as_deref(), args.interactive)?;
     let text_editor = tx.base_workspace_helper().text_editor()?;
-    let squashed_description = SquashedDescription::from_args(args);
+    let squashed_description =
+        SquashedDescription::from_args(tx.settings(), args, insert_destination_commit)?;
 
     let source_commits = select_diff(ui, &tx, &sources, &destination, &matcher, &diff_selector)?;
 
@@ -331,13 +351,28 @@ pub(crate) fn cmd_squash(
     .block_on()?
     {
         let mut commit_builder = squashed.commit_builder.detach();
+        let abandoned_commits = &squashed.abandoned_commits;
         let single_description = match squashed_description {
             SquashedDescription::Exact(description) => Some(description),
-            SquashedDescription::UseDestination => Some(destination.description().to_owned()),
-            SquashedDescription::Combine => {
-                let abandoned_commits = &squashed.abandoned_commits;
+            SquashedDescription::Strategy(MessageStrategy::Combine) => {
                 try_combine_messages(abandoned_commits, &destination)
             }
+            SquashedDescription::Strategy(MessageStrategy::Editor) => None,
+            SquashedDescription::Strategy(MessageStrategy::KeepDestination) => {
+                Some(destination.description().to_owned())
+            }
+            // If no source was abandoned, the sources keep their descriptions.
+            SquashedDescription::Strategy(MessageStrategy::TakeSource)
+                if abandoned_commits.is_empty() =>
+            {
+                Some(destination.description().to_owned())
+            }
+            SquashedDescription::Strategy(MessageStrategy::TakeSource) => {
+                Some(concatenate_messages(abandoned_commits))
+            }
+            SquashedDescription::Strategy(MessageStrategy::Concatenate) => Some(
+                concatenate_messages(once(&destination).chain(abandoned_commits)),
+            ),
         };
         let description = if let Some(description) = single_description {
             if description.is_empty() && !args.editor {
@@ -357,7 +392,6 @@ pub(crate) fn cmd_squash(
             }
         } else {
             // edit combined
-            let abandoned_commits = &squashed.abandoned_commits;
             let combined = combine_messages_for_editing(
                 ui,
                 &tx,
@@ -432,28 +466,65 @@ pub(crate) fn cmd_squash(
     Ok(())
 }
 
+/// How the descriptions of the source and destination revisions are combined.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, serde::Deserialize, clap::ValueEnum)]
+#[serde(rename_all = "kebab-case")]
+enum MessageStrategy {
+    /// Use the only non-empty description, or open an editor if there are
+    /// several
+    Combine,
+    /// Always open an editor prefilled with all descriptions
+    Editor,
+    /// Use the description of the destination revision
+    KeepDestination,
+    /// Use the description(s) of the source revision(s)
+    TakeSource,
+    /// Join the non-empty descriptions, separated by blank lines
+    Concatenate,
+}
+
 enum SquashedDescription {
     // Use this exact description.
     Exact(String),
-    // Use the destination's description and discard the descriptions of the
-    // source revisions.
-    UseDestination,
     // Combine the descriptions of the source and destination revisions.
-    Combine,
+    Strategy(MessageStrategy),
 }
 
 impl SquashedDescription {
-    fn from_args(args: &SquashArgs) -> Self {
+    fn from_args(
+        settings: &UserSettings,
+        args: &SquashArgs,
+        insert_destination_commit: bool,
+    ) -> Result<Self, CommandError> {
         // These options are incompatible and Clap is configured to prevent this.
         assert!(args.message_paragraphs.is_none() || !args.use_destination_message);
 
         if let Some(paragraphs) = &args.message_paragraphs {
-            Self::Exact(join_message_paragraphs(paragraphs))
-        } else if args.use_destination_message {
-            Self::UseDestination
+            return Ok(Self::Exact(join_message_paragraphs(paragraphs)));
+        }
+        if args.use_destination_message {
+            // The new commit created by --onto/--after/--before keeps its
+            // empty description.
+            return Ok(Self::Strategy(MessageStrategy::KeepDestination));
+        }
+        let strategy = if let Some(strategy) = args.message_strategy {
+            strategy
         } else {
-            Self::Combine
+            let strategy = settings.get::<MessageStrategy>("squash.message-strategy")?;
+            // The new commit has no description to keep.
+            if strategy == MessageStrategy::KeepDestination && insert_destination_commit {
+                MessageStrategy::Combine
+            } else {
+                strategy
+            }
+        };
+        if strategy == MessageStrategy::KeepDestination && insert_destination_commit {
+            return Err(user_error(
+                "Cannot keep the destination description when squashing into a new commit",
+            )
+            .hinted("Use `--message` to set the description of the new commit."));
         }
+        Ok(Self::Strategy(strategy))
     }
 }
 
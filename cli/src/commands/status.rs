@@ -12,8 +12,16 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::borrow::Cow;
+use std::fmt::Write as _;
+
+use futures::StreamExt as _;
 use itertools::Itertools as _;
+use jj_lib::backend::Timestamp;
+use jj_lib::commit::Commit;
+use jj_lib::copies::CopiesTreeDiffEntry;
 use jj_lib::copies::CopyRecords;
+use jj_lib::matchers::Matcher;
 use jj_lib::merge::Diff;
 use jj_lib::merged_tree::MergedTree;
 use jj_lib::repo::Repo as _;
@@ -21,17 +29,23 @@ use jj_lib::repo_path::RepoPath;
 use jj_lib::repo_path::RepoPathBuf;
 use jj_lib::revset::RevsetExpression;
 use jj_lib::revset::RevsetFilterPredicate;
+use jj_lib::working_copy::SnapshotStats;
 use pollster::FutureExt as _;
 use tracing::instrument;
 
 use crate::cli_util::CommandHelper;
+use crate::cli_util::WorkspaceCommandHelper;
 use crate::cli_util::print_conflicted_paths;
 use crate::cli_util::print_snapshot_stats;
 use crate::cli_util::print_unmatched_explicit_paths;
 use crate::command_error::CommandError;
+use crate::command_error::internal_error;
+use crate::diff_util::DiffEntryStatus;
 use crate::diff_util::DiffFormat;
+use crate::diff_util::diff_status;
 use crate::diff_util::get_copy_records;
 use crate::formatter::FormatterExt as _;
+use crate::time_util;
 use crate::ui::Ui;
 
 /// Show high-level repo status [default alias: st]
@@ -55,6 +69,38 @@ pub(crate) struct StatusArgs {
     /// Restrict the status display to these paths
     #[arg(value_name = "FILESETS", value_hint = clap::ValueHint::AnyPath)]
     paths: Vec<String>,
+
+    /// Print the working copy changes in the format of `git status
+    /// --porcelain`
+    ///
+    /// This is meant for editor plugins and other tools which parse the output
+    /// of Git. Each changed path is printed as `XY PATH` relative to the
+    /// workspace root, with the status letter in the first column since jj has
+    /// no index. Renamed and copied paths are printed as `XY ORIG -> PATH`,
+    /// conflicted paths as `UU PATH`, and untracked paths as `?? PATH`.
+    #[arg(
+        long,
+        value_name = "VERSION",
+        value_enum,
+        num_args = 0..=1,
+        require_equals = true,
+        default_missing_value = "v1"
+    )]
+    porcelain: Option<PorcelainVersion>,
+
+    /// Don't snapshot the working copy, show the status as of the last snapshot
+    ///
+    /// This is fast even in huge repos, but changes made since the last
+    /// snapshot aren't shown. Instead, it's printed how long ago the last
+    /// snapshot was, and how many paths have changed since then if
+    /// `fsmonitor.backend` is set to `watchman`.
+    #[arg(long)]
+    no_snapshot: bool,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq, clap::ValueEnum)]
+enum PorcelainVersion {
+    V1,
 }
 
 #[instrument(skip_all)]
@@ -63,7 +109,13 @@ pub(crate) fn cmd_status(
     command: &CommandHelper,
     args: &StatusArgs,
 ) -> Result<(), CommandError> {
-    let (workspace_command, snapshot_stats) = command.workspace_helper_with_stats(ui)?;
+    let (workspace_command, snapshot_stats) = if args.no_snapshot {
+        let workspace_command = command.workspace_helper_no_snapshot(ui)?;
+        print_snapshot_freshness(ui, &workspace_command)?;
+        (workspace_command, SnapshotStats::default())
+    } else {
+        command.read_only_workspace_helper_with_stats(ui)?
+    };
     print_snapshot_stats(
         ui,
         &snapshot_stats,
@@ -76,6 +128,18 @@ pub(crate) fn cmd_status(
         .transpose()?;
     let fileset_expression = workspace_command.parse_file_patterns(ui, &args.paths)?;
     let matcher = fileset_expression.to_matcher();
+    if let Some(PorcelainVersion::V1) = args.porcelain {
+        if let Some(wc_commit) = &maybe_wc_commit {
+            print_porcelain_v1(
+                ui,
+                &workspace_command,
+                wc_commit,
+                &matcher,
+                snapshot_stats.untracked_paths.keys(),
+            )?;
+        }
+        return Ok(());
+    }
     ui.request_pager();
     let mut formatter = ui.stdout_formatter();
     let formatter = formatter.as_mut();
@@ -233,6 +297,181 @@ pub(crate) fn cmd_status(
     Ok(())
 }
 
+/// Prints how long ago the working copy was snapshotted, and how many paths
+/// have changed since then according to the filesystem monitor.
+fn print_snapshot_freshness(
+    ui: &Ui,
+    workspace_command: &WorkspaceCommandHelper,
+) -> Result<(), CommandError> {
+    let repo = workspace_command.repo();
+    let operation = repo
+        .loader()
+        .load_operation(workspace_command.working_copy().operation_id())
+        .block_on()?;
+    let snapshot_time = operation.metadata().time.end;
+    // Respect the operation timestamp override so the output is stable in
+    // tests.
+    let now = workspace_command
+        .settings()
+        .operation_timestamp()
+        .unwrap_or_else(Timestamp::now);
+    let when = time_util::format_duration(&snapshot_time, &now, &timeago::Formatter::new())
+        .or_else(|_| {
+            time_util::format_absolute_timestamp(&snapshot_time).map(|time| format!("at {time}"))
+        })
+        .map_err(internal_error)?;
+    match count_fsmonitor_changes(workspace_command)? {
+        Some(count) => writeln!(
+            ui.status(),
+            "Working copy last snapshotted {when}; {count} paths changed since (from fsmonitor)"
+        )?,
+        None => writeln!(
+            ui.status(),
+            "Working copy last snapshotted {when}; changes since then are not shown"
+        )?,
+    }
+    Ok(())
+}
+
+/// Returns the number of paths changed since the last snapshot, or `None` if
+/// it's unknown.
+#[cfg(feature = "watchman")]
+fn count_fsmonitor_changes(
+    workspace_command: &WorkspaceCommandHelper,
+) -> Result<Option<usize>, CommandError> {
+    use jj_lib::fsmonitor::FsmonitorSettings;
+    use jj_lib::local_working_copy::LocalWorkingCopy;
+
+    let FsmonitorSettings::Watchman(config) =
+        FsmonitorSettings::from_settings(workspace_command.settings())?
+    else {
+        return Ok(None);
+    };
+    let Some(wc) = workspace_command
+        .working_copy()
+        .downcast_ref::<LocalWorkingCopy>()
+    else {
+        return Ok(None);
+    };
+    // The status should still be shown if Watchman isn't running.
+    match wc.query_watchman(&config).block_on() {
+        // Watchman returns no files if it can't tell what has changed.
+        Ok((_clock, changed_files)) => Ok(changed_files.map(|files| files.len())),
+        Err(err) => {
+            tracing::warn!(?err, "failed to query watchman");
+            Ok(None)
+        }
+    }
+}
+
+#[cfg(not(feature = "watchman"))]
+fn count_fsmonitor_changes(
+    _workspace_command: &WorkspaceCommandHelper,
+) -> Result<Option<usize>, CommandError> {
+    Ok(None)
+}
+
+/// Prints the changes in the working-copy commit like `git status
+/// --porcelain=v1`.
+fn print_porcelain_v1(
+    ui: &Ui,
+    workspace_command: &WorkspaceCommandHelper,
+    wc_commit: &Commit,
+    matcher: &dyn Matcher,
+    untracked_paths: impl IntoIterator<Item = impl AsRef<RepoPath>>,
+) -> Result<(), CommandError> {
+    let repo = workspace_command.repo();
+    let parent_tree = wc_commit.parent_tree(repo.as_ref())?;
+    let tree = wc_commit.tree();
+    let mut copy_records = CopyRecords::default();
+    for parent in wc_commit.parent_ids() {
+        // Like Git, a rename whose source isn't matched is reported as an
+        // addition.
+        let records = get_copy_records(repo.store(), parent, wc_commit.id(), matcher)?
+            .filter_ok(|record| matcher.matches(&record.source));
+        copy_records.add_records(records)?;
+    }
+
+    // Conflicted paths are listed even if they are unchanged from the parents,
+    // sorted together with the changed paths.
+    let mut lines: Vec<(RepoPathBuf, String)> = tree
+        .conflicts_matching(matcher)
+        .map(|(path, _)| {
+            let line = format!("UU {}", quote_git_path(path.as_internal_file_string()));
+            (path, line)
+        })
+        .collect();
+    let mut tree_diff = parent_tree.diff_stream_with_copies(&tree, matcher, &copy_records);
+    while let Some(CopiesTreeDiffEntry { path, values }) = tree_diff.next().block_on() {
+        let values = values?;
+        if !values.after.is_resolved() {
+            continue;
+        }
+        let status = match diff_status(&path, &values) {
+            DiffEntryStatus::Added => "A ",
+            DiffEntryStatus::Removed => "D ",
+            DiffEntryStatus::Modified => "M ",
+            DiffEntryStatus::Copied => "C ",
+            DiffEntryStatus::Renamed => "R ",
+        };
+        let target = quote_git_path(path.target().as_internal_file_string());
+        let line = if path.copy_operation().is_some() {
+            let source = quote_git_path(path.source().as_internal_file_string());
+            format!("{status} {source} -> {target}")
+        } else {
+            format!("{status} {target}")
+        };
+        lines.push((path.target().to_owned(), line));
+    }
+    lines.sort_by(|(a, _), (b, _)| a.cmp(b));
+
+    let mut formatter = ui.stdout_formatter();
+    for (_, line) in &lines {
+        writeln!(formatter, "{line}")?;
+    }
+
+    // Like Git, untracked paths are filtered by the path arguments.
+    let untracked_paths = untracked_paths
+        .into_iter()
+        .filter(|path| matcher.matches(path.as_ref()));
+    visit_collapsed_untracked_files(untracked_paths, tree, |path, is_dir| {
+        let path = if is_dir {
+            path.to_internal_dir_string()
+        } else {
+            path.as_internal_file_string().to_owned()
+        };
+        writeln!(formatter, "?? {}", quote_git_path(&path))?;
+        Ok(())
+    })
+    .block_on()
+}
+
+/// Quotes the path as a C string literal like Git does if it contains special
+/// characters.
+fn quote_git_path(path: &str) -> Cow<'_, str> {
+    let needs_quote =
+        |b: u8| matches!(b, b'"' | b'\\' | b' ') || b.is_ascii_control() || !b.is_ascii();
+    if !path.bytes().any(needs_quote) {
+        return Cow::Borrowed(path);
+    }
+    let mut quoted = String::from('"');
+    for b in path.bytes() {
+        match b {
+            b'"' => quoted.push_str("\\\""),
+            b'\\' => quoted.push_str("\\\\"),
+            b'\t' => quoted.push_str("\\t"),
+            b'\n' => quoted.push_str("\\n"),
+            b'\r' => quoted.push_str("\\r"),
+            b if b.is_ascii_control() || !b.is_ascii() => {
+                write!(quoted, "\\{b:03o}").unwrap();
+            }
+            b => quoted.push(char::from(b)),
+        }
+    }
+    quoted.push('"');
+    Cow::Owned(quoted)
+}
+
 async fn visit_collapsed_untracked_files(
     untracked_paths: impl IntoIterator<Item = impl AsRef<RepoPath>>,
     tree: MergedTree,
@@ -321,6 +560,15 @@ mod test {
         result
     }
 
+    #[test]
+    fn test_quote_git_path() {
+        assert_eq!(quote_git_path("dir/file.txt"), "dir/file.txt");
+        assert_eq!(quote_git_path("with space"), r#""with space""#);
+        assert_eq!(quote_git_path(r#"a"b\c"#), r#""a\"b\\c""#);
+        assert_eq!(quote_git_path("tab\there"), r#""tab\there""#);
+        assert_eq!(quote_git_path("caf\u{e9}"), r#""caf\303\251""#);
+    }
+
     #[test]
     fn test_collapsed_untracked_files() {
         let repo = TestRepo::init();
@@ -0,0 +1,253 @@
+// Copyright 2026 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashSet;
+use std::io::Write as _;
+
+use itertools::Itertools as _;
+use jj_lib::backend::CommitId;
+use jj_lib::git::REMOTE_NAME_FOR_LOCAL_GIT_REPO;
+use jj_lib::object_id::ObjectId as _;
+use jj_lib::op_store::RefTarget;
+use jj_lib::ref_name::RemoteNameBuf;
+use jj_lib::remote_sync_store::RemoteSyncRecord;
+use jj_lib::remote_sync_store::RemoteSyncStore;
+use jj_lib::repo::Repo;
+use jj_lib::revset::RevsetExpression;
+use serde_json::json;
+use tracing::instrument;
+
+use crate::cli_util::CommandHelper;
+use crate::cli_util::short_operation_hash;
+use crate::command_error::CommandError;
+use crate::command_error::internal_error;
+use crate::formatter::FormatterExt as _;
+use crate::time_util::format_absolute_timestamp;
+use crate::ui::Ui;
+
+/// Show how tracked bookmarks differ from their remotes
+///
+/// For each remote, shows when it was last fetched from and pushed to, and for
+/// each tracked bookmark, how many commits the local bookmark is ahead of and
+/// behind the remote bookmark, whether it needs to be pushed, and whether it's
+/// conflicted.
+///
+/// Fetches and pushes are recorded by `jj git fetch`, `jj git clone`, and
+/// `jj git push`. They aren't part of the operation log, so `jj undo` doesn't
+/// affect them.
+#[derive(clap::Args, Clone, Debug)]
+pub(crate) struct SyncStatusArgs {
+    /// Print the status as a JSON object
+    #[arg(long)]
+    json: bool,
+}
+
+/// Sync status of a tracked bookmark.
+struct BookmarkSyncStatus {
+    name: String,
+    local_target: RefTarget,
+    remote_target: RefTarget,
+    ahead: Option<usize>,
+    behind: Option<usize>,
+}
+
+impl BookmarkSyncStatus {
+    fn has_conflict(&self) -> bool {
+        self.local_target.has_conflict() || self.remote_target.has_conflict()
+    }
+
+    fn needs_push(&self) -> bool {
+        !self.has_conflict() && self.local_target != self.remote_target
+    }
+}
+
+#[instrument(skip_all)]
+pub(crate) fn cmd_sync_status(
+    ui: &mut Ui,
+    command: &CommandHelper,
+    args: &SyncStatusArgs,
+) -> Result<(), CommandError> {
+    let workspace_command = command.workspace_helper(ui)?;
+    let repo = workspace_command.repo().as_ref();
+    let view = repo.view();
+
+    let remote_names: Vec<RemoteNameBuf> = view
+        .remote_views()
+        .map(|(name, _)| name)
+        .filter(|&name| name != REMOTE_NAME_FOR_LOCAL_GIT_REPO)
+        .map(ToOwned::to_owned)
+        .collect();
+    let sync_infos = RemoteSyncStore::load(workspace_command.repo_path()).get_all()?;
+
+    let mut remotes = vec![];
+    for remote_name in &remote_names {
+        let mut bookmarks = vec![];
+        for (name, targets) in view.local_remote_bookmarks(remote_name) {
+            if !targets.remote_ref.is_tracked() {
+                continue;
+            }
+            let local_target = targets.local_target.clone();
+            let remote_target = targets.remote_ref.target.clone();
+            let (ahead, behind) = if let (Some(local_id), Some(remote_id)) =
+                (local_target.as_normal(), remote_target.as_normal())
+                && local_id != remote_id
+            {
+                let (ahead, behind) = count_ahead_behind(repo, local_id, remote_id)?;
+                (Some(ahead), Some(behind))
+            } else {
+                (None, None)
+            };
+            bookmarks.push(BookmarkSyncStatus {
+                name: name.as_str().to_owned(),
+                local_target,
+                remote_target,
+                ahead,
+                behind,
+            });
+        }
+        let sync_info = sync_infos.get(remote_name).cloned().unwrap_or_default();
+        remotes.push((remote_name, sync_info, bookmarks));
+    }
+
+    if args.json {
+        let remotes = remotes
+            .iter()
+            .map(|(remote_name, sync_info, bookmarks)| {
+                let bookmarks = bookmarks
+                    .iter()
+                    .map(|bookmark| {
+                        json!({
+                            "name": bookmark.name,
+                            "local": target_to_json(&bookmark.local_target),
+                            "remote": target_to_json(&bookmark.remote_target),
+                            "ahead": bookmark.ahead,
+                            "behind": bookmark.behind,
+                            "conflict": bookmark.has_conflict(),
+                            "needs_push": bookmark.needs_push(),
+                        })
+                    })
+                    .collect_vec();
+                Ok(json!({
+                    "name": remote_name.as_str(),
+                    "last_fetch": record_to_json(sync_info.last_fetch.as_ref())?,
+                    "last_push": record_to_json(sync_info.last_push.as_ref())?,
+                    "bookmarks": bookmarks,
+                }))
+            })
+            .collect::<Result<Vec<_>, CommandError>>()?;
+        writeln!(ui.stdout(), "{}", json!({ "remotes": remotes }))?;
+        return Ok(());
+    }
+
+    ui.request_pager();
+    let mut formatter = ui.stdout_formatter();
+    for (remote_name, sync_info, bookmarks) in &remotes {
+        write!(formatter.labeled("remote"), "{}", remote_name.as_symbol())?;
+        let last_fetch = match &sync_info.last_fetch {
+            Some(record) => format!("last fetched {}", format_record(record)?),
+            None => "never fetched".to_owned(),
+        };
+        let last_push = match &sync_info.last_push {
+            Some(record) => format!("last pushed {}", format_record(record)?),
+            None => "never pushed".to_owned(),
+        };
+        writeln!(formatter, ": {last_fetch}; {last_push}")?;
+        for bookmark in bookmarks {
+            write!(formatter, "  ")?;
+            write!(formatter.labeled("bookmark"), "{}", bookmark.name)?;
+            let mut details = vec![];
+            if bookmark.has_conflict() {
+                details.push("conflicted".to_owned());
+            } else if bookmark.local_target.is_absent() {
+                details.push("deleted".to_owned());
+            } else if bookmark.remote_target.is_absent() {
+                details.push("not created yet".to_owned());
+            }
+            if let Some(ahead) = bookmark.ahead {
+                details.push(format!("ahead by {ahead} commits"));
+            }
+            if let Some(behind) = bookmark.behind {
+                details.push(format!("behind by {behind} commits"));
+            }
+            if details.is_empty() {
+                details.push("in sync".to_owned());
+            }
+            if bookmark.needs_push() {
+                details.push("needs push".to_owned());
+            }
+            writeln!(formatter, ": {}", details.join(", "))?;
+        }
+    }
+    Ok(())
+}
+
+/// Counts the commits only reachable from `local_id` (ahead) and only
+/// reachable from `remote_id` (behind).
+///
+/// Both counts come from a single walk of the commits reachable from exactly
+/// one of the two. Since the walk visits children before parents, a commit is
+/// reachable from `local_id` if it's `local_id` itself or a parent of a commit
+/// already known to be.
+fn count_ahead_behind(
+    repo: &dyn Repo,
+    local_id: &CommitId,
+    remote_id: &CommitId,
+) -> Result<(usize, usize), CommandError> {
+    let local = RevsetExpression::commit(local_id.clone());
+    let remote = RevsetExpression::commit(remote_id.clone());
+    let revset = local
+        .union(&remote)
+        .ancestors()
+        .minus(&local.ancestors().intersection(&remote.ancestors()))
+        .evaluate(repo)?;
+    let mut reachable_from_local = HashSet::from([local_id.clone()]);
+    let (mut ahead, mut behind) = (0, 0);
+    for node in revset.iter_graph() {
+        let (id, edges) = node?;
+        if reachable_from_local.remove(&id) {
+            ahead += 1;
+            reachable_from_local.extend(edges.into_iter().map(|edge| edge.target));
+        } else {
+            behind += 1;
+        }
+    }
+    Ok((ahead, behind))
+}
+
+fn format_record(record: &RemoteSyncRecord) -> Result<String, CommandError> {
+    let time = format_absolute_timestamp(&record.time).map_err(internal_error)?;
+    Ok(format!(
+        "at {time} by operation {}",
+        short_operation_hash(&record.operation_id)
+    ))
+}
+
+fn record_to_json(record: Option<&RemoteSyncRecord>) -> Result<serde_json::Value, CommandError> {
+    let Some(record) = record else {
+        return Ok(serde_json::Value::Null);
+    };
+    Ok(json!({
+        "operation": record.operation_id.hex(),
+        "time": format_absolute_timestamp(&record.time).map_err(internal_error)?,
+        "ref_count": record.ref_count,
+    }))
+}
+
+fn target_to_json(target: &RefTarget) -> serde_json::Value {
+    match target.as_normal() {
+        Some(id) => id.hex().into(),
+        None if target.is_absent() => serde_json::Value::Null,
+        None => target.added_ids().map(|id| id.hex()).collect_vec().into(),
+    }
+}
@@ -12,13 +12,20 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::collections::HashMap;
 use std::collections::HashSet;
 use std::rc::Rc;
 
 use clap_complete::ArgValueCandidates;
 use itertools::Itertools as _;
+use jj_lib::backend::Timestamp;
+#[cfg(feature = "git")]
+use jj_lib::git;
+#[cfg(feature = "git")]
+use jj_lib::ref_name::RefName;
 use jj_lib::repo::Repo as _;
 use jj_lib::revset::RevsetExpression;
+use jj_lib::store::Store;
 use jj_lib::str_util::StringExpression;
 
 use super::warn_unmatched_local_tags;
@@ -28,6 +35,7 @@ use crate::cli_util::default_ignored_remote_name;
 use crate::command_error::CommandError;
 use crate::commit_ref_list;
 use crate::commit_ref_list::RefFilterPredicates;
+use crate::commit_ref_list::RefListItem;
 use crate::commit_ref_list::SortKey;
 use crate::commit_templater::CommitRef;
 use crate::complete;
@@ -114,6 +122,20 @@ pub struct TagListArgs {
     /// This defaults to the `ui.tag-list-sort-keys` setting.
     #[arg(long, value_name = "SORT_KEY", value_enum, value_delimiter = ',')]
     sort: Vec<SortKey>,
+
+    /// Limit number of tags to show
+    ///
+    /// Applied after tags are sorted and `--offset` tags are skipped.
+    /// A tag and its tracked remote tags count as one.
+    #[arg(long, short = 'n')]
+    limit: Option<usize>,
+
+    /// Skip the given number of tags before showing any
+    ///
+    /// Applied after tags are sorted. Combined with `--limit`, this can be
+    /// used to page through tags.
+    #[arg(long, default_value_t = 0)]
+    offset: usize,
 }
 
 pub fn cmd_tag_list(
@@ -172,13 +194,25 @@ pub fn cmd_tag_list(
         name_matcher: name_expr.to_matcher(),
         remote_matcher: remote_expr.to_matcher(),
         matched_local_targets,
+        matched_tracked_remote_targets: HashSet::new(),
         conflicted: args.conflicted,
         include_local_only: !args.tracked && args.remotes.is_none(),
         include_synced_remotes: args.tracked || args.all_remotes || args.remotes.is_some(),
         include_untracked_remotes: !args.tracked && (args.all_remotes || args.remotes.is_some()),
     };
     let mut list_items = commit_ref_list::collect_items(view.tags(), &predicates);
-    commit_ref_list::sort(repo.store(), &mut list_items, &sort_keys)?;
+    let tag_timestamps = if sort_keys.iter().any(|key| key.is_tag_dependant()) {
+        load_annotated_tag_timestamps(repo.store(), &list_items)?
+    } else {
+        HashMap::new()
+    };
+    commit_ref_list::sort_with_tag_timestamps(
+        repo.as_ref(),
+        &mut list_items,
+        &sort_keys,
+        &tag_timestamps,
+    )?;
+    commit_ref_list::paginate(&mut list_items, args.offset, args.limit);
 
     ui.request_pager();
     let mut formatter = ui.stdout_formatter();
@@ -191,3 +225,38 @@ pub fn cmd_tag_list(
     warn_unmatched_local_tags(ui, view, &name_expr)?;
     Ok(())
 }
+
+/// Looks up the creation dates of the local annotated tags in the Git repo.
+///
+/// Only Git tags pointing to the same target as the listed tag are used, so
+/// tags that have been moved since the selected operation fall back to the
+/// committer date.
+#[cfg(feature = "git")]
+fn load_annotated_tag_timestamps(
+    store: &Store,
+    items: &[RefListItem],
+) -> Result<HashMap<String, Timestamp>, CommandError> {
+    let Ok(git_repo) = git::get_git_repo(store) else {
+        return Ok(HashMap::new());
+    };
+    let tags = items
+        .iter()
+        .filter(|item| item.primary.remote_name().is_none())
+        .filter_map(|item| {
+            let name = RefName::new(item.primary.name());
+            Some((name, item.primary.target().as_normal()?))
+        });
+    let timestamps = git::get_annotated_tag_timestamps(&git_repo, tags)?;
+    Ok(timestamps
+        .into_iter()
+        .map(|(name, timestamp)| (name.into_string(), timestamp))
+        .collect())
+}
+
+#[cfg(not(feature = "git"))]
+fn load_annotated_tag_timestamps(
+    _store: &Store,
+    _items: &[RefListItem],
+) -> Result<HashMap<String, Timestamp>, CommandError> {
+    Ok(HashMap::new())
+}
@@ -12,10 +12,23 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use clap_complete::ArgValueCompleter;
+use itertools::Itertools as _;
+use jj_lib::commit::Commit;
+use jj_lib::default_working_copy_store::DefaultWorkingCopyStore;
+use jj_lib::object_id::ObjectId as _;
+use jj_lib::repo::Repo as _;
+use jj_lib::working_copy_store::StoredWorkingCopy;
+use jj_lib::working_copy_store::WorkingCopyStore as _;
+use pollster::FutureExt as _;
+
 use crate::cli_util::CommandHelper;
+use crate::cli_util::RevisionArg;
+use crate::cli_util::WorkspaceCommandHelper;
 use crate::command_error::CommandError;
 use crate::command_error::user_error;
 use crate::command_error::user_error_with_message;
+use crate::complete;
 use crate::ui::Ui;
 
 /// Execute an external command via jj
@@ -63,9 +76,40 @@ use crate::ui::Ui;
 ///
 /// > Note: Shebangs (e.g. `#!/usr/bin/env`) aren't necessary since you're
 /// > already explicitly passing your script into the right shell.
+///
+/// With `--at-rev`, the command runs in a separate working copy of the given
+/// revision instead of the current directory. The working copies are shared
+/// with `jj run`, so ignored build outputs are kept between invocations. The
+/// environment variables JJ_COMMIT_ID and JJ_CHANGE_ID are set to the ids of
+/// that revision. With `--template-args`, `{...}` in the arguments is also
+/// expanded as a commit template:
+///
+/// ```shell
+/// $ jj util exec --at-rev main --template-args -- cargo build --target-dir 'target-{change_id.short()}'
+/// ```
+///
+/// Use `{{` and `}}` to pass literal braces in that mode.
 #[derive(clap::Args, Clone, Debug)]
 #[command(verbatim_doc_comment)]
 pub(crate) struct UtilExecArgs {
+    /// Run the command in a working copy of this revision
+    ///
+    /// Changes made to the files by an earlier command are reverted before the
+    /// command is run, except for ignored files. Use `jj run --clean` to remove
+    /// the working copies.
+    #[arg(long, value_name = "REVSET")]
+    #[arg(add = ArgValueCompleter::new(complete::revset_expression_all))]
+    at_rev: Option<RevisionArg>,
+
+    /// Expand `{...}` in the arguments as a commit template of the `--at-rev`
+    /// revision
+    #[arg(long, requires = "at_rev")]
+    template_args: bool,
+
+    /// Set an environment variable for the command
+    #[arg(long, value_name = "KEY=VALUE", value_parser = parse_env_arg)]
+    env: Vec<(String, String)>,
+
     /// External command to execute
     command: String,
 
@@ -75,7 +119,7 @@ pub(crate) struct UtilExecArgs {
 }
 
 pub fn cmd_util_exec(
-    _: &mut Ui,
+    ui: &mut Ui,
     command: &CommandHelper,
     args: &UtilExecArgs,
 ) -> Result<(), CommandError> {
@@ -84,18 +128,50 @@ pub fn cmd_util_exec(
         .ok()
         .map(|loader| loader.workspace_root());
     let mut cmd = std::process::Command::new(&args.command);
-    cmd.args(&args.args);
+
+    // The working copy is locked until this is dropped
+    let mut stored_working_copy = None;
+    if let Some(rev) = &args.at_rev {
+        let workspace_command = command.workspace_helper(ui)?;
+        let commit = workspace_command.resolve_single_rev(ui, rev)?;
+        if args.template_args {
+            let expanded_args: Vec<_> = args
+                .args
+                .iter()
+                .map(|arg| {
+                    expand_template_arg(arg, |text| {
+                        let template = workspace_command.parse_commit_template(ui, text)?;
+                        let output = template.format_plain_text(&commit);
+                        Ok(String::from_utf8_lossy(&output).into_owned())
+                    })
+                })
+                .try_collect()?;
+            cmd.args(expanded_args);
+        } else {
+            cmd.args(&args.args);
+        }
+        let stored = check_out_commit(ui, &workspace_command, &commit)?;
+        cmd.current_dir(&stored.path);
+        cmd.env("JJ_COMMIT_ID", commit.id().hex());
+        cmd.env("JJ_CHANGE_ID", commit.change_id().reverse_hex());
+        stored_working_copy = Some(stored);
+    } else {
+        cmd.args(&args.args);
+    }
 
     if let Some(workspace_root) = workspace_root {
         cmd.env("JJ_WORKSPACE_ROOT", workspace_root);
     }
+    cmd.envs(args.env.iter().map(|(key, value)| (key, value)));
 
     let status = cmd.status().map_err(|err| {
         user_error_with_message(
             format!("Failed to execute external command '{}'", &args.command),
             err,
         )
-    })?;
+    });
+    drop(stored_working_copy);
+    let status = status?;
 
     // Try to match the exit status of the executed process.
     if let Some(exit_code) = status.code() {
@@ -109,3 +185,69 @@ pub fn cmd_util_exec(
     }
     Ok(())
 }
+
+/// Parses `KEY=VALUE` string.
+fn parse_env_arg(item_str: &str) -> Result<(String, String), &'static str> {
+    match item_str.split_once('=') {
+        Some((key, value)) if !key.is_empty() => Ok((key.to_owned(), value.to_owned())),
+        _ => Err("must be specified as KEY=VALUE"),
+    }
+}
+
+/// Expands `{template}` segments in the argument by using the `render`
+/// function. `{{` and `}}` are replaced with literal braces.
+fn expand_template_arg(
+    arg: &str,
+    mut render: impl FnMut(&str) -> Result<String, CommandError>,
+) -> Result<String, CommandError> {
+    let mut expanded = String::with_capacity(arg.len());
+    let mut rest = arg;
+    while let Some(pos) = rest.find(['{', '}']) {
+        expanded.push_str(&rest[..pos]);
+        let tail = &rest[pos..];
+        if let Some(after) = tail.strip_prefix("{{") {
+            expanded.push('{');
+            rest = after;
+        } else if let Some(after) = tail.strip_prefix("}}") {
+            expanded.push('}');
+            rest = after;
+        } else if let Some(after) = tail.strip_prefix('{')
+            && let Some((text, after)) = after.split_once('}')
+        {
+            expanded.push_str(&render(text)?);
+            rest = after;
+        } else {
+            return Err(user_error(format!("Unmatched brace in argument: {arg}")));
+        }
+    }
+    expanded.push_str(rest);
+    Ok(expanded)
+}
+
+/// Returns a working copy of the commit from the store used by `jj run`,
+/// reverting the changes left by earlier commands if it's reused.
+fn check_out_commit(
+    ui: &Ui,
+    workspace_command: &WorkspaceCommandHelper,
+    commit: &Commit,
+) -> Result<StoredWorkingCopy, CommandError> {
+    let working_copy_store = DefaultWorkingCopyStore::load(
+        workspace_command.repo().store().clone(),
+        workspace_command.repo_path().join("run"),
+        workspace_command.settings(),
+    );
+    let operation_id = workspace_command.repo().op_id();
+    let mut stored = working_copy_store.get_or_create_working_copy(commit, operation_id)?;
+    if stored.reused {
+        let auto_tracking_matcher = workspace_command.auto_tracking_matcher(ui)?;
+        let ignore_matcher = workspace_command.ignore_matcher(ui)?;
+        let snapshot_options = workspace_command.snapshot_options_with_start_tracking_matcher(
+            auto_tracking_matcher.as_ref(),
+            ignore_matcher.as_ref(),
+        )?;
+        stored
+            .discard_changes(commit, &snapshot_options, operation_id)
+            .block_on()?;
+    }
+    Ok(stored)
+}
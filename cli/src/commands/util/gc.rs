@@ -19,6 +19,7 @@ use std::time::SystemTime;
 use jj_lib::repo::Repo as _;
 
 use crate::cli_util::CommandHelper;
+use crate::cli_util::WorkspaceCommandHelper;
 use crate::command_error::CommandError;
 use crate::command_error::user_error;
 use crate::ui::Ui;
@@ -46,18 +47,32 @@ pub fn cmd_util_gc(
     command: &CommandHelper,
     args: &UtilGcArgs,
 ) -> Result<(), CommandError> {
-    if !command.is_at_head_operation() {
-        return Err(user_error(
-            "Cannot garbage collect from a non-head operation",
-        ));
-    }
     let keep_newer = match args.expire.as_deref() {
-        None => SystemTime::now() - Duration::from_secs(14 * 86400),
+        None => default_gc_keep_newer(),
         Some("now") => SystemTime::now() - Duration::ZERO,
         _ => return Err(user_error("--expire only accepts 'now'")),
     };
     let workspace_command = command.workspace_helper(ui)?;
+    gc_repo(command, &workspace_command, keep_newer)
+}
+
+/// Returns the time before which obsolete objects and operations are pruned
+/// by default.
+pub(super) fn default_gc_keep_newer() -> SystemTime {
+    SystemTime::now() - Duration::from_secs(14 * 86400)
+}
 
+/// Prunes obsolete operations and objects older than `keep_newer`.
+pub(super) fn gc_repo(
+    command: &CommandHelper,
+    workspace_command: &WorkspaceCommandHelper,
+    keep_newer: SystemTime,
+) -> Result<(), CommandError> {
+    if !command.is_at_head_operation() {
+        return Err(user_error(
+            "Cannot garbage collect from a non-head operation",
+        ));
+    }
     let repo = workspace_command.repo();
     repo.op_store()
         .gc(slice::from_ref(repo.op_id()), keep_newer)?;
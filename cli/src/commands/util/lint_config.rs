@@ -0,0 +1,465 @@
+// Copyright 2026 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::convert::Infallible;
+use std::error;
+use std::io;
+use std::io::Write as _;
+use std::iter;
+use std::ops::Range;
+
+use itertools::Itertools as _;
+use jj_lib::config::ConfigFile;
+use jj_lib::config::ConfigSource;
+use jj_lib::dsl_util;
+use jj_lib::dsl_util::ExpressionFolder;
+use jj_lib::dsl_util::FoldableExpression as _;
+use jj_lib::dsl_util::PatternNode;
+use jj_lib::fileset;
+use jj_lib::fileset::FilesetAliasesMap;
+use jj_lib::revset;
+use jj_lib::revset::RevsetAliasesMap;
+use jj_lib::revset::RevsetParseError;
+
+use crate::cli_util::CommandHelper;
+use crate::command_error::CommandError;
+use crate::command_error::user_error;
+use crate::command_error::user_error_with_message;
+use crate::config::FilesetAliasDefinition;
+use crate::template_parser;
+use crate::template_parser::TemplateAliasesMap;
+use crate::template_parser::TemplateParseError;
+use crate::ui::Ui;
+
+/// Check aliases in config files for errors and deprecated functions
+///
+/// Parses all `revset-aliases`, `template-aliases`, and `fileset-aliases` in
+/// the user, repo, and workspace config files and `--config` arguments.
+/// Unlike the warnings printed when an alias is used, all aliases are checked
+/// whether they are used or not.
+///
+/// Calls to renamed functions can be rewritten with `--fix`. Other deprecated
+/// functions have to be replaced manually.
+#[derive(clap::Args, Clone, Debug)]
+pub struct UtilLintConfigArgs {
+    /// Rewrite calls to renamed functions in the config files
+    #[arg(long)]
+    fix: bool,
+}
+
+/// Deprecated function or method which may be used in aliases.
+///
+/// These should be kept in sync with the warnings emitted when expressions are
+/// built.
+struct Deprecation {
+    name: &'static str,
+    message: &'static str,
+    /// New name if the function is just renamed.
+    new_name: Option<&'static str>,
+}
+
+const REVSET_DEPRECATIONS: &[Deprecation] = &[
+    // TODO: Remove in jj 0.43+
+    Deprecation {
+        name: "git_refs",
+        message: "git_refs() is deprecated; use remote_bookmarks()/tags() instead",
+        new_name: None,
+    },
+    // TODO: Remove in jj 0.43+
+    Deprecation {
+        name: "git_head",
+        message: "git_head() is deprecated; use first_parent(@) instead",
+        new_name: None,
+    },
+    // TODO: Remove in jj 0.44+
+    Deprecation {
+        name: "diff_contains",
+        message: "diff_contains() is deprecated; use diff_lines() instead",
+        new_name: Some("diff_lines"),
+    },
+];
+
+const TEMPLATE_DEPRECATIONS: &[Deprecation] = &[
+    // TODO: Remove in jj 0.43+
+    Deprecation {
+        name: "git_refs",
+        message: "commit.git_refs() is deprecated; use .remote_bookmarks()/tags() instead",
+        new_name: None,
+    },
+    // TODO: Remove in jj 0.43+
+    Deprecation {
+        name: "git_head",
+        message: "commit.git_head() is deprecated; use .contained_in('first_parent(@)') instead",
+        new_name: None,
+    },
+];
+
+type BoxedError = Box<dyn error::Error + Send + Sync>;
+
+#[derive(Clone, Copy, Debug)]
+enum AliasKind {
+    Revset,
+    Template,
+    Fileset,
+}
+
+impl AliasKind {
+    fn table_name(self) -> &'static str {
+        match self {
+            Self::Revset => "revset-aliases",
+            Self::Template => "template-aliases",
+            Self::Fileset => "fileset-aliases",
+        }
+    }
+
+    fn description(self) -> &'static str {
+        match self {
+            Self::Revset => "revset alias",
+            Self::Template => "template alias",
+            Self::Fileset => "fileset alias",
+        }
+    }
+
+    /// Parses the alias, and returns the deprecated functions used in it.
+    /// Returns `Err` if the alias can't be parsed.
+    fn lint(self, decl: &str, defn: &str) -> Result<Vec<Deprecated>, BoxedError> {
+        match self {
+            Self::Revset => {
+                RevsetAliasesMap::new().insert(decl, defn)?;
+                let node = revset::parse_program(defn)?;
+                let mut collector = DeprecationCollector::new(REVSET_DEPRECATIONS);
+                let Ok(_) = collector.fold_expression(node);
+                Ok(collector.into_deprecated(RevsetParseError::expression))
+            }
+            Self::Template => {
+                TemplateAliasesMap::new().insert(decl, defn)?;
+                let node = template_parser::parse_template(defn)?;
+                let mut collector = DeprecationCollector::new(TEMPLATE_DEPRECATIONS);
+                let Ok(_) = collector.fold_expression(node);
+                Ok(collector.into_deprecated(TemplateParseError::expression))
+            }
+            Self::Fileset => {
+                FilesetAliasesMap::new().insert_with_defaults(decl, defn)?;
+                fileset::parse_program(defn)?;
+                Ok(vec![])
+            }
+        }
+    }
+}
+
+/// Use of deprecated function found in alias.
+struct Deprecated {
+    error: BoxedError,
+    /// Range of the function name to be replaced, and the new name.
+    fix: Option<(Range<usize>, &'static str)>,
+}
+
+/// Collects uses of deprecated functions by walking the AST.
+struct DeprecationCollector<'i> {
+    deprecations: &'static [Deprecation],
+    found: Vec<(&'static Deprecation, pest::Span<'i>)>,
+}
+
+impl<'i> DeprecationCollector<'i> {
+    fn new(deprecations: &'static [Deprecation]) -> Self {
+        Self {
+            deprecations,
+            found: vec![],
+        }
+    }
+
+    fn check(&mut self, name: &str, span: pest::Span<'i>) {
+        if let Some(deprecation) = self.deprecations.iter().find(|d| d.name == name) {
+            self.found.push((deprecation, span));
+        }
+    }
+
+    fn into_deprecated<E>(
+        self,
+        to_error: impl Fn(&'static str, pest::Span<'i>) -> E,
+    ) -> Vec<Deprecated>
+    where
+        E: error::Error + Send + Sync + 'static,
+    {
+        self.found
+            .into_iter()
+            .map(|(deprecation, span)| Deprecated {
+                error: to_error(deprecation.message, span).into(),
+                fix: deprecation
+                    .new_name
+                    .map(|new_name| (span.start()..span.end(), new_name)),
+            })
+            .collect()
+    }
+}
+
+impl<'i> ExpressionFolder<'i, revset::ExpressionKind<'i>> for DeprecationCollector<'i> {
+    type Error = Infallible;
+
+    fn fold_identifier(
+        &mut self,
+        name: &'i str,
+        _span: pest::Span<'i>,
+    ) -> Result<revset::ExpressionKind<'i>, Self::Error> {
+        Ok(revset::ExpressionKind::Identifier(name))
+    }
+
+    fn fold_pattern(
+        &mut self,
+        pattern: Box<PatternNode<'i, revset::ExpressionKind<'i>>>,
+        _span: pest::Span<'i>,
+    ) -> Result<revset::ExpressionKind<'i>, Self::Error> {
+        let pattern = dsl_util::fold_pattern_value(self, *pattern)?;
+        Ok(revset::ExpressionKind::Pattern(Box::new(pattern)))
+    }
+
+    fn fold_function_call(
+        &mut self,
+        function: Box<revset::FunctionCallNode<'i>>,
+        _span: pest::Span<'i>,
+    ) -> Result<revset::ExpressionKind<'i>, Self::Error> {
+        self.check(function.name, function.name_span);
+        let function = dsl_util::fold_function_call_args(self, *function)?;
+        Ok(revset::ExpressionKind::FunctionCall(Box::new(function)))
+    }
+}
+
+impl<'i> ExpressionFolder<'i, template_parser::ExpressionKind<'i>> for DeprecationCollector<'i> {
+    type Error = Infallible;
+
+    fn fold_expression(
+        &mut self,
+        node: template_parser::ExpressionNode<'i>,
+    ) -> Result<template_parser::ExpressionNode<'i>, Self::Error> {
+        // Method call isn't passed to fold_function_call().
+        if let template_parser::ExpressionKind::MethodCall(method) = &node.kind {
+            self.check(method.function.name, method.function.name_span);
+        }
+        let template_parser::ExpressionNode { kind, span } = node;
+        let kind = kind.fold(self, span)?;
+        Ok(template_parser::ExpressionNode { kind, span })
+    }
+
+    fn fold_identifier(
+        &mut self,
+        name: &'i str,
+        span: pest::Span<'i>,
+    ) -> Result<template_parser::ExpressionKind<'i>, Self::Error> {
+        // Keyword is a method call on self.
+        self.check(name, span);
+        Ok(template_parser::ExpressionKind::Identifier(name))
+    }
+
+    fn fold_pattern(
+        &mut self,
+        pattern: Box<PatternNode<'i, template_parser::ExpressionKind<'i>>>,
+        _span: pest::Span<'i>,
+    ) -> Result<template_parser::ExpressionKind<'i>, Self::Error> {
+        let pattern = dsl_util::fold_pattern_value(self, *pattern)?;
+        Ok(template_parser::ExpressionKind::Pattern(Box::new(pattern)))
+    }
+
+    fn fold_function_call(
+        &mut self,
+        function: Box<template_parser::FunctionCallNode<'i>>,
+        _span: pest::Span<'i>,
+    ) -> Result<template_parser::ExpressionKind<'i>, Self::Error> {
+        let function = dsl_util::fold_function_call_args(self, *function)?;
+        Ok(template_parser::ExpressionKind::FunctionCall(Box::new(
+            function,
+        )))
+    }
+}
+
+pub fn cmd_util_lint_config(
+    ui: &mut Ui,
+    command: &CommandHelper,
+    args: &UtilLintConfigArgs,
+) -> Result<(), CommandError> {
+    let mut problem_count = 0;
+    let mut fixable_count = 0;
+    let mut fixed_count = 0;
+    for layer in command.raw_config().as_ref().layers() {
+        if layer.source == ConfigSource::Default {
+            continue;
+        }
+        let location = match &layer.path {
+            Some(path) => format!("{} config {}", layer.source, path.display()),
+            None => format!("{} config", layer.source),
+        };
+        let mut new_values = vec![];
+        for kind in [AliasKind::Revset, AliasKind::Template, AliasKind::Fileset] {
+            let table_name = kind.table_name();
+            let table = match layer.look_up_table(table_name) {
+                Ok(Some(table)) => table,
+                Ok(None) => continue,
+                Err(item) => {
+                    let context = format!("In `{table_name}` ({location})");
+                    let err = format!("Expected a table, but is {}", item.type_name());
+                    print_problem(ui, &context, &*BoxedError::from(err), true)?;
+                    problem_count += 1;
+                    continue;
+                }
+            };
+            for (decl, item) in table.iter() {
+                let context = format!("In {} `{decl}` ({location})", kind.description());
+                let defn = match kind {
+                    AliasKind::Fileset => {
+                        FilesetAliasDefinition::from_item(item).map(|defn| defn.definition)
+                    }
+                    AliasKind::Revset | AliasKind::Template => item
+                        .as_str()
+                        .map(str::to_owned)
+                        .ok_or_else(|| format!("Expected a string, but is {}", item.type_name())),
+                };
+                let defn = match defn {
+                    Ok(defn) => defn,
+                    Err(err) => {
+                        print_problem(ui, &context, &*BoxedError::from(err), true)?;
+                        problem_count += 1;
+                        continue;
+                    }
+                };
+                let deprecated = match kind.lint(decl, &defn) {
+                    Ok(deprecated) => deprecated,
+                    Err(err) => {
+                        print_problem(ui, &context, &*err, true)?;
+                        problem_count += 1;
+                        continue;
+                    }
+                };
+                let fixes = deprecated
+                    .iter()
+                    .filter_map(|deprecated| deprecated.fix.clone())
+                    .collect_vec();
+                if args.fix && !fixes.is_empty() && layer.path.is_some() {
+                    new_values.push(([table_name, decl], apply_fixes(&defn, fixes)));
+                    for deprecated in deprecated.iter().filter(|d| d.fix.is_none()) {
+                        print_problem(ui, &context, &*deprecated.error, false)?;
+                        problem_count += 1;
+                    }
+                } else {
+                    for deprecated in &deprecated {
+                        print_problem(ui, &context, &*deprecated.error, false)?;
+                        problem_count += 1;
+                    }
+                    fixable_count += fixes.len();
+                }
+            }
+        }
+        if !new_values.is_empty() {
+            let mut file = ConfigFile::from_layer(layer.clone()).expect("layer should have path");
+            for (name, value) in &new_values {
+                file.set_value(name, value.as_str()).map_err(|err| {
+                    user_error_with_message(format!("Failed to set {}", name.join(".")), err)
+                })?;
+            }
+            file.save()?;
+            writeln!(
+                ui.status(),
+                "Rewrote {} aliases in {}",
+                new_values.len(),
+                file.path().display()
+            )?;
+            fixed_count += new_values.len();
+        }
+    }
+
+    if problem_count > 0 {
+        let mut err = user_error(format!("Found {problem_count} problems in config"));
+        if fixable_count > 0 {
+            err.add_hint(format!(
+                "{fixable_count} of them can be fixed by `jj util lint-config --fix`"
+            ));
+        }
+        Err(err)
+    } else {
+        if fixed_count == 0 {
+            writeln!(ui.status(), "No problems found in config")?;
+        }
+        Ok(())
+    }
+}
+
+fn print_problem(ui: &Ui, context: &str, err: &dyn error::Error, is_error: bool) -> io::Result<()> {
+    if is_error {
+        writeln!(ui.error_with_heading("Error: "), "{context}")?;
+    } else {
+        writeln!(ui.warning_default(), "{context}")?;
+    }
+    for err in iter::successors(Some(err), |&err| err.source()) {
+        writeln!(ui.stderr(), "{err}")?;
+    }
+    Ok(())
+}
+
+/// Replaces the function names at the given ranges.
+fn apply_fixes(text: &str, mut fixes: Vec<(Range<usize>, &str)>) -> String {
+    fixes.sort_by_key(|(range, _)| range.start);
+    let mut new_text = text.to_owned();
+    for (range, new_name) in fixes.into_iter().rev() {
+        new_text.replace_range(range, new_name);
+    }
+    new_text
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lint_revset_alias() {
+        let deprecated = AliasKind::Revset
+            .lint(
+                "f(x)",
+                "diff_contains(x) | git_head() | diff_contains(x, y)",
+            )
+            .unwrap();
+        let fixes = deprecated
+            .iter()
+            .filter_map(|deprecated| deprecated.fix.clone())
+            .collect_vec();
+        assert_eq!(deprecated.len(), 3);
+        assert_eq!(
+            apply_fixes("diff_contains(x) | git_head() | diff_contains(x, y)", fixes),
+            "diff_lines(x) | git_head() | diff_lines(x, y)"
+        );
+        assert!(AliasKind::Revset.lint("f(x)", "x &").is_err());
+        assert!(AliasKind::Revset.lint("f(x, x)", "x").is_err());
+    }
+
+    #[test]
+    fn test_lint_template_alias() {
+        let deprecated = AliasKind::Template
+            .lint("f", "if(git_head, self.git_refs().map(|r| r.name()))")
+            .unwrap();
+        assert_eq!(deprecated.len(), 2);
+        assert!(deprecated.iter().all(|deprecated| deprecated.fix.is_none()));
+        // Deprecated name isn't reported if it isn't a method call
+        let deprecated = AliasKind::Template.lint("f", "'git_head'").unwrap();
+        assert!(deprecated.is_empty());
+        assert!(AliasKind::Template.lint("f", "'").is_err());
+    }
+
+    #[test]
+    fn test_lint_fileset_alias() {
+        assert!(
+            AliasKind::Fileset
+                .lint("f", "glob:'*.rs'")
+                .unwrap()
+                .is_empty()
+        );
+        assert!(AliasKind::Fileset.lint("f", "~").is_err());
+    }
+}
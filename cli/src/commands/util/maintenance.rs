@@ -0,0 +1,353 @@
+// Copyright 2026 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::path::Path;
+use std::process::Command;
+
+use clap::ValueEnum as _;
+use jj_lib::content_hash::blake2b_hash;
+use jj_lib::hex_util::encode_hex;
+use jj_lib::repo::Repo as _;
+
+use super::gc::default_gc_keep_newer;
+use super::gc::gc_repo;
+use crate::cli_util::CommandHelper;
+use crate::cli_util::WorkspaceCommandHelper;
+use crate::command_error::CommandError;
+use crate::command_error::user_error;
+use crate::command_error::user_error_with_message;
+use crate::ui::Ui;
+
+/// Run repository maintenance tasks
+///
+/// Runs the tasks enabled in the `maintenance` config table, or the tasks
+/// given by `--task`. This is meant to be run periodically in the background,
+/// which can be set up with `--register`.
+///
+/// The available tasks are:
+///
+/// - `gc`: Prune operations and objects which were abandoned more than 2 weeks
+///   ago, like `jj util gc`.
+/// - `git-repack`: Repack the Git repository of a colocated workspace, which
+///   keeps Git commands fast.
+/// - `secure-config-gc`: Remove the per-repo and per-workspace config
+///   directories of repos which no longer exist. Directories containing a
+///   non-empty config are kept.
+#[derive(clap::Args, Clone, Debug)]
+#[command(verbatim_doc_comment)]
+pub struct UtilMaintenanceArgs {
+    /// Run only the given tasks, regardless of the config
+    #[arg(long = "task", value_name = "TASK", value_enum)]
+    tasks: Vec<MaintenanceTask>,
+
+    /// Schedule `jj util maintenance` to run daily in this workspace
+    ///
+    /// This installs a systemd user timer on Linux, a launchd agent on macOS,
+    /// or a scheduled task on Windows.
+    #[arg(long, conflicts_with = "tasks")]
+    register: bool,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq, clap::ValueEnum)]
+enum MaintenanceTask {
+    Gc,
+    GitRepack,
+    SecureConfigGc,
+}
+
+impl MaintenanceTask {
+    fn name(self) -> &'static str {
+        match self {
+            Self::Gc => "gc",
+            Self::GitRepack => "git-repack",
+            Self::SecureConfigGc => "secure-config-gc",
+        }
+    }
+}
+
+pub fn cmd_util_maintenance(
+    ui: &mut Ui,
+    command: &CommandHelper,
+    args: &UtilMaintenanceArgs,
+) -> Result<(), CommandError> {
+    let workspace_command = command.workspace_helper_no_snapshot(ui)?;
+    if args.register {
+        return register_maintenance(ui, &workspace_command);
+    }
+    let tasks = if args.tasks.is_empty() {
+        let settings = command.settings();
+        let mut tasks = vec![];
+        for &task in MaintenanceTask::value_variants() {
+            if settings.get_bool(["maintenance", task.name()])? {
+                tasks.push(task);
+            }
+        }
+        tasks
+    } else {
+        args.tasks.clone()
+    };
+
+    for task in tasks {
+        match task {
+            MaintenanceTask::Gc => {
+                gc_repo(command, &workspace_command, default_gc_keep_newer())?;
+            }
+            MaintenanceTask::GitRepack => {
+                if !workspace_command.working_copy_shared_with_git() {
+                    writeln!(
+                        ui.status(),
+                        "Skipped maintenance task git-repack: the workspace isn't colocated with \
+                         Git"
+                    )?;
+                    continue;
+                }
+                run_git_repack(&workspace_command)?;
+            }
+            MaintenanceTask::SecureConfigGc => {
+                let removed = command.config_env().gc_secure_configs()?;
+                if !removed.is_empty() {
+                    writeln!(
+                        ui.status(),
+                        "Removed {} unused repo config directories",
+                        removed.len()
+                    )?;
+                }
+            }
+        }
+        writeln!(ui.status(), "Ran maintenance task {}", task.name())?;
+    }
+    Ok(())
+}
+
+#[cfg(feature = "git")]
+fn run_git_repack(workspace_command: &WorkspaceCommandHelper) -> Result<(), CommandError> {
+    let git_backend = jj_lib::git::get_git_backend(workspace_command.repo().store())?;
+    let git_settings = jj_lib::git::GitSettings::from_settings(workspace_command.settings())?;
+    let mut git = Command::new(&git_settings.executable_path);
+    git.arg("--git-dir=.") // turn off discovery
+        .args(["repack", "-d", "-q"])
+        .current_dir(git_backend.git_repo_path());
+    run_command(git)
+}
+
+#[cfg(not(feature = "git"))]
+fn run_git_repack(_workspace_command: &WorkspaceCommandHelper) -> Result<(), CommandError> {
+    Ok(())
+}
+
+/// Installs a job which runs `jj util maintenance` daily in the workspace.
+fn register_maintenance(
+    ui: &mut Ui,
+    workspace_command: &WorkspaceCommandHelper,
+) -> Result<(), CommandError> {
+    let workspace_root = workspace_command.workspace_root();
+    let jj_path = std::env::current_exe()
+        .map_err(|err| user_error_with_message("Failed to find the jj executable", err))?;
+    let root_str = workspace_root.to_string_lossy();
+    // The job name must be stable so that registering again updates the job.
+    let job_name = format!(
+        "jj-maintenance-{}",
+        encode_hex(&blake2b_hash(root_str.as_ref())[..8])
+    );
+    let path = install_job(&job_name, &jj_path, workspace_root)?;
+    writeln!(
+        ui.status(),
+        "Registered daily maintenance job {job_name} for {root_str}"
+    )?;
+    if let Some(path) = path {
+        writeln!(
+            ui.hint_default(),
+            "The job is defined in {}",
+            path.display()
+        )?;
+    }
+    Ok(())
+}
+
+#[cfg(all(unix, not(target_os = "macos")))]
+fn install_job(
+    job_name: &str,
+    jj_path: &Path,
+    workspace_root: &Path,
+) -> Result<Option<std::path::PathBuf>, CommandError> {
+    use std::fs;
+
+    use etcetera::BaseStrategy as _;
+    use indoc::formatdoc;
+    use jj_lib::file_util::IoResultExt as _;
+
+    let unit_dir = etcetera::choose_base_strategy()
+        .map_err(|err| user_error_with_message("Failed to find the config directory", err))?
+        .config_dir()
+        .join("systemd")
+        .join("user");
+    fs::create_dir_all(&unit_dir).context(&unit_dir)?;
+    let service_path = unit_dir.join(format!("{job_name}.service"));
+    let description = workspace_root.display().to_string().replace('%', "%%");
+    let service = formatdoc! {"
+        [Unit]
+        Description=jj maintenance for {description}
+
+        [Service]
+        Type=oneshot
+        ExecStart={jj} --repository {root} util maintenance
+        ",
+        jj = systemd_quote(&jj_path.to_string_lossy()),
+        root = systemd_quote(&workspace_root.to_string_lossy()),
+    };
+    fs::write(&service_path, service).context(&service_path)?;
+    let timer_path = unit_dir.join(format!("{job_name}.timer"));
+    let timer = formatdoc! {"
+        [Unit]
+        Description=Daily jj maintenance for {description}
+
+        [Timer]
+        OnCalendar=daily
+        Persistent=true
+        RandomizedDelaySec=1h
+
+        [Install]
+        WantedBy=timers.target
+    "};
+    fs::write(&timer_path, timer).context(&timer_path)?;
+
+    let mut systemctl = Command::new("systemctl");
+    systemctl.args(["--user", "daemon-reload"]);
+    run_command(systemctl)?;
+    let mut systemctl = Command::new("systemctl");
+    systemctl
+        .args(["--user", "enable", "--now"])
+        .arg(format!("{job_name}.timer"));
+    run_command(systemctl)?;
+    Ok(Some(timer_path))
+}
+
+#[cfg(all(unix, not(target_os = "macos")))]
+fn systemd_quote(s: &str) -> String {
+    let escaped = s
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('%', "%%");
+    format!("\"{escaped}\"")
+}
+
+#[cfg(target_os = "macos")]
+fn install_job(
+    job_name: &str,
+    jj_path: &Path,
+    workspace_root: &Path,
+) -> Result<Option<std::path::PathBuf>, CommandError> {
+    use std::fs;
+
+    use jj_lib::file_util::IoResultExt as _;
+
+    let home_dir = etcetera::home_dir()
+        .map_err(|err| user_error_with_message("Failed to find the home directory", err))?;
+    let agent_dir = home_dir.join("Library").join("LaunchAgents");
+    fs::create_dir_all(&agent_dir).context(&agent_dir)?;
+    let plist_path = agent_dir.join(format!("{job_name}.plist"));
+    let plist = format!(
+        r#"<?xml version="1.0" encoding="UTF-8"?>
+<!DOCTYPE plist PUBLIC "-//Apple//DTD PLIST 1.0//EN" "http://www.apple.com/DTDs/PropertyList-1.0.dtd">
+<plist version="1.0">
+<dict>
+  <key>Label</key>
+  <string>{label}</string>
+  <key>ProgramArguments</key>
+  <array>
+    <string>{jj}</string>
+    <string>--repository</string>
+    <string>{root}</string>
+    <string>util</string>
+    <string>maintenance</string>
+  </array>
+  <key>StartCalendarInterval</key>
+  <dict>
+    <key>Hour</key>
+    <integer>3</integer>
+    <key>Minute</key>
+    <integer>0</integer>
+  </dict>
+</dict>
+</plist>
+"#,
+        label = xml_escape(job_name),
+        jj = xml_escape(&jj_path.to_string_lossy()),
+        root = xml_escape(&workspace_root.to_string_lossy()),
+    );
+    fs::write(&plist_path, plist).context(&plist_path)?;
+
+    // Unload the old definition if the job was registered before.
+    Command::new("launchctl")
+        .arg("unload")
+        .arg(&plist_path)
+        .output()
+        .ok();
+    let mut launchctl = Command::new("launchctl");
+    launchctl.args(["load", "-w"]).arg(&plist_path);
+    run_command(launchctl)?;
+    Ok(Some(plist_path))
+}
+
+#[cfg(target_os = "macos")]
+fn xml_escape(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(windows)]
+fn install_job(
+    job_name: &str,
+    jj_path: &Path,
+    workspace_root: &Path,
+) -> Result<Option<std::path::PathBuf>, CommandError> {
+    let mut schtasks = Command::new("schtasks");
+    schtasks
+        .args([
+            "/Create", "/F", "/SC", "DAILY", "/ST", "03:00", "/TN", job_name,
+        ])
+        .arg("/TR")
+        .arg(format!(
+            r#""{}" --repository "{}" util maintenance"#,
+            jj_path.display(),
+            workspace_root.display()
+        ));
+    run_command(schtasks)?;
+    Ok(None)
+}
+
+#[cfg(not(any(unix, windows)))]
+fn install_job(
+    _job_name: &str,
+    _jj_path: &Path,
+    _workspace_root: &Path,
+) -> Result<Option<std::path::PathBuf>, CommandError> {
+    Err(user_error(
+        "Registering maintenance jobs isn't supported on this platform",
+    ))
+}
+
+fn run_command(mut cmd: Command) -> Result<(), CommandError> {
+    let program = cmd.get_program().to_string_lossy().into_owned();
+    tracing::info!(?cmd, "running maintenance command");
+    let status = cmd
+        .status()
+        .map_err(|err| user_error_with_message(format!("Failed to run {program}"), err))?;
+    if !status.success() {
+        return Err(user_error(format!("{program} failed with {status}")));
+    }
+    Ok(())
+}
@@ -17,6 +17,8 @@ mod config_schema;
 mod exec;
 mod gc;
 mod install_man_pages;
+mod lint_config;
+mod maintenance;
 mod markdown_help;
 mod snapshot;
 
@@ -33,6 +35,10 @@ use self::gc::UtilGcArgs;
 use self::gc::cmd_util_gc;
 use self::install_man_pages::UtilInstallManPagesArgs;
 use self::install_man_pages::cmd_util_install_man_pages;
+use self::lint_config::UtilLintConfigArgs;
+use self::lint_config::cmd_util_lint_config;
+use self::maintenance::UtilMaintenanceArgs;
+use self::maintenance::cmd_util_maintenance;
 use self::markdown_help::UtilMarkdownHelp;
 use self::markdown_help::cmd_util_markdown_help;
 use self::snapshot::UtilSnapshotArgs;
@@ -49,6 +55,8 @@ pub(crate) enum UtilCommand {
     Exec(UtilExecArgs),
     Gc(UtilGcArgs),
     InstallManPages(UtilInstallManPagesArgs),
+    LintConfig(UtilLintConfigArgs),
+    Maintenance(UtilMaintenanceArgs),
     MarkdownHelp(UtilMarkdownHelp),
     Snapshot(UtilSnapshotArgs),
 }
@@ -65,6 +73,8 @@ pub(crate) fn cmd_util(
         UtilCommand::Exec(args) => cmd_util_exec(ui, command, args),
         UtilCommand::Gc(args) => cmd_util_gc(ui, command, args),
         UtilCommand::InstallManPages(args) => cmd_util_install_man_pages(ui, command, args),
+        UtilCommand::LintConfig(args) => cmd_util_lint_config(ui, command, args),
+        UtilCommand::Maintenance(args) => cmd_util_maintenance(ui, command, args),
         UtilCommand::MarkdownHelp(args) => cmd_util_markdown_help(ui, command, args),
         UtilCommand::Snapshot(args) => cmd_util_snapshot(ui, command, args),
     }
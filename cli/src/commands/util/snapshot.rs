@@ -12,6 +12,9 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::thread;
+use std::time::Duration;
+
 use crate::cli_util::CommandHelper;
 use crate::command_error::CommandError;
 use crate::ui::Ui;
@@ -27,13 +30,23 @@ use crate::ui::Ui;
 /// `jj operation log --limit 1`. However, since that command also snapshots the
 /// working copy, there would be no need to run `jj util snapshot` first.
 #[derive(clap::Args, Clone, Debug)]
-pub struct UtilSnapshotArgs {}
+pub struct UtilSnapshotArgs {
+    /// Keep snapshotting the working copy until interrupted
+    ///
+    /// The working copy is checked every `snapshot.watch-interval-seconds`.
+    /// Changes to the config files are picked up without restarting.
+    #[arg(long)]
+    watch: bool,
+}
 
 pub fn cmd_util_snapshot(
     ui: &mut Ui,
     command: &CommandHelper,
-    _args: &UtilSnapshotArgs,
+    args: &UtilSnapshotArgs,
 ) -> Result<(), CommandError> {
+    if args.watch {
+        return watch_snapshot(ui, command);
+    }
     let mut workspace_command = command.workspace_helper_no_snapshot(ui)?;
 
     // Trigger the snapshot if needed.
@@ -46,3 +59,25 @@ pub fn cmd_util_snapshot(
 
     Ok(())
 }
+
+fn watch_snapshot(ui: &mut Ui, command: &CommandHelper) -> Result<(), CommandError> {
+    let mut watcher = command.config_watcher();
+    let mut settings = command.settings().clone();
+    loop {
+        if let Some(config) = watcher.reload_if_changed(ui)? {
+            ui.reset(&config)?;
+            settings = settings.with_new_config(config)?;
+            writeln!(ui.status(), "Reloaded config.")?;
+        }
+        // The workspace is reloaded every time so that the new settings and
+        // operations by other processes are picked up.
+        let mut workspace_command =
+            command.workspace_helper_no_snapshot_with_settings(ui, &settings)?;
+        if workspace_command.maybe_snapshot(ui)? {
+            writeln!(ui.status(), "Snapshot complete.")?;
+        }
+        drop(workspace_command);
+        let interval: u64 = settings.get("snapshot.watch-interval-seconds")?;
+        thread::sleep(Duration::from_secs(interval));
+    }
+}
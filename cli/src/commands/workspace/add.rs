@@ -16,6 +16,7 @@ use std::fs;
 
 use itertools::Itertools as _;
 use jj_lib::commit::CommitIteratorExt as _;
+use jj_lib::config::ConfigGetResultExt as _;
 use jj_lib::file_util;
 use jj_lib::file_util::IoResultExt as _;
 use jj_lib::ref_name::WorkspaceNameBuf;
@@ -49,6 +50,9 @@ enum SparseInheritance {
 ///
 /// By default, the new workspace inherits the sparse patterns of the current
 /// workspace. You can override this with the `--sparse-patterns` option.
+///
+/// The workspace config of the new workspace is initialized from the
+/// `workspace-config-template` table of the repo config, if set.
 #[derive(clap::Args, Clone, Debug)]
 pub struct WorkspaceAddArgs {
     /// Where to create the new workspace
@@ -122,10 +126,16 @@ pub fn cmd_workspace_add(
         ));
     }
 
+    let config_template = old_workspace_command
+        .settings()
+        .get_table("workspace-config-template")
+        .optional()?
+        .filter(|table| !table.is_empty());
+
     let working_copy_factory = command.get_working_copy_factory()?;
     let repo_path = old_workspace_command.repo_path();
-    // If we add per-workspace configuration, we'll need to reload settings for
-    // the new workspace.
+    // The workspace config is written below, but the settings aren't reloaded
+    // for the new workspace. It will be picked up by the next command.
     let (new_workspace, repo) = Workspace::init_workspace_with_existing_repo(
         &destination_path,
         repo_path,
@@ -134,6 +144,12 @@ pub fn cmd_workspace_add(
         workspace_name.clone(),
     )
     .block_on()?;
+    if let Some(table) = config_template {
+        let content = toml_edit::DocumentMut::from(table).to_string();
+        command
+            .config_env()
+            .init_workspace_config(new_workspace.workspace_root(), &content)?;
+    }
     writeln!(
         ui.status(),
         "Created workspace in \"{}\"",
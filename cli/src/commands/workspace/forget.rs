@@ -15,8 +15,6 @@
 use clap_complete::ArgValueCandidates;
 use itertools::Itertools as _;
 use jj_lib::ref_name::WorkspaceNameBuf;
-use jj_lib::workspace_store::SimpleWorkspaceStore;
-use jj_lib::workspace_store::WorkspaceStore as _;
 use pollster::FutureExt as _;
 use tracing::instrument;
 
@@ -73,7 +71,7 @@ pub fn cmd_workspace_forget(
         return Ok(());
     }
 
-    let workspace_store = SimpleWorkspaceStore::load(workspace_command.repo_path())?;
+    let workspace_store = workspace_command.workspace_store().clone();
 
     // bundle every workspace forget into a single transaction, so that e.g.
     // undo correctly restores all of them at once.
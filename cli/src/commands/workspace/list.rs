@@ -14,6 +14,7 @@
 
 use clap_complete::ArgValueCandidates;
 use jj_lib::repo::Repo as _;
+use jj_lib::workspace_store::WorkspaceInfo;
 use tracing::instrument;
 
 use crate::cli_util::CommandHelper;
@@ -24,6 +25,14 @@ use crate::templater::TemplateRenderer;
 use crate::ui::Ui;
 
 /// List workspaces
+///
+/// The template can show the path recorded for each workspace, and whether
+/// the workspace directory exists or is stale. A workspace is stale if its
+/// working copy hasn't been updated to the working-copy commit, which can be
+/// fixed by running `jj workspace update-stale` in it.
+///
+/// It can also show when, by whom, and by which operation each workspace was
+/// created, which helps to find workspaces that are no longer used.
 #[derive(clap::Args, Clone, Debug)]
 pub struct WorkspaceListArgs {
     /// Render each workspace using the given template
@@ -66,11 +75,25 @@ pub fn cmd_workspace_list(
     };
 
     let repo = workspace_command.repo();
+    let repo_path = workspace_command.repo_path();
+    let workspace_store = workspace_command.workspace_store();
     let mut formatter = ui.stdout_formatter();
 
     for (name, wc_commit_id) in repo.view().wc_commit_ids() {
         let commit = repo.store().get_commit(wc_commit_id)?;
-        let ws_ref = WorkspaceRef::new(name.clone(), commit);
+        let mut info = WorkspaceInfo::load(workspace_store.as_ref(), repo_path, name)?;
+        if name == workspace_command.workspace_name() {
+            info.check_stale(workspace_command.working_copy(), &commit)?;
+        } else if info.exists
+            && let Some(path) = &info.absolute_path
+            // The directory may have been reused or removed, in which case the
+            // staleness can't be checked.
+            && let Ok(workspace) = command.load_workspace_at(path, workspace_command.settings())
+            && name == workspace.workspace_name()
+        {
+            info.check_stale(workspace.working_copy(), &commit)?;
+        }
+        let ws_ref = WorkspaceRef::new(name.clone(), commit).with_info(info);
 
         template.format(&ws_ref, formatter.as_mut())?;
     }
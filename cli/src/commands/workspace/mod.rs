@@ -16,6 +16,7 @@ mod add;
 mod forget;
 mod list;
 mod rename;
+mod repair;
 mod root;
 mod update_stale;
 
@@ -30,6 +31,8 @@ use self::list::WorkspaceListArgs;
 use self::list::cmd_workspace_list;
 use self::rename::WorkspaceRenameArgs;
 use self::rename::cmd_workspace_rename;
+use self::repair::WorkspaceRepairArgs;
+use self::repair::cmd_workspace_repair;
 use self::root::WorkspaceRootArgs;
 use self::root::cmd_workspace_root;
 use self::update_stale::WorkspaceUpdateStaleArgs;
@@ -55,6 +58,7 @@ pub(crate) enum WorkspaceCommand {
     Forget(WorkspaceForgetArgs),
     List(WorkspaceListArgs),
     Rename(WorkspaceRenameArgs),
+    Repair(WorkspaceRepairArgs),
     Root(WorkspaceRootArgs),
     UpdateStale(WorkspaceUpdateStaleArgs),
 }
@@ -70,6 +74,7 @@ pub(crate) fn cmd_workspace(
         WorkspaceCommand::Forget(args) => cmd_workspace_forget(ui, command, args),
         WorkspaceCommand::List(args) => cmd_workspace_list(ui, command, args),
         WorkspaceCommand::Rename(args) => cmd_workspace_rename(ui, command, args),
+        WorkspaceCommand::Repair(args) => cmd_workspace_repair(ui, command, args),
         WorkspaceCommand::Root(args) => cmd_workspace_root(ui, command, args),
         WorkspaceCommand::UpdateStale(args) => cmd_workspace_update_stale(ui, command, args),
     }
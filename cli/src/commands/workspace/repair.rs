@@ -0,0 +1,112 @@
+// Copyright 2020 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::io::Write as _;
+
+use clap_complete::ArgValueCandidates;
+use jj_lib::ref_name::WorkspaceNameBuf;
+use tracing::instrument;
+
+use crate::cli_util::CommandHelper;
+use crate::command_error::CommandError;
+use crate::command_error::user_error;
+use crate::command_error::user_error_with_message;
+use crate::complete;
+use crate::ui::Ui;
+
+/// Update the recorded path of a moved workspace
+///
+/// The path of each workspace is recorded in the repo so that e.g. `jj
+/// workspace root --name` can find it. If a workspace directory is moved, run
+/// this command in the moved workspace, or pass the new path of the workspace
+/// with `--name` and `--path`.
+///
+/// If `working-copy.auto-repair-path` is enabled, the recorded path is updated
+/// automatically when jj is run in a moved workspace.
+#[derive(clap::Args, Clone, Debug)]
+pub struct WorkspaceRepairArgs {
+    /// Name of the workspace to repair (defaults to current)
+    #[arg(
+        long,
+        value_name = "NAME",
+        requires = "path",
+        add = ArgValueCandidates::new(complete::workspaces),
+    )]
+    name: Option<WorkspaceNameBuf>,
+    /// New path of the workspace
+    #[arg(long, value_name = "PATH", requires = "name", value_hint = clap::ValueHint::DirPath)]
+    path: Option<String>,
+}
+
+#[instrument(skip_all)]
+pub fn cmd_workspace_repair(
+    ui: &mut Ui,
+    command: &CommandHelper,
+    args: &WorkspaceRepairArgs,
+) -> Result<(), CommandError> {
+    let workspace_command = command.workspace_helper_no_snapshot(ui)?;
+    let (name, path) = match (&args.name, &args.path) {
+        (Some(name), Some(path)) => {
+            if !workspace_command
+                .repo()
+                .view()
+                .wc_commit_ids()
+                .contains_key(name)
+            {
+                return Err(user_error(format!(
+                    "No such workspace: {}",
+                    name.as_symbol()
+                )));
+            }
+            let path = command.cwd().join(path);
+            let path = dunce::canonicalize(&path).map_err(|err| {
+                user_error_with_message(
+                    format!("Cannot resolve workspace path: {}", path.display()),
+                    err,
+                )
+            })?;
+            let workspace = command.load_workspace_at(&path, workspace_command.settings())?;
+            if workspace.repo_path() != workspace_command.repo_path()
+                || name != workspace.workspace_name()
+            {
+                return Err(user_error(format!(
+                    "{} is not the workspace {} of this repo",
+                    path.display(),
+                    name.as_symbol()
+                )));
+            }
+            (name.clone(), path)
+        }
+        _ => (
+            workspace_command.workspace_name().to_owned(),
+            workspace_command.workspace_root().to_path_buf(),
+        ),
+    };
+
+    let workspace_store = workspace_command.workspace_store();
+    let old_path = workspace_store.get_workspace_path(&name)?;
+    workspace_store.add(&name, &path, None)?;
+    let new_path = workspace_store.get_workspace_path(&name)?;
+    if old_path == new_path {
+        writeln!(ui.status(), "Nothing changed.")?;
+    } else {
+        writeln!(
+            ui.status(),
+            "Updated the path of workspace {} to {}",
+            name.as_symbol(),
+            path.display()
+        )?;
+    }
+    Ok(())
+}
@@ -12,11 +12,23 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::io::Write as _;
+
+use itertools::Itertools as _;
+use jj_lib::copies::CopyRecords;
+use jj_lib::matchers::EverythingMatcher;
+use jj_lib::merge::Diff;
+use jj_lib::op_store::OpStoreError;
+use jj_lib::op_walk;
+use jj_lib::repo::Repo as _;
+use pollster::FutureExt as _;
 use tracing::instrument;
 
 use crate::cli_util::CommandHelper;
 use crate::cli_util::print_snapshot_stats;
 use crate::command_error::CommandError;
+use crate::command_error::user_error;
+use crate::diff_util::DiffFormat;
 use crate::ui::Ui;
 
 /// Update a workspace that has become stale
@@ -26,16 +38,88 @@ use crate::ui::Ui;
 /// [stale working copy documentation]:
 ///     https://docs.jj-vcs.dev/latest/working-copy/#stale-working-copy
 #[derive(clap::Args, Clone, Debug)]
-pub struct WorkspaceUpdateStaleArgs {}
+pub struct WorkspaceUpdateStaleArgs {
+    /// Show what would be updated without changing anything
+    ///
+    /// Lists the operations the working copy missed and the files which would
+    /// be updated. Changes made in the working copy since it became stale
+    /// aren't included; they're snapshotted before the update.
+    #[arg(long)]
+    preview: bool,
+}
 
 #[instrument(skip_all)]
 pub fn cmd_workspace_update_stale(
     ui: &mut Ui,
     command: &CommandHelper,
-    _args: &WorkspaceUpdateStaleArgs,
+    args: &WorkspaceUpdateStaleArgs,
 ) -> Result<(), CommandError> {
+    if args.preview {
+        return preview_update_stale(ui, command);
+    }
     let (workspace_command, stats) = command.recover_stale_working_copy(ui)?;
     print_snapshot_stats(ui, &stats, workspace_command.env().path_converter())?;
 
     Ok(())
 }
+
+fn preview_update_stale(ui: &mut Ui, command: &CommandHelper) -> Result<(), CommandError> {
+    let workspace_command = command.workspace_helper_no_snapshot(ui)?;
+    let repo = workspace_command.repo();
+    let wc_op_id = workspace_command.working_copy().operation_id();
+    let wc_op = match repo.loader().load_operation(wc_op_id).block_on() {
+        Ok(op) => op,
+        Err(OpStoreError::ObjectNotFound { .. }) => {
+            writeln!(
+                ui.status(),
+                "The working copy's operation could not be read. Updating will create a recovery \
+                 commit with the current working-copy contents."
+            )?;
+            return Ok(());
+        }
+        Err(err) => return Err(err.into()),
+    };
+    let missed_ops: Vec<_> =
+        op_walk::walk_ancestors_range(std::slice::from_ref(repo.operation()), &[wc_op])
+            .try_collect()?;
+    let wc_commit_id = workspace_command
+        .get_wc_commit_id()
+        .ok_or_else(|| user_error("This command requires a working copy"))?;
+    let wc_commit = repo.store().get_commit(wc_commit_id)?;
+    let stale_tree = workspace_command.working_copy().tree()?;
+    let new_tree = wc_commit.tree();
+    if missed_ops.is_empty() {
+        writeln!(ui.status(), "The working copy is not stale")?;
+        return Ok(());
+    }
+
+    ui.request_pager();
+    let mut formatter = ui.stdout_formatter();
+    let template = workspace_command.operation_summary_template();
+    writeln!(formatter, "Operations not yet applied to the working copy:")?;
+    for op in &missed_ops {
+        write!(formatter, "  ")?;
+        template.format(op, formatter.as_mut())?;
+        writeln!(formatter)?;
+    }
+    // The working copy only needs to record the new operation.
+    if stale_tree.tree_ids_and_labels() == new_tree.tree_ids_and_labels() {
+        writeln!(formatter, "No files to update.")?;
+        return Ok(());
+    }
+    write!(formatter, "Files to update when checking out ")?;
+    workspace_command.write_commit_summary(formatter.as_mut(), &wc_commit)?;
+    writeln!(formatter, ":")?;
+    workspace_command
+        .diff_renderer(vec![DiffFormat::Summary])
+        .show_diff(
+            ui,
+            formatter.as_mut(),
+            Diff::new(stale_tree, &new_tree),
+            &EverythingMatcher,
+            &CopyRecords::default(),
+            ui.term_width(),
+        )
+        .block_on()?;
+    Ok(())
+}
@@ -17,19 +17,26 @@
 use std::cmp;
 use std::collections::HashMap;
 use std::collections::HashSet;
+use std::collections::hash_map;
 use std::rc::Rc;
 use std::sync::Arc;
 
 use clap::ValueEnum;
+use clap::builder::PossibleValuesParser;
+use clap::builder::TypedValueParser;
+use futures::future::try_join_all;
 use itertools::Itertools as _;
 use jj_lib::backend;
-use jj_lib::backend::BackendResult;
+use jj_lib::backend::BackendError;
 use jj_lib::backend::CommitId;
+use jj_lib::backend::Timestamp;
 use jj_lib::config::ConfigValue;
 use jj_lib::op_store::LocalRemoteRefTarget;
 use jj_lib::ref_name::RefName;
-use jj_lib::store::Store;
+use jj_lib::repo::Repo;
+use jj_lib::revset::RevsetEvaluationError;
 use jj_lib::str_util::StringMatcher;
+use pollster::FutureExt as _;
 
 use crate::commit_templater::CommitRef;
 
@@ -42,6 +49,20 @@ pub struct RefListItem {
     pub tracked: Vec<Rc<CommitRef>>,
 }
 
+impl RefListItem {
+    /// Total number of commits the `tracked` remote refs are ahead of and
+    /// behind the local ref. The counts are cached by the remote refs.
+    pub fn divergence(&self, repo: &dyn Repo) -> Result<usize, RevsetEvaluationError> {
+        let mut total = 0;
+        for commit_ref in &self.tracked {
+            let (ahead, _) = commit_ref.tracking_ahead_count(repo)?.unwrap_or_default();
+            let (behind, _) = commit_ref.tracking_behind_count(repo)?.unwrap_or_default();
+            total += ahead + behind;
+        }
+        Ok(total)
+    }
+}
+
 /// Conditions to select local/remote refs.
 pub struct RefFilterPredicates {
     /// Matches local names.
@@ -50,6 +71,8 @@ pub struct RefFilterPredicates {
     pub remote_matcher: StringMatcher,
     /// Matches any of the local targets.
     pub matched_local_targets: HashSet<CommitId>,
+    /// Matches any of the targets of the tracked remote refs.
+    pub matched_tracked_remote_targets: HashSet<CommitId>,
     /// Selects local refs having conflicted targets.
     pub conflicted: bool,
     /// Includes local-only refs.
@@ -74,6 +97,14 @@ pub fn collect_items<'a>(
                     .local_target
                     .added_ids()
                     .any(|id| predicates.matched_local_targets.contains(id))
+                || targets.remote_refs.iter().any(|(remote_name, remote_ref)| {
+                    remote_ref.is_tracked()
+                        && predicates.remote_matcher.is_match(remote_name.as_str())
+                        && remote_ref
+                            .target
+                            .added_ids()
+                            .any(|id| predicates.matched_tracked_remote_targets.contains(id))
+                })
         })
         .filter(|(_, targets)| !predicates.conflicted || targets.local_target.has_conflict());
     for (name, targets) in refs_to_list {
@@ -93,11 +124,7 @@ pub fn collect_items<'a>(
         if predicates.include_local_only && local_target.is_present()
             || !tracked_remote_refs.is_empty()
         {
-            let primary = CommitRef::local(
-                name,
-                local_target.clone(),
-                remote_refs.iter().map(|&(_, remote_ref)| remote_ref),
-            );
+            let primary = CommitRef::local(name, local_target.clone(), remote_refs.iter().copied());
             let tracked = tracked_remote_refs
                 .iter()
                 .map(|&(remote, remote_ref)| {
@@ -144,12 +171,33 @@ pub enum SortKey {
     CommitterDate,
     #[value(name = "committer-date-")]
     CommitterDateDesc,
+    // Creation date of annotated tags, or the committer date of the target
+    // for other refs
+    TagDate,
+    #[value(name = "tag-date-")]
+    TagDateDesc,
+    Description,
+    #[value(name = "description-")]
+    DescriptionDesc,
+    TargetId,
+    #[value(name = "target-id-")]
+    TargetIdDesc,
+    // Number of commits the tracked remote refs are ahead of and behind the
+    // local ref
+    Divergence,
+    #[value(name = "divergence-")]
+    DivergenceDesc,
 }
 
 impl SortKey {
     fn is_commit_dependant(&self) -> bool {
         match self {
-            Self::Name | Self::NameDesc => false,
+            Self::Name
+            | Self::NameDesc
+            | Self::TargetId
+            | Self::TargetIdDesc
+            | Self::Divergence
+            | Self::DivergenceDesc => false,
             Self::AuthorName
             | Self::AuthorNameDesc
             | Self::AuthorEmail
@@ -161,9 +209,45 @@ impl SortKey {
             | Self::CommitterEmail
             | Self::CommitterEmailDesc
             | Self::CommitterDate
-            | Self::CommitterDateDesc => true,
+            | Self::CommitterDateDesc
+            | Self::TagDate
+            | Self::TagDateDesc
+            | Self::Description
+            | Self::DescriptionDesc => true,
         }
     }
+
+    /// Returns true if the key needs the creation dates of annotated tags.
+    pub fn is_tag_dependant(&self) -> bool {
+        matches!(self, Self::TagDate | Self::TagDateDesc)
+    }
+
+    fn is_divergence_dependant(&self) -> bool {
+        matches!(self, Self::Divergence | Self::DivergenceDesc)
+    }
+}
+
+/// Parses the `--sort` argument of `jj bookmark list`, which doesn't accept
+/// the keys that only apply to tags.
+pub fn bookmark_sort_key_parser() -> impl TypedValueParser<Value = SortKey> {
+    let values = SortKey::value_variants()
+        .iter()
+        .filter(|key| !key.is_tag_dependant())
+        .filter_map(|key| key.to_possible_value());
+    PossibleValuesParser::new(values).map(|name| SortKey::from_str(&name, false).unwrap())
+}
+
+/// Like [`parse_sort_keys()`], but rejects the keys that only apply to tags.
+pub fn parse_bookmark_sort_keys(value: ConfigValue) -> Result<Vec<SortKey>, String> {
+    let keys = parse_sort_keys(value)?;
+    if let Some(key) = keys.iter().find(|key| key.is_tag_dependant()) {
+        let name = key.to_possible_value().unwrap();
+        return Err(format!(
+            "Sort key {} is only supported by tag list",
+            name.get_name()
+        ));
+    }
+    Ok(keys)
 }
 
 pub fn parse_sort_keys(value: ConfigValue) -> Result<Vec<SortKey>, String> {
@@ -186,35 +270,150 @@ pub fn parse_sort_keys(value: ConfigValue) -> Result<Vec<SortKey>, String> {
 /// The first key is most significant. The input items should have been sorted
 /// by [`SortKey::Name`].
 pub fn sort(
-    store: &Arc<Store>,
+    repo: &dyn Repo,
     items: &mut [RefListItem],
     sort_keys: &[SortKey],
-) -> BackendResult<()> {
+) -> Result<(), RevsetEvaluationError> {
+    sort_with_tag_timestamps(repo, items, sort_keys, &HashMap::new())
+}
+
+/// Sorts `items` like [`sort()`], using `tag_timestamps` as the creation
+/// dates of the local annotated tags for [`SortKey::TagDate`].
+pub fn sort_with_tag_timestamps(
+    repo: &dyn Repo,
+    items: &mut [RefListItem],
+    sort_keys: &[SortKey],
+    tag_timestamps: &HashMap<String, Timestamp>,
+) -> Result<(), RevsetEvaluationError> {
+    let store = repo.store();
     let mut commits: HashMap<CommitId, Arc<backend::Commit>> = HashMap::new();
     if sort_keys.iter().any(|key| key.is_commit_dependant()) {
-        commits = items
+        let commit_ids: HashSet<&CommitId> = items
             .iter()
             .filter_map(|item| item.primary.target().added_ids().next())
-            .map(|commit_id| {
-                store
-                    .get_commit(commit_id)
-                    .map(|commit| (commit_id.clone(), commit.store_commit().clone()))
-            })
-            .try_collect()?;
+            .collect();
+        // Load the target commits concurrently, which is faster than one by
+        // one if the backend has to fetch them.
+        commits = try_join_all(commit_ids.into_iter().map(async |commit_id| {
+            let commit = store.get_commit_async(commit_id).await?;
+            Ok::<_, BackendError>((commit_id.clone(), commit.store_commit().clone()))
+        }))
+        .block_on()?
+        .into_iter()
+        .collect();
+    }
+    let mut divergences = HashMap::new();
+    if sort_keys.iter().any(|key| key.is_divergence_dependant()) {
+        for item in items.iter() {
+            divergences.insert(item_key(item), item.divergence(repo)?);
+        }
     }
-    sort_inner(items, sort_keys, &commits);
+    sort_inner(items, sort_keys, &commits, tag_timestamps, &divergences);
     Ok(())
 }
 
+/// Skips the first `offset` items of the sorted `items`, and keeps at most
+/// `limit` items of the rest.
+pub fn paginate<T>(items: &mut Vec<T>, offset: usize, limit: Option<usize>) {
+    items.drain(..offset.min(items.len()));
+    if let Some(limit) = limit {
+        items.truncate(limit);
+    }
+}
+
+/// Item of ref list, or namespace in which items are collapsed.
+pub enum RefListEntry {
+    Item(RefListItem),
+    /// Items whose names start with the namespace `name`, which ends with `/`.
+    Namespace {
+        name: String,
+        count: usize,
+    },
+}
+
+impl RefListEntry {
+    pub fn item(&self) -> Option<&RefListItem> {
+        match self {
+            Self::Item(item) => Some(item),
+            Self::Namespace { .. } => None,
+        }
+    }
+}
+
+/// Returns the namespaces containing the ref `name`, e.g. `a/` and `a/b/` for
+/// `a/b/c`.
+pub fn name_namespaces(name: &str) -> impl Iterator<Item = &str> {
+    name.match_indices('/').map(|(i, _)| &name[..=i])
+}
+
+/// Collapses the items in each namespace directly below the `prefix` into one
+/// entry. The namespace entry is placed at the position of its first item.
+pub fn collapse_namespaces(items: Vec<RefListItem>, prefix: &str) -> Vec<RefListEntry> {
+    let mut entries = vec![];
+    let mut namespace_indices = HashMap::new();
+    for item in items {
+        let namespace = item
+            .primary
+            .name()
+            .strip_prefix(prefix)
+            .and_then(|rest| rest.split_once('/'))
+            .map(|(component, _)| format!("{prefix}{component}/"));
+        let Some(namespace) = namespace else {
+            entries.push(RefListEntry::Item(item));
+            continue;
+        };
+        match namespace_indices.entry(namespace) {
+            hash_map::Entry::Occupied(entry) => {
+                let RefListEntry::Namespace { count, .. } = &mut entries[*entry.get()] else {
+                    panic!("namespace index should point to namespace entry");
+                };
+                *count += 1;
+            }
+            hash_map::Entry::Vacant(entry) => {
+                entries.push(RefListEntry::Namespace {
+                    name: entry.key().clone(),
+                    count: 1,
+                });
+                entry.insert(entries.len() - 1);
+            }
+        }
+    }
+    entries
+}
+
+/// Returns the local and remote names identifying the `item`.
+fn item_key(item: &RefListItem) -> (String, Option<String>) {
+    (
+        item.primary.name().to_owned(),
+        item.primary.remote_name().map(|name| name.to_owned()),
+    )
+}
+
 fn sort_inner(
     items: &mut [RefListItem],
     sort_keys: &[SortKey],
     commits: &HashMap<CommitId, Arc<backend::Commit>>,
+    tag_timestamps: &HashMap<String, Timestamp>,
+    divergences: &HashMap<(String, Option<String>), usize>,
 ) {
     let to_commit = |item: &RefListItem| {
         let id = item.primary.target().added_ids().next()?;
         commits.get(id)
     };
+    let to_description_line = |item: &RefListItem| {
+        to_commit(item).map(|commit| commit.description.lines().next().unwrap_or(""))
+    };
+    let to_target_id = |item: &RefListItem| item.primary.target().added_ids().next().cloned();
+    let to_tag_timestamp = |item: &RefListItem| {
+        item.primary
+            .remote_name()
+            .is_none()
+            .then(|| tag_timestamps.get(item.primary.name()))
+            .flatten()
+            .or_else(|| to_commit(item).map(|commit| &commit.committer.timestamp))
+            .copied()
+    };
+    let to_divergence = |item: &RefListItem| divergences.get(&item_key(item)).copied();
 
     // Multi-pass sorting, the first key is most significant. Skip first
     // iteration if sort key is `Name`, since items are already sorted by name.
@@ -225,20 +424,10 @@ fn sort_inner(
     {
         match sort_key {
             SortKey::Name => {
-                items.sort_by_key(|item| {
-                    (
-                        item.primary.name().to_owned(),
-                        item.primary.remote_name().map(|name| name.to_owned()),
-                    )
-                });
+                items.sort_by_key(item_key);
             }
             SortKey::NameDesc => {
-                items.sort_by_key(|item| {
-                    cmp::Reverse((
-                        item.primary.name().to_owned(),
-                        item.primary.remote_name().map(|name| name.to_owned()),
-                    ))
-                });
+                items.sort_by_key(|item| cmp::Reverse(item_key(item)));
             }
             SortKey::AuthorName => {
                 items.sort_by_key(|item| to_commit(item).map(|commit| commit.author.name.as_str()));
@@ -293,6 +482,30 @@ fn sort_inner(
                     cmp::Reverse(to_commit(item).map(|commit| commit.committer.timestamp))
                 });
             }
+            SortKey::TagDate => {
+                items.sort_by_key(to_tag_timestamp);
+            }
+            SortKey::TagDateDesc => {
+                items.sort_by_key(|item| cmp::Reverse(to_tag_timestamp(item)));
+            }
+            SortKey::Description => {
+                items.sort_by_key(to_description_line);
+            }
+            SortKey::DescriptionDesc => {
+                items.sort_by_key(|item| cmp::Reverse(to_description_line(item)));
+            }
+            SortKey::TargetId => {
+                items.sort_by_key(to_target_id);
+            }
+            SortKey::TargetIdDesc => {
+                items.sort_by_key(|item| cmp::Reverse(to_target_id(item)));
+            }
+            SortKey::Divergence => {
+                items.sort_by_cached_key(to_divergence);
+            }
+            SortKey::DivergenceDesc => {
+                items.sort_by_cached_key(|item| cmp::Reverse(to_divergence(item)));
+            }
         }
     }
 }
@@ -302,7 +515,6 @@ mod tests {
     use jj_lib::backend::ChangeId;
     use jj_lib::backend::MillisSinceEpoch;
     use jj_lib::backend::Signature;
-    use jj_lib::backend::Timestamp;
     use jj_lib::backend::TreeId;
     use jj_lib::merge::Merge;
     use jj_lib::op_store::RefTarget;
@@ -455,7 +667,7 @@ mod tests {
         sort_keys: &[SortKey],
         commits: &HashMap<CommitId, Arc<backend::Commit>>,
     ) -> String {
-        sort_inner(items, sort_keys, commits);
+        sort_inner(items, sort_keys, commits, &HashMap::new(), &HashMap::new());
 
         let to_commit = |item: &RefListItem| {
             let id = item.primary.target().added_ids().next()?;
@@ -525,6 +737,41 @@ mod tests {
         result.join("\n")
     }
 
+    #[test]
+    fn test_name_namespaces() {
+        assert_eq!(name_namespaces("main").collect_vec(), Vec::<&str>::new());
+        assert_eq!(name_namespaces("a/b/c").collect_vec(), ["a/", "a/b/"]);
+    }
+
+    #[test]
+    fn test_collapse_namespaces() {
+        let make_items = |names: &[&str]| {
+            names
+                .iter()
+                .map(|&name| RefListItem {
+                    primary: CommitRef::local_only(name, RefTarget::absent()),
+                    tracked: vec![],
+                })
+                .collect_vec()
+        };
+        let to_strings = |entries: &[RefListEntry]| {
+            entries
+                .iter()
+                .map(|entry| match entry {
+                    RefListEntry::Item(item) => item.primary.name().to_owned(),
+                    RefListEntry::Namespace { name, count } => format!("{name} ({count})"),
+                })
+                .collect_vec()
+        };
+        let items = make_items(&["a/x", "b", "a/b/y", "c/z", "a/z", "ab"]);
+        let entries = collapse_namespaces(items, "");
+        assert_eq!(to_strings(&entries), ["a/ (3)", "b", "c/ (1)", "ab"]);
+
+        let items = make_items(&["a/x", "a/b/y", "a/b/z", "a/c/d/e"]);
+        let entries = collapse_namespaces(items, "a/");
+        assert_eq!(to_strings(&entries), ["a/x", "a/b/ (2)", "a/c/ (1)"]);
+    }
+
     #[test]
     fn test_sort_by_name() {
         insta::assert_snapshot!(
@@ -733,6 +980,159 @@ mod tests {
         ");
     }
 
+    #[test]
+    fn test_sort_by_tag_date() {
+        // Annotated tags are sorted by their creation date, other refs by the
+        // committer date of the target.
+        let mut new_commit_id = commit_id_generator();
+        let mut commits = HashMap::new();
+        let mut items = vec![];
+        for (name, committer_millis) in [("v1", 3), ("v2", 1), ("v3", 2)] {
+            let commit_id = new_commit_id();
+            let mut committer = make_default_signature();
+            committer.timestamp.timestamp = MillisSinceEpoch(committer_millis);
+            commits.insert(
+                commit_id.clone(),
+                make_backend_commit(make_default_signature(), committer),
+            );
+            items.push(RefListItem {
+                primary: CommitRef::local_only(name, RefTarget::normal(commit_id)),
+                tracked: vec![],
+            });
+        }
+        let tag_timestamps = HashMap::from([(
+            "v1".to_owned(),
+            Timestamp {
+                timestamp: MillisSinceEpoch(0),
+                tz_offset: 0,
+            },
+        )]);
+        let sorted_names = |items: &[RefListItem]| {
+            items
+                .iter()
+                .map(|item| item.primary.name().to_owned())
+                .collect_vec()
+        };
+
+        sort_inner(
+            &mut items,
+            &[SortKey::TagDate],
+            &commits,
+            &tag_timestamps,
+            &HashMap::new(),
+        );
+        assert_eq!(sorted_names(&items), ["v1", "v2", "v3"]);
+        sort_inner(
+            &mut items,
+            &[SortKey::TagDateDesc],
+            &commits,
+            &tag_timestamps,
+            &HashMap::new(),
+        );
+        assert_eq!(sorted_names(&items), ["v3", "v2", "v1"]);
+    }
+
+    #[test]
+    fn test_sort_by_description_and_target_id() {
+        let mut new_commit_id = commit_id_generator();
+        let mut commits = HashMap::new();
+        let mut items = vec![];
+        for (name, description) in [("a", "fix\nzzz\n"), ("b", "add\n"), ("c", "")] {
+            let commit_id = new_commit_id();
+            let mut commit =
+                make_backend_commit(make_default_signature(), make_default_signature());
+            Arc::make_mut(&mut commit).description = description.to_owned();
+            commits.insert(commit_id.clone(), commit);
+            items.push(RefListItem {
+                primary: CommitRef::local_only(name, RefTarget::normal(commit_id)),
+                tracked: vec![],
+            });
+        }
+        items.push(RefListItem {
+            primary: CommitRef::local_only("d", RefTarget::absent()),
+            tracked: vec![],
+        });
+        let sorted_names = |items: &[RefListItem]| {
+            items
+                .iter()
+                .map(|item| item.primary.name().to_owned())
+                .collect_vec()
+        };
+
+        sort_inner(
+            &mut items,
+            &[SortKey::Description],
+            &commits,
+            &HashMap::new(),
+            &HashMap::new(),
+        );
+        assert_eq!(sorted_names(&items), ["d", "c", "b", "a"]);
+        sort_inner(
+            &mut items,
+            &[SortKey::DescriptionDesc],
+            &commits,
+            &HashMap::new(),
+            &HashMap::new(),
+        );
+        assert_eq!(sorted_names(&items), ["a", "b", "c", "d"]);
+        sort_inner(
+            &mut items,
+            &[SortKey::TargetId],
+            &commits,
+            &HashMap::new(),
+            &HashMap::new(),
+        );
+        assert_eq!(sorted_names(&items), ["d", "a", "b", "c"]);
+        sort_inner(
+            &mut items,
+            &[SortKey::TargetIdDesc],
+            &commits,
+            &HashMap::new(),
+            &HashMap::new(),
+        );
+        assert_eq!(sorted_names(&items), ["c", "b", "a", "d"]);
+    }
+
+    #[test]
+    fn test_sort_by_divergence() {
+        let mut items = ["a", "b", "c"]
+            .map(|name| RefListItem {
+                primary: CommitRef::local_only(name, RefTarget::absent()),
+                tracked: vec![],
+            })
+            .to_vec();
+        let divergences = HashMap::from([
+            (("a".to_owned(), None), 2),
+            (("b".to_owned(), None), 5),
+            (("c".to_owned(), None), 0),
+        ]);
+        let sorted_names = |items: &[RefListItem]| {
+            items
+                .iter()
+                .map(|item| item.primary.name().to_owned())
+                .collect_vec()
+        };
+
+        let commits = HashMap::new();
+        let tag_timestamps = HashMap::new();
+        sort_inner(
+            &mut items,
+            &[SortKey::Divergence],
+            &commits,
+            &tag_timestamps,
+            &divergences,
+        );
+        assert_eq!(sorted_names(&items), ["c", "a", "b"]);
+        sort_inner(
+            &mut items,
+            &[SortKey::DivergenceDesc],
+            &commits,
+            &tag_timestamps,
+            &divergences,
+        );
+        assert_eq!(sorted_names(&items), ["b", "a", "c"]);
+    }
+
     // Bookmarks are already sorted by name
     // Test when sorting by name is not the only/last criteria
     #[test]
@@ -15,12 +15,15 @@
 //! Template environment for `jj log`, `jj evolog` and similar.
 
 use std::any::Any;
+use std::cell::RefCell;
 use std::cmp::Ordering;
 use std::cmp::max;
+use std::collections::BTreeSet;
 use std::collections::HashMap;
 use std::fmt;
 use std::fmt::Display;
 use std::io;
+use std::path::Path;
 use std::rc::Rc;
 use std::sync::Arc;
 
@@ -41,6 +44,7 @@ use jj_lib::conflicts::ConflictMarkerStyle;
 use jj_lib::copies::CopiesTreeDiffEntry;
 use jj_lib::copies::CopiesTreeDiffEntryPath;
 use jj_lib::copies::CopyRecords;
+use jj_lib::draft_store::DraftStore;
 use jj_lib::evolution::CommitEvolutionEntry;
 use jj_lib::extensions_map::ExtensionsMap;
 use jj_lib::fileset;
@@ -60,6 +64,7 @@ use jj_lib::op_store::OperationId;
 use jj_lib::op_store::RefTarget;
 use jj_lib::op_store::RemoteRef;
 use jj_lib::ref_name::RefName;
+use jj_lib::ref_name::RemoteName;
 use jj_lib::ref_name::WorkspaceName;
 use jj_lib::ref_name::WorkspaceNameBuf;
 use jj_lib::repo::Repo;
@@ -70,6 +75,7 @@ use jj_lib::revset;
 use jj_lib::revset::Revset;
 use jj_lib::revset::RevsetContainingFn;
 use jj_lib::revset::RevsetDiagnostics;
+use jj_lib::revset::RevsetEvaluationError;
 use jj_lib::revset::RevsetParseContext;
 use jj_lib::revset::UserRevsetExpression;
 use jj_lib::rewrite::rebase_to_dest_parent;
@@ -81,10 +87,12 @@ use jj_lib::signing::Verification;
 use jj_lib::store::Store;
 use jj_lib::trailer;
 use jj_lib::trailer::Trailer;
+use jj_lib::workspace_store::WorkspaceInfo;
 use once_cell::unsync::OnceCell;
 use pollster::FutureExt as _;
 use serde::Serialize as _;
 
+use crate::decorations::Decorations;
 use crate::diff_util;
 use crate::diff_util::DiffStatEntry;
 use crate::diff_util::DiffStats;
@@ -133,6 +141,7 @@ pub trait CommitTemplateLanguageExtension {
 /// Template environment for `jj log` and `jj evolog`.
 pub struct CommitTemplateLanguage<'repo> {
     repo: &'repo dyn Repo,
+    repo_path: &'repo Path,
     path_converter: &'repo RepoPathUiConverter,
     workspace_name: WorkspaceNameBuf,
     // RevsetParseContext doesn't borrow a repo, but we'll need 'repo lifetime
@@ -146,6 +155,7 @@ pub struct CommitTemplateLanguage<'repo> {
     id_prefix_context: &'repo IdPrefixContext,
     immutable_expression: Arc<UserRevsetExpression>,
     conflict_marker_style: ConflictMarkerStyle,
+    decorations: &'repo OnceCell<Rc<Decorations>>,
     build_fn_table: CommitTemplateBuildFnTable<'repo>,
     keyword_cache: CommitKeywordCache<'repo>,
     cache_extensions: ExtensionsMap,
@@ -157,12 +167,14 @@ impl<'repo> CommitTemplateLanguage<'repo> {
     #[expect(clippy::too_many_arguments)]
     pub fn new(
         repo: &'repo dyn Repo,
+        repo_path: &'repo Path,
         path_converter: &'repo RepoPathUiConverter,
         workspace_name: &WorkspaceName,
         revset_parse_context: RevsetParseContext<'repo>,
         id_prefix_context: &'repo IdPrefixContext,
         immutable_expression: Arc<UserRevsetExpression>,
         conflict_marker_style: ConflictMarkerStyle,
+        decorations: &'repo OnceCell<Rc<Decorations>>,
         extensions: &[impl AsRef<dyn CommitTemplateLanguageExtension>],
     ) -> Self {
         let mut build_fn_table = CommitTemplateBuildFnTable::builtin();
@@ -177,12 +189,14 @@ impl<'repo> CommitTemplateLanguage<'repo> {
 
         CommitTemplateLanguage {
             repo,
+            repo_path,
             path_converter,
             workspace_name: workspace_name.to_owned(),
             revset_parse_context,
             id_prefix_context,
             immutable_expression,
             conflict_marker_style,
+            decorations,
             build_fn_table,
             keyword_cache: CommitKeywordCache::default(),
             cache_extensions,
@@ -195,6 +209,18 @@ impl<'repo> CommitTemplateLanguage<'repo> {
             path_converter: self.path_converter,
         }
     }
+
+    /// Loads decorations from the configured sources. The result is shared
+    /// with the other templates of the same command invocation.
+    fn decorations(&self, span: pest::Span<'_>) -> TemplateParseResult<&Rc<Decorations>> {
+        self.decorations.get_or_try_init(|| {
+            let RepoPathUiConverter::Fs { base, .. } = self.path_converter;
+            let decorations = Decorations::load(self.settings(), base).map_err(|err| {
+                TemplateParseError::expression("Failed to load decorations", span).with_source(err)
+            })?;
+            Ok(Rc::new(decorations))
+        })
+    }
 }
 
 impl<'repo> TemplateLanguage<'repo> for CommitTemplateLanguage<'repo> {
@@ -270,6 +296,16 @@ impl<'repo> TemplateLanguage<'repo> for CommitTemplateLanguage<'repo> {
                 let build = template_parser::lookup_method(type_name, table, function)?;
                 build(self, diagnostics, build_ctx, property, function)
             }
+            CommitTemplatePropertyKind::RefConflictSide(property) => {
+                let table = &self.build_fn_table.ref_conflict_side_methods;
+                let build = template_parser::lookup_method(type_name, table, function)?;
+                build(self, diagnostics, build_ctx, property, function)
+            }
+            CommitTemplatePropertyKind::RefConflictSideList(property) => {
+                let table = &self.build_fn_table.ref_conflict_side_list_methods;
+                let build = template_parser::lookup_method(type_name, table, function)?;
+                build(self, diagnostics, build_ctx, property, function)
+            }
             CommitTemplatePropertyKind::WorkspaceRef(property) => {
                 let table = &self.build_fn_table.workspace_ref_methods;
                 let build = template_parser::lookup_method(type_name, table, function)?;
@@ -386,6 +422,24 @@ impl<'repo> TemplateLanguage<'repo> for CommitTemplateLanguage<'repo> {
                 let build = template_parser::lookup_method(type_name, table, function)?;
                 build(self, diagnostics, build_ctx, property, function)
             }
+            CommitTemplatePropertyKind::GerritUploadEntry(property) => {
+                let type_name = "GerritUploadEntry";
+                let table = &self.build_fn_table.gerrit_upload_entry_methods;
+                let build = template_parser::lookup_method(type_name, table, function)?;
+                build(self, diagnostics, build_ctx, property, function)
+            }
+            CommitTemplatePropertyKind::GerritStatusEntry(property) => {
+                let type_name = "GerritStatusEntry";
+                let table = &self.build_fn_table.gerrit_status_entry_methods;
+                let build = template_parser::lookup_method(type_name, table, function)?;
+                build(self, diagnostics, build_ctx, property, function)
+            }
+            CommitTemplatePropertyKind::GitPushResultEntry(property) => {
+                let type_name = "GitPushResultEntry";
+                let table = &self.build_fn_table.git_push_result_entry_methods;
+                let build = template_parser::lookup_method(type_name, table, function)?;
+                build(self, diagnostics, build_ctx, property, function)
+            }
             CommitTemplatePropertyKind::Trailer(property) => {
                 let table = &self.build_fn_table.trailer_methods;
                 let build = template_parser::lookup_method(type_name, table, function)?;
@@ -441,6 +495,8 @@ pub enum CommitTemplatePropertyKind<'repo> {
     CommitRef(BoxedTemplateProperty<'repo, Rc<CommitRef>>),
     CommitRefOpt(BoxedTemplateProperty<'repo, Option<Rc<CommitRef>>>),
     CommitRefList(BoxedTemplateProperty<'repo, Vec<Rc<CommitRef>>>),
+    RefConflictSide(BoxedTemplateProperty<'repo, RefConflictSide>),
+    RefConflictSideList(BoxedTemplateProperty<'repo, Vec<RefConflictSide>>),
     WorkspaceRef(BoxedTemplateProperty<'repo, WorkspaceRef>),
     WorkspaceRefOpt(BoxedTemplateProperty<'repo, Option<WorkspaceRef>>),
     WorkspaceRefList(BoxedTemplateProperty<'repo, Vec<WorkspaceRef>>),
@@ -461,6 +517,9 @@ pub enum CommitTemplatePropertyKind<'repo> {
     DiffStatEntryList(BoxedTemplateProperty<'repo, Vec<DiffStatEntry>>),
     CryptographicSignatureOpt(BoxedTemplateProperty<'repo, Option<CryptographicSignature>>),
     AnnotationLine(BoxedTemplateProperty<'repo, AnnotationLine>),
+    GerritUploadEntry(BoxedTemplateProperty<'repo, GerritUploadEntry>),
+    GerritStatusEntry(BoxedTemplateProperty<'repo, GerritStatusEntry>),
+    GitPushResultEntry(BoxedTemplateProperty<'repo, GitPushResultEntry>),
     Trailer(BoxedTemplateProperty<'repo, Trailer>),
     TrailerList(BoxedTemplateProperty<'repo, Vec<Trailer>>),
 }
@@ -475,6 +534,8 @@ template_builder::impl_property_wrappers!(<'repo> CommitTemplatePropertyKind<'re
     CommitRef(Rc<CommitRef>),
     CommitRefOpt(Option<Rc<CommitRef>>),
     CommitRefList(Vec<Rc<CommitRef>>),
+    RefConflictSide(RefConflictSide),
+    RefConflictSideList(Vec<RefConflictSide>),
     WorkspaceRef(WorkspaceRef),
     WorkspaceRefOpt(Option<WorkspaceRef>),
     WorkspaceRefList(Vec<WorkspaceRef>),
@@ -495,6 +556,9 @@ template_builder::impl_property_wrappers!(<'repo> CommitTemplatePropertyKind<'re
     DiffStatEntryList(Vec<DiffStatEntry>),
     CryptographicSignatureOpt(Option<CryptographicSignature>),
     AnnotationLine(AnnotationLine),
+    GerritUploadEntry(GerritUploadEntry),
+    GerritStatusEntry(GerritStatusEntry),
+    GitPushResultEntry(GitPushResultEntry),
     Trailer(Trailer),
     TrailerList(Vec<Trailer>),
 });
@@ -523,6 +587,8 @@ impl<'repo> CoreTemplatePropertyVar<'repo> for CommitTemplatePropertyKind<'repo>
             Self::CommitRef(_) => "CommitRef",
             Self::CommitRefOpt(_) => "Option<CommitRef>",
             Self::CommitRefList(_) => "List<CommitRef>",
+            Self::RefConflictSide(_) => "RefConflictSide",
+            Self::RefConflictSideList(_) => "List<RefConflictSide>",
             Self::WorkspaceRef(_) => "WorkspaceRef",
             Self::WorkspaceRefOpt(_) => "Option<WorkspaceRef>",
             Self::WorkspaceRefList(_) => "List<WorkspaceRef>",
@@ -543,6 +609,9 @@ impl<'repo> CoreTemplatePropertyVar<'repo> for CommitTemplatePropertyKind<'repo>
             Self::DiffStatEntryList(_) => "List<DiffStatEntry>",
             Self::CryptographicSignatureOpt(_) => "Option<CryptographicSignature>",
             Self::AnnotationLine(_) => "AnnotationLine",
+            Self::GerritUploadEntry(_) => "GerritUploadEntry",
+            Self::GerritStatusEntry(_) => "GerritStatusEntry",
+            Self::GitPushResultEntry(_) => "GitPushResultEntry",
             Self::Trailer(_) => "Trailer",
             Self::TrailerList(_) => "List<Trailer>",
         }
@@ -559,6 +628,8 @@ impl<'repo> CoreTemplatePropertyVar<'repo> for CommitTemplatePropertyKind<'repo>
             Self::CommitRef(_) => None,
             Self::CommitRefOpt(property) => Some(property.map(|opt| opt.is_some()).into_dyn()),
             Self::CommitRefList(property) => Some(property.map(|l| !l.is_empty()).into_dyn()),
+            Self::RefConflictSide(_) => None,
+            Self::RefConflictSideList(property) => Some(property.map(|l| !l.is_empty()).into_dyn()),
             Self::WorkspaceRef(_) => None,
             Self::WorkspaceRefOpt(property) => Some(property.map(|opt| opt.is_some()).into_dyn()),
             Self::WorkspaceRefList(property) => Some(property.map(|l| !l.is_empty()).into_dyn()),
@@ -583,6 +654,9 @@ impl<'repo> CoreTemplatePropertyVar<'repo> for CommitTemplatePropertyKind<'repo>
                 Some(property.map(|sig| sig.is_some()).into_dyn())
             }
             Self::AnnotationLine(_) => None,
+            Self::GerritUploadEntry(_) => None,
+            Self::GerritStatusEntry(_) => None,
+            Self::GitPushResultEntry(_) => None,
             Self::Trailer(_) => None,
             Self::TrailerList(property) => Some(property.map(|l| !l.is_empty()).into_dyn()),
         }
@@ -632,6 +706,8 @@ impl<'repo> CoreTemplatePropertyVar<'repo> for CommitTemplatePropertyKind<'repo>
             Self::CommitRef(property) => Some(property.into_serialize()),
             Self::CommitRefOpt(property) => Some(property.into_serialize()),
             Self::CommitRefList(property) => Some(property.into_serialize()),
+            Self::RefConflictSide(_) => None,
+            Self::RefConflictSideList(_) => None,
             Self::WorkspaceRef(property) => Some(property.into_serialize()),
             Self::WorkspaceRefOpt(property) => Some(property.into_serialize()),
             Self::WorkspaceRefList(property) => Some(property.into_serialize()),
@@ -652,6 +728,9 @@ impl<'repo> CoreTemplatePropertyVar<'repo> for CommitTemplatePropertyKind<'repo>
             Self::DiffStatEntryList(_) => None,
             Self::CryptographicSignatureOpt(_) => None,
             Self::AnnotationLine(_) => None,
+            Self::GerritUploadEntry(_) => None,
+            Self::GerritStatusEntry(_) => None,
+            Self::GitPushResultEntry(property) => Some(property.into_serialize()),
             Self::Trailer(_) => None,
             Self::TrailerList(_) => None,
         }
@@ -668,6 +747,8 @@ impl<'repo> CoreTemplatePropertyVar<'repo> for CommitTemplatePropertyKind<'repo>
             Self::CommitRef(property) => Some(property.into_template()),
             Self::CommitRefOpt(property) => Some(property.into_template()),
             Self::CommitRefList(property) => Some(property.into_template()),
+            Self::RefConflictSide(_) => None,
+            Self::RefConflictSideList(_) => None,
             Self::WorkspaceRef(property) => Some(property.into_template()),
             Self::WorkspaceRefOpt(property) => Some(property.into_template()),
             Self::WorkspaceRefList(property) => Some(property.into_template()),
@@ -688,6 +769,9 @@ impl<'repo> CoreTemplatePropertyVar<'repo> for CommitTemplatePropertyKind<'repo>
             Self::DiffStatEntryList(_) => None,
             Self::CryptographicSignatureOpt(_) => None,
             Self::AnnotationLine(_) => None,
+            Self::GerritUploadEntry(_) => None,
+            Self::GerritStatusEntry(_) => None,
+            Self::GitPushResultEntry(_) => None,
             Self::Trailer(property) => Some(property.into_template()),
             Self::TrailerList(property) => Some(property.into_template()),
         }
@@ -737,6 +821,8 @@ impl<'repo> CoreTemplatePropertyVar<'repo> for CommitTemplatePropertyKind<'repo>
             (Self::CommitRef(_), _) => None,
             (Self::CommitRefOpt(_), _) => None,
             (Self::CommitRefList(_), _) => None,
+            (Self::RefConflictSide(_), _) => None,
+            (Self::RefConflictSideList(_), _) => None,
             (Self::WorkspaceRef(_), _) => None,
             (Self::WorkspaceRefOpt(_), _) => None,
             (Self::WorkspaceRefList(_), _) => None,
@@ -757,6 +843,9 @@ impl<'repo> CoreTemplatePropertyVar<'repo> for CommitTemplatePropertyKind<'repo>
             (Self::DiffStatEntryList(_), _) => None,
             (Self::CryptographicSignatureOpt(_), _) => None,
             (Self::AnnotationLine(_), _) => None,
+            (Self::GerritUploadEntry(_), _) => None,
+            (Self::GerritStatusEntry(_), _) => None,
+            (Self::GitPushResultEntry(_), _) => None,
             (Self::Trailer(_), _) => None,
             (Self::TrailerList(_), _) => None,
         }
@@ -779,6 +868,8 @@ impl<'repo> CoreTemplatePropertyVar<'repo> for CommitTemplatePropertyKind<'repo>
             (Self::CommitRef(_), _) => None,
             (Self::CommitRefOpt(_), _) => None,
             (Self::CommitRefList(_), _) => None,
+            (Self::RefConflictSide(_), _) => None,
+            (Self::RefConflictSideList(_), _) => None,
             (Self::WorkspaceRef(_), _) => None,
             (Self::WorkspaceRefOpt(_), _) => None,
             (Self::WorkspaceRefList(_), _) => None,
@@ -799,6 +890,9 @@ impl<'repo> CoreTemplatePropertyVar<'repo> for CommitTemplatePropertyKind<'repo>
             (Self::DiffStatEntryList(_), _) => None,
             (Self::CryptographicSignatureOpt(_), _) => None,
             (Self::AnnotationLine(_), _) => None,
+            (Self::GerritUploadEntry(_), _) => None,
+            (Self::GerritStatusEntry(_), _) => None,
+            (Self::GitPushResultEntry(_), _) => None,
             (Self::Trailer(_), _) => None,
             (Self::TrailerList(_), _) => None,
         }
@@ -820,6 +914,8 @@ pub struct CommitTemplateBuildFnTable<'repo> {
     pub commit_evolution_entry_methods: CommitTemplateBuildMethodFnMap<'repo, CommitEvolutionEntry>,
     pub commit_ref_methods: CommitTemplateBuildMethodFnMap<'repo, Rc<CommitRef>>,
     pub commit_ref_list_methods: CommitTemplateBuildMethodFnMap<'repo, Vec<Rc<CommitRef>>>,
+    pub ref_conflict_side_methods: CommitTemplateBuildMethodFnMap<'repo, RefConflictSide>,
+    pub ref_conflict_side_list_methods: CommitTemplateBuildMethodFnMap<'repo, Vec<RefConflictSide>>,
     pub workspace_ref_methods: CommitTemplateBuildMethodFnMap<'repo, WorkspaceRef>,
     pub workspace_ref_list_methods: CommitTemplateBuildMethodFnMap<'repo, Vec<WorkspaceRef>>,
     pub repo_path_methods: CommitTemplateBuildMethodFnMap<'repo, RepoPathBuf>,
@@ -837,6 +933,9 @@ pub struct CommitTemplateBuildFnTable<'repo> {
     pub cryptographic_signature_methods:
         CommitTemplateBuildMethodFnMap<'repo, CryptographicSignature>,
     pub annotation_line_methods: CommitTemplateBuildMethodFnMap<'repo, AnnotationLine>,
+    pub gerrit_upload_entry_methods: CommitTemplateBuildMethodFnMap<'repo, GerritUploadEntry>,
+    pub gerrit_status_entry_methods: CommitTemplateBuildMethodFnMap<'repo, GerritStatusEntry>,
+    pub git_push_result_entry_methods: CommitTemplateBuildMethodFnMap<'repo, GitPushResultEntry>,
     pub trailer_methods: CommitTemplateBuildMethodFnMap<'repo, Trailer>,
     pub trailer_list_methods: CommitTemplateBuildMethodFnMap<'repo, Vec<Trailer>>,
 }
@@ -851,6 +950,8 @@ impl CommitTemplateBuildFnTable<'_> {
             commit_evolution_entry_methods: HashMap::new(),
             commit_ref_methods: HashMap::new(),
             commit_ref_list_methods: HashMap::new(),
+            ref_conflict_side_methods: HashMap::new(),
+            ref_conflict_side_list_methods: HashMap::new(),
             workspace_ref_methods: HashMap::new(),
             workspace_ref_list_methods: HashMap::new(),
             repo_path_methods: HashMap::new(),
@@ -867,6 +968,9 @@ impl CommitTemplateBuildFnTable<'_> {
             diff_stat_entry_list_methods: HashMap::new(),
             cryptographic_signature_methods: HashMap::new(),
             annotation_line_methods: HashMap::new(),
+            gerrit_upload_entry_methods: HashMap::new(),
+            gerrit_status_entry_methods: HashMap::new(),
+            git_push_result_entry_methods: HashMap::new(),
             trailer_methods: HashMap::new(),
             trailer_list_methods: HashMap::new(),
         }
@@ -881,6 +985,8 @@ impl CommitTemplateBuildFnTable<'_> {
             commit_evolution_entry_methods,
             commit_ref_methods,
             commit_ref_list_methods,
+            ref_conflict_side_methods,
+            ref_conflict_side_list_methods,
             workspace_ref_methods,
             workspace_ref_list_methods,
             repo_path_methods,
@@ -897,6 +1003,9 @@ impl CommitTemplateBuildFnTable<'_> {
             diff_stat_entry_list_methods,
             cryptographic_signature_methods,
             annotation_line_methods,
+            gerrit_upload_entry_methods,
+            gerrit_status_entry_methods,
+            git_push_result_entry_methods,
             trailer_methods,
             trailer_list_methods,
         } = other;
@@ -911,6 +1020,14 @@ impl CommitTemplateBuildFnTable<'_> {
         );
         merge_fn_map(&mut self.commit_ref_methods, commit_ref_methods);
         merge_fn_map(&mut self.commit_ref_list_methods, commit_ref_list_methods);
+        merge_fn_map(
+            &mut self.ref_conflict_side_methods,
+            ref_conflict_side_methods,
+        );
+        merge_fn_map(
+            &mut self.ref_conflict_side_list_methods,
+            ref_conflict_side_list_methods,
+        );
         merge_fn_map(&mut self.workspace_ref_methods, workspace_ref_methods);
         merge_fn_map(
             &mut self.workspace_ref_list_methods,
@@ -942,6 +1059,18 @@ impl CommitTemplateBuildFnTable<'_> {
             cryptographic_signature_methods,
         );
         merge_fn_map(&mut self.annotation_line_methods, annotation_line_methods);
+        merge_fn_map(
+            &mut self.gerrit_upload_entry_methods,
+            gerrit_upload_entry_methods,
+        );
+        merge_fn_map(
+            &mut self.gerrit_status_entry_methods,
+            gerrit_status_entry_methods,
+        );
+        merge_fn_map(
+            &mut self.git_push_result_entry_methods,
+            git_push_result_entry_methods,
+        );
         merge_fn_map(&mut self.trailer_methods, trailer_methods);
         merge_fn_map(&mut self.trailer_list_methods, trailer_list_methods);
     }
@@ -958,6 +1087,8 @@ impl CommitTemplateBuildFnTable<'_> {
             commit_evolution_entry_methods: builtin_commit_evolution_entry_methods(),
             commit_ref_methods: builtin_commit_ref_methods(),
             commit_ref_list_methods: template_builder::builtin_formattable_list_methods(),
+            ref_conflict_side_methods: builtin_ref_conflict_side_methods(),
+            ref_conflict_side_list_methods: template_builder::builtin_unformattable_list_methods(),
             workspace_ref_methods: builtin_workspace_ref_methods(),
             workspace_ref_list_methods: template_builder::builtin_formattable_list_methods(),
             repo_path_methods: builtin_repo_path_methods(),
@@ -974,6 +1105,9 @@ impl CommitTemplateBuildFnTable<'_> {
             diff_stat_entry_list_methods: template_builder::builtin_unformattable_list_methods(),
             cryptographic_signature_methods: builtin_cryptographic_signature_methods(),
             annotation_line_methods: builtin_annotation_line_methods(),
+            gerrit_upload_entry_methods: builtin_gerrit_upload_entry_methods(),
+            gerrit_status_entry_methods: builtin_gerrit_status_entry_methods(),
+            git_push_result_entry_methods: builtin_git_push_result_entry_methods(),
             trailer_methods: builtin_trailer_methods(),
             trailer_list_methods: builtin_trailer_list_methods(),
         }
@@ -987,6 +1121,8 @@ pub struct CommitKeywordCache<'repo> {
     tags_index: OnceCell<Rc<CommitRefsIndex>>,
     git_refs_index: OnceCell<Rc<CommitRefsIndex>>,
     is_immutable_fn: OnceCell<Rc<RevsetContainingFn<'repo>>>,
+    contained_in_fns: RefCell<HashMap<String, Rc<RevsetContainingFn<'repo>>>>,
+    draft_change_ids: OnceCell<Rc<BTreeSet<ChangeId>>>,
 }
 
 impl<'repo> CommitKeywordCache<'repo> {
@@ -1019,6 +1155,45 @@ impl<'repo> CommitKeywordCache<'repo> {
             Ok(revset.containing_fn().into())
         })
     }
+
+    /// Returns a function which tests whether a commit is included in the
+    /// `revset`. The revset is evaluated once, and the result is shared by all
+    /// `contained_in()` calls with the same revset text.
+    pub fn contained_in_fn(
+        &self,
+        language: &CommitTemplateLanguage<'repo>,
+        diagnostics: &mut TemplateDiagnostics,
+        span: pest::Span<'_>,
+        revset: &str,
+    ) -> TemplateParseResult<Rc<RevsetContainingFn<'repo>>> {
+        if let Some(is_contained) = self.contained_in_fns.borrow().get(revset) {
+            return Ok(is_contained.clone());
+        }
+        let is_contained: Rc<RevsetContainingFn<'repo>> =
+            evaluate_user_revset(language, diagnostics, span, revset)?
+                .containing_fn()
+                .into();
+        self.contained_in_fns
+            .borrow_mut()
+            .insert(revset.to_owned(), is_contained.clone());
+        Ok(is_contained)
+    }
+
+    pub fn draft_change_ids(
+        &self,
+        language: &CommitTemplateLanguage<'repo>,
+        span: pest::Span<'_>,
+    ) -> TemplateParseResult<&Rc<BTreeSet<ChangeId>>> {
+        self.draft_change_ids.get_or_try_init(|| {
+            let drafts = DraftStore::load(language.repo_path)
+                .get_all()
+                .map_err(|err| {
+                    TemplateParseError::expression("Failed to load draft changes", span)
+                        .with_source(err)
+                })?;
+            Ok(Rc::new(drafts))
+        })
+    }
 }
 
 /// Builtin functions for the commit template language.
@@ -1296,6 +1471,27 @@ fn builtin_commit_methods<'repo>() -> CommitTemplateBuildMethodFnMap<'repo, Comm
             Ok(out_property.into_dyn_wrapped())
         },
     );
+    map.insert(
+        "draft",
+        |language, _diagnostics, _build_ctx, self_property, function| {
+            function.expect_no_arguments()?;
+            let drafts = language
+                .keyword_cache
+                .draft_change_ids(language, function.name_span)?
+                .clone();
+            let out_property = self_property.map(move |commit| drafts.contains(commit.change_id()));
+            Ok(out_property.into_dyn_wrapped())
+        },
+    );
+    map.insert(
+        "decorations",
+        |language, _diagnostics, _build_ctx, self_property, function| {
+            function.expect_no_arguments()?;
+            let decorations = language.decorations(function.name_span)?.clone();
+            let out_property = self_property.map(move |commit| decorations.get(&commit));
+            Ok(out_property.into_dyn_wrapped())
+        },
+    );
     map.insert(
         "contained_in",
         |language, diagnostics, _build_ctx, self_property, function| {
@@ -1304,8 +1500,9 @@ fn builtin_commit_methods<'repo>() -> CommitTemplateBuildMethodFnMap<'repo, Comm
             let is_contained =
                 template_parser::catch_aliases(diagnostics, revset_node, |diagnostics, node| {
                     let text = template_parser::expect_string_literal(node)?;
-                    let revset = evaluate_user_revset(language, diagnostics, node.span, text)?;
-                    Ok(revset.containing_fn())
+                    language
+                        .keyword_cache
+                        .contained_in_fn(language, diagnostics, node.span, text)
                 })?;
 
             let out_property = self_property.and_then(move |commit| Ok(is_contained(commit.id())?));
@@ -1551,6 +1748,35 @@ pub struct CommitRef {
     /// ref is synchronized with the local.
     #[serde(skip)] // internal state used mainly for Template impl
     synced: bool,
+    /// Remote ref targets of the same name if the local target has conflicts.
+    #[serde(skip)] // internal state used to explain conflicts
+    remote_targets: Vec<(RefSymbolBuf, RefTarget)>,
+}
+
+/// Side of a conflicted ref target.
+#[derive(Clone, Debug)]
+pub struct RefConflictSide {
+    target: CommitId,
+    removed: bool,
+    remotes: Vec<String>,
+}
+
+impl RefConflictSide {
+    /// Commit the side points to.
+    pub fn target(&self) -> &CommitId {
+        &self.target
+    }
+
+    /// Returns true if this is a removed side, i.e. the target the other sides
+    /// were moved from.
+    pub fn is_removed(&self) -> bool {
+        self.removed
+    }
+
+    /// Remotes whose ref of the same name points to this side.
+    pub fn remotes(&self) -> &[String] {
+        &self.remotes
+    }
 }
 
 #[derive(Debug)]
@@ -1572,17 +1798,30 @@ impl CommitRef {
     pub fn local<'a>(
         name: impl Into<String>,
         target: RefTarget,
-        remote_refs: impl IntoIterator<Item = &'a RemoteRef>,
+        remote_refs: impl IntoIterator<Item = (&'a RemoteName, &'a RemoteRef)>,
     ) -> Rc<Self> {
+        let remote_refs = remote_refs.into_iter().collect_vec();
         let synced = remote_refs
-            .into_iter()
-            .all(|remote_ref| !remote_ref.is_tracked() || remote_ref.target == target);
+            .iter()
+            .all(|(_, remote_ref)| !remote_ref.is_tracked() || remote_ref.target == target);
+        let remote_targets = if target.has_conflict() {
+            remote_refs
+                .iter()
+                .map(|(remote_name, remote_ref)| {
+                    let remote = RefSymbolBuf(remote_name.as_str().to_owned());
+                    (remote, remote_ref.target.clone())
+                })
+                .collect()
+        } else {
+            vec![]
+        };
         Rc::new(Self {
             name: RefSymbolBuf(name.into()),
             remote: None,
             target,
             tracking_ref: None,
             synced,
+            remote_targets,
         })
     }
 
@@ -1618,6 +1857,7 @@ impl CommitRef {
             target: remote_ref.target,
             tracking_ref,
             synced,
+            remote_targets: vec![],
         })
     }
 
@@ -1633,6 +1873,7 @@ impl CommitRef {
             target,
             tracking_ref: None,
             synced: false, // has no local counterpart
+            remote_targets: vec![],
         })
     }
 
@@ -1676,6 +1917,32 @@ impl CommitRef {
         self.target.has_conflict()
     }
 
+    /// Sides of the conflicted target, added sides first. Each side lists the
+    /// remotes whose ref of the same name points to the side. Returns an empty
+    /// list if the target isn't conflicted.
+    pub fn conflict_sides(&self) -> Vec<RefConflictSide> {
+        if !self.target.has_conflict() {
+            return vec![];
+        }
+        let added = self.target.added_ids().map(|id| (id, false));
+        let removed = self.target.removed_ids().map(|id| (id, true));
+        itertools::chain(added, removed)
+            .map(|(id, removed)| {
+                let remotes = self
+                    .remote_targets
+                    .iter()
+                    .filter(|(_, target)| target.as_normal() == Some(id))
+                    .map(|(remote, _)| remote.0.clone())
+                    .collect();
+                RefConflictSide {
+                    target: id.clone(),
+                    removed,
+                    remotes,
+                }
+            })
+            .collect()
+    }
+
     /// Returns true if this ref is tracked by a local ref. The local ref might
     /// have been deleted (but not pushed yet.)
     pub fn is_tracked(&self) -> bool {
@@ -1690,34 +1957,44 @@ impl CommitRef {
             .is_some_and(|tracking| tracking.target.is_present())
     }
 
-    /// Number of commits ahead of the tracking local ref.
-    fn tracking_ahead_count(&self, repo: &dyn Repo) -> Result<SizeHint, TemplatePropertyError> {
+    /// Number of commits ahead of the tracking local ref, or `None` if this
+    /// isn't a tracked remote ref. The count is computed once and cached.
+    pub fn tracking_ahead_count(
+        &self,
+        repo: &dyn Repo,
+    ) -> Result<Option<SizeHint>, RevsetEvaluationError> {
         let Some(tracking) = &self.tracking_ref else {
-            return Err(TemplatePropertyError("Not a tracked remote ref".into()));
+            return Ok(None);
         };
         tracking
             .ahead_count
             .get_or_try_init(|| {
                 let self_ids = self.target.added_ids().cloned().collect_vec();
                 let other_ids = tracking.target.added_ids().cloned().collect_vec();
-                Ok(revset::walk_revs(repo, &self_ids, &other_ids)?.count_estimate()?)
+                revset::walk_revs(repo, &self_ids, &other_ids)?.count_estimate()
             })
             .copied()
+            .map(Some)
     }
 
-    /// Number of commits behind of the tracking local ref.
-    fn tracking_behind_count(&self, repo: &dyn Repo) -> Result<SizeHint, TemplatePropertyError> {
+    /// Number of commits behind of the tracking local ref, or `None` if this
+    /// isn't a tracked remote ref. The count is computed once and cached.
+    pub fn tracking_behind_count(
+        &self,
+        repo: &dyn Repo,
+    ) -> Result<Option<SizeHint>, RevsetEvaluationError> {
         let Some(tracking) = &self.tracking_ref else {
-            return Err(TemplatePropertyError("Not a tracked remote ref".into()));
+            return Ok(None);
         };
         tracking
             .behind_count
             .get_or_try_init(|| {
                 let self_ids = self.target.added_ids().cloned().collect_vec();
                 let other_ids = tracking.target.added_ids().cloned().collect_vec();
-                Ok(revset::walk_revs(repo, &other_ids, &self_ids)?.count_estimate()?)
+                revset::walk_revs(repo, &other_ids, &self_ids)?.count_estimate()
             })
             .copied()
+            .map(Some)
     }
 }
 
@@ -1753,12 +2030,25 @@ pub struct WorkspaceRef {
     name: WorkspaceNameBuf,
     /// Working-copy commit of this workspace.
     target: Commit,
+    /// Recorded path and state of this workspace, if loaded.
+    #[serde(skip)]
+    info: Option<WorkspaceInfo>,
 }
 
 impl WorkspaceRef {
     /// Creates a new workspace reference from the workspace name and commit.
     pub fn new(name: WorkspaceNameBuf, target: Commit) -> Self {
-        Self { name, target }
+        Self {
+            name,
+            target,
+            info: None,
+        }
+    }
+
+    /// Attaches the recorded path and state of this workspace.
+    pub fn with_info(mut self, info: WorkspaceInfo) -> Self {
+        self.info = Some(info);
+        self
     }
 
     /// Returns the workspace name symbol.
@@ -1770,6 +2060,17 @@ impl WorkspaceRef {
     pub fn target(&self) -> &Commit {
         &self.target
     }
+
+    /// Returns the recorded path and state of this workspace, if loaded.
+    pub fn info(&self) -> Option<&WorkspaceInfo> {
+        self.info.as_ref()
+    }
+
+    fn expect_info(&self) -> Result<&WorkspaceInfo, TemplatePropertyError> {
+        self.info
+            .as_ref()
+            .ok_or_else(|| TemplatePropertyError("Workspace info isn't available".into()))
+    }
 }
 
 impl Template for WorkspaceRef {
@@ -1802,9 +2103,93 @@ fn builtin_workspace_ref_methods<'repo>() -> CommitTemplateBuildMethodFnMap<'rep
             Ok(out_property.into_dyn_wrapped())
         },
     );
+    map.insert(
+        "path",
+        |_language, _diagnostics, _build_ctx, self_property, function| {
+            function.expect_no_arguments()?;
+            let out_property = self_property.and_then(|ws_ref| {
+                workspace_path_to_string(ws_ref.expect_info()?.recorded_path.as_deref())
+            });
+            Ok(out_property.into_dyn_wrapped())
+        },
+    );
+    map.insert(
+        "absolute_path",
+        |_language, _diagnostics, _build_ctx, self_property, function| {
+            function.expect_no_arguments()?;
+            let out_property = self_property.and_then(|ws_ref| {
+                workspace_path_to_string(ws_ref.expect_info()?.absolute_path.as_deref())
+            });
+            Ok(out_property.into_dyn_wrapped())
+        },
+    );
+    map.insert(
+        "exists",
+        |_language, _diagnostics, _build_ctx, self_property, function| {
+            function.expect_no_arguments()?;
+            let out_property = self_property.and_then(|ws_ref| Ok(ws_ref.expect_info()?.exists));
+            Ok(out_property.into_dyn_wrapped())
+        },
+    );
+    map.insert(
+        "stale",
+        |_language, _diagnostics, _build_ctx, self_property, function| {
+            function.expect_no_arguments()?;
+            let out_property =
+                self_property.and_then(|ws_ref| Ok(ws_ref.expect_info()?.stale == Some(true)));
+            Ok(out_property.into_dyn_wrapped())
+        },
+    );
+    map.insert(
+        "created_time",
+        |_language, _diagnostics, _build_ctx, self_property, function| {
+            function.expect_no_arguments()?;
+            let out_property = self_property.and_then(|ws_ref| {
+                let creation = ws_ref.expect_info()?.creation.as_ref();
+                let creation = creation.ok_or_else(|| {
+                    TemplatePropertyError("Workspace creation time isn't recorded".into())
+                })?;
+                Ok(creation.time)
+            });
+            Ok(out_property.into_dyn_wrapped())
+        },
+    );
+    map.insert(
+        "created_by",
+        |_language, _diagnostics, _build_ctx, self_property, function| {
+            function.expect_no_arguments()?;
+            let out_property = self_property.and_then(|ws_ref| {
+                let creation = ws_ref.expect_info()?.creation.as_ref();
+                Ok(creation.map_or_else(String::new, |c| c.username.clone()))
+            });
+            Ok(out_property.into_dyn_wrapped())
+        },
+    );
+    map.insert(
+        "created_operation_id",
+        |_language, _diagnostics, _build_ctx, self_property, function| {
+            function.expect_no_arguments()?;
+            let out_property = self_property.and_then(|ws_ref| {
+                let creation = ws_ref.expect_info()?.creation.as_ref();
+                Ok(creation.map_or_else(String::new, |c| c.operation_id.hex()))
+            });
+            Ok(out_property.into_dyn_wrapped())
+        },
+    );
     map
 }
 
+/// Converts the recorded workspace path to string. Returns an empty string if
+/// the workspace has no recorded path.
+fn workspace_path_to_string(path: Option<&Path>) -> Result<String, TemplatePropertyError> {
+    let Some(path) = path else {
+        return Ok(String::new());
+    };
+    path.to_str()
+        .map(ToOwned::to_owned)
+        .ok_or_else(|| TemplatePropertyError("Invalid UTF-8 sequence in path".into()))
+}
+
 #[expect(clippy::ref_option)]
 fn serialize_tracking_target<S>(
     tracking_ref: &Option<TrackingRef>,
@@ -1853,6 +2238,14 @@ fn builtin_commit_ref_methods<'repo>() -> CommitTemplateBuildMethodFnMap<'repo,
             Ok(out_property.into_dyn_wrapped())
         },
     );
+    map.insert(
+        "conflict_sides",
+        |_language, _diagnostics, _build_ctx, self_property, function| {
+            function.expect_no_arguments()?;
+            let out_property = self_property.map(|commit_ref| commit_ref.conflict_sides());
+            Ok(out_property.into_dyn_wrapped())
+        },
+    );
     map.insert(
         "normal_target",
         |language, _diagnostics, _build_ctx, self_property, function| {
@@ -1912,8 +2305,11 @@ fn builtin_commit_ref_methods<'repo>() -> CommitTemplateBuildMethodFnMap<'repo,
         |language, _diagnostics, _build_ctx, self_property, function| {
             function.expect_no_arguments()?;
             let repo = language.repo;
-            let out_property =
-                self_property.and_then(|commit_ref| commit_ref.tracking_ahead_count(repo));
+            let out_property = self_property.and_then(|commit_ref| {
+                commit_ref
+                    .tracking_ahead_count(repo)?
+                    .ok_or_else(|| TemplatePropertyError("Not a tracked remote ref".into()))
+            });
             Ok(out_property.into_dyn_wrapped())
         },
     );
@@ -1922,8 +2318,11 @@ fn builtin_commit_ref_methods<'repo>() -> CommitTemplateBuildMethodFnMap<'repo,
         |language, _diagnostics, _build_ctx, self_property, function| {
             function.expect_no_arguments()?;
             let repo = language.repo;
-            let out_property =
-                self_property.and_then(|commit_ref| commit_ref.tracking_behind_count(repo));
+            let out_property = self_property.and_then(|commit_ref| {
+                commit_ref
+                    .tracking_behind_count(repo)?
+                    .ok_or_else(|| TemplatePropertyError("Not a tracked remote ref".into()))
+            });
             Ok(out_property.into_dyn_wrapped())
         },
     );
@@ -1938,6 +2337,40 @@ fn builtin_commit_ref_methods<'repo>() -> CommitTemplateBuildMethodFnMap<'repo,
     map
 }
 
+fn builtin_ref_conflict_side_methods<'repo>()
+-> CommitTemplateBuildMethodFnMap<'repo, RefConflictSide> {
+    // Not using maplit::hashmap!{} or custom declarative macro here because
+    // code completion inside macro is quite restricted.
+    let mut map = CommitTemplateBuildMethodFnMap::<RefConflictSide>::new();
+    map.insert(
+        "target",
+        |language, _diagnostics, _build_ctx, self_property, function| {
+            function.expect_no_arguments()?;
+            let repo = language.repo;
+            let out_property =
+                self_property.and_then(|side| Ok(repo.store().get_commit(side.target())?));
+            Ok(out_property.into_dyn_wrapped())
+        },
+    );
+    map.insert(
+        "removed",
+        |_language, _diagnostics, _build_ctx, self_property, function| {
+            function.expect_no_arguments()?;
+            let out_property = self_property.map(|side| side.is_removed());
+            Ok(out_property.into_dyn_wrapped())
+        },
+    );
+    map.insert(
+        "remotes",
+        |_language, _diagnostics, _build_ctx, self_property, function| {
+            function.expect_no_arguments()?;
+            let out_property = self_property.map(|side| side.remotes().to_vec());
+            Ok(out_property.into_dyn_wrapped())
+        },
+    );
+    map
+}
+
 /// Cache for reverse lookup refs.
 #[derive(Clone, Debug, Default)]
 pub struct CommitRefsIndex {
@@ -1965,11 +2398,8 @@ fn build_local_remote_refs_index<'a>(
         let local_target = target.local_target;
         let remote_refs = target.remote_refs;
         if local_target.is_present() {
-            let commit_ref = CommitRef::local(
-                name,
-                local_target.clone(),
-                remote_refs.iter().map(|&(_, remote_ref)| remote_ref),
-            );
+            let commit_ref =
+                CommitRef::local(name, local_target.clone(), remote_refs.iter().copied());
             index.insert(local_target.added_ids(), commit_ref);
         }
         for &(remote_name, remote_ref) in &remote_refs {
@@ -2083,6 +2513,18 @@ fn builtin_repo_path_methods<'repo>() -> CommitTemplateBuildMethodFnMap<'repo, R
             Ok(out_property.into_dyn_wrapped())
         },
     );
+    map.insert(
+        "name",
+        |_language, _diagnostics, _build_ctx, self_property, function| {
+            function.expect_no_arguments()?;
+            let out_property = self_property.map(|path| {
+                path.split()
+                    .map(|(_, name)| name.as_internal_str().to_owned())
+                    .unwrap_or_default()
+            });
+            Ok(out_property.into_dyn_wrapped())
+        },
+    );
     map
 }
 
@@ -2528,6 +2970,24 @@ impl TreeDiffEntry {
         diff_util::diff_status(&self.path, &self.values)
     }
 
+    async fn size_delta(&self, store: &Store) -> BackendResult<i64> {
+        let before =
+            diff_util::file_content_size(store, self.path.source(), &self.values.before).await?;
+        let after =
+            diff_util::file_content_size(store, self.path.target(), &self.values.after).await?;
+        Ok(after as i64 - before as i64)
+    }
+
+    fn executable_changed(&self) -> bool {
+        match (
+            is_executable_file(&self.values.before),
+            is_executable_file(&self.values.after),
+        ) {
+            (Some(before), Some(after)) => before != after,
+            _ => false,
+        }
+    }
+
     fn into_source_entry(self) -> TreeEntry {
         TreeEntry {
             path: self.path.source.map_or(self.path.target, |(path, _)| path),
@@ -2592,6 +3052,24 @@ fn builtin_tree_diff_entry_methods<'repo>() -> CommitTemplateBuildMethodFnMap<'r
             Ok(out_property.into_dyn_wrapped())
         },
     );
+    map.insert(
+        "size_delta",
+        |language, _diagnostics, _build_ctx, self_property, function| {
+            function.expect_no_arguments()?;
+            let store = language.repo.store().clone();
+            let out_property =
+                self_property.and_then(move |entry| Ok(entry.size_delta(&store).block_on()?));
+            Ok(out_property.into_dyn_wrapped())
+        },
+    );
+    map.insert(
+        "executable_changed",
+        |_language, _diagnostics, _build_ctx, self_property, function| {
+            function.expect_no_arguments()?;
+            let out_property = self_property.map(|entry| entry.executable_changed());
+            Ok(out_property.into_dyn_wrapped())
+        },
+    );
     map.insert(
         "source",
         |_language, _diagnostics, _build_ctx, self_property, function| {
@@ -2665,9 +3143,42 @@ fn builtin_tree_entry_methods<'repo>() -> CommitTemplateBuildMethodFnMap<'repo,
             Ok(out_property.into_dyn_wrapped())
         },
     );
+    map.insert(
+        "size",
+        |language, _diagnostics, _build_ctx, self_property, function| {
+            function.expect_no_arguments()?;
+            let store = language.repo.store().clone();
+            let out_property = self_property.and_then(move |entry| {
+                let size =
+                    diff_util::file_content_size(&store, &entry.path, &entry.value).block_on()?;
+                Ok(i64::try_from(size)?)
+            });
+            Ok(out_property.into_dyn_wrapped())
+        },
+    );
+    map.insert(
+        "id",
+        |_language, _diagnostics, _build_ctx, self_property, function| {
+            function.expect_no_arguments()?;
+            let out_property = self_property.map(|entry| describe_object_id(&entry.value));
+            Ok(out_property.into_dyn_wrapped())
+        },
+    );
     map
 }
 
+/// Returns the hex ID of the object the value points to, or an empty string if
+/// the value is absent or conflicted.
+fn describe_object_id(value: &MergedTreeValue) -> String {
+    match value.as_resolved() {
+        Some(Some(TreeValue::File { id, .. })) => id.hex(),
+        Some(Some(TreeValue::Symlink(id))) => id.hex(),
+        Some(Some(TreeValue::Tree(id))) => id.hex(),
+        Some(Some(TreeValue::GitSubmodule(id))) => id.hex(),
+        Some(None) | None => String::new(),
+    }
+}
+
 fn describe_file_type(value: &MergedTreeValue) -> &'static str {
     match value.as_resolved() {
         Some(Some(TreeValue::File { .. })) => "file",
@@ -2939,6 +3450,201 @@ fn builtin_annotation_line_methods<'repo>() -> CommitTemplateBuildMethodFnMap<'r
     map
 }
 
+/// Commit to be uploaded by `jj gerrit upload`.
+#[derive(Debug, Clone)]
+pub struct GerritUploadEntry {
+    /// The commit before the Change-Id footer is added.
+    pub commit: Commit,
+    /// The Gerrit Change-Id of the commit.
+    pub change_id: String,
+    /// The URL of the Gerrit change, if known.
+    pub url: Option<String>,
+    /// Whether the commit is only shown, not pushed.
+    pub dry_run: bool,
+}
+
+fn builtin_gerrit_upload_entry_methods<'repo>()
+-> CommitTemplateBuildMethodFnMap<'repo, GerritUploadEntry> {
+    let mut map = CommitTemplateBuildMethodFnMap::<GerritUploadEntry>::new();
+    map.insert(
+        "commit",
+        |_language, _diagnostics, _build_ctx, self_property, function| {
+            function.expect_no_arguments()?;
+            let out_property = self_property.map(|entry| entry.commit);
+            Ok(out_property.into_dyn_wrapped())
+        },
+    );
+    map.insert(
+        "change_id",
+        |_language, _diagnostics, _build_ctx, self_property, function| {
+            function.expect_no_arguments()?;
+            let out_property = self_property.map(|entry| entry.change_id);
+            Ok(out_property.into_dyn_wrapped())
+        },
+    );
+    map.insert(
+        "url",
+        |_language, _diagnostics, _build_ctx, self_property, function| {
+            function.expect_no_arguments()?;
+            let out_property = self_property.map(|entry| entry.url.unwrap_or_default());
+            Ok(out_property.into_dyn_wrapped())
+        },
+    );
+    map.insert(
+        "dry_run",
+        |_language, _diagnostics, _build_ctx, self_property, function| {
+            function.expect_no_arguments()?;
+            let out_property = self_property.map(|entry| entry.dry_run);
+            Ok(out_property.into_dyn_wrapped())
+        },
+    );
+    map
+}
+
+/// Review status of a commit shown by `jj gerrit status`.
+#[derive(Debug, Clone)]
+pub struct GerritStatusEntry {
+    /// The local commit.
+    pub commit: Commit,
+    /// The Gerrit Change-Id of the commit.
+    pub change_id: String,
+    /// The status of the Gerrit change, e.g. `NEW`, or empty if the change
+    /// wasn't found.
+    pub status: String,
+    /// The number of the Gerrit change, if found.
+    pub number: Option<i64>,
+    /// The votes on the Gerrit change, e.g. `Code-Review+2`.
+    pub votes: Vec<String>,
+    /// The URL of the Gerrit change, if found.
+    pub url: Option<String>,
+}
+
+fn builtin_gerrit_status_entry_methods<'repo>()
+-> CommitTemplateBuildMethodFnMap<'repo, GerritStatusEntry> {
+    let mut map = CommitTemplateBuildMethodFnMap::<GerritStatusEntry>::new();
+    map.insert(
+        "commit",
+        |_language, _diagnostics, _build_ctx, self_property, function| {
+            function.expect_no_arguments()?;
+            let out_property = self_property.map(|entry| entry.commit);
+            Ok(out_property.into_dyn_wrapped())
+        },
+    );
+    map.insert(
+        "change_id",
+        |_language, _diagnostics, _build_ctx, self_property, function| {
+            function.expect_no_arguments()?;
+            let out_property = self_property.map(|entry| entry.change_id);
+            Ok(out_property.into_dyn_wrapped())
+        },
+    );
+    map.insert(
+        "status",
+        |_language, _diagnostics, _build_ctx, self_property, function| {
+            function.expect_no_arguments()?;
+            let out_property = self_property.map(|entry| entry.status);
+            Ok(out_property.into_dyn_wrapped())
+        },
+    );
+    map.insert(
+        "number",
+        |_language, _diagnostics, _build_ctx, self_property, function| {
+            function.expect_no_arguments()?;
+            let out_property = self_property.map(|entry| entry.number);
+            Ok(out_property.into_dyn_wrapped())
+        },
+    );
+    map.insert(
+        "votes",
+        |_language, _diagnostics, _build_ctx, self_property, function| {
+            function.expect_no_arguments()?;
+            let out_property = self_property.map(|entry| entry.votes);
+            Ok(out_property.into_dyn_wrapped())
+        },
+    );
+    map.insert(
+        "url",
+        |_language, _diagnostics, _build_ctx, self_property, function| {
+            function.expect_no_arguments()?;
+            let out_property = self_property.map(|entry| entry.url.unwrap_or_default());
+            Ok(out_property.into_dyn_wrapped())
+        },
+    );
+    map
+}
+
+/// Result of pushing a bookmark, shown by `jj git push`.
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct GitPushResultEntry {
+    /// The name of the remote.
+    pub remote: String,
+    /// The name of the bookmark.
+    pub bookmark: String,
+    /// The commit the bookmark pointed to on the remote before the push.
+    pub old_target: Option<Commit>,
+    /// The commit the bookmark points to after the push, or `None` if the
+    /// bookmark was deleted.
+    pub new_target: Option<Commit>,
+    /// `pushed`, `rejected`, `remote-rejected`, or `dry-run`.
+    pub status: String,
+    /// The web URL of the bookmark on the forge, if known.
+    pub url: Option<String>,
+}
+
+fn builtin_git_push_result_entry_methods<'repo>()
+-> CommitTemplateBuildMethodFnMap<'repo, GitPushResultEntry> {
+    let mut map = CommitTemplateBuildMethodFnMap::<GitPushResultEntry>::new();
+    map.insert(
+        "remote",
+        |_language, _diagnostics, _build_ctx, self_property, function| {
+            function.expect_no_arguments()?;
+            let out_property = self_property.map(|entry| entry.remote);
+            Ok(out_property.into_dyn_wrapped())
+        },
+    );
+    map.insert(
+        "bookmark",
+        |_language, _diagnostics, _build_ctx, self_property, function| {
+            function.expect_no_arguments()?;
+            let out_property = self_property.map(|entry| entry.bookmark);
+            Ok(out_property.into_dyn_wrapped())
+        },
+    );
+    map.insert(
+        "old_target",
+        |_language, _diagnostics, _build_ctx, self_property, function| {
+            function.expect_no_arguments()?;
+            let out_property = self_property.map(|entry| entry.old_target);
+            Ok(out_property.into_dyn_wrapped())
+        },
+    );
+    map.insert(
+        "new_target",
+        |_language, _diagnostics, _build_ctx, self_property, function| {
+            function.expect_no_arguments()?;
+            let out_property = self_property.map(|entry| entry.new_target);
+            Ok(out_property.into_dyn_wrapped())
+        },
+    );
+    map.insert(
+        "status",
+        |_language, _diagnostics, _build_ctx, self_property, function| {
+            function.expect_no_arguments()?;
+            let out_property = self_property.map(|entry| entry.status);
+            Ok(out_property.into_dyn_wrapped())
+        },
+    );
+    map.insert(
+        "url",
+        |_language, _diagnostics, _build_ctx, self_property, function| {
+            function.expect_no_arguments()?;
+            let out_property = self_property.map(|entry| entry.url.unwrap_or_default());
+            Ok(out_property.into_dyn_wrapped())
+        },
+    );
+    map
+}
+
 impl Template for Trailer {
     fn format(&self, formatter: &mut TemplateFormatter) -> io::Result<()> {
         write!(formatter, "{}: {}", self.key, self.value)
@@ -2992,7 +3698,6 @@ fn builtin_trailer_list_methods<'repo>() -> CommitTemplateBuildMethodFnMap<'repo
 #[cfg(test)]
 mod tests {
     use std::path::Component;
-    use std::path::Path;
     use std::path::PathBuf;
 
     use jj_lib::config::ConfigLayer;
@@ -3028,6 +3733,7 @@ mod tests {
         revset_aliases_map: RevsetAliasesMap,
         template_aliases_map: TemplateAliasesMap,
         immutable_expression: Arc<UserRevsetExpression>,
+        decorations: OnceCell<Rc<Decorations>>,
         extra_functions: HashMap<&'static str, BuildFunctionFn>,
     }
 
@@ -3052,6 +3758,7 @@ mod tests {
                 revset_aliases_map: RevsetAliasesMap::new(),
                 template_aliases_map: TemplateAliasesMap::new(),
                 immutable_expression: RevsetExpression::none(),
+                decorations: OnceCell::new(),
                 extra_functions: HashMap::new(),
             }
         }
@@ -3084,12 +3791,14 @@ mod tests {
             };
             let mut language = CommitTemplateLanguage::new(
                 self.test_workspace.repo.as_ref(),
+                self.test_workspace.workspace.repo_path(),
                 &self.path_converter,
                 self.test_workspace.workspace.workspace_name(),
                 revset_parse_context,
                 &self.id_prefix_context,
                 self.immutable_expression.clone(),
                 ConflictMarkerStyle::Diff,
+                &self.decorations,
                 &[] as &[Box<dyn CommitTemplateLanguageExtension>],
             );
             // Not using .extend() to infer lifetime of f
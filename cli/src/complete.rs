@@ -35,6 +35,7 @@ use crate::cli_util::load_revset_aliases;
 use crate::cli_util::load_template_aliases;
 use crate::command_error::CommandError;
 use crate::command_error::user_error;
+use crate::commit_ref_list;
 use crate::config::CONFIG_SCHEMA;
 use crate::config::ConfigArgKind;
 use crate::config::ConfigEnv;
@@ -229,6 +230,51 @@ pub fn bookmarks() -> Vec<CompletionCandidate> {
     })
 }
 
+/// Bookmark names, and glob patterns matching the bookmarks in each namespace.
+pub fn bookmark_patterns() -> Vec<CompletionCandidate> {
+    let mut candidates = bookmarks();
+    candidates.extend(with_jj(|jj, _| {
+        Ok(bookmark_namespace_names(&jj)?
+            .into_iter()
+            .map(|namespace| {
+                CompletionCandidate::new(format!("{namespace}*")).display_order(Some(2))
+            })
+            .collect())
+    }));
+    candidates
+}
+
+/// Namespaces of bookmark names, e.g. `dev/` and `dev/alice/` for
+/// `dev/alice/fix`.
+pub fn bookmark_namespaces() -> Vec<CompletionCandidate> {
+    with_jj(|jj, _| {
+        Ok(bookmark_namespace_names(&jj)?
+            .into_iter()
+            .map(CompletionCandidate::new)
+            .collect())
+    })
+}
+
+fn bookmark_namespace_names(jj: &JjBuilder) -> Result<Vec<String>, CommandError> {
+    let output = jj
+        .build()
+        .arg("bookmark")
+        .arg("list")
+        .arg("--all-remotes")
+        .arg("--template")
+        .arg(r#"name ++ "\n""#)
+        .output()
+        .map_err(user_error)?;
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    Ok(stdout
+        .lines()
+        .flat_map(commit_ref_list::name_namespaces)
+        .sorted()
+        .dedup()
+        .map(str::to_owned)
+        .collect())
+}
+
 pub fn local_tags() -> Vec<CompletionCandidate> {
     with_jj(|jj, _| {
         let output = jj
@@ -376,6 +422,11 @@ fn revisions(match_prefix: &str, revset_filter: Option<&str>) -> Vec<CompletionC
         let revisions = revset_filter
             .map(String::from)
             .or_else(|| settings.get_string("revsets.short-prefixes").ok())
+            .or_else(|| {
+                let revsets: Vec<String> = settings.get("revsets.short-prefixes").ok()?;
+                let revsets = revsets.iter().filter(|revset| !revset.is_empty());
+                Some(revsets.map(|revset| format!("({revset})")).join(" | "))
+            })
             .or_else(|| settings.get_string("revsets.log").ok())
             .unwrap_or_default();
 
@@ -601,6 +652,16 @@ pub fn diff_editors() -> Vec<CompletionCandidate> {
     })
 }
 
+/// List of identities defined in the config
+pub fn identities() -> Vec<CompletionCandidate> {
+    with_jj(|_, settings| {
+        Ok(settings
+            .table_keys("identities")
+            .map(CompletionCandidate::new)
+            .collect())
+    })
+}
+
 /// Approximate list of known diff tools
 pub fn diff_formatters() -> Vec<CompletionCandidate> {
     let builtin_format_kinds = crate::diff_util::all_builtin_diff_format_names();
@@ -28,7 +28,10 @@ use std::sync::Mutex;
 use etcetera::BaseStrategy as _;
 use itertools::Itertools as _;
 use jj_lib::config::ConfigFile;
+use jj_lib::config::ConfigFileStamps;
 use jj_lib::config::ConfigGetError;
+use jj_lib::config::ConfigGetResultExt as _;
+use jj_lib::config::ConfigItem;
 use jj_lib::config::ConfigLayer;
 use jj_lib::config::ConfigLoadError;
 use jj_lib::config::ConfigMigrationRule;
@@ -37,10 +40,14 @@ use jj_lib::config::ConfigResolutionContext;
 use jj_lib::config::ConfigSource;
 use jj_lib::config::ConfigValue;
 use jj_lib::config::StackedConfig;
+use jj_lib::dsl_util::AliasDeclaration;
 use jj_lib::dsl_util::AliasDeclarationParser;
 use jj_lib::dsl_util::AliasesMap;
+use jj_lib::fileset::FilesetAliasesMap;
 use jj_lib::secure_config::LoadedSecureConfig;
 use jj_lib::secure_config::SecureConfig;
+use jj_lib::secure_config::SecureConfigError;
+use jj_lib::secure_config::gc_stale_configs;
 use rand::SeedableRng as _;
 use rand_chacha::ChaCha20Rng;
 use regex::Captures;
@@ -413,6 +420,19 @@ impl ConfigEnv {
         })
     }
 
+    /// Removes the repo and workspace config directories which are no longer
+    /// used. Returns the removed directories.
+    pub fn gc_secure_configs(&self) -> Result<Vec<PathBuf>, SecureConfigError> {
+        let Some(root_config_dir) = &self.root_config_dir else {
+            return Ok(vec![]);
+        };
+        let mut removed = vec![];
+        for kind in [REPO_CONFIG_DIR, WORKSPACE_CONFIG_DIR] {
+            removed.extend(gc_stale_configs(&root_config_dir.join(kind))?);
+        }
+        Ok(removed)
+    }
+
     /// Returns the paths to the user-specific config files or directories.
     pub fn user_config_paths(&self) -> impl Iterator<Item = &Path> {
         self.user_config_paths.iter().map(ConfigPath::as_path)
@@ -477,8 +497,9 @@ impl ConfigEnv {
         self.repo_path = Some(path.to_owned());
     }
 
-    /// Returns a path to the existing repo-specific config file.
-    fn maybe_repo_config_path(&self, ui: &Ui) -> Result<Option<PathBuf>, CommandError> {
+    /// Returns a path to the existing repo-specific config file. Unlike
+    /// [`Self::repo_config_path()`], this doesn't create a new config ID.
+    pub fn maybe_repo_config_path(&self, ui: &Ui) -> Result<Option<PathBuf>, CommandError> {
         Ok(self
             .load_secure_config(ui, self.repo_config.as_ref(), REPO_CONFIG_DIR, false)?
             .and_then(|c| c.config_file))
@@ -504,7 +525,10 @@ impl ConfigEnv {
         ui: &Ui,
         config: &RawConfig,
     ) -> Result<Vec<ConfigFile>, CommandError> {
-        config_files_for(config, ConfigSource::Repo, || self.new_repo_config_file(ui))
+        let files = config_files_for(config, ConfigSource::Repo, || self.new_repo_config_file(ui))?;
+        let loaded =
+            self.load_secure_config(ui, self.repo_config.as_ref(), REPO_CONFIG_DIR, false)?;
+        with_secure_config_encoder(files, loaded.as_ref())
     }
 
     fn new_repo_config_file(&self, ui: &Ui) -> Result<Option<ConfigFile>, CommandError> {
@@ -521,10 +545,10 @@ impl ConfigEnv {
     #[instrument(skip(ui))]
     pub fn reload_repo_config(&self, ui: &Ui, config: &mut RawConfig) -> Result<(), CommandError> {
         config.as_mut().remove_layers(ConfigSource::Repo);
-        if let Some(path) = self.maybe_repo_config_path(ui)?
-            && path.exists()
+        if let Some(loaded) =
+            self.load_secure_config(ui, self.repo_config.as_ref(), REPO_CONFIG_DIR, false)?
         {
-            config.as_mut().load_file(ConfigSource::Repo, path)?;
+            load_secure_config_file(config, ConfigSource::Repo, &loaded)?;
         }
         Ok(())
     }
@@ -535,18 +559,6 @@ impl ConfigEnv {
         self.workspace_path = Some(path.to_owned());
     }
 
-    /// Returns a path to the workspace-specific config file, if it exists.
-    fn maybe_workspace_config_path(&self, ui: &Ui) -> Result<Option<PathBuf>, CommandError> {
-        Ok(self
-            .load_secure_config(
-                ui,
-                self.workspace_config.as_ref(),
-                WORKSPACE_CONFIG_DIR,
-                false,
-            )?
-            .and_then(|c| c.config_file))
-    }
-
     /// Returns a path to the existing workspace-specific config file.
     /// If the config file does not exist, will create a new config ID and
     /// create a new directory for this.
@@ -572,9 +584,16 @@ impl ConfigEnv {
         ui: &Ui,
         config: &RawConfig,
     ) -> Result<Vec<ConfigFile>, CommandError> {
-        config_files_for(config, ConfigSource::Workspace, || {
+        let files = config_files_for(config, ConfigSource::Workspace, || {
             self.new_workspace_config_file(ui)
-        })
+        })?;
+        let loaded = self.load_secure_config(
+            ui,
+            self.workspace_config.as_ref(),
+            WORKSPACE_CONFIG_DIR,
+            false,
+        )?;
+        with_secure_config_encoder(files, loaded.as_ref())
     }
 
     fn new_workspace_config_file(&self, ui: &Ui) -> Result<Option<ConfigFile>, CommandError> {
@@ -593,14 +612,76 @@ impl ConfigEnv {
         config: &mut RawConfig,
     ) -> Result<(), CommandError> {
         config.as_mut().remove_layers(ConfigSource::Workspace);
-        if let Some(path) = self.maybe_workspace_config_path(ui)?
-            && path.exists()
-        {
-            config.as_mut().load_file(ConfigSource::Workspace, path)?;
+        if let Some(loaded) = self.load_secure_config(
+            ui,
+            self.workspace_config.as_ref(),
+            WORKSPACE_CONFIG_DIR,
+            false,
+        )? {
+            load_secure_config_file(config, ConfigSource::Workspace, &loaded)?;
         }
         Ok(())
     }
 
+    /// Creates the config file of a new workspace at `workspace_root` with the
+    /// given `content`. Returns the path to the new config file.
+    ///
+    /// If the user configuration directory is unknown, this function returns
+    /// `None`.
+    pub fn init_workspace_config(
+        &self,
+        workspace_root: &Path,
+        content: &str,
+    ) -> Result<Option<PathBuf>, CommandError> {
+        let Some(root_config_dir) = &self.root_config_dir else {
+            return Ok(None);
+        };
+        let config = SecureConfig::new_workspace(workspace_root.join(".jj"));
+        let mut guard = self.rng.lock().unwrap();
+        let loaded = config.init_config(
+            &mut guard,
+            &root_config_dir.join(WORKSPACE_CONFIG_DIR),
+            content.as_bytes(),
+        )?;
+        Ok(loaded.config_file)
+    }
+
+    /// Encrypts the repo or workspace config file with the given age identity
+    /// file, or decrypts it if `age_identity` is `None`. Returns the path to
+    /// the new config file.
+    ///
+    /// If the repo or workspace path is unknown, this function returns `None`.
+    pub fn set_secure_config_encryption(
+        &self,
+        source: ConfigSource,
+        age_identity: Option<&Path>,
+    ) -> Result<Option<PathBuf>, CommandError> {
+        let (config, kind) = match source {
+            ConfigSource::Repo => (self.repo_config.as_ref(), REPO_CONFIG_DIR),
+            ConfigSource::Workspace => (self.workspace_config.as_ref(), WORKSPACE_CONFIG_DIR),
+            _ => panic!("{source} config is not a secure config"),
+        };
+        let (Some(config), Some(root_config_dir)) = (config, self.root_config_dir.as_ref()) else {
+            return Ok(None);
+        };
+        let mut guard = self.rng.lock().unwrap();
+        let loaded =
+            config.set_encryption(&mut guard, &root_config_dir.join(kind), age_identity)?;
+        Ok(loaded.config_file)
+    }
+
+    /// Returns the paths which config files may be loaded from. The files may
+    /// not exist.
+    fn config_source_paths(&self) -> impl Iterator<Item = PathBuf> {
+        let secure_config_paths = [&self.repo_config, &self.workspace_config]
+            .into_iter()
+            .flatten()
+            .map(SecureConfig::config_id_path);
+        self.user_config_paths()
+            .map(Path::to_owned)
+            .chain(secure_config_paths)
+    }
+
     /// Resolves conditional scopes within the current environment. Returns new
     /// resolved config.
     pub fn resolve_config(&self, config: &RawConfig) -> Result<StackedConfig, ConfigGetError> {
@@ -615,6 +696,133 @@ impl ConfigEnv {
     }
 }
 
+/// Reloads the user, repo, and workspace config files when they are edited.
+///
+/// This is meant for long-lived commands which would otherwise keep using the
+/// config loaded at startup.
+pub struct ConfigWatcher<'a> {
+    config_env: ConfigEnv,
+    raw_config: RawConfig,
+    config_migrations: &'a [ConfigMigrationRule],
+    stamps: ConfigFileStamps,
+    #[expect(clippy::type_complexity)]
+    invalidation_hooks: Vec<Box<dyn FnMut(&Ui, &StackedConfig) -> Result<(), CommandError> + 'a>>,
+}
+
+impl<'a> ConfigWatcher<'a> {
+    /// Starts watching the config files `raw_config` was loaded from. The
+    /// `config_migrations` are applied to the reloaded config.
+    pub fn new(
+        config_env: ConfigEnv,
+        raw_config: RawConfig,
+        config_migrations: &'a [ConfigMigrationRule],
+    ) -> Self {
+        let stamps = capture_config_stamps(&config_env, &raw_config);
+        Self {
+            config_env,
+            raw_config,
+            config_migrations,
+            stamps,
+            invalidation_hooks: vec![],
+        }
+    }
+
+    /// Registers a function to be called with the new config after reloading.
+    ///
+    /// Caches derived from the settings should be invalidated by the hook.
+    pub fn add_invalidation_hook(
+        &mut self,
+        hook: impl FnMut(&Ui, &StackedConfig) -> Result<(), CommandError> + 'a,
+    ) {
+        self.invalidation_hooks.push(Box::new(hook));
+    }
+
+    /// Unprocessed config data, including the layers reloaded so far.
+    pub fn raw_config(&self) -> &RawConfig {
+        &self.raw_config
+    }
+
+    /// Reloads the config files if any of them has changed since the last
+    /// check. Returns the new resolved config if reloaded.
+    pub fn reload_if_changed(&mut self, ui: &Ui) -> Result<Option<StackedConfig>, CommandError> {
+        if !self.stamps.has_changed() {
+            return Ok(None);
+        }
+        let mut raw_config = self.raw_config.clone();
+        self.config_env.reload_user_config(&mut raw_config)?;
+        self.config_env.reload_repo_config(ui, &mut raw_config)?;
+        self.config_env
+            .reload_workspace_config(ui, &mut raw_config)?;
+        let mut config = self.config_env.resolve_config(&raw_config)?;
+        // The migration messages were printed when the command started.
+        jj_lib::config::migrate(&mut config, self.config_migrations)?;
+        self.stamps = capture_config_stamps(&self.config_env, &raw_config);
+        self.raw_config = raw_config;
+        for hook in &mut self.invalidation_hooks {
+            hook(ui, &config)?;
+        }
+        Ok(Some(config))
+    }
+}
+
+fn capture_config_stamps(config_env: &ConfigEnv, config: &RawConfig) -> ConfigFileStamps {
+    let layer_paths = config
+        .as_ref()
+        .layers()
+        .iter()
+        .filter(|layer| {
+            matches!(
+                layer.source,
+                ConfigSource::User | ConfigSource::Repo | ConfigSource::Workspace
+            )
+        })
+        .filter_map(|layer| layer.path.clone());
+    ConfigFileStamps::capture(config_env.config_source_paths().chain(layer_paths))
+}
+
+/// Loads the file of a repo or workspace config into the given `config`,
+/// decrypting it if needed.
+fn load_secure_config_file(
+    config: &mut RawConfig,
+    source: ConfigSource,
+    loaded: &LoadedSecureConfig,
+) -> Result<(), CommandError> {
+    let Some(path) = loaded.config_file.as_ref().filter(|path| path.exists()) else {
+        return Ok(());
+    };
+    if let Some(encryption) = loaded.encryption()? {
+        let content = encryption.decrypt_file(path)?;
+        let text = String::from_utf8(content).map_err(|err| {
+            config_error_with_message(format!("Invalid config file {}", path.display()), err)
+        })?;
+        let mut layer = ConfigLayer::parse(source, &text)?;
+        layer.path = Some(path.clone());
+        config.as_mut().add_layer(layer);
+    } else {
+        config.as_mut().load_file(source, path.clone())?;
+    }
+    Ok(())
+}
+
+/// Makes the repo or workspace config `files` encrypted on save if needed.
+fn with_secure_config_encoder(
+    files: Vec<ConfigFile>,
+    loaded: Option<&LoadedSecureConfig>,
+) -> Result<Vec<ConfigFile>, CommandError> {
+    let Some(encryption) = loaded
+        .map(LoadedSecureConfig::encryption)
+        .transpose()?
+        .flatten()
+    else {
+        return Ok(files);
+    };
+    let encoder = Arc::new(encryption);
+    Ok(files
+        .into_iter()
+        .map(|file| file.with_encoder(encoder.clone()))
+        .collect())
+}
+
 fn config_files_for(
     config: &RawConfig,
     source: ConfigSource,
@@ -952,6 +1160,164 @@ where
     P::Error: fmt::Display,
 {
     let mut aliases_map = AliasesMap::new();
+    load_aliases_into(
+        ui,
+        config,
+        table_name,
+        &mut aliases_map,
+        |aliases_map, decl, item| {
+            let defn = item
+                .as_str()
+                .ok_or_else(|| format!("Expected a string, but is {}", item.type_name()))?;
+            let decl = P::default()
+                .parse_declaration(decl)
+                .map_err(|e| format!("{e}"))?;
+            aliases_map.insert_declaration(decl.clone(), defn.to_owned());
+            Ok(decl)
+        },
+    )?;
+    Ok(aliases_map)
+}
+
+/// Loads `fileset-aliases`. Unlike the other aliases, function parameters can
+/// have default values, and the definition can be documented.
+pub fn load_fileset_aliases_map(
+    ui: &Ui,
+    config: &StackedConfig,
+) -> Result<FilesetAliasesMap, CommandError> {
+    let table_name = ConfigNamePathBuf::from_iter(["fileset-aliases"]);
+    let mut aliases_map = FilesetAliasesMap::new();
+    load_aliases_into(
+        ui,
+        config,
+        &table_name,
+        &mut aliases_map,
+        |aliases_map, decl, item| {
+            let defn = FilesetAliasDefinition::from_item(item)?;
+            aliases_map
+                .insert_with_defaults(decl, defn.definition)
+                .map_err(|e| format!("{e}"))
+        },
+    )?;
+    Ok(aliases_map)
+}
+
+/// Loads aliases in `table_name` by calling `insert`, which returns the parsed
+/// declaration.
+///
+/// The function aliases defined in the default config are also added with
+/// `builtin::` prefixed names. If `ui.warn-alias-overrides` is enabled,
+/// aliases overridden by another config layer are reported.
+fn load_aliases_into<P, V, F>(
+    ui: &Ui,
+    config: &StackedConfig,
+    table_name: &ConfigNamePathBuf,
+    aliases_map: &mut AliasesMap<P, V>,
+    mut insert: F,
+) -> Result<(), CommandError>
+where
+    V: Clone,
+    F: FnMut(&mut AliasesMap<P, V>, &str, &ConfigItem) -> Result<AliasDeclaration, String>,
+{
+    let warn_overrides = config
+        .get::<bool>("ui.warn-alias-overrides")
+        .optional()?
+        .unwrap_or(false);
+    // (kind, name, arity): (layer, decl)
+    let mut origins = HashMap::new();
+    let mut overrides = vec![];
+    let mut builtin_copied = false;
+    load_alias_items(ui, config, table_name, |layer, decl, item| {
+        if layer.source != ConfigSource::Default && !builtin_copied {
+            aliases_map.insert_builtin_copies();
+            builtin_copied = true;
+        }
+        let key = match insert(aliases_map, decl, item)? {
+            AliasDeclaration::Symbol(name) => ("symbol", name, 0),
+            AliasDeclaration::Pattern(name, _) => ("pattern", name, 1),
+            AliasDeclaration::Function(name, params) => ("function", name, params.len()),
+        };
+        if let Some((old_layer, old_decl)) = origins.insert(key, (layer, decl))
+            && layer.source != ConfigSource::Default
+        {
+            overrides.push((old_layer, old_decl, layer, decl));
+        }
+        Ok(())
+    })?;
+    if !builtin_copied {
+        aliases_map.insert_builtin_copies();
+    }
+    if warn_overrides {
+        for (old_layer, old_decl, new_layer, new_decl) in overrides {
+            writeln!(
+                ui.warning_default(),
+                "`{table_name}.{old_decl}` in {old_location} is overridden by \
+                 `{table_name}.{new_decl}` in {new_location}",
+                old_location = describe_config_layer(old_layer),
+                new_location = describe_config_layer(new_layer),
+            )?;
+        }
+    }
+    Ok(())
+}
+
+fn describe_config_layer(layer: &ConfigLayer) -> String {
+    match &layer.path {
+        Some(path) => format!("{} config {}", layer.source, path.display()),
+        None => format!("{} config", layer.source),
+    }
+}
+
+/// Definition of fileset alias, which is either a string or a table of
+/// `definition` and `description`.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FilesetAliasDefinition {
+    pub definition: String,
+    pub description: Option<String>,
+}
+
+impl FilesetAliasDefinition {
+    pub fn from_item(item: &ConfigItem) -> Result<Self, String> {
+        if let Some(definition) = item.as_str() {
+            return Ok(Self {
+                definition: definition.to_owned(),
+                description: None,
+            });
+        }
+        let Some(table) = item.as_table_like() else {
+            return Err(format!(
+                "Expected a string or a table, but is {}",
+                item.type_name()
+            ));
+        };
+        if let Some((key, _)) = table
+            .iter()
+            .find(|(key, _)| !["definition", "description"].contains(key))
+        {
+            return Err(format!("Unknown key `{key}`"));
+        }
+        let get_str = |key: &str| match table.get(key) {
+            Some(item) => item
+                .as_str()
+                .map(|s| Some(s.to_owned()))
+                .ok_or_else(|| format!("Expected `{key}` to be a string")),
+            None => Ok(None),
+        };
+        Ok(Self {
+            definition: get_str("definition")?.ok_or("Missing `definition`")?,
+            description: get_str("description")?,
+        })
+    }
+}
+
+/// Calls `insert` with the config layer, declaration, and definition of each
+/// alias in `table_name`. Invalid aliases are reported as warnings.
+pub fn load_alias_items<'a>(
+    ui: &Ui,
+    config: &'a StackedConfig,
+    table_name: &ConfigNamePathBuf,
+    mut insert: impl FnMut(&'a ConfigLayer, &'a str, &'a ConfigItem) -> Result<(), String>,
+) -> Result<(), CommandError> {
     // Load from all config layers in order. 'f(x)' in default layer should be
     // overridden by 'f(a)' in user.
     for layer in config.layers() {
@@ -968,11 +1334,7 @@ where
             }
         };
         for (decl, item) in table.iter() {
-            let r = item
-                .as_str()
-                .ok_or_else(|| format!("Expected a string, but is {}", item.type_name()))
-                .and_then(|v| aliases_map.insert(decl, v).map_err(|e| format!("{e}")));
-            if let Err(s) = r {
+            if let Err(s) = insert(layer, decl, item) {
                 writeln!(
                     ui.warning_default(),
                     "Failed to load `{table_name}.{decl}`: {s}"
@@ -980,7 +1342,7 @@ where
             }
         }
     }
-    Ok(aliases_map)
+    Ok(())
 }
 
 // Not interested in $UPPER_CASE_VARIABLES
@@ -1832,6 +2194,40 @@ mod tests {
         assert_eq!(exists_paths, exists_expected_paths);
     }
 
+    #[test]
+    fn test_config_watcher_reload() {
+        let tmp = setup_config_fs(&[]);
+        let config_path = tmp.path().join("config.toml");
+        std::fs::write(&config_path, "a = 1\n").unwrap();
+        let env = resolve_config_env(
+            &UnresolvedConfigEnv {
+                jj_config: Some("config.toml".to_owned()),
+                ..Default::default()
+            },
+            tmp.path(),
+        );
+        let mut raw_config = RawConfig(StackedConfig::empty());
+        env.reload_user_config(&mut raw_config).unwrap();
+
+        let ui = Ui::null();
+        let reloaded_values = std::cell::RefCell::new(vec![]);
+        let mut watcher = ConfigWatcher::new(env, raw_config, &[]);
+        watcher.add_invalidation_hook(|_ui, config| {
+            reloaded_values
+                .borrow_mut()
+                .push(config.get::<i64>("a").unwrap());
+            Ok(())
+        });
+        assert!(watcher.reload_if_changed(&ui).unwrap().is_none());
+
+        std::fs::write(&config_path, "a = 22\n").unwrap();
+        let config = watcher.reload_if_changed(&ui).unwrap().unwrap();
+        assert_eq!(config.get::<i64>("a").unwrap(), 22);
+        assert!(watcher.reload_if_changed(&ui).unwrap().is_none());
+        assert_eq!(watcher.raw_config().as_ref().get::<i64>("a").unwrap(), 22);
+        assert_eq!(*reloaded_values.borrow(), [22]);
+    }
+
     fn setup_config_fs(files: &[&str]) -> tempfile::TempDir {
         let tmp = testutils::new_temp_dir();
         for file in files {
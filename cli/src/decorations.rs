@@ -0,0 +1,214 @@
+// Copyright 2026 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Badges attached to commits by external sources.
+//!
+//! Each source configured in `[decorations.sources.<name>]` is a file or a
+//! command which outputs lines of `<commit or change id> <badge>`. The sources
+//! are read once per command invocation, and the badges are rendered by the
+//! `decorations()` template method.
+
+use std::collections::HashMap;
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::process::Stdio;
+
+use bstr::ByteSlice as _;
+use jj_lib::backend::ChangeId;
+use jj_lib::backend::CommitId;
+use jj_lib::commit::Commit;
+use jj_lib::config::ConfigGetError;
+use jj_lib::config::ConfigNamePathBuf;
+use jj_lib::file_util::expand_home_path;
+use jj_lib::settings::UserSettings;
+use thiserror::Error;
+
+use crate::config::CommandNameAndArgs;
+
+/// Error that can occur when loading decorations.
+#[derive(Debug, Error)]
+pub enum DecorationsError {
+    #[error(transparent)]
+    Config(#[from] ConfigGetError),
+    #[error("Decoration source '{name}' must specify exactly one of `file` or `command`")]
+    InvalidSource { name: String },
+    #[error("Failed to read decoration source '{name}'")]
+    Io {
+        name: String,
+        #[source]
+        source: io::Error,
+    },
+    #[error("Decoration source '{name}' exited with {exit_status}: {stderr}")]
+    Command {
+        name: String,
+        exit_status: std::process::ExitStatus,
+        stderr: String,
+    },
+    #[error("Invalid line {line_number} in decoration source '{name}': {line}")]
+    InvalidLine {
+        name: String,
+        line_number: usize,
+        line: String,
+    },
+}
+
+#[derive(Clone, Debug, serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+struct DecorationSourceConfig {
+    file: Option<String>,
+    command: Option<CommandNameAndArgs>,
+}
+
+/// Badges loaded from all the configured decoration sources.
+#[derive(Clone, Debug, Default)]
+pub struct Decorations {
+    commit_badges: HashMap<CommitId, Vec<String>>,
+    change_badges: HashMap<ChangeId, Vec<String>>,
+}
+
+impl Decorations {
+    /// Reads all sources in `decorations.sources`. Relative file paths are
+    /// resolved, and commands are run, in the `workspace_root` directory.
+    pub fn load(settings: &UserSettings, workspace_root: &Path) -> Result<Self, DecorationsError> {
+        let mut decorations = Self::default();
+        for name in settings.table_keys("decorations.sources") {
+            let full_name = ConfigNamePathBuf::from_iter(["decorations", "sources", name]);
+            let source: DecorationSourceConfig = settings.get(&full_name)?;
+            let output = match (&source.file, &source.command) {
+                (Some(path), None) => read_file_source(name, workspace_root, path)?,
+                (None, Some(command)) => run_command_source(name, workspace_root, command)?,
+                _ => {
+                    return Err(DecorationsError::InvalidSource {
+                        name: name.to_owned(),
+                    });
+                }
+            };
+            decorations.add_source_output(name, &output)?;
+        }
+        Ok(decorations)
+    }
+
+    /// Returns the badges attached to the `commit` by commit ID or change ID.
+    pub fn get(&self, commit: &Commit) -> Vec<String> {
+        let by_commit = self.commit_badges.get(commit.id());
+        let by_change = self.change_badges.get(commit.change_id());
+        by_commit
+            .into_iter()
+            .chain(by_change)
+            .flatten()
+            .cloned()
+            .collect()
+    }
+
+    fn add_source_output(&mut self, name: &str, output: &str) -> Result<(), DecorationsError> {
+        for (index, line) in output.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let invalid_line = || DecorationsError::InvalidLine {
+                name: name.to_owned(),
+                line_number: index + 1,
+                line: line.to_owned(),
+            };
+            let (id, badge) = line
+                .split_once(char::is_whitespace)
+                .ok_or_else(invalid_line)?;
+            let badge = badge.trim().to_owned();
+            // Change IDs are encoded in "reverse hex" (z-k), which doesn't
+            // overlap with the hex digits of commit IDs.
+            if let Some(change_id) = ChangeId::try_from_reverse_hex(id) {
+                self.change_badges.entry(change_id).or_default().push(badge);
+            } else if let Some(commit_id) = CommitId::try_from_hex(id) {
+                self.commit_badges.entry(commit_id).or_default().push(badge);
+            } else {
+                return Err(invalid_line());
+            }
+        }
+        Ok(())
+    }
+}
+
+fn read_file_source(
+    name: &str,
+    workspace_root: &Path,
+    path: &str,
+) -> Result<String, DecorationsError> {
+    let path = workspace_root.join(expand_home_path(path));
+    fs::read_to_string(&path).map_err(|source| DecorationsError::Io {
+        name: name.to_owned(),
+        source,
+    })
+}
+
+fn run_command_source(
+    name: &str,
+    workspace_root: &Path,
+    command: &CommandNameAndArgs,
+) -> Result<String, DecorationsError> {
+    let output = command
+        .to_command()
+        .current_dir(workspace_root)
+        .stdin(Stdio::null())
+        .output()
+        .map_err(|source| DecorationsError::Io {
+            name: name.to_owned(),
+            source,
+        })?;
+    if !output.status.success() {
+        return Err(DecorationsError::Command {
+            name: name.to_owned(),
+            exit_status: output.status,
+            stderr: output.stderr.to_str_lossy().trim_end().to_owned(),
+        });
+    }
+    Ok(output.stdout.to_str_lossy().into_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_add_source_output() {
+        let commit_id = CommitId::from_hex("0123456789abcdef0123456789abcdef01234567");
+        let change_id = ChangeId::try_from_reverse_hex("zyxwvutsrqponmlkzyxwvutsrqponmlk").unwrap();
+        let mut decorations = Decorations::default();
+        decorations
+            .add_source_output(
+                "test",
+                "# comment\n\n0123456789abcdef0123456789abcdef01234567 \
+                 deployed\nzyxwvutsrqponmlkzyxwvutsrqponmlk  PROJ-123 open \n",
+            )
+            .unwrap();
+        assert_eq!(decorations.commit_badges[&commit_id], ["deployed"]);
+        assert_eq!(decorations.change_badges[&change_id], ["PROJ-123 open"]);
+
+        let err = decorations
+            .add_source_output("test", "deployed\n")
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Invalid line 1 in decoration source 'test': deployed"
+        );
+        let err = decorations
+            .add_source_output("test", "\nnot-an-id badge\n")
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Invalid line 2 in decoration source 'test': not-an-id badge"
+        );
+    }
+}
@@ -1,5 +1,4 @@
 use std::collections::HashMap;
-use std::fmt::Write as _;
 use std::fs;
 use std::io;
 use std::io::Write as _;
@@ -19,6 +18,8 @@ use jj_lib::config::ConfigGetError;
 use jj_lib::file_util::IoResultExt as _;
 use jj_lib::file_util::PathError;
 use jj_lib::settings::UserSettings;
+use jj_lib::trailer::Trailer;
+use jj_lib::trailer::append_trailers;
 use jj_lib::trailer::parse_description_trailers;
 use jj_lib::trailer::parse_trailers;
 use pollster::FutureExt as _;
@@ -317,6 +318,16 @@ pub fn try_combine_messages(sources: &[Commit], destination: &Commit) -> Option<
     }
 }
 
+/// Joins the non-empty descriptions of the `commits`, separated by blank
+/// lines.
+pub fn concatenate_messages<'a>(commits: impl IntoIterator<Item = &'a Commit>) -> String {
+    commits
+        .into_iter()
+        .map(|commit| commit.description())
+        .filter(|description| !description.is_empty())
+        .join("\n")
+}
+
 /// Produces a combined description with "JJ: " comment lines.
 ///
 /// This includes empty descriptins too, so the user doesn't have to wonder why
@@ -400,27 +411,12 @@ pub fn add_trailers_with_template(
     template: &TemplateRenderer<'_, Commit>,
     commit: &Commit,
 ) -> Result<String, CommandError> {
-    let trailers = parse_description_trailers(commit.description());
     let trailer_lines = template
         .format_plain_text(commit)
         .into_string()
         .map_err(|_| user_error("Trailers should be valid utf-8"))?;
     let new_trailers = parse_trailers(&trailer_lines)?;
-    let mut description = commit.description().to_owned();
-    if trailers.is_empty() && !new_trailers.is_empty() {
-        if description.is_empty() {
-            // a first empty line where the user will edit the commit summary
-            description.push('\n');
-        }
-        // create a new paragraph for the trailer
-        description.push('\n');
-    }
-    for new_trailer in new_trailers {
-        if !trailers.contains(&new_trailer) {
-            writeln!(description, "{}: {}", new_trailer.key, new_trailer.value).unwrap();
-        }
-    }
-    Ok(description)
+    Ok(append_trailers(commit.description(), &new_trailers))
 }
 
 /// Add the trailers from `templates.commit_trailers` in the last paragraph of
@@ -440,6 +436,25 @@ pub fn add_trailers(
     }
 }
 
+/// Returns whether a `Signed-off-by` trailer should be added, either because
+/// `--signoff` was passed or `commit.signoff` is enabled.
+pub fn should_signoff(settings: &UserSettings, signoff_arg: bool) -> Result<bool, ConfigGetError> {
+    Ok(signoff_arg || settings.get_bool("commit.signoff")?)
+}
+
+/// Add a `Signed-off-by` trailer for the committer in the last paragraph of
+/// the description
+///
+/// It just lets the description untouched if the trailer is already there.
+pub fn add_signoff_trailer(commit_builder: &DetachedCommitBuilder) -> String {
+    let committer = commit_builder.committer();
+    let trailer = Trailer {
+        key: "Signed-off-by".to_owned(),
+        value: format!("{} <{}>", committer.name, committer.email),
+    };
+    append_trailers(commit_builder.description(), &[trailer])
+}
+
 /// Renders commit description template, which will be edited by user.
 pub fn description_template(
     ui: &Ui,
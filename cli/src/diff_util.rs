@@ -1823,6 +1823,27 @@ fn show_git_diff_texts<T: AsRef<[u8]>>(
     show_unified_diff_hunks(formatter, contents.as_ref().map(Cow::as_ref), options)
 }
 
+/// Returns the size of the file content, or 0 if the value isn't a resolved
+/// file.
+pub async fn file_content_size(
+    store: &Store,
+    path: &RepoPath,
+    value: &MergedTreeValue,
+) -> BackendResult<u64> {
+    let Some(Some(TreeValue::File { id, .. })) = value.as_resolved() else {
+        return Ok(0);
+    };
+    let mut reader = store.read_file(path, id).await?;
+    let size = tokio::io::copy(&mut reader, &mut tokio::io::sink())
+        .await
+        .map_err(|err| BackendError::ReadFile {
+            path: path.to_owned(),
+            id: id.clone(),
+            source: err.into(),
+        })?;
+    Ok(size)
+}
+
 #[instrument(skip_all)]
 pub async fn show_diff_summary(
     formatter: &mut dyn Formatter,
@@ -15,6 +15,7 @@
 //! Git utilities shared by various commands.
 
 use std::error;
+use std::fs;
 use std::io;
 use std::io::Write as _;
 use std::iter;
@@ -28,7 +29,10 @@ use crossterm::terminal::Clear;
 use crossterm::terminal::ClearType;
 use indoc::writedoc;
 use itertools::Itertools as _;
+use jj_lib::backend::Timestamp;
 use jj_lib::commit::Commit;
+use jj_lib::file_util::IoResultExt as _;
+use jj_lib::file_util::persist_temp_file;
 use jj_lib::git;
 use jj_lib::git::FailedRefExportReason;
 use jj_lib::git::GitExportStats;
@@ -37,27 +41,39 @@ use jj_lib::git::GitImportStats;
 use jj_lib::git::GitProgress;
 use jj_lib::git::GitPushStats;
 use jj_lib::git::GitRefKind;
+use jj_lib::git::GitRefMapping;
 use jj_lib::git::GitSettings;
 use jj_lib::git::GitSidebandLineTerminator;
 use jj_lib::git::GitSubprocessCallback;
+use jj_lib::git_backend::FileWriteStats;
+use jj_lib::lock::FileLock;
 use jj_lib::op_store::RefTarget;
 use jj_lib::op_store::RemoteRef;
+use jj_lib::ref_name::RemoteName;
 use jj_lib::ref_name::RemoteRefSymbol;
+use jj_lib::remote_sync_store::RemoteSyncRecord;
+use jj_lib::remote_sync_store::RemoteSyncStore;
+use jj_lib::repo::MutableRepo;
 use jj_lib::repo::ReadonlyRepo;
 use jj_lib::repo::Repo;
 use jj_lib::settings::RemoteSettingsMap;
+use jj_lib::store::Store;
 use jj_lib::workspace::Workspace;
+use tempfile::NamedTempFile;
 use unicode_width::UnicodeWidthStr as _;
 
 use crate::cleanup_guard::CleanupGuard;
+use crate::cli_util::WorkspaceCommandHelper;
 use crate::cli_util::WorkspaceCommandTransaction;
 use crate::cli_util::print_updated_commits;
 use crate::command_error::CommandError;
 use crate::command_error::cli_error;
+use crate::command_error::internal_error_with_message;
 use crate::command_error::user_error;
 use crate::formatter::Formatter;
 use crate::formatter::FormatterExt as _;
 use crate::revset_util::parse_remote_auto_track_bookmarks_map;
+use crate::revset_util::parse_union_name_patterns;
 use crate::ui::ProgressOutput;
 use crate::ui::Ui;
 
@@ -125,6 +141,42 @@ pub fn get_remote_web_url(repo: &ReadonlyRepo, remote_name: &str) -> Option<Stri
     git_remote_url_to_web(url)
 }
 
+/// Converts the web URL of a repository to the URL of the `bookmark` on the
+/// forge, which is recognized by the host name.
+///
+/// Returns `None` if the forge is unknown.
+fn bookmark_web_url(repo_web_url: &str, bookmark: &str) -> Option<String> {
+    let (_scheme, rest) = repo_web_url.split_once("://")?;
+    let host = rest.split('/').next()?;
+    let path = if host == "github.com" || host.starts_with("github.") {
+        "tree"
+    } else if host == "gitlab.com" || host.starts_with("gitlab.") {
+        "-/tree"
+    } else if ["codeberg.org", "gitea.", "forgejo."]
+        .iter()
+        .any(|pattern| host == *pattern || host.starts_with(pattern))
+    {
+        "src/branch"
+    } else if host == "bitbucket.org" {
+        "branch"
+    } else {
+        return None;
+    };
+    Some(format!("{repo_web_url}/{path}/{bookmark}"))
+}
+
+/// Returns the web URL of the `bookmark` on a git remote.
+///
+/// Returns `None` if the remote doesn't exist, or it isn't hosted on a known
+/// forge.
+pub fn get_bookmark_web_url(
+    repo: &ReadonlyRepo,
+    remote_name: &str,
+    bookmark: &str,
+) -> Option<String> {
+    bookmark_web_url(&get_remote_web_url(repo, remote_name)?, bookmark)
+}
+
 /// [`Ui`] adapter to forward Git command outputs.
 pub struct GitSubprocessUi<'a> {
     // Don't hold locked ui.status() which could block tracing output in
@@ -213,9 +265,34 @@ pub fn load_git_import_options(
         auto_local_bookmark: git_settings.auto_local_bookmark,
         abandon_unreachable_commits: git_settings.abandon_unreachable_commits,
         remote_auto_track_bookmarks: parse_remote_auto_track_bookmarks_map(ui, remote_settings)?,
+        ref_mapping: load_git_ref_mapping(ui, git_settings)?,
+    })
+}
+
+/// Loads the rules to skip or rename refs from `git.ignored-refs` and
+/// `git.branch-prefixes`.
+pub fn load_git_ref_mapping(
+    ui: &Ui,
+    git_settings: &GitSettings,
+) -> Result<GitRefMapping, CommandError> {
+    Ok(GitRefMapping {
+        ignored_refs: parse_union_name_patterns(ui, &git_settings.ignored_refs)?.to_matcher(),
+        branch_prefixes: git_settings.branch_prefixes.clone(),
     })
 }
 
+/// Exports refs to the underlying Git repo, skipping or renaming refs as
+/// configured.
+pub fn export_git_refs(
+    ui: &Ui,
+    mut_repo: &mut MutableRepo,
+) -> Result<GitExportStats, CommandError> {
+    let git_settings = GitSettings::from_settings(mut_repo.base_repo().settings())?;
+    let ref_mapping = load_git_ref_mapping(ui, &git_settings)?;
+    let stats = git::export_refs(mut_repo, &ref_mapping)?;
+    Ok(stats)
+}
+
 pub fn print_git_import_stats(
     ui: &Ui,
     tx: &WorkspaceCommandTransaction<'_>,
@@ -576,6 +653,114 @@ pub fn print_push_stats(ui: &Ui, stats: &GitPushStats) -> io::Result<()> {
     Ok(())
 }
 
+/// Records that the `remotes` have been fetched from at the current operation.
+pub fn record_remote_fetches(
+    workspace_command: &WorkspaceCommandHelper,
+    remotes: &[&RemoteName],
+) -> Result<(), CommandError> {
+    let store = RemoteSyncStore::load(workspace_command.repo_path());
+    let repo = workspace_command.repo();
+    let view = repo.view();
+    let time = remote_sync_timestamp(workspace_command);
+    for &remote in remotes {
+        let record = RemoteSyncRecord {
+            time,
+            operation_id: repo.op_id().clone(),
+            ref_count: view.remote_bookmarks(remote).count() + view.remote_tags(remote).count(),
+        };
+        store.record_fetch(remote, record)?;
+    }
+    Ok(())
+}
+
+/// Records that the `remote` has been pushed to at the current operation.
+pub fn record_remote_push(
+    workspace_command: &WorkspaceCommandHelper,
+    remote: &RemoteName,
+    stats: &GitPushStats,
+) -> Result<(), CommandError> {
+    let store = RemoteSyncStore::load(workspace_command.repo_path());
+    let record = RemoteSyncRecord {
+        time: remote_sync_timestamp(workspace_command),
+        operation_id: workspace_command.repo().op_id().clone(),
+        ref_count: stats.pushed.len(),
+    };
+    store.record_push(remote, record)?;
+    Ok(())
+}
+
+/// Name of the file in the repo directory which stores the counters of files
+/// written to the Git backend.
+const FILE_WRITE_STATS_FILE_NAME: &str = "git_file_write_stats.toml";
+
+/// Adds the files written to the Git backend by this process to the counters
+/// stored in the repo directory, so they accumulate across commands.
+pub fn record_file_write_stats(repo_path: &Path, store: &Store) -> Result<(), CommandError> {
+    let Ok(git_backend) = git::get_git_backend(store) else {
+        return Ok(());
+    };
+    let stats = git_backend.take_file_write_stats();
+    if stats.is_empty() {
+        return Ok(());
+    }
+    let path = repo_path.join(FILE_WRITE_STATS_FILE_NAME);
+    let _lock = FileLock::lock(path.with_extension("lock"))
+        .map_err(|err| internal_error_with_message("Failed to lock file write stats", err))?;
+    let mut total = read_file_write_stats(&path)?;
+    total.add(&stats);
+    let text = format!(
+        "written-files = {}\nreused-files = {}\nreused-bytes = {}\n",
+        total.written_files, total.reused_files, total.reused_bytes
+    );
+    let temp_file = NamedTempFile::new_in(repo_path).context(repo_path)?;
+    temp_file
+        .as_file()
+        .write_all(text.as_bytes())
+        .context(temp_file.path())?;
+    persist_temp_file(temp_file, &path).context(&path)?;
+    Ok(())
+}
+
+/// Returns the counters of files written to the Git backend by all commands,
+/// including the ones not yet recorded by this process.
+pub fn load_file_write_stats(
+    repo_path: &Path,
+    store: &Store,
+) -> Result<FileWriteStats, CommandError> {
+    record_file_write_stats(repo_path, store)?;
+    read_file_write_stats(&repo_path.join(FILE_WRITE_STATS_FILE_NAME))
+}
+
+fn read_file_write_stats(path: &Path) -> Result<FileWriteStats, CommandError> {
+    let text = match fs::read_to_string(path) {
+        Ok(text) => text,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(FileWriteStats::default()),
+        Err(err) => return Err(err.into()),
+    };
+    let parse_err =
+        |err| internal_error_with_message(format!("Failed to parse {}", path.display()), err);
+    let doc: toml_edit::DocumentMut = text.parse().map_err(parse_err)?;
+    let get = |key: &str| {
+        doc.get(key)
+            .and_then(|item| item.as_integer())
+            .map_or(0, |n| n.try_into().unwrap_or(0))
+    };
+    Ok(FileWriteStats {
+        written_files: get("written-files"),
+        reused_files: get("reused-files"),
+        reused_bytes: get("reused-bytes"),
+    })
+}
+
+fn remote_sync_timestamp(workspace_command: &WorkspaceCommandHelper) -> Timestamp {
+    // Respect the operation timestamp override so the records match the
+    // operation log in tests.
+    workspace_command
+        .settings()
+        .operation_timestamp()
+        .unwrap_or_else(Timestamp::now)
+}
+
 #[cfg(test)]
 mod tests {
     use std::path::MAIN_SEPARATOR;
@@ -674,6 +859,30 @@ mod tests {
         assert_eq!(to_web("/path/to/repo"), None);
     }
 
+    #[test]
+    fn test_bookmark_web_url() {
+        assert_eq!(
+            bookmark_web_url("https://github.com/owner/repo", "main"),
+            Some("https://github.com/owner/repo/tree/main".to_owned())
+        );
+        assert_eq!(
+            bookmark_web_url("https://gitlab.com/group/sub/repo", "feature/x"),
+            Some("https://gitlab.com/group/sub/repo/-/tree/feature/x".to_owned())
+        );
+        assert_eq!(
+            bookmark_web_url("https://codeberg.org/owner/repo", "main"),
+            Some("https://codeberg.org/owner/repo/src/branch/main".to_owned())
+        );
+        assert_eq!(
+            bookmark_web_url("https://bitbucket.org/owner/repo", "main"),
+            Some("https://bitbucket.org/owner/repo/branch/main".to_owned())
+        );
+        assert_eq!(
+            bookmark_web_url("https://example.com/owner/repo", "main"),
+            None
+        );
+    }
+
     #[test]
     fn test_bar() {
         let mut buf = String::new();
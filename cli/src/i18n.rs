@@ -0,0 +1,261 @@
+// Copyright 2026 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Catalog of translated user-facing messages.
+//!
+//! Messages are identified by dotted IDs like
+//! `bookmark.rename.no-such-bookmark`. The English text is written at the call
+//! site with [`tr!`], and is used unless the active catalog has a translation,
+//! so a missing or partial catalog never loses a message.
+//!
+//! A catalog is a TOML file named after the language, e.g. `de.toml` or
+//! `pt_BR.toml`, which maps message IDs to translated text. Tables can be used
+//! to group IDs sharing a prefix. Catalogs are looked up in `$JJ_LOCALE_DIR` if
+//! set, or in `../share/jj/locale` relative to the `jj` executable, where
+//! distributions can install them.
+
+use std::collections::BTreeMap;
+use std::env;
+use std::fmt;
+use std::fmt::Write as _;
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+use std::sync::RwLock;
+
+use jj_lib::config::ConfigGetError;
+use jj_lib::config::StackedConfig;
+use thiserror::Error;
+
+static ACTIVE_CATALOG: RwLock<BTreeMap<String, String>> = RwLock::new(BTreeMap::new());
+
+/// Error that can occur when loading a message catalog.
+#[derive(Debug, Error)]
+pub enum CatalogLoadError {
+    #[error(transparent)]
+    Config(#[from] ConfigGetError),
+    #[error("Failed to read message catalog {path}")]
+    Read {
+        path: PathBuf,
+        #[source]
+        source: io::Error,
+    },
+    #[error("Failed to parse message catalog {path}")]
+    Parse {
+        path: PathBuf,
+        #[source]
+        source: Box<dyn std::error::Error + Send + Sync>,
+    },
+}
+
+/// Formats the message `id` using the active catalog, or the English `text`
+/// if the catalog has no translation for it.
+///
+/// `{name}` placeholders are replaced with the value of the argument of the
+/// same name. Placeholders without a matching argument are left as is.
+pub fn format_message(id: &str, text: &str, args: &[(&str, &dyn fmt::Display)]) -> String {
+    let catalog = ACTIVE_CATALOG.read().unwrap();
+    let template = catalog.get(id).map_or(text, String::as_str);
+    substitute_args(template, args)
+}
+
+/// Formats a user-facing message which can be translated.
+///
+/// `tr!("some.id", "Text with {name}", name = value)` looks up `some.id` in
+/// the active catalog, falling back to the English text. Unlike `format!()`,
+/// arguments must be passed by name, and format specs aren't supported.
+macro_rules! tr {
+    ($id:literal, $text:literal $(, $name:ident = $value:expr)* $(,)?) => {
+        $crate::i18n::format_message(
+            $id,
+            $text,
+            &[$((stringify!($name), &$value as &dyn ::std::fmt::Display)),*],
+        )
+    };
+}
+
+pub(crate) use tr;
+
+/// Loads the catalog for the language selected by `ui.language`, and makes it
+/// active. English is used if there's no catalog for the language, or if the
+/// catalog couldn't be loaded.
+pub fn load_catalog(config: &StackedConfig) -> Result<(), CatalogLoadError> {
+    *ACTIVE_CATALOG.write().unwrap() = BTreeMap::new();
+    let language: String = config.get("ui.language")?;
+    let language = if language == "auto" {
+        language_from_env().unwrap_or_default()
+    } else {
+        language
+    };
+    let mut messages = BTreeMap::new();
+    if let Some(dir) = catalog_dir() {
+        for name in candidate_catalog_names(&language) {
+            let path = dir.join(format!("{name}.toml"));
+            match fs::read_to_string(&path) {
+                Ok(text) => {
+                    messages = parse_catalog(&text).map_err(|source| CatalogLoadError::Parse {
+                        path: path.clone(),
+                        source,
+                    })?;
+                    break;
+                }
+                Err(err) if err.kind() == io::ErrorKind::NotFound => {}
+                Err(source) => return Err(CatalogLoadError::Read { path, source }),
+            }
+        }
+    }
+    *ACTIVE_CATALOG.write().unwrap() = messages;
+    Ok(())
+}
+
+/// Returns the language from the POSIX locale environment variables.
+fn language_from_env() -> Option<String> {
+    ["LC_ALL", "LC_MESSAGES", "LANG"]
+        .iter()
+        .filter_map(|name| env::var(name).ok())
+        .find(|value| !value.is_empty())
+}
+
+/// Returns catalog names to try for the `language`, from the most specific.
+/// For example, `pt_BR.UTF-8` tries `pt_BR` and then `pt`.
+fn candidate_catalog_names(language: &str) -> Vec<&str> {
+    // Strip encoding and modifier, e.g. ".UTF-8" and "@euro"
+    let language = language.split(['.', '@']).next().unwrap_or_default().trim();
+    if matches!(language, "" | "C" | "POSIX" | "en") {
+        return vec![];
+    }
+    let mut names = vec![language];
+    if let Some((base, _)) = language.split_once(['_', '-']) {
+        names.push(base);
+    }
+    names
+}
+
+fn catalog_dir() -> Option<PathBuf> {
+    if let Some(dir) = env::var_os("JJ_LOCALE_DIR") {
+        return Some(PathBuf::from(dir));
+    }
+    let exe = env::current_exe().ok()?;
+    Some(
+        exe.parent()?
+            .parent()?
+            .join("share")
+            .join("jj")
+            .join("locale"),
+    )
+}
+
+fn parse_catalog(
+    text: &str,
+) -> Result<BTreeMap<String, String>, Box<dyn std::error::Error + Send + Sync>> {
+    let doc: toml_edit::DocumentMut = text.parse()?;
+    let mut messages = BTreeMap::new();
+    flatten_table(doc.as_table(), "", &mut messages)?;
+    Ok(messages)
+}
+
+fn flatten_table(
+    table: &toml_edit::Table,
+    prefix: &str,
+    messages: &mut BTreeMap<String, String>,
+) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    for (key, item) in table {
+        let id = format!("{prefix}{key}");
+        if let Some(table) = item.as_table() {
+            flatten_table(table, &format!("{id}."), messages)?;
+        } else if let Some(text) = item.as_str() {
+            messages.insert(id, text.to_owned());
+        } else {
+            return Err(format!("Message {id} is not a string").into());
+        }
+    }
+    Ok(())
+}
+
+fn substitute_args(template: &str, args: &[(&str, &dyn fmt::Display)]) -> String {
+    let mut output = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        output.push_str(&rest[..start]);
+        let after = &rest[start + 1..];
+        let arg = after.find('}').and_then(|end| {
+            let name = &after[..end];
+            let (_, value) = args.iter().find(|(arg_name, _)| *arg_name == name)?;
+            Some((end, value))
+        });
+        if let Some((end, value)) = arg {
+            write!(output, "{value}").unwrap();
+            rest = &after[end + 1..];
+        } else {
+            output.push('{');
+            rest = after;
+        }
+    }
+    output.push_str(rest);
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_substitute_args() {
+        assert_eq!(substitute_args("plain", &[]), "plain");
+        assert_eq!(
+            substitute_args("No such bookmark: {name}", &[("name", &"main")]),
+            "No such bookmark: main"
+        );
+        assert_eq!(
+            substitute_args("{a}{b}{a}", &[("a", &1), ("b", &"x")]),
+            "1x1"
+        );
+        // Unknown and unterminated placeholders are kept
+        assert_eq!(
+            substitute_args("{unknown} {name", &[("name", &"x")]),
+            "{unknown} {name"
+        );
+    }
+
+    #[test]
+    fn test_candidate_catalog_names() {
+        assert_eq!(candidate_catalog_names(""), Vec::<&str>::new());
+        assert_eq!(candidate_catalog_names("C.UTF-8"), Vec::<&str>::new());
+        assert_eq!(candidate_catalog_names("en"), Vec::<&str>::new());
+        assert_eq!(candidate_catalog_names("de"), ["de"]);
+        assert_eq!(candidate_catalog_names("pt_BR.UTF-8"), ["pt_BR", "pt"]);
+        assert_eq!(candidate_catalog_names("sr_RS@latin"), ["sr_RS", "sr"]);
+    }
+
+    #[test]
+    fn test_parse_catalog() {
+        let messages = parse_catalog(indoc::indoc! {r#"
+            top = "Top"
+            [bookmark.rename]
+            no-such-bookmark = "Kein Bookmark: {name}"
+        "#})
+        .unwrap();
+        assert_eq!(
+            messages,
+            BTreeMap::from([
+                ("top".to_owned(), "Top".to_owned()),
+                (
+                    "bookmark.rename.no-such-bookmark".to_owned(),
+                    "Kein Bookmark: {name}".to_owned()
+                ),
+            ])
+        );
+        assert!(parse_catalog("number = 1").is_err());
+    }
+}
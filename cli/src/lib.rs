@@ -14,6 +14,7 @@
 
 #![deny(unused_must_use)]
 
+pub mod change_limits;
 pub mod cleanup_guard;
 pub mod cli_util;
 pub mod command_error;
@@ -22,6 +23,7 @@ pub mod commit_ref_list;
 pub mod commit_templater;
 pub mod complete;
 pub mod config;
+pub mod decorations;
 pub mod description_util;
 pub mod diff_util;
 pub mod formatter;
@@ -44,10 +46,14 @@ pub mod git_util {
     }
 }
 pub mod graphlog;
+pub mod i18n;
 pub mod merge_tools;
 pub mod movement_util;
 pub mod operation_templater;
 mod progress;
+pub mod push_checks;
+pub mod ref_filter;
+pub mod resource_limits;
 pub mod revset_util;
 pub mod template_builder;
 pub mod template_parser;
@@ -14,6 +14,10 @@
 
 use jj_cli::cli_util::CliRunner;
 
+#[global_allocator]
+static ALLOCATOR: jj_cli::resource_limits::AccountingAllocator =
+    jj_cli::resource_limits::AccountingAllocator;
+
 fn main() -> std::process::ExitCode {
     CliRunner::init().version(env!("JJ_VERSION")).run().into()
 }
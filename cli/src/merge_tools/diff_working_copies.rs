@@ -8,9 +8,11 @@ use std::sync::Arc;
 
 use futures::StreamExt as _;
 use jj_lib::conflicts::ConflictMarkerStyle;
+use jj_lib::content_filter::ContentFilters;
 use jj_lib::fsmonitor::FsmonitorSettings;
 use jj_lib::gitignore::GitIgnoreFile;
 use jj_lib::local_working_copy::EolConversionMode;
+use jj_lib::local_working_copy::EolOverrides;
 use jj_lib::local_working_copy::ExecChangeSetting;
 use jj_lib::local_working_copy::TreeState;
 use jj_lib::local_working_copy::TreeStateError;
@@ -155,8 +157,10 @@ pub(crate) fn check_out_trees(
         let tree_state_settings = TreeStateSettings {
             conflict_marker_style,
             eol_conversion_mode: EolConversionMode::None,
+            eol_overrides: EolOverrides::default(),
             exec_change_setting: ExecChangeSetting::Auto,
             fsmonitor_settings: FsmonitorSettings::None,
+            content_filters: ContentFilters::default(),
         };
         let mut state = TreeState::init(store.clone(), wc_path, state_dir, &tree_state_settings)?;
         state.set_sparse_patterns(changed_files.clone())?;
@@ -285,6 +289,7 @@ diff editing in mind and be a little inaccurate.
         output_tree_state
             .snapshot(&SnapshotOptions {
                 base_ignores,
+                ignore_matcher: &NothingMatcher,
                 progress: None,
                 start_tracking_matcher: &EverythingMatcher,
                 force_tracking_matcher: &NothingMatcher,
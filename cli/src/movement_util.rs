@@ -37,6 +37,7 @@ pub(crate) struct MovementArgs {
     pub edit: bool,
     pub no_edit: bool,
     pub conflict: bool,
+    pub auto_first: bool,
 }
 
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -44,6 +45,7 @@ struct MovementArgsInternal {
     offset: u64,
     should_edit: bool,
     conflict: bool,
+    auto_first: bool,
 }
 
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
@@ -197,6 +199,7 @@ fn get_target_commit(
                 .try_collect()?;
             return Err(direction.target_not_found_error(workspace_command, args, &start_commits));
         }
+        [first, ..] if args.auto_first => first,
         commits => choose_commit(ui, workspace_command, direction, commits)?,
     };
 
@@ -227,11 +230,23 @@ fn choose_commit<'a>(
     choices.push("q".to_string());
     drop(formatter);
 
-    let index = ui.prompt_choice(
-        "enter the index of the commit you want to target",
-        &choices,
-        None,
-    )?;
+    let index = ui
+        .prompt_choice(
+            "enter the index of the commit you want to target",
+            &choices,
+            None,
+        )
+        .map_err(|err| {
+            let err = CommandError::from(err);
+            if Ui::can_prompt() {
+                err
+            } else {
+                err.hinted(format!(
+                    "Use `jj {} --auto-first` to choose the first candidate",
+                    direction.cmd()
+                ))
+            }
+        })?;
     commits
         .get(index)
         .ok_or_else(|| user_error("ambiguous target commit"))
@@ -254,6 +269,7 @@ pub(crate) fn move_to_commit(
         should_edit: args.edit || (!args.no_edit && config_edit_flag),
         offset: args.offset,
         conflict: args.conflict,
+        auto_first: args.auto_first,
     };
 
     let target = get_target_commit(ui, &workspace_command, direction, current_wc_id, &args)?;
@@ -0,0 +1,308 @@
+// Copyright 2026 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Checks run against the commits to be pushed.
+//!
+//! `jj git push` and `jj gerrit upload` refuse to push commits which fail any
+//! of the builtin checks of the command or the checks listed in
+//! `git.push-checks`. Each kind of check can be skipped with `--skip-check`.
+
+use futures::StreamExt as _;
+use itertools::Itertools as _;
+use jj_lib::commit::Commit;
+use jj_lib::config::ConfigGetError;
+use jj_lib::config::ConfigValue;
+use jj_lib::matchers::EverythingMatcher;
+use jj_lib::object_id::ObjectId as _;
+use jj_lib::repo::Repo as _;
+use jj_lib::settings::HumanByteSize;
+use jj_lib::settings::UserSettings;
+use pollster::FutureExt as _;
+use regex::Regex;
+
+use crate::cli_util::WorkspaceCommandHelper;
+use crate::command_error::CommandError;
+use crate::command_error::user_error_with_message;
+use crate::config::CommandNameAndArgs;
+use crate::diff_util::file_content_size;
+use crate::ui::Ui;
+
+/// Names of the kinds of checks, which can be passed to `--skip-check`.
+pub const PUSH_CHECK_NAMES: [&str; 6] = [
+    "no-empty-commits",
+    "no-empty-description",
+    "no-fixup-commits",
+    "description-regex",
+    "max-size",
+    "command",
+];
+
+/// Prefixes of the descriptions of commits created by `git commit --fixup`
+/// and `--squash`.
+const FIXUP_PREFIXES: [&str; 3] = ["fixup! ", "squash! ", "amend! "];
+
+/// Check to be passed by every commit to be pushed.
+#[derive(Clone, Debug)]
+pub enum PushCheck {
+    /// The commit must change some files.
+    NoEmptyCommits,
+    /// The commit must have a description.
+    NoEmptyDescription,
+    /// The description must not start with `fixup!`, `squash!`, or `amend!`.
+    NoFixupCommits,
+    /// The description must match the regex.
+    DescriptionRegex(Regex),
+    /// Files added or modified by the commit must not be larger than this.
+    MaxSize(u64),
+    /// The command must succeed. It's run in the workspace root with the
+    /// `JJ_COMMIT_ID` and `JJ_CHANGE_ID` environment variables set.
+    Command(CommandNameAndArgs),
+}
+
+impl PushCheck {
+    /// Parses a check in `<name>` or `<name>:<argument>` form.
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let (name, arg) = match text.split_once(':') {
+            Some((name, arg)) => (name, Some(arg)),
+            None => (text, None),
+        };
+        let check = match (name, arg) {
+            ("no-empty-commits", None) => Self::NoEmptyCommits,
+            ("no-empty-description", None) => Self::NoEmptyDescription,
+            ("no-fixup-commits", None) => Self::NoFixupCommits,
+            ("description-regex", Some(arg)) => Regex::new(arg)
+                .map(Self::DescriptionRegex)
+                .map_err(|err| format!("Invalid regex in push check `{text}`: {err}"))?,
+            ("max-size", Some(arg)) => {
+                let HumanByteSize(size) = arg
+                    .parse()
+                    .map_err(|err| format!("Invalid size in push check `{text}`: {err}"))?;
+                Self::MaxSize(size)
+            }
+            ("command", Some(arg)) if !arg.is_empty() => Self::Command(arg.into()),
+            _ if PUSH_CHECK_NAMES.contains(&name) => {
+                let expected = if arg.is_some() {
+                    "takes no argument"
+                } else {
+                    "requires an argument"
+                };
+                return Err(format!("Push check `{name}` {expected}"));
+            }
+            _ => return Err(format!("Unknown push check `{name}`")),
+        };
+        Ok(check)
+    }
+
+    /// Name of the kind of check.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::NoEmptyCommits => "no-empty-commits",
+            Self::NoEmptyDescription => "no-empty-description",
+            Self::NoFixupCommits => "no-fixup-commits",
+            Self::DescriptionRegex(_) => "description-regex",
+            Self::MaxSize(_) => "max-size",
+            Self::Command(_) => "command",
+        }
+    }
+
+    /// Returns the reason why the `commit` fails this check, or `None` if it
+    /// passes.
+    fn check(
+        &self,
+        ui: &Ui,
+        workspace_command: &WorkspaceCommandHelper,
+        commit: &Commit,
+    ) -> Result<Option<String>, CommandError> {
+        let reason = match self {
+            Self::NoEmptyCommits => commit
+                .is_empty(workspace_command.repo().as_ref())?
+                .then(|| "it is empty".to_owned()),
+            Self::NoEmptyDescription => commit
+                .description()
+                .is_empty()
+                .then(|| "it has no description".to_owned()),
+            Self::NoFixupCommits => FIXUP_PREFIXES
+                .iter()
+                .any(|prefix| commit.description().starts_with(prefix))
+                .then(|| "it is a fixup commit".to_owned()),
+            Self::DescriptionRegex(regex) => (!regex.is_match(commit.description()))
+                .then(|| format!("its description doesn't match `{regex}`")),
+            Self::MaxSize(max_size) => {
+                find_large_file(workspace_command, commit, *max_size)?.map(|(path, size)| {
+                    format!(
+                        "{path} is {size} (limit: {max_size})",
+                        size = HumanByteSize(size),
+                        max_size = HumanByteSize(*max_size)
+                    )
+                })
+            }
+            Self::Command(command) => {
+                let status = command
+                    .to_command()
+                    .current_dir(workspace_command.workspace_root())
+                    .env("JJ_COMMIT_ID", commit.id().hex())
+                    .env("JJ_CHANGE_ID", commit.change_id().reverse_hex())
+                    .stdin(std::process::Stdio::null())
+                    .stdout(ui.stderr_for_child()?)
+                    .stderr(ui.stderr_for_child()?)
+                    .status()
+                    .map_err(|err| {
+                        user_error_with_message(
+                            format!("Failed to run push check `{command}`"),
+                            err,
+                        )
+                    })?;
+                (!status.success()).then(|| format!("`{command}` failed with {status}"))
+            }
+        };
+        Ok(reason)
+    }
+}
+
+/// Failed check of a commit.
+#[derive(Clone, Debug)]
+pub struct PushCheckFailure {
+    /// Name of the kind of check.
+    pub name: &'static str,
+    /// Why the commit failed the check, e.g. "it has no description".
+    pub reason: String,
+}
+
+/// Checks to be run by a push command.
+#[derive(Clone, Debug)]
+pub struct PushChecks {
+    checks: Vec<PushCheck>,
+}
+
+impl PushChecks {
+    /// Loads the checks in `git.push-checks`, and adds the `builtin` checks of
+    /// the command in front of them. Checks of the kinds named in `skipped` are
+    /// left out.
+    pub fn from_settings(
+        settings: &UserSettings,
+        builtin: impl IntoIterator<Item = PushCheck>,
+        skipped: &[String],
+    ) -> Result<Self, ConfigGetError> {
+        let configured = settings.get_value_with("git.push-checks", parse_push_checks)?;
+        let checks = builtin
+            .into_iter()
+            .chain(configured)
+            .filter(|check| !skipped.iter().any(|name| name == check.name()))
+            .collect();
+        Ok(Self { checks })
+    }
+
+    /// Runs all checks against the `commit`, and returns the failed ones.
+    pub fn run(
+        &self,
+        ui: &Ui,
+        workspace_command: &WorkspaceCommandHelper,
+        commit: &Commit,
+    ) -> Result<Vec<PushCheckFailure>, CommandError> {
+        let mut failures = vec![];
+        for check in &self.checks {
+            if let Some(reason) = check.check(ui, workspace_command, commit)? {
+                failures.push(PushCheckFailure {
+                    name: check.name(),
+                    reason,
+                });
+            }
+        }
+        Ok(failures)
+    }
+}
+
+fn parse_push_checks(value: ConfigValue) -> Result<Vec<PushCheck>, String> {
+    let array = value
+        .as_array()
+        .ok_or_else(|| "Expected an array of strings".to_owned())?;
+    array
+        .iter()
+        .map(|item| {
+            let text = item
+                .as_str()
+                .ok_or_else(|| "Expected an array of strings".to_owned())?;
+            PushCheck::parse(text)
+        })
+        .try_collect()
+}
+
+/// Returns the first file added or modified by the `commit` which is larger
+/// than `max_size`.
+fn find_large_file(
+    workspace_command: &WorkspaceCommandHelper,
+    commit: &Commit,
+    max_size: u64,
+) -> Result<Option<(String, u64)>, CommandError> {
+    let repo = workspace_command.repo().as_ref();
+    let store = repo.store();
+    async {
+        let parent_tree = commit.parent_tree_async(repo).await?;
+        let tree = commit.tree();
+        let mut diff_stream = parent_tree.diff_stream(&tree, &EverythingMatcher);
+        while let Some(entry) = diff_stream.next().await {
+            let diff = entry.values?;
+            let size = file_content_size(store, &entry.path, &diff.after).await?;
+            if size > max_size {
+                let path = workspace_command.format_file_path(&entry.path);
+                return Ok(Some((path, size)));
+            }
+        }
+        Ok(None)
+    }
+    .block_on()
+}
+
+#[cfg(test)]
+mod tests {
+    use assert_matches::assert_matches;
+
+    use super::*;
+
+    #[test]
+    fn test_parse_push_check() {
+        assert_matches!(
+            PushCheck::parse("no-fixup-commits"),
+            Ok(PushCheck::NoFixupCommits)
+        );
+        assert_matches!(
+            PushCheck::parse("description-regex:^[A-Z]+-[0-9]+: "),
+            Ok(PushCheck::DescriptionRegex(regex)) if regex.as_str() == "^[A-Z]+-[0-9]+: "
+        );
+        assert_matches!(
+            PushCheck::parse("max-size:5MB"),
+            Ok(PushCheck::MaxSize(5242880))
+        );
+        assert_matches!(
+            PushCheck::parse("command:./scripts/check.sh --strict"),
+            Ok(PushCheck::Command(command)) if command.to_string() == "./scripts/check.sh --strict"
+        );
+        assert_eq!(
+            PushCheck::parse("no-fixup-commits:yes").unwrap_err(),
+            "Push check `no-fixup-commits` takes no argument"
+        );
+        assert_eq!(
+            PushCheck::parse("max-size").unwrap_err(),
+            "Push check `max-size` requires an argument"
+        );
+        assert_eq!(
+            PushCheck::parse("max-size:5XB").unwrap_err(),
+            "Invalid size in push check `max-size:5XB`: unrecognized unit prefix"
+        );
+        assert_eq!(
+            PushCheck::parse("unknown").unwrap_err(),
+            "Unknown push check `unknown`"
+        );
+    }
+}
@@ -0,0 +1,906 @@
+// Copyright 2026 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Expressions selecting bookmarks by their properties, such as
+//! `tracked() & ~remote("ci-*")`.
+//!
+//! The expressions are used by the `ui.bookmark-list-filter` setting. The
+//! syntax is a small subset of the revset language: function calls combined
+//! with `~x`, `x & y`, `x ~ y`, `x | y`, and parentheses. Symbol and function
+//! aliases can be defined in the `bookmark-list-filter-aliases` table.
+
+use std::error;
+use std::sync::LazyLock;
+
+use itertools::Itertools as _;
+use jj_lib::dsl_util;
+use jj_lib::dsl_util::AliasDeclaration;
+use jj_lib::dsl_util::AliasDeclarationParser;
+use jj_lib::dsl_util::AliasDefinitionParser;
+use jj_lib::dsl_util::AliasExpandError;
+use jj_lib::dsl_util::AliasExpandableExpression;
+use jj_lib::dsl_util::AliasId;
+use jj_lib::dsl_util::AliasesMap;
+use jj_lib::dsl_util::ExpressionFolder;
+use jj_lib::dsl_util::FoldableExpression;
+use jj_lib::dsl_util::InvalidArguments;
+use jj_lib::dsl_util::StringLiteralParser;
+use jj_lib::dsl_util::collect_similar;
+use jj_lib::op_store::LocalRemoteRefTarget;
+use jj_lib::ref_name::RefName;
+use jj_lib::ref_name::RemoteName;
+use jj_lib::str_util::StringExpression;
+use jj_lib::str_util::StringMatcher;
+use jj_lib::str_util::StringPattern;
+use pest::Parser as _;
+use pest::iterators::Pair;
+use pest::pratt_parser::Assoc;
+use pest::pratt_parser::Op;
+use pest::pratt_parser::PrattParser;
+use pest_derive::Parser;
+use thiserror::Error;
+
+#[derive(Parser)]
+#[grammar = "ref_filter.pest"]
+struct RefFilterParser;
+
+const STRING_LITERAL_PARSER: StringLiteralParser<Rule> = StringLiteralParser {
+    content_rule: Rule::string_content,
+    escape_rule: Rule::string_escape,
+};
+
+impl Rule {
+    fn to_symbol(self) -> Option<&'static str> {
+        match self {
+            Self::EOI => None,
+            Self::whitespace => None,
+            Self::identifier => None,
+            Self::strict_identifier_part => None,
+            Self::strict_identifier => None,
+            Self::string_escape => None,
+            Self::string_content_char => None,
+            Self::string_content => None,
+            Self::string_literal => None,
+            Self::raw_string_content => None,
+            Self::raw_string_literal => None,
+            Self::pattern_kind_op => Some(":"),
+            Self::negate_op => Some("~"),
+            Self::union_op => Some("|"),
+            Self::intersection_op => Some("&"),
+            Self::difference_op => Some("~"),
+            Self::prefix_ops => None,
+            Self::infix_ops => None,
+            Self::function => None,
+            Self::function_name => None,
+            Self::function_arguments => None,
+            Self::formal_parameters => None,
+            Self::string_pattern => None,
+            Self::primary => None,
+            Self::expression => None,
+            Self::program => None,
+            Self::function_alias_declaration => None,
+            Self::alias_declaration => None,
+        }
+    }
+}
+
+/// Names of the builtin filter functions.
+const BUILTIN_FUNCTION_NAMES: &[&str] = &[
+    "all",
+    "none",
+    "local",
+    "deleted",
+    "tracked",
+    "conflicted",
+    "name",
+    "remote",
+];
+
+/// Result of filter expression parsing.
+pub type RefFilterParseResult<T> = Result<T, RefFilterParseError>;
+
+/// Error occurred during filter expression parsing.
+#[derive(Debug, Error)]
+#[error("{pest_error}")]
+pub struct RefFilterParseError {
+    kind: RefFilterParseErrorKind,
+    pest_error: Box<pest::error::Error<Rule>>,
+    source: Option<Box<dyn error::Error + Send + Sync>>,
+}
+
+/// Categories of filter expression parsing error.
+#[derive(Clone, Debug, Eq, Error, PartialEq)]
+pub enum RefFilterParseErrorKind {
+    #[error("Syntax error")]
+    SyntaxError,
+    #[error("Function `{name}` doesn't exist")]
+    NoSuchFunction {
+        name: String,
+        candidates: Vec<String>,
+    },
+    #[error("Function `{name}`: {message}")]
+    InvalidArguments { name: String, message: String },
+    #[error("Redefinition of function parameter")]
+    RedefinedFunctionParameter,
+    #[error("{0}")]
+    Expression(String),
+    #[error("In alias `{0}`")]
+    InAliasExpansion(String),
+    #[error("In function parameter `{0}`")]
+    InParameterExpansion(String),
+    #[error("Alias `{0}` expanded recursively")]
+    RecursiveAlias(String),
+}
+
+impl RefFilterParseError {
+    fn new(kind: RefFilterParseErrorKind, span: pest::Span<'_>) -> Self {
+        let message = kind.to_string();
+        let pest_error = Box::new(pest::error::Error::new_from_span(
+            pest::error::ErrorVariant::CustomError { message },
+            span,
+        ));
+        Self {
+            kind,
+            pest_error,
+            source: None,
+        }
+    }
+
+    fn with_source(mut self, source: impl Into<Box<dyn error::Error + Send + Sync>>) -> Self {
+        self.source = Some(source.into());
+        self
+    }
+
+    fn expression(message: impl Into<String>, span: pest::Span<'_>) -> Self {
+        Self::new(RefFilterParseErrorKind::Expression(message.into()), span)
+    }
+
+    /// Category of the underlying error.
+    pub fn kind(&self) -> &RefFilterParseErrorKind {
+        &self.kind
+    }
+}
+
+impl AliasExpandError for RefFilterParseError {
+    fn invalid_arguments(err: InvalidArguments<'_>) -> Self {
+        err.into()
+    }
+
+    fn recursive_expansion(id: AliasId<'_>, span: pest::Span<'_>) -> Self {
+        Self::new(
+            RefFilterParseErrorKind::RecursiveAlias(id.to_string()),
+            span,
+        )
+    }
+
+    fn within_alias_expansion(self, id: AliasId<'_>, span: pest::Span<'_>) -> Self {
+        let kind = match id {
+            AliasId::Symbol(_) | AliasId::Pattern(..) | AliasId::Function(..) => {
+                RefFilterParseErrorKind::InAliasExpansion(id.to_string())
+            }
+            AliasId::Parameter(_) => RefFilterParseErrorKind::InParameterExpansion(id.to_string()),
+        };
+        Self::new(kind, span).with_source(self)
+    }
+}
+
+impl From<pest::error::Error<Rule>> for RefFilterParseError {
+    fn from(err: pest::error::Error<Rule>) -> Self {
+        Self {
+            kind: RefFilterParseErrorKind::SyntaxError,
+            pest_error: Box::new(rename_rules_in_pest_error(err)),
+            source: None,
+        }
+    }
+}
+
+impl From<InvalidArguments<'_>> for RefFilterParseError {
+    fn from(err: InvalidArguments<'_>) -> Self {
+        let kind = RefFilterParseErrorKind::InvalidArguments {
+            name: err.name.to_owned(),
+            message: err.message,
+        };
+        Self::new(kind, err.span)
+    }
+}
+
+fn rename_rules_in_pest_error(err: pest::error::Error<Rule>) -> pest::error::Error<Rule> {
+    err.renamed_rules(|rule| {
+        rule.to_symbol()
+            .map(|sym| format!("`{sym}`"))
+            .unwrap_or_else(|| format!("<{rule:?}>"))
+    })
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ExpressionKind<'i> {
+    Identifier(&'i str),
+    String(String),
+    /// `<name>:<value>` where `<value>` is usually `Identifier` or `String`.
+    Pattern(Box<PatternNode<'i>>),
+    Unary(UnaryOp, Box<ExpressionNode<'i>>),
+    Binary(BinaryOp, Box<ExpressionNode<'i>>, Box<ExpressionNode<'i>>),
+    FunctionCall(Box<FunctionCallNode<'i>>),
+    /// Identity node to preserve the span in the source text.
+    AliasExpanded(AliasId<'i>, Box<ExpressionNode<'i>>),
+}
+
+impl<'i> FoldableExpression<'i> for ExpressionKind<'i> {
+    fn fold<F>(self, folder: &mut F, span: pest::Span<'i>) -> Result<Self, F::Error>
+    where
+        F: ExpressionFolder<'i, Self> + ?Sized,
+    {
+        match self {
+            Self::Identifier(name) => folder.fold_identifier(name, span),
+            Self::String(_) => Ok(self),
+            Self::Pattern(pattern) => folder.fold_pattern(pattern, span),
+            Self::Unary(op, arg) => {
+                let arg = Box::new(folder.fold_expression(*arg)?);
+                Ok(Self::Unary(op, arg))
+            }
+            Self::Binary(op, lhs, rhs) => {
+                let lhs = Box::new(folder.fold_expression(*lhs)?);
+                let rhs = Box::new(folder.fold_expression(*rhs)?);
+                Ok(Self::Binary(op, lhs, rhs))
+            }
+            Self::FunctionCall(function) => folder.fold_function_call(function, span),
+            Self::AliasExpanded(id, subst) => {
+                let subst = Box::new(folder.fold_expression(*subst)?);
+                Ok(Self::AliasExpanded(id, subst))
+            }
+        }
+    }
+}
+
+impl<'i> AliasExpandableExpression<'i> for ExpressionKind<'i> {
+    fn identifier(name: &'i str) -> Self {
+        Self::Identifier(name)
+    }
+
+    fn pattern(pattern: Box<PatternNode<'i>>) -> Self {
+        Self::Pattern(pattern)
+    }
+
+    fn function_call(function: Box<FunctionCallNode<'i>>) -> Self {
+        Self::FunctionCall(function)
+    }
+
+    fn alias_expanded(id: AliasId<'i>, subst: Box<ExpressionNode<'i>>) -> Self {
+        Self::AliasExpanded(id, subst)
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum UnaryOp {
+    /// `~`
+    Negate,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum BinaryOp {
+    /// `|`
+    Union,
+    /// `&`
+    Intersection,
+    /// `~`
+    Difference,
+}
+
+pub type ExpressionNode<'i> = dsl_util::ExpressionNode<'i, ExpressionKind<'i>>;
+pub type FunctionCallNode<'i> = dsl_util::FunctionCallNode<'i, ExpressionKind<'i>>;
+pub type PatternNode<'i> = dsl_util::PatternNode<'i, ExpressionKind<'i>>;
+
+fn parse_function_call_node(pair: Pair<Rule>) -> RefFilterParseResult<FunctionCallNode> {
+    assert_eq!(pair.as_rule(), Rule::function);
+    let [name_pair, args_pair] = pair.into_inner().collect_array().unwrap();
+    assert_eq!(name_pair.as_rule(), Rule::function_name);
+    assert_eq!(args_pair.as_rule(), Rule::function_arguments);
+    let name_span = name_pair.as_span();
+    let args_span = args_pair.as_span();
+    let name = name_pair.as_str();
+    let args = args_pair
+        .into_inner()
+        .map(parse_expression_node)
+        .try_collect()?;
+    Ok(FunctionCallNode {
+        name,
+        name_span,
+        args,
+        keyword_args: vec![], // unsupported
+        args_span,
+    })
+}
+
+fn parse_as_string_literal(pair: Pair<Rule>) -> String {
+    match pair.as_rule() {
+        Rule::identifier => pair.as_str().to_owned(),
+        Rule::string_literal => STRING_LITERAL_PARSER.parse(pair.into_inner()),
+        Rule::raw_string_literal => {
+            let [content] = pair.into_inner().collect_array().unwrap();
+            assert_eq!(content.as_rule(), Rule::raw_string_content);
+            content.as_str().to_owned()
+        }
+        r => panic!("unexpected string literal rule: {r:?}"),
+    }
+}
+
+fn parse_primary_node(pair: Pair<Rule>) -> RefFilterParseResult<ExpressionNode> {
+    assert_eq!(pair.as_rule(), Rule::primary);
+    let span = pair.as_span();
+    let first = pair.into_inner().next().unwrap();
+    let expr = match first.as_rule() {
+        // Ignore inner span to preserve parenthesized expression as such.
+        Rule::expression => parse_expression_node(first)?.kind,
+        Rule::function => {
+            let function = Box::new(parse_function_call_node(first)?);
+            ExpressionKind::FunctionCall(function)
+        }
+        Rule::string_pattern => {
+            let [lhs, op, rhs] = first.into_inner().collect_array().unwrap();
+            assert_eq!(lhs.as_rule(), Rule::strict_identifier);
+            assert_eq!(op.as_rule(), Rule::pattern_kind_op);
+            let name_span = lhs.as_span();
+            let value_span = rhs.as_span();
+            let name = lhs.as_str();
+            let value_expr = match rhs.as_rule() {
+                Rule::identifier => ExpressionKind::Identifier(rhs.as_str()),
+                _ => ExpressionKind::String(parse_as_string_literal(rhs)),
+            };
+            let pattern = Box::new(PatternNode {
+                name,
+                name_span,
+                value: ExpressionNode::new(value_expr, value_span),
+            });
+            ExpressionKind::Pattern(pattern)
+        }
+        Rule::identifier => ExpressionKind::Identifier(first.as_str()),
+        Rule::string_literal | Rule::raw_string_literal => {
+            ExpressionKind::String(parse_as_string_literal(first))
+        }
+        r => panic!("unexpected primary rule: {r:?}"),
+    };
+    Ok(ExpressionNode::new(expr, span))
+}
+
+fn parse_expression_node(pair: Pair<Rule>) -> RefFilterParseResult<ExpressionNode> {
+    assert_eq!(pair.as_rule(), Rule::expression);
+    static PRATT: LazyLock<PrattParser<Rule>> = LazyLock::new(|| {
+        PrattParser::new()
+            .op(Op::infix(Rule::union_op, Assoc::Left))
+            .op(Op::infix(Rule::intersection_op, Assoc::Left)
+                | Op::infix(Rule::difference_op, Assoc::Left))
+            .op(Op::prefix(Rule::negate_op))
+    });
+    PRATT
+        .map_primary(parse_primary_node)
+        .map_prefix(|op, rhs| {
+            let op_kind = match op.as_rule() {
+                Rule::negate_op => UnaryOp::Negate,
+                r => panic!("unexpected prefix operator rule {r:?}"),
+            };
+            let rhs = Box::new(rhs?);
+            let span = op.as_span().start_pos().span(&rhs.span.end_pos());
+            let expr = ExpressionKind::Unary(op_kind, rhs);
+            Ok(ExpressionNode::new(expr, span))
+        })
+        .map_infix(|lhs, op, rhs| {
+            let op_kind = match op.as_rule() {
+                Rule::union_op => BinaryOp::Union,
+                Rule::intersection_op => BinaryOp::Intersection,
+                Rule::difference_op => BinaryOp::Difference,
+                r => panic!("unexpected infix operator rule {r:?}"),
+            };
+            let lhs = Box::new(lhs?);
+            let rhs = Box::new(rhs?);
+            let span = lhs.span.start_pos().span(&rhs.span.end_pos());
+            let expr = ExpressionKind::Binary(op_kind, lhs, rhs);
+            Ok(ExpressionNode::new(expr, span))
+        })
+        .parse(pair.into_inner())
+}
+
+fn parse_program(text: &str) -> RefFilterParseResult<ExpressionNode<'_>> {
+    let mut pairs = RefFilterParser::parse(Rule::program, text)?;
+    let first = pairs.next().unwrap();
+    parse_expression_node(first)
+}
+
+/// Map of filter expression aliases.
+pub type RefFilterAliasesMap = AliasesMap<RefFilterAliasParser, String>;
+
+#[derive(Clone, Debug, Default)]
+pub struct RefFilterAliasParser;
+
+impl AliasDeclarationParser for RefFilterAliasParser {
+    type Error = RefFilterParseError;
+
+    fn parse_declaration(&self, source: &str) -> Result<AliasDeclaration, Self::Error> {
+        let mut pairs = RefFilterParser::parse(Rule::alias_declaration, source)?;
+        let first = pairs.next().unwrap();
+        match first.as_rule() {
+            Rule::strict_identifier => Ok(AliasDeclaration::Symbol(first.as_str().to_owned())),
+            Rule::function_alias_declaration => {
+                let [name_pair, params_pair] = first.into_inner().collect_array().unwrap();
+                assert_eq!(name_pair.as_rule(), Rule::function_name);
+                assert_eq!(params_pair.as_rule(), Rule::formal_parameters);
+                let name = name_pair.as_str().to_owned();
+                let params_span = params_pair.as_span();
+                let params = params_pair
+                    .into_inner()
+                    .map(|pair| match pair.as_rule() {
+                        Rule::strict_identifier => pair.as_str().to_owned(),
+                        r => panic!("unexpected formal parameter rule {r:?}"),
+                    })
+                    .collect_vec();
+                if params.iter().all_unique() {
+                    Ok(AliasDeclaration::Function(name, params))
+                } else {
+                    Err(RefFilterParseError::new(
+                        RefFilterParseErrorKind::RedefinedFunctionParameter,
+                        params_span,
+                    ))
+                }
+            }
+            r => panic!("unexpected alias declaration rule {r:?}"),
+        }
+    }
+}
+
+impl AliasDefinitionParser for RefFilterAliasParser {
+    type Output<'i> = ExpressionKind<'i>;
+    type Error = RefFilterParseError;
+
+    fn parse_definition<'i>(&self, source: &'i str) -> Result<ExpressionNode<'i>, Self::Error> {
+        parse_program(source)
+    }
+}
+
+/// Parsed filter expression.
+pub enum RefFilterExpression {
+    /// Matches all refs.
+    All,
+    /// Matches no refs.
+    None,
+    /// Matches refs having a local target.
+    Local,
+    /// Matches refs which were deleted locally, but still exist on a tracked
+    /// remote.
+    Deleted,
+    /// Matches refs tracking any remote.
+    Tracked,
+    /// Matches refs having a conflicted local target.
+    Conflicted,
+    /// Matches refs whose name matches.
+    Name(StringMatcher),
+    /// Matches refs existing on any remote whose name matches.
+    Remote(StringMatcher),
+    /// Matches refs not matching the expression.
+    NotIn(Box<Self>),
+    /// Matches refs matching either expression.
+    Union(Box<Self>, Box<Self>),
+    /// Matches refs matching both expressions.
+    Intersection(Box<Self>, Box<Self>),
+}
+
+impl RefFilterExpression {
+    /// Returns true if the ref named `name` is selected.
+    ///
+    /// Refs of the `ignored_remote`, which is the Git-tracking remote by
+    /// default, aren't considered to be tracked.
+    pub fn matches(
+        &self,
+        name: &RefName,
+        targets: &LocalRemoteRefTarget,
+        ignored_remote: Option<&RemoteName>,
+    ) -> bool {
+        match self {
+            Self::All => true,
+            Self::None => false,
+            Self::Local => targets.local_target.is_present(),
+            Self::Deleted => {
+                targets.local_target.is_absent()
+                    && targets
+                        .remote_refs
+                        .iter()
+                        .any(|(_, remote_ref)| remote_ref.is_tracked() && remote_ref.is_present())
+            }
+            Self::Tracked => targets.remote_refs.iter().any(|&(remote, remote_ref)| {
+                remote_ref.is_tracked() && Some(remote) != ignored_remote
+            }),
+            Self::Conflicted => targets.local_target.has_conflict(),
+            Self::Name(matcher) => matcher.is_match(name.as_str()),
+            Self::Remote(matcher) => targets
+                .remote_refs
+                .iter()
+                .any(|(remote, _)| matcher.is_match(remote.as_str())),
+            Self::NotIn(expression) => !expression.matches(name, targets, ignored_remote),
+            Self::Union(expression1, expression2) => {
+                expression1.matches(name, targets, ignored_remote)
+                    || expression2.matches(name, targets, ignored_remote)
+            }
+            Self::Intersection(expression1, expression2) => {
+                expression1.matches(name, targets, ignored_remote)
+                    && expression2.matches(name, targets, ignored_remote)
+            }
+        }
+    }
+}
+
+/// Parses the filter expression `text`, expanding the aliases.
+pub fn parse(
+    text: &str,
+    aliases_map: &RefFilterAliasesMap,
+) -> RefFilterParseResult<RefFilterExpression> {
+    let node = parse_program(text)?;
+    let node = dsl_util::expand_aliases(node, aliases_map)?;
+    resolve_expression(&node, aliases_map)
+}
+
+fn resolve_expression(
+    node: &ExpressionNode,
+    aliases_map: &RefFilterAliasesMap,
+) -> RefFilterParseResult<RefFilterExpression> {
+    catch_aliases(node, |node| match &node.kind {
+        ExpressionKind::Identifier(_) | ExpressionKind::String(_) | ExpressionKind::Pattern(_) => {
+            Err(RefFilterParseError::expression(
+                "Expected function call, but is string pattern",
+                node.span,
+            ))
+        }
+        ExpressionKind::Unary(UnaryOp::Negate, arg_node) => {
+            let arg = resolve_expression(arg_node, aliases_map)?;
+            Ok(RefFilterExpression::NotIn(Box::new(arg)))
+        }
+        ExpressionKind::Binary(op, lhs_node, rhs_node) => {
+            let lhs = Box::new(resolve_expression(lhs_node, aliases_map)?);
+            let rhs = Box::new(resolve_expression(rhs_node, aliases_map)?);
+            match op {
+                BinaryOp::Union => Ok(RefFilterExpression::Union(lhs, rhs)),
+                BinaryOp::Intersection => Ok(RefFilterExpression::Intersection(lhs, rhs)),
+                BinaryOp::Difference => Ok(RefFilterExpression::Intersection(
+                    lhs,
+                    Box::new(RefFilterExpression::NotIn(rhs)),
+                )),
+            }
+        }
+        ExpressionKind::FunctionCall(function) => resolve_function_call(function, aliases_map),
+        ExpressionKind::AliasExpanded(..) => unreachable!(),
+    })
+}
+
+fn resolve_function_call(
+    function: &FunctionCallNode,
+    aliases_map: &RefFilterAliasesMap,
+) -> RefFilterParseResult<RefFilterExpression> {
+    let no_arguments = |expression| -> RefFilterParseResult<_> {
+        function.expect_no_arguments()?;
+        Ok(expression)
+    };
+    let pattern = || -> RefFilterParseResult<_> {
+        let [arg] = function.expect_exact_arguments()?;
+        Ok(resolve_string_expression(arg)?.to_matcher())
+    };
+    match function.name {
+        "all" => no_arguments(RefFilterExpression::All),
+        "none" => no_arguments(RefFilterExpression::None),
+        "local" => no_arguments(RefFilterExpression::Local),
+        "deleted" => no_arguments(RefFilterExpression::Deleted),
+        "tracked" => no_arguments(RefFilterExpression::Tracked),
+        "conflicted" => no_arguments(RefFilterExpression::Conflicted),
+        "name" => Ok(RefFilterExpression::Name(pattern()?)),
+        "remote" => Ok(RefFilterExpression::Remote(pattern()?)),
+        _ => {
+            let candidates = collect_similar(
+                function.name,
+                BUILTIN_FUNCTION_NAMES
+                    .iter()
+                    .copied()
+                    .chain(aliases_map.function_names()),
+            );
+            Err(RefFilterParseError::new(
+                RefFilterParseErrorKind::NoSuchFunction {
+                    name: function.name.to_owned(),
+                    candidates,
+                },
+                function.name_span,
+            ))
+        }
+    }
+}
+
+/// Resolves the argument of `name()` or `remote()`. Patterns without a kind
+/// are globs.
+fn resolve_string_expression(node: &ExpressionNode) -> RefFilterParseResult<StringExpression> {
+    catch_aliases(node, |node| {
+        let pattern_error = |err| {
+            RefFilterParseError::expression("Invalid string pattern", node.span).with_source(err)
+        };
+        match &node.kind {
+            ExpressionKind::Identifier(value) => StringPattern::glob(value)
+                .map(StringExpression::pattern)
+                .map_err(pattern_error),
+            ExpressionKind::String(value) => StringPattern::glob(value)
+                .map(StringExpression::pattern)
+                .map_err(pattern_error),
+            ExpressionKind::Pattern(pattern) => {
+                let value = catch_aliases(&pattern.value, |node| match &node.kind {
+                    ExpressionKind::Identifier(value) => Ok(*value),
+                    ExpressionKind::String(value) => Ok(value.as_str()),
+                    _ => Err(RefFilterParseError::expression(
+                        "Expected string",
+                        node.span,
+                    )),
+                })?;
+                StringPattern::from_str_kind(value, pattern.name)
+                    .map(StringExpression::pattern)
+                    .map_err(pattern_error)
+            }
+            ExpressionKind::Unary(UnaryOp::Negate, arg_node) => {
+                Ok(resolve_string_expression(arg_node)?.negated())
+            }
+            ExpressionKind::Binary(op, lhs_node, rhs_node) => {
+                let lhs = resolve_string_expression(lhs_node)?;
+                let rhs = resolve_string_expression(rhs_node)?;
+                match op {
+                    BinaryOp::Union => Ok(lhs.union(rhs)),
+                    BinaryOp::Intersection => Ok(lhs.intersection(rhs)),
+                    BinaryOp::Difference => Ok(lhs.intersection(rhs.negated())),
+                }
+            }
+            ExpressionKind::FunctionCall(_) => Err(RefFilterParseError::expression(
+                "Expected string pattern",
+                node.span,
+            )),
+            ExpressionKind::AliasExpanded(..) => unreachable!(),
+        }
+    })
+}
+
+/// Applies the given function to the innermost `node` by unwrapping alias
+/// expansion nodes. Appends alias expansion stack to error.
+fn catch_aliases<'a, 'i, T>(
+    mut node: &'a ExpressionNode<'i>,
+    f: impl FnOnce(&'a ExpressionNode<'i>) -> RefFilterParseResult<T>,
+) -> RefFilterParseResult<T> {
+    let mut stack = Vec::new();
+    while let ExpressionKind::AliasExpanded(id, subst) = &node.kind {
+        stack.push((*id, node.span));
+        node = subst;
+    }
+    f(node).map_err(|err| {
+        stack
+            .iter()
+            .rfold(err, |err, &(id, span)| err.within_alias_expansion(id, span))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::error::Error as _;
+
+    use assert_matches::assert_matches;
+    use jj_lib::backend::CommitId;
+    use jj_lib::op_store::RefTarget;
+    use jj_lib::op_store::RemoteRef;
+    use jj_lib::op_store::RemoteRefState;
+
+    use super::*;
+
+    fn remote_ref(target: &RefTarget, state: RemoteRefState) -> RemoteRef {
+        RemoteRef {
+            target: target.clone(),
+            state,
+        }
+    }
+
+    #[test]
+    fn test_matches() {
+        let target = RefTarget::normal(CommitId::from_hex("111111"));
+        let absent = RefTarget::absent();
+        let conflict = RefTarget::from_legacy_form(
+            [],
+            [CommitId::from_hex("111111"), CommitId::from_hex("222222")],
+        );
+        let git = RemoteName::new("git");
+        let origin = RemoteName::new("origin");
+        let ci = RemoteName::new("ci");
+        let tracked = remote_ref(&target, RemoteRefState::Tracked);
+        let untracked = remote_ref(&target, RemoteRefState::New);
+
+        let local_only = LocalRemoteRefTarget {
+            local_target: &target,
+            remote_refs: vec![(git, &tracked)],
+        };
+        let tracking = LocalRemoteRefTarget {
+            local_target: &target,
+            remote_refs: vec![(git, &tracked), (origin, &tracked)],
+        };
+        let deleted = LocalRemoteRefTarget {
+            local_target: &absent,
+            remote_refs: vec![(origin, &tracked)],
+        };
+        let conflicted = LocalRemoteRefTarget {
+            local_target: &conflict,
+            remote_refs: vec![],
+        };
+        let remote_only = LocalRemoteRefTarget {
+            local_target: &absent,
+            remote_refs: vec![(ci, &untracked)],
+        };
+        let all_targets = [
+            ("local-only", &local_only),
+            ("tracking", &tracking),
+            ("deleted", &deleted),
+            ("conflicted", &conflicted),
+            ("ci/build", &remote_only),
+        ];
+        let aliases_map = RefFilterAliasesMap::new();
+        let select = |text: &str| {
+            let expression = parse(text, &aliases_map).unwrap();
+            all_targets
+                .iter()
+                .filter(|(name, targets)| {
+                    expression.matches(RefName::new(name), targets, Some(git))
+                })
+                .map(|(name, _)| *name)
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(select("all()"), all_targets.map(|(name, _)| name));
+        assert_eq!(select("none()"), Vec::<&str>::new());
+        assert_eq!(select("local()"), ["local-only", "tracking", "conflicted"]);
+        assert_eq!(select("deleted()"), ["deleted"]);
+        assert_eq!(select("tracked()"), ["tracking", "deleted"]);
+        assert_eq!(select("conflicted()"), ["conflicted"]);
+        assert_eq!(select("name(ci/*)"), ["ci/build"]);
+        assert_eq!(select("remote(origin)"), ["tracking", "deleted"]);
+        assert_eq!(select(r#"remote(exact:"ci")"#), ["ci/build"]);
+        assert_eq!(
+            select("~remote(ci)"),
+            ["local-only", "tracking", "deleted", "conflicted"]
+        );
+        assert_eq!(select("local() ~ tracked()"), ["local-only", "conflicted"]);
+        assert_eq!(select("tracked() & local()"), ["tracking"]);
+        assert_eq!(
+            select("deleted() | conflicted()"),
+            ["deleted", "conflicted"]
+        );
+        assert_eq!(
+            select(" ~ ( name( 'ci/*' ) | deleted() ) & ~conflicted() "),
+            ["local-only", "tracking"]
+        );
+        // `&` binds tighter than `|`
+        assert_eq!(
+            select("conflicted() | tracked() & local()"),
+            ["tracking", "conflicted"]
+        );
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let aliases_map = RefFilterAliasesMap::new();
+        let parse_err = |text| parse(text, &aliases_map).err().unwrap().kind().clone();
+        assert_eq!(parse_err(""), RefFilterParseErrorKind::SyntaxError);
+        assert_eq!(
+            parse_err("tracked() |"),
+            RefFilterParseErrorKind::SyntaxError
+        );
+        assert_eq!(
+            parse_err("tracked())"),
+            RefFilterParseErrorKind::SyntaxError
+        );
+        assert_eq!(
+            parse_err("(tracked()"),
+            RefFilterParseErrorKind::SyntaxError
+        );
+        assert_eq!(
+            parse_err(r#"remote("ci)"#),
+            RefFilterParseErrorKind::SyntaxError
+        );
+        assert_eq!(
+            parse_err("tracked"),
+            RefFilterParseErrorKind::Expression(
+                "Expected function call, but is string pattern".to_owned()
+            )
+        );
+        assert_eq!(
+            parse_err("trackd()"),
+            RefFilterParseErrorKind::NoSuchFunction {
+                name: "trackd".to_owned(),
+                candidates: vec!["tracked".to_owned()],
+            }
+        );
+        assert_eq!(
+            parse_err("tracked(x)"),
+            RefFilterParseErrorKind::InvalidArguments {
+                name: "tracked".to_owned(),
+                message: "Expected 0 arguments".to_owned(),
+            }
+        );
+        assert_eq!(
+            parse_err("remote()"),
+            RefFilterParseErrorKind::InvalidArguments {
+                name: "remote".to_owned(),
+                message: "Expected 1 arguments".to_owned(),
+            }
+        );
+        assert_eq!(
+            parse_err("remote(tracked())"),
+            RefFilterParseErrorKind::Expression("Expected string pattern".to_owned())
+        );
+        assert_eq!(
+            parse_err("remote(foo:bar)"),
+            RefFilterParseErrorKind::Expression("Invalid string pattern".to_owned())
+        );
+    }
+
+    #[test]
+    fn test_parse_aliases() {
+        let mut aliases_map = RefFilterAliasesMap::new();
+        aliases_map.insert("ci", r#"name("ci/*")"#).unwrap();
+        aliases_map
+            .insert("on(x)", "remote(x) & tracked()")
+            .unwrap();
+        aliases_map.insert("loop", "~loop").unwrap();
+        aliases_map.insert("bad(x)", "name(x) | trackd()").unwrap();
+        let parse = |text| parse(text, &aliases_map);
+
+        let target = RefTarget::normal(CommitId::from_hex("111111"));
+        let tracked = remote_ref(&target, RemoteRefState::Tracked);
+        let targets = LocalRemoteRefTarget {
+            local_target: &target,
+            remote_refs: vec![(RemoteName::new("origin"), &tracked)],
+        };
+        let is_match = |text, name| {
+            parse(text)
+                .unwrap()
+                .matches(RefName::new(name), &targets, None)
+        };
+        assert!(is_match("ci", "ci/1"));
+        assert!(!is_match("ci", "main"));
+        assert!(is_match("~ci & on(origin)", "main"));
+        assert!(!is_match("on(upstream)", "main"));
+        // Parameters can be string expressions
+        assert!(is_match("on(exact:upstream | 'orig*')", "main"));
+
+        assert_eq!(
+            *parse("loop").err().unwrap().kind(),
+            RefFilterParseErrorKind::InAliasExpansion("loop".to_owned())
+        );
+        assert_eq!(
+            *parse("on()").err().unwrap().kind(),
+            RefFilterParseErrorKind::InvalidArguments {
+                name: "on".to_owned(),
+                message: "Expected 1 arguments".to_owned(),
+            }
+        );
+        // Errors in the alias definition are reported with the alias trace
+        let err = parse("bad(x)").err().unwrap();
+        assert_eq!(
+            *err.kind(),
+            RefFilterParseErrorKind::InAliasExpansion("bad(x)".to_owned())
+        );
+        assert_matches!(
+            err.source().unwrap().downcast_ref::<RefFilterParseError>().unwrap().kind(),
+            RefFilterParseErrorKind::NoSuchFunction { name, .. } if name == "trackd"
+        );
+        assert_matches!(
+            RefFilterAliasParser
+                .parse_declaration("f(x, x)")
+                .unwrap_err()
+                .kind(),
+            RefFilterParseErrorKind::RedefinedFunctionParameter
+        );
+    }
+}
@@ -0,0 +1,330 @@
+// Copyright 2026 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Per-command memory and wall-time limits.
+//!
+//! The command checks the limits with [`check_limits()`] after snapshotting the
+//! working copy, before committing a transaction, and when it finishes, so an
+//! aborted command leaves the repository as it was after the last completed
+//! operation. In between, a watchdog thread records the first exceeded limit,
+//! so peaks of memory usage aren't missed.
+//!
+//! Memory usage can only be measured if the binary installs
+//! [`AccountingAllocator`] as its global allocator, which `jj` does. The
+//! allocations are only counted once [`enable_memory_accounting()`] is called.
+
+use std::alloc::GlobalAlloc;
+use std::alloc::Layout;
+use std::alloc::System;
+use std::sync::Mutex;
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::AtomicIsize;
+use std::sync::atomic::Ordering;
+use std::sync::mpsc;
+use std::sync::mpsc::RecvTimeoutError;
+use std::thread;
+use std::thread::JoinHandle;
+use std::time::Duration;
+use std::time::Instant;
+
+use jj_lib::config::ConfigGetError;
+use jj_lib::config::ConfigValue;
+use jj_lib::settings::HumanByteSize;
+use jj_lib::settings::UserSettings;
+
+use crate::command_error::CommandError;
+use crate::command_error::user_error;
+
+/// How often the watchdog checks the resource usage.
+const POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+static ACCOUNTING_ENABLED: AtomicBool = AtomicBool::new(false);
+static ALLOCATOR_INSTALLED: AtomicBool = AtomicBool::new(false);
+// Memory allocated before the accounting was enabled may be freed later, so
+// the counter can become negative.
+static ALLOCATED_BYTES: AtomicIsize = AtomicIsize::new(0);
+
+/// The command whose resource usage is monitored by [`ResourceWatchdog`].
+static WATCHED_COMMAND: Mutex<Option<WatchedCommand>> = Mutex::new(None);
+/// The first limit exceeded by the running command.
+static EXCEEDED_LIMIT: Mutex<Option<ExceededLimit>> = Mutex::new(None);
+
+#[derive(Debug)]
+struct WatchedCommand {
+    limits: ResourceLimits,
+    command_name: String,
+    start_time: Instant,
+}
+
+impl WatchedCommand {
+    fn find_exceeded_limit(&self) -> Option<ExceededLimit> {
+        let elapsed = self.start_time.elapsed();
+        if let Some(max) = self.limits.max_wall_time
+            && elapsed > max
+        {
+            Some(ExceededLimit {
+                command_name: self.command_name.clone(),
+                message: format!("Command exceeded the wall-time limit of {}s", max.as_secs()),
+                hint: "Set `limits.max-wall-time` to allow the command to run longer.",
+            })
+        } else if let Some(max) = self.limits.max_memory
+            && let Some(allocated) = allocated_bytes()
+            && allocated as u64 > max
+        {
+            Some(ExceededLimit {
+                command_name: self.command_name.clone(),
+                message: format!(
+                    "Command exceeded the memory limit of {} (allocated {} after {:.1}s)",
+                    HumanByteSize(max),
+                    HumanByteSize(allocated as u64),
+                    elapsed.as_secs_f32()
+                ),
+                hint: "Set `limits.max-memory` to allow the command to use more memory.",
+            })
+        } else {
+            None
+        }
+    }
+}
+
+#[derive(Debug)]
+struct ExceededLimit {
+    command_name: String,
+    message: String,
+    hint: &'static str,
+}
+
+/// Global allocator which keeps track of the number of allocated bytes.
+///
+/// Install it in the binary to make `limits.max-memory` effective:
+///
+/// ```ignore
+/// #[global_allocator]
+/// static ALLOCATOR: AccountingAllocator = AccountingAllocator;
+/// ```
+///
+/// Until [`enable_memory_accounting()`] is called, the allocations are only
+/// delegated to the system allocator.
+pub struct AccountingAllocator;
+
+impl AccountingAllocator {
+    fn record(size: usize, sign: isize) {
+        if ACCOUNTING_ENABLED.load(Ordering::Relaxed) {
+            ALLOCATED_BYTES.fetch_add(sign * size as isize, Ordering::Relaxed);
+            if !ALLOCATOR_INSTALLED.load(Ordering::Relaxed) {
+                ALLOCATOR_INSTALLED.store(true, Ordering::Relaxed);
+            }
+        }
+    }
+}
+
+// SAFETY: All allocations are delegated to the system allocator.
+unsafe impl GlobalAlloc for AccountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = unsafe { System.alloc(layout) };
+        if !ptr.is_null() {
+            Self::record(layout.size(), 1);
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = unsafe { System.alloc_zeroed(layout) };
+        if !ptr.is_null() {
+            Self::record(layout.size(), 1);
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        unsafe { System.dealloc(ptr, layout) };
+        Self::record(layout.size(), -1);
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = unsafe { System.realloc(ptr, layout, new_size) };
+        if !new_ptr.is_null() {
+            Self::record(new_size, 1);
+            Self::record(layout.size(), -1);
+        }
+        new_ptr
+    }
+}
+
+/// Starts counting the allocations made through [`AccountingAllocator`].
+/// Returns false if it isn't installed as the global allocator.
+pub fn enable_memory_accounting() -> bool {
+    ACCOUNTING_ENABLED.store(true, Ordering::Relaxed);
+    // Any allocation made through the allocator marks it as installed.
+    drop(std::hint::black_box(Box::new(0_u64)));
+    ALLOCATOR_INSTALLED.load(Ordering::Relaxed)
+}
+
+/// Returns the number of bytes allocated through [`AccountingAllocator`] since
+/// the accounting was enabled, or `None` if it isn't enabled.
+pub fn allocated_bytes() -> Option<usize> {
+    ALLOCATOR_INSTALLED
+        .load(Ordering::Relaxed)
+        .then(|| ALLOCATED_BYTES.load(Ordering::Relaxed).max(0) as usize)
+}
+
+/// Returns an error if the command has exceeded one of the limits, either now
+/// or when the watchdog last checked.
+pub fn check_limits() -> Result<(), CommandError> {
+    let mut exceeded = EXCEEDED_LIMIT.lock().unwrap();
+    if exceeded.is_none()
+        && let Some(watched) = WATCHED_COMMAND.lock().unwrap().as_ref()
+    {
+        *exceeded = watched.find_exceeded_limit();
+    }
+    let Some(limit) = exceeded.as_ref() else {
+        return Ok(());
+    };
+    Err(user_error(limit.message.clone())
+        .hinted(format!(
+            "`jj {}` was aborted. Operations completed before that were kept; use `jj op log` to \
+             inspect them.",
+            limit.command_name
+        ))
+        .hinted(limit.hint))
+}
+
+/// Resource limits configured by the `limits.*` settings.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct ResourceLimits {
+    /// Maximum number of allocated bytes.
+    pub max_memory: Option<u64>,
+    /// Maximum duration of the command.
+    pub max_wall_time: Option<Duration>,
+}
+
+impl ResourceLimits {
+    /// Loads the limits from settings. A limit of 0 disables the check.
+    pub fn from_settings(settings: &UserSettings) -> Result<Self, ConfigGetError> {
+        let HumanByteSize(max_memory) =
+            settings.get_value_with("limits.max-memory", TryInto::try_into)?;
+        let max_wall_time = settings.get_value_with("limits.max-wall-time", parse_wall_time)?;
+        Ok(Self {
+            max_memory: (max_memory != 0).then_some(max_memory),
+            max_wall_time: (!max_wall_time.is_zero()).then_some(max_wall_time),
+        })
+    }
+
+    /// Returns true if any limit is configured.
+    pub fn is_enabled(&self) -> bool {
+        self.max_memory.is_some() || self.max_wall_time.is_some()
+    }
+}
+
+/// Parses a duration specified as a number of seconds, or as a string with
+/// one of the `s`, `m`, or `h` suffixes.
+fn parse_wall_time(value: ConfigValue) -> Result<Duration, &'static str> {
+    if let Some(n) = value.as_integer() {
+        let secs = u64::try_from(n).map_err(|_| "Integer out of range")?;
+        return Ok(Duration::from_secs(secs));
+    }
+    let s = value
+        .as_str()
+        .ok_or("Expected a number of seconds or a duration string")?
+        .trim();
+    let (number, unit_secs) = if let Some(number) = s.strip_suffix('h') {
+        (number, 3600)
+    } else if let Some(number) = s.strip_suffix('m') {
+        (number, 60)
+    } else if let Some(number) = s.strip_suffix('s') {
+        (number, 1)
+    } else {
+        (s, 1)
+    };
+    let number: u64 = number
+        .trim()
+        .parse()
+        .map_err(|_| "Invalid duration, expected e.g. \"90s\", \"10m\", or \"1h\"")?;
+    number
+        .checked_mul(unit_secs)
+        .map(Duration::from_secs)
+        .ok_or("Duration out of range")
+}
+
+/// Background thread monitoring [`ResourceLimits`] until dropped.
+#[derive(Debug)]
+pub struct ResourceWatchdog {
+    stop_sender: Option<mpsc::Sender<()>>,
+    handle: Option<JoinHandle<()>>,
+}
+
+impl ResourceWatchdog {
+    /// Starts watching the resource usage of `command_name`. Returns `None` if
+    /// no limit is configured.
+    pub fn start(limits: ResourceLimits, command_name: String) -> Option<Self> {
+        if !limits.is_enabled() {
+            return None;
+        }
+        *WATCHED_COMMAND.lock().unwrap() = Some(WatchedCommand {
+            limits,
+            command_name,
+            start_time: Instant::now(),
+        });
+        let (stop_sender, stop_receiver) = mpsc::channel();
+        let handle = thread::spawn(move || {
+            loop {
+                match stop_receiver.recv_timeout(POLL_INTERVAL) {
+                    Err(RecvTimeoutError::Timeout) => {}
+                    Ok(()) | Err(RecvTimeoutError::Disconnected) => return,
+                }
+                let exceeded = WATCHED_COMMAND
+                    .lock()
+                    .unwrap()
+                    .as_ref()
+                    .and_then(WatchedCommand::find_exceeded_limit);
+                if let Some(limit) = exceeded {
+                    EXCEEDED_LIMIT.lock().unwrap().get_or_insert(limit);
+                    return;
+                }
+            }
+        });
+        Some(Self {
+            stop_sender: Some(stop_sender),
+            handle: Some(handle),
+        })
+    }
+}
+
+impl Drop for ResourceWatchdog {
+    fn drop(&mut self) {
+        drop(self.stop_sender.take());
+        if let Some(handle) = self.handle.take() {
+            handle.join().ok();
+        }
+        WATCHED_COMMAND.lock().unwrap().take();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_wall_time() {
+        let parse = parse_wall_time;
+        assert_eq!(parse(90_i64.into()), Ok(Duration::from_secs(90)));
+        assert_eq!(parse("90".into()), Ok(Duration::from_secs(90)));
+        assert_eq!(parse("90s".into()), Ok(Duration::from_secs(90)));
+        assert_eq!(parse("10m".into()), Ok(Duration::from_secs(600)));
+        assert_eq!(parse(" 1h ".into()), Ok(Duration::from_secs(3600)));
+        assert!(parse("1d".into()).is_err());
+        assert!(parse((-1_i64).into()).is_err());
+        assert!(parse(true.into()).is_err());
+    }
+}
@@ -25,6 +25,7 @@ use jj_lib::config::ConfigNamePathBuf;
 use jj_lib::config::ConfigSource;
 use jj_lib::config::StackedConfig;
 use jj_lib::id_prefix::IdPrefixContext;
+use jj_lib::object_id::HexPrefix;
 use jj_lib::ref_name::RefNameBuf;
 use jj_lib::ref_name::RemoteName;
 use jj_lib::ref_name::RemoteNameBuf;
@@ -141,6 +142,30 @@ impl<'repo> RevsetExpressionEvaluator<'repo> {
     > {
         Ok(self.evaluate()?.iter().commits(self.repo.store()))
     }
+
+    /// Returns the visible commits matching the ambiguous commit or change ID
+    /// prefix reported by `err`.
+    ///
+    /// Returns `None` if `err` isn't about an ambiguous prefix, or if the
+    /// candidates couldn't be looked up.
+    pub fn ambiguous_prefix_candidates(
+        &self,
+        err: &RevsetResolutionError,
+    ) -> Option<Vec<CommitId>> {
+        let index = self.id_prefix_context.populate(self.repo).ok()?;
+        let candidates = match err {
+            RevsetResolutionError::AmbiguousCommitIdPrefix(hex) => {
+                let prefix = HexPrefix::try_from_hex(hex)?;
+                index.commit_prefix_candidates(self.repo, &prefix)
+            }
+            RevsetResolutionError::AmbiguousChangeIdPrefix(reverse_hex) => {
+                let prefix = HexPrefix::try_from_reverse_hex(reverse_hex)?;
+                index.change_prefix_candidates(self.repo, &prefix)
+            }
+            _ => return None,
+        };
+        candidates.ok()
+    }
 }
 
 pub(super) fn warn_user_redefined_builtin(
@@ -222,7 +247,16 @@ pub(super) fn evaluate_revset_to_single_commit<'a>(
     expression: &RevsetExpressionEvaluator<'_>,
     commit_summary_template: impl FnOnce() -> TemplateRenderer<'a, Commit>,
 ) -> Result<Commit, CommandError> {
-    let mut iter = expression.evaluate_to_commits()?.fuse();
+    let mut iter = match expression.evaluate_to_commits() {
+        Ok(iter) => iter.fuse(),
+        Err(err) => {
+            return Err(format_evaluation_error(
+                err,
+                expression,
+                commit_summary_template,
+            ));
+        }
+    };
     match (iter.next(), iter.next()) {
         (Some(commit), None) => Ok(commit?),
         (None, _) => Err(user_error(format!(
@@ -273,6 +307,54 @@ fn format_multiple_revisions_error(
     cmd_err
 }
 
+/// Converts revset evaluation error to command error. If the error is caused by
+/// an ambiguous commit or change ID prefix, the matching revisions are listed
+/// in hint.
+pub(super) fn format_evaluation_error<'a>(
+    err: UserRevsetEvaluationError,
+    expression: &RevsetExpressionEvaluator<'_>,
+    commit_summary_template: impl FnOnce() -> TemplateRenderer<'a, Commit>,
+) -> CommandError {
+    let (prefix, candidates) = match &err {
+        UserRevsetEvaluationError::Resolution(
+            resolution_err @ (RevsetResolutionError::AmbiguousCommitIdPrefix(prefix)
+            | RevsetResolutionError::AmbiguousChangeIdPrefix(prefix)),
+        ) => (
+            prefix.clone(),
+            expression.ambiguous_prefix_candidates(resolution_err),
+        ),
+        _ => return err.into(),
+    };
+    let mut cmd_err = CommandError::from(err);
+    let Some(candidates) = candidates.filter(|ids| ids.len() >= 2) else {
+        return cmd_err;
+    };
+    let store = expression.repo.store();
+    let Ok(commits) = candidates
+        .iter()
+        .take(5)
+        .map(|id| store.get_commit(id))
+        .collect::<Result<Vec<_>, _>>()
+    else {
+        return cmd_err;
+    };
+    let elided = candidates.len() > commits.len();
+    let template = commit_summary_template();
+    cmd_err.add_formatted_hint_with(|formatter| {
+        writeln!(formatter, "The prefix `{prefix}` matches these revisions:")?;
+        for commit in &commits {
+            write!(formatter, "  ")?;
+            template.format(commit, formatter)?;
+            writeln!(formatter)?;
+        }
+        if elided {
+            writeln!(formatter, "  ...")?;
+        }
+        Ok(())
+    });
+    cmd_err
+}
+
 #[derive(Debug, Error)]
 #[error("Failed to parse bookmark name: {}", source.kind())]
 pub struct BookmarkNameParseError {
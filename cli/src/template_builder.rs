@@ -1001,6 +1001,17 @@ fn builtin_string_methods<'a, L: TemplateLanguage<'a> + ?Sized>()
             Ok(out_property.into_dyn_wrapped())
         },
     );
+    map.insert(
+        "matches",
+        |_language, _diagnostics, _build_ctx, self_property, function| {
+            let [needle_node] = function.expect_exact_arguments()?;
+            let needle = template_parser::expect_string_pattern(needle_node)?;
+            let regex = needle.to_regex();
+            let out_property =
+                self_property.map(move |haystack| regex.is_match(haystack.as_bytes()));
+            Ok(out_property.into_dyn_wrapped())
+        },
+    );
     map.insert(
         "starts_with",
         |language, diagnostics, build_ctx, self_property, function| {
@@ -1997,6 +2008,24 @@ fn builtin_functions<'a, L: TemplateLanguage<'a> + ?Sized>() -> TemplateBuildFun
             content, labels,
         ))))
     });
+    map.insert("label_if", |language, diagnostics, build_ctx, function| {
+        let [condition_node, label_node, content_node] = function.expect_exact_arguments()?;
+        let condition =
+            expect_boolean_expression(language, diagnostics, build_ctx, condition_node)?;
+        let label_property =
+            expect_stringify_expression(language, diagnostics, build_ctx, label_node)?;
+        let content = expect_template_expression(language, diagnostics, build_ctx, content_node)?;
+        let labels = (condition, label_property).map(|(condition, s)| {
+            if condition {
+                s.split_whitespace().map(ToString::to_string).collect()
+            } else {
+                vec![]
+            }
+        });
+        Ok(L::Property::wrap_template(Box::new(LabelTemplate::new(
+            content, labels,
+        ))))
+    });
     map.insert(
         "raw_escape_sequence",
         |language, diagnostics, build_ctx, function| {
@@ -2104,6 +2133,34 @@ fn builtin_functions<'a, L: TemplateLanguage<'a> + ?Sized>() -> TemplateBuildFun
         });
         Ok(L::Property::wrap_template(Box::new(template)))
     });
+    map.insert(
+        "surround_if",
+        |language, diagnostics, build_ctx, function| {
+            let [condition_node, prefix_node, suffix_node, content_node] =
+                function.expect_exact_arguments()?;
+            let condition =
+                expect_boolean_expression(language, diagnostics, build_ctx, condition_node)?;
+            let prefix = expect_template_expression(language, diagnostics, build_ctx, prefix_node)?;
+            let suffix = expect_template_expression(language, diagnostics, build_ctx, suffix_node)?;
+            let content =
+                expect_template_expression(language, diagnostics, build_ctx, content_node)?;
+            let template = ReformatTemplate::new(content, move |formatter, recorded| {
+                let surround = match condition.extract() {
+                    Ok(condition) => condition && !recorded.data().is_empty(),
+                    Err(err) => return formatter.handle_error(err),
+                };
+                if surround {
+                    prefix.format(formatter)?;
+                }
+                recorded.replay(formatter.as_mut())?;
+                if surround {
+                    suffix.format(formatter)?;
+                }
+                Ok(())
+            });
+            Ok(L::Property::wrap_template(Box::new(template)))
+        },
+    );
     map.insert("config", |language, diagnostics, _build_ctx, function| {
         // Dynamic lookup can be implemented if needed. The name is literal
         // string for now so the error can be reported early.
@@ -3573,6 +3630,12 @@ mod tests {
         insta::assert_snapshot!(env.render_ok(r#""hello".match(substring:"he")"#), @"he");
         insta::assert_snapshot!(env.render_ok(r#""hello".match(exact:"he")"#), @"");
 
+        insta::assert_snapshot!(env.render_ok(r#""fooo".matches(regex:'^[a-f]o+$')"#), @"true");
+        insta::assert_snapshot!(env.render_ok(r#""fa".matches(regex:'[a-f]o+')"#), @"false");
+        insta::assert_snapshot!(env.render_ok(r#""HEllo".matches(glob-i:"h*o")"#), @"true");
+        insta::assert_snapshot!(env.render_ok(r#""hello".matches("ell")"#), @"true");
+        insta::assert_snapshot!(env.render_ok(r#""hello".matches(exact:"he")"#), @"false");
+
         // Evil regexes can cause invalid UTF-8 output, which nothing can
         // really be done about given we're matching against non-UTF-8 stuff a
         // lot as well.
@@ -4261,6 +4324,25 @@ mod tests {
             @"[38;5;1mtext[39m");
     }
 
+    #[test]
+    fn test_label_if_function() {
+        let mut env = TestTemplateEnv::new();
+        env.add_keyword("empty", || literal(true));
+        env.add_color("error", crossterm::style::Color::DarkRed);
+
+        insta::assert_snapshot!(
+            env.render_ok(r#"label_if(empty, "error", "text")"#),
+            @"[38;5;1mtext[39m");
+        insta::assert_snapshot!(
+            env.render_ok(r#"label_if(!empty, "error", "text")"#),
+            @"text");
+
+        // Property evaluation error
+        insta::assert_snapshot!(
+            env.render_ok("label_if(empty, fill(-1, 'foo'), 'text')"),
+            @"[38;5;1m<Error: out of range integral type conversion attempted>[39m");
+    }
+
     #[test]
     fn test_raw_escape_sequence_function_strip_labels() {
         let mut env = TestTemplateEnv::new();
@@ -4764,6 +4846,18 @@ mod tests {
             @"");
     }
 
+    #[test]
+    fn test_surround_if_function() {
+        let mut env = TestTemplateEnv::new();
+        env.add_keyword("yes", || literal(true));
+        env.add_keyword("no", || literal(false));
+
+        insta::assert_snapshot!(env.render_ok(r#"surround_if(yes, "{", "}", "a")"#), @"{a}");
+        insta::assert_snapshot!(env.render_ok(r#"surround_if(no, "{", "}", "a")"#), @"a");
+        insta::assert_snapshot!(env.render_ok(r#"surround_if(yes, "{", "}", "")"#), @"");
+        insta::assert_snapshot!(env.render_ok(r#"surround_if(no, "{", "}", "")"#), @"");
+    }
+
     #[test]
     fn test_config_function() {
         use jj_lib::config::ConfigLayer;
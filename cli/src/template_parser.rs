@@ -52,7 +52,7 @@ const STRING_LITERAL_PARSER: StringLiteralParser<Rule> = StringLiteralParser {
     escape_rule: Rule::string_escape,
 };
 const FUNCTION_CALL_PARSER: FunctionCallParser<Rule> = FunctionCallParser {
-    function_name_rule: Rule::identifier,
+    function_name_rule: Rule::function_name,
     function_arguments_rule: Rule::function_arguments,
     keyword_argument_rule: Rule::keyword_argument,
     argument_name_rule: Rule::identifier,
@@ -92,6 +92,7 @@ impl Rule {
             Self::prefix_ops => None,
             Self::infix_ops => None,
             Self::function => None,
+            Self::function_name => None,
             Self::keyword_argument => None,
             Self::argument => None,
             Self::function_arguments => None,
@@ -426,7 +427,7 @@ pub struct LambdaNode<'i> {
 fn parse_identifier_or_literal(pair: Pair<Rule>) -> ExpressionKind {
     assert!(matches!(
         pair.as_rule(),
-        Rule::identifier | Rule::pattern_identifier
+        Rule::identifier | Rule::pattern_identifier | Rule::function_name
     ));
     match pair.as_str() {
         "false" => ExpressionKind::Boolean(false),
@@ -1668,4 +1669,33 @@ mod tests {
             TemplateParseErrorKind::InAliasExpansion("F(x)".to_owned())
         );
     }
+
+    #[test]
+    fn test_expand_builtin_function_alias() {
+        let mut aliases = with_aliases([("F(x)", "x ++ a"), ("G()", "F(b)")]);
+        aliases.0.insert_builtin_copies();
+        aliases.0.insert("F(x)", "builtin::F(x) ++ c").unwrap();
+
+        assert_eq!(
+            aliases.parse_normalized("F(y)"),
+            parse_normalized("(y ++ a) ++ c"),
+        );
+        assert_eq!(
+            aliases.parse_normalized("builtin::G()"),
+            parse_normalized("(b ++ a) ++ c"),
+        );
+
+        // Builtin aliases can't be declared.
+        assert!(
+            TemplateAliasesMap::new()
+                .insert("builtin::F(x)", "x")
+                .is_err()
+        );
+        // Methods aren't namespaced, but the name is parsed as such.
+        assert_matches!(
+            parse_template("x.builtin::f()").unwrap().kind,
+            ExpressionKind::MethodCall(method) if method.function.name == "builtin::f"
+        );
+        assert!(parse_template("builtin::x").is_err());
+    }
 }
@@ -214,6 +214,8 @@ impl Write for UiStderr<'_> {
 
 pub struct Ui {
     quiet: bool,
+    dry_run: bool,
+    error_format: ErrorFormat,
     pager: PagerConfig,
     progress_indicator: bool,
     formatter_factory: FormatterFactory,
@@ -241,6 +243,25 @@ impl fmt::Display for ColorChoice {
     }
 }
 
+#[derive(Clone, Copy, Debug, Eq, PartialEq, serde::Deserialize, clap::ValueEnum)]
+#[serde(rename_all = "kebab-case")]
+pub enum ErrorFormat {
+    /// Human-readable error message with hints
+    Text,
+    /// JSON object with error code, message, sources, and hints
+    Json,
+}
+
+impl fmt::Display for ErrorFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = match self {
+            Self::Text => "text",
+            Self::Json => "json",
+        };
+        write!(f, "{s}")
+    }
+}
+
 fn prepare_formatter_factory(
     config: &StackedConfig,
     stdout: &Stdout,
@@ -344,6 +365,8 @@ impl Ui {
     pub fn null() -> Self {
         Self {
             quiet: true,
+            dry_run: false,
+            error_format: ErrorFormat::Text,
             pager: PagerConfig::Disabled,
             progress_indicator: false,
             formatter_factory: FormatterFactory::plain_text(),
@@ -355,6 +378,8 @@ impl Ui {
         let formatter_factory = prepare_formatter_factory(config, &io::stdout())?;
         Ok(Self {
             quiet: config.get("ui.quiet")?,
+            dry_run: false,
+            error_format: config.get("ui.error-format")?,
             formatter_factory,
             pager: PagerConfig::from_config(config)?,
             progress_indicator: config.get("ui.progress-indicator")?,
@@ -364,6 +389,7 @@ impl Ui {
 
     pub fn reset(&mut self, config: &StackedConfig) -> Result<(), CommandError> {
         self.quiet = config.get("ui.quiet")?;
+        self.error_format = config.get("ui.error-format")?;
         self.pager = PagerConfig::from_config(config)?;
         self.progress_indicator = config.get("ui.progress-indicator")?;
         self.formatter_factory = prepare_formatter_factory(config, &io::stdout())?;
@@ -418,6 +444,11 @@ impl Ui {
         self.formatter_factory.maybe_color()
     }
 
+    /// Format in which the command error should be printed.
+    pub fn error_format(&self) -> ErrorFormat {
+        self.error_format
+    }
+
     pub fn new_formatter<'output, W: Write + 'output>(
         &self,
         output: W,
@@ -486,9 +517,15 @@ impl Ui {
             .then(ProgressOutput::for_stderr)
     }
 
+    /// Suppresses the status output for `--dry-run`, since it would describe
+    /// changes that aren't made.
+    pub fn set_dry_run(&mut self, dry_run: bool) {
+        self.dry_run = dry_run;
+    }
+
     /// Writer to print an update that's not part of the command's main output.
     pub fn status(&self) -> Box<dyn Write + '_> {
-        if self.quiet {
+        if self.quiet || self.dry_run {
             Box::new(io::sink())
         } else {
             Box::new(self.stderr())
@@ -496,8 +533,15 @@ impl Ui {
     }
 
     /// A formatter to print an update that's not part of the command's main
-    /// output. Returns `None` if `--quiet` was requested.
+    /// output. Returns `None` if `--quiet` or `--dry-run` was requested.
     pub fn status_formatter(&self) -> Option<Box<dyn Formatter + '_>> {
+        (!self.quiet && !self.dry_run).then(|| self.stderr_formatter())
+    }
+
+    /// Like [`Self::status_formatter()`], but also used with `--dry-run`, to
+    /// print the changes the command would make. Returns `None` if `--quiet`
+    /// was requested.
+    pub fn dry_run_formatter(&self) -> Option<Box<dyn Formatter + '_>> {
         (!self.quiet).then(|| self.stderr_formatter())
     }
 
@@ -509,7 +553,7 @@ impl Ui {
     /// Writer to print hint without the "Hint: " heading.
     pub fn hint_no_heading(&self) -> LabeledScope<Box<dyn Formatter + '_>> {
         let formatter = self
-            .status_formatter()
+            .dry_run_formatter()
             .unwrap_or_else(|| Box::new(PlainTextFormatter::new(io::sink())));
         formatter.into_labeled("hint")
     }
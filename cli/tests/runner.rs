@@ -14,9 +14,11 @@ mod test_acls;
 mod test_advance_bookmarks;
 mod test_alias;
 mod test_arrange_command;
+mod test_back_command;
 mod test_bisect_command;
 mod test_bookmark_command;
 mod test_builtin_aliases;
+mod test_change_limits;
 mod test_commit_command;
 mod test_commit_template;
 mod test_completion;
@@ -29,6 +31,7 @@ mod test_debug_init_simple_command;
 mod test_describe_command;
 mod test_diff_command;
 mod test_diffedit_command;
+mod test_draft_command;
 mod test_duplicate_command;
 mod test_edit_command;
 mod test_evolog_command;
@@ -41,6 +44,8 @@ mod test_file_track_untrack_commands;
 mod test_fileset_output;
 mod test_fix_command;
 mod test_generate_md_cli_help;
+mod test_gerrit_download;
+mod test_gerrit_status;
 mod test_gerrit_upload;
 mod test_git_clone;
 mod test_git_colocated;
@@ -54,6 +59,7 @@ mod test_git_remotes;
 mod test_git_root;
 mod test_gitignores;
 mod test_global_opts;
+mod test_heads_command;
 mod test_help_command;
 mod test_identical_commits;
 mod test_immutable_commits;
@@ -66,13 +72,16 @@ mod test_op_integrate_command;
 mod test_op_revert_command;
 mod test_operations;
 mod test_parallelize_command;
+mod test_prompt_command;
 mod test_rebase_command;
 mod test_repo_change_report;
 mod test_resolve_command;
+mod test_resource_limits;
 mod test_restore_command;
 mod test_revert_command;
 mod test_revset_output;
 mod test_root;
+mod test_run_command;
 mod test_show_command;
 mod test_sign_unsign_commands;
 mod test_simplify_parents_command;
@@ -80,6 +89,7 @@ mod test_sparse_command;
 mod test_split_command;
 mod test_squash_command;
 mod test_status_command;
+mod test_sync_status_command;
 mod test_tag_command;
 mod test_templater;
 mod test_undo_redo_commands;
@@ -523,6 +523,49 @@ fn test_abandon_tracking_bookmarks() {
     ");
 }
 
+#[test]
+fn test_abandon_dry_run() {
+    let mut test_env = TestEnvironment::default();
+    // Rewritten commits get the same ids in the preview and the actual run
+    test_env.add_env_var("JJ_TIMESTAMP", "2001-02-03T04:05:06+07:00");
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+
+    create_commit(&work_dir, "a", &[]);
+    create_commit(&work_dir, "b", &["a"]);
+    create_commit(&work_dir, "c", &["b"]);
+    let setup_opid = work_dir.current_operation_id();
+
+    let output = work_dir.run_jj(["abandon", "b", "--dry-run"]);
+    insta::assert_snapshot!(output, @"
+    ------- stderr -------
+    Dry-run requested, discarding the following changes:
+    Rewritten commits:
+      royxmykx c2c4561c c | c
+    Abandoned commits:
+      zsuskuln 791cacb0 b | b
+    Changed bookmarks:
+      b: 791cacb0bcf6 -> (absent)
+      c: 3ea586650c42 -> c2c4561c7f7a
+    [EOF]
+    ");
+    assert_eq!(work_dir.current_operation_id(), setup_opid);
+
+    // The preview matches the actual changes
+    let output = work_dir.run_jj(["abandon", "b"]);
+    insta::assert_snapshot!(output, @"
+    ------- stderr -------
+    Abandoned 1 commits:
+      zsuskuln 791cacb0 b | b
+    Deleted bookmarks: b
+    Rebased 1 descendant commits onto parents of abandoned commits
+    Working copy  (@) now at: royxmykx c2c4561c c | c
+    Parent commit (@-)      : rlvkpnrz 94331adf a | a
+    Added 0 files, modified 0 files, removed 1 files
+    [EOF]
+    ");
+}
+
 #[must_use]
 fn get_log_output(work_dir: &TestWorkDir) -> CommandOutput {
     let template = r#"separate(" ", "[" ++ change_id.short(3) ++ "]", bookmarks)"#;
@@ -12,7 +12,9 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use crate::common::CommandOutput;
 use crate::common::TestEnvironment;
+use crate::common::TestWorkDir;
 use crate::common::create_commit;
 
 #[test]
@@ -41,3 +43,60 @@ fn test_arrange_bad_revisions() {
     [exit status: 1]
     ");
 }
+
+#[test]
+fn test_arrange_script() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+
+    create_commit(&work_dir, "a", &[]);
+    create_commit(&work_dir, "b", &["a"]);
+    create_commit(&work_dir, "c", &["b"]);
+    create_commit(&work_dir, "d", &["c"]);
+    work_dir.run_jj(["new", "root()"]).success();
+
+    // Move d onto a
+    work_dir.write_file("script", "# Move d onto a\n\nset-parent d a\n");
+    work_dir
+        .run_jj(["arrange", "-r", "a::", "--script", "script"])
+        .success();
+    insta::assert_snapshot!(get_log_output(&work_dir), @"
+    d: a
+    c: b
+    b: a
+    a
+    [EOF]
+    ");
+
+    // Cycles are rejected
+    work_dir.write_file("script", "set-parent b c\n");
+    let output = work_dir.run_jj(["arrange", "-r", "a::", "--script", "script"]);
+    insta::assert_snapshot!(output, @"
+    ------- stderr -------
+    Error: Setting the parents of revision 123b4d91f6e5 would create a cycle
+    Hint: The error is in line 1: set-parent b c
+    [EOF]
+    [exit status: 1]
+    ");
+
+    // The script can be read from stdin
+    let output = work_dir.run_jj_with(|cmd| {
+        cmd.args(["arrange", "-r", "a::", "--script", "-"])
+            .write_stdin("set-parent c a\nfrobnicate c\n")
+    });
+    insta::assert_snapshot!(output, @"
+    ------- stderr -------
+    Error: Invalid arrange command: frobnicate c
+    Hint: The commands are `set-parent REV PARENT...`, `abandon REV`, `squash REV`, and `keep REV`
+    Hint: The error is in line 2: frobnicate c
+    [EOF]
+    [exit status: 1]
+    ");
+}
+
+#[must_use]
+fn get_log_output(work_dir: &TestWorkDir) -> CommandOutput {
+    let template = r#"bookmarks ++ surround(": ", "", parents.map(|c| c.bookmarks())) ++ "\n""#;
+    work_dir.run_jj(["log", "--no-graph", "-r", "a::", "-T", template])
+}
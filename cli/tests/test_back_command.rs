@@ -0,0 +1,88 @@
+// Copyright 2026 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::common::CommandOutput;
+use crate::common::TestEnvironment;
+use crate::common::TestWorkDir;
+
+#[test]
+fn test_back() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+    work_dir.run_jj(["describe", "-m", "base"]).success();
+    work_dir.run_jj(["new", "-m", "home"]).success();
+
+    // Errors out if no home change is recorded
+    let output = work_dir.run_jj(["back"]);
+    insta::assert_snapshot!(output, @"
+    ------- stderr -------
+    Error: No home change is set for this workspace
+    Hint: Use `jj back --set` to record the working-copy change as home
+    [EOF]
+    [exit status: 1]
+    ");
+
+    work_dir.run_jj(["back", "--set"]).success();
+    let output = work_dir.run_jj(["back"]);
+    insta::assert_snapshot!(output, @"
+    ------- stderr -------
+    Already editing the home change
+    [EOF]
+    ");
+
+    // Make an excursion, rewrite the home change, and come back
+    work_dir.run_jj(["edit", "subject(base)"]).success();
+    work_dir
+        .run_jj(["describe", "-r", "subject(home)", "-m", "home 2"])
+        .success();
+    work_dir.run_jj(["back"]).success();
+    insta::assert_snapshot!(get_wc_description(&work_dir), @"
+    home 2
+    [EOF]
+    ");
+
+    // The home change can be set explicitly
+    work_dir
+        .run_jj(["back", "--set", "subject(base)"])
+        .success();
+    work_dir.run_jj(["new", "root()"]).success();
+    work_dir.run_jj(["back"]).success();
+    insta::assert_snapshot!(get_wc_description(&work_dir), @"
+    base
+    [EOF]
+    ");
+
+    // Abandoned home change
+    work_dir
+        .run_jj(["back", "--set", "subject('home 2')"])
+        .success();
+    work_dir.run_jj(["abandon", "subject('home 2')"]).success();
+    let output = work_dir.run_jj(["back"]);
+    insta::assert_snapshot!(output.normalize_stderr_with(|s| {
+        s.lines()
+            .filter(|line| line.starts_with("Hint: Use `jj back"))
+            .flat_map(|line| [line, "\n"])
+            .collect()
+    }), @"
+    ------- stderr -------
+    Hint: Use `jj back --set <REVSET>` to record another change as home
+    [EOF]
+    [exit status: 1]
+    ");
+}
+
+fn get_wc_description(work_dir: &TestWorkDir) -> CommandOutput {
+    work_dir.run_jj(["log", "--no-graph", "-r@", "-Tdescription"])
+}
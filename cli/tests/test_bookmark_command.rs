@@ -12,11 +12,13 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use regex::Regex;
 use testutils::git;
 
 use crate::common::CommandOutput;
 use crate::common::TestEnvironment;
 use crate::common::TestWorkDir;
+use crate::common::force_interactive;
 
 fn create_commit_with_refs(
     repo: &gix::Repository,
@@ -909,6 +911,33 @@ fn test_bookmark_delete_glob() {
     ");
 }
 
+#[test]
+fn test_bookmark_delete_dry_run() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+
+    work_dir
+        .run_jj(["bookmark", "create", "-r@", "foo", "bar"])
+        .success();
+    let setup_opid = work_dir.current_operation_id();
+
+    let output = work_dir.run_jj(["bookmark", "delete", "foo", "--dry-run"]);
+    insta::assert_snapshot!(output, @"
+    ------- stderr -------
+    Dry-run requested, discarding the following changes:
+    Changed bookmarks:
+      foo: e8849ae12c70 -> (absent)
+    [EOF]
+    ");
+    assert_eq!(work_dir.current_operation_id(), setup_opid);
+    insta::assert_snapshot!(get_bookmark_output(&work_dir), @"
+    bar: qpvuntsm e8849ae1 (empty) (no description set)
+    foo: qpvuntsm e8849ae1 (empty) (no description set)
+    [EOF]
+    ");
+}
+
 #[test]
 fn test_bookmark_delete_export() {
     let test_env = TestEnvironment::default();
@@ -1724,6 +1753,112 @@ fn test_bookmark_track_absent() {
     ");
 }
 
+#[test]
+fn test_bookmark_track_all_matching_local() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+
+    // Set up remotes
+    let git_repo1 = {
+        let git_repo_path = test_env.env_root().join("remote1");
+        let git_repo = git::init(git_repo_path);
+        work_dir
+            .run_jj(["git", "remote", "add", "remote1", "../remote1"])
+            .success();
+        git_repo
+    };
+    let git_repo2 = {
+        let git_repo_path = test_env.env_root().join("remote2");
+        let git_repo = git::init(git_repo_path);
+        work_dir
+            .run_jj(["git", "remote", "add", "remote2", "../remote2"])
+            .success();
+        git_repo
+    };
+    create_commit_with_refs(
+        &git_repo1,
+        "commit 1",
+        b"content 1",
+        &["refs/heads/feature1", "refs/heads/feature2"],
+    );
+    create_commit_with_refs(
+        &git_repo2,
+        "commit 2",
+        b"content 2",
+        &["refs/heads/feature1", "refs/heads/feature3"],
+    );
+    test_env.add_config("remotes.remote1.auto-track-bookmarks = '~*'");
+    test_env.add_config("remotes.remote2.auto-track-bookmarks = '~*'");
+    work_dir.run_jj(["git", "fetch", "--all-remotes"]).success();
+    work_dir
+        .run_jj([
+            "bookmark",
+            "create",
+            "-rfeature1@remote1",
+            "feature1",
+            "feature2",
+        ])
+        .success();
+
+    // feature1 is skipped because the remotes disagree, and feature3 has no
+    // local bookmark.
+    let output = work_dir.run_jj(["bookmark", "track", "--all-matching-local"]);
+    insta::assert_snapshot!(output, @"
+    ------- stderr -------
+    Warning: Skipping bookmark feature1 because its remotes point to different commits: remote1, remote2
+    Started tracking 1 remote bookmarks.
+    [EOF]
+    ");
+    let template = r#"name ++ if(remote, "@" ++ remote) ++ if(tracked, " (tracked)") ++ "\n""#;
+    let output = work_dir.run_jj(["bookmark", "list", "--all-remotes", "-T", template]);
+    insta::assert_snapshot!(output, @"
+    feature1
+    feature1@remote1
+    feature1@remote2
+    feature2
+    feature2@remote1 (tracked)
+    feature3@remote2
+    [EOF]
+    ");
+
+    // The remote can be picked explicitly
+    let output = work_dir.run_jj([
+        "bookmark",
+        "track",
+        "--all-matching-local",
+        "--remote=remote1",
+    ]);
+    insta::assert_snapshot!(output, @"
+    ------- stderr -------
+    Started tracking 1 remote bookmarks.
+    [EOF]
+    ");
+
+    // Untracking can also forget the remote bookmark state
+    let output = work_dir.run_jj([
+        "bookmark",
+        "untrack",
+        "feature2",
+        "--remote=remote1",
+        "--delete-remote-ref-state",
+    ]);
+    insta::assert_snapshot!(output, @"
+    ------- stderr -------
+    Stopped tracking 1 remote bookmarks.
+    [EOF]
+    ");
+    let output = work_dir.run_jj(["bookmark", "list", "--all-remotes", "-T", template]);
+    insta::assert_snapshot!(output, @"
+    feature1
+    feature1@remote1 (tracked)
+    feature1@remote2
+    feature2
+    feature3@remote2
+    [EOF]
+    ");
+}
+
 #[test]
 fn test_bookmark_list() {
     let test_env = TestEnvironment::default();
@@ -1824,6 +1959,23 @@ fn test_bookmark_list() {
     [EOF]
     ");
 
+    // Tracking counts can be disabled
+    let output = local_dir.run_jj(["bookmark", "list", "--config=ui.show-tracking-counts=false"]);
+    insta::assert_snapshot!(output, @"
+    absent-tracked: wqnwkozp 0353dd35 (empty) local-only
+      @origin (not created yet)
+    local-only: wqnwkozp 0353dd35 (empty) local-only
+    remote-delete (deleted)
+      @origin: vruxwmqv b32031cf (empty) remote-delete
+    remote-sync: rlvkpnrz 7a07dbee (empty) remote-sync
+    remote-unsync: wqnwkozp 0353dd35 (empty) local-only
+      @origin: zsuskuln 553203ba (empty) remote-unsync
+    [EOF]
+    ------- stderr -------
+    Hint: Bookmarks marked as deleted can be *deleted permanently* on the remote by running `jj git push --deleted`. Use `jj bookmark forget` if you don't want that.
+    [EOF]
+    ");
+
     let output = local_dir.run_jj(["bookmark", "list", "--all-remotes", "--color=always"]);
     insta::assert_snapshot!(output, @"
     [38;5;5mabsent-tracked[39m: [1m[38;5;13mw[38;5;8mqnwkozp[39m [38;5;12m03[38;5;8m53dd35[39m [38;5;10m(empty)[39m local-only[0m
@@ -2169,6 +2321,34 @@ fn test_bookmark_list_filtered() {
     [exit status: 1]
     ");
 
+    // --points-to also matches the targets of tracked remote bookmarks, which
+    // include deleted and locally moved bookmarks.
+    insta::assert_snapshot!(query(&["--points-to=all()"]), @"
+    local-keep: kpqxywon 4b2bc95c (empty) local-keep
+    remote-delete (deleted)
+      @origin: zsuskuln 0e6b7968 (empty) remote-delete
+    remote-keep: rlvkpnrz c2f2ee40 (empty) remote-keep
+    remote-rewrite: royxmykx e6970e0e (empty) rewritten
+      @origin (ahead by 1 commits, behind by 1 commits): royxmykx/1 331d500d (hidden) (empty) remote-rewrite
+    [EOF]
+    ------- stderr -------
+    Hint: Bookmarks marked as deleted can be *deleted permanently* on the remote by running `jj git push --deleted`. Use `jj bookmark forget` if you don't want that.
+    [EOF]
+    ");
+    insta::assert_snapshot!(query(&["--points-to=remote-delete@origin"]), @"
+    remote-delete (deleted)
+      @origin: zsuskuln 0e6b7968 (empty) remote-delete
+    [EOF]
+    ------- stderr -------
+    Hint: Bookmarks marked as deleted can be *deleted permanently* on the remote by running `jj git push --deleted`. Use `jj bookmark forget` if you don't want that.
+    [EOF]
+    ");
+    insta::assert_snapshot!(query(&["--points-to=remote-rewrite@origin"]), @"
+    remote-rewrite: royxmykx e6970e0e (empty) rewritten
+      @origin (ahead by 1 commits, behind by 1 commits): royxmykx/1 331d500d (hidden) (empty) remote-rewrite
+    [EOF]
+    ");
+
     // Name patterns are OR-ed.
     insta::assert_snapshot!(query(&["*-keep", "remote-* & *-delete"]), @"
     local-keep: kpqxywon 4b2bc95c (empty) local-keep
@@ -2561,6 +2741,254 @@ fn test_bookmark_list_conflicted() {
     ");
 }
 
+#[test]
+fn test_bookmark_list_conflict_detail() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+
+    work_dir.run_jj(["new", "root()", "-m", "a"]).success();
+    work_dir.run_jj(["new", "root()", "-m", "b"]).success();
+    work_dir.run_jj(["new", "root()", "-m", "c"]).success();
+    work_dir
+        .run_jj(["bookmark", "create", "foo", "-rsubject(a)"])
+        .success();
+    work_dir
+        .run_jj([
+            "bookmark",
+            "set",
+            "foo",
+            "-rsubject(b)",
+            "--allow-backwards",
+        ])
+        .success();
+    work_dir
+        .run_jj([
+            "bookmark",
+            "set",
+            "foo",
+            "-rsubject(c)",
+            "--allow-backwards",
+            "--at-op=@-",
+        ])
+        .success();
+    work_dir.run_jj(["status"]).success();
+
+    let template = r#"
+        name ++ ":" ++ self.conflict_sides().map(|side|
+          if(side.removed(), " -", " +") ++ side.target().description().first_line()
+        ).join("") ++ "\n"
+    "#;
+    let output = work_dir.run_jj(["bookmark", "list", "--quiet", "-T", template]);
+    insta::assert_snapshot!(output, @"
+    foo: +b +c -a
+    [EOF]
+    ");
+
+    // Each side is shown with the operation which set the bookmark to it
+    let id_re = Regex::new(r"[k-z]{8} [0-9a-f]{8}").unwrap();
+    let op_re = Regex::new(r"operation [0-9a-f]{12}").unwrap();
+    let hash_re = Regex::new(r"[0-9a-f]{40}").unwrap();
+    let output = work_dir
+        .run_jj(["bookmark", "list", "--quiet", "--show-conflict-detail"])
+        .normalize_stdout_with(|s| {
+            let s = id_re.replace_all(&s, "[ID]");
+            let s = op_re.replace_all(&s, "operation [OP]");
+            hash_re.replace_all(&s, "[HASH]").into_owned()
+        });
+    insta::assert_snapshot!(output, @"
+    foo (conflicted):
+      - [ID] (empty) a
+      + [ID] (empty) b
+      + [ID] (empty) c
+      Added target: [ID] foo?? | (empty) b
+        Set by operation [OP]: point bookmark foo to commit [HASH]
+      Added target: [ID] foo?? | (empty) c
+        Set by operation [OP]: point bookmark foo to commit [HASH]
+      Removed target: [ID] (empty) a
+        Set by operation [OP]: create bookmark foo pointing to commit [HASH]
+    [EOF]
+    ");
+}
+
+#[test]
+fn test_bookmark_resolve() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+    let id_re = Regex::new(r"[k-z]{8} [0-9a-f]{8}").unwrap();
+    let normalize = |s: String| id_re.replace_all(&s, "[ID]").into_owned();
+
+    work_dir.run_jj(["new", "root()", "-m", "a"]).success();
+    work_dir.run_jj(["new", "root()", "-m", "b"]).success();
+    work_dir.run_jj(["new", "root()", "-m", "c"]).success();
+    work_dir
+        .run_jj(["bookmark", "create", "foo", "-rsubject(a)"])
+        .success();
+
+    let output = work_dir.run_jj(["bookmark", "resolve", "foo", "--keep=subject(a)"]);
+    insta::assert_snapshot!(output, @"
+    ------- stderr -------
+    Error: Bookmark foo is not conflicted
+    [EOF]
+    [exit status: 1]
+    ");
+
+    work_dir
+        .run_jj([
+            "bookmark",
+            "set",
+            "foo",
+            "-rsubject(b)",
+            "--allow-backwards",
+        ])
+        .success();
+    work_dir
+        .run_jj([
+            "bookmark",
+            "set",
+            "foo",
+            "-rsubject(c)",
+            "--allow-backwards",
+            "--at-op=@-",
+        ])
+        .success();
+    work_dir.run_jj(["status"]).success();
+    let parents_template = r#"parents.map(|c| c.description().first_line()).join(",") ++ "\n""#;
+
+    // The kept revision must be one of the targets
+    let output = work_dir.run_jj(["bookmark", "resolve", "foo", "--keep=subject(a)"]);
+    insta::assert_snapshot!(output, @"
+    ------- stderr -------
+    Error: Revision subject(a) is not a target of bookmark foo
+    Hint: Use `jj bookmark set` to point the bookmark to another revision.
+    [EOF]
+    [exit status: 1]
+    ");
+
+    let output = work_dir
+        .run_jj(["bookmark", "resolve", "foo", "--keep=subject(c)"])
+        .normalize_stderr_with(normalize);
+    insta::assert_snapshot!(output, @"
+    ------- stderr -------
+    Resolved bookmark foo to [ID] foo | (empty) c
+    [EOF]
+    ");
+    let output = work_dir.run_jj(["log", "-rfoo", "--no-graph", "-T", "description"]);
+    insta::assert_snapshot!(output, @"
+    c
+    [EOF]
+    ");
+
+    // Create a merge commit of all targets
+    work_dir.run_jj(["undo"]).success();
+    let output = work_dir
+        .run_jj(["bookmark", "resolve", "foo", "--merge"])
+        .normalize_stderr_with(normalize);
+    insta::assert_snapshot!(output, @"
+    ------- stderr -------
+    Created merge commit [ID] foo | (empty) (no description set)
+    Resolved bookmark foo to [ID] foo | (empty) (no description set)
+    [EOF]
+    ");
+    let output = work_dir.run_jj(["log", "-rfoo", "--no-graph", "-T", parents_template]);
+    insta::assert_snapshot!(output, @"
+    b,c
+    [EOF]
+    ");
+
+    // Choose the target interactively
+    work_dir.run_jj(["undo"]).success();
+    let output = work_dir
+        .run_jj_with(|cmd| {
+            force_interactive(cmd)
+                .args(["bookmark", "resolve", "foo"])
+                .write_stdin("1\n")
+        })
+        .normalize_stderr_with(normalize);
+    insta::assert_snapshot!(output, @"
+    ------- stderr -------
+    Bookmark foo is conflicted, choose the target to keep:
+    1: [ID] foo?? | (empty) b
+    2: [ID] foo?? | (empty) c
+    q: quit the prompt
+    enter the index of the target you want to keep: Resolved bookmark foo to [ID] foo | (empty) b
+    [EOF]
+    ");
+    let output = work_dir.run_jj([
+        "op",
+        "log",
+        "-n1",
+        "--no-graph",
+        "-T",
+        r#"description ++ "\n""#,
+    ]);
+    insta::assert_snapshot!(output.normalize_stdout_with(|s| {
+        Regex::new(r"[0-9a-f]{40}").unwrap().replace_all(&s, "[HASH]").into_owned()
+    }), @"
+    resolve conflicted bookmark foo to commit [HASH]
+    [EOF]
+    ");
+
+    // Fast-forward instead of merging if one target is a descendant of the other
+    work_dir.run_jj(["new", "subject(b)", "-m", "d"]).success();
+    work_dir
+        .run_jj(["bookmark", "create", "bar", "-rsubject(a)"])
+        .success();
+    work_dir
+        .run_jj([
+            "bookmark",
+            "set",
+            "bar",
+            "-rsubject(b)",
+            "--allow-backwards",
+        ])
+        .success();
+    work_dir
+        .run_jj([
+            "bookmark",
+            "set",
+            "bar",
+            "-rsubject(d)",
+            "--allow-backwards",
+            "--at-op=@-",
+        ])
+        .success();
+    work_dir.run_jj(["status"]).success();
+    let output = work_dir
+        .run_jj(["bookmark", "resolve", "bar", "--merge"])
+        .normalize_stderr_with(normalize);
+    insta::assert_snapshot!(output, @"
+    ------- stderr -------
+    Resolved bookmark bar to [ID] bar | (empty) d
+    [EOF]
+    ");
+
+    // A name that is both a remote and a revision is ambiguous
+    work_dir.run_jj(["undo"]).success();
+    work_dir
+        .run_jj(["git", "remote", "add", "origin", "../origin"])
+        .success();
+    work_dir
+        .run_jj(["bookmark", "create", "origin", "-rsubject(d)"])
+        .success();
+    let output = work_dir.run_jj(["bookmark", "resolve", "bar", "--keep=origin"]);
+    insta::assert_snapshot!(output, @"
+    ------- stderr -------
+    Error: origin is both a remote and a revision
+    Hint: Use a commit or change ID to keep the revision, or `--keep bar@origin` to keep the remote bookmark's target.
+    [EOF]
+    [exit status: 1]
+    ");
+    let output = work_dir.run_jj(["bookmark", "resolve", "bar", "--keep=bar@origin"]);
+    insta::assert_snapshot!(output, @"
+    ------- stderr -------
+    Error: No such remote bookmark: bar@origin
+    [EOF]
+    [exit status: 1]
+    ");
+}
+
 #[test]
 fn test_bookmark_list_sort_unknown_key_error() {
     let test_env = TestEnvironment::default();
@@ -2570,7 +2998,7 @@ fn test_bookmark_list_sort_unknown_key_error() {
     insta::assert_snapshot!(work_dir.run_jj(["bookmark", "list", "--sort", "date"]), @"
     ------- stderr -------
     error: invalid value 'date' for '--sort <SORT_KEY>'
-      [possible values: name, name-, author-name, author-name-, author-email, author-email-, author-date, author-date-, committer-name, committer-name-, committer-email, committer-email-, committer-date, committer-date-]
+      [possible values: name, name-, author-name, author-name-, author-email, author-email-, author-date, author-date-, committer-name, committer-name-, committer-email, committer-email-, committer-date, committer-date-, description, description-, target-id, target-id-, divergence, divergence-]
 
     For more information, try '--help'.
     [EOF]
@@ -2673,6 +3101,245 @@ fn test_bookmark_list_sort_overriding_config() {
     ");
 }
 
+#[test]
+fn test_bookmark_list_limit_offset() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+    work_dir
+        .run_jj(["bookmark", "create", "a", "b", "c", "d", "e"])
+        .success();
+
+    let template = r#"name ++ "\n""#;
+    let output = work_dir.run_jj(["bookmark", "list", "-T", template, "--limit=2"]);
+    insta::assert_snapshot!(output, @"
+    a
+    b
+    [EOF]
+    ");
+
+    // The offset and limit are applied after sorting
+    let output = work_dir.run_jj([
+        "bookmark",
+        "list",
+        "-T",
+        template,
+        "--sort=name-",
+        "--offset=1",
+        "--limit=2",
+    ]);
+    insta::assert_snapshot!(output, @"
+    d
+    c
+    [EOF]
+    ");
+
+    let output = work_dir.run_jj(["bookmark", "list", "-T", template, "--offset=3"]);
+    insta::assert_snapshot!(output, @"
+    d
+    e
+    [EOF]
+    ");
+
+    let output = work_dir.run_jj(["bookmark", "list", "-T", template, "--offset=10"]);
+    insta::assert_snapshot!(output, @"");
+}
+
+#[test]
+fn test_bookmark_list_sort_divergence() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "origin"]).success();
+    let origin_dir = test_env.work_dir("origin");
+    let origin_git_repo_path = origin_dir
+        .root()
+        .join(".jj")
+        .join("repo")
+        .join("store")
+        .join("git");
+    for bookmark in ["a", "b", "c"] {
+        origin_dir
+            .run_jj(["new", "root()", "-m", bookmark])
+            .success();
+        origin_dir
+            .run_jj(["bookmark", "create", bookmark])
+            .success();
+    }
+    origin_dir.run_jj(["git", "export"]).success();
+
+    test_env
+        .run_jj_in(
+            ".",
+            [
+                "git",
+                "clone",
+                origin_git_repo_path.to_str().unwrap(),
+                "local",
+            ],
+        )
+        .success();
+    let local_dir = test_env.work_dir("local");
+    // "a" is ahead of its remote by 2 commits, "c" by 1 commit
+    for (bookmark, count) in [("a", 2), ("c", 1)] {
+        local_dir.run_jj(["new", bookmark]).success();
+        for _ in 1..count {
+            local_dir.run_jj(["new"]).success();
+        }
+        local_dir
+            .run_jj(["bookmark", "set", bookmark, "-r@"])
+            .success();
+    }
+
+    let template = r#"name ++ "\n""#;
+    let output = local_dir.run_jj(["bookmark", "list", "-T", template, "--sort=divergence-"]);
+    insta::assert_snapshot!(output, @"
+    a
+    c
+    b
+    [EOF]
+    ");
+
+    // Ties are broken by name
+    let output = local_dir.run_jj(["bookmark", "list", "-T", template, "--sort=divergence"]);
+    insta::assert_snapshot!(output, @"
+    b
+    c
+    a
+    [EOF]
+    ");
+}
+
+#[test]
+fn test_bookmark_list_prefix_and_collapse() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+    work_dir
+        .run_jj([
+            "bookmark",
+            "create",
+            "main",
+            "dev/alice/fix",
+            "dev/alice/feature",
+            "dev/bob/fix",
+            "dev/wip",
+            "release/1.0",
+        ])
+        .success();
+
+    let template = r#"name ++ "\n""#;
+    let output = work_dir.run_jj(["bookmark", "list", "-T", template, "--prefix=dev/"]);
+    insta::assert_snapshot!(output, @"
+    dev/alice/feature
+    dev/alice/fix
+    dev/bob/fix
+    dev/wip
+    [EOF]
+    ");
+
+    let output = work_dir.run_jj(["bookmark", "list", "-T", template, "--collapse"]);
+    insta::assert_snapshot!(output, @"
+    dev/ (4 bookmarks)
+    main
+    release/ (1 bookmarks)
+    [EOF]
+    ");
+
+    let output = work_dir.run_jj([
+        "bookmark",
+        "list",
+        "-T",
+        template,
+        "--prefix=dev/",
+        "--collapse",
+    ]);
+    insta::assert_snapshot!(output, @"
+    dev/alice/ (2 bookmarks)
+    dev/bob/ (1 bookmarks)
+    dev/wip
+    [EOF]
+    ");
+
+    // The limit counts a collapsed namespace as one
+    let output = work_dir.run_jj([
+        "bookmark",
+        "list",
+        "-T",
+        template,
+        "--collapse",
+        "--limit=2",
+    ]);
+    insta::assert_snapshot!(output, @"
+    dev/ (4 bookmarks)
+    main
+    [EOF]
+    ");
+}
+
+#[test]
+fn test_bookmark_list_filter_using_config() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+    work_dir
+        .run_jj(["bookmark", "create", "main", "ci/1", "ci/2"])
+        .success();
+    test_env.add_config(r#"ui.bookmark-list-filter = '~name("ci/*")'"#);
+
+    let list = |args: &[&str]| {
+        work_dir.run_jj_with(|cmd| {
+            cmd.args(["bookmark", "list", "-T", r#"name ++ "\n""#])
+                .args(args)
+        })
+    };
+    insta::assert_snapshot!(list(&[]), @"
+    main
+    [EOF]
+    ");
+    // The filter doesn't apply if bookmarks are selected by the arguments
+    insta::assert_snapshot!(list(&["*"]), @"
+    ci/1
+    ci/2
+    main
+    [EOF]
+    ");
+    insta::assert_snapshot!(list(&["-r@"]), @"
+    ci/1
+    ci/2
+    main
+    [EOF]
+    ");
+    insta::assert_snapshot!(list(&["--all-remotes"]), @"
+    ci/1
+    ci/2
+    main
+    [EOF]
+    ");
+
+    // Aliases can be used in the filter
+    insta::assert_snapshot!(list(&[
+        "--config=bookmark-list-filter-aliases.'except(x)'='~name(x)'",
+        "--config=ui.bookmark-list-filter='except(ci/2)'",
+    ]), @"
+    ci/1
+    main
+    [EOF]
+    ");
+
+    insta::assert_snapshot!(list(&["--config=ui.bookmark-list-filter='tracked('"]), @"
+    ------- stderr -------
+    Config error: Invalid `ui.bookmark-list-filter`
+    Caused by:  --> 1:9
+      |
+    1 | tracked(
+      |         ^---
+      |
+      = expected <expression>
+    For help, see https://docs.jj-vcs.dev/latest/config/ or use `jj help -k config`.
+    [EOF]
+    [exit status: 1]
+    ");
+}
+
 #[test]
 fn test_create_and_set_auto_track_bookmarks() {
     let test_env = TestEnvironment::default();
@@ -0,0 +1,112 @@
+// Copyright 2026 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::common::TestEnvironment;
+
+#[test]
+fn test_change_limits_snapshot() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    test_env.add_config(
+        r#"
+        [limits]
+        max-changed-files = 2
+        max-changed-bytes = 10
+        "#,
+    );
+    let work_dir = test_env.work_dir("repo");
+
+    // Small changes are fine
+    work_dir.write_file("file1", "a\n");
+    work_dir.run_jj(["util", "snapshot"]).success();
+
+    // Large changes produce a warning by default
+    work_dir.write_file("file2", "b\n");
+    work_dir.write_file("file3", "c\n");
+    work_dir.write_file("file4", "0123456789\n");
+    let output = work_dir.run_jj(["util", "snapshot"]);
+    insta::assert_snapshot!(output, @"
+    ------- stderr -------
+    Warning: Large change: 3 files were modified (limit: 2), 15.0B were modified (limit: 10.0B)
+    Snapshot complete.
+    [EOF]
+    ");
+
+    // Large changes can be refused
+    test_env.add_config("limits.refuse-large-change = true");
+    work_dir.remove_file("file1");
+    work_dir.remove_file("file2");
+    work_dir.remove_file("file3");
+    let output = work_dir.run_jj(["util", "snapshot"]);
+    insta::assert_snapshot!(output, @"
+    ------- stderr -------
+    Error: Refusing to make a large change: 3 files were modified (limit: 2)
+    Hint: Use `--allow-large-change` if this is intended, or adjust the `limits.*` settings.
+    [EOF]
+    [exit status: 1]
+    ");
+
+    // The refusal can be overridden
+    let output = work_dir.run_jj(["util", "snapshot", "--allow-large-change"]);
+    insta::assert_snapshot!(output, @"
+    ------- stderr -------
+    Warning: Large change: 3 files were modified (limit: 2)
+    Snapshot complete.
+    [EOF]
+    ");
+    let output = work_dir.run_jj(["file", "list"]);
+    insta::assert_snapshot!(output, @"
+    file4
+    [EOF]
+    ");
+}
+
+#[test]
+fn test_change_limits_rewrite() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+    work_dir.run_jj(["describe", "-m", "a"]).success();
+    work_dir.write_file("file1", "a\n");
+    work_dir.write_file("file2", "a\n");
+    work_dir.write_file("file3", "a\n");
+    work_dir.run_jj(["new", "root()", "-m", "b"]).success();
+    work_dir.write_file("file4", "b\n");
+    work_dir.run_jj(["new", "root()", "-m", "c"]).success();
+    test_env.add_config(
+        r#"
+        limits.max-changed-files = 2
+        limits.refuse-large-change = true
+        "#,
+    );
+
+    // Files changed by rebasing onto different parents aren't counted
+    let output = work_dir.run_jj(["rebase", "-r", "subject(b)", "-o", "subject(a)"]);
+    insta::assert_snapshot!(output, @"
+    ------- stderr -------
+    Rebased 1 commits to destination
+    [EOF]
+    ");
+
+    // Restoring the contents of a commit modifies all of its files
+    let output = work_dir.run_jj(["restore", "--from", "root()", "--into", "subject(a)"]);
+    insta::assert_snapshot!(output, @"
+    ------- stderr -------
+    Rebased 1 descendant commits
+    Error: Refusing to make a large change: 3 files were modified (limit: 2)
+    Hint: Use `--allow-large-change` if this is intended, or adjust the `limits.*` settings.
+    [EOF]
+    [exit status: 1]
+    ");
+}
@@ -592,6 +592,38 @@ fn test_commit_trailers() {
     ");
 }
 
+#[test]
+fn test_commit_signoff() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+    work_dir.write_file("file1", "foo\n");
+
+    work_dir
+        .run_jj(["commit", "-m=first", "--signoff"])
+        .success();
+    let output = work_dir.run_jj(["log", "--no-graph", "-r@-", "-Tdescription"]);
+    insta::assert_snapshot!(output, @"
+    first
+
+    Signed-off-by: Test User <test.user@example.com>
+    [EOF]
+    ");
+
+    // enabled by config
+    test_env.add_config("commit.signoff = true");
+    work_dir
+        .run_jj(["commit", "--config=user.name=Someone Else", "-m=second"])
+        .success();
+    let output = work_dir.run_jj(["log", "--no-graph", "-r@-", "-Tdescription"]);
+    insta::assert_snapshot!(output, @"
+    second
+
+    Signed-off-by: Someone Else <test.user@example.com>
+    [EOF]
+    ");
+}
+
 #[test]
 fn test_commit_with_editor_and_message_args() {
     let mut test_env = TestEnvironment::default();
@@ -920,6 +920,82 @@ fn test_log_immutable() {
     "#);
 }
 
+#[test]
+fn test_log_decorations() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+    work_dir.run_jj(["new", "-mA"]).success();
+    work_dir.run_jj(["new", "-mB"]).success();
+    let output = work_dir.run_jj(["log", "--no-graph", "-rsubject(A)", "-Tcommit_id"]);
+    let commit_id = output.success().stdout.into_raw();
+    let output = work_dir.run_jj(["log", "--no-graph", "-r@", "-Tchange_id"]);
+    let change_id = output.success().stdout.into_raw();
+
+    // Relative paths are resolved against the workspace root
+    std::fs::write(
+        work_dir.root().join(".jj").join("decorations.txt"),
+        format!("# comment\n{commit_id} deployed\n{change_id} PROJ-1 open\n"),
+    )
+    .unwrap();
+    test_env.add_config("decorations.sources.test.file = '.jj/decorations.txt'");
+    let template = r#"separate(" ", description.first_line(), decorations) ++ "\n""#;
+    let output = work_dir.run_jj(["log", "-T", template]);
+    insta::assert_snapshot!(output, @"
+    @  B PROJ-1 open
+    ○  A deployed
+    ○
+    ◆
+    [EOF]
+    ");
+
+    // Shown in the default templates
+    let output = work_dir.run_jj([
+        "log",
+        "--no-graph",
+        "-r@",
+        "-T",
+        r#"format_commit_labels(self) ++ "\n""#,
+    ]);
+    insta::assert_snapshot!(output, @"
+    PROJ-1 open
+    [EOF]
+    ");
+
+    std::fs::write(
+        work_dir.root().join(".jj").join("decorations.txt"),
+        "deployed\n",
+    )
+    .unwrap();
+    let output = work_dir.run_jj(["log", "-T", template]);
+    insta::assert_snapshot!(output, @r#"
+    ------- stderr -------
+    Error: Failed to parse template: In alias `format_commit_summary_with_refs(commit, refs)`
+    Caused by:
+    1:  --> 1:1
+      |
+    1 | format_commit_summary_with_refs(self, bookmarks)
+      | ^----------------------------------------------^
+      |
+      = In alias `format_commit_summary_with_refs(commit, refs)`
+    2:  --> 8:9
+      |
+    8 |         format_commit_labels(commit),
+      |         ^--------------------------^
+      |
+      = In alias `format_commit_labels(commit)`
+    3:  --> 7:10
+      |
+    7 |   commit.decorations().map(|d| label("decoration", d)),
+      |          ^---------^
+      |
+      = Failed to load decorations
+    4: Invalid line 1 in decoration source 'test': deployed
+    [EOF]
+    [exit status: 1]
+    "#);
+}
+
 #[test]
 fn test_log_contained_in() {
     let test_env = TestEnvironment::default();
@@ -972,6 +1048,25 @@ fn test_log_contained_in() {
     [EOF]
     ");
 
+    // The same revset can be used more than once
+    let template = r#"
+    separate(" ",
+      description.first_line(),
+      if(self.contained_in("subject(A)::"), "[1]"),
+      if(self.contained_in("subject(A)::"), "[2]"),
+    ) ++ "\n"
+    "#;
+    let output = work_dir.run_jj(["log", "-r::", "-T", template]);
+    insta::assert_snapshot!(output, @"
+    @  D
+    │ ○  C [1] [2]
+    │ ○  B [1] [2]
+    │ ○  A [1] [2]
+    ├─╯
+    ◆
+    [EOF]
+    ");
+
     // Suppress error that could be detected earlier
     let output = work_dir.run_jj(["log", "-r::", "-T", &template_for_revset("unknown_fn()")]);
     insta::assert_snapshot!(output, @r#"
@@ -1418,6 +1513,32 @@ fn test_log_diff_predefined_formats() {
     [EOF]
     ");
 
+    // per-file metadata
+    let template = indoc! {r#"
+        concat(
+          "=== " ++ commit_id.short() ++ " ===\n",
+          diff.files().map(|e| separate(" ",
+            e.status_char(),
+            e.path(),
+            "size_delta=" ++ e.size_delta(),
+            if(e.executable_changed(), "exec-changed"),
+          ) ++ "\n").join(""),
+        )
+    "#};
+    let output = work_dir.run_jj(["log", "--no-graph", "-T", template]);
+    insta::assert_snapshot!(output, @"
+    === d9ea8f447a3b ===
+    M file1 size_delta=2
+    M file2 size_delta=2
+    R rename-target size_delta=0
+    === 20bc00d202c2 ===
+    A file1 size_delta=4
+    A file2 size_delta=2
+    A rename-source size_delta=6
+    === 000000000000 ===
+    [EOF]
+    ");
+
     // custom diff stat template
     let template = indoc! {r#"
         concat(
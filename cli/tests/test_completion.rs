@@ -226,14 +226,20 @@ fn test_bookmark_names() {
     bbb-tracked	x
     --repository	Path to repository to operate on
     --ignore-working-copy	Don't snapshot the working copy, and don't update it
+    --no-snapshot-if-busy	Skip the snapshot if another process is snapshotting
     --ignore-immutable	Allow rewriting immutable commits
+    --allow-large-change	Allow operations exceeding the configured change limits
+    --dry-run	Show what the command would change, without changing the repo
+    --include-hidden	Allow change IDs to refer to hidden commits
     --at-operation	Operation to load the repo at
     --debug	Enable debug logging
     --color	When to colorize output
+    --error-format	How to print errors
     --quiet	Silence non-primary command output
     --no-pager	Disable the pager
     --config	Additional configuration options (can be repeated)
     --config-file	Additional configuration files (can be repeated)
+    --identity	Identity to use for authoring, committing, and signing
     --help	Print help (see more with '--help')
     [EOF]
     ");
@@ -327,6 +333,40 @@ fn test_bookmark_names() {
     ");
 }
 
+#[test]
+fn test_bookmark_namespaces() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+    work_dir.run_jj(["describe", "-m", "x"]).success();
+    work_dir
+        .run_jj([
+            "bookmark",
+            "create",
+            "-r@",
+            "dev/alice/fix",
+            "dev/bob",
+            "main",
+        ])
+        .success();
+
+    let output = work_dir.complete_fish(["bookmark", "list", "d"]);
+    insta::assert_snapshot!(output, @"
+    dev/alice/fix	x
+    dev/bob	x
+    dev/*
+    dev/alice/*
+    [EOF]
+    ");
+
+    let output = work_dir.complete_fish(["bookmark", "list", "--prefix", ""]);
+    insta::assert_snapshot!(output, @"
+    dev/
+    dev/alice/
+    [EOF]
+    ");
+}
+
 #[test]
 fn test_tag_names() {
     let test_env = TestEnvironment::default();
@@ -665,18 +705,21 @@ fn test_command_completion(shell: Shell) {
     match shell {
         Shell::Zsh => {
             insta::assert_snapshot!(output, @"
+            back:Return the working copy to the home change
             bisect:Find a bad revision by bisection
             bookmark:Manage bookmarks [default alias: b][EOF]
             ");
         }
         Shell::Bash => {
             insta::assert_snapshot!(output, @"
+            back
             bisect
             bookmark[EOF]
             ");
         }
         Shell::Fish => {
             insta::assert_snapshot!(output, @"
+            back	Return the working copy to the home change
             bisect	Find a bad revision by bisection
             bookmark	Manage bookmarks [default alias: b]
             [EOF]
@@ -710,7 +753,9 @@ fn test_command_completion_short_name() {
     // Short command names should be omitted
     let output = test_env.complete_fish(["config", ""]);
     insta::assert_snapshot!(output, @"
+    decrypt	Decrypt the repo or workspace config file
     edit	Start an editor on a jj config file
+    encrypt	Encrypt the repo or workspace config file
     get	Get the value of a given config option.
     list	List variables set in config files, along with their values
     path	Print the paths to the config files
@@ -718,14 +763,20 @@ fn test_command_completion_short_name() {
     unset	Update a config file to unset the given option
     --repository	Path to repository to operate on
     --ignore-working-copy	Don't snapshot the working copy, and don't update it
+    --no-snapshot-if-busy	Skip the snapshot if another process is snapshotting
     --ignore-immutable	Allow rewriting immutable commits
+    --allow-large-change	Allow operations exceeding the configured change limits
+    --dry-run	Show what the command would change, without changing the repo
+    --include-hidden	Allow change IDs to refer to hidden commits
     --at-operation	Operation to load the repo at
     --debug	Enable debug logging
     --color	When to colorize output
+    --error-format	How to print errors
     --quiet	Silence non-primary command output
     --no-pager	Disable the pager
     --config	Additional configuration options (can be repeated)
     --config-file	Additional configuration files (can be repeated)
+    --identity	Identity to use for authoring, committing, and signing
     --help	Print help (see more with '--help')
     [EOF]
     ");
@@ -734,6 +785,7 @@ fn test_command_completion_short_name() {
     let output = test_env.complete_fish(["config", "e"]);
     insta::assert_snapshot!(output, @"
     edit	Start an editor on a jj config file
+    encrypt	Encrypt the repo or workspace config file
     [EOF]
     ");
 
@@ -1296,6 +1348,8 @@ fn test_config() {
 
     let output = test_env.complete_fish(["config", "get", "f"]);
     insta::assert_snapshot!(output, @"
+    fix.cache.enabled	Whether to cache tool results by default, as if --incremental was given
+    fix.cache.max-entries	Maximum number of cached results. The least recently used results are discarded first.
     fsmonitor.backend	Whether to use an external filesystem monitor, useful for large repos
     fsmonitor.watchman.register-snapshot-trigger	Whether to use triggers to monitor for changes in the background.
     [EOF]
@@ -1312,6 +1366,8 @@ fn test_config() {
 
     let output = test_env.complete_fish(["log", "--config", "f"]);
     insta::assert_snapshot!(output, @"
+    fix.cache.enabled=	Whether to cache tool results by default, as if --incremental was given
+    fix.cache.max-entries=	Maximum number of cached results. The least recently used results are discarded first.
     fsmonitor.backend=	Whether to use an external filesystem monitor, useful for large repos
     fsmonitor.watchman.register-snapshot-trigger=	Whether to use triggers to monitor for changes in the background.
     [EOF]
@@ -351,6 +351,20 @@ fn test_config_list_origin() {
     [EOF]
     "#);
 
+    // The repo config is stored outside of the repo
+    let output = work_dir.run_jj([
+        "config",
+        "list",
+        "--origin",
+        "--include-overridden",
+        "test-layered-key",
+    ]);
+    insta::assert_snapshot!(output, @r#"
+    # test-layered-key = "test-original-val" # user $TEST_ENV/config/config.toml
+    test-layered-key = "test-layered-val" # repo $TEST_ENV/home/.config/jj/repos/0757f5ec8418b4f0983d/config.toml
+    [EOF]
+    "#);
+
     let output = work_dir.run_jj([
         "config",
         "list",
@@ -1221,6 +1235,49 @@ fn test_config_path() {
     ");
 }
 
+#[test]
+fn test_config_encrypt_decrypt() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+
+    let output = work_dir.run_jj(["config", "encrypt", "--user", "--age-identity", "key.txt"]);
+    insta::assert_snapshot!(output, @"
+    ------- stderr -------
+    Error: Only the repo and workspace configs can be encrypted
+    [EOF]
+    [exit status: 1]
+    ");
+
+    let output = work_dir.run_jj(["config", "encrypt", "--repo", "--age-identity", "key.txt"]);
+    insta::assert_snapshot!(output.normalize_backslash(), @"
+    ------- stderr -------
+    Error: Identity file $TEST_ENV/repo/key.txt doesn't exist
+    [EOF]
+    [exit status: 1]
+    ");
+
+    // Decrypting a plain text config file keeps it as is
+    work_dir
+        .run_jj(["config", "set", "--repo", "test-key", "test-val"])
+        .success();
+    let output = work_dir.run_jj(["config", "decrypt", "--repo"]);
+    insta::with_settings!({
+        filters => [(r"repos/[0-9a-f]+", "repos/<id>")],
+    }, {
+        insta::assert_snapshot!(output.normalize_backslash(), @"
+        ------- stderr -------
+        Decrypted config file $TEST_ENV/home/.config/jj/repos/<id>/config.toml
+        [EOF]
+        ");
+    });
+    let output = work_dir.run_jj(["config", "get", "test-key"]);
+    insta::assert_snapshot!(output, @"
+    test-val
+    [EOF]
+    ");
+}
+
 #[test]
 fn test_config_path_multiple() {
     let mut test_env = TestEnvironment::default();
@@ -1323,6 +1380,77 @@ fn test_config_get() {
     ");
 }
 
+#[test]
+fn test_config_get_resolve_origin() {
+    let test_env = TestEnvironment::default();
+    test_env.add_config(
+        r#"
+    [table]
+    key = "foo"
+    "#,
+    );
+    test_env.add_config(
+        r#"
+    [table]
+    key = "bar"
+    "#,
+    );
+
+    let output = test_env.run_jj_in(
+        ".",
+        [
+            "config",
+            "get",
+            "--resolve-origin",
+            "table.key",
+            "--config=table.key=baz",
+        ],
+    );
+    insta::assert_snapshot!(output, @r#"
+    # table.key = "foo" # user $TEST_ENV/config/config0002.toml
+    # table.key = "bar" # user $TEST_ENV/config/config0003.toml
+    table.key = "baz" # cli
+    [EOF]
+    "#);
+
+    let output = test_env
+        .run_jj_in(
+            ".",
+            ["config", "get", "--resolve-origin", "--json", "table.key"],
+        )
+        .success();
+    let entries: serde_json::Value = serde_json::from_str(output.stdout.raw()).unwrap();
+    let config_path = test_env.config_path();
+    assert_eq!(
+        entries,
+        serde_json::json!([
+            {
+                "name": "table.key",
+                "value": "foo",
+                "source": "user",
+                "path": config_path.join("config0002.toml"),
+                "is_overridden": true,
+            },
+            {
+                "name": "table.key",
+                "value": "bar",
+                "source": "user",
+                "path": config_path.join("config0003.toml"),
+                "is_overridden": false,
+            },
+        ])
+    );
+
+    let output = test_env.run_jj_in(".", ["config", "get", "--resolve-origin", "nonexistent"]);
+    insta::assert_snapshot!(output, @"
+    ------- stderr -------
+    Config error: Value not found for nonexistent
+    For help, see https://docs.jj-vcs.dev/latest/config/ or use `jj help -k config`.
+    [EOF]
+    [exit status: 1]
+    ");
+}
+
 #[test]
 fn test_config_get_yields_values_consistent_with_schema_defaults() {
     let mut test_env = TestEnvironment::default();
@@ -12,6 +12,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use indoc::indoc;
 use insta::assert_snapshot;
 use regex::Regex;
 
@@ -147,6 +148,97 @@ fn test_debug_revset() {
     ");
 }
 
+#[test]
+fn test_debug_template_check() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+    test_env.add_config(r#"template-aliases.'my_id' = 'change_id.shortest()'"#);
+
+    let output = work_dir.run_jj(["debug", "template", "--check", "my_id ++ description"]);
+    assert_snapshot!(output, @"
+    ------- stderr -------
+    Template is valid
+    [EOF]
+    ");
+
+    let output = work_dir.run_jj([
+        "debug",
+        "template",
+        "--check",
+        "--context=commit-ref",
+        "name ++ normal_target.commit_id()",
+    ]);
+    assert_snapshot!(output, @"
+    ------- stderr -------
+    Template is valid
+    [EOF]
+    ");
+
+    let output = work_dir.run_jj([
+        "debug",
+        "template",
+        "--check",
+        "--context=operation",
+        "id.short() ++ user",
+    ]);
+    assert_snapshot!(output, @"
+    ------- stderr -------
+    Template is valid
+    [EOF]
+    ");
+
+    // Keywords of other contexts aren't available
+    let output = work_dir.run_jj([
+        "debug",
+        "template",
+        "--check",
+        "--context=operation",
+        "change_id",
+    ]);
+    assert_snapshot!(output, @"
+    ------- stderr -------
+    Error: Failed to parse template: Keyword `change_id` doesn't exist
+    Caused by:  --> 1:1
+      |
+    1 | change_id
+      | ^-------^
+      |
+      = Keyword `change_id` doesn't exist
+    [EOF]
+    [exit status: 1]
+    ");
+
+    let output = work_dir.run_jj(["debug", "template", "--check", "commit_id.shorter()"]);
+    assert_snapshot!(output, @"
+    ------- stderr -------
+    Error: Failed to parse template: Method `shorter` doesn't exist for type `CommitId`
+    Caused by:  --> 1:11
+      |
+    1 | commit_id.shorter()
+      |           ^-----^
+      |
+      = Method `shorter` doesn't exist for type `CommitId`
+    Hint: Did you mean `short`, `shortest`?
+    [EOF]
+    [exit status: 1]
+    ");
+
+    // --context requires --check
+    let output = work_dir.run_jj(["debug", "template", "--context=operation", "id"]);
+    assert_snapshot!(output, @"
+    ------- stderr -------
+    error: the following required arguments were not provided:
+      --check
+
+    Usage: jj debug template --check --context <CONTEXT> <TEMPLATE>
+
+    For more information, try '--help'.
+    [EOF]
+    [exit status: 2]
+    ");
+}
+
 #[test]
 fn test_debug_index() {
     let test_env = TestEnvironment::default();
@@ -253,6 +345,117 @@ fn test_debug_reindex() {
     ");
 }
 
+#[test]
+fn test_debug_index_verify() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+    work_dir.run_jj(["new"]).success();
+    let output = work_dir.run_jj(["debug", "index", "verify"]);
+    assert!(output.status.success(), "{output}");
+    assert!(output.stderr.normalized().contains("is valid."), "{output}");
+
+    // Corrupt the index files without changing their size
+    let segments_dir = work_dir.root().join(".jj/repo/index/segments");
+    for entry in std::fs::read_dir(&segments_dir).unwrap() {
+        let path = entry.unwrap().path();
+        let mut data = std::fs::read(&path).unwrap();
+        *data.last_mut().unwrap() ^= 1;
+        std::fs::write(&path, data).unwrap();
+    }
+    let output = work_dir.run_jj(["debug", "index", "verify"]);
+    assert_eq!(output.status.code(), Some(1));
+    assert!(
+        output
+            .stdout
+            .normalized()
+            .starts_with("Index files are corrupt: Failed to load commit index file '"),
+        "{output}"
+    );
+    assert!(
+        output
+            .stdout
+            .normalized()
+            .ends_with("': checksum mismatch\n"),
+        "{output}"
+    );
+
+    // The checksums aren't verified when the index is loaded by other commands
+    work_dir.run_jj(["debug", "reindex"]).success();
+    work_dir.run_jj(["debug", "index", "verify"]).success();
+}
+
+#[test]
+fn test_debug_op_store_verify() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+    work_dir.run_jj(["new"]).success();
+    let output = work_dir.run_jj(["debug", "op-store", "verify"]);
+    insta::assert_snapshot!(output, @"
+    ------- stderr -------
+    Checked 3 operations and 3 commits, found 0 problems.
+    [EOF]
+    ");
+
+    // Create a concurrent operation and lose it
+    let op_heads_dir = work_dir.root().join(".jj/repo/op_heads/heads");
+    let list_op_heads = || {
+        let mut ids: Vec<String> = std::fs::read_dir(&op_heads_dir)
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name().into_string().unwrap())
+            .collect();
+        ids.sort();
+        ids
+    };
+    let [good_head_id] = list_op_heads().try_into().unwrap();
+    work_dir
+        .run_jj(["describe", "-m", "concurrent", "--at-op", "@-"])
+        .success();
+    let broken_head_id = list_op_heads()
+        .into_iter()
+        .find(|id| *id != good_head_id)
+        .unwrap();
+    std::fs::remove_file(
+        work_dir
+            .root()
+            .join(".jj/repo/op_store/operations")
+            .join(&broken_head_id),
+    )
+    .unwrap();
+
+    let output = work_dir.run_jj(["debug", "op-store", "verify"]);
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(
+        output.stdout.normalized(),
+        format!("Operation {broken_head_id}: operation is missing\n")
+    );
+    assert!(
+        output
+            .stderr
+            .normalized()
+            .contains("Hint: Use --repair to quarantine the broken operation heads.")
+    );
+
+    let output = work_dir.run_jj(["debug", "op-store", "verify", "--repair"]);
+    assert!(output.status.success(), "{output}");
+    assert!(
+        output
+            .stderr
+            .normalized()
+            .contains(&format!("Quarantined operation head {broken_head_id}"))
+    );
+    assert_eq!(list_op_heads(), [good_head_id]);
+    assert!(
+        work_dir
+            .root()
+            .join(".jj/repo/op_heads/quarantine")
+            .join(&broken_head_id)
+            .exists()
+    );
+    work_dir.run_jj(["debug", "op-store", "verify"]).success();
+}
+
 #[test]
 fn test_debug_stacked_table() {
     let test_env = TestEnvironment::default();
@@ -282,6 +485,136 @@ fn test_debug_stacked_table() {
     ");
 }
 
+#[test]
+fn test_debug_env() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+    work_dir
+        .run_jj(["config", "set", "--repo", "user.name", "Repo User"])
+        .success();
+
+    let output = work_dir.run_jj(["debug", "env"]).success();
+    let stdout = output.stdout.raw();
+    assert!(stdout.starts_with("jj "), "{stdout}");
+    for line in [
+        "\nWorkspace:\n",
+        "  Backend: git\n",
+        "  Op store: simple_op_store\n",
+        "  Working copy: local\n",
+        "  JJ_USER=Test User\n",
+        "  JJ_OP_USERNAME=test-username\n",
+        "\nGit:\n",
+        "\nTerminal:\n",
+    ] {
+        assert!(stdout.contains(line), "{line:?} not found in:\n{stdout}");
+    }
+    let repo_config_re =
+        Regex::new(r"(?m)^  repo: .*config\.toml \(config ID: [0-9a-f]+\)$").unwrap();
+    assert!(repo_config_re.is_match(stdout), "{stdout}");
+
+    // Names are replaced, but the report is otherwise the same
+    let output = work_dir.run_jj(["debug", "env", "--redact"]).success();
+    let stdout = output.stdout.raw();
+    assert!(stdout.contains("  JJ_OP_USERNAME=[USER]\n"), "{stdout}");
+    assert!(stdout.contains("  JJ_OP_HOSTNAME=[HOST]\n"), "{stdout}");
+    assert!(stdout.contains("  Backend: git\n"), "{stdout}");
+
+    // The report can be printed outside of a repo
+    let output = test_env.run_jj_in(".", ["debug", "env"]).success();
+    let stdout = output.stdout.raw();
+    assert!(
+        stdout.contains("\nWorkspace:\n  Not loaded: There is no jj repo in \".\"\n"),
+        "{stdout}"
+    );
+}
+
+#[test]
+fn test_debug_stats() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+
+    let output = work_dir.run_jj(["debug", "stats"]);
+    assert_snapshot!(output, @"
+    Git file writes:
+      Written blobs: 0
+      Reused blobs: 0 (0 bytes)
+    [EOF]
+    ");
+
+    work_dir.write_file("file", "contents");
+    let output = work_dir.run_jj(["debug", "stats"]);
+    assert_snapshot!(output, @"
+    Git file writes:
+      Written blobs: 1
+      Reused blobs: 0 (0 bytes)
+    [EOF]
+    ");
+
+    // The counters accumulate across commands and workspaces
+    work_dir
+        .run_jj(["workspace", "add", "--name=second", "../secondary"])
+        .success();
+    let secondary_dir = test_env.work_dir("secondary");
+    secondary_dir.write_file("file", "contents");
+    let output = secondary_dir.run_jj(["debug", "stats"]);
+    assert_snapshot!(output, @"
+    Git file writes:
+      Written blobs: 1
+      Reused blobs: 1 (8 bytes)
+    [EOF]
+    ");
+}
+
+#[test]
+fn test_debug_watch_events() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+
+    let output = work_dir.run_jj(["debug", "watch-events"]);
+    assert_snapshot!(output, @"
+    ------- stderr -------
+    Error: No filesystem monitor is configured
+    Hint: Set `fsmonitor.backend` to enable one, or use `--replay` to replay events.
+    [EOF]
+    [exit status: 1]
+    ");
+
+    work_dir.write_file(
+        "events",
+        indoc! {"
+            1000 src/main.rs
+            1000 src/lib.rs
+
+            2500 README.md
+        "},
+    );
+    let output = work_dir.run_jj(["debug", "watch-events", "--replay", "events"]);
+    assert_snapshot!(output, @"
+    1970-01-01 00:00:01.000 +00:00 src/main.rs
+    1970-01-01 00:00:01.000 +00:00 src/lib.rs
+    1970-01-01 00:00:02.500 +00:00 README.md
+    [EOF]
+    ");
+
+    let output = work_dir.run_jj(["debug", "watch-events", "--replay", "events", "-n1"]);
+    assert_snapshot!(output, @"
+    1970-01-01 00:00:01.000 +00:00 src/main.rs
+    [EOF]
+    ");
+
+    work_dir.write_file("bad-events", "soon src/main.rs\n");
+    let output = work_dir.run_jj(["debug", "watch-events", "--replay", "bad-events"]);
+    assert_snapshot!(output, @"
+    ------- stderr -------
+    Error: Invalid event on line 1: soon src/main.rs
+    [EOF]
+    [exit status: 1]
+    ");
+}
+
 #[test]
 fn test_debug_tree() {
     let test_env = TestEnvironment::default();
@@ -1095,6 +1095,73 @@ fn test_add_trailer() {
     ");
 }
 
+#[test]
+fn test_describe_signoff() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+
+    work_dir
+        .run_jj(["describe", "-m", "Message from CLI", "--signoff"])
+        .success();
+    let output = work_dir.run_jj(["log", "--no-graph", "-r@", "-Tdescription"]);
+    insta::assert_snapshot!(output, @"
+    Message from CLI
+
+    Signed-off-by: Test User <test.user@example.com>
+    [EOF]
+    ");
+
+    // the trailer isn't duplicated, and goes after the configured trailers
+    work_dir
+        .run_jj([
+            "describe",
+            "-m",
+            "Message from CLI",
+            "--config",
+            r#"templates.commit_trailers='"CC: alice@example.com"'"#,
+            "--config",
+            "commit.signoff=true",
+        ])
+        .success();
+    let output = work_dir.run_jj(["log", "--no-graph", "-r@", "-Tdescription"]);
+    insta::assert_snapshot!(output, @"
+    Message from CLI
+
+    CC: alice@example.com
+    Signed-off-by: Test User <test.user@example.com>
+    [EOF]
+    ");
+
+    // the active identity is used
+    work_dir
+        .run_jj([
+            "describe",
+            "-m",
+            "Message from CLI",
+            "--signoff",
+            "--config",
+            "user.email=foo@bar.org",
+        ])
+        .success();
+    let output = work_dir.run_jj(["log", "--no-graph", "-r@", "-Tdescription"]);
+    insta::assert_snapshot!(output, @"
+    Message from CLI
+
+    Signed-off-by: Test User <foo@bar.org>
+    [EOF]
+    ");
+
+    // an empty description is left untouched
+    work_dir.run_jj(["new"]).success();
+    let output = work_dir.run_jj(["describe", "-m", "", "--signoff"]);
+    insta::assert_snapshot!(output, @"
+    ------- stderr -------
+    Nothing changed.
+    [EOF]
+    ");
+}
+
 #[test]
 fn test_add_trailer_committer() {
     let mut test_env = TestEnvironment::default();
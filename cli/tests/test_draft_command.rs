@@ -0,0 +1,94 @@
+// Copyright 2026 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::common::TestEnvironment;
+
+#[test]
+fn test_draft_set_unset_list() {
+    let test_env = TestEnvironment::default();
+    test_env.add_config("templates.commit_summary = 'description.first_line()'");
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+    work_dir.run_jj(["describe", "-m", "first"]).success();
+    work_dir.run_jj(["new", "-m", "second"]).success();
+    work_dir.run_jj(["new", "-m", "third"]).success();
+
+    let output = work_dir.run_jj(["draft", "list"]);
+    insta::assert_snapshot!(output, @"");
+
+    // Defaults to @
+    let output = work_dir.run_jj(["draft", "set"]);
+    insta::assert_snapshot!(output, @"
+    ------- stderr -------
+    Marked 1 changes as drafts
+    [EOF]
+    ");
+    let output = work_dir.run_jj(["draft", "set", "@-", "@--"]);
+    insta::assert_snapshot!(output, @"
+    ------- stderr -------
+    Marked 2 changes as drafts
+    [EOF]
+    ");
+    let output = work_dir.run_jj(["draft", "set", "@"]);
+    insta::assert_snapshot!(output, @"
+    ------- stderr -------
+    Nothing changed.
+    [EOF]
+    ");
+    let output = work_dir.run_jj(["draft", "list"]);
+    insta::assert_snapshot!(output, @"
+    third
+    second
+    first
+    [EOF]
+    ");
+
+    // The flag is kept when the change is rewritten
+    work_dir
+        .run_jj(["describe", "-r@-", "-m", "second (edited)"])
+        .success();
+    let output = work_dir.run_jj(["draft", "unset", "@--"]);
+    insta::assert_snapshot!(output, @"
+    ------- stderr -------
+    Unmarked 1 draft changes
+    [EOF]
+    ");
+    let output = work_dir.run_jj(["draft", "list"]);
+    insta::assert_snapshot!(output, @"
+    third
+    second (edited)
+    [EOF]
+    ");
+
+    // Abandoned changes aren't listed
+    work_dir.run_jj(["abandon", "@"]).success();
+    let output = work_dir.run_jj(["draft", "list"]);
+    insta::assert_snapshot!(output, @"
+    second (edited)
+    [EOF]
+    ");
+
+    let output = work_dir.run_jj([
+        "log",
+        "--no-graph",
+        "-r..@-",
+        "-T",
+        r#"description.first_line() ++ if(self.draft(), " (draft)") ++ "\n""#,
+    ]);
+    insta::assert_snapshot!(output, @"
+    second (edited) (draft)
+    first
+    [EOF]
+    ");
+}
@@ -93,3 +93,81 @@ fn test_file_list() {
     [EOF]
     ");
 }
+
+#[test]
+fn test_file_list_tree_long_and_revisions() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+
+    work_dir.create_dir("dir");
+    work_dir.write_file("dir/file", "content1");
+    work_dir.write_file("exec-file", "content1");
+    work_dir.write_file("conflict-exec-file", "content1");
+    work_dir.write_file("conflict-file", "content1");
+    work_dir
+        .run_jj(["file", "chmod", "x", "exec-file", "conflict-exec-file"])
+        .success();
+
+    work_dir.run_jj(["new", "root()"]).success();
+    work_dir.write_file("conflict-exec-file", "content2");
+    work_dir.write_file("conflict-file", "content2");
+    work_dir
+        .run_jj(["file", "chmod", "x", "conflict-exec-file"])
+        .success();
+
+    work_dir.run_jj(["new", "visible_heads()"]).success();
+
+    let output = work_dir.run_jj(["file", "list", "--tree"]);
+    insta::assert_snapshot!(output, @"
+    conflict-exec-file
+    conflict-file
+    dir/
+    └── file
+    exec-file
+    [EOF]
+    ");
+
+    let output = work_dir.run_jj(["file", "list", "--long"]);
+    insta::assert_snapshot!(output.normalize_backslash(), @"
+    x          0 conflict     conflict-exec-file
+    -          0 conflict     conflict-file
+    -          8 dd954e7a4e1a dir/file
+    x          8 dd954e7a4e1a exec-file
+    [EOF]
+    ");
+
+    // Compare the second parent, the working copy, and the first parent
+    let revisions = ["-r=@- ~ first_parent(@)", "-r=@", "-r=first_parent(@)"];
+    let output = work_dir.run_jj(["file", "list"].iter().chain(&revisions));
+    insta::assert_snapshot!(output.normalize_backslash(), @"
+    abc conflict-exec-file
+    abc conflict-file
+    aa- dir/file
+    aa- exec-file
+    [EOF]
+    ");
+
+    let output = work_dir.run_jj(["file", "list", "--tree"].iter().chain(&revisions));
+    insta::assert_snapshot!(output, @"
+    abc conflict-exec-file
+    abc conflict-file
+        dir/
+    aa- └── file
+    aa- exec-file
+    [EOF]
+    ");
+
+    // --long can't be combined with --tree
+    let output = work_dir.run_jj(["file", "list", "--long", "--tree"]);
+    insta::assert_snapshot!(output, @"
+    ------- stderr -------
+    error: the argument '--long' cannot be used with '--tree'
+
+    Usage: jj file list --long [FILESETS]...
+
+    For more information, try '--help'.
+    [EOF]
+    [exit status: 2]
+    ");
+}
@@ -222,3 +222,109 @@ fn test_bad_alias_decl() {
     [EOF]
     "#);
 }
+
+#[test]
+fn test_alias_with_default_parameters() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+    work_dir.write_file("docs/a.md", "");
+    work_dir.write_file("docs/b.txt", "");
+    work_dir.write_file("src/c.md", "");
+    work_dir.write_file("src/d.rs", "");
+
+    test_env.add_config(indoc! {r#"
+        [fileset-aliases]
+        'markdown(dir = "docs")' = 'dir & glob:"**/*.md"'
+
+        [fileset-aliases.'other(dir = "src")']
+        definition = 'dir & ~glob:"**/*.md"'
+        description = "Files other than Markdown documents"
+    "#});
+    let query = |arg: &str| work_dir.run_jj(["file", "list", arg]);
+
+    insta::assert_snapshot!(query("markdown()"), @"
+    docs/a.md
+    [EOF]
+    ");
+    insta::assert_snapshot!(query("markdown(src)"), @"
+    src/c.md
+    [EOF]
+    ");
+    insta::assert_snapshot!(query("other()"), @"
+    src/d.rs
+    [EOF]
+    ");
+    insta::assert_snapshot!(query("other(docs)"), @"
+    docs/b.txt
+    [EOF]
+    ");
+
+    // Parameter without default value can't follow parameter with default value.
+    test_env.add_config(indoc! {r#"
+        [fileset-aliases]
+        'bad(x = "a", y)' = 'x | y'
+        'undocumented' = { description = "Undefined" }
+    "#});
+    insta::assert_snapshot!(query("markdown()"), @r#"
+    docs/a.md
+    [EOF]
+    ------- stderr -------
+    Warning: Failed to load `fileset-aliases.bad(x = "a", y)`:  --> 1:14
+      |
+    1 | bad(x = "a", y)
+      |              ^
+      |
+      = Parameter without default value follows parameter with default value
+    Warning: Failed to load `fileset-aliases.undocumented`: Missing `definition`
+    [EOF]
+    "#);
+}
+
+#[test]
+fn test_fileset_aliases_list() {
+    let test_env = TestEnvironment::default();
+    test_env.add_config(indoc! {r#"
+        [fileset-aliases]
+        'LOCK' = '**/Cargo.lock'
+        'markdown(dir = "docs")' = 'dir & glob:"**/*.md"'
+
+        [fileset-aliases.'tests(dir, ext = glob:"**/*.rs")']
+        definition = 'dir & ext'
+        description = """
+        Test sources in the directory.
+        Fixtures are included."""
+    "#});
+
+    let output = test_env.run_jj_in(".", ["fileset", "aliases"]);
+    insta::assert_snapshot!(output, @r#"
+    LOCK = **/Cargo.lock
+    markdown(dir = "docs") = dir & glob:"**/*.md"
+    tests(dir, ext = glob:"**/*.rs") = dir & ext
+      Test sources in the directory.
+      Fixtures are included.
+    [EOF]
+    "#);
+
+    // The later definition wins.
+    let output = test_env.run_jj_in(
+        ".",
+        [
+            "fileset",
+            "aliases",
+            "--config=fileset-aliases.LOCK='**/uv.lock'",
+            "--config=fileset-aliases.bad=1",
+        ],
+    );
+    insta::assert_snapshot!(output, @r#"
+    LOCK = **/uv.lock
+    markdown(dir = "docs") = dir & glob:"**/*.md"
+    tests(dir, ext = glob:"**/*.rs") = dir & ext
+      Test sources in the directory.
+      Fixtures are included.
+    [EOF]
+    ------- stderr -------
+    Warning: Failed to load `fileset-aliases.bad`: Expected a string or a table, but is integer
+    [EOF]
+    "#);
+}
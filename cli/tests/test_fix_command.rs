@@ -1103,6 +1103,59 @@ fn test_deduplication() {
     ");
 }
 
+#[test]
+fn test_incremental() {
+    // Log the tool invocations outside the workspace so the log isn't affected
+    // by working-copy updates.
+    let mut test_env = TestEnvironment::default();
+    let fixlog_path = test_env.env_root().join("fixlog");
+    set_up_fake_formatter(
+        &mut test_env,
+        &["--uppercase", "--tee", fixlog_path.to_str().unwrap()],
+    );
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+
+    work_dir.write_file("file", "foo\n");
+    work_dir.run_jj(["fix", "--incremental"]).success();
+
+    // The same content in another commit is fixed with the cached result
+    work_dir.run_jj(["new", "root()"]).success();
+    work_dir.write_file("file", "foo\n");
+    work_dir.run_jj(["fix", "--incremental"]).success();
+    let output = work_dir.run_jj(["file", "show", "file", "-r", "@"]);
+    insta::assert_snapshot!(output, @"
+    FOO
+    [EOF]
+    ");
+    insta::assert_snapshot!(sorted_lines(fixlog_path.clone()), @"FOO");
+
+    // The tool is run again with --no-cache
+    work_dir.run_jj(["new", "root()"]).success();
+    work_dir.write_file("file", "foo\n");
+    work_dir.run_jj(["fix", "--no-cache"]).success();
+    let output = work_dir.run_jj(["file", "show", "file", "-r", "@"]);
+    insta::assert_snapshot!(output, @"
+    FOO
+    [EOF]
+    ");
+    insta::assert_snapshot!(sorted_lines(fixlog_path.clone()), @"
+    FOO
+    FOO
+    ");
+
+    // Changing the cache key invalidates the cached results
+    test_env.add_config("fix.tools.fake-formatter.cache-key = 'v2'");
+    work_dir.run_jj(["new", "root()"]).success();
+    work_dir.write_file("file", "foo\n");
+    work_dir.run_jj(["fix", "--incremental"]).success();
+    insta::assert_snapshot!(sorted_lines(fixlog_path), @"
+    FOO
+    FOO
+    FOO
+    ");
+}
+
 fn sorted_lines(path: PathBuf) -> String {
     let mut log: Vec<_> = std::fs::read_to_string(path.as_os_str())
         .unwrap()
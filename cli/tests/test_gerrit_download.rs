@@ -0,0 +1,151 @@
+// Copyright 2026 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use regex::Regex;
+use testutils::git;
+
+use crate::common::TestEnvironment;
+
+const DESCRIPTION: &str = "Fix the bug\n\nChange-Id: I0123456789abcdef0123456789abcdef01234567\n";
+
+#[test]
+fn test_gerrit_download() {
+    let test_env = TestEnvironment::default();
+    let remote_repo = git::init_bare(test_env.env_root().join("remote"));
+    let base = git::add_commit(
+        &remote_repo,
+        "refs/heads/main",
+        "file",
+        b"base\n",
+        "base",
+        &[],
+    );
+    for (patchset, content) in [(1, b"v1\n"), (2, b"v2\n")] {
+        git::add_commit(
+            &remote_repo,
+            &format!("refs/changes/34/1234/{patchset}"),
+            "file",
+            content,
+            DESCRIPTION,
+            &[base.commit_id],
+        );
+    }
+    // Review data is stored in the `meta` ref.
+    git::add_commit(
+        &remote_repo,
+        "refs/changes/34/1234/meta",
+        "review",
+        b"",
+        "Update patch set 2",
+        &[],
+    );
+
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+    work_dir
+        .run_jj(["git", "remote", "add", "gerrit", "../remote"])
+        .success();
+
+    let commit_re = Regex::new(r"\b[k-z]{8} [0-9a-f]{8}\b").unwrap();
+    let normalize = |s: String| commit_re.replace_all(&s, "[COMMIT]").into_owned();
+
+    // The latest patchset is downloaded by default.
+    let output = work_dir
+        .run_jj(["gerrit", "download", "1234"])
+        .normalize_stderr_with(normalize);
+    insta::assert_snapshot!(output, @"
+    ------- stderr -------
+    Downloaded patchset 2 of change 1234 as [COMMIT] Fix the bug
+    [EOF]
+    ");
+    let output = work_dir.run_jj(["file", "show", "-r", "visible_heads() ~ @", "file"]);
+    insta::assert_snapshot!(output, @"
+    v2
+    [EOF]
+    ");
+    // The Change-Id footer is preserved.
+    let output = work_dir.run_jj([
+        "log",
+        "--no-graph",
+        "-r",
+        "visible_heads() ~ @",
+        "-T",
+        "description",
+    ]);
+    insta::assert_snapshot!(output, @"
+    Fix the bug
+
+    Change-Id: I0123456789abcdef0123456789abcdef01234567
+    [EOF]
+    ");
+
+    let output = work_dir
+        .run_jj(["gerrit", "download", "1234/1", "--remote=gerrit"])
+        .normalize_stderr_with(normalize);
+    insta::assert_snapshot!(output, @"
+    ------- stderr -------
+    Downloaded patchset 1 of change 1234 as [COMMIT] Fix the bug
+    [EOF]
+    ");
+    let output = work_dir.run_jj(["op", "log", "-n1", "--no-graph", "-T", "description"]);
+    insta::assert_snapshot!(output, @"
+    download patchset 1 of Gerrit change 1234
+    [EOF]
+    ");
+
+    let output = work_dir.run_jj(["gerrit", "download", "1234/3"]);
+    insta::assert_snapshot!(output, @"
+    ------- stderr -------
+    Error: Patchset 3 of change 1234 was not found on remote 'gerrit'
+    [EOF]
+    [exit status: 1]
+    ");
+
+    let output = work_dir.run_jj(["gerrit", "download", "99"]);
+    insta::assert_snapshot!(output, @"
+    ------- stderr -------
+    Error: Change 99 was not found on remote 'gerrit'
+    [EOF]
+    [exit status: 1]
+    ");
+
+    // No remote bookmarks are created.
+    let output = work_dir.run_jj(["bookmark", "list", "--all-remotes"]);
+    insta::assert_snapshot!(output, @"");
+}
+
+#[test]
+fn test_gerrit_download_invalid_args() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+
+    let output = work_dir.run_jj(["gerrit", "download", "1234"]);
+    insta::assert_snapshot!(output, @"
+    ------- stderr -------
+    Error: No remote specified, and no 'gerrit' remote was found
+    [EOF]
+    [exit status: 1]
+    ");
+
+    let output = work_dir.run_jj(["gerrit", "download", "1234/meta"]);
+    insta::assert_snapshot!(output, @"
+    ------- stderr -------
+    error: invalid value '1234/meta' for '<CHANGE[/PATCHSET]>': Expected a change number, optionally followed by `/<patchset>`
+
+    For more information, try '--help'.
+    [EOF]
+    [exit status: 2]
+    ");
+}
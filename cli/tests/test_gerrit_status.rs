@@ -0,0 +1,92 @@
+// Copyright 2026 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::common::TestEnvironment;
+
+#[test]
+fn test_gerrit_status_without_changes() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+    work_dir.run_jj(["describe", "-m", "no footer"]).success();
+
+    // Nothing is queried if no revisions have a Change-Id footer.
+    let output = work_dir.run_jj(["gerrit", "status"]);
+    insta::assert_snapshot!(output, @"
+    ------- stderr -------
+    No revisions with a Change-Id or Link footer
+    [EOF]
+    ");
+}
+
+#[test]
+fn test_gerrit_status_config_errors() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+    work_dir
+        .run_jj(["git", "remote", "add", "gerrit", "../remote"])
+        .success();
+    work_dir
+        .run_jj([
+            "describe",
+            "-m",
+            "Fix the bug\n\nChange-Id: I0123456789abcdef0123456789abcdef01234567",
+        ])
+        .success();
+
+    let output = work_dir.run_jj(["gerrit", "status"]);
+    insta::assert_snapshot!(output, @"
+    ------- stderr -------
+    Error: No review URL is configured for remote 'gerrit'
+    Hint: Set `gerrit.review-url` to the URL of your Gerrit server
+    [EOF]
+    [exit status: 1]
+    ");
+
+    // The URL of a Link footer isn't queried
+    work_dir
+        .run_jj([
+            "describe",
+            "-m",
+            "Fix the bug\n\nLink: https://evil.example.com/id/I0123456789abcdef0123456789abcdef01234567",
+        ])
+        .success();
+    let output = work_dir.run_jj(["gerrit", "status"]);
+    insta::assert_snapshot!(output, @"
+    ------- stderr -------
+    Error: No review URL is configured for remote 'gerrit'
+    Hint: Set `gerrit.review-url` to the URL of your Gerrit server
+    [EOF]
+    [exit status: 1]
+    ");
+
+    test_env.add_config("gerrit.review-url = 'https://review.example.com'");
+    test_env.add_config("gerrit.http-user = 'alice'");
+    let output = work_dir.run_jj(["gerrit", "status"]);
+    insta::assert_snapshot!(output, @"
+    ------- stderr -------
+    Error: Both `gerrit.http-user` and `gerrit.http-password` must be set
+    [EOF]
+    [exit status: 1]
+    ");
+
+    let output = work_dir.run_jj(["gerrit", "status", "--remote", "missing"]);
+    insta::assert_snapshot!(output, @"
+    ------- stderr -------
+    Error: The remote 'missing' (specified via `--remote`) does not exist
+    [EOF]
+    [exit status: 1]
+    ");
+}
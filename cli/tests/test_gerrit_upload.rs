@@ -12,6 +12,8 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use regex::Regex;
+
 use crate::common::TestEnvironment;
 use crate::common::create_commit;
 use crate::common::create_commit_with_files;
@@ -83,6 +85,207 @@ fn test_gerrit_upload_dryrun() {
     Dry-run: Would push zsuskuln 123b4d91 b | b
     [EOF]
     ");
+
+    // -n is the short form of --dry-run
+    let output = work_dir.run_jj(["gerrit", "upload", "-r", "b", "-n"]);
+    insta::assert_snapshot!(output, @"
+    ------- stderr -------
+    Found 1 heads to push to Gerrit (remote 'origin'), target branch 'main'
+    Dry-run: Would push zsuskuln 123b4d91 b | b
+    [EOF]
+    ");
+}
+
+#[test]
+fn test_gerrit_upload_drafts() {
+    let test_env = TestEnvironment::default();
+    test_env.add_config("templates.commit_summary = 'description.first_line()'");
+    test_env.add_config(r#"templates.gerrit_upload = '"Would push " ++ commit.description()'"#);
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+    work_dir
+        .run_jj([
+            "git",
+            "remote",
+            "add",
+            "gerrit",
+            "http://example.com/repo/foo",
+        ])
+        .success();
+    test_env.add_config(r#"gerrit.default-remote-branch="main""#);
+
+    create_commit(&work_dir, "a", &[]);
+    create_commit(&work_dir, "b", &["a"]);
+    work_dir.run_jj(["draft", "set", "a"]).success();
+    let output = work_dir.run_jj(["gerrit", "upload", "-r", "b", "--dry-run"]);
+    insta::assert_snapshot!(output, @"
+    ------- stderr -------
+    Error: Refusing to upload 1 revisions because they are drafts
+    Hint: Draft revisions:
+      a
+    Hint: Unmark them with `jj draft unset`, or use --allow-drafts to upload them anyway.
+    [EOF]
+    [exit status: 1]
+    ");
+
+    let output = work_dir.run_jj(["gerrit", "upload", "-r", "b", "--dry-run", "--allow-drafts"]);
+    insta::assert_snapshot!(output, @"
+    ------- stderr -------
+    Found 1 heads to push to Gerrit (remote 'gerrit'), target branch 'main'
+    Would push b
+    [EOF]
+    ");
+}
+
+#[test]
+fn test_gerrit_upload_template_and_json() {
+    let test_env = TestEnvironment::default();
+    test_env.add_config(
+        r#"
+[gerrit]
+default-remote = "origin"
+default-remote-branch = "main"
+review-url = "https://gerrit.example.com/"
+
+[templates]
+gerrit_upload = '''
+if(dry_run, "Would upload ", "Uploading ")
+++ commit.description().first_line() ++ ": " ++ url ++ "\n"
+'''
+        "#,
+    );
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+    work_dir
+        .run_jj([
+            "git",
+            "remote",
+            "add",
+            "origin",
+            "http://example.com/repo/foo",
+        ])
+        .success();
+    create_commit(&work_dir, "a", &[]);
+    create_commit(&work_dir, "b", &["a"]);
+
+    let change_id_re = Regex::new(r"I[0-9a-f]{40}").unwrap();
+    let output = work_dir
+        .run_jj(["gerrit", "upload", "-r", "b", "--dry-run", "--json"])
+        .success();
+    let stderr = change_id_re.replace_all(output.stderr.raw(), "[CHANGE_ID]");
+    insta::assert_snapshot!(stderr, @"
+    Found 1 heads to push to Gerrit (remote 'origin'), target branch 'main'
+    Would upload b: https://gerrit.example.com/id/[CHANGE_ID]
+    ");
+
+    let value: serde_json::Value = serde_json::from_str(output.stdout.raw()).unwrap();
+    assert_eq!(value["remote"], "origin");
+    assert_eq!(value["remote_branch"], "main");
+    assert_eq!(value["dry_run"], true);
+    let changes = value["changes"].as_array().unwrap();
+    assert_eq!(changes.len(), 2);
+    for change in changes {
+        let change_id = change["change_id"].as_str().unwrap();
+        assert!(change_id_re.is_match(change_id));
+        assert_eq!(
+            change["url"],
+            format!("https://gerrit.example.com/id/{change_id}")
+        );
+        assert!(change["commit_id"].is_string());
+    }
+}
+
+#[test]
+fn test_gerrit_upload_remote_config() {
+    let test_env = TestEnvironment::default();
+    test_env.add_config(
+        r#"
+[gerrit]
+default-remote = "origin"
+default-remote-branch = "main"
+
+[gerrit.remotes.upstream]
+branch = "release"
+review-url = "https://gerrit.example.org/"
+reviewers = ["maintainer@example.org"]
+        "#,
+    );
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+    create_commit(&work_dir, "a", &[]);
+    for remote in ["origin", "upstream"] {
+        work_dir
+            .run_jj([
+                "git",
+                "remote",
+                "add",
+                remote,
+                &format!("http://example.com/repo/{remote}"),
+            ])
+            .success();
+    }
+
+    // The default remote has no table, so the global settings apply
+    let output = work_dir.run_jj(["gerrit", "upload", "-r", "a", "--dry-run", "--json"]);
+    let value: serde_json::Value = serde_json::from_str(output.stdout.raw()).unwrap();
+    assert_eq!(value["remote"], "origin");
+    assert_eq!(value["remote_branch"], "main");
+    assert_eq!(value["changes"][0]["url"], serde_json::Value::Null);
+
+    let output = work_dir.run_jj([
+        "gerrit",
+        "upload",
+        "-r",
+        "a",
+        "--dry-run",
+        "--json",
+        "--remote",
+        "upstream",
+    ]);
+    let value: serde_json::Value = serde_json::from_str(output.stdout.raw()).unwrap();
+    assert_eq!(value["remote"], "upstream");
+    assert_eq!(value["remote_branch"], "release");
+    let change_id = value["changes"][0]["change_id"].as_str().unwrap();
+    assert_eq!(
+        value["changes"][0]["url"],
+        format!("https://gerrit.example.org/id/{change_id}")
+    );
+
+    // --remote-branch takes precedence over the remote table
+    let output = work_dir.run_jj([
+        "gerrit",
+        "upload",
+        "-r",
+        "a",
+        "--dry-run",
+        "--remote",
+        "upstream",
+        "--remote-branch",
+        "other",
+    ]);
+    insta::assert_snapshot!(output, @"
+    ------- stderr -------
+    Found 1 heads to push to Gerrit (remote 'upstream'), target branch 'other'
+    Dry-run: Would push rlvkpnrz 7d980be7 a | a
+    [EOF]
+    ");
+
+    // Default reviewers are validated like --reviewer
+    let output = work_dir.run_jj([
+        "gerrit",
+        "upload",
+        "-r",
+        "a",
+        "--dry-run",
+        "--config=gerrit.default-reviewers=['Some One']",
+    ]);
+    insta::assert_snapshot!(output, @"
+    ------- stderr -------
+    Error: Invalid email 'Some One' in `gerrit.default-reviewers`
+    Hint: Emails must not be empty or contain whitespace or commas. Repeat the option to add several people.
+    [EOF]
+    [exit status: 1]
+    ");
 }
 
 #[test]
@@ -189,6 +392,56 @@ fn test_gerrit_upload_option_failure() {
     [EOF]
     [exit status: 1]
     ");
+
+    // empty hashtag
+    let output = work_dir.run_jj(["gerrit", "upload", "--hashtag", "foo", "--hashtag", ""]);
+    insta::assert_snapshot!(output, @"
+    ------- stderr -------
+    Error: Hashtags must not be empty
+    [EOF]
+    [exit status: 1]
+    ");
+
+    // malformed reviewers
+    let output = work_dir.run_jj([
+        "gerrit",
+        "upload",
+        "--reviewer",
+        "foo@example.com,bar@example.com",
+    ]);
+    insta::assert_snapshot!(output, @"
+    ------- stderr -------
+    Error: Invalid email 'foo@example.com,bar@example.com' in --reviewer
+    Hint: Emails must not be empty or contain whitespace or commas. Repeat the option to add several people.
+    [EOF]
+    [exit status: 1]
+    ");
+    let output = work_dir.run_jj(["gerrit", "upload", "--cc", ""]);
+    insta::assert_snapshot!(output, @"
+    ------- stderr -------
+    Error: Invalid email '' in --cc
+    Hint: Emails must not be empty or contain whitespace or commas. Repeat the option to add several people.
+    [EOF]
+    [exit status: 1]
+    ");
+
+    // labels not in the allowlist
+    test_env.add_config(r#"gerrit.allowed-labels = ["Verified", "Code-Review"]"#);
+    let output = work_dir.run_jj([
+        "gerrit",
+        "upload",
+        "--label",
+        "Verified+1",
+        "--label",
+        "Commit-Queue+2",
+    ]);
+    insta::assert_snapshot!(output, @"
+    ------- stderr -------
+    Error: Label Commit-Queue is not in `gerrit.allowed-labels`
+    Hint: Allowed labels: Verified, Code-Review
+    [EOF]
+    [exit status: 1]
+    ");
 }
 
 #[test]
@@ -224,6 +477,7 @@ fn test_gerrit_upload_failure() {
     ------- stderr -------
     Error: Refusing to upload revision mzvwutvlkqwt because it is empty
     Hint: Perhaps you squashed then ran upload? Maybe you meant to upload the parent commit instead (eg. @-)
+    Hint: Use --skip-check=no-empty-commits to upload it anyway.
     [EOF]
     [exit status: 1]
     ");
@@ -234,6 +488,7 @@ fn test_gerrit_upload_failure() {
     ------- stderr -------
     Error: Refusing to upload revision yqosqzytrlsw because it is has no description
     Hint: Maybe you meant to upload the parent commit instead (eg. @-)
+    Hint: Use --skip-check=no-empty-description to upload it anyway.
     [EOF]
     [exit status: 1]
     ");
@@ -822,3 +1077,54 @@ fn test_gerrit_upload_rejected_by_remote() {
     [exit status: 1]
     ");
 }
+
+#[test]
+fn test_gerrit_upload_topic() {
+    let test_env = TestEnvironment::default();
+    test_env
+        .run_jj_in(".", ["git", "init", "--colocate", "remote"])
+        .success();
+    let remote_dir = test_env.work_dir("remote");
+    create_commit(&remote_dir, "a", &[]);
+
+    test_env
+        .run_jj_in(".", ["git", "clone", "remote", "local"])
+        .success();
+    let local_dir = test_env.work_dir("local");
+    create_commit(&local_dir, "b", &["a@origin"]);
+    create_commit(&local_dir, "c", &["a@origin"]);
+
+    local_dir
+        .run_jj([
+            "gerrit",
+            "upload",
+            "-r=b",
+            "--remote-branch=main",
+            "--topic=my topic",
+        ])
+        .success();
+
+    // The topic is rendered from each head if `--topic` isn't given
+    test_env.add_config(
+        r#"gerrit.default-topic-template = 'local_bookmarks().map(|b| "feature-" ++ b.name())'"#,
+    );
+    local_dir
+        .run_jj(["gerrit", "upload", "-r=b|c", "--remote-branch=main"])
+        .success();
+
+    let output = remote_dir.run_jj([
+        "util",
+        "exec",
+        "--",
+        "git",
+        "for-each-ref",
+        "--format=%(refname)",
+        "refs/for",
+    ]);
+    insta::assert_snapshot!(output, @"
+    refs/for/main%topic=feature-b
+    refs/for/main%topic=feature-c
+    refs/for/main%topic=my%20topic
+    [EOF]
+    ");
+}
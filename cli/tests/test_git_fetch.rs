@@ -2270,6 +2270,58 @@ fn test_git_fetch_tracked_multiple_remotes() {
     ");
 }
 
+#[test]
+fn test_git_fetch_custom_refspecs() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+    let git_repo = add_git_remote(&test_env, &work_dir, "origin");
+    git::add_commit(
+        &git_repo,
+        "refs/merge-requests/1/head",
+        "mr1",
+        b"mr1",
+        "merge request 1",
+        &[],
+    );
+
+    test_env.add_config(
+        r#"remotes.origin.fetch = ["+refs/merge-requests/*/head:refs/remotes/origin/mr/*"]"#,
+    );
+    let output = work_dir.run_jj(["git", "fetch"]);
+    insta::assert_snapshot!(output, @"
+    ------- stderr -------
+    bookmark: mr/1@origin   [new] untracked
+    bookmark: origin@origin [new] untracked
+    [EOF]
+    ");
+    let output = work_dir.run_jj([
+        "log",
+        "--no-graph",
+        "-r",
+        "remote_bookmarks('mr/*', origin)",
+        "-Tdescription",
+    ]);
+    insta::assert_snapshot!(output, @"
+    merge request 1
+    [EOF]
+    ");
+
+    // The destination must be in the remote-tracking namespace
+    let output = work_dir.run_jj([
+        "git",
+        "fetch",
+        "--config=remotes.origin.fetch=['refs/merge-requests/*/head:refs/jj/mr/*']",
+    ]);
+    insta::assert_snapshot!(output, @"
+    ------- stderr -------
+    Config error: Invalid fetch refspec `refs/merge-requests/*/head:refs/jj/mr/*`: destination must be in `refs/remotes/origin/`
+    For help, see https://docs.jj-vcs.dev/latest/config/ or use `jj help -k config`.
+    [EOF]
+    [exit status: 1]
+    ");
+}
+
 #[test]
 fn test_git_fetch_auto_track_bookmarks() {
     let test_env = TestEnvironment::default();
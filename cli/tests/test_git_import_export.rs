@@ -212,6 +212,122 @@ fn test_git_import_undo() {
     ");
 }
 
+#[test]
+fn test_git_import_export_ignored_refs() {
+    let test_env = TestEnvironment::default();
+    test_env.add_config(r#"git.ignored-refs = ["refs/heads/wip/*"]"#);
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+    let git_repo = git::open(work_dir.root().join(".jj/repo/store/git"));
+
+    // Ignored bookmarks aren't exported
+    work_dir
+        .run_jj(["bookmark", "create", "-r@", "main", "wip/foo"])
+        .success();
+    let output = work_dir.run_jj(["git", "export"]);
+    insta::assert_snapshot!(output, @"");
+    insta::assert_debug_snapshot!(get_git_repo_refs(&git_repo), @r#"
+    [
+        (
+            "refs/heads/main",
+            CommitId(
+                "e8849ae12c709f2321908879bc724fdb2ab8a781",
+            ),
+        ),
+    ]
+    "#);
+
+    // Ignored Git refs aren't imported
+    let commit_id = gix::ObjectId::from_hex(b"e8849ae12c709f2321908879bc724fdb2ab8a781").unwrap();
+    git_repo
+        .reference(
+            "refs/heads/wip/bar",
+            commit_id,
+            gix::refs::transaction::PreviousValue::Any,
+            "",
+        )
+        .unwrap();
+    let output = work_dir.run_jj(["git", "import"]);
+    insta::assert_snapshot!(output, @"
+    ------- stderr -------
+    Nothing changed.
+    [EOF]
+    ");
+    insta::assert_snapshot!(get_bookmark_output(&work_dir), @"
+    main: qpvuntsm e8849ae1 (empty) (no description set)
+      @git: qpvuntsm e8849ae1 (empty) (no description set)
+    wip/foo: qpvuntsm e8849ae1 (empty) (no description set)
+    [EOF]
+    ");
+}
+
+#[test]
+fn test_git_import_export_branch_prefixes() {
+    let test_env = TestEnvironment::default();
+    test_env.add_config(r#"git.branch-prefixes."user/alice/" = "alice/""#);
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+    let git_repo = git::open(work_dir.root().join(".jj/repo/store/git"));
+
+    // Renamed branches are imported under the bookmark prefix
+    let commit_id = gix::ObjectId::from_hex(b"e8849ae12c709f2321908879bc724fdb2ab8a781").unwrap();
+    for name in ["refs/heads/user/alice/topic", "refs/heads/alice/clash"] {
+        git_repo
+            .reference(
+                name,
+                commit_id,
+                gix::refs::transaction::PreviousValue::Any,
+                "",
+            )
+            .unwrap();
+    }
+    let output = work_dir.run_jj(["git", "import"]);
+    insta::assert_snapshot!(output, @"
+    ------- stderr -------
+    bookmark: alice/topic@git [new] tracked
+    [EOF]
+    ");
+    insta::assert_snapshot!(get_bookmark_output(&work_dir), @"
+    alice/topic: qpvuntsm e8849ae1 (empty) (no description set)
+      @git: qpvuntsm e8849ae1 (empty) (no description set)
+    [EOF]
+    ");
+
+    // Bookmarks are exported back to the original branch names
+    work_dir
+        .run_jj(["bookmark", "create", "-r@", "alice/new", "user/alice/other"])
+        .success();
+    let output = work_dir.run_jj(["git", "export"]);
+    insta::assert_snapshot!(output, @"
+    ------- stderr -------
+    Warning: Failed to export some bookmarks:
+      user/alice/other@git: Name is not allowed in Git
+    [EOF]
+    ");
+    insta::assert_debug_snapshot!(get_git_repo_refs(&git_repo), @r#"
+        [
+            (
+                "refs/heads/alice/clash",
+                CommitId(
+                    "e8849ae12c709f2321908879bc724fdb2ab8a781",
+                ),
+            ),
+            (
+                "refs/heads/user/alice/new",
+                CommitId(
+                    "e8849ae12c709f2321908879bc724fdb2ab8a781",
+                ),
+            ),
+            (
+                "refs/heads/user/alice/topic",
+                CommitId(
+                    "e8849ae12c709f2321908879bc724fdb2ab8a781",
+                ),
+            ),
+        ]
+        "#);
+}
+
 #[test]
 fn test_git_import_move_export_with_default_undo() {
     let test_env = TestEnvironment::default();
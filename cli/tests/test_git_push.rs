@@ -18,6 +18,7 @@ use testutils::git;
 use crate::common::CommandOutput;
 use crate::common::TestEnvironment;
 use crate::common::TestWorkDir;
+use crate::common::force_interactive;
 use crate::common::to_toml_value;
 
 fn git_repo_dir_for_jj_repo(work_dir: &TestWorkDir<'_>) -> std::path::PathBuf {
@@ -96,6 +97,84 @@ fn test_git_push_nothing() {
     ");
 }
 
+#[test]
+fn test_git_push_refspecs() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "origin"]).success();
+    let origin_git_repo_path = git_repo_dir_for_jj_repo(&test_env.work_dir("origin"));
+    test_env.run_jj_in(".", ["git", "init", "local"]).success();
+    let work_dir = test_env.work_dir("local");
+    work_dir
+        .run_jj([
+            "git",
+            "remote",
+            "add",
+            "origin",
+            origin_git_repo_path.to_str().unwrap(),
+        ])
+        .success();
+    work_dir.run_jj(["describe", "-m=foo"]).success();
+    work_dir
+        .run_jj(["bookmark", "create", "-r@", "main", "topic"])
+        .success();
+    test_env.add_config(
+        r#"
+        [remotes.origin]
+        push = ["refs/heads/main:refs/heads/trunk", "refs/heads/*:refs/heads/users/me/*"]
+        "#,
+    );
+
+    let output = work_dir.run_jj(["git", "push", "--all"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Changes to push to origin:
+      Add bookmark main to 34311e6bbb49
+      Add bookmark topic to 34311e6bbb49
+    Warning: The working-copy commit in workspace 'default' became immutable, so a new commit has been created on top of it.
+    Working copy  (@) now at: royxmykx 57efdfdb (empty) (no description set)
+    Parent commit (@-)      : rlvkpnrz 34311e6b main topic | (empty) foo
+    [EOF]
+    ");
+
+    // The bookmarks are pushed to the mapped refs
+    let origin_git_repo = git::open(&origin_git_repo_path);
+    let remote_refs: Vec<_> = origin_git_repo
+        .references()
+        .unwrap()
+        .local_branches()
+        .unwrap()
+        .map(|git_ref| git_ref.unwrap().name().as_bstr().to_string())
+        .collect();
+    insta::assert_debug_snapshot!(remote_refs, @r#"
+    [
+        "refs/heads/trunk",
+        "refs/heads/users/me/topic",
+    ]
+    "#);
+    insta::assert_snapshot!(get_bookmark_output(&work_dir), @r"
+    main: rlvkpnrz 34311e6b (empty) foo
+      @origin: rlvkpnrz 34311e6b (empty) foo
+    topic: rlvkpnrz 34311e6b (empty) foo
+      @origin: rlvkpnrz 34311e6b (empty) foo
+    [EOF]
+    ");
+
+    // Invalid refspecs are reported before pushing
+    let output = work_dir.run_jj([
+        "git",
+        "push",
+        "--all",
+        "--config=remotes.origin.push=['refs/tags/*:refs/tags/*']",
+    ]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Config error: Invalid push refspec `refs/tags/*:refs/tags/*`: source must be in `refs/heads/`
+    For help, see https://docs.jj-vcs.dev/latest/config/ or use `jj help -k config`.
+    [EOF]
+    [exit status: 1]
+    ");
+}
+
 #[test]
 fn test_git_push_default_remote_selection() {
     let test_env = TestEnvironment::default();
@@ -231,6 +310,50 @@ fn test_git_push_current_bookmark() {
     ");
 }
 
+#[test]
+fn test_git_push_result_template() {
+    let test_env = TestEnvironment::default();
+    set_up(&test_env);
+    test_env.add_config("remotes.origin.auto-track-bookmarks = '*'");
+    test_env.add_config(
+        r#"templates.git_push_result = '''
+        separate(" ", status, remote, bookmark,
+          if(old_target, old_target.description().first_line(), "-") ++ " -> " ++
+          if(new_target, new_target.description().first_line(), "-"),
+          url,
+        ) ++ "\n"
+        '''"#,
+    );
+    let work_dir = test_env.work_dir("local");
+    work_dir.run_jj(["new", "bookmark2"]).success();
+    work_dir
+        .run_jj(["bookmark", "set", "bookmark2", "-r@"])
+        .success();
+    work_dir
+        .run_jj(["bookmark", "create", "-r@", "my-bookmark"])
+        .success();
+    work_dir.run_jj(["describe", "-m", "foo"]).success();
+    work_dir
+        .run_jj(["bookmark", "delete", "bookmark1"])
+        .success();
+
+    // The results are printed even if the status messages are suppressed
+    let output = work_dir.run_jj(["git", "push", "--all", "--deleted", "--dry-run", "--quiet"]);
+    insta::assert_snapshot!(output, @"
+    dry-run origin bookmark1 description 1 -> -
+    dry-run origin bookmark2 description 2 -> foo
+    dry-run origin my-bookmark - -> foo
+    [EOF]
+    ");
+    let output = work_dir.run_jj(["git", "push", "--all", "--deleted", "--quiet"]);
+    insta::assert_snapshot!(output, @"
+    pushed origin bookmark1 description 1 -> -
+    pushed origin bookmark2 description 2 -> foo
+    pushed origin my-bookmark - -> foo
+    [EOF]
+    ");
+}
+
 #[test]
 fn test_git_push_parent_bookmark() {
     let test_env = TestEnvironment::default();
@@ -1664,6 +1787,7 @@ fn test_git_push_no_description() {
     ------- stderr -------
     Error: Won't push commit 8d23abddc924 since it has no description
     Hint: Rejected commit: yqosqzyt 8d23abdd my-bookmark* | (empty) (no description set)
+    Hint: Use --skip-check=no-empty-description to push it anyway.
     [EOF]
     [exit status: 1]
     ");
@@ -1678,6 +1802,114 @@ fn test_git_push_no_description() {
         .success();
 }
 
+#[test]
+fn test_git_push_checks() {
+    let test_env = TestEnvironment::default();
+    set_up(&test_env);
+    test_env.add_config("remotes.origin.auto-track-bookmarks = '*'");
+    test_env.add_config("templates.commit_summary = 'description.first_line()'");
+    test_env.add_config(
+        r#"git.push-checks = ["no-fixup-commits", "description-regex:^[A-Z]+-[0-9]+: "]"#,
+    );
+    let work_dir = test_env.work_dir("local");
+    work_dir
+        .run_jj(["new", "bookmark1", "-m", "fixup! PROJ-1: fix"])
+        .success();
+    work_dir
+        .run_jj(["bookmark", "create", "-r@", "my-bookmark"])
+        .success();
+    insta::with_settings!({filters => vec![(r"commit [0-9a-f]{12}", "commit <commit-id>")]}, {
+        let output = work_dir.run_jj(["git", "push", "--bookmark", "my-bookmark"]);
+        insta::assert_snapshot!(output, @"
+        ------- stderr -------
+        Error: Won't push commit <commit-id> since it is a fixup commit and its description doesn't match `^[A-Z]+-[0-9]+: `
+        Hint: Rejected commit: fixup! PROJ-1: fix
+        Hint: Use --skip-check=no-fixup-commits --skip-check=description-regex to push it anyway.
+        [EOF]
+        [exit status: 1]
+        ");
+    });
+
+    let output = work_dir.run_jj([
+        "git",
+        "push",
+        "--bookmark",
+        "my-bookmark",
+        "--config=git.push-checks=['unknown']",
+    ]);
+    insta::assert_snapshot!(output, @"
+    ------- stderr -------
+    Config error: Invalid type or value for git.push-checks
+    Caused by: Unknown push check `unknown`
+
+    For help, see https://docs.jj-vcs.dev/latest/config/ or use `jj help -k config`.
+    [EOF]
+    [exit status: 1]
+    ");
+
+    work_dir
+        .run_jj([
+            "git",
+            "push",
+            "--bookmark",
+            "my-bookmark",
+            "--skip-check=no-fixup-commits",
+            "--skip-check=description-regex",
+        ])
+        .success();
+}
+
+#[test]
+fn test_git_push_drafts() {
+    let test_env = TestEnvironment::default();
+    set_up(&test_env);
+    test_env.add_config("remotes.origin.auto-track-bookmarks = '*'");
+    test_env.add_config("templates.commit_summary = 'description.first_line()'");
+    let work_dir = test_env.work_dir("local");
+    work_dir
+        .run_jj(["new", "bookmark1", "-m", "work in progress"])
+        .success();
+    work_dir.run_jj(["new", "-m", "more work"]).success();
+    work_dir
+        .run_jj(["bookmark", "create", "-r@", "my-bookmark"])
+        .success();
+    work_dir.run_jj(["draft", "set", "@-", "@"]).success();
+
+    let output = work_dir.run_jj(["git", "push", "-b", "my-bookmark"]);
+    insta::assert_snapshot!(output, @"
+    ------- stderr -------
+    Error: Won't push 2 commits since they are drafts
+    Hint: Draft commits:
+      more work
+      work in progress
+    Hint: Unmark them with `jj draft unset`, or use --allow-drafts to push them anyway.
+    [EOF]
+    [exit status: 1]
+    ");
+
+    work_dir.run_jj(["draft", "unset", "@-"]).success();
+    let output = work_dir.run_jj(["git", "push", "-b", "my-bookmark"]);
+    insta::assert_snapshot!(output, @"
+    ------- stderr -------
+    Error: Won't push 1 commits since they are drafts
+    Hint: Draft commits:
+      more work
+    Hint: Unmark them with `jj draft unset`, or use --allow-drafts to push them anyway.
+    [EOF]
+    [exit status: 1]
+    ");
+
+    let output = work_dir.run_jj([
+        "git",
+        "push",
+        "-b",
+        "my-bookmark",
+        "--allow-drafts",
+        "--quiet",
+    ]);
+    insta::assert_snapshot!(output, @"");
+}
+
 #[test]
 fn test_git_push_no_description_in_immutable() {
     let test_env = TestEnvironment::default();
@@ -1699,6 +1931,7 @@ fn test_git_push_no_description_in_immutable() {
     ------- stderr -------
     Error: Won't push commit 8d23abddc924 since it has no description
     Hint: Rejected commit: yqosqzyt 8d23abdd imm* | (empty) (no description set)
+    Hint: Use --skip-check=no-empty-description to push it anyway.
     [EOF]
     [exit status: 1]
     ");
@@ -1834,6 +2067,7 @@ fn test_git_push_missing_committer() {
     ------- stderr -------
     Error: Won't push commit 4bd3b55c7759 since it has no description and it has no author and/or committer set
     Hint: Rejected commit: kpqxywon 4bd3b55c missing-email* | (empty) (no description set)
+    Hint: Use --skip-check=no-empty-description to push it anyway.
     [EOF]
     [exit status: 1]
     ");
@@ -1917,6 +2151,80 @@ fn test_git_push_deleted() {
     ");
 }
 
+#[test]
+fn test_git_push_deleted_confirmation() {
+    let test_env = TestEnvironment::default();
+    set_up(&test_env);
+    test_env.add_config("git.max-push-deletions = 1");
+    let work_dir = test_env.work_dir("local");
+
+    work_dir
+        .run_jj(["bookmark", "delete", "bookmark1", "bookmark2"])
+        .success();
+    // A dry run doesn't ask for confirmation
+    let output = work_dir.run_jj(["git", "push", "--deleted", "--dry-run"]);
+    insta::assert_snapshot!(output, @"
+    ------- stderr -------
+    Changes to push to origin:
+      Delete bookmark bookmark1 from 9b2e76de3920
+      Delete bookmark bookmark2 from 38a204733702
+    Dry-run requested, not pushing.
+    [EOF]
+    ");
+    // Deletions are declined by default in non-interactive sessions
+    let output = work_dir.run_jj(["git", "push", "--deleted"]);
+    insta::assert_snapshot!(output, @"
+    ------- stderr -------
+    Changes to push to origin:
+      Delete bookmark bookmark1 from 9b2e76de3920
+      Delete bookmark bookmark2 from 38a204733702
+    Warning: The following 2 remote bookmarks will be deleted:
+      bookmark1@origin
+      bookmark2@origin
+    Delete these bookmarks from the remote? (yN): n
+    Error: Push canceled because of the bookmark deletions
+    Hint: Set `git.max-push-deletions` to a larger number, or 0, to push without confirmation.
+    [EOF]
+    [exit status: 1]
+    ");
+    let output = work_dir.run_jj_with(|cmd| {
+        force_interactive(cmd)
+            .args(["git", "push", "--deleted"])
+            .write_stdin("y\n")
+    });
+    insta::assert_snapshot!(output, @"
+    ------- stderr -------
+    Changes to push to origin:
+      Delete bookmark bookmark1 from 9b2e76de3920
+      Delete bookmark bookmark2 from 38a204733702
+    Warning: The following 2 remote bookmarks will be deleted:
+      bookmark1@origin
+      bookmark2@origin
+    Delete these bookmarks from the remote? (yN): [EOF]
+    ");
+    insta::assert_snapshot!(get_bookmark_output(&work_dir), @"");
+}
+
+#[test]
+fn test_git_push_deleted_protected_remote() {
+    let test_env = TestEnvironment::default();
+    set_up(&test_env);
+    test_env.add_config(r#"git.protect-remote-deletions = ["origin"]"#);
+    let work_dir = test_env.work_dir("local");
+
+    work_dir
+        .run_jj(["bookmark", "delete", "bookmark1"])
+        .success();
+    let output = work_dir.run_jj(["git", "push", "--deleted", "--dry-run"]);
+    insta::assert_snapshot!(output, @"
+    ------- stderr -------
+    Error: Refusing to delete bookmarks from protected remote origin: bookmark1
+    Hint: Remote origin is listed in `git.protect-remote-deletions`.
+    [EOF]
+    [exit status: 1]
+    ");
+}
+
 #[test]
 fn test_git_push_conflicting_bookmarks() {
     let test_env = TestEnvironment::default();
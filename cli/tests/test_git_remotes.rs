@@ -122,6 +122,83 @@ fn test_git_remotes() {
     "#);
 }
 
+#[test]
+fn test_git_remote_list_template() {
+    let test_env = TestEnvironment::default();
+    let git_repo = git::init(test_env.env_root().join("upstream"));
+    for bookmark in ["main", "feature"] {
+        git::add_commit(
+            &git_repo,
+            &format!("refs/heads/{bookmark}"),
+            "file",
+            bookmark.as_bytes(),
+            "message",
+            &[],
+        );
+    }
+
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+    work_dir
+        .run_jj(["git", "remote", "add", "origin", "../upstream"])
+        .success();
+    work_dir
+        .run_jj([
+            "git",
+            "remote",
+            "add",
+            "other",
+            "http://example.com/repo/other",
+        ])
+        .success();
+
+    let template =
+        r#"name ++ ": " ++ last_fetch_ref_count ++ " " ++ last_fetch_time.ago() ++ "\n""#;
+    let output = work_dir.run_jj(["git", "remote", "list", "-T", template]);
+    insta::assert_snapshot!(output, @"
+    origin: <Error: Remote has never been fetched from> <Error: Remote has never been fetched from>
+    other: <Error: Remote has never been fetched from> <Error: Remote has never been fetched from>
+    [EOF]
+    ");
+
+    work_dir
+        .run_jj(["git", "fetch", "--remote", "origin"])
+        .success();
+    let template = r#"
+        separate(" ",
+          name,
+          if(fetched, last_fetch_ref_count),
+          if(fetched, "fetched", "not fetched"),
+          if(pushed, "pushed", "not pushed"),
+        ) ++ "\n"
+    "#;
+    let output = work_dir.run_jj(["git", "remote", "list", "-T", template]);
+    insta::assert_snapshot!(output, @"
+    origin 2 fetched not pushed
+    other not fetched not pushed
+    [EOF]
+    ");
+
+    // The default template is unaffected by the records
+    let output = work_dir.run_jj(["git", "remote", "list"]);
+    insta::assert_snapshot!(output, @"
+    origin $TEST_ENV/upstream
+    other http://example.com/repo/other
+    [EOF]
+    ");
+
+    // The records follow renamed remotes
+    work_dir
+        .run_jj(["git", "remote", "rename", "origin", "upstream"])
+        .success();
+    let output = work_dir.run_jj(["git", "remote", "list", "-T", template]);
+    insta::assert_snapshot!(output, @"
+    other not fetched not pushed
+    upstream 2 fetched not pushed
+    [EOF]
+    ");
+}
+
 #[test]
 fn test_git_remote_add() {
     let test_env = TestEnvironment::default();
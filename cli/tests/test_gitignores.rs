@@ -153,3 +153,53 @@ fn test_gitignores_ignored_file_in_target_commit() {
     [EOF]
     ");
 }
+
+#[test]
+fn test_jjignore() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+    test_env.add_config(r#"ui.excludes-file = "~/jj-ignores""#);
+    test_env.add_config(r#"fileset-aliases.'objects' = 'glob:"**/*.o"'"#);
+    std::fs::write(test_env.home_dir().join("jj-ignores"), "personal\n").unwrap();
+
+    work_dir.write_file(".jjignore", "# Build outputs\ntarget\n\nobjects\n");
+    work_dir.write_file("main.c", "contents");
+    work_dir.write_file("main.o", "contents");
+    work_dir.write_file("sub/lib.o", "contents");
+    work_dir.write_file("target/out", "contents");
+    work_dir.write_file("personal", "contents");
+    let output = work_dir.run_jj(["diff", "-s"]);
+    insta::assert_snapshot!(output, @"
+    A .jjignore
+    A main.c
+    [EOF]
+    ");
+
+    // Ignored files can be tracked explicitly.
+    work_dir
+        .run_jj(["file", "track", "--include-ignored", "main.o"])
+        .success();
+    let output = work_dir.run_jj(["diff", "-s"]);
+    insta::assert_snapshot!(output, @"
+    A .jjignore
+    A main.c
+    A main.o
+    [EOF]
+    ");
+
+    work_dir.write_file(".jjignore", "unknown_fn()\n");
+    let output = work_dir.run_jj(["diff", "-s"]);
+    insta::assert_snapshot!(output.normalize_backslash(), @"
+    ------- stderr -------
+    Error: Failed to parse $TEST_ENV/repo/.jjignore
+    Caused by:  --> 1:1
+      |
+    1 | unknown_fn()
+      | ^--------^
+      |
+      = Function `unknown_fn` doesn't exist
+    [EOF]
+    [exit status: 1]
+    ");
+}
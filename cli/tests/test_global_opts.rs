@@ -16,6 +16,7 @@ use std::ffi::OsString;
 
 use indoc::indoc;
 use itertools::Itertools as _;
+use jj_lib::lock::FileLock;
 use regex::Regex;
 
 use crate::common::TestEnvironment;
@@ -226,6 +227,141 @@ fn test_ignore_working_copy() {
     ");
 }
 
+#[test]
+fn test_dry_run_unsupported_command() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+    work_dir.run_jj(["new"]).success();
+
+    let output = work_dir.run_jj(["--dry-run", "op", "abandon", "..@-"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Error: `jj operation abandon` doesn't support --dry-run
+    [EOF]
+    [exit status: 2]
+    ");
+    let output = work_dir.run_jj(["--dry-run", "config", "set", "--repo", "user.name", "X"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Error: `jj config set` doesn't support --dry-run
+    [EOF]
+    [exit status: 2]
+    ");
+
+    // Nothing was changed
+    let output = work_dir.run_jj(["op", "log", "-Tdescription"]);
+    insta::assert_snapshot!(output, @r"
+    @  new empty commit
+    ○  add workspace 'default'
+    ○
+    [EOF]
+    ");
+    let output = work_dir.run_jj(["config", "list", "--repo"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Warning: No config to list
+    [EOF]
+    ");
+}
+
+#[test]
+fn test_no_snapshot_if_busy() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+
+    work_dir.write_file("file", "initial");
+    let output = work_dir.run_jj(["log", "-T", "commit_id"]);
+    insta::assert_snapshot!(output, @"
+    @  82a10a4d9ef783fd68b661f40ce10dd80d599d9e
+    ◆  0000000000000000000000000000000000000000
+    [EOF]
+    ");
+
+    let marker_path = work_dir.root().join(".jj/working_copy/last_snapshot");
+    assert!(!marker_path.exists());
+
+    // While another process is snapshotting, the snapshot is skipped
+    work_dir.write_file("file", "modified");
+    let lock = FileLock::lock(work_dir.root().join(".jj/snapshot.lock")).unwrap();
+    let output_again = work_dir.run_jj(["log", "-T", "commit_id", "--no-snapshot-if-busy"]);
+    assert_eq!(output_again, output);
+    drop(lock);
+
+    // Once the lock is released, the snapshot is taken. The snapshot time is
+    // only recorded if the cooldown is enabled.
+    let output = work_dir.run_jj([
+        "log",
+        "-T",
+        "commit_id",
+        "--no-snapshot-if-busy",
+        "--config=snapshot.cooldown-seconds=3600",
+    ]);
+    assert!(marker_path.exists());
+    insta::assert_snapshot!(output, @"
+    @  00fc09f48ccf5c8b025a0f93b0ec3b0e4294a598
+    ◆  0000000000000000000000000000000000000000
+    [EOF]
+    ");
+
+    // Within the cooldown, the snapshot is skipped only with
+    // --no-snapshot-if-busy
+    work_dir.write_file("file", "modified again");
+    let output_again = work_dir.run_jj([
+        "log",
+        "-T",
+        "commit_id",
+        "--no-snapshot-if-busy",
+        "--config=snapshot.cooldown-seconds=3600",
+    ]);
+    assert_eq!(output_again, output);
+    let output_again = work_dir.run_jj([
+        "log",
+        "-T",
+        "commit_id",
+        "--config=snapshot.cooldown-seconds=3600",
+    ]);
+    assert_ne!(output_again, output);
+}
+
+#[test]
+fn test_read_only_command_during_checkout() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+
+    work_dir.write_file("file", "initial");
+    let output = work_dir.run_jj(["log", "-T", "commit_id"]);
+    insta::assert_snapshot!(output, @"
+    @  82a10a4d9ef783fd68b661f40ce10dd80d599d9e
+    ◆  0000000000000000000000000000000000000000
+    [EOF]
+    ");
+
+    // While another process is checking out, read-only commands skip the
+    // snapshot instead of waiting
+    work_dir.write_file("file", "modified");
+    let lock = FileLock::lock(work_dir.root().join(".jj/checkout.lock")).unwrap();
+    let output = work_dir.run_jj(["log", "-T", "commit_id"]);
+    insta::assert_snapshot!(output, @"
+    @  82a10a4d9ef783fd68b661f40ce10dd80d599d9e
+    ◆  0000000000000000000000000000000000000000
+    [EOF]
+    ------- stderr -------
+    Warning: Skipped snapshot because the working copy is being updated by another process
+    [EOF]
+    ");
+    drop(lock);
+
+    let output = work_dir.run_jj(["log", "-T", "commit_id"]);
+    insta::assert_snapshot!(output, @"
+    @  00fc09f48ccf5c8b025a0f93b0ec3b0e4294a598
+    ◆  0000000000000000000000000000000000000000
+    [EOF]
+    ");
+}
+
 #[test]
 fn test_repo_arg_with_git_init() {
     let test_env = TestEnvironment::default();
@@ -1093,6 +1229,114 @@ fn test_no_user_configured() {
     "#);
 }
 
+#[test]
+fn test_include_hidden() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+    work_dir.run_jj(["new", "-m", "hidden"]).success();
+    let output = work_dir
+        .run_jj(["log", "--no-graph", "-r@", "-Tchange_id"])
+        .success();
+    let change_id = output.stdout.into_raw();
+    work_dir.run_jj(["abandon"]).success();
+
+    // Hidden commit isn't resolved by default
+    let output = work_dir.run_jj(["log", "--no-graph", "-r", &change_id, "-Tdescription"]);
+    assert!(!output.status.success());
+
+    let output = work_dir.run_jj([
+        "log",
+        "--include-hidden",
+        "--no-graph",
+        "-r",
+        &change_id,
+        "-Tdescription",
+    ]);
+    insta::assert_snapshot!(output, @"
+    hidden
+    [EOF]
+    ");
+
+    let output = work_dir.run_jj(["log", "--no-graph", "-rhidden()", "-Tdescription"]);
+    insta::assert_snapshot!(output, @"
+    hidden
+    [EOF]
+    ");
+}
+
+#[test]
+fn test_identity() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+    test_env.add_config(
+        r#"
+        [identities.work]
+        name = "Work User"
+        email = "work.user@example.com"
+
+        [identities.oss]
+        email = "oss.user@example.com"
+        "#,
+    );
+    let template = r#"author ++ " / " ++ committer ++ "\n""#;
+
+    // Only the committer of an existing commit changes
+    work_dir
+        .run_jj(["--identity=work", "describe", "-m", "work"])
+        .success();
+    let output = work_dir.run_jj(["log", "--no-graph", "-r@", "-T", template]);
+    insta::assert_snapshot!(output, @"
+    Test User <test.user@example.com> / Work User <work.user@example.com>
+    [EOF]
+    ");
+    work_dir
+        .run_jj(["--identity=work", "metaedit", "--update-author"])
+        .success();
+    let output = work_dir.run_jj(["log", "--no-graph", "-r@", "-T", template]);
+    insta::assert_snapshot!(output, @"
+    Work User <work.user@example.com> / Work User <work.user@example.com>
+    [EOF]
+    ");
+
+    // Default identity can be selected by config, unset fields fall back to
+    // the user settings
+    work_dir
+        .run_jj(["new", "-m", "oss", "--config=user.identity=oss"])
+        .success();
+    let output = work_dir.run_jj(["log", "--no-graph", "-r@", "-T", template]);
+    insta::assert_snapshot!(output, @"
+    Test User <oss.user@example.com> / Test User <oss.user@example.com>
+    [EOF]
+    ");
+
+    // --identity overrides the configured identity
+    work_dir
+        .run_jj([
+            "new",
+            "-m",
+            "work again",
+            "--config=user.identity=oss",
+            "--identity=work",
+        ])
+        .success();
+    let output = work_dir.run_jj(["log", "--no-graph", "-r@", "-T", template]);
+    insta::assert_snapshot!(output, @"
+    Work User <work.user@example.com> / Work User <work.user@example.com>
+    [EOF]
+    ");
+
+    let output = work_dir.run_jj(["--identity=unknown", "log"]);
+    insta::assert_snapshot!(output, @"
+    ------- stderr -------
+    Config error: Value not found for identities.unknown
+    For help, see https://docs.jj-vcs.dev/latest/config/ or use `jj help -k config`.
+    [EOF]
+    [exit status: 1]
+    ");
+}
+
 #[test]
 fn test_help() {
     // Test that global options are separated out in the help output
@@ -1118,15 +1362,21 @@ fn test_help() {
     Global Options:
       -R, --repository <REPOSITORY>      Path to repository to operate on
           --ignore-working-copy          Don't snapshot the working copy, and don't update it
+          --no-snapshot-if-busy          Skip the snapshot if another process is snapshotting
           --ignore-immutable             Allow rewriting immutable commits
+          --allow-large-change           Allow operations exceeding the configured change limits
+          --dry-run                      Show what the command would change, without changing the repo
+          --include-hidden               Allow change IDs to refer to hidden commits
           --at-operation <AT_OPERATION>  Operation to load the repo at [aliases: --at-op]
           --debug                        Enable debug logging
           --color <WHEN>                 When to colorize output [possible values: always, never, debug,
                                          auto]
+          --error-format <FORMAT>        How to print errors [possible values: text, json]
           --quiet                        Silence non-primary command output
           --no-pager                     Disable the pager
           --config <NAME=VALUE>          Additional configuration options (can be repeated)
           --config-file <PATH>           Additional configuration files (can be repeated)
+          --identity <NAME>              Identity to use for authoring, committing, and signing
     [EOF]
     ");
 }
@@ -1151,3 +1401,139 @@ fn test_debug_logging_enabled() {
     // Luckily, insta will print this in color when reviewing.
     insta::assert_snapshot!(log_line, @"[32m INFO[0m [2mjj_cli::cli_util[0m[2m:[0m debug logging enabled");
 }
+
+#[test]
+fn test_translated_messages() {
+    let mut test_env = TestEnvironment::default();
+    let locale_dir = test_env.env_root().join("locale");
+    std::fs::create_dir(&locale_dir).unwrap();
+    std::fs::write(
+        locale_dir.join("de.toml"),
+        indoc! {r#"
+            [heading]
+            error = "Fehler: "
+            [bookmark.rename]
+            no-such-bookmark = "Kein Bookmark: {old_bookmark}"
+        "#},
+    )
+    .unwrap();
+    test_env.add_env_var("JJ_LOCALE_DIR", &locale_dir);
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+
+    // English is used by default
+    let output = work_dir.run_jj(["bookmark", "rename", "foo", "bar"]);
+    insta::assert_snapshot!(output, @"
+    ------- stderr -------
+    Error: No such bookmark: foo
+    [EOF]
+    [exit status: 1]
+    ");
+
+    // Regional variant falls back to the base language
+    let output = work_dir.run_jj([
+        "bookmark",
+        "rename",
+        "foo",
+        "bar",
+        "--config=ui.language=de_DE.UTF-8",
+    ]);
+    insta::assert_snapshot!(output, @"
+    ------- stderr -------
+    Fehler: Kein Bookmark: foo
+    [EOF]
+    [exit status: 1]
+    ");
+
+    // Untranslated messages are printed in English
+    work_dir
+        .run_jj(["bookmark", "create", "-r@", "foo"])
+        .success();
+    let output = work_dir.run_jj([
+        "bookmark",
+        "create",
+        "-r@",
+        "foo",
+        "--config=ui.language=de",
+    ]);
+    insta::assert_snapshot!(output, @"
+    ------- stderr -------
+    Fehler: Bookmark already exists: foo
+    Hint: Use `jj bookmark set` to update it.
+    [EOF]
+    [exit status: 1]
+    ");
+
+    // Broken catalog is reported, and English is used instead
+    std::fs::write(locale_dir.join("fr.toml"), "invalid").unwrap();
+    let output = work_dir.run_jj([
+        "bookmark",
+        "rename",
+        "baz",
+        "qux",
+        "--config=ui.language=fr",
+    ]);
+    insta::assert_snapshot!(output, @"
+    ------- stderr -------
+    Warning: Failed to parse message catalog $TEST_ENV/locale/fr.toml
+    Caused by: TOML parse error at line 1, column 8
+      |
+    1 | invalid
+      |        ^
+    key with no value, expected `=`
+
+    Error: No such bookmark: baz
+    [EOF]
+    [exit status: 1]
+    ");
+}
+
+#[test]
+fn test_error_format_json() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+    let parse_error = |output: &crate::common::CommandOutput| -> serde_json::Value {
+        serde_json::from_str(output.stderr.raw()).unwrap()
+    };
+
+    let output = work_dir.run_jj(["log", "-rfoo", "--error-format=json"]);
+    assert_eq!(output.status.code(), Some(1));
+    let error = parse_error(&output);
+    assert_eq!(error["code"], "revset.no-such-revision");
+    assert_eq!(error["message"], "Revision `foo` doesn't exist");
+    assert_eq!(error["exit_code"], 1);
+
+    let output = work_dir.run_jj(["describe", "-rroot()", "-mfoo", "--error-format=json"]);
+    let error = parse_error(&output);
+    assert_eq!(error["code"], "commit.immutable");
+    assert_eq!(
+        error["message"],
+        "The root commit 000000000000 is immutable"
+    );
+    assert_eq!(error["sources"], serde_json::json!([]));
+    assert_eq!(error["hints"], serde_json::json!([]));
+
+    // Sources and hints are included, and the format can be set by config
+    let output = work_dir.run_jj(["log", "-r(", "--config=ui.error-format=json"]);
+    let error = parse_error(&output);
+    assert_eq!(error["code"], "revset.parse");
+    assert!(!error["sources"].as_array().unwrap().is_empty());
+    assert!(!error["hints"].as_array().unwrap().is_empty());
+
+    let output = work_dir.run_jj(["log", "--error-format=json", "--config=templates.log=1"]);
+    assert_eq!(output.status.code(), Some(1));
+    let error = parse_error(&output);
+    assert_eq!(error["code"], "config.invalid");
+
+    // Errors without a specific code fall back to the kind of error
+    let output = work_dir.run_jj(["log", "--error-format=json", "--no-such-flag"]);
+    assert_eq!(output.status.code(), Some(2));
+    let error = parse_error(&output);
+    assert_eq!(error["code"], "cli");
+
+    // Help isn't an error
+    let output = work_dir.run_jj(["log", "--error-format=json", "--help"]);
+    assert!(output.status.success());
+    assert!(output.stderr.raw().is_empty());
+}
@@ -0,0 +1,100 @@
+// Copyright 2026 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::common::TestEnvironment;
+use crate::common::force_interactive;
+
+#[test]
+fn test_heads_list_and_prune() {
+    let test_env = TestEnvironment::default();
+    test_env.add_config("templates.commit_summary = 'description.first_line()'");
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+    work_dir.run_jj(["describe", "-m", "main"]).success();
+    work_dir
+        .run_jj(["bookmark", "create", "-r@", "main"])
+        .success();
+    work_dir.run_jj(["new", "main", "-m", "a1"]).success();
+    work_dir.run_jj(["new", "-m", "a2"]).success();
+    work_dir.run_jj(["new", "main", "-m", "b1"]).success();
+    work_dir.run_jj(["new", "main", "-m", "c1"]).success();
+    work_dir
+        .run_jj(["bookmark", "create", "-r@", "c"])
+        .success();
+    work_dir.run_jj(["new", "main", "-m", "wip"]).success();
+
+    // Heads pointed to by a bookmark or a working copy aren't anonymous
+    let template = r#"description.first_line() ++ "\n""#;
+    let output = work_dir.run_jj(["heads", "-T", template]);
+    insta::assert_snapshot!(output, @"
+    b1
+    a2
+    [EOF]
+    ");
+    // The default template includes the age, which changes over time
+    work_dir.run_jj(["heads"]).success();
+
+    let output = work_dir.run_jj(["heads", "prune", "--older-than", "100000w"]);
+    insta::assert_snapshot!(output, @"
+    ------- stderr -------
+    No anonymous heads to prune.
+    [EOF]
+    ");
+
+    // Heads are kept by default in non-interactive sessions
+    let output = work_dir.run_jj(["heads", "prune", "--older-than", "30d"]);
+    insta::assert_snapshot!(output, @"
+    ------- stderr -------
+    Abandon b1? (yN): n
+    Abandon a2? (yN): n
+    No revisions to abandon.
+    [EOF]
+    ");
+
+    // Exclusive ancestors of a pruned head are abandoned with it
+    let output = work_dir.run_jj_with(|cmd| {
+        force_interactive(cmd)
+            .args(["heads", "prune", "--older-than", "30d"])
+            .write_stdin("n\ny\n")
+    });
+    insta::assert_snapshot!(output, @"
+    ------- stderr -------
+    Abandon b1? (yN): Abandon a2? (yN): Abandoned 2 commits:
+      a2
+      a1
+    [EOF]
+    ");
+    let output = work_dir.run_jj(["heads", "-T", template]);
+    insta::assert_snapshot!(output, @"
+    b1
+    [EOF]
+    ");
+}
+
+#[test]
+fn test_heads_prune_invalid_age() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+
+    let output = work_dir.run_jj(["heads", "prune", "--older-than", "30"]);
+    insta::assert_snapshot!(output, @"
+    ------- stderr -------
+    error: invalid value '30' for '--older-than <AGE>': Expected a number with one of the `m`, `h`, `d`, or `w` suffixes
+
+    For more information, try '--help'.
+    [EOF]
+    [exit status: 2]
+    ");
+}
@@ -169,7 +169,7 @@ fn test_help_keyword() {
     ------- stderr -------
     error: unrecognized subcommand 'revsets'
 
-      tip: some similar subcommands exist: 'resolve', 'prev', 'restore', 'rebase', 'revert'
+      tip: some similar subcommands exist: 'fileset', 'resolve', 'prev', 'restore', 'rebase', 'revert'
 
     Usage: jj [OPTIONS] [COMMAND]
 
@@ -536,6 +536,24 @@ fn test_log_shortest_accessors() {
     [EOF]
     ");
 
+    // Earlier revsets get shorter prefixes
+    test_env.add_config(r#"revsets.short-prefixes = ["@--", "(@----)::"]"#);
+    insta::assert_snapshot!(
+        render("::@", r#"format_id(change_id) ++ " " ++ format_id(commit_id) ++ "\n""#), @"
+    w[qnwkozpkust] c[2b4c0bb3362]
+    km[kuslswpqwq] 74[fcd50c0643]
+    k[pqxywonksrl] 9[7dcaada9b8d]
+    z[nkkpsqqskkl] 78[c03ab2235b]
+    y[ostqsxwqrlt] 4[01119280761]
+    vr[uxwmqvtpmx] bc9[e8942b459]
+    yq[osqzytrlsw] 28[edbc9658ef]
+    ro[yxmykxtrkr] af[3e6a27a1d0]
+    mz[vwutvlkqwt] 04[6c6a1df762]
+    qpv[untsmwlqt] 82[16f646c36d]
+    zzz[zzzzzzzzz] 00[0000000000]
+    [EOF]
+    ");
+
     // Can disable short prefixes by setting to empty string
     test_env.add_config(r#"revsets.short-prefixes = """#);
     insta::assert_snapshot!(
@@ -806,6 +824,16 @@ fn test_log_prefix_highlight_counts_hidden_commits() {
     ~
     [EOF]
     ");
+    // Only the visible revisions are listed as candidates
+    insta::assert_snapshot!(work_dir.run_jj(["show", "8"]), @"
+    ------- stderr -------
+    Error: Commit ID prefix `8` is ambiguous
+    Hint: The prefix `8` matches these revisions:
+      wqnwkozp 88e8407a (empty) (no description set)
+      qpvuntsm 8216f646 original | initial
+    [EOF]
+    [exit status: 1]
+    ");
 }
 
 #[test]
@@ -896,6 +924,80 @@ fn test_log_reversed() {
     ");
 }
 
+#[test]
+fn test_log_order() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+
+    work_dir.run_jj(["describe", "-m", "a1"]).success();
+    work_dir.run_jj(["new", "root()", "-m", "b1"]).success();
+    work_dir
+        .run_jj(["new", "subject(a1)", "-m", "a2"])
+        .success();
+    work_dir
+        .run_jj(["new", "subject(b1)", "-m", "b2"])
+        .success();
+    // Rewriting b1 updates the committer dates of b1 and b2, but not their
+    // author dates
+    work_dir
+        .run_jj([
+            "metaedit",
+            "-r=subject(b1)",
+            "--author-timestamp=2000-01-01T00:00:00Z",
+        ])
+        .success();
+
+    let output = work_dir.run_jj(["log", "-T", "description"]);
+    insta::assert_snapshot!(output, @"
+    @  b2
+    ○  b1
+    │ ○  a2
+    │ ○  a1
+    ├─╯
+    ◆
+    [EOF]
+    ");
+
+    let output = work_dir.run_jj(["log", "-T", "description", "--order=date-author"]);
+    insta::assert_snapshot!(output, @"
+    @  b2
+    │ ○  a2
+    │ ○  a1
+    ○ │  b1
+    ├─╯
+    ◆
+    [EOF]
+    ");
+
+    let output = work_dir.run_jj([
+        "log",
+        "-T",
+        "description",
+        "--order=date-author",
+        "--no-graph",
+        "--reversed",
+    ]);
+    insta::assert_snapshot!(output, @"
+    b1
+    a1
+    a2
+    b2
+    [EOF]
+    ");
+
+    // Committer dates of b1 and b2 are the same, so their parent-child order
+    // is kept
+    let output = work_dir.run_jj(["log", "-T", "description", "--order=date", "--no-graph"]);
+    insta::assert_snapshot!(output, @"
+    b2
+    b1
+    a2
+    a1
+    [EOF]
+    ");
+}
+
 #[test]
 fn test_log_filtered_by_path() {
     let test_env = TestEnvironment::default();
@@ -1688,6 +1790,53 @@ fn test_elided() {
     ");
 }
 
+#[test]
+fn test_log_elide_linear() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+
+    work_dir.run_jj(["describe", "-m", "A"]).success();
+    for name in ["B", "C", "D", "E", "F", "G"] {
+        work_dir.run_jj(["new", "-m", name]).success();
+    }
+    work_dir
+        .run_jj(["bookmark", "create", "-r", "subject(D)", "mark"])
+        .success();
+
+    let get_log = |args: &[&str]| {
+        let template = r#"description ++ "\n""#;
+        let base_args = ["log", "-r", "::", "-T", template, "--elide-linear"];
+        work_dir.run_jj([&base_args[..], args].concat())
+    };
+
+    insta::assert_snapshot!(get_log(&[]), @"
+    @  G
+    │
+    ~  · 2 commits ·
+    ○  D
+    │
+    ~  · 3 commits ·
+    ◆
+    [EOF]
+    ");
+
+    // A single elided commit
+    let keep_config = "--config=revsets.log-elide-linear-keep='@ | subject(C) | subject(A)'";
+    insta::assert_snapshot!(get_log(&[keep_config]), @"
+    @  G
+    │
+    ~  · 3 commits ·
+    ○  C
+    │
+    ~  · 1 commit ·
+    ○  A
+    │
+    ◆
+    [EOF]
+    ");
+}
+
 #[test]
 fn test_log_with_custom_symbols() {
     // Test that elided commits are shown as synthetic nodes.
@@ -387,6 +387,7 @@ fn test_next_fails_on_bookmarking_children_no_stdin() {
     2: rlvkpnrz 9439bf06 (empty) second
     q: quit the prompt
     Error: Cannot prompt for input since the output is not connected to a terminal
+    Hint: Use `jj next --auto-first` to choose the first candidate
     [EOF]
     [exit status: 1]
     ");
@@ -455,6 +456,38 @@ fn test_next_choose_bookmarking_child() {
     ");
 }
 
+#[test]
+fn test_next_auto_first_child() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+    work_dir.run_jj(["commit", "-m", "first"]).success();
+    work_dir.run_jj(["commit", "-m", "second"]).success();
+    work_dir.run_jj(["new", "@--"]).success();
+    work_dir.run_jj(["commit", "-m", "third"]).success();
+    work_dir.run_jj(["new", "@--"]).success();
+
+    // Advance the working copy commit without prompting.
+    work_dir.run_jj(["next", "--auto-first"]).success();
+    let output = work_dir.run_jj(["log", "--no-graph", "-r@-", "-Tdescription"]);
+    insta::assert_snapshot!(output, @"
+    third
+    [EOF]
+    ");
+
+    // Same for prev on a merge commit.
+    work_dir
+        .run_jj(["new", "subject(second)", "subject(third)"])
+        .success();
+    work_dir.run_jj(["new"]).success();
+    work_dir.run_jj(["prev", "--auto-first"]).success();
+    let output = work_dir.run_jj(["log", "--no-graph", "-r@-", "-Tdescription"]);
+    insta::assert_snapshot!(output, @"
+    third
+    [EOF]
+    ");
+}
+
 #[test]
 fn test_prev_on_merge_commit() {
     let test_env = TestEnvironment::default();
@@ -84,6 +84,75 @@ fn test_revert_rewrite_with_child() {
     ");
 }
 
+#[test]
+fn test_revert_range() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+
+    work_dir.run_jj(["describe", "-m", "initial"]).success();
+    work_dir.run_jj(["describe", "-m", "second"]).success();
+    work_dir.run_jj(["describe", "-m", "third"]).success();
+    let output = work_dir.run_jj(["op", "revert", "@..@"]);
+    insta::assert_snapshot!(output, @"
+    ------- stderr -------
+    Error: The range of operations to revert is empty
+    [EOF]
+    [exit status: 1]
+    ");
+
+    let output = work_dir.run_jj(["op", "revert", "@--..@"]).success();
+    assert!(output.stderr.raw().starts_with("Reverted 2 operations:\n"));
+    let output = work_dir.run_jj(["log", "-T", "description"]);
+    insta::assert_snapshot!(output, @"
+    @  initial
+    ◆
+    [EOF]
+    ");
+    let output = work_dir.run_jj(["op", "log", "-n1", "-T", "description.first_line()"]);
+    assert!(output.stdout.raw().contains("revert operations "));
+}
+
+#[test]
+fn test_revert_range_with_later_bookmark_change() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+
+    work_dir
+        .run_jj(["bookmark", "create", "-r@", "foo"])
+        .success();
+    work_dir.run_jj(["new"]).success();
+    work_dir.run_jj(["bookmark", "set", "-r@", "foo"]).success();
+    work_dir.run_jj(["new"]).success();
+    work_dir.run_jj(["bookmark", "set", "-r@", "foo"]).success();
+
+    // Revert the first `jj new` and the first move of the bookmark. The bookmark
+    // was moved again afterwards, so it becomes conflicted.
+    let output = work_dir.run_jj(["op", "revert", "@----..@--"]).success();
+    let stderr = output.stderr.raw();
+    assert!(stderr.contains(
+        "Warning: Later operations also changed these bookmarks, which are now conflicted: foo\n"
+    ));
+    assert!(stderr.contains(
+        "Hint: Use `jj bookmark resolve` to resolve them, or revert the later operations too.\n"
+    ));
+    assert!(stderr.contains("Reverted 2 operations:\n"));
+    let output = work_dir.run_jj([
+        "bookmark",
+        "list",
+        "-T",
+        r#"name ++ " " ++ conflict ++ "\n""#,
+    ]);
+    insta::assert_snapshot!(output, @"
+    foo true
+    [EOF]
+    ------- stderr -------
+    Hint: Some bookmarks have conflicts. Use `jj bookmark set <name> -r <rev>` to resolve.
+    [EOF]
+    ");
+}
+
 #[test]
 fn test_git_push_revert() {
     let test_env = TestEnvironment::default();
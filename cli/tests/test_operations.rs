@@ -355,6 +355,154 @@ fn test_op_log_reversed() {
     ");
 }
 
+#[test]
+fn test_op_log_workspace() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "main"]).success();
+    let main_dir = test_env.work_dir("main");
+    let secondary_dir = test_env.work_dir("secondary");
+    main_dir
+        .run_jj(["workspace", "add", "../secondary"])
+        .success();
+    main_dir.run_jj(["new", "-m", "main"]).success();
+    secondary_dir.run_jj(["new", "-m", "secondary"]).success();
+    let template = r#"description ++ "\n""#;
+
+    let output = main_dir.run_jj(["op", "log", "--no-graph", "-T", template]);
+    insta::assert_snapshot!(output, @"
+    new empty commit
+    new empty commit
+    create initial working-copy commit in workspace secondary
+    add workspace 'secondary'
+    add workspace 'default'
+
+    [EOF]
+    ");
+
+    let output = main_dir.run_jj([
+        "op",
+        "log",
+        "--no-graph",
+        "-T",
+        template,
+        "--workspace=secondary",
+    ]);
+    insta::assert_snapshot!(output, @"
+    new empty commit
+    create initial working-copy commit in workspace secondary
+    add workspace 'secondary'
+    [EOF]
+    ");
+
+    let output = main_dir.run_jj([
+        "op",
+        "log",
+        "--no-graph",
+        "-T",
+        template,
+        "--workspace=default",
+        "--limit=1",
+    ]);
+    insta::assert_snapshot!(output, @"
+    new empty commit
+    [EOF]
+    ");
+
+    let output = main_dir.run_jj(["op", "log", "-T", template, "--graph-by-workspace"]);
+    insta::assert_snapshot!(output, @"
+    @  new empty commit
+    ╷ ○  new empty commit
+    ○ ╷  create initial working-copy commit in workspace secondary
+    ○ ╷  add workspace 'secondary'
+      ○  add workspace 'default'
+    ○
+    [EOF]
+    ");
+
+    // Nodes are colored per workspace
+    let output = main_dir.run_jj([
+        "op",
+        "log",
+        "-T",
+        template,
+        "--graph-by-workspace",
+        "--color=debug",
+    ]);
+    insta::assert_snapshot!(output, @"
+    [1m[38;5;2m<<workspace_lane_0 op_log operation node current_operation::@>>[0m  <<op_log operation description::new empty commit>><<op_log operation::>>
+    ╷ [38;5;5m<<workspace_lane_1 op_log operation node::○>>[39m  <<op_log operation description::new empty commit>><<op_log operation::>>
+    [38;5;4m<<workspace_lane_0 op_log operation node::○>>[39m ╷  <<op_log operation description::create initial working-copy commit in workspace secondary>><<op_log operation::>>
+    [38;5;4m<<workspace_lane_0 op_log operation node::○>>[39m ╷  <<op_log operation description::add workspace 'secondary'>><<op_log operation::>>
+      [38;5;5m<<workspace_lane_1 op_log operation node::○>>[39m  <<op_log operation description::add workspace 'default'>><<op_log operation::>>
+    <<op_log operation node::○>>  <<op_log operation::>>
+    [EOF]
+    ");
+
+    let output = main_dir.run_jj([
+        "op",
+        "log",
+        "-T",
+        template,
+        "--graph-by-workspace",
+        "--no-graph",
+    ]);
+    insta::assert_snapshot!(output, @"
+    ------- stderr -------
+    error: the argument '--graph-by-workspace' cannot be used with '--no-graph'
+
+    Usage: jj operation log --template <TEMPLATE> --graph-by-workspace
+
+    For more information, try '--help'.
+    [EOF]
+    [exit status: 2]
+    ");
+}
+
+#[test]
+fn test_op_log_diff_stat() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+    work_dir.write_file("file1", "foo\n");
+    work_dir.write_file("file2", "bar\n");
+    work_dir
+        .run_jj(["bookmark", "create", "-r@", "foo"])
+        .success();
+    work_dir.run_jj(["new"]).success();
+    work_dir.run_jj(["abandon", "@-"]).success();
+
+    let hash_re = Regex::new(r"[0-9a-f]{40}").unwrap();
+    let template = r#"description.first_line() ++ "\n""#;
+    let output = work_dir
+        .run_jj(["op", "log", "--no-graph", "--diff-stat", "-T", template])
+        .normalize_stdout_with(|s| hash_re.replace_all(&s, "[HASH]").into_owned());
+    insta::assert_snapshot!(output, @"
+    abandon commit [HASH]
+    Impact: 1 commit added, 2 commits hidden, 1 bookmark changed, 2 files changed in working copies
+    new empty commit
+    Impact: 1 commit added
+    create bookmark foo pointing to commit [HASH]
+    Impact: 1 bookmark changed
+    snapshot working copy
+    Impact: 1 commit added, 1 commit hidden, 2 files changed in working copies
+    add workspace 'default'
+    Impact: 1 commit added
+
+    [EOF]
+    ");
+
+    let output = work_dir
+        .run_jj(["op", "log", "--diff-stat", "-T", template, "-n2"])
+        .normalize_stdout_with(|s| hash_re.replace_all(&s, "[HASH]").into_owned());
+    insta::assert_snapshot!(output, @"
+    @  abandon commit [HASH]
+    │  Impact: 1 commit added, 2 commits hidden, 1 bookmark changed, 2 files changed in working copies
+    ○  new empty commit
+    │  Impact: 1 commit added
+    [EOF]
+    ");
+}
+
 #[test]
 fn test_op_log_no_graph_null_terminated() {
     let test_env = TestEnvironment::default();
@@ -3130,6 +3278,127 @@ fn test_op_log_anonymize() {
     ");
 }
 
+#[test]
+fn test_op_tag() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+    work_dir.run_jj(["describe", "-m", "before"]).success();
+
+    let op_re = Regex::new(
+        r"[0-9a-f]{12} \(\d{4}-\d{2}-\d{2} \d{2}:\d{2}:\d{2}\) describe commit [0-9a-f]{40}",
+    )
+    .unwrap();
+    let normalize = |s: String| op_re.replace_all(&s, "[OP]").into_owned();
+    let output = work_dir
+        .run_jj(["op", "tag", "saved"])
+        .normalize_stderr_with(normalize);
+    insta::assert_snapshot!(output, @"
+    ------- stderr -------
+    Created operation tag saved at [OP]
+    [EOF]
+    ");
+    let output = work_dir.run_jj(["op", "tag", "saved"]);
+    insta::assert_snapshot!(output, @"
+    ------- stderr -------
+    Error: Operation tag saved already exists
+    Hint: Use `jj op tag delete saved` first to move the tag.
+    [EOF]
+    [exit status: 1]
+    ");
+    let output = work_dir.run_jj(["op", "tag", "abc123"]);
+    insta::assert_snapshot!(output, @"
+    ------- stderr -------
+    error: invalid value 'abc123' for '<NAME>': Tag name must not be a hexadecimal string, which looks like an operation ID
+
+    For more information, try '--help'.
+    [EOF]
+    [exit status: 2]
+    ");
+    let output = work_dir
+        .run_jj(["op", "tag", "list"])
+        .normalize_stdout_with(normalize);
+    insta::assert_snapshot!(output, @"
+    saved: [OP]
+    [EOF]
+    ");
+
+    // The tag can be used in place of an operation ID
+    work_dir.run_jj(["describe", "-m", "after"]).success();
+    let output = work_dir.run_jj([
+        "--at-op=saved",
+        "log",
+        "--no-graph",
+        "-r@",
+        "-T=description",
+    ]);
+    insta::assert_snapshot!(output, @"
+    before
+    [EOF]
+    ");
+    work_dir.run_jj(["op", "restore", "saved"]).success();
+    let output = work_dir.run_jj(["log", "--no-graph", "-r@", "-T=description"]);
+    insta::assert_snapshot!(output, @"
+    before
+    [EOF]
+    ");
+
+    let output = work_dir.run_jj(["op", "tag", "delete", "saved", "unknown"]);
+    insta::assert_snapshot!(output, @"
+    ------- stderr -------
+    Warning: No such operation tag: unknown
+    Deleted 1 operation tags.
+    [EOF]
+    ");
+    let output = work_dir.run_jj(["op", "tag", "list"]);
+    insta::assert_snapshot!(output, @"");
+}
+
+#[test]
+fn test_op_pin() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+    work_dir.run_jj(["describe", "-m", "before"]).success();
+
+    // The working copy is snapshotted before the operation is pinned.
+    work_dir.write_file("file", "contents");
+    let output = work_dir.run_jj(["op", "pin"]).success();
+    let op_id = output.stdout.into_raw().trim_end().to_owned();
+    assert_eq!(op_id, work_dir.current_operation_id());
+
+    // Read commands see the pinned operation.
+    work_dir.run_jj(["describe", "-m", "after"]).success();
+    let run_pinned =
+        |args: &[&str]| work_dir.run_jj_with(|cmd| cmd.env("JJ_AT_OPERATION", &op_id).args(args));
+    let output = run_pinned(&["log", "--no-graph", "-r@", "-T=description"]);
+    insta::assert_snapshot!(output, @"
+    before
+    [EOF]
+    ");
+    // --at-op takes precedence.
+    let output = run_pinned(&["--at-op=@", "log", "--no-graph", "-r@", "-T=description"]);
+    insta::assert_snapshot!(output, @"
+    after
+    [EOF]
+    ");
+
+    // Commands which would create a new operation fail.
+    let output = run_pinned(&["describe", "-m", "pinned"]);
+    insta::assert_snapshot!(output, @"
+    ------- stderr -------
+    Error: Cannot create a new operation while `JJ_AT_OPERATION` is set
+    Hint: Unset `JJ_AT_OPERATION` to modify the repo at the latest operation.
+    [EOF]
+    [exit status: 1]
+    ");
+    let output = work_dir.run_jj(["log", "--no-graph", "-r@", "-T=description"]);
+    insta::assert_snapshot!(output, @"
+    after
+    [EOF]
+    ");
+}
+
 fn init_bare_git_repo(git_repo_path: &Path) -> gix::Repository {
     let git_repo = git::init_bare(git_repo_path);
     let commit_result = git::add_commit(
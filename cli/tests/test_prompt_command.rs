@@ -0,0 +1,100 @@
+// Copyright 2026 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::common::TestEnvironment;
+
+#[test]
+fn test_prompt() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    // Don't let a slow test machine trigger the fallback
+    test_env.add_config("prompt.timeout-ms = 0");
+    let work_dir = test_env.work_dir("repo");
+
+    let output = work_dir.run_jj(["prompt"]);
+    insta::assert_snapshot!(output, @"
+    q
+    [EOF]
+    ");
+
+    work_dir
+        .run_jj(["bookmark", "create", "-r@", "main"])
+        .success();
+    let output = work_dir.run_jj(["prompt"]);
+    insta::assert_snapshot!(output, @"
+    q main
+    [EOF]
+    ");
+
+    // The current operation can be included
+    let output = work_dir.run_jj([
+        "prompt",
+        "--config=templates.prompt_op='description.first_line().substr(0, 15)'",
+    ]);
+    insta::assert_snapshot!(output, @"
+    q main create bookmark
+    [EOF]
+    ");
+}
+
+#[test]
+fn test_prompt_does_not_snapshot() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    test_env.add_config("prompt.timeout-ms = 0");
+    let work_dir = test_env.work_dir("repo");
+
+    let output = work_dir.run_jj(["prompt", "-T", "commit_id"]);
+    insta::assert_snapshot!(output, @"
+    e8849ae12c709f2321908879bc724fdb2ab8a781
+    [EOF]
+    ");
+
+    // The modified file isn't snapshotted, so the working-copy commit is
+    // unchanged
+    work_dir.write_file("file", "contents");
+    let output_again = work_dir.run_jj(["prompt", "-T", "commit_id"]);
+    assert_eq!(output_again, output);
+
+    let output_log = work_dir.run_jj(["log", "-r@", "--no-graph", "-T", "commit_id"]);
+    assert_ne!(output_log.stdout.raw(), output.stdout.raw());
+}
+
+#[test]
+fn test_prompt_divergent_operations() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    test_env.add_config("prompt.timeout-ms = 0");
+    let work_dir = test_env.work_dir("repo");
+
+    let setup_op_id = work_dir.current_operation_id();
+    work_dir.run_jj(["describe", "-m", "first"]).success();
+    work_dir
+        .run_jj(["describe", "-m", "second", "--at-op", &setup_op_id])
+        .success();
+
+    // Reconciling the operations would write to the repo, so the fallback is
+    // printed instead
+    let output = work_dir.run_jj(["prompt"]);
+    insta::assert_snapshot!(output, @"
+    ?
+    [EOF]
+    ");
+    // The operations are still divergent
+    let output = work_dir.run_jj(["prompt"]);
+    insta::assert_snapshot!(output, @"
+    ?
+    [EOF]
+    ");
+}
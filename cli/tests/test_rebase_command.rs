@@ -3076,6 +3076,56 @@ fn test_rebase_simplify_parents() {
     ");
 }
 
+#[test]
+fn test_rebase_dry_run() {
+    let mut test_env = TestEnvironment::default();
+    // Rewritten commits get the same ids in the preview and the actual run
+    test_env.add_env_var("JJ_TIMESTAMP", "2001-02-03T04:05:06+07:00");
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+
+    create_commit(&work_dir, "a", &[]);
+    create_commit(&work_dir, "b", &["a"]);
+    create_commit(&work_dir, "c", &["b"]);
+    create_commit(&work_dir, "d", &["a"]);
+    let setup_opid = work_dir.current_operation_id();
+
+    let output = work_dir.run_jj(["rebase", "-r=c", "-o=d", "--dry-run"]);
+    insta::assert_snapshot!(output, @"
+    ------- stderr -------
+    Dry-run requested, discarding the following changes:
+    Rewritten commits:
+      royxmykx f9804797 c | c
+    Changed bookmarks:
+      c: 3ea586650c42 -> f98047974693
+    [EOF]
+    ");
+    // Nothing was changed
+    assert_eq!(work_dir.current_operation_id(), setup_opid);
+    insta::assert_snapshot!(get_log_output(&work_dir), @"
+    @  d: a
+    │ ○  c: b
+    │ ○  b: a
+    ├─╯
+    ○  a
+    ◆
+    [EOF]
+    ");
+
+    // The preview matches the actual changes
+    let output = work_dir.run_jj(["rebase", "-r=c", "-o=d"]);
+    insta::assert_snapshot!(output, @"
+    ------- stderr -------
+    Rebased 1 commits to destination
+    [EOF]
+    ");
+    let output = work_dir.run_jj(["bookmark", "list", "c"]);
+    insta::assert_snapshot!(output, @"
+    c: royxmykx f9804797 c
+    [EOF]
+    ");
+}
+
 #[must_use]
 fn get_log_output(work_dir: &TestWorkDir) -> CommandOutput {
     let template = "bookmarks ++ surround(': ', '', parents.map(|c| c.bookmarks()))";
@@ -0,0 +1,105 @@
+// Copyright 2026 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use regex::Regex;
+
+use crate::common::TestEnvironment;
+
+#[cfg(unix)]
+#[test]
+fn test_max_wall_time() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+    work_dir.write_file("file", "content\n");
+    test_env.add_config(
+        r#"
+        [fix.tools.slow]
+        command = ["sh", "-c", "sleep 2; tr a-z A-Z"]
+        patterns = ["all()"]
+        "#,
+    );
+
+    // The operation isn't committed once the limit is exceeded
+    let output = work_dir.run_jj(["fix", "--config=limits.max-wall-time='1s'"]);
+    insta::assert_snapshot!(output, @"
+    ------- stderr -------
+    Fixed 1 commits of 1 checked.
+    Error: Command exceeded the wall-time limit of 1s
+    Hint: `jj fix` was aborted. Operations completed before that were kept; use `jj op log` to inspect them.
+    Hint: Set `limits.max-wall-time` to allow the command to run longer.
+    [EOF]
+    [exit status: 1]
+    ");
+    let output = work_dir.run_jj(["op", "log", "-n1", "-Tdescription"]);
+    insta::assert_snapshot!(output, @"
+    @  snapshot working copy
+    [EOF]
+    ");
+}
+
+#[cfg(unix)]
+#[test]
+fn test_max_memory() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+    work_dir.write_file("file", "content\n");
+    test_env.add_config(
+        r#"
+        [fix.tools.upper]
+        command = ["tr", "a-z", "A-Z"]
+        patterns = ["all()"]
+        "#,
+    );
+
+    // The limit is exceeded once the working copy is snapshotted
+    let output = work_dir.run_jj(["fix", "--config=limits.max-memory='1KiB'"]);
+    insta::assert_snapshot!(output.normalize_stderr_with(|s| {
+        Regex::new(r"allocated .* after [0-9.]+s")
+            .unwrap()
+            .replace(&s, "allocated <size> after <time>")
+            .into_owned()
+    }), @"
+    ------- stderr -------
+    Error: Command exceeded the memory limit of 1.0KiB (allocated <size> after <time>)
+    Hint: `jj fix` was aborted. Operations completed before that were kept; use `jj op log` to inspect them.
+    Hint: Set `limits.max-memory` to allow the command to use more memory.
+    [EOF]
+    [exit status: 1]
+    ");
+    // The snapshot isn't committed
+    let output = work_dir.run_jj(["op", "log", "-n1", "-Tdescription", "--ignore-working-copy"]);
+    insta::assert_snapshot!(output, @"
+    @  add workspace 'default'
+    [EOF]
+    ");
+}
+
+#[test]
+fn test_invalid_wall_time() {
+    let test_env = TestEnvironment::default();
+    let output = test_env.run_jj_in(
+        ".",
+        ["config", "list", "--config=limits.max-wall-time='1d'"],
+    );
+    insta::assert_snapshot!(output, @r#"
+    ------- stderr -------
+    Config error: Invalid type or value for limits.max-wall-time
+    Caused by: Invalid duration, expected e.g. "90s", "10m", or "1h"
+    For help, see https://docs.jj-vcs.dev/latest/config/ or use `jj help -k config`.
+    [EOF]
+    [exit status: 1]
+    "#);
+}
@@ -806,6 +806,61 @@ fn test_alias_override() {
     ");
 }
 
+#[test]
+fn test_builtin_alias() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+
+    test_env.add_config(
+        r#"
+    [revset-aliases]
+    'visible()' = 'builtin::visible() ~ root()'
+    "#,
+    );
+    let template = r#"if(root, "root", "commit") ++ "\n""#;
+
+    // The builtin definition can be referred to from the override.
+    let output = work_dir.run_jj(["log", "--no-graph", "-r", "visible()", "-T", template]);
+    insta::assert_snapshot!(output, @"
+    commit
+    [EOF]
+    ");
+    let output = work_dir.run_jj([
+        "log",
+        "--no-graph",
+        "-r",
+        "builtin::visible()",
+        "-T",
+        template,
+    ]);
+    insta::assert_snapshot!(output, @"
+    commit
+    root
+    [EOF]
+    ");
+
+    // Overridden aliases are reported if enabled. The test environment also
+    // overrides a template alias.
+    let output = work_dir.run_jj([
+        "log",
+        "--no-graph",
+        "-r",
+        "visible()",
+        "-T",
+        template,
+        "--config=ui.warn-alias-overrides=true",
+    ]);
+    insta::assert_snapshot!(output, @"
+    commit
+    [EOF]
+    ------- stderr -------
+    Warning: `revset-aliases.visible()` in default config is overridden by `revset-aliases.visible()` in user config $TEST_ENV/config/config0002.toml
+    Warning: `template-aliases.format_time_range(time_range)` in default config is overridden by `template-aliases.format_time_range(time_range)` in user config $TEST_ENV/config/config0001.toml
+    [EOF]
+    ");
+}
+
 #[test]
 fn test_bad_alias_decl() {
     let test_env = TestEnvironment::default();
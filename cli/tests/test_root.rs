@@ -46,3 +46,65 @@ fn test_root_outside_a_repo() {
     [exit status: 1]
     "#);
 }
+
+#[test]
+fn test_root_paths() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+
+    let output = work_dir.run_jj(["root", "--repo"]);
+    insta::assert_snapshot!(output.normalize_backslash(), @"
+    $TEST_ENV/repo/.jj/repo
+    [EOF]
+    ");
+    let output = work_dir.run_jj(["root", "--op-store"]);
+    insta::assert_snapshot!(output.normalize_backslash(), @"
+    $TEST_ENV/repo/.jj/repo/op_store
+    [EOF]
+    ");
+
+    // The repo config directory isn't created by the query
+    let output = work_dir.run_jj(["root", "--repo-config-dir"]);
+    insta::assert_snapshot!(output, @"
+    ------- stderr -------
+    Error: No repo-specific config directory exists yet
+    Hint: Run `jj config path --repo` to create one.
+    [EOF]
+    [exit status: 1]
+    ");
+    let output = work_dir.run_jj(["root", "--all"]);
+    insta::assert_snapshot!(output.normalize_backslash(), @"
+    workspace_root: $TEST_ENV/repo
+    repo: $TEST_ENV/repo/.jj/repo
+    store: $TEST_ENV/repo/.jj/repo/store
+    op_store: $TEST_ENV/repo/.jj/repo/op_store
+    [EOF]
+    ");
+
+    work_dir.run_jj(["config", "path", "--repo"]).success();
+    let output = work_dir.run_jj(["root", "--repo-config-dir"]);
+    insta::assert_snapshot!(output.normalize_backslash(), @"
+    $TEST_ENV/home/.config/jj/repos/8b12d1f268f89aab6c84
+    [EOF]
+    ");
+    let output = work_dir.run_jj(["root", "--all", "--json"]).success();
+    let paths: serde_json::Value = serde_json::from_str(output.stdout.raw()).unwrap();
+    assert_eq!(
+        paths["store"].as_str().map(Path::new),
+        Some(work_dir.root().join(".jj/repo/store").as_path())
+    );
+    assert!(paths["repo_config_dir"].is_string());
+
+    let output = work_dir.run_jj(["root", "--repo", "--store"]);
+    insta::assert_snapshot!(output, @"
+    ------- stderr -------
+    error: the argument '--repo' cannot be used with '--store'
+
+    Usage: jj root --repo
+
+    For more information, try '--help'.
+    [EOF]
+    [exit status: 2]
+    ");
+}
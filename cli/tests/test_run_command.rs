@@ -0,0 +1,289 @@
+// Copyright 2026 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::path::PathBuf;
+
+use itertools::Itertools as _;
+
+use crate::common::TestEnvironment;
+use crate::common::TestWorkDir;
+
+fn fake_formatter_path() -> String {
+    let path = assert_cmd::cargo::cargo_bin!("fake-formatter");
+    assert!(path.is_file());
+    path.as_os_str().to_str().unwrap().to_owned()
+}
+
+/// Returns the directories of the cached working copies, sorted by their
+/// contents of `file`.
+fn cached_working_copies(work_dir: &TestWorkDir) -> Vec<PathBuf> {
+    let run_dir = work_dir.root().join(".jj").join("repo").join("run");
+    let Ok(entries) = std::fs::read_dir(run_dir) else {
+        return vec![];
+    };
+    entries
+        .map(|entry| entry.unwrap().path().join("working_copy"))
+        .sorted_by_key(|path| std::fs::read(path.join("file")).unwrap())
+        .collect()
+}
+
+#[test]
+fn test_run() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    test_env.add_config("templates.commit_summary = 'description.first_line()'");
+    let work_dir = test_env.work_dir("repo");
+    work_dir.write_file("file", "a\n");
+    work_dir.run_jj(["commit", "-m", "a"]).success();
+    work_dir.write_file("file", "b\n");
+    work_dir.run_jj(["describe", "-m", "b"]).success();
+
+    let command = format!("{} --stdout=ok --tee=out", fake_formatter_path());
+    let output = work_dir.run_jj(["run", &command, "-r=::@ ~ root()", "-j=1"]);
+    insta::assert_snapshot!(output, @r"
+    okok[EOF]
+    ------- stderr -------
+    Ran command in b
+    Ran command in a
+    Exit codes:
+        0  b
+        0  a
+    [EOF]
+    ");
+    let working_copies = cached_working_copies(&work_dir);
+    assert_eq!(working_copies.len(), 2);
+    assert_eq!(
+        std::fs::read(working_copies[0].join("file")).unwrap(),
+        b"a\n"
+    );
+    assert_eq!(
+        std::fs::read(working_copies[1].join("file")).unwrap(),
+        b"b\n"
+    );
+    // The workspace's own working copy isn't touched
+    assert!(!work_dir.root().join("out").exists());
+    // The output of each command is saved next to its working copy
+    for working_copy in &working_copies {
+        let output_dir = working_copy.parent().unwrap().join("output");
+        let outputs = std::fs::read_dir(output_dir)
+            .unwrap()
+            .map(|entry| std::fs::read(entry.unwrap().path()).unwrap())
+            .sorted()
+            .collect_vec();
+        assert_eq!(outputs, [b"".to_vec(), b"ok".to_vec()]);
+    }
+
+    // The working copies are reused, but the files written by the earlier run
+    // are removed before the command is run again
+    let output = work_dir.run_jj(["run", &command, "-r=::@ ~ root()"]);
+    insta::assert_snapshot!(output, @r"
+    okok[EOF]
+    ------- stderr -------
+    Ran command in b
+    Ran command in a
+    Exit codes:
+        0  b
+        0  a
+    [EOF]
+    ");
+    assert_eq!(cached_working_copies(&work_dir), working_copies);
+    assert_eq!(std::fs::read(working_copies[0].join("out")).unwrap(), b"ok");
+    assert_eq!(std::fs::read(working_copies[1].join("out")).unwrap(), b"ok");
+
+    let output = work_dir.run_jj(["run", "--clean"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Removed 2 cached working copies
+    [EOF]
+    ");
+    assert!(cached_working_copies(&work_dir).is_empty());
+}
+
+#[test]
+fn test_run_failure() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    test_env.add_config("templates.commit_summary = 'description.first_line()'");
+    let work_dir = test_env.work_dir("repo");
+    work_dir.write_file("file", "a\n");
+    work_dir.run_jj(["describe", "-m", "a"]).success();
+
+    let command = format!("{} --stderr=broken --fail", fake_formatter_path());
+    let output = work_dir.run_jj(["run", &command]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Ran command in a
+    brokenExit codes:
+        1  a
+    Error: Command failed in 1 of 1 revisions
+    [EOF]
+    [exit status: 1]
+    ");
+}
+
+#[test]
+fn test_run_reuse_working_copy() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    test_env.add_config("templates.commit_summary = 'description.first_line()'");
+    let work_dir = test_env.work_dir("repo");
+    work_dir.write_file(".gitignore", "ignored\n");
+    work_dir.write_file("file", "a\n");
+    work_dir.run_jj(["describe", "-m", "a"]).success();
+
+    // The changes to tracked files made by the first run are reverted before
+    // the next runs, but ignored files are kept
+    let command = format!("{} --stdout=ok --tee=file", fake_formatter_path());
+    work_dir.run_jj(["run", &command]).success();
+    let command = format!("{} --stdout=ok --tee=ignored", fake_formatter_path());
+    work_dir.run_jj(["run", &command]).success();
+    work_dir.run_jj(["run", &command]).success();
+    let working_copies = cached_working_copies(&work_dir);
+    assert_eq!(working_copies.len(), 1);
+    assert_eq!(
+        std::fs::read(working_copies[0].join("file")).unwrap(),
+        b"a\n"
+    );
+    assert_eq!(
+        std::fs::read(working_copies[0].join("ignored")).unwrap(),
+        b"okok"
+    );
+}
+
+#[test]
+fn test_run_same_contents() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    test_env.add_config("templates.commit_summary = 'description.first_line()'");
+    let work_dir = test_env.work_dir("repo");
+    work_dir.write_file("file", "a\n");
+    work_dir.run_jj(["commit", "-m", "a"]).success();
+    work_dir.run_jj(["describe", "-m", "b"]).success();
+
+    // Revisions with the same contents share a working copy, and their
+    // commands are run one after another. The working copy is reset in
+    // between, so the second command doesn't see the changes of the first.
+    let command = format!("{} --stdout=ok --tee=out", fake_formatter_path());
+    let output = work_dir.run_jj(["run", &command, "-r=::@ ~ root()"]);
+    insta::assert_snapshot!(output, @r"
+    okok[EOF]
+    ------- stderr -------
+    Ran command in b
+    Ran command in a
+    Exit codes:
+        0  b
+        0  a
+    [EOF]
+    ");
+    let working_copies = cached_working_copies(&work_dir);
+    assert_eq!(working_copies.len(), 1);
+    assert_eq!(std::fs::read(working_copies[0].join("out")).unwrap(), b"ok");
+
+    // Each revision is rewritten with the changes made by its own run
+    let command = format!("{} --stdout=fmt --tee=file", fake_formatter_path());
+    let output = work_dir.run_jj(["run", &command, "-r=::@ ~ root()", "--rewrite"]);
+    insta::assert_snapshot!(output, @r"
+    fmtfmt[EOF]
+    ------- stderr -------
+    Ran command in b
+    Ran command in a
+    Exit codes:
+        0  b
+        0  a
+    Rewrote 2 commits
+    Working copy  (@) now at: b
+    Parent commit (@-)      : a
+    Added 0 files, modified 1 files, removed 0 files
+    [EOF]
+    ");
+    let output = work_dir.run_jj(["file", "show", "-r=@-", "file"]);
+    insta::assert_snapshot!(output, @r"
+    a
+    fmt[EOF]
+    ");
+    let output = work_dir.run_jj(["file", "show", "-r=@", "file"]);
+    insta::assert_snapshot!(output, @r"
+    a
+    fmt[EOF]
+    ");
+}
+
+#[test]
+fn test_run_rewrite() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    test_env.add_config("templates.commit_summary = 'description.first_line()'");
+    let work_dir = test_env.work_dir("repo");
+    work_dir.write_file("file", "a\n");
+    work_dir.run_jj(["commit", "-m", "a"]).success();
+    work_dir.write_file("other", "b\n");
+    work_dir.run_jj(["describe", "-m", "b"]).success();
+
+    // Without --rewrite, the changes made by the command aren't recorded
+    let command = format!("{} --stdout=stale --tee=file", fake_formatter_path());
+    work_dir.run_jj(["run", &command, "-r=@-"]).success();
+    let output = work_dir.run_jj(["file", "show", "-r=@-", "file"]);
+    insta::assert_snapshot!(output, @r"
+    a
+    [EOF]
+    ");
+
+    // The changes left by the earlier run are discarded, and the revision is
+    // rewritten with the changes made by this run
+    let command = format!("{} --stdout=fmt --tee=file", fake_formatter_path());
+    let output = work_dir.run_jj(["run", &command, "-r=@-", "--rewrite"]);
+    insta::assert_snapshot!(output, @r"
+    fmt[EOF]
+    ------- stderr -------
+    Ran command in a
+    Exit codes:
+        0  a
+    Rewrote 1 commits
+    Rebased 1 descendant commits
+    Working copy  (@) now at: b
+    Parent commit (@-)      : a
+    Added 0 files, modified 1 files, removed 0 files
+    [EOF]
+    ");
+    let output = work_dir.run_jj(["file", "show", "-r=@-", "file"]);
+    insta::assert_snapshot!(output, @r"
+    a
+    fmt[EOF]
+    ");
+    assert_eq!(work_dir.read_file("file"), "a\nfmt");
+
+    // Revisions for which the command failed aren't rewritten
+    let command = format!("{} --stdout=fail --tee=file --fail", fake_formatter_path());
+    let output = work_dir.run_jj(["run", &command, "-r=@", "--rewrite"]);
+    insta::assert_snapshot!(output, @r"
+    fail[EOF]
+    ------- stderr -------
+    Ran command in b
+    Exit codes:
+        1  b
+    Error: Command failed in 1 of 1 revisions
+    [EOF]
+    [exit status: 1]
+    ");
+    assert_eq!(work_dir.read_file("file"), "a\nfmt");
+
+    // Immutable revisions can't be rewritten
+    let output = work_dir.run_jj(["run", &command, "-r=root()", "--rewrite"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Error: The root commit 000000000000 is immutable
+    [EOF]
+    [exit status: 1]
+    ");
+}
@@ -14,6 +14,7 @@
 
 use std::path::PathBuf;
 
+use regex::Regex;
 use test_case::test_case;
 
 use crate::common::CommandOutput;
@@ -842,6 +843,88 @@ fn test_split_parallel_with_conflict() {
 }
 
 // Make sure `jj split` would refuse to split an empty commit.
+#[test]
+fn test_split_into_filesets() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+
+    work_dir.write_file("src/main.rs", "foo");
+    work_dir.write_file("tests/test.rs", "foo");
+    work_dir.write_file("README", "foo");
+    work_dir.run_jj(["describe", "-m", "big change"]).success();
+    work_dir
+        .run_jj(["bookmark", "create", "-r@", "big"])
+        .success();
+    work_dir.run_jj(["new", "-m", "child"]).success();
+
+    let id_re = Regex::new(r"\b[k-z]{8} [0-9a-f]{8}\b").unwrap();
+    let output = work_dir
+        .run_jj(["split", "-r@-", "--into", "src", "--into", "tests"])
+        .normalize_stderr_with(|s| id_re.replace_all(&s, "[COMMIT]").into_owned());
+    insta::assert_snapshot!(output, @"
+    ------- stderr -------
+    Rebased 1 descendant commits
+    Split into 3 commits:
+      [COMMIT] big change
+      [COMMIT] big change
+      [COMMIT] big | big change
+    Working copy  (@) now at: [COMMIT] (empty) child
+    Parent commit (@-)      : [COMMIT] big | big change
+    [EOF]
+    ");
+    let template = r#"separate(" ", local_bookmarks, description)"#;
+    let output = work_dir.run_jj(["log", "-T", template, "--summary"]);
+    insta::assert_snapshot!(output, @"
+    @  child
+    ○  big big change
+    │  A README
+    ○  big change
+    │  A tests/test.rs
+    ○  big change
+    │  A src/main.rs
+    ◆
+    [EOF]
+    ");
+    // The first commit keeps the change id of the split commit
+    let output = work_dir.run_jj(["log", "--no-graph", "-T", "description", "-r", "qpvuntsm"]);
+    insta::assert_snapshot!(output, @"
+    big change
+    [EOF]
+    ");
+
+    // Filesets not matching any changes produce empty commits
+    let output = work_dir
+        .run_jj(["split", "-r@-", "--into", "nonexistent", "--into", "README"])
+        .normalize_stderr_with(|s| id_re.replace_all(&s, "[COMMIT]").into_owned());
+    insta::assert_snapshot!(output, @"
+    ------- stderr -------
+    Warning: No matching entries for paths: nonexistent
+    Warning: No changes match the fileset nonexistent, so its revision will be empty
+    Warning: All changes match the filesets, so the last revision will be empty
+    Rebased 1 descendant commits
+    Split into 3 commits:
+      [COMMIT] (empty) big change
+      [COMMIT] big change
+      [COMMIT] big | (empty) big change
+    Working copy  (@) now at: [COMMIT] (empty) child
+    Parent commit (@-)      : [COMMIT] big | (empty) big change
+    [EOF]
+    ");
+
+    let output = work_dir.run_jj(["split", "--into", "src", "-i"]);
+    insta::assert_snapshot!(output, @"
+    ------- stderr -------
+    error: the argument '--into <FILESET>' cannot be used with '--interactive'
+
+    Usage: jj split --into <FILESET> [FILESETS]...
+
+    For more information, try '--help'.
+    [EOF]
+    [exit status: 2]
+    ");
+}
+
 #[test]
 fn test_split_empty() {
     let mut test_env = TestEnvironment::default();
@@ -1558,6 +1558,128 @@ fn test_squash_description_editor_avoids_unc() {
     assert_eq!(edited_path, dunce::simplified(&edited_path));
 }
 
+#[test]
+fn test_squash_message_strategy() {
+    let mut test_env = TestEnvironment::default();
+    let edit_script = test_env.set_up_fake_editor();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+
+    std::fs::write(&edit_script, "fail").unwrap();
+    work_dir.write_file("file1", "a\n");
+    work_dir.run_jj(["describe", "-m", "destination"]).success();
+    work_dir.run_jj(["new", "-m", "source"]).success();
+    work_dir.write_file("file1", "b\n");
+    work_dir.run_jj(["debug", "snapshot"]).success();
+    let setup_opid = work_dir.current_operation_id();
+
+    work_dir
+        .run_jj(["squash", "--message-strategy=concatenate"])
+        .success();
+    insta::assert_snapshot!(get_description(&work_dir, "@-"), @"
+    destination
+
+    source
+    [EOF]
+    ");
+
+    work_dir.run_jj(["op", "restore", &setup_opid]).success();
+    work_dir
+        .run_jj(["squash", "--message-strategy=take-source"])
+        .success();
+    insta::assert_snapshot!(get_description(&work_dir, "@-"), @"
+    source
+    [EOF]
+    ");
+
+    work_dir.run_jj(["op", "restore", &setup_opid]).success();
+    work_dir
+        .run_jj(["squash", "--message-strategy=keep-destination"])
+        .success();
+    insta::assert_snapshot!(get_description(&work_dir, "@-"), @"
+    destination
+    [EOF]
+    ");
+
+    // The setting applies to the --from/--into forms too
+    work_dir.run_jj(["op", "restore", &setup_opid]).success();
+    test_env.add_config("squash.message-strategy = 'concatenate'");
+    work_dir
+        .run_jj(["squash", "--from=@", "--into=@-"])
+        .success();
+    insta::assert_snapshot!(get_description(&work_dir, "@-"), @"
+    destination
+
+    source
+    [EOF]
+    ");
+
+    // --use-destination-message overrides the setting
+    work_dir.run_jj(["op", "restore", &setup_opid]).success();
+    work_dir
+        .run_jj(["squash", "--from=@", "--into=@-", "-u"])
+        .success();
+    insta::assert_snapshot!(get_description(&work_dir, "@-"), @"
+    destination
+    [EOF]
+    ");
+
+    // The editor is opened even if only one description is non-empty
+    work_dir.run_jj(["op", "restore", &setup_opid]).success();
+    work_dir.run_jj(["describe", "-m", ""]).success();
+    std::fs::write(&edit_script, "dump editor0").unwrap();
+    work_dir
+        .run_jj(["squash", "--message-strategy=editor"])
+        .success();
+    insta::assert_snapshot!(
+        std::fs::read_to_string(test_env.env_root().join("editor0")).unwrap(), @r#"
+    JJ: Enter a description for the combined commit.
+    JJ: Description from the destination commit:
+    destination
+
+    JJ: Description from source commit:
+
+    JJ: Change ID: qpvuntsm
+    JJ: This commit contains the following changes:
+    JJ:     A file1
+    JJ:
+    JJ: Lines starting with "JJ:" (like this one) will be removed.
+    "#);
+    insta::assert_snapshot!(get_description(&work_dir, "@-"), @"
+    destination
+    [EOF]
+    ");
+
+    // There's no destination description to keep when creating a new commit
+    work_dir.run_jj(["op", "restore", &setup_opid]).success();
+    let output = work_dir.run_jj([
+        "squash",
+        "--message-strategy=keep-destination",
+        "--onto=root()",
+    ]);
+    insta::assert_snapshot!(output, @"
+    ------- stderr -------
+    Error: Cannot keep the destination description when squashing into a new commit
+    Hint: Use `--message` to set the description of the new commit.
+    [EOF]
+    [exit status: 1]
+    ");
+
+    // -u can still be used with --onto, which leaves the new commit without a
+    // description
+    work_dir.run_jj(["op", "restore", &setup_opid]).success();
+    work_dir.run_jj(["squash", "-u", "--onto=root()"]).success();
+    let output = work_dir.run_jj(["log", "-r=all()", "-T=builtin_log_oneline"]);
+    insta::assert_snapshot!(output, @"
+    @  nmpuuozl test.user 2001-02-03 08:05:32 3506b499 (empty) (no description set)
+    ○  qpvuntsm test.user 2001-02-03 08:05:08 b1700263 destination
+    │ ×  uuuvxpvw test.user 2001-02-03 08:05:32 5f6e7419 (conflict) (no description set)
+    ├─╯
+    ◆  zzzzzzzz root() 00000000
+    [EOF]
+    ");
+}
+
 #[test]
 fn test_squash_empty() {
     let mut test_env = TestEnvironment::default();
@@ -41,6 +41,66 @@ fn test_status_copies() {
     ");
 }
 
+#[test]
+fn test_status_porcelain() {
+    let test_env = TestEnvironment::default();
+    test_env.add_config(r#"snapshot.auto-track = "~untracked*""#);
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+
+    work_dir.write_file("conflict", "base\n");
+    work_dir.write_file("modified", "1\n");
+    work_dir.write_file("removed", "removed\n");
+    work_dir.write_file("rename-source", "rename");
+    work_dir.run_jj(["new", "-m=left"]).success();
+    work_dir.write_file("conflict", "left\n");
+    work_dir.run_jj(["new", "@-", "-m=right"]).success();
+    work_dir.write_file("conflict", "right\n");
+    work_dir
+        .run_jj(["rebase", "-r=@", "-o=subject(left)"])
+        .success();
+    work_dir.run_jj(["new"]).success();
+    work_dir.write_file("added file", "added\n");
+    work_dir.write_file("modified", "2\n");
+    work_dir.remove_file("removed");
+    work_dir.remove_file("rename-source");
+    work_dir.write_file("sub/rename-target", "rename");
+    work_dir.write_file("untracked-file", "...");
+    work_dir.write_file("untracked-dir/file", "...");
+
+    let output = work_dir.run_jj(["status", "--porcelain"]);
+    insta::assert_snapshot!(output, @r#"
+    A  "added file"
+    UU conflict
+    M  modified
+    D  removed
+    R  rename-source -> sub/rename-target
+    ?? untracked-dir/
+    ?? untracked-file
+    [EOF]
+    "#);
+
+    // The paths are relative to the workspace root, and can be filtered
+    let output = work_dir
+        .dir("sub")
+        .run_jj(["status", "--porcelain=v1", "."]);
+    insta::assert_snapshot!(output, @"
+    A  sub/rename-target
+    [EOF]
+    ");
+
+    let output = work_dir.run_jj(["status", "--porcelain=v2"]);
+    insta::assert_snapshot!(output, @"
+    ------- stderr -------
+    error: invalid value 'v2' for '--porcelain[=<VERSION>]'
+      [possible values: v1]
+
+    For more information, try '--help'.
+    [EOF]
+    [exit status: 2]
+    ");
+}
+
 #[test]
 fn test_status_merge() {
     let test_env = TestEnvironment::default();
@@ -606,3 +666,32 @@ fn test_status_no_working_copy() {
     [EOF]
     ");
 }
+
+#[test]
+fn test_status_no_snapshot() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+
+    work_dir.write_file("file1", "1\n");
+    work_dir.run_jj(["describe", "-m=first"]).success();
+    work_dir.write_file("file2", "2\n");
+
+    // Changes since the last snapshot aren't shown
+    let output = work_dir.run_jj(["status", "--no-snapshot", "--porcelain"]);
+    insta::assert_snapshot!(output, @"
+    A  file1
+    [EOF]
+    ------- stderr -------
+    Working copy last snapshotted 1 second ago; changes since then are not shown
+    [EOF]
+    ");
+
+    // The working copy wasn't snapshotted by the previous command
+    let output = work_dir.run_jj(["status", "--porcelain"]);
+    insta::assert_snapshot!(output, @"
+    A  file1
+    A  file2
+    [EOF]
+    ");
+}
@@ -0,0 +1,155 @@
+// Copyright 2026 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use regex::Regex;
+use testutils::git;
+
+use crate::common::TestEnvironment;
+
+#[test]
+fn test_sync_status() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "remote"]).success();
+    let remote_dir = test_env.work_dir("remote");
+    for bookmark in ["remote-delete", "sync", "unsync"] {
+        remote_dir
+            .run_jj(["new", "root()", "-m", bookmark])
+            .success();
+        remote_dir
+            .run_jj(["bookmark", "create", bookmark])
+            .success();
+    }
+    remote_dir.run_jj(["new"]).success();
+    remote_dir.run_jj(["git", "export"]).success();
+
+    let mut remote_git_path = remote_dir.root().to_owned();
+    remote_git_path.extend([".jj", "repo", "store", "git"]);
+    test_env
+        .run_jj_in(
+            ".",
+            ["git", "clone", remote_git_path.to_str().unwrap(), "local"],
+        )
+        .success();
+    let local_dir = test_env.work_dir("local");
+    local_dir.run_jj(["new", "root()", "-m", "local"]).success();
+    local_dir
+        .run_jj(["bookmark", "set", "--allow-backwards", "unsync"])
+        .success();
+    local_dir
+        .run_jj(["bookmark", "delete", "remote-delete"])
+        .success();
+    local_dir
+        .run_jj(["bookmark", "create", "new-bookmark"])
+        .success();
+    local_dir
+        .run_jj(["bookmark", "track", "new-bookmark@origin"])
+        .success();
+
+    let time_re =
+        Regex::new(r"\d{4}-\d{2}-\d{2} \d{2}:\d{2}:\d{2}\.\d{3} [+-]\d{2}:\d{2}").unwrap();
+    let op_re = Regex::new(r"operation [0-9a-f]{12}").unwrap();
+    let output = local_dir
+        .run_jj(["sync-status"])
+        .normalize_stdout_with(|s| {
+            let s = time_re.replace_all(&s, "[TIME]");
+            op_re.replace_all(&s, "operation [OP]").into_owned()
+        });
+    insta::assert_snapshot!(output, @"
+    origin: last fetched at [TIME] by operation [OP]; never pushed
+      new-bookmark: not created yet, needs push
+      remote-delete: deleted, needs push
+      sync: in sync
+      unsync: ahead by 1 commits, behind by 1 commits, needs push
+    [EOF]
+    ");
+
+    let output = local_dir.run_jj(["sync-status", "--json"]).success();
+    let value: serde_json::Value = serde_json::from_str(output.stdout.raw()).unwrap();
+    let remote = &value["remotes"][0];
+    assert_eq!(remote["name"], "origin");
+    assert!(remote["last_fetch"]["operation"].is_string());
+    assert_eq!(remote["last_fetch"]["ref_count"], 3);
+    assert_eq!(remote["last_push"], serde_json::Value::Null);
+    let bookmarks = remote["bookmarks"].as_array().unwrap();
+    let names: Vec<_> = bookmarks.iter().map(|b| &b["name"]).collect();
+    assert_eq!(names, ["new-bookmark", "remote-delete", "sync", "unsync"]);
+    assert_eq!(bookmarks[0]["remote"], serde_json::Value::Null);
+    assert_eq!(bookmarks[1]["local"], serde_json::Value::Null);
+    assert_eq!(bookmarks[2]["needs_push"], false);
+    assert_eq!(bookmarks[3]["ahead"], 1);
+    assert_eq!(bookmarks[3]["behind"], 1);
+    assert_eq!(bookmarks[3]["conflict"], false);
+    assert_eq!(bookmarks[3]["needs_push"], true);
+}
+
+#[test]
+fn test_sync_status_ahead_behind() {
+    let test_env = TestEnvironment::default();
+    test_env
+        .run_jj_in(".", ["git", "init", "--colocate", "repo"])
+        .success();
+    let work_dir = test_env.work_dir("repo");
+    work_dir
+        .run_jj(["git", "remote", "add", "origin", "../remote"])
+        .success();
+    work_dir.run_jj(["new", "root()", "-m", "base"]).success();
+    for message in ["r1", "r2", "r3"] {
+        work_dir.run_jj(["new", "-m", message]).success();
+    }
+    work_dir
+        .run_jj(["new", "subject(base)", "-m", "l1"])
+        .success();
+    work_dir.run_jj(["new", "-m", "l2"]).success();
+
+    // Set up the remote bookmark as if it had been fetched
+    let output = work_dir.run_jj(["log", "--no-graph", "-rsubject(r3)", "-T", "commit_id"]);
+    let remote_id = gix::ObjectId::from_hex(output.stdout.raw().as_bytes()).unwrap();
+    git::open(work_dir.root())
+        .reference(
+            "refs/remotes/origin/main",
+            remote_id,
+            gix::refs::transaction::PreviousValue::Any,
+            "",
+        )
+        .unwrap();
+    work_dir
+        .run_jj(["bookmark", "track", "main@origin"])
+        .success();
+    work_dir
+        .run_jj(["bookmark", "set", "main", "--allow-backwards"])
+        .success();
+
+    let output = work_dir.run_jj(["sync-status"]);
+    insta::assert_snapshot!(output, @"
+    origin: never fetched; never pushed
+      main: ahead by 2 commits, behind by 3 commits, needs push
+    [EOF]
+    ");
+}
+
+#[test]
+fn test_sync_status_never_fetched() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+    work_dir
+        .run_jj(["git", "remote", "add", "origin", "../remote"])
+        .success();
+
+    let output = work_dir.run_jj(["sync-status"]);
+    insta::assert_snapshot!(output, @"
+    origin: never fetched; never pushed
+    [EOF]
+    ");
+}
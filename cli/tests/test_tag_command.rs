@@ -12,6 +12,8 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use testutils::git;
+
 use crate::common::CommandOutput;
 use crate::common::TestEnvironment;
 use crate::common::TestWorkDir;
@@ -392,6 +394,31 @@ fn test_tag_list() {
     ");
 }
 
+#[test]
+fn test_tag_list_limit_offset() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+    work_dir
+        .run_jj(["tag", "set", "-r@", "v1", "v2", "v3"])
+        .success();
+
+    let template = r#"name ++ "\n""#;
+    let output = work_dir.run_jj([
+        "tag",
+        "list",
+        "-T",
+        template,
+        "--sort=name-",
+        "--offset=1",
+        "--limit=1",
+    ]);
+    insta::assert_snapshot!(output, @"
+    v2
+    [EOF]
+    ");
+}
+
 #[test]
 fn test_tag_list_remotes() {
     let test_env = TestEnvironment::default();
@@ -438,6 +465,82 @@ fn test_tag_list_remotes() {
     ");
 }
 
+#[test]
+fn test_tag_list_sort_by_tag_date() {
+    let test_env = TestEnvironment::default();
+    test_env
+        .run_jj_in(".", ["git", "init", "--colocate", "repo"])
+        .success();
+    let work_dir = test_env.work_dir("repo");
+    work_dir.run_jj(["new", "root()", "-m", "a"]).success();
+    work_dir.run_jj(["tag", "set", "v1"]).success();
+    work_dir.run_jj(["new", "root()", "-m", "b"]).success();
+    work_dir.run_jj(["tag", "set", "v2"]).success();
+
+    // Replace the exported tags with annotated tags, v2 created before v1
+    let git_repo = git::open(work_dir.root());
+    let commit_id = |name: &str| {
+        let output = work_dir.run_jj(["log", "--no-graph", "-r", name, "-T", "commit_id"]);
+        gix::ObjectId::from_hex(output.stdout.raw().as_bytes()).unwrap()
+    };
+    git::write_annotated_tag(&git_repo, "v1", commit_id("v1"), 2000);
+    git::write_annotated_tag(&git_repo, "v2", commit_id("v2"), 1000);
+
+    let template = r#"name ++ "\n""#;
+    let output = work_dir.run_jj(["tag", "list", "-T", template, "--sort=tag-date"]);
+    insta::assert_snapshot!(output, @"
+    v2
+    v1
+    [EOF]
+    ");
+
+    // The annotated tag doesn't apply to v2 at an operation where it had
+    // another target, so the committer date of the target is used.
+    work_dir
+        .run_jj(["tag", "set", "--allow-move", "v2", "-rv1"])
+        .success();
+    let output = work_dir.run_jj([
+        "tag",
+        "list",
+        "-T",
+        template,
+        "--sort=tag-date",
+        "--at-op=@-",
+    ]);
+    insta::assert_snapshot!(output, @"
+    v1
+    v2
+    [EOF]
+    ");
+
+    // The key isn't supported by bookmark list
+    let output = work_dir.run_jj(["bookmark", "list", "--sort=tag-date"]);
+    insta::assert_snapshot!(output, @"
+    ------- stderr -------
+    error: invalid value 'tag-date' for '--sort <SORT_KEY>'
+      [possible values: name, name-, author-name, author-name-, author-email, author-email-, author-date, author-date-, committer-name, committer-name-, committer-email, committer-email-, committer-date, committer-date-, description, description-, target-id, target-id-, divergence, divergence-]
+
+      tip: a similar value exists: 'author-date'
+
+    For more information, try '--help'.
+    [EOF]
+    [exit status: 2]
+    ");
+    let output = work_dir.run_jj([
+        "bookmark",
+        "list",
+        "--config=ui.bookmark-list-sort-keys=['tag-date']",
+    ]);
+    insta::assert_snapshot!(output, @"
+    ------- stderr -------
+    Config error: Invalid type or value for ui.bookmark-list-sort-keys
+    Caused by: Sort key tag-date is only supported by tag list
+    For help, see https://docs.jj-vcs.dev/latest/config/ or use `jj help -k config`.
+    [EOF]
+    [exit status: 1]
+    ");
+}
+
 #[must_use]
 fn get_log_output(work_dir: &TestWorkDir) -> CommandOutput {
     let template = r#"separate(" ", commit_id.short(), tags) ++ "\n""#;
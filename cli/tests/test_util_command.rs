@@ -243,6 +243,100 @@ fn test_util_exec_sets_env() {
     ");
 }
 
+#[cfg(unix)]
+#[test]
+fn test_util_exec_at_rev() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+    work_dir.write_file("file", "old\n");
+    work_dir
+        .run_jj(["commit", "-m", "first", "--quiet"])
+        .success();
+    work_dir.write_file("file", "new\n");
+
+    let output = work_dir.run_jj([
+        "util",
+        "exec",
+        "--at-rev",
+        "@-",
+        "--template-args",
+        "--env",
+        "GREETING=hello",
+        "--",
+        "/bin/sh",
+        "-c",
+        r#"echo "$GREETING"; cat file; test "$JJ_COMMIT_ID" = "$1" && echo "$2""#,
+        "",
+        "{commit_id}",
+        "{{{description.first_line()}}}",
+    ]);
+    insta::assert_snapshot!(output, @"
+    hello
+    old
+    {first}
+    [EOF]
+    ");
+
+    // The working copy isn't touched
+    assert_eq!(work_dir.read_file("file"), "new\n");
+
+    // The arguments are passed as is without --template-args
+    let output = work_dir.run_jj(["util", "exec", "--at-rev", "@-", "--", "echo", "{print $1}"]);
+    insta::assert_snapshot!(output, @"
+    {print $1}
+    [EOF]
+    ");
+
+    // Changes made by an earlier command are reverted
+    let output = work_dir.run_jj([
+        "util",
+        "exec",
+        "--at-rev",
+        "@-",
+        "--",
+        "/bin/sh",
+        "-c",
+        "cat file; echo changed > file",
+    ]);
+    insta::assert_snapshot!(output, @"
+    old
+    [EOF]
+    ");
+    let output = work_dir.run_jj(["util", "exec", "--at-rev", "@-", "--", "cat", "file"]);
+    insta::assert_snapshot!(output, @"
+    old
+    [EOF]
+    ");
+
+    let output = work_dir.run_jj([
+        "util",
+        "exec",
+        "--at-rev",
+        "@-",
+        "--template-args",
+        "--",
+        "echo",
+        "{",
+    ]);
+    insta::assert_snapshot!(output, @"
+    ------- stderr -------
+    Error: Unmatched brace in argument: {
+    [EOF]
+    [exit status: 1]
+    ");
+
+    let output = work_dir.run_jj(["util", "exec", "--env", "=x", "--", "true"]);
+    insta::assert_snapshot!(output, @"
+    ------- stderr -------
+    error: invalid value '=x' for '--env <KEY=VALUE>': must be specified as KEY=VALUE
+
+    For more information, try '--help'.
+    [EOF]
+    [exit status: 2]
+    ");
+}
+
 #[test]
 fn test_install_man_pages() {
     let test_env = TestEnvironment::default();
@@ -260,6 +354,97 @@ fn test_install_man_pages() {
     assert!(fs::read_dir(man_dir).unwrap().next().is_some());
 }
 
+#[test]
+fn test_util_lint_config() {
+    let test_env = TestEnvironment::default();
+    let output = test_env.run_jj_in(".", ["util", "lint-config"]);
+    insta::assert_snapshot!(output, @"
+    ------- stderr -------
+    No problems found in config
+    [EOF]
+    ");
+
+    test_env.add_config(
+        r#"
+        [revset-aliases]
+        'old(x)' = 'diff_contains(x) | git_head()'
+        'bad' = 'x &'
+
+        [template-aliases]
+        head = 'if(git_head, "HEAD")'
+        "#,
+    );
+    let output = test_env.run_jj_in(".", ["util", "lint-config"]);
+    insta::assert_snapshot!(output, @r#"
+    ------- stderr -------
+    Warning: In revset alias `old(x)` (user config $TEST_ENV/config/config0002.toml)
+     --> 1:1
+      |
+    1 | diff_contains(x) | git_head()
+      | ^-----------^
+      |
+      = diff_contains() is deprecated; use diff_lines() instead
+    Warning: In revset alias `old(x)` (user config $TEST_ENV/config/config0002.toml)
+     --> 1:20
+      |
+    1 | diff_contains(x) | git_head()
+      |                    ^------^
+      |
+      = git_head() is deprecated; use first_parent(@) instead
+    Error: In revset alias `bad` (user config $TEST_ENV/config/config0002.toml)
+     --> 1:4
+      |
+    1 | x &
+      |    ^---
+      |
+      = expected `::`, `..`, `~`, or <primary>
+    Warning: In template alias `head` (user config $TEST_ENV/config/config0002.toml)
+     --> 1:4
+      |
+    1 | if(git_head, "HEAD")
+      |    ^------^
+      |
+      = commit.git_head() is deprecated; use .contained_in('first_parent(@)') instead
+    Error: Found 4 problems in config
+    Hint: 1 of them can be fixed by `jj util lint-config --fix`
+    [EOF]
+    [exit status: 1]
+    "#);
+
+    // Only renamed functions are rewritten
+    let output = test_env.run_jj_in(".", ["util", "lint-config", "--fix"]);
+    insta::assert_snapshot!(output, @r#"
+    ------- stderr -------
+    Warning: In revset alias `old(x)` (user config $TEST_ENV/config/config0002.toml)
+     --> 1:20
+      |
+    1 | diff_contains(x) | git_head()
+      |                    ^------^
+      |
+      = git_head() is deprecated; use first_parent(@) instead
+    Error: In revset alias `bad` (user config $TEST_ENV/config/config0002.toml)
+     --> 1:4
+      |
+    1 | x &
+      |    ^---
+      |
+      = expected `::`, `..`, `~`, or <primary>
+    Warning: In template alias `head` (user config $TEST_ENV/config/config0002.toml)
+     --> 1:4
+      |
+    1 | if(git_head, "HEAD")
+      |    ^------^
+      |
+      = commit.git_head() is deprecated; use .contained_in('first_parent(@)') instead
+    Rewrote 1 aliases in $TEST_ENV/config/config0002.toml
+    Error: Found 3 problems in config
+    [EOF]
+    [exit status: 1]
+    "#);
+    let config = fs::read_to_string(test_env.last_config_file_path()).unwrap();
+    assert!(config.contains("diff_lines(x) | git_head()"));
+}
+
 #[test]
 fn test_util_snapshot() {
     let test_env = TestEnvironment::default();
@@ -289,3 +474,80 @@ fn test_util_snapshot_nothing_changed() {
     [EOF]
     ");
 }
+
+#[test]
+fn test_util_maintenance() {
+    let test_env = TestEnvironment::default();
+    test_env
+        .run_jj_in(".", ["git", "init", "--colocate", "repo"])
+        .success();
+    let work_dir = test_env.work_dir("repo");
+
+    // The config of a removed repo should be garbage-collected.
+    test_env
+        .run_jj_in(".", ["git", "init", "removed"])
+        .success();
+    let removed_dir = test_env.work_dir("removed");
+    removed_dir.run_jj(["config", "path", "--repo"]).success();
+    fs::remove_dir_all(removed_dir.root()).unwrap();
+
+    let output = work_dir.run_jj(["util", "maintenance"]);
+    insta::assert_snapshot!(output, @"
+    ------- stderr -------
+    Ran maintenance task gc
+    Ran maintenance task git-repack
+    Removed 1 unused repo config directories
+    Ran maintenance task secure-config-gc
+    [EOF]
+    ");
+
+    // Tasks can be disabled by config.
+    let output = work_dir.run_jj([
+        "util",
+        "maintenance",
+        "--config=maintenance.gc=false",
+        "--config=maintenance.git-repack=false",
+    ]);
+    insta::assert_snapshot!(output, @"
+    ------- stderr -------
+    Ran maintenance task secure-config-gc
+    [EOF]
+    ");
+
+    // --task overrides the config.
+    let output = work_dir.run_jj([
+        "util",
+        "maintenance",
+        "--config=maintenance.gc=false",
+        "--task=gc",
+    ]);
+    insta::assert_snapshot!(output, @"
+    ------- stderr -------
+    Ran maintenance task gc
+    [EOF]
+    ");
+
+    let output = work_dir.run_jj(["util", "maintenance", "--task=gc", "--at-op=@-"]);
+    insta::assert_snapshot!(output, @"
+    ------- stderr -------
+    Error: Cannot garbage collect from a non-head operation
+    [EOF]
+    [exit status: 1]
+    ");
+}
+
+#[test]
+fn test_util_maintenance_not_colocated() {
+    let test_env = TestEnvironment::default();
+    test_env
+        .run_jj_in(".", ["git", "init", "--no-colocate", "repo"])
+        .success();
+    let work_dir = test_env.work_dir("repo");
+
+    let output = work_dir.run_jj(["util", "maintenance", "--task=git-repack"]);
+    insta::assert_snapshot!(output, @"
+    ------- stderr -------
+    Skipped maintenance task git-repack: the workspace isn't colocated with Git
+    [EOF]
+    ");
+}
@@ -609,3 +609,102 @@ fn test_snapshot_jjconflict_trees() {
     [EOF]
     ");
 }
+
+#[test]
+fn test_content_filters() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+    test_env.add_config(indoc! {r#"
+        [filters.text]
+        files = "glob:'*.txt'"
+        keywords = true
+
+        [working-copy]
+        eol-conversion = "input-output"
+    "#});
+
+    work_dir.write_file("a.txt", "$Id$\nb\n");
+    work_dir.write_file("a.bin", "$Id$\nb\n");
+    work_dir.run_jj(["commit", "-m", "first"]).success();
+    work_dir
+        .run_jj(["bookmark", "create", "-r@-", "first"])
+        .success();
+
+    // Files are stored unfiltered
+    let output = work_dir.run_jj(["file", "show", "-r@-", "a.txt"]);
+    insta::assert_snapshot!(output, @r"
+    $Id$
+    b
+    [EOF]
+    ");
+
+    // Files are filtered when checked out
+    work_dir.run_jj(["new", "root()"]).success();
+    work_dir.run_jj(["new", "first"]).success();
+    assert_eq!(
+        work_dir.read_file("a.txt"),
+        "$Id: 8b25302e75788555c06597ac70d0a79d670478ad $\r\nb\r\n"
+    );
+    assert_eq!(work_dir.read_file("a.bin"), "$Id$\r\nb\r\n");
+
+    // Filtered files round-trip, so they aren't modified
+    let output = work_dir.run_jj(["diff", "--summary"]);
+    insta::assert_snapshot!(output, @"");
+
+    // Edits to filtered files are cleaned when snapshotted
+    work_dir.write_file(
+        "a.txt",
+        "$Id: 8b25302e75788555c06597ac70d0a79d670478ad $\r\nc\r\n",
+    );
+    let output = work_dir.run_jj(["file", "show", "a.txt"]);
+    insta::assert_snapshot!(output, @r"
+    $Id$
+    c
+    [EOF]
+    ");
+}
+
+#[test]
+fn test_eol_setting_with_overrides() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+    test_env.add_config(indoc! {r#"
+        [working-copy]
+        eol = "crlf"
+
+        [working-copy.eol-overrides.scripts]
+        files = "glob:'*.sh'"
+        eol = "lf"
+    "#});
+
+    // Files are stored with LF line endings, and mixed line endings are
+    // reported
+    work_dir.write_file("a.txt", "a\r\nb\r\n");
+    work_dir.write_file("b.sh", "a\r\nb\n");
+    let output = work_dir.run_jj(["file", "show", "a.txt", "b.sh"]);
+    insta::assert_snapshot!(output, @r"
+    a
+    b
+    a
+    b
+    [EOF]
+    ------- stderr -------
+    Warning: Converted mixed line endings to LF in some files:
+      b.sh
+    [EOF]
+    ");
+    work_dir.run_jj(["commit", "-m", "first"]).success();
+    work_dir
+        .run_jj(["bookmark", "create", "-r@-", "first"])
+        .success();
+
+    // Files are checked out with the configured line endings
+    work_dir.run_jj(["new", "root()"]).success();
+    work_dir.run_jj(["new", "first"]).success();
+    assert_eq!(work_dir.read_file("a.txt"), "a\r\nb\r\n");
+    assert_eq!(work_dir.read_file("b.sh"), "a\nb\n");
+    let output = work_dir.run_jj(["diff", "--summary"]);
+    insta::assert_snapshot!(output, @"");
+}
@@ -194,6 +194,41 @@ fn test_workspaces_add_with_message() {
 }
 
 /// Test how sparse patterns are inherited
+#[test]
+fn test_workspaces_add_with_config_template() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "main"]).success();
+    let main_dir = test_env.work_dir("main");
+    let secondary_dir = test_env.work_dir("secondary");
+
+    main_dir
+        .run_jj([
+            "config",
+            "set",
+            "--repo",
+            "workspace-config-template.snapshot.max-new-file-size",
+            "1KiB",
+        ])
+        .success();
+    main_dir
+        .run_jj(["workspace", "add", "../secondary"])
+        .success();
+
+    let output = secondary_dir.run_jj(["config", "list", "--workspace"]);
+    insta::assert_snapshot!(output, @r#"
+    snapshot.max-new-file-size = "1KiB"
+    [EOF]
+    "#);
+
+    // The template isn't applied to the existing workspace
+    let output = main_dir.run_jj(["config", "list", "--workspace"]);
+    insta::assert_snapshot!(output, @"
+    ------- stderr -------
+    Warning: No config to list
+    [EOF]
+    ");
+}
+
 #[test]
 fn test_workspaces_sparse_patterns() {
     let test_env = TestEnvironment::default();
@@ -820,6 +855,21 @@ fn test_workspaces_updated_by_other() {
     [EOF]
     [exit status: 1]
     ");
+    // Preview the update without changing anything
+    let output = secondary_dir.run_jj([
+        "workspace",
+        "update-stale",
+        "--preview",
+        "--config=templates.op_summary='description.first_line().substr(0, 19)'",
+    ]);
+    insta::assert_snapshot!(output, @"
+    Operations not yet applied to the working copy:
+      squash commits into
+      snapshot working co
+    Files to update when checking out pmmvwywv 90f3d42e (empty) (no description set):
+    M file
+    [EOF]
+    ");
     // It was detected that the working copy is now stale, but clean. So no
     // divergent commit should be created.
     let output = secondary_dir.run_jj(["workspace", "update-stale"]);
@@ -840,6 +890,23 @@ fn test_workspaces_updated_by_other() {
     ◆  000000000000
     [EOF]
     ");
+
+    // Operations which don't change the files are still listed
+    main_dir
+        .run_jj(["describe", "secondary@", "-m", "described"])
+        .success();
+    let output = secondary_dir.run_jj([
+        "workspace",
+        "update-stale",
+        "--preview",
+        "--config=templates.op_summary='description.first_line()'",
+    ]);
+    insta::assert_snapshot!(output, @"
+    Operations not yet applied to the working copy:
+      describe commit 90f3d42e0bff073721e2640e32c18fb1c386d7ce
+    No files to update.
+    [EOF]
+    ");
 }
 
 /// Test a clean working copy that gets rewritten from another workspace
@@ -1604,6 +1671,125 @@ fn test_list_workspaces_template() {
     ");
 }
 
+#[test]
+fn test_list_workspaces_path_and_state() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "main"]).success();
+    let main_dir = test_env.work_dir("main");
+    main_dir.write_file("file", "contents");
+    main_dir.run_jj(["commit", "-m", "initial"]).success();
+    main_dir
+        .run_jj(["workspace", "add", "--name", "second", "../secondary"])
+        .success();
+    main_dir
+        .run_jj(["workspace", "add", "--name", "third", "../third"])
+        .success();
+
+    let template = r#"
+    separate(" ",
+      name,
+      path,
+      absolute_path,
+      if(exists, "exists", "missing"),
+      if(stale, "stale"),
+    ) ++ "\n"
+    "#;
+    let output = main_dir.run_jj(["workspace", "list", "-T", template]);
+    insta::assert_snapshot!(output, @"
+    default ../../ $TEST_ENV/main exists
+    second ../../../secondary $TEST_ENV/secondary exists
+    third ../../../third $TEST_ENV/third exists
+    [EOF]
+    ");
+
+    // Make the second workspace stale, and remove the third workspace.
+    main_dir
+        .run_jj(["rebase", "-r", "second@", "-o", "root()"])
+        .success();
+    std::fs::remove_dir_all(test_env.env_root().join("third")).unwrap();
+    let output = main_dir.run_jj(["workspace", "list", "-T", template]);
+    insta::assert_snapshot!(output, @"
+    default ../../ $TEST_ENV/main exists
+    second ../../../secondary $TEST_ENV/secondary exists stale
+    third ../../../third $TEST_ENV/third missing
+    [EOF]
+    ");
+
+    // The path isn't available in other commands.
+    let output = main_dir.run_jj([
+        "log",
+        "-r@",
+        "--no-graph",
+        "-T",
+        "working_copies.map(|w| w.path())",
+    ]);
+    insta::assert_snapshot!(output, @"
+    <Error: Workspace info isn't available>[EOF]
+    ");
+}
+
+#[test]
+fn test_list_workspaces_creation() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "main"]).success();
+    let main_dir = test_env.work_dir("main");
+    main_dir
+        .run_jj(["workspace", "add", "--name", "second", "../secondary"])
+        .success();
+
+    let template = r#"
+    separate(" ",
+      name,
+      created_by,
+      if(created_by, created_time.format("%Y-%m-%d %H:%M:%S")),
+    ) ++ "\n"
+    "#;
+    let output = main_dir.run_jj(["workspace", "list", "-T", template]);
+    insta::assert_snapshot!(output, @"
+    default test-username 2001-02-03 04:05:07
+    second test-username 2001-02-03 04:05:08
+    [EOF]
+    ");
+
+    // The operation which added the workspace is recorded.
+    let output = main_dir.run_jj([
+        "workspace",
+        "list",
+        "-T",
+        r#"name ++ ": " ++ created_operation_id.substr(0, 12) ++ "\n""#,
+    ]);
+    let op_id = output.stdout.raw().lines().nth(1).unwrap()["second: ".len()..].to_owned();
+    let output = main_dir.run_jj([
+        "op",
+        "show",
+        "--no-op-diff",
+        "-T",
+        r#"description ++ "\n""#,
+        &op_id,
+    ]);
+    insta::assert_snapshot!(output, @"
+    add workspace 'second'
+    [EOF]
+    ");
+
+    // The creation metadata is kept when the workspace path is repaired.
+    std::fs::rename(
+        test_env.env_root().join("secondary"),
+        test_env.env_root().join("moved"),
+    )
+    .unwrap();
+    test_env
+        .work_dir("moved")
+        .run_jj(["workspace", "repair"])
+        .success();
+    let output = main_dir.run_jj(["workspace", "list", "-T", template]);
+    insta::assert_snapshot!(output, @"
+    default test-username 2001-02-03 04:05:07
+    second test-username 2001-02-03 04:05:08
+    [EOF]
+    ");
+}
+
 /// Test getting the workspace root from primary and secondary workspaces
 #[test]
 fn test_workspaces_root() {
@@ -1718,6 +1904,119 @@ fn test_workspaces_root_unavailable() {
     ");
 }
 
+#[test]
+fn test_workspaces_repair() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "main"]).success();
+    let main_dir = test_env.work_dir("main");
+    main_dir
+        .run_jj(["workspace", "add", "--name", "secondary", "../secondary"])
+        .success();
+
+    // Nothing to repair
+    let output = main_dir.run_jj(["workspace", "repair"]);
+    insta::assert_snapshot!(output, @"
+    ------- stderr -------
+    Nothing changed.
+    [EOF]
+    ");
+
+    // The path is updated by name
+    std::fs::rename(
+        test_env.env_root().join("secondary"),
+        test_env.env_root().join("moved1"),
+    )
+    .unwrap();
+    let output = main_dir.run_jj(["workspace", "root", "--name", "secondary"]);
+    insta::assert_snapshot!(output.normalize_backslash().strip_stderr_last_line(), @"
+    ------- stderr -------
+    Error: Cannot resolve absolute workspace path: $TEST_ENV/main/.jj/repo/../../../secondary
+    [EOF]
+    [exit status: 1]
+    ");
+    let output = main_dir.run_jj([
+        "workspace",
+        "repair",
+        "--name",
+        "secondary",
+        "--path",
+        "../moved1",
+    ]);
+    insta::assert_snapshot!(output, @"
+    ------- stderr -------
+    Updated the path of workspace secondary to $TEST_ENV/moved1
+    [EOF]
+    ");
+    let output = main_dir.run_jj(["workspace", "root", "--name", "secondary"]);
+    insta::assert_snapshot!(output, @"
+    $TEST_ENV/moved1
+    [EOF]
+    ");
+
+    // The path must point to the named workspace
+    let output = main_dir.run_jj(["workspace", "repair", "--name", "secondary", "--path", "."]);
+    insta::assert_snapshot!(output, @"
+    ------- stderr -------
+    Error: $TEST_ENV/main is not the workspace secondary of this repo
+    [EOF]
+    [exit status: 1]
+    ");
+    let output = main_dir.run_jj(["workspace", "repair", "--name", "unknown", "--path", "."]);
+    insta::assert_snapshot!(output, @"
+    ------- stderr -------
+    Error: No such workspace: unknown
+    [EOF]
+    [exit status: 1]
+    ");
+
+    // The path of the current workspace is updated
+    std::fs::rename(
+        test_env.env_root().join("moved1"),
+        test_env.env_root().join("moved2"),
+    )
+    .unwrap();
+    let moved_dir = test_env.work_dir("moved2");
+    let output = moved_dir.run_jj(["workspace", "repair"]);
+    insta::assert_snapshot!(output, @"
+    ------- stderr -------
+    Updated the path of workspace secondary to $TEST_ENV/moved2
+    [EOF]
+    ");
+
+    // The path is updated automatically if enabled
+    std::fs::rename(
+        test_env.env_root().join("moved2"),
+        test_env.env_root().join("moved3"),
+    )
+    .unwrap();
+    let moved_dir = test_env.work_dir("moved3");
+    let output = moved_dir.run_jj([
+        "workspace",
+        "root",
+        "--name",
+        "secondary",
+        "--config=working-copy.auto-repair-path=true",
+    ]);
+    insta::assert_snapshot!(output, @"
+    $TEST_ENV/moved3
+    [EOF]
+    ------- stderr -------
+    Updated the path of workspace secondary to $TEST_ENV/moved3
+    [EOF]
+    ");
+    let output = moved_dir.run_jj([
+        "workspace",
+        "root",
+        "--name",
+        "secondary",
+        "--config=working-copy.auto-repair-path=true",
+    ]);
+    insta::assert_snapshot!(output, @"
+    $TEST_ENV/moved3
+    [EOF]
+    ");
+}
+
 #[test]
 fn test_debug_snapshot() {
     let test_env = TestEnvironment::default();
@@ -501,6 +501,12 @@ pub trait Backend: Any + Send + Sync + Debug {
         sign_with: Option<&mut SigningFn>,
     ) -> BackendResult<(CommitId, Commit)>;
 
+    /// Makes objects written so far durable, if the backend deferred flushing
+    /// them to disk. Called before a transaction is committed.
+    async fn sync(&self) -> BackendResult<()> {
+        Ok(())
+    }
+
     /// Get copy records for the dag range `root..head`. If `paths` is None
     /// include all paths, otherwise restrict to only `paths`.
     ///
@@ -15,6 +15,7 @@
 //! Configuration store helpers.
 
 use std::borrow::Borrow;
+use std::collections::BTreeMap;
 use std::convert::Infallible;
 use std::fmt;
 use std::fmt::Display;
@@ -27,6 +28,7 @@ use std::slice;
 use std::str::FromStr;
 use std::sync::Arc;
 use std::sync::LazyLock;
+use std::time::SystemTime;
 
 use itertools::Itertools as _;
 use serde::Deserialize;
@@ -543,11 +545,18 @@ fn new_implicit_table() -> ConfigItem {
     ConfigItem::Table(table)
 }
 
+/// Writes config files in an encoded form, e.g. encrypted.
+pub trait ConfigFileEncoder: fmt::Debug + Send + Sync {
+    /// Encodes the TOML `text` and writes it to the file at `path`.
+    fn write_file(&self, path: &Path, text: &str) -> Result<(), PathError>;
+}
+
 /// Wrapper for file-based [`ConfigLayer`], providing convenient methods for
 /// modification.
 #[derive(Clone, Debug)]
 pub struct ConfigFile {
     layer: Arc<ConfigLayer>,
+    encoder: Option<Arc<dyn ConfigFileEncoder>>,
 }
 
 impl ConfigFile {
@@ -575,24 +584,44 @@ impl ConfigFile {
             }
             Err(err) => return Err(err),
         };
-        Ok(Self { layer })
+        Ok(Self {
+            layer,
+            encoder: None,
+        })
     }
 
     /// Wraps file-based [`ConfigLayer`] for modification. Returns `Err(layer)`
     /// if the source `path` is unknown.
     pub fn from_layer(layer: Arc<ConfigLayer>) -> Result<Self, Arc<ConfigLayer>> {
         if layer.path.is_some() {
-            Ok(Self { layer })
+            Ok(Self {
+                layer,
+                encoder: None,
+            })
         } else {
             Err(layer)
         }
     }
 
+    /// Makes [`Self::save()`] write the file through the given `encoder`.
+    pub fn with_encoder(mut self, encoder: Arc<dyn ConfigFileEncoder>) -> Self {
+        self.encoder = Some(encoder);
+        self
+    }
+
+    /// Returns the encoder the file is written through, if any.
+    pub fn encoder(&self) -> Option<&dyn ConfigFileEncoder> {
+        self.encoder.as_deref()
+    }
+
     /// Writes serialized data to the source file.
     pub fn save(&self) -> Result<(), ConfigFileSaveError> {
-        fs::write(self.path(), self.layer.data.to_string())
-            .context(self.path())
-            .map_err(ConfigFileSaveError)
+        let text = self.layer.data.to_string();
+        match &self.encoder {
+            Some(encoder) => encoder.write_file(self.path(), &text),
+            None => fs::write(self.path(), text).context(self.path()),
+        }
+        .map_err(ConfigFileSaveError)
     }
 
     /// Source file path.
@@ -897,6 +926,63 @@ fn merge_items(lower_item: &mut ConfigItem, upper_item: &ConfigItem) {
     }
 }
 
+/// Modification stamps of config files and directories, used by long-lived
+/// processes to detect edits made after the config was loaded.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct ConfigFileStamps {
+    stamps: BTreeMap<PathBuf, Option<(SystemTime, u64)>>,
+}
+
+impl ConfigFileStamps {
+    /// Records the current state of the given files or directories. Paths that
+    /// don't exist are recorded as well, so creating them will be detected.
+    pub fn capture(paths: impl IntoIterator<Item = PathBuf>) -> Self {
+        let stamps = paths
+            .into_iter()
+            .map(|path| {
+                let stamp = file_stamp(&path);
+                (path, stamp)
+            })
+            .collect();
+        Self { stamps }
+    }
+
+    /// Records the files the layers of the `config` were loaded from.
+    pub fn capture_layers(config: &StackedConfig) -> Self {
+        Self::capture(
+            config
+                .layers()
+                .iter()
+                .filter_map(|layer| layer.path.clone()),
+        )
+    }
+
+    /// Returns the paths which have been created, modified, or deleted since
+    /// they were captured.
+    ///
+    /// Directories are compared by their own modification time, which changes
+    /// when entries are added or removed, but not when files in it are edited.
+    pub fn changed_paths(&self) -> Vec<&Path> {
+        self.stamps
+            .iter()
+            .filter(|(path, stamp)| file_stamp(path) != **stamp)
+            .map(|(path, _)| path.as_path())
+            .collect()
+    }
+
+    /// Returns true if any of the paths has changed since captured.
+    pub fn has_changed(&self) -> bool {
+        self.stamps
+            .iter()
+            .any(|(path, stamp)| file_stamp(path) != *stamp)
+    }
+}
+
+fn file_stamp(path: &Path) -> Option<(SystemTime, u64)> {
+    let metadata = path.metadata().ok()?;
+    Some((metadata.modified().ok()?, metadata.len()))
+}
+
 static DEFAULT_CONFIG_LAYERS: LazyLock<[Arc<ConfigLayer>; 1]> = LazyLock::new(|| {
     let parse = |text: &str| Arc::new(ConfigLayer::parse(ConfigSource::Default, text).unwrap());
     [parse(include_str!("config/misc.toml"))]
@@ -909,6 +995,7 @@ mod tests {
     use pretty_assertions::assert_eq;
 
     use super::*;
+    use crate::tests::new_temp_dir;
 
     #[test]
     fn test_config_layer_set_value() {
@@ -1362,4 +1449,32 @@ mod tests {
         insta::assert_snapshot!(config.get_table("a.a").unwrap(), @"b = 'a.a.b #2'");
         assert_eq!(config.table_keys("a.a").collect_vec(), vec!["b"]);
     }
+
+    #[test]
+    fn test_config_file_stamps() {
+        let temp_dir = new_temp_dir();
+        let file_path = temp_dir.path().join("config.toml");
+        let dir_path = temp_dir.path().join("conf.d");
+        let missing_path = temp_dir.path().join("missing.toml");
+        fs::write(&file_path, "a = 1\n").unwrap();
+        fs::create_dir(&dir_path).unwrap();
+
+        let mut config = StackedConfig::empty();
+        config.load_file(ConfigSource::User, &file_path).unwrap();
+        let stamps = ConfigFileStamps::capture_layers(&config);
+        assert!(!stamps.has_changed());
+
+        fs::write(&file_path, "a = 12\n").unwrap();
+        assert_eq!(stamps.changed_paths(), [file_path.as_path()]);
+
+        let stamps = ConfigFileStamps::capture([file_path.clone(), dir_path, missing_path.clone()]);
+        assert!(!stamps.has_changed());
+        fs::write(&missing_path, "").unwrap();
+        assert_eq!(stamps.changed_paths(), [missing_path.as_path()]);
+        fs::remove_file(&file_path).unwrap();
+        assert_eq!(
+            stamps.changed_paths(),
+            [file_path.as_path(), missing_path.as_path()]
+        );
+    }
 }
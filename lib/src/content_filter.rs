@@ -0,0 +1,399 @@
+// Copyright 2026 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Content filters applied to files when they are checked out and snapshotted.
+//!
+//! A filter is configured in a `[filters.<name>]` table, and applies to the
+//! files matched by its `files` fileset. When a file is checked out, its
+//! content is "smudged": keywords are expanded, and the `smudge` command is
+//! run. When a file is snapshotted, its content is "cleaned" by undoing these
+//! steps in reverse order, so the content stored in the repo doesn't depend on
+//! the filters. Line endings aren't converted by the filters, but by the
+//! `working-copy.eol-conversion` setting.
+
+use std::fs;
+use std::fs::File;
+use std::io;
+use std::io::Write as _;
+use std::path::Path;
+use std::path::PathBuf;
+use std::process::Command;
+use std::process::Stdio;
+use std::sync::Arc;
+use std::time::SystemTime;
+
+use bstr::ByteSlice as _;
+use tempfile::NamedTempFile;
+use thiserror::Error;
+
+use crate::backend::FileId;
+use crate::config::ConfigGetError;
+use crate::config::ConfigGetResultExt as _;
+use crate::content_hash::blake2b_hash;
+use crate::file_util::persist_content_addressed_temp_file;
+use crate::fileset;
+use crate::hex_util::encode_hex;
+use crate::matchers::Matcher;
+use crate::object_id::ObjectId as _;
+use crate::repo_path::RepoPath;
+use crate::settings::UserSettings;
+
+/// Maximum number of command results kept in the cache.
+const MAX_CACHE_ENTRIES: usize = 1000;
+
+/// Error that can occur when filtering file content.
+#[derive(Debug, Error)]
+#[error("Filter {filter} failed to process {path}")]
+pub struct ContentFilterError {
+    /// Name of the filter.
+    pub filter: String,
+    /// Path of the file in the repo.
+    pub path: String,
+    /// Underlying error.
+    #[source]
+    pub source: Box<dyn std::error::Error + Send + Sync>,
+}
+
+#[derive(Debug)]
+struct ContentFilter {
+    name: String,
+    matcher: Box<dyn Matcher>,
+    keywords: bool,
+    clean: Option<Vec<String>>,
+    smudge: Option<Vec<String>>,
+}
+
+/// Content filters configured for a working copy.
+#[derive(Clone, Debug, Default)]
+pub struct ContentFilters {
+    filters: Arc<Vec<ContentFilter>>,
+    working_copy_path: PathBuf,
+    cache_dir: Option<PathBuf>,
+}
+
+impl ContentFilters {
+    /// Loads the filters from the `filters` table of the `settings`.
+    pub fn from_settings(settings: &UserSettings) -> Result<Self, ConfigGetError> {
+        let mut filters = vec![];
+        for name in settings.table_keys("filters") {
+            let matcher = settings
+                .get_value_with(["filters", name, "files"], fileset::parse_config_matcher)?;
+            filters.push(ContentFilter {
+                name: name.to_owned(),
+                matcher,
+                keywords: settings
+                    .get_bool(["filters", name, "keywords"])
+                    .optional()?
+                    .unwrap_or(false),
+                clean: settings.get(["filters", name, "clean"]).optional()?,
+                smudge: settings.get(["filters", name, "smudge"]).optional()?,
+            });
+        }
+        Ok(Self {
+            filters: Arc::new(filters),
+            working_copy_path: PathBuf::new(),
+            cache_dir: None,
+        })
+    }
+
+    /// Returns a copy of the filters which run commands in the
+    /// `working_copy_path`, and cache their results in the `cache_dir`.
+    pub fn for_working_copy(&self, working_copy_path: &Path, cache_dir: &Path) -> Self {
+        Self {
+            filters: self.filters.clone(),
+            working_copy_path: working_copy_path.to_owned(),
+            cache_dir: Some(cache_dir.to_owned()),
+        }
+    }
+
+    /// Returns true if any filter applies to the `path`.
+    pub fn matches(&self, path: &RepoPath) -> bool {
+        self.filters
+            .iter()
+            .any(|filter| filter.matcher.matches(path))
+    }
+
+    /// Transforms the `contents` of the file `file_id` at `path` to be written
+    /// to the working copy.
+    pub fn smudge(
+        &self,
+        path: &RepoPath,
+        file_id: &FileId,
+        mut contents: Vec<u8>,
+    ) -> Result<Vec<u8>, ContentFilterError> {
+        for filter in self.filters.iter() {
+            if !filter.matcher.matches(path) {
+                continue;
+            }
+            if filter.keywords {
+                contents = expand_keywords(&contents, file_id);
+            }
+            if let Some(command) = &filter.smudge {
+                contents = self.run_command(filter, "smudge", command, path, contents)?;
+            }
+        }
+        Ok(contents)
+    }
+
+    /// Transforms the `contents` of the working-copy file at `path` to be
+    /// stored in the repo.
+    pub fn clean(
+        &self,
+        path: &RepoPath,
+        mut contents: Vec<u8>,
+    ) -> Result<Vec<u8>, ContentFilterError> {
+        for filter in self.filters.iter().rev() {
+            if !filter.matcher.matches(path) {
+                continue;
+            }
+            if let Some(command) = &filter.clean {
+                contents = self.run_command(filter, "clean", command, path, contents)?;
+            }
+            if filter.keywords {
+                contents = collapse_keywords(&contents);
+            }
+        }
+        Ok(contents)
+    }
+
+    /// Runs the filter `command` with the `contents` as stdin, and returns its
+    /// stdout. The result is cached by the command, path, and content.
+    fn run_command(
+        &self,
+        filter: &ContentFilter,
+        kind: &str,
+        command: &[String],
+        path: &RepoPath,
+        contents: Vec<u8>,
+    ) -> Result<Vec<u8>, ContentFilterError> {
+        let make_error = |source: Box<dyn std::error::Error + Send + Sync>| ContentFilterError {
+            filter: filter.name.clone(),
+            path: path.as_internal_file_string().to_owned(),
+            source,
+        };
+        let cache_path = self.cache_dir.as_ref().map(|dir| {
+            let mut key = vec![kind.as_bytes().to_vec()];
+            key.extend(command.iter().map(|arg| arg.as_bytes().to_vec()));
+            key.push(path.as_internal_file_string().as_bytes().to_vec());
+            key.push(contents.clone());
+            dir.join(encode_hex(&blake2b_hash(&key)))
+        });
+        if let Some(cache_path) = &cache_path
+            && let Ok(output) = fs::read(cache_path)
+        {
+            // Bump the modification time so recently used entries survive gc.
+            if let Ok(file) = File::options().append(true).open(cache_path) {
+                file.set_modified(SystemTime::now()).ok();
+            }
+            return Ok(output);
+        }
+
+        let (name, args) = command
+            .split_first()
+            .ok_or_else(|| make_error("Empty filter command".into()))?;
+        let root = self.working_copy_path.to_string_lossy();
+        let args = args.iter().map(|arg| {
+            arg.replace("$path", path.as_internal_file_string())
+                .replace("$root", &root)
+        });
+        let mut child = Command::new(name)
+            .args(args)
+            .current_dir(&self.working_copy_path)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .map_err(|err| make_error(format!("Failed to start `{name}`: {err}").into()))?;
+        let mut stdin = child.stdin.take().unwrap();
+        let output = std::thread::scope(|s| {
+            // The command may not read all of its input, so the error is
+            // ignored.
+            s.spawn(move || stdin.write_all(&contents).ok());
+            child.wait_with_output()
+        })
+        .map_err(|err| make_error(err.into()))?;
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            let message = format!("`{name}` {}: {}", output.status, stderr.trim_end());
+            return Err(make_error(message.into()));
+        }
+        if let Some(cache_path) = &cache_path {
+            // The cache is only an optimization, so failing to update it isn't
+            // an error.
+            if let Err(err) = write_cache_entry(cache_path, &output.stdout) {
+                tracing::debug!(?err, ?cache_path, "failed to cache filter result");
+            }
+        }
+        Ok(output.stdout)
+    }
+
+    /// Removes the least recently used cache entries exceeding the limit.
+    pub fn gc_cache(&self) {
+        let Some(dir) = &self.cache_dir else {
+            return;
+        };
+        let Ok(entries) = fs::read_dir(dir) else {
+            return;
+        };
+        let mut entries: Vec<(SystemTime, PathBuf)> = entries
+            .filter_map(|entry| {
+                let entry = entry.ok()?;
+                let modified = entry.metadata().ok()?.modified().ok()?;
+                Some((modified, entry.path()))
+            })
+            .collect();
+        if entries.len() <= MAX_CACHE_ENTRIES {
+            return;
+        }
+        entries.sort_unstable();
+        let num_to_remove = entries.len() - MAX_CACHE_ENTRIES;
+        for (_, path) in entries.into_iter().take(num_to_remove) {
+            fs::remove_file(&path).ok();
+        }
+    }
+}
+
+fn write_cache_entry(cache_path: &Path, contents: &[u8]) -> io::Result<()> {
+    let dir = cache_path.parent().unwrap();
+    fs::create_dir_all(dir)?;
+    let mut temp_file = NamedTempFile::new_in(dir)?;
+    temp_file.write_all(contents)?;
+    persist_content_addressed_temp_file(temp_file, cache_path)?;
+    Ok(())
+}
+
+/// Expands `$Id$` keywords to `$Id: <file id> $`.
+fn expand_keywords(contents: &[u8], file_id: &FileId) -> Vec<u8> {
+    contents.replace(b"$Id$", format!("$Id: {} $", file_id.hex()))
+}
+
+/// Collapses expanded `$Id: ... $` keywords back to `$Id$`.
+fn collapse_keywords(contents: &[u8]) -> Vec<u8> {
+    let mut res = Vec::with_capacity(contents.len());
+    let mut rest = contents;
+    while let Some(pos) = rest.find(b"$Id:") {
+        let (before, keyword) = rest.split_at(pos);
+        res.extend_from_slice(before);
+        let value = &keyword[4..];
+        // The expanded keyword ends at the next `$` on the same line.
+        match value.find_byteset(b"$\n") {
+            Some(end) if value[end] == b'$' => {
+                res.extend_from_slice(b"$Id$");
+                rest = &value[end + 1..];
+            }
+            _ => {
+                res.extend_from_slice(b"$Id:");
+                rest = value;
+            }
+        }
+    }
+    res.extend_from_slice(rest);
+    res
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::ConfigLayer;
+    use crate::config::ConfigSource;
+    use crate::config::StackedConfig;
+    use crate::tests::new_temp_dir;
+
+    fn filters_from_toml(text: &str) -> ContentFilters {
+        let mut config = StackedConfig::with_defaults();
+        config.add_layer(ConfigLayer::parse(ConfigSource::User, text).unwrap());
+        let settings = UserSettings::from_config(config).unwrap();
+        ContentFilters::from_settings(&settings).unwrap()
+    }
+
+    fn repo_path(value: &str) -> &RepoPath {
+        RepoPath::from_internal_string(value).unwrap()
+    }
+
+    #[test]
+    fn test_keywords() {
+        let file_id = FileId::from_hex("abcdef");
+        let expanded = expand_keywords(b"a $Id$ b\n$Id$", &file_id);
+        assert_eq!(expanded, b"a $Id: abcdef $ b\n$Id: abcdef $");
+        assert_eq!(collapse_keywords(&expanded), b"a $Id$ b\n$Id$");
+        // Unterminated keywords are left alone.
+        assert_eq!(collapse_keywords(b"$Id: x\n$ $Id:"), b"$Id: x\n$ $Id:");
+    }
+
+    #[test]
+    fn test_smudge_and_clean() {
+        let filters = filters_from_toml(
+            r#"
+            [filters.text]
+            files = "glob:'*.txt'"
+            keywords = true
+            "#,
+        );
+        let file_id = FileId::from_hex("abcdef");
+        assert!(filters.matches(repo_path("a.txt")));
+        assert!(!filters.matches(repo_path("dir/a.txt")));
+        assert!(!filters.matches(repo_path("a.bin")));
+
+        let smudged = filters
+            .smudge(repo_path("a.txt"), &file_id, b"$Id$\nb\n".to_vec())
+            .unwrap();
+        assert_eq!(smudged, b"$Id: abcdef $\nb\n");
+        let cleaned = filters.clean(repo_path("a.txt"), smudged).unwrap();
+        assert_eq!(cleaned, b"$Id$\nb\n");
+
+        // Unmatched files are passed through.
+        let smudged = filters
+            .smudge(repo_path("a.bin"), &file_id, b"$Id$\n".to_vec())
+            .unwrap();
+        assert_eq!(smudged, b"$Id$\n");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_commands() {
+        let temp_dir = new_temp_dir();
+        let filters = filters_from_toml(
+            r#"
+            [filters.upper]
+            files = "all()"
+            clean = ["tr", "A-Z", "a-z"]
+            smudge = ["sh", "-c", "tr a-z A-Z; echo $path"]
+            "#,
+        )
+        .for_working_copy(temp_dir.path(), &temp_dir.path().join("cache"));
+        let file_id = FileId::from_hex("abcdef");
+        let smudged = filters
+            .smudge(repo_path("a"), &file_id, b"foo\n".to_vec())
+            .unwrap();
+        assert_eq!(smudged, b"FOO\na\n");
+        let cleaned = filters.clean(repo_path("a"), b"BAR\n".to_vec()).unwrap();
+        assert_eq!(cleaned, b"bar\n");
+        // The results are cached.
+        assert_eq!(
+            fs::read_dir(temp_dir.path().join("cache")).unwrap().count(),
+            2
+        );
+
+        let filters = filters_from_toml(
+            r#"
+            [filters.fail]
+            files = "all()"
+            clean = ["false"]
+            "#,
+        )
+        .for_working_copy(temp_dir.path(), &temp_dir.path().join("cache"));
+        let err = filters.clean(repo_path("a"), b"".to_vec()).unwrap_err();
+        assert_eq!(err.to_string(), "Filter fail failed to process a");
+    }
+}
@@ -21,6 +21,7 @@ use std::fs::File;
 use std::io::Read;
 use std::io::Write as _;
 use std::path::Path;
+use std::path::PathBuf;
 use std::sync::Arc;
 
 use blake2::Blake2b512;
@@ -29,6 +30,7 @@ use either::Either;
 use futures::StreamExt as _;
 use futures::TryStreamExt as _;
 use itertools::Itertools as _;
+use once_cell::sync::OnceCell;
 use tempfile::NamedTempFile;
 
 use super::entry::GlobalCommitPosition;
@@ -561,6 +563,95 @@ impl CompositeChangedPathIndex {
     }
 }
 
+/// Changed-path index which is loaded from the segment files on first access.
+///
+/// Most commands never query changed paths, so this saves reading and
+/// verifying the segment files, which grow with the number of indexed commits.
+#[derive(Clone, Debug)]
+pub(super) struct LazyChangedPathIndex {
+    index: OnceCell<CompositeChangedPathIndex>,
+    source: Option<Arc<ChangedPathIndexSource>>,
+}
+
+#[derive(Debug)]
+struct ChangedPathIndexSource {
+    dir: PathBuf,
+    start_commit_pos: GlobalCommitPosition,
+    ids: Vec<ChangedPathIndexSegmentId>,
+}
+
+impl LazyChangedPathIndex {
+    /// Wraps the already loaded `index`.
+    pub(super) fn loaded(index: CompositeChangedPathIndex) -> Self {
+        Self {
+            index: OnceCell::with_value(index),
+            source: None,
+        }
+    }
+
+    /// Creates index which will be loaded from the segment files `ids` in
+    /// `dir`.
+    pub(super) fn unloaded(
+        dir: &Path,
+        start_commit_pos: GlobalCommitPosition,
+        ids: Vec<ChangedPathIndexSegmentId>,
+    ) -> Self {
+        let source = ChangedPathIndexSource {
+            dir: dir.to_owned(),
+            start_commit_pos,
+            ids,
+        };
+        Self {
+            index: OnceCell::new(),
+            source: Some(Arc::new(source)),
+        }
+    }
+
+    /// Loads the index if not loaded yet. Unlike [`Self::get()`], this reports
+    /// the error if the segment files can't be loaded.
+    pub(super) fn try_get(&self) -> Result<&CompositeChangedPathIndex, ReadonlyIndexLoadError> {
+        self.index.get_or_try_init(|| {
+            let source = self
+                .source
+                .as_ref()
+                .expect("unloaded index should have source");
+            CompositeChangedPathIndex::load(&source.dir, source.start_commit_pos, &source.ids)
+        })
+    }
+
+    /// Loads the index if not loaded yet. If the segment files can't be
+    /// loaded, a warning is printed and the changed-path index is disabled.
+    /// The index can then be rebuilt by `jj debug index-changed-paths`.
+    ///
+    /// Queries should use [`Self::try_get()`] to report the error instead.
+    pub(super) fn get(&self) -> &CompositeChangedPathIndex {
+        match self.try_get() {
+            Ok(index) => index,
+            Err(err) => {
+                match &err {
+                    ReadonlyIndexLoadError::UnexpectedVersion { .. } => {
+                        eprintln!("{err}. Disabling changed-path index...");
+                    }
+                    ReadonlyIndexLoadError::Other { error, .. } => {
+                        eprintln!("{err}: {error}. Disabling changed-path index...");
+                    }
+                }
+                self.index.get_or_init(CompositeChangedPathIndex::null)
+            }
+        }
+    }
+
+    pub(super) fn get_mut(&mut self) -> &mut CompositeChangedPathIndex {
+        self.get();
+        self.index.get_mut().unwrap()
+    }
+
+    pub(super) fn into_inner(self) -> CompositeChangedPathIndex {
+        self.get();
+        self.index.into_inner().unwrap()
+    }
+}
+
 /// Calculates the parent tree of the given `commit`, and builds a sorted list
 /// of changed paths compared to the parent tree.
 pub(super) async fn collect_changed_paths(
@@ -29,6 +29,7 @@ use ref_cast::ref_cast_custom;
 use super::bit_set::AncestorsBitSet;
 use super::bit_set::PositionsBitSet;
 use super::changed_path::CompositeChangedPathIndex;
+use super::changed_path::LazyChangedPathIndex;
 use super::entry::CommitIndexEntry;
 use super::entry::GlobalCommitPosition;
 use super::entry::LocalCommitPosition;
@@ -36,6 +37,7 @@ use super::entry::SmallGlobalCommitPositionsVec;
 use super::entry::SmallLocalCommitPositionsVec;
 use super::mutable::MutableCommitIndexSegment;
 use super::readonly::ReadonlyCommitIndexSegment;
+use super::readonly::ReadonlyIndexLoadError;
 use super::rev_walk::filter_slice_by_range;
 use super::revset_engine;
 use crate::backend::ChangeId;
@@ -43,6 +45,7 @@ use crate::backend::CommitId;
 use crate::hex_util;
 use crate::index::ChangeIdIndex;
 use crate::index::Index;
+use crate::index::IndexError;
 use crate::index::IndexResult;
 use crate::index::ResolvedChangeState;
 use crate::index::ResolvedChangeTargets;
@@ -523,13 +526,13 @@ enum CompositeCommitIndexSegment {
 #[derive(Clone, Debug)]
 pub(super) struct CompositeIndex {
     commits: CompositeCommitIndexSegment,
-    changed_paths: CompositeChangedPathIndex,
+    changed_paths: LazyChangedPathIndex,
 }
 
 impl CompositeIndex {
     pub(super) fn from_readonly(
         commits: Arc<ReadonlyCommitIndexSegment>,
-        changed_paths: CompositeChangedPathIndex,
+        changed_paths: LazyChangedPathIndex,
     ) -> Self {
         Self {
             commits: CompositeCommitIndexSegment::Readonly(commits),
@@ -543,7 +546,7 @@ impl CompositeIndex {
     ) -> Self {
         Self {
             commits: CompositeCommitIndexSegment::Mutable(commits),
-            changed_paths,
+            changed_paths: LazyChangedPathIndex::loaded(changed_paths),
         }
     }
 
@@ -554,7 +557,7 @@ impl CompositeIndex {
             CompositeCommitIndexSegment::Readonly(_) => return None,
             CompositeCommitIndexSegment::Mutable(segment) => segment,
         };
-        Some((commits, self.changed_paths))
+        Some((commits, self.changed_paths.into_inner()))
     }
 
     pub(super) fn commits(&self) -> &CompositeCommitIndex {
@@ -579,11 +582,19 @@ impl CompositeIndex {
     }
 
     pub(super) fn changed_paths(&self) -> &CompositeChangedPathIndex {
-        &self.changed_paths
+        self.changed_paths.get()
+    }
+
+    /// Like [`Self::changed_paths()`], but reports the error if the
+    /// changed-path index can't be loaded.
+    pub(super) fn try_changed_paths(
+        &self,
+    ) -> Result<&CompositeChangedPathIndex, ReadonlyIndexLoadError> {
+        self.changed_paths.try_get()
     }
 
     pub(super) fn changed_paths_mut(&mut self) -> &mut CompositeChangedPathIndex {
-        &mut self.changed_paths
+        self.changed_paths.get_mut()
     }
 }
 
@@ -635,10 +646,13 @@ impl Index for CompositeIndex {
         &self,
         commit_id: &CommitId,
     ) -> IndexResult<Option<Box<dyn Iterator<Item = RepoPathBuf> + '_>>> {
+        let changed_paths = self
+            .try_changed_paths()
+            .map_err(|err| IndexError::Other(err.into()))?;
         let Some(paths) = self
             .commits()
             .commit_id_to_pos(commit_id)
-            .and_then(|pos| self.changed_paths().changed_paths(pos))
+            .and_then(|pos| changed_paths.changed_paths(pos))
         else {
             return Ok(None);
         };
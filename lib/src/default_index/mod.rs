@@ -39,6 +39,7 @@ pub use self::readonly::DefaultReadonlyIndexRevset;
 pub use self::readonly::IndexStats;
 pub use self::readonly::ReadonlyIndexLoadError;
 pub use self::store::DefaultChangedPathIndexProgress;
+pub use self::store::DefaultIndexProblem;
 pub use self::store::DefaultIndexStore;
 pub use self::store::DefaultIndexStoreError;
 pub use self::store::DefaultIndexStoreInitError;
@@ -26,11 +26,14 @@ use std::ops::Range;
 use std::path::Path;
 use std::sync::Arc;
 
+use blake2::Blake2b512;
+use digest::Digest as _;
 use itertools::Itertools as _;
 use smallvec::smallvec;
 use thiserror::Error;
 
 use super::changed_path::CompositeChangedPathIndex;
+use super::changed_path::LazyChangedPathIndex;
 use super::composite::AsCompositeIndex;
 use super::composite::ChangeIdIndexImpl;
 use super::composite::CommitIndexSegment;
@@ -126,6 +129,30 @@ impl ReadonlyIndexLoadError {
     }
 }
 
+/// Checks that the contents of the index segment file of the given `id` match
+/// the file name, which is the hash of the contents.
+///
+/// This reads the whole file, so it's only done when verifying the index, not
+/// every time the segment is loaded.
+pub(super) fn verify_segment_file(
+    kind: &'static str,
+    dir: &Path,
+    id: &impl ObjectId,
+) -> Result<(), ReadonlyIndexLoadError> {
+    let from_io_err = |err| ReadonlyIndexLoadError::from_io_err(kind, id.hex(), err);
+    let mut file = File::open(dir.join(id.hex())).map_err(from_io_err)?;
+    let mut hasher = Blake2b512::new();
+    io::copy(&mut file, &mut hasher).map_err(from_io_err)?;
+    if hasher.finalize().as_slice() != id.as_bytes() {
+        return Err(ReadonlyIndexLoadError::invalid_data(
+            kind,
+            id.hex(),
+            "checksum mismatch",
+        ));
+    }
+    Ok(())
+}
+
 /// Current format version of the commit index segment file.
 pub(super) const COMMIT_INDEX_SEGMENT_FILE_FORMAT_VERSION: u32 = 6;
 
@@ -616,6 +643,15 @@ impl DefaultReadonlyIndex {
     pub(super) fn from_segment(
         commits: Arc<ReadonlyCommitIndexSegment>,
         changed_paths: CompositeChangedPathIndex,
+    ) -> Self {
+        let changed_paths = LazyChangedPathIndex::loaded(changed_paths);
+        Self(CompositeIndex::from_readonly(commits, changed_paths))
+    }
+
+    /// Creates index whose changed-path index is loaded on first access.
+    pub(super) fn from_lazy_segment(
+        commits: Arc<ReadonlyCommitIndexSegment>,
+        changed_paths: LazyChangedPathIndex,
     ) -> Self {
         Self(CompositeIndex::from_readonly(commits, changed_paths))
     }
@@ -628,6 +664,14 @@ impl DefaultReadonlyIndex {
         self.0.changed_paths()
     }
 
+    /// Loads the changed-path index if not loaded yet, and reports the error
+    /// if it can't be loaded.
+    pub(super) fn try_changed_paths(
+        &self,
+    ) -> Result<&CompositeChangedPathIndex, ReadonlyIndexLoadError> {
+        self.0.try_changed_paths()
+    }
+
     pub(super) fn has_id_impl(&self, commit_id: &CommitId) -> bool {
         self.0.commits().has_id(commit_id)
     }
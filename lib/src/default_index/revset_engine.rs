@@ -58,11 +58,13 @@ use crate::object_id::HexPrefix;
 use crate::object_id::ObjectId as _;
 use crate::object_id::PrefixResolution;
 use crate::repo_path::RepoPath;
+use crate::revset::CursorGraphNode;
 use crate::revset::GENERATION_RANGE_FULL;
 use crate::revset::ResolvedExpression;
 use crate::revset::ResolvedPredicateExpression;
 use crate::revset::Revset;
 use crate::revset::RevsetContainingFn;
+use crate::revset::RevsetCursor;
 use crate::revset::RevsetEvaluationError;
 use crate::revset::RevsetFilterPredicate;
 use crate::rewrite;
@@ -102,6 +104,12 @@ trait InternalRevset: fmt::Debug + ToPredicateFn {
     fn positions<'a>(&self) -> BoxedRevWalk<'a>
     where
         Self: 'a;
+
+    /// Like [`InternalRevset::positions()`], but starts at the first position
+    /// below `end`.
+    fn positions_below<'a>(&self, end: GlobalCommitPosition) -> BoxedRevWalk<'a>
+    where
+        Self: 'a;
 }
 
 impl<T: InternalRevset + ?Sized> InternalRevset for Box<T> {
@@ -111,6 +119,13 @@ impl<T: InternalRevset + ?Sized> InternalRevset for Box<T> {
     {
         <T as InternalRevset>::positions(self)
     }
+
+    fn positions_below<'a>(&self, end: GlobalCommitPosition) -> BoxedRevWalk<'a>
+    where
+        Self: 'a,
+    {
+        <T as InternalRevset>::positions_below(self, end)
+    }
 }
 
 pub(super) struct RevsetImpl<I> {
@@ -138,6 +153,17 @@ impl<I: AsCompositeIndex + Clone> RevsetImpl<I> {
         let mut graph_walk = RevsetGraphWalk::new(walk, skip_transitive_edges);
         iter::from_fn(move || graph_walk.next(index.as_composite()))
     }
+
+    /// Walks positions below the one the `after` cursor points to.
+    fn positions_after<'a>(&self, after: Option<RevsetCursor>) -> BoxedRevWalk<'a>
+    where
+        Self: 'a,
+    {
+        match after {
+            Some(after) => self.inner.positions_below(cursor_to_position(after)),
+            None => self.inner.positions(),
+        }
+    }
 }
 
 impl<I> fmt::Debug for RevsetImpl<I> {
@@ -185,6 +211,41 @@ impl<I: AsCompositeIndex + Clone> Revset for RevsetImpl<I> {
         Box::new(self.iter_graph_impl(skip_transitive_edges))
     }
 
+    fn iter_with_cursors<'a>(
+        &self,
+        after: Option<RevsetCursor>,
+    ) -> Box<dyn Iterator<Item = Result<(RevsetCursor, CommitId), RevsetEvaluationError>> + 'a>
+    where
+        Self: 'a,
+    {
+        let index = self.index.clone();
+        let mut walk = self.positions_after(after).map(|index, pos| {
+            let pos = pos?;
+            let commit_id = index.commits().entry_by_pos(pos).commit_id();
+            Ok((position_to_cursor(pos), commit_id))
+        });
+        Box::new(iter::from_fn(move || walk.next(index.as_composite())))
+    }
+
+    fn iter_graph_with_cursors<'a>(
+        &self,
+        after: Option<RevsetCursor>,
+    ) -> Box<dyn Iterator<Item = Result<CursorGraphNode, RevsetEvaluationError>> + 'a>
+    where
+        Self: 'a,
+    {
+        let index = self.index.clone();
+        let skip_transitive_edges = true;
+        // Edges of a node only depend on the nodes below it, so the graph can
+        // be resumed by dropping the positions above the cursor.
+        let mut graph_walk =
+            RevsetGraphWalk::new(self.positions_after(after), skip_transitive_edges);
+        Box::new(iter::from_fn(move || {
+            let node = graph_walk.next_with_position(index.as_composite())?;
+            Some(node.map(|(pos, node)| (position_to_cursor(pos), node)))
+        }))
+    }
+
     fn is_empty(&self) -> bool {
         self.positions().next().is_none()
     }
@@ -218,6 +279,16 @@ impl<I: AsCompositeIndex + Clone> Revset for RevsetImpl<I> {
     }
 }
 
+fn position_to_cursor(pos: GlobalCommitPosition) -> RevsetCursor {
+    RevsetCursor::new(pos.0.into())
+}
+
+fn cursor_to_position(cursor: RevsetCursor) -> GlobalCommitPosition {
+    // A cursor beyond the index can't have been obtained from this index.
+    // Clamp it so that nothing is skipped.
+    GlobalCommitPosition(cursor.value().try_into().unwrap_or(u32::MAX))
+}
+
 /// Incrementally consumes `RevWalk` of the revset collecting positions.
 struct PositionsAccumulator<'a, I> {
     index: I,
@@ -304,6 +375,17 @@ impl InternalRevset for EagerRevset {
         let walk = EagerRevWalk::new(self.positions.clone().into_iter());
         Box::new(walk.map(|_index, pos| Ok(pos)))
     }
+
+    fn positions_below<'a>(&self, end: GlobalCommitPosition) -> BoxedRevWalk<'a>
+    where
+        Self: 'a,
+    {
+        // Positions are sorted in descending order
+        let start = self.positions.partition_point(|&pos| pos >= end);
+        let positions = self.positions[start..].to_vec();
+        let walk = EagerRevWalk::new(positions.into_iter());
+        Box::new(walk.map(|_index, pos| Ok(pos)))
+    }
 }
 
 impl ToPredicateFn for EagerRevset {
@@ -337,6 +419,19 @@ where
     {
         Box::new(self.walk.clone().map(|_index, pos| Ok(pos)))
     }
+
+    fn positions_below<'a>(&self, end: GlobalCommitPosition) -> BoxedRevWalk<'a>
+    where
+        Self: 'a,
+    {
+        // Walks such as ancestors() can't be resumed in the middle, but the
+        // positions above `end` are skipped without loading their commits.
+        Box::new(
+            self.walk
+                .clone()
+                .filter_map(move |_index, pos| (pos < end).then_some(Ok(pos))),
+        )
+    }
 }
 
 impl<W> ToPredicateFn for RevWalkRevset<W>
@@ -385,6 +480,21 @@ where
                 .transpose()
         }))
     }
+
+    fn positions_below<'a>(&self, end: GlobalCommitPosition) -> BoxedRevWalk<'a>
+    where
+        Self: 'a,
+    {
+        let mut p = self.predicate.to_predicate_fn();
+        Box::new(
+            self.candidates
+                .positions_below(end)
+                .filter_map(move |index, pos| {
+                    pos.and_then(|pos| Ok(p(index, pos)?.then_some(pos)))
+                        .transpose()
+                }),
+        )
+    }
 }
 
 impl<S, P> ToPredicateFn for FilterRevset<S, P>
@@ -436,6 +546,17 @@ where
             |pos1, pos2| pos1.cmp(pos2).reverse(),
         ))
     }
+
+    fn positions_below<'a>(&self, end: GlobalCommitPosition) -> BoxedRevWalk<'a>
+    where
+        Self: 'a,
+    {
+        Box::new(union_by(
+            self.set1.positions_below(end),
+            self.set2.positions_below(end),
+            |pos1, pos2| pos1.cmp(pos2).reverse(),
+        ))
+    }
 }
 
 impl<S1, S2> ToPredicateFn for UnionRevset<S1, S2>
@@ -524,6 +645,17 @@ where
             |pos1, pos2| pos1.cmp(pos2).reverse(),
         ))
     }
+
+    fn positions_below<'a>(&self, end: GlobalCommitPosition) -> BoxedRevWalk<'a>
+    where
+        Self: 'a,
+    {
+        Box::new(intersection_by(
+            self.set1.positions_below(end),
+            self.set2.positions_below(end),
+            |pos1, pos2| pos1.cmp(pos2).reverse(),
+        ))
+    }
 }
 
 impl<S1, S2> ToPredicateFn for IntersectionRevset<S1, S2>
@@ -632,6 +764,17 @@ where
             |pos1, pos2| pos1.cmp(pos2).reverse(),
         ))
     }
+
+    fn positions_below<'a>(&self, end: GlobalCommitPosition) -> BoxedRevWalk<'a>
+    where
+        Self: 'a,
+    {
+        Box::new(difference_by(
+            self.set1.positions_below(end),
+            self.set2.positions_below(end),
+            |pos1, pos2| pos1.cmp(pos2).reverse(),
+        ))
+    }
 }
 
 impl<S1, S2> ToPredicateFn for DifferenceRevset<S1, S2>
@@ -1308,7 +1451,10 @@ fn build_predicate_fn(
         RevsetFilterPredicate::File(expr) => {
             let matcher: Rc<dyn Matcher> = expr.to_matcher().into();
             box_pure_predicate_fn(move |index, pos| {
-                if let Some(mut paths) = index.changed_paths().changed_paths(pos) {
+                let changed_paths = index
+                    .try_changed_paths()
+                    .map_err(|err| RevsetEvaluationError::Other(err.into()))?;
+                if let Some(mut paths) = changed_paths.changed_paths(pos) {
                     return Ok(paths.any(|path| matcher.matches(path)));
                 }
                 let entry = index.commits().entry_by_pos(pos);
@@ -1320,8 +1466,11 @@ fn build_predicate_fn(
             let text_matcher = Rc::new(text.to_matcher());
             let files_matcher: Rc<dyn Matcher> = files.to_matcher().into();
             box_pure_predicate_fn(move |index, pos| {
+                let changed_paths = index
+                    .try_changed_paths()
+                    .map_err(|err| RevsetEvaluationError::Other(err.into()))?;
                 let narrowed_files_matcher;
-                let files_matcher = if let Some(paths) = index.changed_paths().changed_paths(pos) {
+                let files_matcher = if let Some(paths) = changed_paths.changed_paths(pos) {
                     let matched_paths = paths
                         .filter(|path| files_matcher.matches(path))
                         .collect_vec();
@@ -355,7 +355,7 @@ impl<'a> RevsetGraphWalk<'a> {
     fn try_next(
         &mut self,
         index: &CompositeIndex,
-    ) -> Result<Option<GraphNode<CommitId>>, RevsetEvaluationError> {
+    ) -> Result<Option<(GlobalCommitPosition, GraphNode<CommitId>)>, RevsetEvaluationError> {
         let Some(position) = self.next_index_position(index)? else {
             return Ok(None);
         };
@@ -365,7 +365,15 @@ impl<'a> RevsetGraphWalk<'a> {
             .iter()
             .map(|edge| edge.map(|pos| index.commits().entry_by_pos(pos).commit_id()))
             .collect();
-        Ok(Some((entry.commit_id(), edges)))
+        Ok(Some((position, (entry.commit_id(), edges))))
+    }
+
+    /// Like [`RevWalk::next()`], but also returns the position of the node.
+    pub fn next_with_position(
+        &mut self,
+        index: &CompositeIndex,
+    ) -> Option<Result<(GlobalCommitPosition, GraphNode<CommitId>), RevsetEvaluationError>> {
+        self.try_next(index).transpose()
     }
 }
 
@@ -373,7 +381,9 @@ impl RevWalk<CompositeIndex> for RevsetGraphWalk<'_> {
     type Item = Result<GraphNode<CommitId>, RevsetEvaluationError>;
 
     fn next(&mut self, index: &CompositeIndex) -> Option<Self::Item> {
-        self.try_next(index).transpose()
+        self.try_next(index)
+            .map(|node| node.map(|(_, node)| node))
+            .transpose()
     }
 }
 
@@ -19,6 +19,7 @@ use std::collections::HashSet;
 use std::fs;
 use std::io;
 use std::io::Write as _;
+use std::iter;
 use std::path::Path;
 use std::path::PathBuf;
 use std::slice;
@@ -32,8 +33,10 @@ use thiserror::Error;
 
 use super::changed_path::ChangedPathIndexSegmentId;
 use super::changed_path::CompositeChangedPathIndex;
+use super::changed_path::LazyChangedPathIndex;
 use super::changed_path::collect_changed_paths;
 use super::composite::AsCompositeIndex as _;
+use super::composite::CommitIndexSegment as _;
 use super::composite::CommitIndexSegmentId;
 use super::entry::GlobalCommitPosition;
 use super::mutable::DefaultMutableIndex;
@@ -41,6 +44,7 @@ use super::readonly::DefaultReadonlyIndex;
 use super::readonly::FieldLengths;
 use super::readonly::ReadonlyCommitIndexSegment;
 use super::readonly::ReadonlyIndexLoadError;
+use super::readonly::verify_segment_file;
 use crate::backend::BackendError;
 use crate::backend::BackendInitError;
 use crate::backend::CommitId;
@@ -98,6 +102,20 @@ pub enum DefaultIndexStoreError {
     OpStore(#[from] OpStoreError),
 }
 
+/// Problem found by [`DefaultIndexStore::verify_index_at_operation()`].
+#[derive(Debug, Error)]
+pub enum DefaultIndexProblem {
+    /// Index files are missing, truncated, or don't match their checksums.
+    #[error("Index files are corrupt")]
+    Corrupt(#[source] ReadonlyIndexLoadError),
+    /// Indexed commit doesn't exist in the commit store.
+    #[error("Commit {0} is indexed but missing in the store")]
+    MissingCommit(CommitId),
+    /// Indexed parents or change id differ from the commit in the store.
+    #[error("Commit {0} is indexed with different parents or change id than in the store")]
+    Mismatch(CommitId),
+}
+
 #[derive(Debug)]
 pub struct DefaultIndexStore {
     dir: PathBuf,
@@ -226,18 +244,95 @@ impl DefaultIndexStore {
             lengths,
         )
         .map_err(DefaultIndexStoreError::LoadIndex)?;
-        // TODO: lazy load or mmap?
+        // The changed-path index is only needed by path queries, so it's
+        // loaded on first access.
         let changed_paths = if let Some(start_commit_pos) = changed_path_start_commit_pos {
-            CompositeChangedPathIndex::load(
+            LazyChangedPathIndex::unloaded(
                 &self.changed_path_segments_dir(),
                 start_commit_pos,
-                &changed_path_segment_ids,
+                changed_path_segment_ids,
             )
-            .map_err(DefaultIndexStoreError::LoadIndex)?
         } else {
-            CompositeChangedPathIndex::null()
+            LazyChangedPathIndex::loaded(CompositeChangedPathIndex::null())
         };
-        Ok(DefaultReadonlyIndex::from_segment(commits, changed_paths))
+        Ok(DefaultReadonlyIndex::from_lazy_segment(
+            commits,
+            changed_paths,
+        ))
+    }
+
+    /// Checks the index at the given `operation` against the commit store.
+    ///
+    /// Index files are verified against their checksums, which isn't done
+    /// when loading the index for other commands. If they are intact, the
+    /// parents and change id of every indexed commit are compared with the
+    /// commit in the store. Unlike
+    /// [`IndexStore::get_index_at_op()`], this never rebuilds the index.
+    pub async fn verify_index_at_operation(
+        &self,
+        operation: &Operation,
+        store: &Arc<Store>,
+    ) -> Result<Vec<DefaultIndexProblem>, DefaultIndexStoreError> {
+        let field_lengths = FieldLengths {
+            commit_id: store.commit_id_length(),
+            change_id: store.change_id_length(),
+        };
+        let index = match self.load_index_at_operation(operation.id(), field_lengths) {
+            Ok(index) => index,
+            Err(DefaultIndexStoreError::LoadIndex(err)) if err.is_corrupt_or_not_found() => {
+                return Ok(vec![DefaultIndexProblem::Corrupt(err)]);
+            }
+            Err(err) => return Err(err),
+        };
+        let verify_segment_files = || -> Result<(), ReadonlyIndexLoadError> {
+            let commit_segments = iter::successors(Some(index.readonly_commits()), |segment| {
+                segment.parent_file()
+            });
+            for segment in commit_segments {
+                verify_segment_file("commit", &self.commit_segments_dir(), segment.id())?;
+            }
+            for segment in index.try_changed_paths()?.readonly_segments() {
+                verify_segment_file(
+                    "changed-path",
+                    &self.changed_path_segments_dir(),
+                    segment.id(),
+                )?;
+            }
+            Ok(())
+        };
+        match verify_segment_files() {
+            Ok(()) => {}
+            Err(err) if err.is_corrupt_or_not_found() => {
+                return Ok(vec![DefaultIndexProblem::Corrupt(err)]);
+            }
+            Err(err) => return Err(DefaultIndexStoreError::LoadIndex(err)),
+        }
+        let commits = index.as_composite().commits();
+        let mut problems = vec![];
+        for pos in (0..commits.num_commits()).map(GlobalCommitPosition) {
+            let entry = commits.entry_by_pos(pos);
+            let commit_id = entry.commit_id();
+            let commit = match store.get_commit_async(&commit_id).await {
+                Ok(commit) => commit,
+                Err(BackendError::ObjectNotFound { .. }) => {
+                    problems.push(DefaultIndexProblem::MissingCommit(commit_id));
+                    continue;
+                }
+                Err(source) => {
+                    return Err(DefaultIndexStoreError::IndexCommits {
+                        op_id: operation.id().clone(),
+                        source,
+                    });
+                }
+            };
+            let parent_ids = entry.parents().map(|parent| parent.commit_id());
+            if !parent_ids.eq(commit.parent_ids().iter().cloned())
+                || entry.change_id() != *commit.change_id()
+            {
+                problems.push(DefaultIndexProblem::Mismatch(commit_id));
+            }
+        }
+        Ok(problems)
     }
 
     /// Rebuilds index for the given `operation`.
@@ -585,6 +680,14 @@ impl IndexStore for DefaultIndexStore {
             {
                 self.build_index_at_operation(op, store).await
             }
+            Err(DefaultIndexStoreError::LoadAssociation(err))
+                if err.source.kind() == io::ErrorKind::InvalidData =>
+            {
+                // The index files are intact, but we don't know which ones
+                // belong to this operation. Index it as if it were new.
+                eprintln!("{err}: {}. Reindexing...", err.source);
+                self.build_index_at_operation(op, store).await
+            }
             Err(DefaultIndexStoreError::LoadIndex(err)) if err.is_corrupt_or_not_found() => {
                 // If the index was corrupt (maybe it was written in a different format),
                 // we just reindex.
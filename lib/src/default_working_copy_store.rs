@@ -0,0 +1,196 @@
+// Copyright 2026 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Working copy store which keeps one local working copy per tree.
+
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use pollster::FutureExt as _;
+
+use crate::commit::Commit;
+use crate::content_hash::blake2b_hash;
+use crate::file_util::IoResultExt as _;
+use crate::file_util::PathError;
+use crate::hex_util;
+use crate::local_working_copy::LocalWorkingCopy;
+use crate::lock::FileLock;
+use crate::object_id::ObjectId as _;
+use crate::op_store::OperationId;
+use crate::ref_name::WorkspaceNameBuf;
+use crate::settings::UserSettings;
+use crate::store::Store;
+use crate::working_copy::WorkingCopy as _;
+use crate::working_copy::WorkingCopyStateError;
+use crate::working_copy_store::StoredWorkingCopy;
+use crate::working_copy_store::WorkingCopyStore;
+use crate::working_copy_store::WorkingCopyStoreError;
+
+/// Working copy store which keeps the working copies in subdirectories of the
+/// store directory, which is `.jj/repo/run/` by default.
+///
+/// Each working copy is named after the tree it has checked out, so revisions
+/// with the same contents share a working copy. A subdirectory contains the
+/// `working_copy/` directory where the files are materialized, the `state/`
+/// directory of the [`LocalWorkingCopy`], and the `output/` directory for
+/// command outputs.
+#[derive(Debug)]
+pub struct DefaultWorkingCopyStore {
+    store: Arc<Store>,
+    store_path: PathBuf,
+    settings: UserSettings,
+}
+
+impl DefaultWorkingCopyStore {
+    /// Returns the name of this working copy store implementation.
+    pub fn name() -> &'static str {
+        "default"
+    }
+
+    /// Loads the store from the given directory. The directory is created
+    /// when the first working copy is added.
+    pub fn load(store: Arc<Store>, store_path: PathBuf, settings: &UserSettings) -> Self {
+        Self {
+            store,
+            store_path,
+            settings: settings.clone(),
+        }
+    }
+
+    /// Returns the directory where the working copies are stored.
+    pub fn store_path(&self) -> &Path {
+        &self.store_path
+    }
+
+    fn working_copy_dir(&self, commit: &Commit) -> PathBuf {
+        let tree_ids = commit.tree_ids();
+        let name = match tree_ids.as_resolved() {
+            Some(tree_id) => tree_id.hex(),
+            None => hex_util::encode_hex(&blake2b_hash(tree_ids)),
+        };
+        self.store_path.join(name)
+    }
+
+    /// Loads the existing working copy, or returns `None` if it's missing or
+    /// doesn't have the tree of the `commit` checked out, e.g. because an
+    /// earlier checkout was interrupted.
+    fn load_existing(
+        &self,
+        working_copy_path: &Path,
+        state_path: &Path,
+        commit: &Commit,
+    ) -> Result<Option<LocalWorkingCopy>, WorkingCopyStateError> {
+        if !state_path.is_dir() {
+            return Ok(None);
+        }
+        let working_copy = LocalWorkingCopy::load(
+            self.store.clone(),
+            working_copy_path.to_owned(),
+            state_path.to_owned(),
+            &self.settings,
+        )?;
+        if working_copy.tree()?.tree_ids() == commit.tree_ids() {
+            Ok(Some(working_copy))
+        } else {
+            Ok(None)
+        }
+    }
+}
+
+impl WorkingCopyStore for DefaultWorkingCopyStore {
+    fn name(&self) -> &str {
+        Self::name()
+    }
+
+    fn get_or_create_working_copy(
+        &self,
+        commit: &Commit,
+        operation_id: &OperationId,
+    ) -> Result<StoredWorkingCopy, WorkingCopyStoreError> {
+        fs::create_dir_all(&self.store_path).context(&self.store_path)?;
+        let dir = self.working_copy_dir(commit);
+        let lock = FileLock::lock(dir.with_extension("lock"))?;
+        let working_copy_path = dir.join("working_copy");
+        let state_path = dir.join("state");
+        let output_path = dir.join("output");
+        // A working copy which can't be loaded is as good as a missing one.
+        if let Ok(Some(working_copy)) = self.load_existing(&working_copy_path, &state_path, commit)
+        {
+            fs::create_dir_all(&output_path).context(&output_path)?;
+            return Ok(StoredWorkingCopy {
+                working_copy: Box::new(working_copy),
+                path: working_copy_path,
+                output_path,
+                reused: true,
+                lock: Some(lock),
+            });
+        }
+
+        match fs::remove_dir_all(&dir) {
+            Ok(()) => {}
+            Err(err) if err.kind() == io::ErrorKind::NotFound => {}
+            Err(source) => return Err(PathError { path: dir, source }.into()),
+        }
+        fs::create_dir_all(&working_copy_path).context(&working_copy_path)?;
+        fs::create_dir(&state_path).context(&state_path)?;
+        fs::create_dir(&output_path).context(&output_path)?;
+        let working_copy = LocalWorkingCopy::init(
+            self.store.clone(),
+            working_copy_path.clone(),
+            state_path,
+            operation_id.clone(),
+            WorkspaceNameBuf::from("run"),
+            &self.settings,
+        )?;
+        let mut locked_wc = working_copy.start_mutation()?;
+        locked_wc.check_out(commit).block_on()?;
+        let working_copy = locked_wc.finish(operation_id.clone()).block_on()?;
+        Ok(StoredWorkingCopy {
+            working_copy,
+            path: working_copy_path,
+            output_path,
+            reused: false,
+            lock: Some(lock),
+        })
+    }
+
+    fn clean(&self) -> Result<usize, WorkingCopyStoreError> {
+        let entries = match fs::read_dir(&self.store_path) {
+            Ok(entries) => entries,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(0),
+            Err(source) => {
+                let path = self.store_path.clone();
+                return Err(PathError { path, source }.into());
+            }
+        };
+        let mut count = 0;
+        for entry in entries {
+            let path = entry.context(&self.store_path)?.path();
+            if path.is_dir() {
+                // Skip working copies in use by another process instead of
+                // waiting for it to finish.
+                let Some(_lock) = FileLock::try_lock(path.with_extension("lock"))? else {
+                    continue;
+                };
+                fs::remove_dir_all(&path).context(&path)?;
+                count += 1;
+            }
+        }
+        // The lock files are removed by the time the locks are released.
+        Ok(count)
+    }
+}
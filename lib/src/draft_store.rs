@@ -0,0 +1,203 @@
+// Copyright 2026 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Set of changes marked as drafts, which shouldn't be pushed.
+//!
+//! Changes are identified by their change ID, so the flag is kept when a
+//! change is rewritten. The flags are stored next to the operation log rather
+//! than in it, and aren't rolled back by `jj undo` or `jj op restore`.
+
+use std::collections::BTreeSet;
+use std::fs;
+use std::io;
+use std::io::Write as _;
+use std::path::Path;
+use std::path::PathBuf;
+
+use tempfile::NamedTempFile;
+use thiserror::Error;
+
+use crate::backend::ChangeId;
+use crate::file_util::IoResultExt as _;
+use crate::file_util::PathError;
+use crate::file_util::persist_temp_file;
+use crate::lock::FileLock;
+use crate::lock::FileLockError;
+use crate::object_id::ObjectId as _;
+
+/// Error that can occur when reading or updating the draft changes.
+#[derive(Debug, Error)]
+pub enum DraftStoreError {
+    /// An I/O error related to a file path.
+    #[error(transparent)]
+    Path(#[from] PathError),
+    /// Failed to lock the store.
+    #[error("Failed to lock draft store")]
+    Lock(#[from] FileLockError),
+    /// The store file is malformed.
+    #[error("Failed to parse draft store {path}")]
+    Parse {
+        /// Path to the store file.
+        path: PathBuf,
+        /// Underlying error.
+        #[source]
+        source: Box<dyn std::error::Error + Send + Sync>,
+    },
+}
+
+/// File-based store of the change IDs marked as drafts.
+///
+/// The change IDs are stored in `drafts/changes.toml` in the repo directory,
+/// which is created when the first change is marked.
+#[derive(Debug)]
+pub struct DraftStore {
+    store_file: PathBuf,
+    lock_file: PathBuf,
+}
+
+impl DraftStore {
+    /// Loads the store from the given repository path.
+    pub fn load(repo_path: &Path) -> Self {
+        let store_file = repo_path.join("drafts").join("changes.toml");
+        Self {
+            lock_file: store_file.with_extension("lock"),
+            store_file,
+        }
+    }
+
+    /// Returns the change IDs marked as drafts.
+    pub fn get_all(&self) -> Result<BTreeSet<ChangeId>, DraftStoreError> {
+        self.read_store()
+    }
+
+    /// Marks the `change_ids` as drafts. Returns the number of changes which
+    /// weren't marked before.
+    pub fn add<'a>(
+        &self,
+        change_ids: impl IntoIterator<Item = &'a ChangeId>,
+    ) -> Result<usize, DraftStoreError> {
+        self.update(|drafts| {
+            change_ids
+                .into_iter()
+                .filter(|&id| drafts.insert(id.clone()))
+                .count()
+        })
+    }
+
+    /// Unmarks the `change_ids`. Returns the number of changes which were
+    /// marked before.
+    pub fn remove<'a>(
+        &self,
+        change_ids: impl IntoIterator<Item = &'a ChangeId>,
+    ) -> Result<usize, DraftStoreError> {
+        self.update(|drafts| {
+            change_ids
+                .into_iter()
+                .filter(|&id| drafts.remove(id))
+                .count()
+        })
+    }
+
+    fn update<T>(
+        &self,
+        f: impl FnOnce(&mut BTreeSet<ChangeId>) -> T,
+    ) -> Result<T, DraftStoreError> {
+        let store_dir = self.store_file.parent().unwrap();
+        fs::create_dir_all(store_dir).context(store_dir)?;
+        let _lock = FileLock::lock(self.lock_file.clone())?;
+        let mut drafts = self.read_store()?;
+        let result = f(&mut drafts);
+        self.write_store(&drafts)?;
+        Ok(result)
+    }
+
+    fn read_store(&self) -> Result<BTreeSet<ChangeId>, DraftStoreError> {
+        let text = match fs::read_to_string(&self.store_file).context(&self.store_file) {
+            Ok(text) => text,
+            Err(err) if err.source.kind() == io::ErrorKind::NotFound => {
+                return Ok(BTreeSet::new());
+            }
+            Err(err) => return Err(err.into()),
+        };
+        parse_store(&text).map_err(|source| DraftStoreError::Parse {
+            path: self.store_file.clone(),
+            source,
+        })
+    }
+
+    fn write_store(&self, drafts: &BTreeSet<ChangeId>) -> Result<(), DraftStoreError> {
+        let store_dir = self.store_file.parent().unwrap();
+        let temp_file = NamedTempFile::new_in(store_dir).context(store_dir)?;
+        temp_file
+            .as_file()
+            .write_all(format_store(drafts).as_bytes())
+            .context(temp_file.path())?;
+        persist_temp_file(temp_file, &self.store_file).context(&self.store_file)?;
+        Ok(())
+    }
+}
+
+fn parse_store(text: &str) -> Result<BTreeSet<ChangeId>, Box<dyn std::error::Error + Send + Sync>> {
+    let doc: toml_edit::DocumentMut = text.parse()?;
+    let Some(item) = doc.get("changes") else {
+        return Ok(BTreeSet::new());
+    };
+    let array = item.as_array().ok_or("Draft changes are not an array")?;
+    array
+        .iter()
+        .map(|value| {
+            value
+                .as_str()
+                .and_then(ChangeId::try_from_hex)
+                .ok_or_else(|| format!("Invalid change ID {value}").into())
+        })
+        .collect()
+}
+
+fn format_store(drafts: &BTreeSet<ChangeId>) -> String {
+    let mut doc = toml_edit::DocumentMut::new();
+    let array: toml_edit::Array = drafts.iter().map(|id| id.hex()).collect();
+    doc.insert("changes", toml_edit::value(array));
+    doc.to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::new_temp_dir;
+
+    #[test]
+    fn test_add_and_remove() {
+        let temp_dir = new_temp_dir();
+        let store = DraftStore::load(temp_dir.path());
+        let id1 = ChangeId::from_hex("0123456789abcdef");
+        let id2 = ChangeId::from_hex("fedcba9876543210");
+        assert!(store.get_all().unwrap().is_empty());
+
+        assert_eq!(store.add([&id1, &id2]).unwrap(), 2);
+        assert_eq!(store.add([&id1]).unwrap(), 0);
+        assert_eq!(
+            store.get_all().unwrap(),
+            BTreeSet::from([id1.clone(), id2.clone()])
+        );
+
+        assert_eq!(store.remove([&id2]).unwrap(), 1);
+        assert_eq!(store.remove([&id2]).unwrap(), 0);
+        assert_eq!(store.get_all().unwrap(), BTreeSet::from([id1.clone()]));
+
+        // The store is read back from the file.
+        let store = DraftStore::load(temp_dir.path());
+        assert_eq!(store.get_all().unwrap(), BTreeSet::from([id1]));
+    }
+}
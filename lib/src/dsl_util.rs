@@ -562,6 +562,12 @@ impl<R: RuleType> FunctionCallParser<R> {
     }
 }
 
+/// Prefix of function names referring to the builtin definitions of aliases.
+///
+/// `builtin::f()` is expanded to the original definition of function alias
+/// `f()` even if `f()` is overridden by user.
+pub const BUILTIN_NAMESPACE: &str = "builtin::";
+
 /// Map of symbol, pattern, and function aliases.
 #[derive(Clone, Debug, Default)]
 pub struct AliasesMap<P, V> {
@@ -596,22 +602,44 @@ impl<P, V> AliasesMap<P, V> {
     where
         P: AliasDeclarationParser,
     {
-        match self.parser.parse_declaration(decl.as_ref())? {
+        let decl = self.parser.parse_declaration(decl.as_ref())?;
+        self.insert_declaration(decl, defn.into());
+        Ok(())
+    }
+
+    /// Adds new substitution rule for the parsed declaration.
+    pub fn insert_declaration(&mut self, decl: AliasDeclaration, defn: V) {
+        match decl {
             AliasDeclaration::Symbol(name) => {
-                self.symbol_aliases.insert(name, defn.into());
+                self.symbol_aliases.insert(name, defn);
             }
             AliasDeclaration::Pattern(name, param) => {
-                self.pattern_aliases.insert(name, (param, defn.into()));
+                self.pattern_aliases.insert(name, (param, defn));
             }
             AliasDeclaration::Function(name, params) => {
                 let overloads = self.function_aliases.entry(name).or_default();
                 match overloads.binary_search_by_key(&params.len(), |(params, _)| params.len()) {
-                    Ok(i) => overloads[i] = (params, defn.into()),
-                    Err(i) => overloads.insert(i, (params, defn.into())),
+                    Ok(i) => overloads[i] = (params, defn),
+                    Err(i) => overloads.insert(i, (params, defn)),
                 }
             }
         }
-        Ok(())
+    }
+
+    /// Adds copies of the current function aliases with the names prefixed by
+    /// [`BUILTIN_NAMESPACE`]. The copies are kept even if the original aliases
+    /// are overridden later.
+    pub fn insert_builtin_copies(&mut self)
+    where
+        V: Clone,
+    {
+        let copies = self
+            .function_aliases
+            .iter()
+            .filter(|(name, _)| !name.starts_with(BUILTIN_NAMESPACE))
+            .map(|(name, overloads)| (format!("{BUILTIN_NAMESPACE}{name}"), overloads.clone()))
+            .collect_vec();
+        self.function_aliases.extend(copies);
     }
 
     /// Iterates symbol names in arbitrary order.
@@ -13,12 +13,17 @@
 // limitations under the License.
 
 use std::io::Cursor;
+use std::sync::Arc;
 
 use bstr::ByteSlice as _;
 use tokio::io::AsyncRead;
 use tokio::io::AsyncReadExt as _;
 
 use crate::config::ConfigGetError;
+use crate::config::ConfigGetResultExt as _;
+use crate::fileset;
+use crate::matchers::Matcher;
+use crate::repo_path::RepoPath;
 use crate::settings::UserSettings;
 
 fn is_binary(bytes: &[u8]) -> bool {
@@ -29,26 +34,49 @@ fn is_binary(bytes: &[u8]) -> bool {
     while let Some(byte) = bytes.next() {
         match *byte {
             b'\0' => return true,
-            b'\r' => {
-                if bytes.peek() != Some(&&b'\n') {
-                    return true;
-                }
-            }
+            b'\r' if bytes.peek() != Some(&&b'\n') => return true,
             _ => {}
         }
     }
     false
 }
 
+/// Returns true if the text `contents` has both LF and CRLF line endings.
+fn has_mixed_eol(contents: &[u8]) -> bool {
+    let mut has_lf = false;
+    let mut has_crlf = false;
+    for line in contents.lines_with_terminator() {
+        if line.ends_with(b"\r\n") {
+            has_crlf = true;
+        } else if line.ends_with(b"\n") {
+            has_lf = true;
+        }
+        if has_lf && has_crlf {
+            return true;
+        }
+    }
+    false
+}
+
+/// Contents of a file converted to be snapshotted.
+pub(crate) struct SnapshotContents<'a> {
+    pub contents: Box<dyn AsyncRead + Send + Unpin + 'a>,
+    /// Whether the text had both LF and CRLF line endings, which were
+    /// normalized.
+    pub has_mixed_eol: bool,
+}
+
 #[derive(Clone)]
 pub(crate) struct TargetEolStrategy {
     eol_conversion_mode: EolConversionMode,
+    eol_overrides: EolOverrides,
 }
 
 impl TargetEolStrategy {
-    pub(crate) fn new(eol_conversion_mode: EolConversionMode) -> Self {
+    pub(crate) fn new(eol_conversion_mode: EolConversionMode, eol_overrides: EolOverrides) -> Self {
         Self {
             eol_conversion_mode,
+            eol_overrides,
         }
     }
 
@@ -59,6 +87,16 @@ impl TargetEolStrategy {
     /// are potentially misclassified.
     const PROBE_LIMIT: u64 = 8 << 10;
 
+    /// Returns the conversion mode of the first override matching the file at
+    /// `path`, or the default mode if none matches.
+    fn conversion_mode_for(&self, path: &RepoPath) -> EolConversionMode {
+        self.eol_overrides
+            .overrides
+            .iter()
+            .find(|(matcher, _)| matcher.matches(path))
+            .map_or(self.eol_conversion_mode, |&(_, mode)| mode)
+    }
+
     /// Peek into the first [`TargetEolStrategy::PROBE_LIMIT`] bytes of content
     /// to determine if it is binary data.
     ///
@@ -85,29 +123,40 @@ impl TargetEolStrategy {
 
     pub(crate) async fn convert_eol_for_snapshot<'a>(
         &self,
+        path: &RepoPath,
         mut contents: impl AsyncRead + Send + Unpin + 'a,
-    ) -> Result<Box<dyn AsyncRead + Send + Unpin + 'a>, std::io::Error> {
-        match self.eol_conversion_mode {
-            EolConversionMode::None => Ok(Box::new(contents)),
+    ) -> Result<SnapshotContents<'a>, std::io::Error> {
+        match self.conversion_mode_for(path) {
+            EolConversionMode::None => Ok(SnapshotContents {
+                contents: Box::new(contents),
+                has_mixed_eol: false,
+            }),
             EolConversionMode::Input | EolConversionMode::InputOutput => {
                 let mut peek = vec![];
-                let target_eol = if Self::probe_for_binary(&mut contents, &mut peek).await? {
-                    TargetEol::PassThrough
-                } else {
-                    TargetEol::Lf
-                };
-                let peek = Cursor::new(peek);
-                let contents = peek.chain(contents);
-                convert_eol(contents, target_eol).await
+                if Self::probe_for_binary(&mut contents, &mut peek).await? {
+                    let contents = Cursor::new(peek).chain(contents);
+                    return Ok(SnapshotContents {
+                        contents: Box::new(contents),
+                        has_mixed_eol: false,
+                    });
+                }
+                // The whole text is read into memory to be converted anyway.
+                let mut text = peek;
+                contents.read_to_end(&mut text).await?;
+                Ok(SnapshotContents {
+                    has_mixed_eol: has_mixed_eol(&text),
+                    contents: convert_eol(Cursor::new(text), TargetEol::Lf).await?,
+                })
             }
         }
     }
 
     pub(crate) async fn convert_eol_for_update<'a>(
         &self,
+        path: &RepoPath,
         mut contents: impl AsyncRead + Send + Unpin + 'a,
     ) -> Result<Box<dyn AsyncRead + Send + Unpin + 'a>, std::io::Error> {
-        match self.eol_conversion_mode {
+        match self.conversion_mode_for(path) {
             EolConversionMode::None | EolConversionMode::Input => Ok(Box::new(contents)),
             EolConversionMode::InputOutput => {
                 let mut peek = vec![];
@@ -143,11 +192,69 @@ pub enum EolConversionMode {
 impl EolConversionMode {
     /// Try to create the [`EolConversionMode`] based on the
     /// `working-copy.eol-conversion` setting in the [`UserSettings`].
+    ///
+    /// The `working-copy.eol` setting takes precedence if set.
     pub fn try_from_settings(user_settings: &UserSettings) -> Result<Self, ConfigGetError> {
+        if let Some(policy) = user_settings
+            .get::<EolPolicy>("working-copy.eol")
+            .optional()?
+        {
+            return Ok(policy.to_conversion_mode());
+        }
         user_settings.get("working-copy.eol-conversion")
     }
 }
 
+/// Line endings of the text files in the working copy. Files are always stored
+/// with LF line endings.
+#[derive(Debug, PartialEq, Eq, Copy, Clone, serde::Deserialize)]
+#[serde(rename_all(deserialize = "kebab-case"))]
+enum EolPolicy {
+    /// CRLF line endings on Windows, LF line endings elsewhere.
+    Native,
+    /// LF line endings.
+    Lf,
+    /// CRLF line endings.
+    Crlf,
+}
+
+impl EolPolicy {
+    fn to_conversion_mode(self) -> EolConversionMode {
+        match self {
+            Self::Native if cfg!(windows) => EolConversionMode::InputOutput,
+            Self::Native | Self::Lf => EolConversionMode::Input,
+            Self::Crlf => EolConversionMode::InputOutput,
+        }
+    }
+}
+
+/// EOL conversion modes overriding the default mode for the files matched by
+/// filesets, configured by the `working-copy.eol-overrides` tables.
+#[derive(Clone, Debug, Default)]
+pub struct EolOverrides {
+    overrides: Arc<Vec<(Box<dyn Matcher>, EolConversionMode)>>,
+}
+
+impl EolOverrides {
+    /// Loads the overrides from the `working-copy.eol-overrides` tables in the
+    /// [`UserSettings`].
+    pub fn try_from_settings(user_settings: &UserSettings) -> Result<Self, ConfigGetError> {
+        let mut overrides = vec![];
+        for name in user_settings.table_keys("working-copy.eol-overrides") {
+            let matcher = user_settings.get_value_with(
+                ["working-copy", "eol-overrides", name, "files"],
+                fileset::parse_config_matcher,
+            )?;
+            let policy: EolPolicy =
+                user_settings.get(["working-copy", "eol-overrides", name, "eol"])?;
+            overrides.push((matcher, policy.to_conversion_mode()));
+        }
+        Ok(Self {
+            overrides: Arc::new(overrides),
+        })
+    }
+}
+
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 enum TargetEol {
     Lf,
@@ -199,6 +306,7 @@ mod tests {
     use test_case::test_case;
 
     use super::*;
+    use crate::matchers::FilesMatcher;
 
     #[tokio::main(flavor = "current_thread")]
     #[test_case(b"a\n", TargetEol::PassThrough, b"a\n"; "LF text with no EOL conversion")]
@@ -226,6 +334,15 @@ mod tests {
         assert_eq!(output, expected_output);
     }
 
+    #[test_case(b"", false; "empty text")]
+    #[test_case(b"a\nb\n", false; "LF text")]
+    #[test_case(b"a\r\nb", false; "CRLF text")]
+    #[test_case(b"a\r\nb\n", true; "mixed text")]
+    #[test_case(b"a\nb\r\n", true; "mixed text ending with CRLF")]
+    fn test_has_mixed_eol(input: &[u8], expected: bool) {
+        assert_eq!(has_mixed_eol(input), expected);
+    }
+
     struct ErrorReader(Option<std::io::Error>);
 
     impl ErrorReader {
@@ -295,27 +412,35 @@ mod tests {
     #[tokio::main(flavor = "current_thread")]
     #[test_case(TargetEolStrategy {
           eol_conversion_mode: EolConversionMode::None,
+          eol_overrides: EolOverrides::default(),
       }, b"\r\n", b"\r\n"; "none settings")]
     #[test_case(TargetEolStrategy {
           eol_conversion_mode: EolConversionMode::Input,
+          eol_overrides: EolOverrides::default(),
       }, b"\r\n", b"\n"; "input settings text input")]
     #[test_case(TargetEolStrategy {
           eol_conversion_mode: EolConversionMode::InputOutput,
+          eol_overrides: EolOverrides::default(),
       }, b"\r\n", b"\n"; "input output settings text input")]
     #[test_case(TargetEolStrategy {
           eol_conversion_mode: EolConversionMode::Input,
+          eol_overrides: EolOverrides::default(),
       }, b"\0\r\n", b"\0\r\n"; "input settings binary input")]
     #[test_case(TargetEolStrategy {
           eol_conversion_mode: EolConversionMode::InputOutput,
+          eol_overrides: EolOverrides::default(),
       }, b"\0\r\n", b"\0\r\n"; "input output settings binary input with NUL")]
     #[test_case(TargetEolStrategy {
           eol_conversion_mode: EolConversionMode::InputOutput,
+          eol_overrides: EolOverrides::default(),
       }, b"\r\r\n", b"\r\r\n"; "input output settings binary input with lone CR")]
     #[test_case(TargetEolStrategy {
           eol_conversion_mode: EolConversionMode::Input,
+          eol_overrides: EolOverrides::default(),
       }, &[0; 20 << 10], &[0; 20 << 10]; "input settings long binary input")]
     #[test_case(TargetEolStrategy {
           eol_conversion_mode: EolConversionMode::Input,
+          eol_overrides: EolOverrides::default(),
       }, &test_probe_limit_input_crlf(), &test_probe_limit_input_lf(); "input settings with CRLF on probe boundary")]
     async fn test_eol_strategy_convert_eol_for_snapshot(
         strategy: TargetEolStrategy,
@@ -324,9 +449,10 @@ mod tests {
     ) {
         let mut actual_output = vec![];
         strategy
-            .convert_eol_for_snapshot(contents)
+            .convert_eol_for_snapshot(RepoPath::root(), contents)
             .await
             .unwrap()
+            .contents
             .read_to_end(&mut actual_output)
             .await
             .unwrap();
@@ -336,18 +462,23 @@ mod tests {
     #[tokio::main(flavor = "current_thread")]
     #[test_case(TargetEolStrategy {
           eol_conversion_mode: EolConversionMode::None,
+          eol_overrides: EolOverrides::default(),
       }, b"\n", b"\n"; "none settings")]
     #[test_case(TargetEolStrategy {
           eol_conversion_mode: EolConversionMode::Input,
+          eol_overrides: EolOverrides::default(),
       }, b"\n", b"\n"; "input settings")]
     #[test_case(TargetEolStrategy {
           eol_conversion_mode: EolConversionMode::InputOutput,
+          eol_overrides: EolOverrides::default(),
       }, b"\n", b"\r\n"; "input output settings text input")]
     #[test_case(TargetEolStrategy {
           eol_conversion_mode: EolConversionMode::InputOutput,
+          eol_overrides: EolOverrides::default(),
       }, b"\0\n", b"\0\n"; "input output settings binary input")]
     #[test_case(TargetEolStrategy {
           eol_conversion_mode: EolConversionMode::Input,
+          eol_overrides: EolOverrides::default(),
       }, &[0; 20 << 10], &[0; 20 << 10]; "input output settings long binary input")]
     async fn test_eol_strategy_convert_eol_for_update(
         strategy: TargetEolStrategy,
@@ -356,7 +487,7 @@ mod tests {
     ) {
         let mut actual_output = vec![];
         strategy
-            .convert_eol_for_update(contents)
+            .convert_eol_for_update(RepoPath::root(), contents)
             .await
             .unwrap()
             .read_to_end(&mut actual_output)
@@ -364,4 +495,44 @@ mod tests {
             .unwrap();
         assert_eq!(actual_output, expected_output);
     }
+
+    #[tokio::main(flavor = "current_thread")]
+    #[test]
+    async fn test_eol_strategy_overrides() {
+        let overrides = EolOverrides {
+            overrides: Arc::new(vec![(
+                Box::new(FilesMatcher::new([
+                    RepoPath::from_internal_string("a.sh").unwrap()
+                ])),
+                EolConversionMode::Input,
+            )]),
+        };
+        let strategy = TargetEolStrategy::new(EolConversionMode::InputOutput, overrides);
+        let convert_for_update = async |path| {
+            let mut output = vec![];
+            let path = RepoPath::from_internal_string(path).unwrap();
+            strategy
+                .convert_eol_for_update(path, b"a\n".as_slice())
+                .await
+                .unwrap()
+                .read_to_end(&mut output)
+                .await
+                .unwrap();
+            output
+        };
+        assert_eq!(convert_for_update("a.sh").await, b"a\n");
+        assert_eq!(convert_for_update("a.txt").await, b"a\r\n");
+
+        // Mixed line endings are reported when they are normalized
+        let converted = strategy
+            .convert_eol_for_snapshot(RepoPath::root(), b"a\r\nb\n".as_slice())
+            .await
+            .unwrap();
+        assert!(converted.has_mixed_eol);
+        let converted = strategy
+            .convert_eol_for_snapshot(RepoPath::root(), b"\0\r\nb\n".as_slice())
+            .await
+            .unwrap();
+        assert!(!converted.has_mixed_eol);
+    }
 }
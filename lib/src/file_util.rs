@@ -15,6 +15,7 @@
 #![expect(missing_docs)]
 
 use std::borrow::Cow;
+use std::collections::HashSet;
 use std::ffi::OsString;
 use std::fs;
 use std::fs::File;
@@ -22,10 +23,12 @@ use std::io;
 use std::io::ErrorKind;
 use std::io::Read;
 use std::io::Write;
+use std::mem;
 use std::path::Component;
 use std::path::Path;
 use std::path::PathBuf;
 use std::pin::Pin;
+use std::sync::Mutex;
 use std::task::Poll;
 
 use tempfile::NamedTempFile;
@@ -233,6 +236,13 @@ pub fn persist_content_addressed_temp_file<P: AsRef<Path>>(
     // Ensure new file content is flushed to disk, so the old file content
     // wouldn't be lost if existed at the same location.
     temp_file.as_file().sync_data()?;
+    persist_content_addressed_temp_file_unsynced(temp_file, new_path)
+}
+
+fn persist_content_addressed_temp_file_unsynced<P: AsRef<Path>>(
+    temp_file: NamedTempFile,
+    new_path: P,
+) -> io::Result<File> {
     if cfg!(windows) {
         // On Windows, overwriting file can fail if the file is opened without
         // FILE_SHARE_DELETE for example. We don't need to take a risk if the
@@ -479,6 +489,112 @@ mod fallback {
     }
 }
 
+/// When to flush files written to object stores to disk.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, serde::Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum FsyncPolicy {
+    /// Flushes each file before it is persisted.
+    #[default]
+    Always,
+    /// Flushes files and their directories when [`FileSyncer::sync()`] is
+    /// called, which is usually once per transaction. On Linux, each file
+    /// system is flushed by a single `syncfs()` call.
+    Batch,
+    /// Leaves flushing to the operating system.
+    Never,
+}
+
+/// Persists content-addressed files according to [`FsyncPolicy`].
+#[derive(Debug, Default)]
+pub struct FileSyncer {
+    policy: FsyncPolicy,
+    pending: Mutex<PendingSyncs>,
+}
+
+#[derive(Debug, Default)]
+struct PendingSyncs {
+    files: Vec<PathBuf>,
+    dirs: HashSet<PathBuf>,
+}
+
+impl FileSyncer {
+    pub fn new(policy: FsyncPolicy) -> Self {
+        Self {
+            policy,
+            pending: Default::default(),
+        }
+    }
+
+    pub fn policy(&self) -> FsyncPolicy {
+        self.policy
+    }
+
+    /// Like [`persist_content_addressed_temp_file()`], but flushes the file
+    /// according to the policy.
+    pub fn persist_content_addressed_temp_file<P: AsRef<Path>>(
+        &self,
+        temp_file: NamedTempFile,
+        new_path: P,
+    ) -> io::Result<File> {
+        match self.policy {
+            FsyncPolicy::Always => persist_content_addressed_temp_file(temp_file, new_path),
+            FsyncPolicy::Batch => {
+                let new_path = new_path.as_ref();
+                let file = persist_content_addressed_temp_file_unsynced(temp_file, new_path)?;
+                let mut pending = self.pending.lock().unwrap();
+                pending.files.push(new_path.to_owned());
+                if let Some(dir) = new_path.parent() {
+                    pending.dirs.insert(dir.to_owned());
+                }
+                Ok(file)
+            }
+            FsyncPolicy::Never => persist_content_addressed_temp_file_unsynced(temp_file, new_path),
+        }
+    }
+
+    /// Flushes files persisted since the last call, and the directories
+    /// containing them. This is a no-op unless the policy is
+    /// [`FsyncPolicy::Batch`].
+    pub fn sync(&self) -> io::Result<()> {
+        let PendingSyncs { files, dirs } = mem::take(&mut *self.pending.lock().unwrap());
+        sync_batch(&files, &dirs)
+    }
+}
+
+/// Flushes the whole file systems containing the `dirs` at once. The `files`
+/// must be in the `dirs`.
+#[cfg(target_os = "linux")]
+fn sync_batch(_files: &[PathBuf], dirs: &HashSet<PathBuf>) -> io::Result<()> {
+    use std::os::unix::fs::MetadataExt as _;
+
+    let mut devices = HashSet::new();
+    for dir in dirs {
+        let dir_file = File::open(dir)?;
+        if devices.insert(dir_file.metadata()?.dev()) {
+            rustix::fs::syncfs(&dir_file)?;
+        }
+    }
+    Ok(())
+}
+
+/// Flushes the `files` and `dirs` one by one.
+#[cfg(not(target_os = "linux"))]
+fn sync_batch(files: &[PathBuf], dirs: &HashSet<PathBuf>) -> io::Result<()> {
+    for path in files {
+        // Flushing requires write access on Windows
+        let file = File::options().read(true).write(cfg!(windows)).open(path)?;
+        file.sync_data()?;
+    }
+    // Directories can't be opened as files on Windows, where metadata
+    // updates are handled by the file system journal.
+    if cfg!(unix) {
+        for dir in dirs {
+            File::open(dir)?.sync_all()?;
+        }
+    }
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use std::io::Cursor;
@@ -585,6 +701,30 @@ mod tests {
         assert!(persist_content_addressed_temp_file(temp_file, &target).is_ok());
     }
 
+    #[test_case(FsyncPolicy::Always)]
+    #[test_case(FsyncPolicy::Batch)]
+    #[test_case(FsyncPolicy::Never)]
+    fn test_file_syncer(policy: FsyncPolicy) {
+        let temp_dir = new_temp_dir();
+        let syncer = FileSyncer::new(policy);
+        for name in ["file1", "file2"] {
+            let target = temp_dir.path().join(name);
+            let mut temp_file = NamedTempFile::new_in(&temp_dir).unwrap();
+            temp_file.write_all(b"contents").unwrap();
+            syncer
+                .persist_content_addressed_temp_file(temp_file, &target)
+                .unwrap();
+            assert_eq!(fs::read(&target).unwrap(), b"contents");
+        }
+        let num_pending = syncer.pending.lock().unwrap().files.len();
+        assert_eq!(
+            num_pending,
+            if policy == FsyncPolicy::Batch { 2 } else { 0 }
+        );
+        syncer.sync().unwrap();
+        assert!(syncer.pending.lock().unwrap().files.is_empty());
+    }
+
     #[test]
     fn test_file_identity_hard_link() {
         let temp_dir = new_temp_dir();
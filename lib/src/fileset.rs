@@ -25,6 +25,7 @@ use globset::GlobBuilder;
 use itertools::Itertools as _;
 use thiserror::Error;
 
+use crate::config::ConfigValue;
 use crate::dsl_util::collect_similar;
 use crate::fileset_parser;
 use crate::fileset_parser::BinaryOp;
@@ -37,6 +38,7 @@ pub use crate::fileset_parser::FilesetParseErrorKind;
 pub use crate::fileset_parser::FilesetParseResult;
 use crate::fileset_parser::FunctionCallNode;
 use crate::fileset_parser::UnaryOp;
+pub use crate::fileset_parser::parse_program;
 use crate::matchers::DifferenceMatcher;
 use crate::matchers::EverythingMatcher;
 use crate::matchers::FilesMatcher;
@@ -633,6 +635,26 @@ pub fn parse_maybe_bare(
     resolve_expression(diagnostics, context.path_converter, &node)
 }
 
+/// Parses a fileset in a config value into a matcher.
+///
+/// Paths are relative to the workspace root, and aliases aren't expanded.
+pub(crate) fn parse_config_matcher(
+    value: ConfigValue,
+) -> Result<Box<dyn Matcher>, Box<dyn std::error::Error + Send + Sync>> {
+    let text = value.as_str().ok_or("Expected a fileset string")?;
+    let path_converter = RepoPathUiConverter::Fs {
+        cwd: path::PathBuf::new(),
+        base: path::PathBuf::new(),
+    };
+    let context = FilesetParseContext {
+        aliases_map: &FilesetAliasesMap::new(),
+        path_converter: &path_converter,
+    };
+    let mut diagnostics = FilesetDiagnostics::new();
+    let expression = parse_maybe_bare(&mut diagnostics, text, &context)?;
+    Ok(expression.to_matcher())
+}
+
 #[cfg(test)]
 mod tests {
     use std::path::PathBuf;
@@ -74,6 +74,7 @@ impl Rule {
             Self::function => None,
             Self::function_name => None,
             Self::function_arguments => None,
+            Self::formal_parameter => None,
             Self::formal_parameters => None,
             Self::string_pattern => None,
             Self::bare_string_pattern => None,
@@ -461,14 +462,53 @@ pub type FilesetAliasesMap = AliasesMap<FilesetAliasParser, String>;
 #[derive(Clone, Debug, Default)]
 pub struct FilesetAliasParser;
 
-impl AliasDeclarationParser for FilesetAliasParser {
-    type Error = FilesetParseError;
+impl FilesetAliasesMap {
+    /// Adds new substitution rule `decl = defn`. Unlike
+    /// [`AliasesMap::insert()`], trailing parameters of function alias can
+    /// have default values.
+    ///
+    /// For example, `f(x, y = "*.md")` is added as two overloads `f(x, y)` and
+    /// `f(x)`, the latter of which is defined as `f(x, "*.md")`. Returns the
+    /// parsed declaration of the longest overload.
+    pub fn insert_with_defaults(
+        &mut self,
+        decl: &str,
+        defn: impl Into<String>,
+    ) -> FilesetParseResult<AliasDeclaration> {
+        let (decl, defaults) = FilesetAliasParser.parse_declaration_with_defaults(decl)?;
+        if let AliasDeclaration::Function(name, params) = &decl {
+            let required = params.len() - defaults.len();
+            for arity in required..params.len() {
+                let args = params[..arity].iter().map(String::as_str).chain(
+                    defaults[arity - required..]
+                        .iter()
+                        .map(|span| span.as_str()),
+                );
+                let overload_defn = format!("{name}({})", args.format(", "));
+                let overload_decl =
+                    AliasDeclaration::Function(name.clone(), params[..arity].to_vec());
+                self.insert_declaration(overload_decl, overload_defn);
+            }
+        }
+        self.insert_declaration(decl.clone(), defn.into());
+        Ok(decl)
+    }
+}
 
-    fn parse_declaration(&self, source: &str) -> Result<AliasDeclaration, Self::Error> {
+impl FilesetAliasParser {
+    /// Parses alias declaration, and returns the spans of the default values
+    /// of the trailing function parameters.
+    fn parse_declaration_with_defaults<'i>(
+        &self,
+        source: &'i str,
+    ) -> FilesetParseResult<(AliasDeclaration, Vec<pest::Span<'i>>)> {
         let mut pairs = FilesetParser::parse(Rule::alias_declaration, source)?;
         let first = pairs.next().unwrap();
         match first.as_rule() {
-            Rule::strict_identifier => Ok(AliasDeclaration::Symbol(first.as_str().to_owned())),
+            Rule::strict_identifier => {
+                let name = first.as_str().to_owned();
+                Ok((AliasDeclaration::Symbol(name), vec![]))
+            }
             Rule::pattern_alias_declaration => {
                 let [name_pair, op, param_pair] = first.into_inner().collect_array().unwrap();
                 assert_eq!(name_pair.as_rule(), Rule::strict_identifier);
@@ -476,7 +516,7 @@ impl AliasDeclarationParser for FilesetAliasParser {
                 assert_eq!(param_pair.as_rule(), Rule::strict_identifier);
                 let name = name_pair.as_str().to_owned();
                 let param = param_pair.as_str().to_owned();
-                Ok(AliasDeclaration::Pattern(name, param))
+                Ok((AliasDeclaration::Pattern(name, param), vec![]))
             }
             Rule::function_alias_declaration => {
                 let [name_pair, params_pair] = first.into_inner().collect_array().unwrap();
@@ -484,15 +524,27 @@ impl AliasDeclarationParser for FilesetAliasParser {
                 assert_eq!(params_pair.as_rule(), Rule::formal_parameters);
                 let name = name_pair.as_str().to_owned();
                 let params_span = params_pair.as_span();
-                let params = params_pair
-                    .into_inner()
-                    .map(|pair| match pair.as_rule() {
-                        Rule::strict_identifier => pair.as_str().to_owned(),
-                        r => panic!("unexpected formal parameter rule {r:?}"),
-                    })
-                    .collect_vec();
+                let mut params = vec![];
+                let mut defaults = vec![];
+                for pair in params_pair.into_inner() {
+                    assert_eq!(pair.as_rule(), Rule::formal_parameter);
+                    let span = pair.as_span();
+                    let mut inner = pair.into_inner();
+                    let param_pair = inner.next().unwrap();
+                    assert_eq!(param_pair.as_rule(), Rule::strict_identifier);
+                    params.push(param_pair.as_str().to_owned());
+                    if let Some(value_pair) = inner.next() {
+                        assert_eq!(value_pair.as_rule(), Rule::expression);
+                        defaults.push(value_pair.as_span());
+                    } else if !defaults.is_empty() {
+                        return Err(FilesetParseError::expression(
+                            "Parameter without default value follows parameter with default value",
+                            span,
+                        ));
+                    }
+                }
                 if params.iter().all_unique() {
-                    Ok(AliasDeclaration::Function(name, params))
+                    Ok((AliasDeclaration::Function(name, params), defaults))
                 } else {
                     Err(FilesetParseError::new(
                         FilesetParseErrorKind::RedefinedFunctionParameter,
@@ -505,6 +557,21 @@ impl AliasDeclarationParser for FilesetAliasParser {
     }
 }
 
+impl AliasDeclarationParser for FilesetAliasParser {
+    type Error = FilesetParseError;
+
+    fn parse_declaration(&self, source: &str) -> Result<AliasDeclaration, Self::Error> {
+        let (decl, defaults) = self.parse_declaration_with_defaults(source)?;
+        if let Some(span) = defaults.first() {
+            return Err(FilesetParseError::expression(
+                "Default parameter values aren't supported",
+                *span,
+            ));
+        }
+        Ok(decl)
+    }
+}
+
 impl AliasDefinitionParser for FilesetAliasParser {
     type Output<'i> = ExpressionKind<'i>;
     type Error = FilesetParseError;
@@ -1100,6 +1167,61 @@ mod tests {
         assert!(aliases_map.insert("m(a,,b)", "bad").is_err());
     }
 
+    #[test]
+    fn test_parse_alias_default_parameter() {
+        let mut aliases_map = FilesetAliasesMap::new();
+        aliases_map
+            .insert_with_defaults("f(a, b = 'x', c = glob:'*.md')", "a|b|c")
+            .unwrap();
+        assert!(aliases_map.get_function("f", 0).is_none());
+        let (_id, params, defn) = aliases_map.get_function("f", 1).unwrap();
+        assert_eq!(params, ["a"]);
+        assert_eq!(defn, "f(a, 'x', glob:'*.md')");
+        let (_id, params, defn) = aliases_map.get_function("f", 2).unwrap();
+        assert_eq!(params, ["a", "b"]);
+        assert_eq!(defn, "f(a, b, glob:'*.md')");
+        let (_id, params, defn) = aliases_map.get_function("f", 3).unwrap();
+        assert_eq!(params, ["a", "b", "c"]);
+        assert_eq!(defn, "a|b|c");
+
+        // Symbol and pattern aliases can be inserted, too.
+        aliases_map.insert_with_defaults("sym", "symbol").unwrap();
+        aliases_map.insert_with_defaults("pat:x", "x").unwrap();
+        assert_eq!(aliases_map.get_symbol("sym").unwrap().1, "symbol");
+
+        assert_eq!(
+            aliases_map
+                .insert_with_defaults("g(a = 'x', b)", "bad")
+                .unwrap_err()
+                .kind,
+            FilesetParseErrorKind::Expression(
+                "Parameter without default value follows parameter with default value".to_owned()
+            )
+        );
+        assert_eq!(
+            aliases_map
+                .insert_with_defaults("g(a = 'x', a = 'y')", "bad")
+                .unwrap_err()
+                .kind,
+            FilesetParseErrorKind::RedefinedFunctionParameter
+        );
+        assert!(aliases_map.insert_with_defaults("g(a = )", "bad").is_err());
+        assert!(aliases_map.insert_with_defaults("g(a = ~)", "bad").is_err());
+        assert!(
+            aliases_map
+                .insert_with_defaults("g(a = 'x',)", "bad")
+                .is_ok()
+        );
+
+        // Plain insert() doesn't know how to add overloads.
+        assert_eq!(
+            aliases_map.insert("h(a = 'x')", "bad").unwrap_err().kind,
+            FilesetParseErrorKind::Expression(
+                "Default parameter values aren't supported".to_owned()
+            )
+        );
+    }
+
     #[test]
     fn test_expand_symbol_alias() {
         assert_eq!(
@@ -1166,6 +1288,35 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_expand_function_alias_with_defaults() {
+        let mut aliases_map = FilesetAliasesMap::new();
+        aliases_map
+            .insert_with_defaults("F(x, y = b, z = x|c)", "x&y&z")
+            .unwrap();
+        let aliases = WithFilesetAliasesMap { aliases_map };
+        // Default value can refer to the required parameters.
+        assert_eq!(
+            aliases.parse_normalized("F(a)"),
+            parse_normalized("a&b&(a|c)")
+        );
+        assert_eq!(
+            aliases.parse_normalized("F(a, d)"),
+            parse_normalized("a&d&(a|c)")
+        );
+        assert_eq!(
+            aliases.parse_normalized("F(a, d, e)"),
+            parse_normalized("a&d&e")
+        );
+        assert_eq!(
+            aliases.parse("F()").unwrap_err().kind,
+            FilesetParseErrorKind::InvalidArguments {
+                name: "F".to_owned(),
+                message: "Expected 1 to 3 arguments".to_owned()
+            }
+        );
+    }
+
     #[test]
     fn test_expand_pattern_alias() {
         assert_eq!(
@@ -1341,4 +1492,30 @@ mod tests {
             FilesetParseErrorKind::InAliasExpansion("F(x)".to_owned())
         );
     }
+
+    #[test]
+    fn test_expand_builtin_function_alias() {
+        let mut aliases = with_aliases([("F(x)", "x|a")]);
+        aliases.aliases_map.insert_builtin_copies();
+        aliases
+            .aliases_map
+            .insert("F(x)", "builtin::F(x)&c")
+            .unwrap();
+        assert_eq!(
+            aliases.parse_normalized("F(y)"),
+            parse_normalized("(y|a)&c")
+        );
+
+        // Builtin aliases can't be declared.
+        assert!(
+            FilesetAliasesMap::new()
+                .insert("builtin::F(x)", "x")
+                .is_err()
+        );
+        assert!(
+            FilesetAliasesMap::new()
+                .insert_with_defaults("builtin::F(x = a)", "x")
+                .is_err()
+        );
+    }
 }
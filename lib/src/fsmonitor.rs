@@ -22,8 +22,10 @@
 
 #![warn(missing_docs)]
 
+use std::collections::VecDeque;
 use std::path::PathBuf;
 
+use crate::backend::Timestamp;
 use crate::config::ConfigGetError;
 use crate::settings::UserSettings;
 
@@ -78,6 +80,52 @@ impl FsmonitorSettings {
     }
 }
 
+/// A change reported by a filesystem monitor.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FsmonitorEvent {
+    /// Time when the change was reported.
+    pub time: Timestamp,
+    /// Path of the changed file, relative to the working copy root.
+    pub path: PathBuf,
+}
+
+/// Error returned by a [`FsmonitorEventSource`].
+pub type FsmonitorEventError = Box<dyn std::error::Error + Send + Sync>;
+
+/// Source of raw change events reported by a filesystem monitor.
+///
+/// This allows monitor backends to be validated independently of the working
+/// copy snapshot.
+pub trait FsmonitorEventSource {
+    /// Waits for the next batch of events. Returns `None` if no more events
+    /// will be reported.
+    fn next_events(&mut self) -> Result<Option<Vec<FsmonitorEvent>>, FsmonitorEventError>;
+}
+
+/// Filesystem monitor which reports events queued in memory. Used in tests.
+#[derive(Clone, Debug, Default)]
+pub struct InMemoryFsmonitor {
+    batches: VecDeque<Vec<FsmonitorEvent>>,
+}
+
+impl InMemoryFsmonitor {
+    /// Creates a monitor with no queued events.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Queues a batch of events to be reported.
+    pub fn push_events(&mut self, events: impl IntoIterator<Item = FsmonitorEvent>) {
+        self.batches.push_back(events.into_iter().collect());
+    }
+}
+
+impl FsmonitorEventSource for InMemoryFsmonitor {
+    fn next_events(&mut self) -> Result<Option<Vec<FsmonitorEvent>>, FsmonitorEventError> {
+        Ok(self.batches.pop_front())
+    }
+}
+
 /// Filesystem monitor integration using Watchman
 /// (<https://facebook.github.io/watchman/>). Requires `watchman` to already be
 /// installed on the system.
@@ -85,8 +133,10 @@ impl FsmonitorSettings {
 pub mod watchman {
     use std::path::Path;
     use std::path::PathBuf;
+    use std::time::Duration;
 
     use itertools::Itertools as _;
+    use pollster::FutureExt as _;
     use thiserror::Error;
     use tracing::info;
     use tracing::instrument;
@@ -98,6 +148,11 @@ pub mod watchman {
     use watchman_client::prelude::QueryResult;
     use watchman_client::prelude::TriggerRequest;
 
+    use super::FsmonitorEvent;
+    use super::FsmonitorEventError;
+    use super::FsmonitorEventSource;
+    use crate::backend::Timestamp;
+
     /// Represents an instance in time from the perspective of the filesystem
     /// monitor.
     ///
@@ -336,4 +391,80 @@ pub mod watchman {
             expr::Expr::Not(Box::new(expr::Expr::Any(excludes)))
         }
     }
+
+    /// Reports changed files by polling Watchman.
+    pub struct WatchmanEventSource {
+        monitor: Fsmonitor,
+        clock: Option<Clock>,
+        poll_interval: Duration,
+    }
+
+    impl WatchmanEventSource {
+        /// Creates an event source which queries the `monitor` every
+        /// `poll_interval`. Only changes made after the first query are
+        /// reported.
+        pub fn new(monitor: Fsmonitor, poll_interval: Duration) -> Self {
+            Self {
+                monitor,
+                clock: None,
+                poll_interval,
+            }
+        }
+    }
+
+    impl FsmonitorEventSource for WatchmanEventSource {
+        fn next_events(&mut self) -> Result<Option<Vec<FsmonitorEvent>>, FsmonitorEventError> {
+            loop {
+                let is_first_query = self.clock.is_none();
+                let (clock, changed_files) = self
+                    .monitor
+                    .query_changed_files(self.clock.take())
+                    .block_on()?;
+                self.clock = Some(clock);
+                if let Some(paths) = changed_files
+                    && !is_first_query
+                    && !paths.is_empty()
+                {
+                    let time = Timestamp::now();
+                    let events = paths
+                        .into_iter()
+                        .map(|path| FsmonitorEvent { time, path })
+                        .collect();
+                    return Ok(Some(events));
+                }
+                std::thread::sleep(self.poll_interval);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backend::MillisSinceEpoch;
+
+    fn event(millis: i64, path: &str) -> FsmonitorEvent {
+        FsmonitorEvent {
+            time: Timestamp {
+                timestamp: MillisSinceEpoch(millis),
+                tz_offset: 0,
+            },
+            path: PathBuf::from(path),
+        }
+    }
+
+    #[test]
+    fn test_in_memory_fsmonitor() {
+        let mut monitor = InMemoryFsmonitor::new();
+        monitor.push_events([event(1, "a"), event(1, "b")]);
+        monitor.push_events([]);
+        monitor.push_events([event(2, "a")]);
+        assert_eq!(
+            monitor.next_events().unwrap(),
+            Some(vec![event(1, "a"), event(1, "b")])
+        );
+        assert_eq!(monitor.next_events().unwrap(), Some(vec![]));
+        assert_eq!(monitor.next_events().unwrap(), Some(vec![event(2, "a")]));
+        assert_eq!(monitor.next_events().unwrap(), None);
+    }
 }
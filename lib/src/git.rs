@@ -16,16 +16,23 @@
 
 use std::borrow::Borrow;
 use std::borrow::Cow;
+use std::collections::BTreeMap;
 use std::collections::HashMap;
 use std::collections::HashSet;
 use std::default::Default;
 use std::ffi::OsString;
+use std::fs;
 use std::fs::File;
+use std::io;
 use std::iter;
 use std::num::NonZeroU32;
 use std::path::PathBuf;
 use std::sync::Arc;
+use std::time::Duration;
+use std::time::SystemTime;
 
+use blake2::Blake2b512;
+use blake2::Digest as _;
 use bstr::BStr;
 use bstr::BString;
 use futures::StreamExt as _;
@@ -37,6 +44,8 @@ use thiserror::Error;
 use crate::backend::BackendError;
 use crate::backend::BackendResult;
 use crate::backend::CommitId;
+use crate::backend::MillisSinceEpoch;
+use crate::backend::Timestamp;
 use crate::backend::TreeValue;
 use crate::commit::Commit;
 use crate::config::ConfigGetError;
@@ -49,6 +58,7 @@ pub use crate::git_subprocess::GitSidebandLineTerminator;
 pub use crate::git_subprocess::GitSubprocessCallback;
 use crate::git_subprocess::GitSubprocessContext;
 use crate::git_subprocess::GitSubprocessError;
+use crate::hex_util::encode_hex;
 use crate::index::IndexError;
 use crate::matchers::EverythingMatcher;
 use crate::merged_tree::MergedTree;
@@ -88,6 +98,8 @@ const REMOTE_BOOKMARK_REF_NAMESPACE: &str = "refs/remotes/";
 const REMOTE_TAG_REF_NAMESPACE: &str = "refs/jj/remote-tags/";
 /// Ref name used as a placeholder to unset HEAD without a commit.
 const UNBORN_ROOT_REF_NAME: &str = "refs/jj/root";
+/// Git ref where a single commit ref will be temporarily fetched.
+const FETCHED_COMMIT_REF_NAME: &str = "refs/jj/fetched-commit";
 /// Dummy file to be added to the index to indicate that the user is editing a
 /// commit with a conflict that isn't represented in the Git index.
 const INDEX_DUMMY_CONFLICT_FILE: &str = ".jj-do-not-resolve-this-conflict";
@@ -99,6 +111,9 @@ pub struct GitSettings {
     pub abandon_unreachable_commits: bool,
     pub executable_path: PathBuf,
     pub write_change_id_header: bool,
+    pub change_id_notes: bool,
+    pub ignored_refs: Vec<String>,
+    pub branch_prefixes: GitBranchPrefixes,
 }
 
 impl GitSettings {
@@ -108,6 +123,17 @@ impl GitSettings {
             abandon_unreachable_commits: settings.get_bool("git.abandon-unreachable-commits")?,
             executable_path: settings.get("git.executable-path")?,
             write_change_id_header: settings.get("git.write-change-id-header")?,
+            change_id_notes: settings.get("git.change-id-notes")?,
+            ignored_refs: settings.get("git.ignored-refs")?,
+            branch_prefixes: {
+                let name = "git.branch-prefixes";
+                let prefixes: BTreeMap<String, String> = settings.get(name)?;
+                GitBranchPrefixes::new(prefixes).map_err(|err| ConfigGetError::Type {
+                    name: name.to_owned(),
+                    error: err.into(),
+                    source_path: None,
+                })?
+            },
         })
     }
 
@@ -383,6 +409,150 @@ fn to_git_ref_name(kind: GitRefKind, symbol: RemoteRefSymbol<'_>) -> Option<GitR
     }
 }
 
+/// Rules to skip or rename Git refs on import and export.
+#[derive(Debug)]
+pub struct GitRefMapping {
+    /// Full Git ref names which should be neither imported nor exported.
+    pub ignored_refs: StringMatcher,
+    /// Prefixes to rename local Git branches to bookmarks and back.
+    pub branch_prefixes: GitBranchPrefixes,
+}
+
+impl Default for GitRefMapping {
+    fn default() -> Self {
+        Self {
+            ignored_refs: StringExpression::none().to_matcher(),
+            branch_prefixes: GitBranchPrefixes::default(),
+        }
+    }
+}
+
+impl GitRefMapping {
+    /// Like [`parse_git_ref()`], but renames local Git branches according to
+    /// the branch prefixes.
+    pub fn parse_git_ref(
+        &self,
+        full_name: &GitRefName,
+    ) -> Option<(GitRefKind, RemoteRefSymbolBuf)> {
+        let (kind, symbol) = parse_git_ref(full_name)?;
+        if kind == GitRefKind::Bookmark && symbol.remote == REMOTE_NAME_FOR_LOCAL_GIT_REPO {
+            let name = self
+                .branch_prefixes
+                .to_bookmark_name(symbol.name.as_str())?;
+            let symbol = RefName::new(&name)
+                .to_remote_symbol(symbol.remote)
+                .to_owned();
+            Some((kind, symbol))
+        } else {
+            Some((kind, symbol.to_owned()))
+        }
+    }
+
+    /// Translates jj's `name@remote` symbol to Git ref name, renaming local
+    /// bookmarks according to the branch prefixes. Returns `None` if the ref
+    /// cannot be represented in Git.
+    pub fn to_git_ref_name(
+        &self,
+        kind: GitRefKind,
+        symbol: RemoteRefSymbol<'_>,
+    ) -> Option<GitRefNameBuf> {
+        if kind == GitRefKind::Bookmark && symbol.remote == REMOTE_NAME_FOR_LOCAL_GIT_REPO {
+            let name = self.branch_prefixes.to_branch_name(symbol.name.as_str())?;
+            to_git_ref_name(kind, RefName::new(&name).to_remote_symbol(symbol.remote))
+        } else {
+            to_git_ref_name(kind, symbol)
+        }
+    }
+
+    /// Returns true if the Git ref is matched by `ignored_refs`.
+    pub fn is_ignored(&self, full_name: &GitRefName) -> bool {
+        self.ignored_refs.is_match(full_name.as_str())
+    }
+
+    /// Returns true if the Git ref corresponding to `kind` and `symbol` is
+    /// matched by `ignored_refs`.
+    pub fn is_ignored_symbol(&self, kind: GitRefKind, symbol: RemoteRefSymbol<'_>) -> bool {
+        self.to_git_ref_name(kind, symbol)
+            .is_some_and(|name| self.is_ignored(&name))
+    }
+}
+
+/// Prefixes to rename local Git branches to bookmarks and back.
+///
+/// A Git branch whose name starts with one of the Git prefixes is represented
+/// by a bookmark whose name starts with the corresponding bookmark prefix
+/// instead. Other branches keep their names, except for branches starting with
+/// one of the bookmark prefixes, which can't be represented. This makes the
+/// renaming reversible on export.
+#[derive(Clone, Debug, Default)]
+pub struct GitBranchPrefixes {
+    /// `(git_prefix, bookmark_prefix)`s, neither of which overlaps with the
+    /// other prefixes on the same side.
+    prefixes: Vec<(String, String)>,
+}
+
+/// Error that may occur when creating [`GitBranchPrefixes`].
+#[derive(Debug, Error)]
+#[error("Prefixes {0:?} and {1:?} overlap")]
+pub struct OverlappingPrefixesError(String, String);
+
+impl GitBranchPrefixes {
+    /// Creates renames from `(git_prefix, bookmark_prefix)`s.
+    pub fn new(
+        prefixes: impl IntoIterator<Item = (String, String)>,
+    ) -> Result<Self, OverlappingPrefixesError> {
+        let prefixes = prefixes.into_iter().collect_vec();
+        check_overlapping_prefixes(prefixes.iter().map(|(git, _)| git))?;
+        check_overlapping_prefixes(prefixes.iter().map(|(_, bookmark)| bookmark))?;
+        Ok(Self { prefixes })
+    }
+
+    /// Iterates over `(git_prefix, bookmark_prefix)`s.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &str)> + Clone {
+        self.prefixes
+            .iter()
+            .map(|(git, bookmark)| (git.as_str(), bookmark.as_str()))
+    }
+
+    fn to_bookmark_name<'a>(&self, branch_name: &'a str) -> Option<Cow<'a, str>> {
+        rename_prefix(branch_name, self.iter())
+    }
+
+    fn to_branch_name<'a>(&self, bookmark_name: &'a str) -> Option<Cow<'a, str>> {
+        rename_prefix(
+            bookmark_name,
+            self.iter().map(|(git, bookmark)| (bookmark, git)),
+        )
+    }
+}
+
+fn check_overlapping_prefixes<'a>(
+    prefixes: impl Iterator<Item = &'a String> + Clone,
+) -> Result<(), OverlappingPrefixesError> {
+    for (prefix1, prefix2) in prefixes.tuple_combinations() {
+        if prefix1.starts_with(prefix2.as_str()) || prefix2.starts_with(prefix1.as_str()) {
+            return Err(OverlappingPrefixesError(prefix1.clone(), prefix2.clone()));
+        }
+    }
+    Ok(())
+}
+
+/// Replaces the `from` prefix of the `name` with the corresponding `to`
+/// prefix. Returns `None` if the `name` isn't renamed but clashes with one of
+/// the `to` prefixes.
+fn rename_prefix<'a, 'b>(
+    name: &'a str,
+    prefixes: impl Iterator<Item = (&'b str, &'b str)> + Clone,
+) -> Option<Cow<'a, str>> {
+    if let Some((from, to)) = prefixes.clone().find(|(from, _)| name.starts_with(from)) {
+        Some(format!("{to}{}", &name[from.len()..]).into())
+    } else if prefixes.clone().any(|(_, to)| name.starts_with(to)) {
+        None
+    } else {
+        Some(name.into())
+    }
+}
+
 #[derive(Debug, Error)]
 #[error("The repo is not backed by a Git repo")]
 pub struct UnexpectedGitBackendError;
@@ -397,6 +567,73 @@ pub fn get_git_repo(store: &Store) -> Result<gix::Repository, UnexpectedGitBacke
     get_git_backend(store).map(|backend| backend.git_repo())
 }
 
+/// Returns the creation times of the annotated tags `refs/tags/<name>` in the
+/// Git repo, for the given tag names and their targets in a jj view.
+///
+/// The Git tags are scanned once. Lightweight tags are omitted, and so are
+/// tags whose Git target differs from the target in the view (e.g. because the
+/// view is from an older operation.)
+pub fn get_annotated_tag_timestamps<'a>(
+    git_repo: &gix::Repository,
+    tags: impl IntoIterator<Item = (&'a RefName, &'a CommitId)>,
+) -> BackendResult<HashMap<RefNameBuf, Timestamp>> {
+    let targets: HashMap<&RefName, &CommitId> = tags.into_iter().collect();
+    let mut timestamps = HashMap::new();
+    if targets.is_empty() {
+        return Ok(timestamps);
+    }
+    for git_ref in git_repo
+        .references()
+        .map_err(|err| BackendError::Other(err.into()))?
+        .tags()
+        .map_err(|err| BackendError::Other(err.into()))?
+    {
+        let mut git_ref = git_ref.map_err(BackendError::Other)?;
+        let Some(name) = git_ref
+            .name()
+            .as_bstr()
+            .strip_prefix(b"refs/tags/")
+            .and_then(|name| str::from_utf8(name).ok())
+        else {
+            continue;
+        };
+        let Some(&target) = targets.get(RefName::new(name)) else {
+            continue;
+        };
+        let name = RefName::new(name).to_owned();
+        let Some(tag) = git_ref
+            .try_id()
+            .and_then(|id| id.object().ok())
+            .and_then(|object| object.try_into_tag().ok())
+        else {
+            continue;
+        };
+        let Some(time) = tag
+            .tagger()
+            .map_err(|err| BackendError::Other(err.into()))?
+            .map(|tagger| tagger.time())
+            .transpose()
+            .map_err(|err| BackendError::Other(err.into()))?
+        else {
+            continue;
+        };
+        if git_ref
+            .peel_to_id()
+            .map_err(|err| BackendError::Other(err.into()))?
+            .as_bytes()
+            != target.as_bytes()
+        {
+            continue;
+        }
+        let timestamp = Timestamp {
+            timestamp: MillisSinceEpoch(time.seconds * 1000),
+            tz_offset: time.offset.div_euclid(60), // in minutes
+        };
+        timestamps.insert(name, timestamp);
+    }
+    Ok(timestamps)
+}
+
 /// Checks if `git_ref` points to a Git commit object, and returns its id.
 ///
 /// If the ref points to the previously `known_commit_oid` (i.e. unchanged),
@@ -492,6 +729,8 @@ pub struct GitImportOptions {
     pub abandon_unreachable_commits: bool,
     /// Per-remote patterns whether to track bookmarks automatically.
     pub remote_auto_track_bookmarks: HashMap<RemoteNameBuf, StringMatcher>,
+    /// Rules to skip or rename Git refs.
+    pub ref_mapping: GitRefMapping,
 }
 
 /// Describes changes made by `import_refs()` or `fetch()`.
@@ -558,11 +797,77 @@ pub fn import_some_refs(
 
     // Exclude real remote tags, which should never be updated by Git.
     let all_remote_tags = false;
-    let refs_to_import =
-        diff_refs_to_import(mut_repo.view(), &git_repo, all_remote_tags, git_ref_filter)?;
+    let refs_to_import = diff_refs_to_import(
+        mut_repo.view(),
+        &git_repo,
+        &options.ref_mapping,
+        all_remote_tags,
+        git_ref_filter,
+    )?;
     import_refs_inner(mut_repo, refs_to_import, options)
 }
 
+/// Refs modified more recently than this aren't fingerprinted because a
+/// subsequent change might not bump the modification time.
+const GIT_REFS_FINGERPRINT_MIN_AGE: Duration = Duration::from_secs(2);
+
+/// Computes a fingerprint of the Git ref storage without reading the refs.
+///
+/// The fingerprint covers the modification times and sizes of `packed-refs`,
+/// the reftable stack, and all directories and loose ref files under `refs/`.
+/// Git updates a loose ref by renaming a lock file into place, which bumps the
+/// modification time of the containing directory, but the file might also be
+/// edited in place by other tools. If the fingerprint is unchanged, the refs
+/// are very likely unchanged, and importing them can be skipped.
+///
+/// Returns `None` if the fingerprint can't be computed reliably, e.g. because
+/// the refs were modified very recently.
+pub fn git_refs_fingerprint(store: &Store) -> Option<String> {
+    let git_backend = get_git_backend(store).ok()?;
+    let common_dir = git_backend.git_repo().common_dir().to_owned();
+    let mut paths = vec![
+        common_dir.join("packed-refs"),
+        common_dir.join("reftable").join("tables.list"),
+    ];
+    let mut dirs = vec![common_dir.join("refs")];
+    while let Some(dir) = dirs.pop() {
+        for entry in fs::read_dir(&dir).ok()? {
+            let entry = entry.ok()?;
+            if entry.file_type().ok()?.is_dir() {
+                dirs.push(entry.path());
+            } else {
+                paths.push(entry.path());
+            }
+        }
+        paths.push(dir);
+    }
+    paths.sort_unstable();
+
+    let now = SystemTime::now();
+    let mut hasher = Blake2b512::new();
+    for path in &paths {
+        hasher.update(path.as_os_str().as_encoded_bytes());
+        match fs::metadata(path) {
+            Ok(metadata) => {
+                let mtime = metadata.modified().ok()?;
+                if now
+                    .duration_since(mtime)
+                    .is_ok_and(|age| age >= GIT_REFS_FINGERPRINT_MIN_AGE)
+                {
+                    let mtime = mtime.duration_since(SystemTime::UNIX_EPOCH).ok()?;
+                    hasher.update(mtime.as_nanos().to_le_bytes());
+                    hasher.update(metadata.len().to_le_bytes());
+                } else {
+                    return None;
+                }
+            }
+            Err(err) if err.kind() == io::ErrorKind::NotFound => hasher.update(b"-"),
+            Err(_) => return None,
+        }
+    }
+    Some(encode_hex(&hasher.finalize()))
+}
+
 fn import_refs_inner(
     mut_repo: &mut MutableRepo,
     refs_to_import: RefsToImport,
@@ -721,18 +1026,26 @@ fn abandon_unreachable_commits(
 fn diff_refs_to_import(
     view: &View,
     git_repo: &gix::Repository,
+    ref_mapping: &GitRefMapping,
     all_remote_tags: bool,
     git_ref_filter: impl Fn(GitRefKind, RemoteRefSymbol<'_>) -> bool,
 ) -> Result<RefsToImport, GitImportError> {
+    let git_ref_filter = |kind: GitRefKind, symbol: RemoteRefSymbol<'_>| {
+        !ref_mapping.is_ignored_symbol(kind, symbol) && git_ref_filter(kind, symbol)
+    };
     let mut known_git_refs = view
         .git_refs()
         .iter()
-        .filter_map(|(full_name, target)| {
+        .filter(|(full_name, _)| !ref_mapping.is_ignored(full_name))
+        .filter(|(full_name, _)| {
+            // Refs which can no longer be represented due to renamed branches
+            // are removed.
             // TODO: or clean up invalid ref in case it was stored due to historical bug?
-            let (kind, symbol) =
-                parse_git_ref(full_name).expect("stored git ref should be parsable");
-            git_ref_filter(kind, symbol).then_some((full_name.as_ref(), target))
+            ref_mapping
+                .parse_git_ref(full_name)
+                .is_none_or(|(kind, symbol)| git_ref_filter(kind, symbol.as_ref()))
         })
+        .map(|(full_name, target)| (full_name.as_ref(), target))
         .collect();
     let mut known_remote_bookmarks = view
         .all_remote_bookmarks()
@@ -764,30 +1077,33 @@ fn diff_refs_to_import(
     let actual = git_repo.references().map_err(GitImportError::from_git)?;
     collect_changed_refs_to_import(
         actual.local_branches().map_err(GitImportError::from_git)?,
+        ref_mapping,
         &mut known_git_refs,
         &mut known_remote_bookmarks,
         &mut changed_git_refs,
         &mut changed_remote_bookmarks,
         &mut failed_ref_names,
-        &git_ref_filter,
+        git_ref_filter,
     )?;
     collect_changed_refs_to_import(
         actual.remote_branches().map_err(GitImportError::from_git)?,
+        ref_mapping,
         &mut known_git_refs,
         &mut known_remote_bookmarks,
         &mut changed_git_refs,
         &mut changed_remote_bookmarks,
         &mut failed_ref_names,
-        &git_ref_filter,
+        git_ref_filter,
     )?;
     collect_changed_refs_to_import(
         actual.tags().map_err(GitImportError::from_git)?,
+        ref_mapping,
         &mut known_git_refs,
         &mut known_remote_tags,
         &mut changed_git_refs,
         &mut changed_remote_tags,
         &mut failed_ref_names,
-        &git_ref_filter,
+        git_ref_filter,
     )?;
     if all_remote_tags {
         collect_changed_remote_tags_to_import(
@@ -797,7 +1113,7 @@ fn diff_refs_to_import(
             &mut known_remote_tags,
             &mut changed_remote_tags,
             &mut failed_ref_names,
-            &git_ref_filter,
+            git_ref_filter,
         )?;
     }
     for full_name in known_git_refs.into_keys() {
@@ -827,8 +1143,10 @@ fn diff_refs_to_import(
     })
 }
 
+#[expect(clippy::too_many_arguments)]
 fn collect_changed_refs_to_import(
     actual_git_refs: gix::reference::iter::Iter,
+    ref_mapping: &GitRefMapping,
     known_git_refs: &mut HashMap<&GitRefName, &RefTarget>,
     known_remote_refs: &mut HashMap<RemoteRefKey<'_>, &RemoteRef>,
     changed_git_refs: &mut Vec<(GitRefNameBuf, RefTarget)>,
@@ -849,11 +1167,12 @@ fn collect_changed_refs_to_import(
             continue;
         }
         let full_name = GitRefName::new(full_name);
-        let Some((kind, symbol)) = parse_git_ref(full_name) else {
-            // Skip special refs such as refs/remotes/*/HEAD.
+        let Some((kind, symbol)) = ref_mapping.parse_git_ref(full_name) else {
+            // Skip special refs such as refs/remotes/*/HEAD, and branches
+            // which can't be represented due to renamed branches.
             continue;
         };
-        if !git_ref_filter(kind, symbol) {
+        if !git_ref_filter(kind, symbol.as_ref()) {
             continue;
         }
         let old_git_target = known_git_refs.get(full_name).copied().flatten();
@@ -872,10 +1191,10 @@ fn collect_changed_refs_to_import(
         // TODO: Make it configurable which remotes are publishing and update public
         // heads here.
         let old_remote_ref = known_remote_refs
-            .remove(&symbol)
+            .remove(&symbol.as_ref())
             .unwrap_or_else(|| RemoteRef::absent_ref());
         if new_target != old_remote_ref.target {
-            changed_remote_refs.push((symbol.to_owned(), (old_remote_ref.clone(), new_target)));
+            changed_remote_refs.push((symbol, (old_remote_ref.clone(), new_target)));
         }
     }
     Ok(())
@@ -1105,12 +1424,16 @@ struct RefsToExport {
 /// marked conflicted by the next `jj git import`.
 ///
 /// New/updated tags are exported as Git lightweight tags.
-pub fn export_refs(mut_repo: &mut MutableRepo) -> Result<GitExportStats, GitExportError> {
-    export_some_refs(mut_repo, |_, _| true)
+pub fn export_refs(
+    mut_repo: &mut MutableRepo,
+    ref_mapping: &GitRefMapping,
+) -> Result<GitExportStats, GitExportError> {
+    export_some_refs(mut_repo, ref_mapping, |_, _| true)
 }
 
 pub fn export_some_refs(
     mut_repo: &mut MutableRepo,
+    ref_mapping: &GitRefMapping,
     git_ref_filter: impl Fn(GitRefKind, RemoteRefSymbol<'_>) -> bool,
 ) -> Result<GitExportStats, GitExportError> {
     fn get<'a, V>(map: &'a [(RemoteRefSymbolBuf, V)], key: RemoteRefSymbol<'_>) -> Option<&'a V> {
@@ -1122,10 +1445,14 @@ pub fn export_some_refs(
 
     let git_repo = get_git_repo(mut_repo.store())?;
 
+    let git_ref_filter = |kind: GitRefKind, symbol: RemoteRefSymbol<'_>| {
+        !ref_mapping.is_ignored_symbol(kind, symbol) && git_ref_filter(kind, symbol)
+    };
     let AllRefsToExport { bookmarks, tags } = diff_refs_to_export(
         mut_repo.view(),
         mut_repo.store().root_commit_id(),
-        &git_ref_filter,
+        ref_mapping,
+        git_ref_filter,
     );
 
     // TODO: Also check other worktrees' HEAD.
@@ -1134,7 +1461,7 @@ pub fn export_some_refs(
         if let Some((kind, symbol)) = target_name
             .as_ref()
             .and_then(|name| str::from_utf8(name.as_bstr()).ok())
-            .and_then(|name| parse_git_ref(name.as_ref()))
+            .and_then(|name| ref_mapping.parse_git_ref(name.as_ref()))
         {
             let old_target = head_ref.inner.target.clone();
             let current_oid = match head_ref.into_fully_peeled_id() {
@@ -1152,9 +1479,9 @@ pub fn export_some_refs(
                 GitRefKind::Bookmark => &bookmarks,
                 GitRefKind::Tag => &tags,
             };
-            let new_oid = if let Some((_old_oid, new_oid)) = get(&refs.to_update, symbol) {
+            let new_oid = if let Some((_old_oid, new_oid)) = get(&refs.to_update, symbol.as_ref()) {
                 Some(new_oid)
-            } else if get(&refs.to_delete, symbol).is_some() {
+            } else if get(&refs.to_delete, symbol.as_ref()).is_some() {
                 None
             } else {
                 current_oid.as_ref()
@@ -1170,8 +1497,33 @@ pub fn export_some_refs(
         }
     }
 
-    let failed_bookmarks = export_refs_to_git(mut_repo, &git_repo, GitRefKind::Bookmark, bookmarks);
-    let failed_tags = export_refs_to_git(mut_repo, &git_repo, GitRefKind::Tag, tags);
+    // Commits reachable from the previously exported refs already got notes.
+    let change_id_notes_heads = if get_git_backend(mut_repo.store())?.change_id_notes() {
+        let known_heads = mut_repo
+            .view()
+            .git_refs()
+            .values()
+            .flat_map(|target| target.added_ids())
+            .cloned()
+            .collect_vec();
+        let new_heads = bookmarks
+            .to_update
+            .iter()
+            .map(|(_, (_, new_oid))| CommitId::from_bytes(new_oid.as_bytes()))
+            .collect_vec();
+        Some((known_heads, new_heads))
+    } else {
+        None
+    };
+
+    let failed_bookmarks = export_refs_to_git(
+        mut_repo,
+        &git_repo,
+        ref_mapping,
+        GitRefKind::Bookmark,
+        bookmarks,
+    );
+    let failed_tags = export_refs_to_git(mut_repo, &git_repo, ref_mapping, GitRefKind::Tag, tags);
 
     copy_exportable_local_bookmarks_to_remote_view(
         mut_repo,
@@ -1186,21 +1538,57 @@ pub fn export_some_refs(
         git_ref_filter(GitRefKind::Tag, symbol) && get(&failed_tags, symbol).is_none()
     });
 
+    if let Some((known_heads, new_heads)) = change_id_notes_heads
+        && !new_heads.is_empty()
+    {
+        write_change_id_notes(mut_repo, known_heads, new_heads)?;
+    }
+
     Ok(GitExportStats {
         failed_bookmarks,
         failed_tags,
     })
 }
 
+/// Records change ids of the commits in `known_heads..new_heads` as Git
+/// notes.
+fn write_change_id_notes(
+    mut_repo: &MutableRepo,
+    known_heads: Vec<CommitId>,
+    new_heads: Vec<CommitId>,
+) -> Result<(), GitExportError> {
+    let git_backend = get_git_backend(mut_repo.store())?;
+    let commit_ids: Vec<_> = RevsetExpression::commits(known_heads)
+        .range(&RevsetExpression::commits(new_heads))
+        .evaluate(mut_repo)
+        .map_err(|err| GitExportError::from_git(err.into_backend_error()))?
+        .iter()
+        .try_collect()
+        .map_err(|err| GitExportError::from_git(err.into_backend_error()))?;
+    let commits: Vec<_> = commit_ids
+        .iter()
+        .map(|id| mut_repo.store().get_commit(id))
+        .try_collect()
+        .map_err(GitExportError::from_git)?;
+    git_backend
+        .write_change_id_notes(
+            commits
+                .iter()
+                .map(|commit| (commit.id(), commit.change_id())),
+        )
+        .map_err(GitExportError::from_git)
+}
+
 fn export_refs_to_git(
     mut_repo: &mut MutableRepo,
     git_repo: &gix::Repository,
+    ref_mapping: &GitRefMapping,
     kind: GitRefKind,
     refs: RefsToExport,
 ) -> Vec<(RemoteRefSymbolBuf, FailedRefExportReason)> {
     let mut failed = refs.failed;
     for (symbol, old_oid) in refs.to_delete {
-        let Some(git_ref_name) = to_git_ref_name(kind, symbol.as_ref()) else {
+        let Some(git_ref_name) = ref_mapping.to_git_ref_name(kind, symbol.as_ref()) else {
             failed.push((symbol, FailedRefExportReason::InvalidGitName));
             continue;
         };
@@ -1212,7 +1600,7 @@ fn export_refs_to_git(
         }
     }
     for (symbol, (old_oid, new_oid)) in refs.to_update {
-        let Some(git_ref_name) = to_git_ref_name(kind, symbol.as_ref()) else {
+        let Some(git_ref_name) = ref_mapping.to_git_ref_name(kind, symbol.as_ref()) else {
             failed.push((symbol, FailedRefExportReason::InvalidGitName));
             continue;
         };
@@ -1286,8 +1674,23 @@ fn copy_exportable_local_tags_to_remote_view(
 fn diff_refs_to_export(
     view: &View,
     root_commit_id: &CommitId,
+    ref_mapping: &GitRefMapping,
     git_ref_filter: impl Fn(GitRefKind, RemoteRefSymbol<'_>) -> bool,
 ) -> AllRefsToExport {
+    let known_git_refs = view
+        .git_refs()
+        .iter()
+        // Refs which can no longer be represented due to renamed branches are
+        // left alone.
+        .filter_map(|(full_name, target)| {
+            let (kind, symbol) = ref_mapping.parse_git_ref(full_name)?;
+            Some((kind, symbol, target))
+        })
+        // There are two situations where remote refs get out of sync:
+        // 1. `jj bookmark forget --include-remotes`
+        // 2. `jj op revert`/`restore` in colocated repo
+        .filter(|(kind, symbol, _)| git_ref_filter(*kind, symbol.as_ref()))
+        .collect_vec();
     // Local targets will be copied to the "git" remote if successfully exported. So
     // the local refs are considered to be the new "git" remote refs.
     let mut all_bookmark_targets: HashMap<RemoteRefSymbol, (&RefTarget, &RefTarget)> =
@@ -1313,25 +1716,13 @@ fn diff_refs_to_export(
         .filter(|&(symbol, _)| git_ref_filter(GitRefKind::Tag, symbol))
         .map(|(symbol, new_target)| (symbol, (RefTarget::absent_ref(), new_target)))
         .collect();
-    let known_git_refs = view
-        .git_refs()
-        .iter()
-        .map(|(full_name, target)| {
-            let (kind, symbol) =
-                parse_git_ref(full_name).expect("stored git ref should be parsable");
-            ((kind, symbol), target)
-        })
-        // There are two situations where remote refs get out of sync:
-        // 1. `jj bookmark forget --include-remotes`
-        // 2. `jj op revert`/`restore` in colocated repo
-        .filter(|&((kind, symbol), _)| git_ref_filter(kind, symbol));
-    for ((kind, symbol), target) in known_git_refs {
+    for (kind, symbol, target) in &known_git_refs {
         let ref_targets = match kind {
             GitRefKind::Bookmark => &mut all_bookmark_targets,
             GitRefKind::Tag => &mut all_tag_targets,
         };
         ref_targets
-            .entry(symbol)
+            .entry(symbol.as_ref())
             .and_modify(|(old_target, _)| *old_target = target)
             .or_insert((target, RefTarget::absent_ref()));
     }
@@ -2447,6 +2838,8 @@ pub enum GitFetchError {
     RejectedUpdates(Vec<GitRefNameBuf>),
     #[error(transparent)]
     Subprocess(#[from] GitSubprocessError),
+    #[error("Failed to read fetched ref")]
+    FetchedRef(#[source] Box<dyn std::error::Error + Send + Sync>),
 }
 
 #[derive(Error, Debug)]
@@ -2494,6 +2887,49 @@ pub enum GitRefExpansionError {
         chars = INVALID_REFSPEC_CHARS.iter().join("`, `")
     )]
     InvalidBranchPattern(StringPattern),
+    #[error("Invalid fetch refspec `{refspec}`: destination must be in `{namespace}`")]
+    InvalidFetchRefspec { refspec: String, namespace: String },
+}
+
+impl ExpandedFetchRefSpecs {
+    /// Adds custom `<src>:<dst>` refspecs configured for the `remote`.
+    ///
+    /// The destination must be in the remote-tracking namespace of the
+    /// `remote` so the fetched refs are imported as remote bookmarks.
+    pub fn add_custom_refspecs(
+        &mut self,
+        remote: &RemoteName,
+        refspecs: &[String],
+    ) -> Result<(), GitRefExpansionError> {
+        let namespace = format!(
+            "{REMOTE_BOOKMARK_REF_NAMESPACE}{remote}/",
+            remote = remote.as_str()
+        );
+        for refspec in refspecs {
+            let make_error = || GitRefExpansionError::InvalidFetchRefspec {
+                refspec: refspec.clone(),
+                namespace: namespace.clone(),
+            };
+            // Remote refs are always force-updated.
+            let (source, destination) = refspec
+                .strip_prefix('+')
+                .unwrap_or(refspec)
+                .split_once(':')
+                .ok_or_else(make_error)?;
+            let name = destination
+                .strip_prefix(&namespace)
+                .filter(|name| !name.is_empty())
+                .ok_or_else(make_error)?;
+            let pattern = StringPattern::glob(name).map_err(|_| make_error())?;
+            self.expr.bookmark = self
+                .expr
+                .bookmark
+                .clone()
+                .union(StringExpression::pattern(pattern));
+            self.refspecs.push(RefSpec::forced(source, destination));
+        }
+        Ok(())
+    }
 }
 
 /// Expand a list of branch string patterns to refspecs to fetch
@@ -2916,6 +3352,92 @@ impl<'a> GitFetch<'a> {
         Ok(default_branch)
     }
 
+    /// Lists the refs of the remote which match the glob `pattern`, such as
+    /// `refs/changes/34/1234/*`, along with the commits they point to.
+    #[tracing::instrument(skip(self))]
+    pub fn list_remote_refs(
+        &self,
+        remote_name: &RemoteName,
+        pattern: &str,
+    ) -> Result<Vec<(GitRefNameBuf, CommitId)>, GitFetchError> {
+        validate_remote_name(remote_name)?;
+        if self
+            .git_repo
+            .try_find_remote(remote_name.as_str())
+            .is_none()
+        {
+            return Err(GitFetchError::NoSuchRemote(remote_name.to_owned()));
+        }
+        let refs = self.git_ctx.spawn_ls_remote(remote_name, pattern)?;
+        Ok(refs
+            .into_iter()
+            .map(|(name, oid)| (name, CommitId::from_bytes(oid.as_bytes())))
+            .collect())
+    }
+
+    /// Fetches a single ref from the remote, and returns the commit it points
+    /// to.
+    ///
+    /// Unlike [`Self::fetch()`], no remote bookmarks or tags are updated. This
+    /// is meant for refs outside of the bookmark and tag namespaces, such as
+    /// Gerrit's `refs/changes/*`. The returned commit should be imported by
+    /// [`GitBackend::import_head_commits()`] before use. Returns `None` if the
+    /// remote doesn't have the ref.
+    #[tracing::instrument(skip(self, callback))]
+    pub fn fetch_commit_ref(
+        &mut self,
+        remote_name: &RemoteName,
+        ref_name: &GitRefName,
+        callback: &mut dyn GitSubprocessCallback,
+    ) -> Result<Option<CommitId>, GitFetchError> {
+        validate_remote_name(remote_name)?;
+        if self
+            .git_repo
+            .try_find_remote(remote_name.as_str())
+            .is_none()
+        {
+            return Err(GitFetchError::NoSuchRemote(remote_name.to_owned()));
+        }
+
+        let refspec = RefSpec::forced(ref_name.as_str(), FETCHED_COMMIT_REF_NAME);
+        let status = self.git_ctx.spawn_fetch(
+            remote_name,
+            &[refspec],
+            &[],
+            callback,
+            None,
+            Some(FetchTagsOverride::NoTags),
+        )?;
+        match status {
+            GitFetchStatus::Updates(updates) if updates.rejected.is_empty() => {}
+            GitFetchStatus::Updates(updates) => {
+                let names = updates.rejected.into_iter().map(|(name, _)| name).collect();
+                return Err(GitFetchError::RejectedUpdates(names));
+            }
+            GitFetchStatus::NoRemoteRef(_) => return Ok(None),
+        }
+
+        // The temporary ref is deleted so it won't be imported later.
+        let git_ref = self
+            .git_repo
+            .find_reference(FETCHED_COMMIT_REF_NAME)
+            .map_err(|err| GitFetchError::FetchedRef(err.into()))?;
+        let oid = resolve_git_ref_to_commit_id(&git_ref, None);
+        git_ref
+            .delete()
+            .map_err(|err| GitFetchError::FetchedRef(err.into()))?;
+        let oid = oid.ok_or_else(|| {
+            GitFetchError::FetchedRef(
+                format!(
+                    "{ref_name} doesn't point to a commit",
+                    ref_name = ref_name.as_str()
+                )
+                .into(),
+            )
+        })?;
+        Ok(Some(CommitId::from_bytes(oid.as_bytes())))
+    }
+
     /// Import the previously fetched remote-tracking branches and tags into the
     /// jj repo and update jj's local bookmarks and tags.
     ///
@@ -2929,6 +3451,7 @@ impl<'a> GitFetch<'a> {
         let refs_to_import = diff_refs_to_import(
             self.mut_repo.view(),
             &self.git_repo,
+            &self.import_options.ref_mapping,
             all_remote_tags,
             |kind, symbol| match kind {
                 GitRefKind::Bookmark => self
@@ -2984,12 +3507,90 @@ pub struct GitRefUpdate {
     pub new_target: Option<CommitId>,
 }
 
+#[derive(Debug, Error)]
+#[error("Invalid push refspec `{refspec}`: {reason}")]
+pub struct InvalidPushRefSpecError {
+    pub refspec: String,
+    pub reason: &'static str,
+}
+
+/// Custom `<src>:<dst>` refspecs to push bookmarks to other refs on a remote.
+///
+/// The source must be in `refs/heads/`, and the destination must be a full ref
+/// name. Both sides may contain a single `*`, which matches any part of the
+/// bookmark name. Bookmarks not matched by any refspec are pushed to
+/// `refs/heads/<name>`.
+#[derive(Clone, Debug, Default)]
+pub struct GitPushRefSpecs {
+    /// `(source, destination)` pairs in configuration order.
+    refspecs: Vec<(String, String)>,
+}
+
+impl GitPushRefSpecs {
+    pub fn new(refspecs: &[String]) -> Result<Self, InvalidPushRefSpecError> {
+        let refspecs = refspecs
+            .iter()
+            .map(|refspec| {
+                let make_error = |reason| InvalidPushRefSpecError {
+                    refspec: refspec.clone(),
+                    reason,
+                };
+                // Bookmarks are always force-pushed with lease.
+                let (source, destination) = refspec
+                    .strip_prefix('+')
+                    .unwrap_or(refspec)
+                    .split_once(':')
+                    .ok_or_else(|| make_error("expected `<src>:<dst>`"))?;
+                if source.strip_prefix("refs/heads/").is_none_or(str::is_empty) {
+                    return Err(make_error("source must be in `refs/heads/`"));
+                }
+                if destination.strip_prefix("refs/").is_none_or(str::is_empty) {
+                    return Err(make_error("destination must be a full ref name"));
+                }
+                let source_wildcards = source.matches('*').count();
+                let destination_wildcards = destination.matches('*').count();
+                if source_wildcards > 1 || source_wildcards != destination_wildcards {
+                    return Err(make_error(
+                        "source and destination must both contain one `*`, or neither",
+                    ));
+                }
+                Ok((source.to_owned(), destination.to_owned()))
+            })
+            .try_collect()?;
+        Ok(Self { refspecs })
+    }
+
+    /// Returns the ref on the remote to which the bookmark `name` is pushed.
+    pub fn to_remote_ref_name(&self, name: &RefName) -> GitRefNameBuf {
+        let branch = format!("refs/heads/{name}", name = name.as_str());
+        self.refspecs
+            .iter()
+            .find_map(|(source, destination)| match source.split_once('*') {
+                Some((prefix, suffix)) => {
+                    let matched = branch
+                        .strip_prefix(prefix)?
+                        .strip_suffix(suffix)
+                        .filter(|matched| !matched.is_empty())?;
+                    Some(destination.replacen('*', matched, 1))
+                }
+                None => (*source == branch).then(|| destination.clone()),
+            })
+            .unwrap_or(branch)
+            .into()
+    }
+}
+
 /// Pushes the specified branches and updates the repo view accordingly.
+///
+/// Each branch is pushed to the remote ref given by `refspecs`. The remote
+/// bookmark is updated as if the branch were pushed to `refs/heads/<name>`, so
+/// a matching fetch refspec should be configured to keep it in sync.
 pub fn push_branches(
     mut_repo: &mut MutableRepo,
     subprocess_options: GitSubprocessOptions,
     remote: &RemoteName,
     targets: &GitBranchPushTargets,
+    refspecs: &GitPushRefSpecs,
     callback: &mut dyn GitSubprocessCallback,
 ) -> Result<GitPushStats, GitPushError> {
     validate_remote_name(remote)?;
@@ -2998,7 +3599,7 @@ pub fn push_branches(
         .branch_updates
         .iter()
         .map(|(name, update)| GitRefUpdate {
-            qualified_name: format!("refs/heads/{name}", name = name.as_str()).into(),
+            qualified_name: refspecs.to_remote_ref_name(name),
             expected_current_target: update.old_target.clone(),
             new_target: update.new_target.clone(),
         })
@@ -3027,7 +3628,15 @@ pub fn push_branches(
         let git_repo =
             get_git_repo(mut_repo.store()).expect("backend type should have been tested");
         let refs = build_pushed_bookmarks_to_export(remote, pushed_branch_updates());
-        export_refs_to_git(mut_repo, &git_repo, GitRefKind::Bookmark, refs)
+        // Remote-tracking branches aren't renamed.
+        let ref_mapping = GitRefMapping::default();
+        export_refs_to_git(
+            mut_repo,
+            &git_repo,
+            &ref_mapping,
+            GitRefKind::Bookmark,
+            refs,
+        )
     };
 
     debug_assert!(unexported_bookmarks.is_sorted_by_key(|(symbol, _)| symbol));
@@ -14,6 +14,7 @@
 
 #![expect(missing_docs)]
 
+use std::collections::HashMap;
 use std::collections::HashSet;
 use std::ffi::OsStr;
 use std::fmt::Debug;
@@ -22,6 +23,7 @@ use std::fmt::Formatter;
 use std::fs;
 use std::io;
 use std::io::Cursor;
+use std::mem;
 use std::path::Path;
 use std::path::PathBuf;
 use std::pin::Pin;
@@ -36,7 +38,9 @@ use std::time::SystemTime;
 use async_trait::async_trait;
 use futures::stream::BoxStream;
 use gix::bstr::BString;
+use gix::bstr::ByteSlice as _;
 use gix::objs::CommitRefIter;
+use gix::objs::Write as _;
 use gix::objs::WriteTo as _;
 use itertools::Itertools as _;
 use once_cell::sync::OnceCell as OnceLock;
@@ -100,6 +104,9 @@ pub const JJ_CONFLICT_README_FILE_NAME: &str = "JJ-CONFLICT-README";
 pub const JJ_TREES_COMMIT_HEADER: &str = "jj:trees";
 pub const JJ_CONFLICT_LABELS_COMMIT_HEADER: &str = "jj:conflict-labels";
 pub const CHANGE_ID_COMMIT_HEADER: &str = "change-id";
+/// Git notes ref in which change ids are recorded if `git.change-id-notes` is
+/// enabled.
+pub const CHANGE_ID_NOTES_REF: &str = "refs/notes/jj-change-id";
 
 #[derive(Debug, Error)]
 pub enum GitBackendInitError {
@@ -162,6 +169,30 @@ pub enum GitGcError {
     GcCommandErrorStatus(ExitStatus),
 }
 
+/// Counters of files written by [`GitBackend`].
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct FileWriteStats {
+    /// Number of files written to the object database.
+    pub written_files: u64,
+    /// Number of files whose blob already existed in the object database.
+    pub reused_files: u64,
+    /// Total size in bytes of the files whose blob already existed.
+    pub reused_bytes: u64,
+}
+
+impl FileWriteStats {
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Adds the counters of `other` to these counters.
+    pub fn add(&mut self, other: &Self) {
+        self.written_files += other.written_files;
+        self.reused_files += other.reused_files;
+        self.reused_bytes += other.reused_bytes;
+    }
+}
+
 pub struct GitBackend {
     // While gix::Repository can be created from gix::ThreadSafeRepository, it's
     // cheaper to cache the thread-local instance behind a mutex than creating
@@ -175,8 +206,10 @@ pub struct GitBackend {
     shallow_root_ids: OnceLock<Vec<CommitId>>,
     extra_metadata_store: TableStore,
     cached_extra_metadata: Mutex<Option<Arc<ReadonlyTable>>>,
+    file_write_stats: Mutex<FileWriteStats>,
     git_executable: PathBuf,
     write_change_id_header: bool,
+    change_id_notes: bool,
 }
 
 impl GitBackend {
@@ -202,8 +235,10 @@ impl GitBackend {
             shallow_root_ids: OnceLock::new(),
             extra_metadata_store,
             cached_extra_metadata: Mutex::new(None),
+            file_write_stats: Mutex::new(FileWriteStats::default()),
             git_executable: git_settings.executable_path,
             write_change_id_header: git_settings.write_change_id_header,
+            change_id_notes: git_settings.change_id_notes,
         }
     }
 
@@ -324,7 +359,11 @@ impl GitBackend {
             gix_open_opts_from_settings(settings),
         )
         .map_err(GitBackendLoadError::OpenRepository)?;
-        let extra_metadata_store = TableStore::load(store_path.join("extra"), HASH_LENGTH);
+        let fsync_policy = settings
+            .get("store.fsync")
+            .map_err(GitBackendLoadError::Config)?;
+        let extra_metadata_store =
+            TableStore::load(store_path.join("extra"), HASH_LENGTH).with_fsync_policy(fsync_policy);
         let git_settings =
             GitSettings::from_settings(settings).map_err(GitBackendLoadError::Config)?;
         Ok(Self::new(repo, extra_metadata_store, git_settings))
@@ -349,6 +388,12 @@ impl GitBackend {
         self.base_repo.work_dir()
     }
 
+    /// Returns the counters of files written since the last call, and resets
+    /// them.
+    pub fn take_file_write_stats(&self) -> FileWriteStats {
+        mem::take(&mut *self.file_write_stats.lock().unwrap())
+    }
+
     fn shallow_root_ids(&self, git_repo: &gix::Repository) -> BackendResult<&[CommitId]> {
         // The list of shallow roots is cached by gix, but it's still expensive
         // to stat file on every read_object() call. Refreshing shallow roots is
@@ -444,10 +489,135 @@ impl GitBackend {
             &table_lock,
             &head_ids,
             self.shallow_root_ids(&locked_repo)?,
+            self.change_id_notes,
         )?;
         self.save_extra_metadata_table(mut_table, &table_lock)
     }
 
+    /// Whether change ids are recorded in and read from
+    /// [`CHANGE_ID_NOTES_REF`].
+    pub fn change_id_notes(&self) -> bool {
+        self.change_id_notes
+    }
+
+    /// Records the change ids of the given commits in [`CHANGE_ID_NOTES_REF`].
+    ///
+    /// Notes are only written for commits whose change id can't be derived
+    /// from the Git commit object itself. Existing notes are never
+    /// overwritten, so the change id recorded first wins.
+    pub fn write_change_id_notes<'a>(
+        &self,
+        commits: impl IntoIterator<Item = (&'a CommitId, &'a ChangeId)>,
+    ) -> BackendResult<()> {
+        let locked_repo = self.lock_git_repo();
+        let (parent_id, tree_id) = match find_change_id_notes_commit(&locked_repo)? {
+            Some(commit) => {
+                let tree_id = commit
+                    .tree_id()
+                    .map_err(|err| BackendError::Other(err.into()))?;
+                (Some(commit.id), Some(tree_id.detach()))
+            }
+            None => (None, None),
+        };
+        let mut tree = match tree_id {
+            Some(tree_id) => locked_repo
+                .find_tree(tree_id)
+                .map_err(|err| BackendError::Other(err.into()))?
+                .decode()
+                .map_err(|err| BackendError::Other(err.into()))?
+                .into(),
+            None => gix::objs::Tree::empty(),
+        };
+        let existing_notes = match tree_id {
+            Some(tree_id) => collect_change_id_note_blobs(&locked_repo, tree_id)?,
+            None => HashMap::new(),
+        };
+
+        let mut num_added = 0;
+        for (commit_id, change_id) in commits {
+            if *commit_id == self.root_commit_id || existing_notes.contains_key(commit_id) {
+                continue;
+            }
+            let git_object = locked_repo
+                .find_object(validate_git_object_id(commit_id)?)
+                .map_err(|err| map_not_found_err(err, commit_id))?;
+            let git_commit = git_object
+                .try_to_commit_ref()
+                .map_err(|err| to_read_object_err(err, commit_id))?;
+            let derived_change_id = extract_change_id_from_commit(&git_commit)
+                .unwrap_or_else(|| synthetic_change_id_from_git_commit_id(commit_id));
+            if derived_change_id == *change_id {
+                continue;
+            }
+            let blob_id = locked_repo
+                .write_blob(format!("{}\n", change_id.reverse_hex()))
+                .map_err(|err| BackendError::WriteObject {
+                    object_type: "blob",
+                    source: Box::new(err),
+                })?;
+            tree.entries.push(gix::objs::tree::Entry {
+                mode: gix::object::tree::EntryKind::Blob.into(),
+                filename: commit_id.hex().into(),
+                oid: blob_id.detach(),
+            });
+            num_added += 1;
+        }
+        if num_added == 0 {
+            return Ok(());
+        }
+
+        tree.entries.sort();
+        let tree_id = locked_repo
+            .write_object(&tree)
+            .map_err(|err| BackendError::WriteObject {
+                object_type: "tree",
+                source: Box::new(err),
+            })?;
+        let signature = gix::actor::Signature {
+            name: "jj".into(),
+            email: EMPTY_STRING_PLACEHOLDER.into(),
+            time: gix::date::Time::now_utc(),
+        };
+        let commit = gix::objs::Commit {
+            message: format!("Record change ids of {num_added} commits\n").into(),
+            tree: tree_id.detach(),
+            author: signature.clone(),
+            committer: signature,
+            encoding: None,
+            parents: parent_id.into_iter().collect(),
+            extra_headers: vec![],
+        };
+        let new_commit_id =
+            locked_repo
+                .write_object(&commit)
+                .map_err(|err| BackendError::WriteObject {
+                    object_type: "commit",
+                    source: Box::new(err),
+                })?;
+        // Fail rather than lose notes if the ref was updated concurrently.
+        let expected = match parent_id {
+            Some(id) => gix::refs::transaction::PreviousValue::ExistingMustMatch(
+                gix::refs::Target::Object(id),
+            ),
+            None => gix::refs::transaction::PreviousValue::MustNotExist,
+        };
+        locked_repo
+            .edit_reference(gix::refs::transaction::RefEdit {
+                change: gix::refs::transaction::Change::Update {
+                    log: gix::refs::transaction::LogChange {
+                        message: "change ids recorded by jj".into(),
+                        ..Default::default()
+                    },
+                    expected,
+                    new: gix::refs::Target::Object(new_commit_id.detach()),
+                },
+                name: CHANGE_ID_NOTES_REF.try_into().unwrap(),
+                deref: false,
+            })
+            .map_err(|err| BackendError::Other(Box::new(err)))?;
+        Ok(())
+    }
+
     fn read_file_sync(&self, id: &FileId) -> BackendResult<Vec<u8>> {
         let git_blob_id = validate_git_object_id(id)?;
         let locked_repo = self.lock_git_repo();
@@ -943,12 +1113,14 @@ fn import_extra_metadata_entries_from_heads(
     _table_lock: &FileLock,
     head_ids: &HashSet<&CommitId>,
     shallow_roots: &[CommitId],
+    read_change_id_notes: bool,
 ) -> BackendResult<()> {
     let mut work_ids = head_ids
         .iter()
         .filter(|&id| mut_table.get_value(id.as_bytes()).is_none())
         .map(|&id| id.clone())
         .collect_vec();
+    let mut change_id_notes = None;
     while let Some(id) = work_ids.pop() {
         let git_object = git_repo
             .find_object(validate_git_object_id(&id)?)
@@ -957,7 +1129,16 @@ fn import_extra_metadata_entries_from_heads(
         // TODO(#1624): Should we read the root tree here and check if it has a
         // `.jjconflict-...` entries? That could happen if the user used `git` to e.g.
         // change the description of a commit with tree-level conflicts.
-        let commit = commit_from_git_without_root_parent(&id, &git_object, is_shallow)?;
+        let mut commit = commit_from_git_without_root_parent(&id, &git_object, is_shallow)?;
+        if read_change_id_notes && commit.change_id == synthetic_change_id_from_git_commit_id(&id) {
+            let notes = match &mut change_id_notes {
+                Some(notes) => notes,
+                None => change_id_notes.insert(read_change_id_notes_map(git_repo)?),
+            };
+            if let Some(change_id) = notes.remove(&id) {
+                commit.change_id = change_id;
+            }
+        }
         mut_table.add_entry(id.to_bytes(), serialize_extras(&commit));
         work_ids.extend(
             commit
@@ -969,6 +1150,97 @@ fn import_extra_metadata_entries_from_heads(
     Ok(())
 }
 
+fn find_change_id_notes_commit(
+    git_repo: &gix::Repository,
+) -> BackendResult<Option<gix::Commit<'_>>> {
+    let Some(mut notes_ref) = git_repo
+        .try_find_reference(CHANGE_ID_NOTES_REF)
+        .map_err(|err| BackendError::Other(err.into()))?
+    else {
+        return Ok(None);
+    };
+    let commit = notes_ref
+        .peel_to_commit()
+        .map_err(|err| BackendError::Other(err.into()))?;
+    Ok(Some(commit))
+}
+
+/// Collects note blobs in the notes tree, keyed by annotated commit id.
+///
+/// Both flat and fanned-out (e.g. `ab/cdef...`) note paths are supported.
+fn collect_change_id_note_blobs(
+    git_repo: &gix::Repository,
+    tree_id: gix::ObjectId,
+) -> BackendResult<HashMap<CommitId, gix::ObjectId>> {
+    let mut blobs = HashMap::new();
+    let mut work_trees = vec![(tree_id, String::new())];
+    while let Some((tree_id, prefix)) = work_trees.pop() {
+        let tree = git_repo
+            .find_tree(tree_id)
+            .map_err(|err| BackendError::Other(err.into()))?;
+        for entry in tree.iter() {
+            let entry = entry.map_err(|err| BackendError::Other(err.into()))?;
+            let Ok(name) = str::from_utf8(entry.filename()) else {
+                continue;
+            };
+            let hex = format!("{prefix}{name}");
+            match entry.mode().kind() {
+                gix::object::tree::EntryKind::Tree => {
+                    work_trees.push((entry.oid().to_owned(), hex));
+                }
+                gix::object::tree::EntryKind::Blob => {
+                    if let Some(commit_id) = CommitId::try_from_hex(&hex) {
+                        blobs.insert(commit_id, entry.oid().to_owned());
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+    Ok(blobs)
+}
+
+/// Reads change ids recorded in [`CHANGE_ID_NOTES_REF`].
+///
+/// Notes merged from different sources may list more than one change id for
+/// the same commit. Such notes are ambiguous and ignored.
+fn read_change_id_notes_map(
+    git_repo: &gix::Repository,
+) -> BackendResult<HashMap<CommitId, ChangeId>> {
+    let Some(commit) = find_change_id_notes_commit(git_repo)? else {
+        return Ok(HashMap::new());
+    };
+    let tree_id = commit
+        .tree_id()
+        .map_err(|err| BackendError::Other(err.into()))?;
+    let mut change_ids = HashMap::new();
+    for (commit_id, blob_id) in collect_change_id_note_blobs(git_repo, tree_id.detach())? {
+        let blob = git_repo
+            .find_blob(blob_id)
+            .map_err(|err| BackendError::Other(err.into()))?;
+        let candidates: Vec<ChangeId> = blob
+            .data
+            .lines()
+            .filter_map(|line| ChangeId::try_from_reverse_hex(line.trim()))
+            .filter(|change_id| change_id.as_bytes().len() == CHANGE_ID_LENGTH)
+            .unique()
+            .collect();
+        match <[ChangeId; 1]>::try_from(candidates) {
+            Ok([change_id]) => {
+                change_ids.insert(commit_id, change_id);
+            }
+            Err(candidates) => {
+                tracing::warn!(
+                    commit_id = commit_id.hex(),
+                    count = candidates.len(),
+                    "ignoring change-id note without a unique change id"
+                );
+            }
+        }
+    }
+    Ok(change_ids)
+}
+
 impl Debug for GitBackend {
     fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), Error> {
         f.debug_struct("GitBackend")
@@ -1023,13 +1295,27 @@ impl Backend for GitBackend {
     ) -> BackendResult<FileId> {
         let mut bytes = Vec::new();
         contents.read_to_end(&mut bytes).await.unwrap();
-        let locked_repo = self.lock_git_repo();
-        let oid = locked_repo
-            .write_blob(bytes)
-            .map_err(|err| BackendError::WriteObject {
+        let to_write_err =
+            |err: Box<dyn std::error::Error + Send + Sync>| BackendError::WriteObject {
                 object_type: "file",
-                source: Box::new(err),
-            })?;
+                source: err,
+            };
+        let locked_repo = self.lock_git_repo();
+        // Identical files, such as generated artifacts snapshotted by several
+        // workspaces, have the same blob id, so they're only written once.
+        let oid = gix::objs::compute_hash(locked_repo.object_hash(), gix::objs::Kind::Blob, &bytes)
+            .map_err(|err| to_write_err(err.into()))?;
+        let mut stats = self.file_write_stats.lock().unwrap();
+        if locked_repo.has_object(oid) {
+            stats.reused_files += 1;
+            stats.reused_bytes += bytes.len() as u64;
+        } else {
+            locked_repo
+                .objects
+                .write_buf(gix::objs::Kind::Blob, &bytes)
+                .map_err(to_write_err)?;
+            stats.written_files += 1;
+        }
         Ok(FileId::new(oid.as_bytes().to_vec()))
     }
 
@@ -1467,6 +1753,13 @@ impl Backend for GitBackend {
         Ok(Box::pin(futures::stream::iter(records)))
     }
 
+    async fn sync(&self) -> BackendResult<()> {
+        self.extra_metadata_store
+            .sync()
+            .map_err(GitBackendError::WriteMetadata)?;
+        Ok(())
+    }
+
     #[tracing::instrument(skip(self, index))]
     fn gc(&self, index: &dyn Index, keep_newer: SystemTime) -> BackendResult<()> {
         let git_repo = self.lock_git_repo();
@@ -250,6 +250,24 @@ impl GitSubprocessContext {
         Ok(maybe_branch.map(Into::into))
     }
 
+    /// List the refs of the remote which match the `pattern`
+    ///
+    /// `git ls-remote --refs -- <remote_name> <pattern>`
+    pub(crate) fn spawn_ls_remote(
+        &self,
+        remote_name: &RemoteName,
+        pattern: &str,
+    ) -> Result<Vec<(GitRefNameBuf, gix::ObjectId)>, GitSubprocessError> {
+        let mut command = self.create_command();
+        command.stdout(Stdio::piped());
+        command.args(["ls-remote", "--refs", "--", remote_name.as_str(), pattern]);
+        let output = wait_with_output(self.spawn_cmd(command)?)?;
+
+        // The output of `git ls-remote` can be parsed the same way.
+        let output = parse_git_remote_show_output(output)?;
+        parse_git_ls_remote_refs(&output.stdout)
+    }
+
     /// Push references to git
     ///
     /// All pushes are forced, using --force-with-lease to perform a test&set
@@ -529,6 +547,28 @@ fn parse_git_remote_show_output(output: Output) -> Result<Output, GitSubprocessE
     Err(external_git_error(&output.stderr))
 }
 
+/// Parses `<object-id> TAB <ref-name>` lines printed by `git ls-remote`.
+fn parse_git_ls_remote_refs(
+    stdout: &[u8],
+) -> Result<Vec<(GitRefNameBuf, gix::ObjectId)>, GitSubprocessError> {
+    stdout
+        .lines()
+        .filter(|line| !line.is_empty())
+        .map(|line| {
+            let parse_err = || {
+                GitSubprocessError::External(format!(
+                    "Unexpected ls-remote output: {line}",
+                    line = BStr::new(line)
+                ))
+            };
+            let (oid, name) = line.split_once_str("\t").ok_or_else(parse_err)?;
+            let oid = gix::ObjectId::from_hex(oid).map_err(|_| parse_err())?;
+            let name = name.to_str().map_err(|_| parse_err())?;
+            Ok((name.into(), oid))
+        })
+        .collect()
+}
+
 fn parse_git_remote_show_default_branch(
     stdout: &[u8],
 ) -> Result<Option<String>, GitSubprocessError> {
@@ -1039,6 +1079,32 @@ Done";
         assert_eq!(parse_no_remote_ref(SAMPLE_OK_STDERR), None);
     }
 
+    #[test]
+    fn test_parse_git_ls_remote_refs() {
+        let stdout = b"\
+3c6fbbb8d0c0a4ecfd6a79a4d6f2f4c4e0c1c1a2\trefs/changes/34/1234/1
+8d8b5d54b5a1fb5a4f4f1a3f2b93f0c0d2c3e4f5\trefs/changes/34/1234/2
+";
+        let refs = parse_git_ls_remote_refs(stdout).unwrap();
+        assert_eq!(
+            refs.iter()
+                .map(|(name, oid)| (name.as_str(), oid.to_string()))
+                .collect_vec(),
+            [
+                (
+                    "refs/changes/34/1234/1",
+                    "3c6fbbb8d0c0a4ecfd6a79a4d6f2f4c4e0c1c1a2".to_owned()
+                ),
+                (
+                    "refs/changes/34/1234/2",
+                    "8d8b5d54b5a1fb5a4f4f1a3f2b93f0c0d2c3e4f5".to_owned()
+                ),
+            ]
+        );
+        assert!(parse_git_ls_remote_refs(b"").unwrap().is_empty());
+        assert!(parse_git_ls_remote_refs(b"nonsense\n").is_err());
+    }
+
     #[test]
     fn test_parse_no_remote_tracking_branch() {
         assert_eq!(
@@ -14,11 +14,15 @@
 
 #![expect(missing_docs)]
 
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
 use std::collections::HashMap;
 use std::collections::HashSet;
 use std::collections::VecDeque;
 use std::hash::Hash;
 
+use itertools::Itertools as _;
+
 /// Node and edges pair of type `N` and `ID` respectively.
 ///
 /// `ID` uniquely identifies a node within the graph. It's usually cheap to
@@ -113,6 +117,132 @@ pub fn reverse_graph<N, ID: Clone + Eq + Hash, E>(
     Ok(items)
 }
 
+/// Graph returned by [`elide_linear_graph()`].
+#[derive(Clone, Debug)]
+pub struct ElidedGraph<N, ID> {
+    /// Nodes which were kept, with the edges rewritten.
+    pub nodes: Vec<GraphNode<N, ID>>,
+    /// Number of nodes elided behind each indirect edge, keyed by `(source,
+    /// target)` pair.
+    pub elided_counts: HashMap<(ID, ID), usize>,
+}
+
+/// Creates new graph in which linear runs of uninteresting nodes are elided.
+///
+/// A node is elided if `is_interesting()` returns false, and if it has exactly
+/// one parent edge and exactly one child within the graph. In other words,
+/// heads, merges, forks, and roots are always kept. Each run of elided nodes is
+/// replaced by an indirect edge.
+pub fn elide_linear_graph<N, ID: Clone + Eq + Hash, E>(
+    input: impl Iterator<Item = Result<GraphNode<N, ID>, E>>,
+    as_id: impl Fn(&N) -> &ID,
+    is_interesting: impl Fn(&N) -> Result<bool, E>,
+) -> Result<ElidedGraph<N, ID>, E> {
+    let entries: Vec<_> = input.try_collect()?;
+    let mut child_counts: HashMap<&ID, usize> = HashMap::new();
+    for (_, edges) in &entries {
+        for target in reachable_targets(edges) {
+            *child_counts.entry(target).or_default() += 1;
+        }
+    }
+
+    // Maps elidable node to the edge to its parent.
+    let mut elidable_edges: HashMap<ID, GraphEdge<ID>> = HashMap::new();
+    for (node, edges) in &entries {
+        let id = as_id(node);
+        if let [edge] = edges.as_slice()
+            && !edge.is_missing()
+            && child_counts.get(id) == Some(&1)
+            && !is_interesting(node)?
+        {
+            elidable_edges.insert(id.clone(), edge.clone());
+        }
+    }
+
+    let mut items = vec![];
+    let mut elided_counts = HashMap::new();
+    for (node, edges) in entries {
+        if elidable_edges.contains_key(as_id(&node)) {
+            continue;
+        }
+        let edges = edges
+            .into_iter()
+            .map(|mut edge| {
+                let mut count = 0;
+                while let Some(parent_edge) = elidable_edges.get(&edge.target) {
+                    edge = GraphEdge::indirect(parent_edge.target.clone());
+                    count += 1;
+                }
+                if count > 0 {
+                    let key = (as_id(&node).clone(), edge.target.clone());
+                    *elided_counts.entry(key).or_default() += count;
+                }
+                edge
+            })
+            .collect();
+        items.push((node, edges));
+    }
+    Ok(ElidedGraph {
+        nodes: items,
+        elided_counts,
+    })
+}
+
+/// Creates new graph in which nodes are sorted by key in descending order, but
+/// children are still emitted before their parents.
+///
+/// Among the nodes whose children have all been emitted, the node with the
+/// greatest key comes first. Ties are broken by the input order.
+pub fn sort_graph_by_key<N, ID: Eq + Hash, K: Ord, E>(
+    input: impl Iterator<Item = Result<GraphNode<N, ID>, E>>,
+    as_id: impl Fn(&N) -> &ID,
+    key_fn: impl Fn(&N) -> Result<K, E>,
+) -> Result<Vec<GraphNode<N, ID>>, E> {
+    let entries: Vec<_> = input.try_collect()?;
+    let keys: Vec<K> = entries.iter().map(|(node, _)| key_fn(node)).try_collect()?;
+    let index_map: HashMap<&ID, usize> = entries
+        .iter()
+        .enumerate()
+        .map(|(i, (node, _))| (as_id(node), i))
+        .collect();
+    let parent_indices = |edges: &[GraphEdge<ID>]| {
+        reachable_targets(edges)
+            .filter_map(|target| index_map.get(target).copied())
+            .collect_vec()
+    };
+    let mut child_counts = vec![0; entries.len()];
+    for (_, edges) in &entries {
+        for i in parent_indices(edges) {
+            child_counts[i] += 1;
+        }
+    }
+
+    let mut queue: BinaryHeap<(&K, Reverse<usize>)> = child_counts
+        .iter()
+        .positions(|&count| count == 0)
+        .map(|i| (&keys[i], Reverse(i)))
+        .collect();
+    let mut order = Vec::with_capacity(entries.len());
+    while let Some((_, Reverse(i))) = queue.pop() {
+        order.push(i);
+        let (_, edges) = &entries[i];
+        for j in parent_indices(edges) {
+            child_counts[j] -= 1;
+            if child_counts[j] == 0 {
+                queue.push((&keys[j], Reverse(j)));
+            }
+        }
+    }
+    assert_eq!(order.len(), entries.len(), "graph should be acyclic");
+    drop(queue);
+
+    let mut entries = entries.into_iter().map(Some).collect_vec();
+    Ok(order
+        .into_iter()
+        .map(|i| entries[i].take().unwrap())
+        .collect())
+}
+
 /// Graph iterator adapter to group topological branches.
 ///
 /// Basic idea is DFS from the heads. At fork point, the other descendant
@@ -1877,4 +2007,71 @@ mod tests {
         assert!(iter.next().is_none());
         assert!(iter.emittable_ids.is_empty());
     }
+
+    #[test]
+    fn test_elide_linear_graph() {
+        let graph = [
+            ('H', vec![direct('G')]),
+            ('G', vec![direct('F')]),
+            ('F', vec![direct('E'), direct('C')]),
+            ('E', vec![direct('D')]),
+            ('D', vec![direct('B')]),
+            ('C', vec![direct('B')]),
+            ('B', vec![indirect('A')]),
+            ('A', vec![missing('X')]),
+        ]
+        .map(Ok::<_, Infallible>);
+        let elided = elide_linear_graph(graph.iter().cloned(), |c| c, |&c| Ok(c == 'E')).unwrap();
+        assert_eq!(
+            elided.nodes,
+            vec![
+                ('H', vec![indirect('F')]),
+                ('F', vec![direct('E'), indirect('B')]),
+                ('E', vec![indirect('B')]),
+                ('B', vec![indirect('A')]),
+                ('A', vec![missing('X')]),
+            ]
+        );
+        assert_eq!(
+            elided.elided_counts,
+            HashMap::from([(('H', 'F'), 1), (('F', 'B'), 1), (('E', 'B'), 1)])
+        );
+
+        // Nothing is elided if all nodes are interesting
+        let elided = elide_linear_graph(graph.iter().cloned(), |c| c, |_| Ok(true)).unwrap();
+        assert_eq!(elided.nodes, graph.map(Result::unwrap));
+        assert!(elided.elided_counts.is_empty());
+    }
+
+    #[test]
+    fn test_sort_graph_by_key() {
+        let graph = [
+            ('F', vec![direct('E'), direct('C')]),
+            ('E', vec![direct('D')]),
+            ('D', vec![direct('B')]),
+            ('C', vec![direct('B')]),
+            ('B', vec![indirect('A')]),
+            ('A', vec![missing('X')]),
+        ]
+        .map(Ok::<_, Infallible>);
+        let sort = |key: fn(&char) -> i32| {
+            sort_graph_by_key(graph.iter().cloned(), |c| c, |c| Ok(key(c)))
+                .unwrap()
+                .into_iter()
+                .map(|(c, _)| c)
+                .collect::<String>()
+        };
+
+        // Ties are broken by the input order
+        assert_eq!(sort(|_| 0), "FEDCBA");
+        // C has the greatest key, but can't precede its child F
+        assert_eq!(sort(|&c| if c == 'C' { 1 } else { 0 }), "FCEDBA");
+        // B can't precede its children C and D
+        assert_eq!(sort(|&c| if c == 'B' { 1 } else { 0 }), "FEDCBA");
+        assert_eq!(sort(|&c| -(c as i32)), "FCEDBA");
+
+        // Edges are preserved
+        let sorted = sort_graph_by_key(graph.iter().cloned(), |c| c, |&c| Ok(c == 'C')).unwrap();
+        assert_eq!(sorted[1], ('C', vec![direct('B')]));
+    }
 }
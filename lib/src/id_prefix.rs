@@ -14,6 +14,7 @@
 
 #![expect(missing_docs)]
 
+use std::cmp::max;
 use std::iter;
 use std::marker::PhantomData;
 use std::sync::Arc;
@@ -32,6 +33,7 @@ use crate::object_id::ObjectId;
 use crate::object_id::PrefixResolution;
 use crate::repo::Repo;
 use crate::revset::RevsetEvaluationError;
+use crate::revset::RevsetExpression;
 use crate::revset::RevsetExtensions;
 use crate::revset::RevsetResolutionError;
 use crate::revset::SymbolResolver;
@@ -113,47 +115,74 @@ impl IdIndexSourceEntry<ChangeId> for &'_ (CommitId, ChangeId) {
 /// Manages configuration and cache of commit/change ID disambiguation index.
 #[derive(Default)]
 pub struct IdPrefixContext {
-    disambiguation: Option<DisambiguationData>,
+    /// Revsets to disambiguate within, in order of priority.
+    disambiguation: Vec<DisambiguationData>,
     extensions: Arc<RevsetExtensions>,
+    include_hidden: bool,
 }
 
 impl IdPrefixContext {
     pub fn new(extensions: Arc<RevsetExtensions>) -> Self {
         Self {
-            disambiguation: None,
+            disambiguation: vec![],
             extensions,
+            include_hidden: false,
         }
     }
 
-    pub fn disambiguate_within(mut self, expression: Arc<UserRevsetExpression>) -> Self {
-        self.disambiguation = Some(DisambiguationData {
-            expression,
-            indexes: OnceCell::new(),
-        });
+    /// Resolves change ID to hidden commit if the change has no visible
+    /// commits and the hidden commit is unambiguous.
+    pub fn include_hidden(mut self, include_hidden: bool) -> Self {
+        self.include_hidden = include_hidden;
+        self
+    }
+
+    pub fn includes_hidden(&self) -> bool {
+        self.include_hidden
+    }
+
+    pub fn disambiguate_within(self, expression: Arc<UserRevsetExpression>) -> Self {
+        self.disambiguate_within_scopes([expression])
+    }
+
+    /// Disambiguates within the given revsets. A prefix is resolved within the
+    /// first revset containing any matching commits, so the commits in earlier
+    /// revsets get shorter prefixes.
+    pub fn disambiguate_within_scopes(
+        mut self,
+        expressions: impl IntoIterator<Item = Arc<UserRevsetExpression>>,
+    ) -> Self {
+        self.disambiguation = expressions
+            .into_iter()
+            .map(|expression| DisambiguationData {
+                expression,
+                indexes: OnceCell::new(),
+            })
+            .collect();
         self
     }
 
     /// Loads disambiguation index once, returns a borrowed index to
     /// disambiguate commit/change IDs.
     pub fn populate(&self, repo: &dyn Repo) -> Result<IdPrefixIndex<'_>, IdPrefixIndexLoadError> {
-        let indexes = if let Some(disambiguation) = &self.disambiguation {
-            Some(disambiguation.indexes(repo, self.extensions.symbol_resolvers())?)
-        } else {
-            None
-        };
+        let indexes = self
+            .disambiguation
+            .iter()
+            .map(|disambiguation| disambiguation.indexes(repo, self.extensions.symbol_resolvers()))
+            .try_collect()?;
         Ok(IdPrefixIndex { indexes })
     }
 }
 
 /// Loaded index to disambiguate commit/change IDs.
 pub struct IdPrefixIndex<'a> {
-    indexes: Option<&'a Indexes>,
+    indexes: Vec<&'a Indexes>,
 }
 
 impl IdPrefixIndex<'_> {
     /// Returns an empty index that just falls back to a provided `repo`.
     pub const fn empty() -> IdPrefixIndex<'static> {
-        IdPrefixIndex { indexes: None }
+        IdPrefixIndex { indexes: vec![] }
     }
 
     /// Resolve an unambiguous commit ID prefix.
@@ -162,13 +191,13 @@ impl IdPrefixIndex<'_> {
         repo: &dyn Repo,
         prefix: &HexPrefix,
     ) -> IndexResult<PrefixResolution<CommitId>> {
-        if let Some(indexes) = self.indexes {
+        for indexes in &self.indexes {
             let resolution = indexes
                 .commit_index
                 .resolve_prefix_to_key(&*indexes.commit_change_ids, prefix);
             match resolution {
                 PrefixResolution::NoMatch => {
-                    // Fall back to resolving in entire repo
+                    // Fall back to resolving in the next set or entire repo
                 }
                 PrefixResolution::SingleMatch(id) => {
                     // The disambiguation set may be loaded from a different repo,
@@ -207,14 +236,23 @@ impl IdPrefixIndex<'_> {
         repo: &dyn Repo,
         commit_id: &CommitId,
     ) -> IndexResult<usize> {
-        if let Some(indexes) = self.indexes
-            && let Some(lookup) = indexes
+        // The prefix must not match any commits in the sets with higher
+        // priority.
+        let mut min_len = 0;
+        for indexes in &self.indexes {
+            let source = &*indexes.commit_change_ids;
+            if let Some(lookup) = indexes.commit_index.lookup_exact(source, commit_id) {
+                return Ok(max(min_len, lookup.shortest_unique_prefix_len()));
+            }
+            let len = indexes
                 .commit_index
-                .lookup_exact(&*indexes.commit_change_ids, commit_id)
-        {
-            return Ok(lookup.shortest_unique_prefix_len());
+                .shortest_unique_prefix_len(source, commit_id);
+            min_len = max(min_len, len);
         }
-        repo.index().shortest_unique_commit_id_prefix_len(commit_id)
+        let len = repo
+            .index()
+            .shortest_unique_commit_id_prefix_len(commit_id)?;
+        Ok(max(min_len, len))
     }
 
     /// Resolve an unambiguous change ID prefix to the commit IDs in the revset.
@@ -223,13 +261,13 @@ impl IdPrefixIndex<'_> {
         repo: &dyn Repo,
         prefix: &HexPrefix,
     ) -> IndexResult<PrefixResolution<ResolvedChangeTargets>> {
-        if let Some(indexes) = self.indexes {
+        for indexes in &self.indexes {
             let resolution = indexes
                 .change_index
                 .resolve_prefix_to_key(&*indexes.commit_change_ids, prefix);
             match resolution {
                 PrefixResolution::NoMatch => {
-                    // Fall back to resolving in entire repo
+                    // Fall back to resolving in the next set or entire repo
                 }
                 PrefixResolution::SingleMatch(change_id) => {
                     return match repo.resolve_change_id(&change_id)? {
@@ -267,14 +305,74 @@ impl IdPrefixIndex<'_> {
         repo: &dyn Repo,
         change_id: &ChangeId,
     ) -> IndexResult<usize> {
-        if let Some(indexes) = self.indexes
-            && let Some(lookup) = indexes
+        let mut min_len = 0;
+        for indexes in &self.indexes {
+            let source = &*indexes.commit_change_ids;
+            if let Some(lookup) = indexes.change_index.lookup_exact(source, change_id) {
+                return Ok(max(min_len, lookup.shortest_unique_prefix_len()));
+            }
+            let len = indexes
                 .change_index
-                .lookup_exact(&*indexes.commit_change_ids, change_id)
-        {
-            return Ok(lookup.shortest_unique_prefix_len());
+                .shortest_unique_prefix_len(source, change_id);
+            min_len = max(min_len, len);
+        }
+        let len = repo.shortest_unique_change_id_prefix_len(change_id)?;
+        Ok(max(min_len, len))
+    }
+
+    /// Returns the visible commits whose commit IDs match the `prefix`.
+    ///
+    /// The commits are looked up in the first disambiguation set containing
+    /// any matching commits, or in the entire repo. This can be used to report
+    /// the candidates of an ambiguous prefix.
+    pub fn commit_prefix_candidates(
+        &self,
+        repo: &dyn Repo,
+        prefix: &HexPrefix,
+    ) -> Result<Vec<CommitId>, RevsetEvaluationError> {
+        self.prefix_candidates(repo, |(commit_id, _)| prefix.matches(commit_id))
+    }
+
+    /// Returns the visible commits whose change IDs match the `prefix`.
+    ///
+    /// See [`Self::commit_prefix_candidates()`] for details.
+    pub fn change_prefix_candidates(
+        &self,
+        repo: &dyn Repo,
+        prefix: &HexPrefix,
+    ) -> Result<Vec<CommitId>, RevsetEvaluationError> {
+        self.prefix_candidates(repo, |(_, change_id)| prefix.matches(change_id))
+    }
+
+    fn prefix_candidates(
+        &self,
+        repo: &dyn Repo,
+        matches: impl Fn(&(CommitId, ChangeId)) -> bool,
+    ) -> Result<Vec<CommitId>, RevsetEvaluationError> {
+        for indexes in &self.indexes {
+            // The disambiguation set may be loaded from a different repo.
+            let commit_ids: Vec<_> = indexes
+                .commit_change_ids
+                .iter()
+                .filter(|entry| matches(entry))
+                .map(|(commit_id, _)| commit_id.clone())
+                .filter_map(|commit_id| match repo.index().has_id(&commit_id) {
+                    Ok(true) => Some(Ok(commit_id)),
+                    Ok(false) => None,
+                    Err(err) => Some(Err(RevsetEvaluationError::Other(err.into()))),
+                })
+                .try_collect()?;
+            if !commit_ids.is_empty() {
+                return Ok(commit_ids);
+            }
         }
-        repo.shortest_unique_change_id_prefix_len(change_id)
+        RevsetExpression::visible_heads()
+            .ancestors()
+            .evaluate(repo)?
+            .commit_change_ids()
+            .filter_ok(|entry| matches(entry))
+            .map_ok(|(commit_id, _)| commit_id)
+            .try_collect()
     }
 }
 
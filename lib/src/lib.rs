@@ -38,12 +38,15 @@ pub mod config;
 mod config_resolver;
 pub mod conflict_labels;
 pub mod conflicts;
+pub mod content_filter;
 pub mod copies;
 pub mod dag_walk;
 pub mod default_index;
 pub mod default_submodule_store;
+pub mod default_working_copy_store;
 pub mod diff;
 pub mod diff_presentation;
+pub mod draft_store;
 pub mod dsl_util;
 pub(crate) mod eol;
 pub mod evolution;
@@ -83,6 +86,7 @@ pub mod operation;
 pub mod protos;
 pub mod ref_name;
 pub mod refs;
+pub mod remote_sync_store;
 pub mod repo;
 pub mod repo_path;
 pub mod revset;
@@ -115,6 +119,7 @@ pub mod tree_builder;
 pub mod union_find;
 pub mod view;
 pub mod working_copy;
+pub mod working_copy_store;
 pub mod workspace;
 pub mod workspace_store;
 
@@ -77,7 +77,9 @@ use crate::conflicts::MaterializedTreeValue;
 use crate::conflicts::choose_materialized_conflict_marker_len;
 use crate::conflicts::materialize_merge_result_to_bytes;
 use crate::conflicts::materialize_tree_value;
+use crate::content_filter::ContentFilters;
 pub use crate::eol::EolConversionMode;
+pub use crate::eol::EolOverrides;
 use crate::eol::TargetEolStrategy;
 use crate::file_util::BlockingAsyncReader;
 use crate::file_util::FileIdentity;
@@ -99,6 +101,7 @@ use crate::matchers::IntersectionMatcher;
 use crate::matchers::Matcher;
 use crate::matchers::PrefixMatcher;
 use crate::matchers::UnionMatcher;
+use crate::matchers::Visit;
 use crate::merge::Merge;
 use crate::merge::MergeBuilder;
 use crate::merge::MergedTreeValue;
@@ -648,6 +651,17 @@ fn is_file_state_entries_proto_unique_and_sorted(
         .is_sorted_by(|path1, path2| path1 < path2)
 }
 
+fn sparse_patterns_to_proto(
+    sparse_patterns: &[RepoPathBuf],
+) -> crate::protos::local_working_copy::SparsePatterns {
+    crate::protos::local_working_copy::SparsePatterns {
+        prefixes: sparse_patterns
+            .iter()
+            .map(|path| path.as_internal_file_string().to_owned())
+            .collect(),
+    }
+}
+
 fn sparse_patterns_from_proto(
     proto: Option<&crate::protos::local_working_copy::SparsePatterns>,
 ) -> Vec<RepoPathBuf> {
@@ -948,10 +962,16 @@ pub struct TreeStateSettings {
     /// file to the backend, and vice versa when it checks out code onto your
     /// filesystem.
     pub eol_conversion_mode: EolConversionMode,
+    /// EOL conversion modes overriding `eol_conversion_mode` for the files
+    /// matched by filesets.
+    pub eol_overrides: EolOverrides,
     /// Whether to ignore changes to the executable bit for files on Unix.
     pub exec_change_setting: ExecChangeSetting,
     /// The fsmonitor (e.g. Watchman) to use, if any.
     pub fsmonitor_settings: FsmonitorSettings,
+    /// Filters transforming file content when checking out and snapshotting
+    /// files.
+    pub content_filters: ContentFilters,
 }
 
 impl TreeStateSettings {
@@ -960,8 +980,10 @@ impl TreeStateSettings {
         Ok(Self {
             conflict_marker_style: user_settings.get("ui.conflict-marker-style")?,
             eol_conversion_mode: EolConversionMode::try_from_settings(user_settings)?,
+            eol_overrides: EolOverrides::try_from_settings(user_settings)?,
             exec_change_setting: user_settings.get("working-copy.exec-bit-change")?,
             fsmonitor_settings: FsmonitorSettings::from_settings(user_settings)?,
+            content_filters: ContentFilters::from_settings(user_settings)?,
         })
     }
 }
@@ -986,6 +1008,7 @@ pub struct TreeState {
     exec_policy: ExecChangePolicy,
     fsmonitor_settings: FsmonitorSettings,
     target_eol_strategy: TargetEolStrategy,
+    content_filters: ContentFilters,
 }
 
 #[derive(Debug, Error)]
@@ -1044,11 +1067,15 @@ impl TreeState {
         &TreeStateSettings {
             conflict_marker_style,
             eol_conversion_mode,
+            ref eol_overrides,
             exec_change_setting,
             ref fsmonitor_settings,
+            ref content_filters,
         }: &TreeStateSettings,
     ) -> Self {
         let exec_policy = ExecChangePolicy::new(exec_change_setting, &state_path);
+        let content_filters =
+            content_filters.for_working_copy(&working_copy_path, &state_path.join("filter_cache"));
         Self {
             store: store.clone(),
             working_copy_path,
@@ -1062,7 +1089,8 @@ impl TreeState {
             conflict_marker_style,
             exec_policy,
             fsmonitor_settings: fsmonitor_settings.clone(),
-            target_eol_strategy: TargetEolStrategy::new(eol_conversion_mode),
+            target_eol_strategy: TargetEolStrategy::new(eol_conversion_mode, eol_overrides.clone()),
+            content_filters,
         }
     }
 
@@ -1153,13 +1181,7 @@ impl TreeState {
         proto.file_states = self.file_states.data.clone();
         // `FileStatesMap` is guaranteed to be sorted.
         proto.is_file_states_sorted = true;
-        let mut sparse_patterns = crate::protos::local_working_copy::SparsePatterns::default();
-        for path in &self.sparse_patterns {
-            sparse_patterns
-                .prefixes
-                .push(path.as_internal_file_string().to_owned());
-        }
-        proto.sparse_patterns = Some(sparse_patterns);
+        proto.sparse_patterns = Some(sparse_patterns_to_proto(&self.sparse_patterns));
         proto.watchman_clock = self.watchman_clock.clone();
 
         let wrap_write_err = |source| TreeStateError::WriteTreeState {
@@ -1260,6 +1282,7 @@ impl TreeState {
     ) -> Result<(bool, SnapshotStats), SnapshotError> {
         let &SnapshotOptions {
             ref base_ignores,
+            ignore_matcher,
             progress,
             start_tracking_matcher,
             force_tracking_matcher,
@@ -1294,6 +1317,7 @@ impl TreeState {
         let (tree_entries_tx, tree_entries_rx) = channel();
         let (file_states_tx, file_states_rx) = channel();
         let (untracked_paths_tx, untracked_paths_rx) = channel();
+        let (mixed_eol_paths_tx, mixed_eol_paths_rx) = channel();
         let (deleted_files_tx, deleted_files_rx) = channel();
 
         trace_span!("traverse filesystem").in_scope(|| -> Result<(), SnapshotError> {
@@ -1301,12 +1325,14 @@ impl TreeState {
                 tree_state: self,
                 current_tree: &self.tree,
                 matcher: &matcher,
+                ignore_matcher,
                 start_tracking_matcher,
                 force_tracking_matcher,
                 // Move tx sides so they'll be dropped at the end of the scope.
                 tree_entries_tx,
                 file_states_tx,
                 untracked_paths_tx,
+                mixed_eol_paths_tx,
                 deleted_files_tx,
                 error: OnceLock::new(),
                 progress,
@@ -1329,6 +1355,7 @@ impl TreeState {
 
         let stats = SnapshotStats {
             untracked_paths: untracked_paths_rx.into_iter().collect(),
+            mixed_eol_paths: mixed_eol_paths_rx.into_iter().collect(),
         };
         let mut tree_builder = MergedTreeBuilder::new(self.tree.clone());
         trace_span!("process tree entries").in_scope(|| {
@@ -1379,6 +1406,7 @@ impl TreeState {
         } else {
             tracing::info!("not updating watchman clock because there are untracked files");
         }
+        self.content_filters.gc_cache();
         Ok((is_dirty, stats))
     }
 
@@ -1473,11 +1501,13 @@ struct FileSnapshotter<'a> {
     tree_state: &'a TreeState,
     current_tree: &'a MergedTree,
     matcher: &'a dyn Matcher,
+    ignore_matcher: &'a dyn Matcher,
     start_tracking_matcher: &'a dyn Matcher,
     force_tracking_matcher: &'a dyn Matcher,
     tree_entries_tx: Sender<(RepoPathBuf, MergedTreeValue)>,
     file_states_tx: Sender<(RepoPathBuf, FileState)>,
     untracked_paths_tx: Sender<(RepoPathBuf, UntrackedReason)>,
+    mixed_eol_paths_tx: Sender<RepoPathBuf>,
     deleted_files_tx: Sender<RepoPathBuf>,
     error: OnceLock<SnapshotError>,
     progress: Option<&'a SnapshotProgress<'a>>,
@@ -1593,12 +1623,14 @@ impl FileSnapshotter<'_> {
                 }
             }
 
-            if git_ignore.matches(&path.to_internal_dir_string())
+            if (git_ignore.matches(&path.to_internal_dir_string())
+                || self.ignore_matcher.visit(&path) == Visit::AllRecursively)
                 && self.force_tracking_matcher.visit(&path).is_nothing()
             {
-                // If the whole directory is ignored by .gitignore, visit only
-                // paths we're already tracking. This is because .gitignore in
-                // ignored directory must be ignored. It's also more efficient.
+                // If the whole directory is ignored by .gitignore or the
+                // ignore_matcher, visit only paths we're already tracking.
+                // This is because .gitignore in ignored directory must be
+                // ignored. It's also more efficient.
                 // start_tracking_matcher is NOT tested here because we need to
                 // scan directory entries to report untracked paths.
                 self.spawn_ok(scope, move |_| self.visit_tracked_files(file_states));
@@ -1621,7 +1653,8 @@ impl FileSnapshotter<'_> {
                 progress(&path);
             }
             if maybe_current_file_state.is_none()
-                && (git_ignore.matches(path.as_internal_file_string())
+                && ((git_ignore.matches(path.as_internal_file_string())
+                    || self.ignore_matcher.matches(&path))
                     && !self.force_tracking_matcher.matches(&path))
             {
                 // If it wasn't already tracked and it matches
@@ -1890,12 +1923,13 @@ impl FileSnapshotter<'_> {
             })?;
             self.tree_state
                 .target_eol_strategy
-                .convert_eol_for_snapshot(BlockingAsyncReader::new(file))
+                .convert_eol_for_snapshot(repo_path, BlockingAsyncReader::new(file))
                 .await
                 .map_err(|err| SnapshotError::Other {
                     message: "Failed to convert the EOL".to_string(),
                     err: err.into(),
                 })?
+                .contents
                 .read_to_end(&mut contents)
                 .await
                 .map_err(|err| SnapshotError::Other {
@@ -1952,16 +1986,45 @@ impl FileSnapshotter<'_> {
             message: format!("Failed to open file {}", disk_path.display()),
             err: err.into(),
         })?;
-        let mut contents = self
+        let converted = self
             .tree_state
             .target_eol_strategy
-            .convert_eol_for_snapshot(BlockingAsyncReader::new(file))
+            .convert_eol_for_snapshot(path, BlockingAsyncReader::new(file))
             .await
             .map_err(|err| SnapshotError::Other {
                 message: "Failed to convert the EOL".to_string(),
                 err: err.into(),
             })?;
-        Ok(self.store().write_file(path, &mut contents).await?)
+        // Mixed line endings are normalized, so the file will be checked out
+        // differently.
+        if converted.has_mixed_eol {
+            self.mixed_eol_paths_tx.send(path.to_owned()).ok();
+        }
+        let mut contents = converted.contents;
+        if !self.tree_state.content_filters.matches(path) {
+            return Ok(self.store().write_file(path, &mut contents).await?);
+        }
+        // The filters transform the whole content at once.
+        let mut buf = vec![];
+        contents
+            .read_to_end(&mut buf)
+            .await
+            .map_err(|err| SnapshotError::Other {
+                message: "Failed to read the EOL converted contents".to_string(),
+                err: err.into(),
+            })?;
+        let contents = self
+            .tree_state
+            .content_filters
+            .clean(path, buf)
+            .map_err(|err| SnapshotError::Other {
+                message: format!("Failed to filter file {}", disk_path.display()),
+                err: err.into(),
+            })?;
+        Ok(self
+            .store()
+            .write_file(path, &mut contents.as_slice())
+            .await?)
     }
 
     async fn write_symlink_to_store(
@@ -2008,7 +2071,7 @@ impl TreeState {
         disk_path: &Path,
         contents: impl AsyncRead + Send + Unpin,
         exec_bit: ExecBit,
-        apply_eol_conversion: bool,
+        eol_conversion_path: Option<&RepoPath>,
     ) -> Result<FileState, CheckoutError> {
         let mut file = File::options()
             .write(true)
@@ -2018,9 +2081,9 @@ impl TreeState {
                 message: format!("Failed to open file {} for writing", disk_path.display()),
                 err: err.into(),
             })?;
-        let contents = if apply_eol_conversion {
+        let contents = if let Some(path) = eol_conversion_path {
             self.target_eol_strategy
-                .convert_eol_for_update(contents)
+                .convert_eol_for_update(path, contents)
                 .await
                 .map_err(|err| CheckoutError::Other {
                     message: "Failed to convert the EOL for the content".to_string(),
@@ -2091,13 +2154,14 @@ impl TreeState {
 
     async fn write_conflict(
         &self,
+        path: &RepoPath,
         disk_path: &Path,
         contents: &[u8],
         exec_bit: ExecBit,
     ) -> Result<FileState, CheckoutError> {
         let contents = self
             .target_eol_strategy
-            .convert_eol_for_update(contents)
+            .convert_eol_for_update(path, contents)
             .await
             .map_err(|err| CheckoutError::Other {
                 message: "Failed to convert the EOL when writing a merge conflict".to_string(),
@@ -2291,18 +2355,31 @@ impl TreeState {
                     deleted_files.insert(path);
                     return Ok(());
                 }
-                MaterializedTreeValue::File(file) => {
+                MaterializedTreeValue::File(mut file) => {
                     let exec_bit =
                         ExecBit::new_from_repo(file.executable, self.exec_policy, get_prev_exec);
-                    self.write_file(&disk_path, file.reader, exec_bit, true)
-                        .await?
+                    if self.content_filters.matches(&path) {
+                        let contents = file.read_all(&path).await?;
+                        let contents = self
+                            .content_filters
+                            .smudge(&path, &file.id, contents)
+                            .map_err(|err| CheckoutError::Other {
+                                message: format!("Failed to filter file {}", disk_path.display()),
+                                err: err.into(),
+                            })?;
+                        self.write_file(&disk_path, contents.as_slice(), exec_bit, Some(&path))
+                            .await?
+                    } else {
+                        self.write_file(&disk_path, file.reader, exec_bit, Some(&path))
+                            .await?
+                    }
                 }
                 MaterializedTreeValue::Symlink { id: _, target } => {
                     if self.symlink_support {
                         self.write_symlink(&disk_path, target)?
                     } else {
                         // The fake symlink file shouldn't be executable.
-                        self.write_file(&disk_path, target.as_bytes(), ExecBit(false), false)
+                        self.write_file(&disk_path, target.as_bytes(), ExecBit(false), None)
                             .await?
                     }
                 }
@@ -2328,8 +2405,9 @@ impl TreeState {
                     );
                     let contents =
                         materialize_merge_result_to_bytes(&file.contents, &file.labels, &options);
-                    let mut file_state =
-                        self.write_conflict(&disk_path, &contents, exec_bit).await?;
+                    let mut file_state = self
+                        .write_conflict(&path, &disk_path, &contents, exec_bit)
+                        .await?;
                     file_state.materialized_conflict_data = Some(MaterializedConflictData {
                         conflict_marker_len: conflict_marker_len.try_into().unwrap_or(u32::MAX),
                     });
@@ -2340,7 +2418,7 @@ impl TreeState {
                     // better than trying to describe the merge.
                     let contents = id.describe(&labels);
                     // Since this is a dummy file, it shouldn't be executable.
-                    self.write_conflict(&disk_path, contents.as_bytes(), ExecBit(false))
+                    self.write_conflict(&path, &disk_path, contents.as_bytes(), ExecBit(false))
                         .await?
                 }
             };
@@ -2402,6 +2480,7 @@ impl TreeState {
 
         self.file_states
             .merge_in(changed_file_states, &deleted_files);
+        self.content_filters.gc_cache();
         Ok(stats)
     }
 
@@ -2533,6 +2612,84 @@ impl CheckoutState {
     }
 }
 
+/// Journal of a checkout in progress stored in "pending_checkout" file.
+///
+/// The file is written before the working-copy files are updated and removed
+/// once the new tree state has been saved. If the update is interrupted (e.g.
+/// by a crash or power loss), the file is left behind, and the checkout is
+/// resumed the next time the working copy is locked.
+#[derive(Clone, Debug)]
+struct PendingCheckout {
+    tree: MergedTree,
+    sparse_patterns: Vec<RepoPathBuf>,
+}
+
+impl PendingCheckout {
+    const FILE_NAME: &str = "pending_checkout";
+
+    fn load(store: &Arc<Store>, state_path: &Path) -> Result<Option<Self>, WorkingCopyStateError> {
+        let wrap_err = |err| WorkingCopyStateError {
+            message: "Failed to read pending checkout".to_owned(),
+            err,
+        };
+        let buf = match fs::read(state_path.join(Self::FILE_NAME)) {
+            Ok(buf) => buf,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(None),
+            Err(err) => return Err(wrap_err(err.into())),
+        };
+        let proto = crate::protos::local_working_copy::PendingCheckout::decode(&*buf)
+            .map_err(|err| wrap_err(err.into()))?;
+        let tree_ids_builder: MergeBuilder<TreeId> =
+            proto.tree_ids.into_iter().map(TreeId::new).collect();
+        let tree = MergedTree::new(
+            store.clone(),
+            tree_ids_builder.build(),
+            ConflictLabels::from_vec(proto.conflict_labels),
+        );
+        Ok(Some(Self {
+            tree,
+            sparse_patterns: sparse_patterns_from_proto(proto.sparse_patterns.as_ref()),
+        }))
+    }
+
+    fn save(&self, state_path: &Path) -> Result<(), WorkingCopyStateError> {
+        let wrap_err = |err| WorkingCopyStateError {
+            message: "Failed to write pending checkout".to_owned(),
+            err,
+        };
+        let proto = crate::protos::local_working_copy::PendingCheckout {
+            tree_ids: self
+                .tree
+                .tree_ids()
+                .iter()
+                .map(|id| id.to_bytes())
+                .collect(),
+            conflict_labels: self.tree.labels().as_slice().to_owned(),
+            sparse_patterns: Some(sparse_patterns_to_proto(&self.sparse_patterns)),
+        };
+        let mut temp_file =
+            NamedTempFile::new_in(state_path).map_err(|err| wrap_err(err.into()))?;
+        temp_file
+            .as_file_mut()
+            .write_all(&proto.encode_to_vec())
+            .map_err(|err| wrap_err(err.into()))?;
+        persist_temp_file(temp_file, state_path.join(Self::FILE_NAME))
+            .map_err(|err| wrap_err(err.into()))?;
+        Ok(())
+    }
+
+    fn remove(state_path: &Path) -> Result<(), WorkingCopyStateError> {
+        match fs::remove_file(state_path.join(Self::FILE_NAME)) {
+            Ok(()) => Ok(()),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(()),
+            Err(err) => Err(WorkingCopyStateError {
+                message: "Failed to remove pending checkout".to_owned(),
+                err: err.into(),
+            }),
+        }
+    }
+}
+
 pub struct LocalWorkingCopy {
     store: Arc<Store>,
     working_copy_path: PathBuf,
@@ -2583,15 +2740,19 @@ impl WorkingCopy for LocalWorkingCopy {
             tree_state_settings: self.tree_state_settings.clone(),
         };
         let old_operation_id = wc.operation_id().clone();
-        let old_tree = wc.tree()?.clone();
-        Ok(Box::new(LockedLocalWorkingCopy {
+        let mut locked_wc = LockedLocalWorkingCopy {
+            old_tree: wc.tree()?.clone(),
             wc,
             old_operation_id,
-            old_tree,
             tree_state_dirty: false,
             new_workspace_name: None,
             _lock: lock,
-        }))
+        };
+        if let Some(pending) = PendingCheckout::load(&locked_wc.wc.store, &locked_wc.wc.state_path)?
+        {
+            locked_wc.resume_checkout(pending)?;
+        }
+        Ok(Box::new(locked_wc))
     }
 }
 
@@ -2791,12 +2952,24 @@ impl LockedWorkingCopy for LockedLocalWorkingCopy {
     }
 
     async fn check_out(&mut self, commit: &Commit) -> Result<CheckoutStats, CheckoutError> {
-        // TODO: Write a "pending_checkout" file with the new TreeId so we can
-        // continue an interrupted update if we find such a file.
         let new_tree = commit.tree();
         let tree_state = self.wc.tree_state_mut()?;
         if tree_state.tree.tree_ids_and_labels() != new_tree.tree_ids_and_labels() {
-            let stats = tree_state.check_out(&new_tree)?;
+            let pending = PendingCheckout {
+                tree: new_tree.clone(),
+                sparse_patterns: tree_state.sparse_patterns.clone(),
+            };
+            pending.save(&self.wc.state_path)?;
+            let tree_state = self.wc.tree_state_mut()?;
+            // The journal only describes an interrupted checkout. A checkout
+            // which failed cleanly must not be replayed by the next command.
+            let stats = match tree_state.check_out(&new_tree) {
+                Ok(stats) => stats,
+                Err(err) => {
+                    PendingCheckout::remove(&self.wc.state_path)?;
+                    return Err(err);
+                }
+            };
             self.tree_state_dirty = true;
             Ok(stats)
         } else {
@@ -2830,12 +3003,22 @@ impl LockedWorkingCopy for LockedLocalWorkingCopy {
         &mut self,
         new_sparse_patterns: Vec<RepoPathBuf>,
     ) -> Result<CheckoutStats, CheckoutError> {
-        // TODO: Write a "pending_checkout" file with new sparse patterns so we can
-        // continue an interrupted update if we find such a file.
-        let stats = self
+        let pending = PendingCheckout {
+            tree: self.wc.tree()?.clone(),
+            sparse_patterns: new_sparse_patterns.clone(),
+        };
+        pending.save(&self.wc.state_path)?;
+        let stats = match self
             .wc
             .tree_state_mut()?
-            .set_sparse_patterns(new_sparse_patterns)?;
+            .set_sparse_patterns(new_sparse_patterns)
+        {
+            Ok(stats) => stats,
+            Err(err) => {
+                PendingCheckout::remove(&self.wc.state_path)?;
+                return Err(err);
+            }
+        };
         self.tree_state_dirty = true;
         Ok(stats)
     }
@@ -2865,12 +3048,54 @@ impl LockedWorkingCopy for LockedLocalWorkingCopy {
             }
             self.wc.checkout_state.save(&self.wc.state_path)?;
         }
-        // TODO: Clear the "pending_checkout" file here.
+        // The new tree state has been saved, so the checkout is complete.
+        PendingCheckout::remove(&self.wc.state_path)?;
         Ok(Box::new(self.wc))
     }
 }
 
 impl LockedLocalWorkingCopy {
+    /// Completes a checkout which was interrupted before the new tree state
+    /// could be saved.
+    ///
+    /// The working-copy files may be partially updated, so the update is
+    /// rolled forward to the recorded target. Files which were already written
+    /// are left in place and picked up by the next snapshot. The resumed state
+    /// is saved immediately so the journal can be removed.
+    ///
+    /// If the checkout can't be resumed, the journal is discarded and the
+    /// working copy stays at the old tree recorded in the saved tree state.
+    fn resume_checkout(&mut self, pending: PendingCheckout) -> Result<(), WorkingCopyStateError> {
+        tracing::warn!(
+            tree_ids = ?pending.tree.tree_ids(),
+            "resuming interrupted working-copy checkout"
+        );
+        let tree_state = self.wc.tree_state_mut()?;
+        let result = (|| {
+            if tree_state.sparse_patterns != pending.sparse_patterns {
+                tree_state.set_sparse_patterns(pending.sparse_patterns)?;
+            }
+            if tree_state.tree.tree_ids_and_labels() != pending.tree.tree_ids_and_labels() {
+                tree_state.check_out(&pending.tree)?;
+            }
+            Ok::<_, CheckoutError>(())
+        })();
+        if let Err(err) = result {
+            tracing::warn!(?err, "failed to resume checkout, keeping the old tree");
+            // Reload the tree state saved before the interrupted checkout.
+            self.wc.tree_state = OnceCell::new();
+            PendingCheckout::remove(&self.wc.state_path)?;
+            return Ok(());
+        }
+        tree_state.save().map_err(|err| WorkingCopyStateError {
+            message: "Failed to write working copy state".to_string(),
+            err: Box::new(err),
+        })?;
+        PendingCheckout::remove(&self.wc.state_path)?;
+        self.old_tree = pending.tree;
+        Ok(())
+    }
+
     pub fn reset_watchman(&mut self) -> Result<(), SnapshotError> {
         self.wc.tree_state_mut()?.reset_watchman();
         self.tree_state_dirty = true;
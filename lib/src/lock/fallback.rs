@@ -14,6 +14,8 @@
 
 use std::fs::File;
 use std::fs::OpenOptions;
+use std::io;
+use std::io::Write as _;
 use std::path::PathBuf;
 use std::time::Duration;
 
@@ -23,7 +25,7 @@ use super::FileLockError;
 
 pub struct FileLock {
     path: PathBuf,
-    _file: File,
+    file: File,
 }
 
 struct BackoffIterator {
@@ -68,7 +70,7 @@ impl FileLock {
             match options.open(&path) {
                 Ok(file) => {
                     tracing::info!("Locked {path:?}");
-                    return Ok(Self { path, _file: file });
+                    return Ok(Self { path, file });
                 }
                 Err(err)
                     if err.kind() == std::io::ErrorKind::AlreadyExists
@@ -95,6 +97,38 @@ impl FileLock {
             }
         }
     }
+
+    pub fn try_lock(path: PathBuf) -> Result<Option<Self>, FileLockError> {
+        tracing::info!("Attempting to lock {path:?} without waiting");
+        let mut options = OpenOptions::new();
+        options.create_new(true);
+        options.write(true);
+        match options.open(&path) {
+            Ok(file) => {
+                tracing::info!("Locked {path:?}");
+                Ok(Some(Self { path, file }))
+            }
+            Err(err)
+                if err.kind() == std::io::ErrorKind::AlreadyExists
+                    || (cfg!(windows) && err.kind() == std::io::ErrorKind::PermissionDenied) =>
+            {
+                Ok(None)
+            }
+            Err(err) => Err(FileLockError {
+                message: "Failed to create lock file",
+                path,
+                err,
+            }),
+        }
+    }
+
+    // Only tests use this implementation on Unix, and they don't write the
+    // holder info.
+    #[cfg_attr(all(unix, test), expect(dead_code))]
+    pub(super) fn write_holder_info(&self, info: &str) -> io::Result<()> {
+        let mut file = &self.file;
+        file.write_all(info.as_bytes())
+    }
 }
 
 impl Drop for FileLock {
@@ -18,8 +18,12 @@ mod fallback;
 #[cfg(unix)]
 mod unix;
 
+use std::fs;
 use std::io;
 use std::path::PathBuf;
+use std::thread;
+use std::time::Duration;
+use std::time::Instant;
 
 use thiserror::Error;
 
@@ -37,12 +41,89 @@ pub struct FileLockError {
     pub err: io::Error,
 }
 
+/// How [`FileLock::lock_with_retry()`] waits for a lock held by someone else.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct LockRetryPolicy {
+    /// Give up after waiting this long. Zero means waiting forever.
+    pub timeout: Duration,
+    /// Upper bound of the delay between attempts. The actual delay grows
+    /// exponentially up to this bound and is randomized so that processes
+    /// waiting on the same lock don't retry in lockstep.
+    pub max_backoff: Duration,
+}
+
+impl Default for LockRetryPolicy {
+    fn default() -> Self {
+        Self {
+            timeout: Duration::from_secs(60),
+            max_backoff: Duration::from_secs(1),
+        }
+    }
+}
+
+impl FileLock {
+    /// Acquires the lock, retrying with jittered backoff while it's held by
+    /// someone else.
+    ///
+    /// Once acquired, the process ID, `hostname`, and the current time are
+    /// recorded in the lock file. If the lock can't be acquired within the
+    /// policy's timeout, the returned error describes the current holder.
+    pub fn lock_with_retry(
+        path: PathBuf,
+        policy: &LockRetryPolicy,
+        hostname: &str,
+    ) -> Result<Self, FileLockError> {
+        let start = Instant::now();
+        let mut backoff = Duration::from_millis(1);
+        loop {
+            if let Some(lock) = Self::try_lock(path.clone())? {
+                let info = holder_info(hostname);
+                lock.write_holder_info(&info)
+                    .inspect_err(|err| tracing::warn!(?err, ?path, "Failed to write lock holder"))
+                    .ok();
+                return Ok(lock);
+            }
+            let elapsed = start.elapsed();
+            let remaining = if policy.timeout.is_zero() {
+                Duration::MAX
+            } else if let Some(remaining) = policy.timeout.checked_sub(elapsed)
+                && !remaining.is_zero()
+            {
+                remaining
+            } else {
+                let holder = fs::read_to_string(&path).unwrap_or_default();
+                let holder = holder.trim();
+                let detail = if holder.is_empty() {
+                    "The lock holder is unknown".to_owned()
+                } else {
+                    format!("The lock is held by {holder}")
+                };
+                return Err(FileLockError {
+                    message: "Timed out while waiting for lock file",
+                    path,
+                    err: io::Error::new(io::ErrorKind::TimedOut, detail),
+                });
+            };
+            let jittered = backoff.mul_f32(rand::random::<f32>() + 0.5);
+            thread::sleep(jittered.min(remaining));
+            backoff = (backoff * 3 / 2).min(policy.max_backoff);
+        }
+    }
+}
+
+fn holder_info(hostname: &str) -> String {
+    let pid = std::process::id();
+    let since = chrono::Local::now().to_rfc3339_opts(chrono::SecondsFormat::Secs, false);
+    if hostname.is_empty() {
+        format!("PID {pid} since {since}")
+    } else {
+        format!("PID {pid} on {hostname} since {since}")
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use std::cmp::max;
-    use std::fs;
-    use std::thread;
-    use std::time::Duration;
 
     use test_case::test_case;
 
@@ -62,6 +143,60 @@ mod tests {
         assert!(!lock_path.exists());
     }
 
+    #[test_case(FileLock::lock, FileLock::try_lock)]
+    #[cfg_attr(
+        unix,
+        test_case(fallback::FileLock::lock, fallback::FileLock::try_lock)
+    )]
+    fn try_lock_busy<T>(
+        lock_fn: fn(PathBuf) -> Result<T, FileLockError>,
+        try_lock_fn: fn(PathBuf) -> Result<Option<T>, FileLockError>,
+    ) {
+        let temp_dir = new_temp_dir();
+        let lock_path = temp_dir.path().join("test.lock");
+        {
+            let _lock = lock_fn(lock_path.clone()).unwrap();
+            assert!(try_lock_fn(lock_path.clone()).unwrap().is_none());
+        }
+        assert!(!lock_path.exists());
+        let lock = try_lock_fn(lock_path.clone()).unwrap();
+        assert!(lock.is_some());
+        assert!(lock_path.exists());
+    }
+
+    #[test]
+    fn lock_with_retry_reports_holder() {
+        let temp_dir = new_temp_dir();
+        let lock_path = temp_dir.path().join("test.lock");
+        let policy = LockRetryPolicy {
+            timeout: Duration::from_millis(50),
+            max_backoff: Duration::from_millis(10),
+        };
+        let lock = FileLock::lock_with_retry(lock_path.clone(), &policy, "host1").unwrap();
+        let Err(err) = FileLock::lock_with_retry(lock_path.clone(), &policy, "host2") else {
+            panic!("lock should be busy");
+        };
+        assert_eq!(err.err.kind(), io::ErrorKind::TimedOut);
+        let expected = format!(
+            "The lock is held by PID {} on host1 since ",
+            std::process::id()
+        );
+        assert!(err.err.to_string().starts_with(&expected), "{err:?}");
+
+        // The lock is acquired once released by the other holder
+        let policy = LockRetryPolicy {
+            timeout: Duration::from_secs(10),
+            max_backoff: Duration::from_millis(10),
+        };
+        thread::scope(|s| {
+            s.spawn(|| {
+                thread::sleep(Duration::from_millis(50));
+                drop(lock);
+            });
+            FileLock::lock_with_retry(lock_path.clone(), &policy, "host2").unwrap();
+        });
+    }
+
     #[test_case(FileLock::lock)]
     #[cfg_attr(unix, test_case(fallback::FileLock::lock))]
     fn lock_concurrent<T>(lock_fn: fn(PathBuf) -> Result<T, FileLockError>) {
@@ -15,6 +15,10 @@
 #![expect(missing_docs)]
 
 use std::fs::File;
+use std::fs::OpenOptions;
+use std::io;
+use std::io::Write as _;
+use std::path::Path;
 use std::path::PathBuf;
 
 use rustix::fs::FlockOperation;
@@ -32,7 +36,7 @@ impl FileLock {
         tracing::info!("Attempting to lock {path:?}");
         loop {
             // Create lockfile, or open pre-existing one
-            let file = File::create(&path).map_err(|err| FileLockError {
+            let file = open_lock_file(&path).map_err(|err| FileLockError {
                 message: "Failed to open lock file",
                 path: path.clone(),
                 err,
@@ -77,6 +81,64 @@ impl FileLock {
             return Ok(Self { path, file });
         }
     }
+
+    /// Like [`Self::lock()`], but returns `None` instead of waiting if the
+    /// lock is held by someone else.
+    pub fn try_lock(path: PathBuf) -> Result<Option<Self>, FileLockError> {
+        tracing::info!("Attempting to lock {path:?} without waiting");
+        loop {
+            let file = open_lock_file(&path).map_err(|err| FileLockError {
+                message: "Failed to open lock file",
+                path: path.clone(),
+                err,
+            })?;
+            match rustix::fs::flock(&file, FlockOperation::NonBlockingLockExclusive) {
+                Ok(()) => {}
+                Err(rustix::io::Errno::WOULDBLOCK) => return Ok(None),
+                Err(errno) => {
+                    return Err(FileLockError {
+                        message: "Failed to lock lock file",
+                        path: path.clone(),
+                        err: errno.into(),
+                    });
+                }
+            }
+
+            match rustix::fs::fstat(&file) {
+                Ok(stat) if stat.st_nlink == 0 => continue,
+                Ok(_) => {}
+                Err(rustix::io::Errno::STALE) => continue,
+                Err(errno) => {
+                    return Err(FileLockError {
+                        message: "failed to stat lock file",
+                        path: path.clone(),
+                        err: errno.into(),
+                    });
+                }
+            }
+
+            tracing::info!("Locked {path:?}");
+            return Ok(Some(Self { path, file }));
+        }
+    }
+
+    /// Replaces the contents of the lock file with the given description of
+    /// the holder.
+    pub(super) fn write_holder_info(&self, info: &str) -> io::Result<()> {
+        let mut file = &self.file;
+        file.set_len(0)?;
+        file.write_all(info.as_bytes())
+    }
+}
+
+/// Opens the lock file without truncating it, so that waiting on the lock
+/// doesn't clear the information recorded by the current holder.
+fn open_lock_file(path: &Path) -> io::Result<File> {
+    OpenOptions::new()
+        .create(true)
+        .truncate(false)
+        .write(true)
+        .open(path)
 }
 
 impl Drop for FileLock {
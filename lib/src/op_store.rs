@@ -478,12 +478,22 @@ pub trait OpStore: Any + Send + Sync + Debug {
         prefix: &HexPrefix,
     ) -> OpStoreResult<PrefixResolution<OperationId>>;
 
+    /// Returns the operation tags, mapping tag names to operation ids.
+    ///
+    /// Tags aren't recorded in the operation log. They can be moved or deleted
+    /// at any time, and aren't affected by `jj undo`.
+    fn get_operation_tags(&self) -> OpStoreResult<BTreeMap<String, OperationId>>;
+
+    /// Points the tag `name` to the operation `id`, or deletes the tag if `id`
+    /// is `None`.
+    fn set_operation_tag(&self, name: &str, id: Option<&OperationId>) -> OpStoreResult<()>;
+
     /// Prunes unreachable operations and views.
     ///
-    /// All operations and views reachable from the `head_ids` won't be
-    /// removed. In addition to that, objects created after `keep_newer` will be
-    /// preserved. This mitigates a risk of deleting new heads created
-    /// concurrently by another process.
+    /// All operations and views reachable from the `head_ids` or from the
+    /// operation tags won't be removed. In addition to that, objects created
+    /// after `keep_newer` will be preserved. This mitigates a risk of deleting
+    /// new heads created concurrently by another process.
     // TODO: return stats?
     fn gc(&self, head_ids: &[OperationId], keep_newer: SystemTime) -> OpStoreResult<()>;
 }
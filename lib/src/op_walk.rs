@@ -185,6 +185,10 @@ fn resolve_single_op_from_store(
     if op_str.is_empty() {
         return Err(OpsetResolutionError::InvalidIdPrefix(op_str.to_owned()).into());
     }
+    if let Some(op_id) = op_store.get_operation_tags()?.get(op_str) {
+        let data = op_store.read_operation(op_id).block_on()?;
+        return Ok(Operation::new(op_store.clone(), op_id.clone(), data));
+    }
     let prefix = HexPrefix::try_from_hex(op_str)
         .ok_or_else(|| OpsetResolutionError::InvalidIdPrefix(op_str.to_owned()))?;
     match op_store.resolve_operation_id_prefix(&prefix).block_on()? {
@@ -76,6 +76,19 @@ pub struct Checkout {
     #[prost(string, tag = "3")]
     pub workspace_name: ::prost::alloc::string::String,
 }
+/// Journal of a checkout in progress. It's written before the files in the
+/// working copy are updated, and removed once the new tree state has been saved.
+#[derive(Clone, PartialEq, Eq, Hash, ::prost::Message)]
+pub struct PendingCheckout {
+    /// The tree being checked out, in the same format as TreeState.tree_ids.
+    #[prost(bytes = "vec", repeated, tag = "1")]
+    pub tree_ids: ::prost::alloc::vec::Vec<::prost::alloc::vec::Vec<u8>>,
+    #[prost(string, repeated, tag = "2")]
+    pub conflict_labels: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+    /// The sparse patterns to apply.
+    #[prost(message, optional, tag = "3")]
+    pub sparse_patterns: ::core::option::Option<SparsePatterns>,
+}
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
 #[repr(i32)]
 pub enum FileType {
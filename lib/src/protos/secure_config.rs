@@ -7,4 +7,10 @@ pub struct ConfigMetadata {
     /// Access via file_util::path_to/from_bytes
     #[prost(bytes = "vec", optional, tag = "1")]
     pub path: ::core::option::Option<::prost::alloc::vec::Vec<u8>>,
+    /// The path to the age identity file which the config file is encrypted
+    /// with. If set, the config is stored in config.toml.age instead of
+    /// config.toml.
+    /// Access via file_util::path_to/from_bytes
+    #[prost(bytes = "vec", optional, tag = "2")]
+    pub age_identity: ::core::option::Option<::prost::alloc::vec::Vec<u8>>,
 }
@@ -1,10 +1,29 @@
 // This file is @generated by prost-build.
-#[derive(Clone, PartialEq, Eq, Hash, ::prost::Message)]
+#[derive(Clone, PartialEq, ::prost::Message)]
 pub struct Workspace {
     #[prost(string, tag = "1")]
     pub name: ::prost::alloc::string::String,
     #[prost(bytes = "vec", tag = "2")]
     pub path: ::prost::alloc::vec::Vec<u8>,
+    /// Unset for workspaces added before the creation metadata was recorded.
+    #[prost(message, optional, tag = "3")]
+    pub creation: ::core::option::Option<WorkspaceCreation>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct WorkspaceCreation {
+    #[prost(message, optional, tag = "1")]
+    pub time: ::core::option::Option<Timestamp>,
+    #[prost(string, tag = "2")]
+    pub username: ::prost::alloc::string::String,
+    #[prost(bytes = "vec", tag = "3")]
+    pub operation_id: ::prost::alloc::vec::Vec<u8>,
+}
+#[derive(Clone, Copy, PartialEq, Eq, Hash, ::prost::Message)]
+pub struct Timestamp {
+    #[prost(int64, tag = "1")]
+    pub millis_since_epoch: i64,
+    #[prost(int32, tag = "2")]
+    pub tz_offset: i32,
 }
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct Workspaces {
@@ -0,0 +1,330 @@
+// Copyright 2026 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Records of when each remote was last fetched from and pushed to.
+//!
+//! The records describe interactions with the outside world, so they are
+//! stored next to the operation log rather than in it, and aren't rolled back
+//! by `jj undo` or `jj op restore`.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::io;
+use std::io::Write as _;
+use std::path::Path;
+use std::path::PathBuf;
+
+use tempfile::NamedTempFile;
+use thiserror::Error;
+
+use crate::backend::MillisSinceEpoch;
+use crate::backend::Timestamp;
+use crate::file_util::IoResultExt as _;
+use crate::file_util::PathError;
+use crate::file_util::persist_temp_file;
+use crate::lock::FileLock;
+use crate::lock::FileLockError;
+use crate::object_id::ObjectId as _;
+use crate::op_store::OperationId;
+use crate::ref_name::RemoteName;
+use crate::ref_name::RemoteNameBuf;
+
+/// Record of a single fetch from or push to a remote.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RemoteSyncRecord {
+    /// Time when the fetch or push finished.
+    pub time: Timestamp,
+    /// Operation which was current after the fetch or push.
+    pub operation_id: OperationId,
+    /// Number of refs involved. For a fetch, this is the number of remote
+    /// bookmarks and tags known after the fetch. For a push, this is the
+    /// number of refs pushed.
+    pub ref_count: usize,
+}
+
+/// Last fetch and push of a remote.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct RemoteSyncInfo {
+    /// Last fetch from the remote.
+    pub last_fetch: Option<RemoteSyncRecord>,
+    /// Last push to the remote.
+    pub last_push: Option<RemoteSyncRecord>,
+}
+
+/// Error that can occur when reading or updating the remote sync records.
+#[derive(Debug, Error)]
+pub enum RemoteSyncStoreError {
+    /// An I/O error related to a file path.
+    #[error(transparent)]
+    Path(#[from] PathError),
+    /// Failed to lock the store.
+    #[error("Failed to lock remote sync store")]
+    Lock(#[from] FileLockError),
+    /// The store file is malformed.
+    #[error("Failed to parse remote sync store {path}")]
+    Parse {
+        /// Path to the store file.
+        path: PathBuf,
+        /// Underlying error.
+        #[source]
+        source: Box<dyn std::error::Error + Send + Sync>,
+    },
+}
+
+/// File-based store of [`RemoteSyncInfo`] per remote.
+///
+/// The records are stored in `remote_sync/remotes.toml` in the repo
+/// directory, which is created when the first record is written.
+#[derive(Debug)]
+pub struct RemoteSyncStore {
+    store_file: PathBuf,
+    lock_file: PathBuf,
+}
+
+impl RemoteSyncStore {
+    /// Loads the store from the given repository path.
+    pub fn load(repo_path: &Path) -> Self {
+        let store_file = repo_path.join("remote_sync").join("remotes.toml");
+        Self {
+            lock_file: store_file.with_extension("lock"),
+            store_file,
+        }
+    }
+
+    /// Returns the records of all remotes.
+    pub fn get_all(&self) -> Result<BTreeMap<RemoteNameBuf, RemoteSyncInfo>, RemoteSyncStoreError> {
+        self.read_store()
+    }
+
+    /// Returns the records of the `remote`.
+    pub fn get(&self, remote: &RemoteName) -> Result<RemoteSyncInfo, RemoteSyncStoreError> {
+        let mut remotes = self.read_store()?;
+        Ok(remotes.remove(remote).unwrap_or_default())
+    }
+
+    /// Records a fetch from the `remote`.
+    pub fn record_fetch(
+        &self,
+        remote: &RemoteName,
+        record: RemoteSyncRecord,
+    ) -> Result<(), RemoteSyncStoreError> {
+        self.update(|remotes| {
+            remotes.entry(remote.to_owned()).or_default().last_fetch = Some(record);
+        })
+    }
+
+    /// Records a push to the `remote`.
+    pub fn record_push(
+        &self,
+        remote: &RemoteName,
+        record: RemoteSyncRecord,
+    ) -> Result<(), RemoteSyncStoreError> {
+        self.update(|remotes| {
+            remotes.entry(remote.to_owned()).or_default().last_push = Some(record);
+        })
+    }
+
+    /// Moves the records of the `old` remote to the `new` remote.
+    pub fn rename_remote(
+        &self,
+        old: &RemoteName,
+        new: &RemoteName,
+    ) -> Result<(), RemoteSyncStoreError> {
+        self.update(|remotes| {
+            if let Some(info) = remotes.remove(old) {
+                remotes.insert(new.to_owned(), info);
+            }
+        })
+    }
+
+    /// Removes the records of the `remote`.
+    pub fn remove_remote(&self, remote: &RemoteName) -> Result<(), RemoteSyncStoreError> {
+        self.update(|remotes| {
+            remotes.remove(remote);
+        })
+    }
+
+    fn update(
+        &self,
+        f: impl FnOnce(&mut BTreeMap<RemoteNameBuf, RemoteSyncInfo>),
+    ) -> Result<(), RemoteSyncStoreError> {
+        let store_dir = self.store_file.parent().unwrap();
+        fs::create_dir_all(store_dir).context(store_dir)?;
+        let _lock = FileLock::lock(self.lock_file.clone())?;
+        let mut remotes = self.read_store()?;
+        f(&mut remotes);
+        self.write_store(&remotes)
+    }
+
+    fn read_store(&self) -> Result<BTreeMap<RemoteNameBuf, RemoteSyncInfo>, RemoteSyncStoreError> {
+        let text = match fs::read_to_string(&self.store_file).context(&self.store_file) {
+            Ok(text) => text,
+            Err(err) if err.source.kind() == io::ErrorKind::NotFound => {
+                return Ok(BTreeMap::new());
+            }
+            Err(err) => return Err(err.into()),
+        };
+        parse_store(&text).map_err(|source| RemoteSyncStoreError::Parse {
+            path: self.store_file.clone(),
+            source,
+        })
+    }
+
+    fn write_store(
+        &self,
+        remotes: &BTreeMap<RemoteNameBuf, RemoteSyncInfo>,
+    ) -> Result<(), RemoteSyncStoreError> {
+        let store_dir = self.store_file.parent().unwrap();
+        let temp_file = NamedTempFile::new_in(store_dir).context(store_dir)?;
+        temp_file
+            .as_file()
+            .write_all(format_store(remotes).as_bytes())
+            .context(temp_file.path())?;
+        persist_temp_file(temp_file, &self.store_file).context(&self.store_file)?;
+        Ok(())
+    }
+}
+
+fn parse_store(
+    text: &str,
+) -> Result<BTreeMap<RemoteNameBuf, RemoteSyncInfo>, Box<dyn std::error::Error + Send + Sync>> {
+    let doc: toml_edit::DocumentMut = text.parse()?;
+    let mut remotes = BTreeMap::new();
+    for (name, item) in doc.iter() {
+        let table = item
+            .as_table()
+            .ok_or_else(|| format!("Remote {name} is not a table"))?;
+        let info = RemoteSyncInfo {
+            last_fetch: table
+                .get("last-fetch")
+                .map(|item| parse_record(name, item))
+                .transpose()?,
+            last_push: table
+                .get("last-push")
+                .map(|item| parse_record(name, item))
+                .transpose()?,
+        };
+        remotes.insert(RemoteNameBuf::from(name), info);
+    }
+    Ok(remotes)
+}
+
+fn parse_record(
+    name: &str,
+    item: &toml_edit::Item,
+) -> Result<RemoteSyncRecord, Box<dyn std::error::Error + Send + Sync>> {
+    let invalid = || format!("Invalid record for remote {name}");
+    let table = item.as_table().ok_or_else(invalid)?;
+    let get_integer = |key: &str| table.get(key).and_then(|item| item.as_integer());
+    let millis = get_integer("time").ok_or_else(invalid)?;
+    let tz_offset = get_integer("tz-offset").ok_or_else(invalid)?;
+    let operation_id = table
+        .get("operation")
+        .and_then(|item| item.as_str())
+        .and_then(OperationId::try_from_hex)
+        .ok_or_else(invalid)?;
+    let ref_count = get_integer("ref-count").ok_or_else(invalid)?;
+    Ok(RemoteSyncRecord {
+        time: Timestamp {
+            timestamp: MillisSinceEpoch(millis),
+            tz_offset: tz_offset.try_into()?,
+        },
+        operation_id,
+        ref_count: ref_count.try_into()?,
+    })
+}
+
+fn format_store(remotes: &BTreeMap<RemoteNameBuf, RemoteSyncInfo>) -> String {
+    let mut doc = toml_edit::DocumentMut::new();
+    for (name, info) in remotes {
+        let mut table = toml_edit::Table::new();
+        if let Some(record) = &info.last_fetch {
+            table.insert("last-fetch", format_record(record).into());
+        }
+        if let Some(record) = &info.last_push {
+            table.insert("last-push", format_record(record).into());
+        }
+        doc.insert(name.as_str(), table.into());
+    }
+    doc.to_string()
+}
+
+fn format_record(record: &RemoteSyncRecord) -> toml_edit::Table {
+    let mut table = toml_edit::Table::new();
+    table.insert("time", toml_edit::value(record.time.timestamp.0));
+    table.insert(
+        "tz-offset",
+        toml_edit::value(i64::from(record.time.tz_offset)),
+    );
+    table.insert("operation", toml_edit::value(record.operation_id.hex()));
+    table.insert(
+        "ref-count",
+        toml_edit::value(i64::try_from(record.ref_count).unwrap_or(i64::MAX)),
+    );
+    table
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::new_temp_dir;
+
+    fn record(millis: i64, ref_count: usize) -> RemoteSyncRecord {
+        RemoteSyncRecord {
+            time: Timestamp {
+                timestamp: MillisSinceEpoch(millis),
+                tz_offset: -420,
+            },
+            operation_id: OperationId::from_hex("0123456789abcdef"),
+            ref_count,
+        }
+    }
+
+    #[test]
+    fn test_record_and_get() {
+        let temp_dir = new_temp_dir();
+        let store = RemoteSyncStore::load(temp_dir.path());
+        let origin = RemoteName::new("origin");
+        let upstream = RemoteName::new("up.stream");
+        assert_eq!(store.get(origin).unwrap(), RemoteSyncInfo::default());
+
+        store.record_fetch(origin, record(1000, 3)).unwrap();
+        store.record_push(origin, record(2000, 1)).unwrap();
+        store.record_fetch(upstream, record(3000, 5)).unwrap();
+        assert_eq!(
+            store.get(origin).unwrap(),
+            RemoteSyncInfo {
+                last_fetch: Some(record(1000, 3)),
+                last_push: Some(record(2000, 1)),
+            }
+        );
+        assert_eq!(
+            store.get(upstream).unwrap(),
+            RemoteSyncInfo {
+                last_fetch: Some(record(3000, 5)),
+                last_push: None,
+            }
+        );
+
+        store
+            .rename_remote(upstream, RemoteName::new("other"))
+            .unwrap();
+        store.remove_remote(origin).unwrap();
+        let remotes = store.get_all().unwrap();
+        assert_eq!(
+            remotes.keys().map(|name| name.as_str()).collect::<Vec<_>>(),
+            ["other"]
+        );
+    }
+}
@@ -22,8 +22,10 @@ use std::fmt::Debug;
 use std::fmt::Formatter;
 use std::fs;
 use std::path::Path;
+use std::path::PathBuf;
 use std::slice;
 use std::sync::Arc;
+use std::time::Duration;
 
 use futures::future::try_join_all;
 use itertools::Itertools as _;
@@ -44,6 +46,7 @@ use crate::commit::Commit;
 use crate::commit::CommitByCommitterTimestamp;
 use crate::commit_builder::CommitBuilder;
 use crate::commit_builder::DetachedCommitBuilder;
+use crate::config::ConfigGetError;
 use crate::dag_walk;
 use crate::default_index::DefaultIndexStore;
 use crate::default_index::DefaultMutableIndex;
@@ -59,6 +62,7 @@ use crate::index::IndexStoreError;
 use crate::index::MutableIndex;
 use crate::index::ReadonlyIndex;
 use crate::index::ResolvedChangeTargets;
+use crate::lock::LockRetryPolicy;
 use crate::merge::MergeBuilder;
 use crate::merge::SameChange;
 use crate::merge::trivial_merge;
@@ -113,6 +117,9 @@ use crate::transaction::TransactionCommitError;
 use crate::tree_merge::MergeOptions;
 use crate::view::RenameWorkspaceError;
 use crate::view::View;
+use crate::workspace_store::SimpleWorkspaceStore;
+use crate::workspace_store::WorkspaceStore;
+use crate::workspace_store::WorkspaceStoreError;
 
 pub trait Repo {
     /// Base repository that contains all committed data. Returns `self` if this
@@ -178,6 +185,8 @@ pub enum RepoInitError {
     OpHeadsStore(#[from] OpHeadsStoreError),
     #[error(transparent)]
     Path(#[from] PathError),
+    #[error(transparent)]
+    WorkspaceStore(#[from] WorkspaceStoreError),
 }
 
 impl ReadonlyRepo {
@@ -188,7 +197,14 @@ impl ReadonlyRepo {
     }
 
     pub fn default_op_heads_store_initializer() -> &'static OpHeadsStoreInitializer<'static> {
-        &|_settings, store_path| Ok(Box::new(SimpleOpHeadsStore::init(store_path)?))
+        &|settings, store_path| {
+            let lock_policy =
+                op_heads_lock_retry_policy(settings).map_err(|err| BackendInitError(err.into()))?;
+            Ok(Box::new(
+                SimpleOpHeadsStore::init(store_path)?
+                    .with_lock_retry_policy(lock_policy, settings.operation_hostname().to_owned()),
+            ))
+        }
     }
 
     pub fn default_index_store_initializer() -> &'static IndexStoreInitializer<'static> {
@@ -212,7 +228,7 @@ impl ReadonlyRepo {
     ) -> Result<Arc<Self>, RepoInitError> {
         let repo_path = dunce::canonicalize(repo_path).context(repo_path)?;
 
-        let store_path = repo_path.join("store");
+        let store_path = RepoLoader::store_path(&repo_path);
         fs::create_dir(&store_path).context(&store_path)?;
         let backend = backend_initializer(settings, &store_path)?;
         let backend_path = store_path.join("type");
@@ -221,7 +237,7 @@ impl ReadonlyRepo {
             MergeOptions::from_settings(settings).map_err(|err| BackendInitError(err.into()))?;
         let store = Store::new(backend, signer, merge_options);
 
-        let op_store_path = repo_path.join("op_store");
+        let op_store_path = RepoLoader::op_store_path(&repo_path);
         fs::create_dir(&op_store_path).context(&op_store_path)?;
         let root_op_data = RootOperationData {
             root_commit_id: store.root_commit_id().clone(),
@@ -256,6 +272,8 @@ impl ReadonlyRepo {
             .context(&submodule_store_type_path)?;
         let submodule_store = Arc::from(submodule_store);
 
+        let workspace_store = Arc::new(SimpleWorkspaceStore::load(&repo_path)?);
+
         let loader = RepoLoader {
             settings: settings.clone(),
             store,
@@ -263,6 +281,7 @@ impl ReadonlyRepo {
             op_heads_store,
             index_store,
             submodule_store,
+            workspace_store,
         };
 
         let root_operation = loader.root_operation().await;
@@ -404,6 +423,17 @@ type IndexStoreFactory =
     Box<dyn Fn(&UserSettings, &Path) -> Result<Box<dyn IndexStore>, BackendLoadError>>;
 type SubmoduleStoreFactory =
     Box<dyn Fn(&UserSettings, &Path) -> Result<Box<dyn SubmoduleStore>, BackendLoadError>>;
+type WorkspaceStoreFactory =
+    Box<dyn Fn(&UserSettings, &Path) -> Result<Box<dyn WorkspaceStore>, BackendLoadError>>;
+
+fn op_heads_lock_retry_policy(settings: &UserSettings) -> Result<LockRetryPolicy, ConfigGetError> {
+    let timeout_ms: u64 = settings.get("operation.lock-timeout-ms")?;
+    let max_backoff_ms: u64 = settings.get("operation.lock-max-backoff-ms")?;
+    Ok(LockRetryPolicy {
+        timeout: Duration::from_millis(timeout_ms),
+        max_backoff: Duration::from_millis(max_backoff_ms),
+    })
+}
 
 pub fn merge_factories_map<F>(base: &mut HashMap<String, F>, ext: HashMap<String, F>) {
     for (name, factory) in ext {
@@ -424,6 +454,7 @@ pub struct StoreFactories {
     op_heads_store_factories: HashMap<String, OpHeadsStoreFactory>,
     index_store_factories: HashMap<String, IndexStoreFactory>,
     submodule_store_factories: HashMap<String, SubmoduleStoreFactory>,
+    workspace_store_factories: HashMap<String, WorkspaceStoreFactory>,
 }
 
 impl Default for StoreFactories {
@@ -433,7 +464,14 @@ impl Default for StoreFactories {
         // Backends
         factories.add_backend(
             SimpleBackend::name(),
-            Box::new(|_settings, store_path| Ok(Box::new(SimpleBackend::load(store_path)))),
+            Box::new(|settings, store_path| {
+                let fsync_policy = settings
+                    .get("store.fsync")
+                    .map_err(|err| BackendLoadError(err.into()))?;
+                Ok(Box::new(
+                    SimpleBackend::load(store_path).with_fsync_policy(fsync_policy),
+                ))
+            }),
         );
         #[cfg(feature = "git")]
         factories.add_backend(
@@ -465,7 +503,16 @@ impl Default for StoreFactories {
         // OpHeadsStores
         factories.add_op_heads_store(
             SimpleOpHeadsStore::name(),
-            Box::new(|_settings, store_path| Ok(Box::new(SimpleOpHeadsStore::load(store_path)))),
+            Box::new(|settings, store_path| {
+                let lock_policy = op_heads_lock_retry_policy(settings)
+                    .map_err(|err| BackendLoadError(err.into()))?;
+                Ok(Box::new(
+                    SimpleOpHeadsStore::load(store_path).with_lock_retry_policy(
+                        lock_policy,
+                        settings.operation_hostname().to_owned(),
+                    ),
+                ))
+            }),
         );
 
         // Index
@@ -480,6 +527,18 @@ impl Default for StoreFactories {
             Box::new(|_settings, store_path| Ok(Box::new(DefaultSubmoduleStore::load(store_path)))),
         );
 
+        // WorkspaceStores
+        factories.add_workspace_store(
+            SimpleWorkspaceStore::name(),
+            Box::new(|_settings, store_path| {
+                // The simple store is loaded from the repo directory.
+                let repo_path = store_path.parent().unwrap();
+                let store = SimpleWorkspaceStore::load(repo_path)
+                    .map_err(|err| BackendLoadError(err.into()))?;
+                Ok(Box::new(store))
+            }),
+        );
+
         factories
     }
 }
@@ -510,6 +569,7 @@ impl StoreFactories {
             op_heads_store_factories: HashMap::new(),
             index_store_factories: HashMap::new(),
             submodule_store_factories: HashMap::new(),
+            workspace_store_factories: HashMap::new(),
         }
     }
 
@@ -520,6 +580,7 @@ impl StoreFactories {
             op_heads_store_factories,
             index_store_factories,
             submodule_store_factories,
+            workspace_store_factories,
         } = ext;
 
         merge_factories_map(&mut self.backend_factories, backend_factories);
@@ -530,6 +591,10 @@ impl StoreFactories {
             &mut self.submodule_store_factories,
             submodule_store_factories,
         );
+        merge_factories_map(
+            &mut self.workspace_store_factories,
+            workspace_store_factories,
+        );
     }
 
     pub fn add_backend(&mut self, name: &str, factory: BackendFactory) {
@@ -633,6 +698,34 @@ impl StoreFactories {
 
         Ok(submodule_store_factory(settings, store_path)?)
     }
+
+    pub fn add_workspace_store(&mut self, name: &str, factory: WorkspaceStoreFactory) {
+        self.workspace_store_factories
+            .insert(name.to_string(), factory);
+    }
+
+    pub fn load_workspace_store(
+        &self,
+        settings: &UserSettings,
+        store_path: &Path,
+    ) -> Result<Box<dyn WorkspaceStore>, StoreLoadError> {
+        let type_path = store_path.join("type");
+        // Repos created before the workspace store type was recorded use the
+        // simple store.
+        let workspace_store_type = if type_path.exists() {
+            read_store_type("workspace", type_path)?
+        } else {
+            SimpleWorkspaceStore::name().to_owned()
+        };
+        let workspace_store_factory = self
+            .workspace_store_factories
+            .get(&workspace_store_type)
+            .ok_or_else(|| StoreLoadError::UnsupportedType {
+                store: "workspace",
+                store_type: workspace_store_type.clone(),
+            })?;
+        Ok(workspace_store_factory(settings, store_path)?)
+    }
 }
 
 pub fn read_store_type(
@@ -673,6 +766,7 @@ pub struct RepoLoader {
     op_heads_store: Arc<dyn OpHeadsStore>,
     index_store: Arc<dyn IndexStore>,
     submodule_store: Arc<dyn SubmoduleStore>,
+    workspace_store: Arc<dyn WorkspaceStore>,
 }
 
 impl RepoLoader {
@@ -683,6 +777,7 @@ impl RepoLoader {
         op_heads_store: Arc<dyn OpHeadsStore>,
         index_store: Arc<dyn IndexStore>,
         submodule_store: Arc<dyn SubmoduleStore>,
+        workspace_store: Arc<dyn WorkspaceStore>,
     ) -> Self {
         Self {
             settings,
@@ -691,6 +786,7 @@ impl RepoLoader {
             op_heads_store,
             index_store,
             submodule_store,
+            workspace_store,
         }
     }
 
@@ -705,7 +801,7 @@ impl RepoLoader {
         let merge_options =
             MergeOptions::from_settings(settings).map_err(|err| BackendLoadError(err.into()))?;
         let store = Store::new(
-            store_factories.load_backend(settings, &repo_path.join("store"))?,
+            store_factories.load_backend(settings, &Self::store_path(repo_path))?,
             Signer::from_settings(settings)?,
             merge_options,
         );
@@ -714,7 +810,7 @@ impl RepoLoader {
         };
         let op_store = Arc::from(store_factories.load_op_store(
             settings,
-            &repo_path.join("op_store"),
+            &Self::op_store_path(repo_path),
             root_op_data,
         )?);
         let op_heads_store =
@@ -724,6 +820,9 @@ impl RepoLoader {
         let submodule_store = Arc::from(
             store_factories.load_submodule_store(settings, &repo_path.join("submodule_store"))?,
         );
+        let workspace_store = Arc::from(
+            store_factories.load_workspace_store(settings, &repo_path.join("workspace_store"))?,
+        );
         Ok(Self {
             settings: settings.clone(),
             store,
@@ -731,9 +830,22 @@ impl RepoLoader {
             op_heads_store,
             index_store,
             submodule_store,
+            workspace_store,
         })
     }
 
+    /// Returns the directory where the commit store of the repo at
+    /// `repo_path` lives.
+    pub fn store_path(repo_path: &Path) -> PathBuf {
+        repo_path.join("store")
+    }
+
+    /// Returns the directory where the operation store of the repo at
+    /// `repo_path` lives.
+    pub fn op_store_path(repo_path: &Path) -> PathBuf {
+        repo_path.join("op_store")
+    }
+
     pub fn settings(&self) -> &UserSettings {
         &self.settings
     }
@@ -758,6 +870,10 @@ impl RepoLoader {
         &self.submodule_store
     }
 
+    pub fn workspace_store(&self) -> &Arc<dyn WorkspaceStore> {
+        &self.workspace_store
+    }
+
     pub async fn load_at_head(&self) -> Result<Arc<ReadonlyRepo>, RepoLoaderError> {
         let op = op_heads_store::resolve_op_heads(
             self.op_heads_store.as_ref(),
@@ -972,6 +1088,15 @@ impl MutableRepo {
         // `self.rewritten_commits`
     }
 
+    /// Returns the commits written in this transaction, mapped to their
+    /// predecessors.
+    ///
+    /// Unlike the rewrite records consumed by `rebase_descendants()`, this
+    /// mapping is kept until the transaction is committed.
+    pub fn commit_predecessors(&self) -> &BTreeMap<CommitId, Vec<CommitId>> {
+        &self.commit_predecessors
+    }
+
     pub(crate) fn set_predecessors(&mut self, id: CommitId, predecessors: Vec<CommitId>) {
         self.commit_predecessors.insert(id, predecessors);
     }
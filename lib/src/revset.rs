@@ -158,6 +158,8 @@ pub enum RevsetCommitRef {
     },
     GitRefs,
     GitHead,
+    /// Heads of all indexed commits, including hidden ones.
+    IndexedHeads,
 }
 
 /// String expressions to match `name@remote` bookmarks/tags.
@@ -454,6 +456,14 @@ impl<St: ExpressionState<CommitRef = RevsetCommitRef>> RevsetExpression<St> {
     pub fn git_head() -> Arc<Self> {
         Arc::new(Self::CommitRef(RevsetCommitRef::GitHead))
     }
+
+    /// Indexed commits which aren't reachable from the visible heads.
+    pub fn hidden() -> Arc<Self> {
+        let indexed_heads = Arc::new(Self::CommitRef(RevsetCommitRef::IndexedHeads));
+        indexed_heads
+            .ancestors()
+            .minus(&Self::visible_heads().ancestors())
+    }
 }
 
 // Compound expression
@@ -889,6 +899,10 @@ static BUILTIN_FUNCTION_MAP: LazyLock<HashMap<&str, RevsetFunction>> = LazyLock:
         function.expect_no_arguments()?;
         Ok(RevsetExpression::visible_heads())
     });
+    map.insert("hidden", |_diagnostics, function, _context| {
+        function.expect_no_arguments()?;
+        Ok(RevsetExpression::hidden())
+    });
     map.insert("root", |_diagnostics, function, _context| {
         function.expect_no_arguments()?;
         Ok(RevsetExpression::root())
@@ -1153,17 +1167,12 @@ static BUILTIN_FUNCTION_MAP: LazyLock<HashMap<&str, RevsetFunction>> = LazyLock:
         let expression = lower_expression(diagnostics, arg, context)?;
         Ok(expression.present())
     });
-    map.insert("at_operation", |diagnostics, function, context| {
-        let [op_arg, cand_arg] = function.expect_exact_arguments()?;
-        // TODO: Parse "opset" here if we add proper language support.
-        let operation = revset_parser::catch_aliases(diagnostics, op_arg, |_diagnostics, node| {
-            Ok(node.span.as_str().to_owned())
-        })?;
-        let candidates = lower_expression(diagnostics, cand_arg, context)?;
-        Ok(Arc::new(RevsetExpression::AtOperation {
-            operation,
-            candidates,
-        }))
+    map.insert("at_operation", lower_at_operation);
+    map.insert("visible_at_operation", |diagnostics, function, context| {
+        // Unlike at_operation(), commits which are hidden in the current repo
+        // aren't brought into the search space.
+        let expression = lower_at_operation(diagnostics, function, context)?;
+        Ok(expression.intersection(&RevsetExpression::visible_heads().ancestors()))
     });
     map.insert("coalesce", |diagnostics, function, context| {
         let ([], args) = function.expect_some_arguments()?;
@@ -1176,6 +1185,23 @@ static BUILTIN_FUNCTION_MAP: LazyLock<HashMap<&str, RevsetFunction>> = LazyLock:
     map
 });
 
+fn lower_at_operation(
+    diagnostics: &mut RevsetDiagnostics,
+    function: &FunctionCallNode,
+    context: &LoweringContext,
+) -> Result<Arc<UserRevsetExpression>, RevsetParseError> {
+    let [op_arg, cand_arg] = function.expect_exact_arguments()?;
+    // TODO: Parse "opset" here if we add proper language support.
+    let operation = revset_parser::catch_aliases(diagnostics, op_arg, |_diagnostics, node| {
+        Ok(node.span.as_str().to_owned())
+    })?;
+    let candidates = lower_expression(diagnostics, cand_arg, context)?;
+    Ok(Arc::new(RevsetExpression::AtOperation {
+        operation,
+        candidates,
+    }))
+}
+
 /// Parses the given `node` as a fileset expression.
 pub fn expect_fileset_expression(
     diagnostics: &mut RevsetDiagnostics,
@@ -2814,6 +2840,13 @@ impl PartialSymbolResolver for ChangePrefixResolver<'_> {
             return Ok(targets.at_offset(offset).cloned());
         }
         match targets.visible_with_offsets().at_most_one() {
+            Ok(None) if self.context.is_some_and(|ctx| ctx.includes_hidden()) => {
+                // Hidden commit is resolved only if it's the sole target.
+                match targets.targets.iter().exactly_one() {
+                    Ok((target, _)) => Ok(Some(target.clone())),
+                    Err(_) => Ok(None),
+                }
+            }
             Ok(maybe_resolved) => Ok(maybe_resolved.map(|(_, target)| target.clone())),
             Err(visible_targets) => Err(RevsetResolutionError::DivergentChangeId {
                 symbol: change_id.to_owned(),
@@ -3005,6 +3038,14 @@ fn resolve_commit_ref(
             Ok(commit_ids)
         }
         RevsetCommitRef::GitHead => Ok(repo.view().git_head().added_ids().cloned().collect()),
+        RevsetCommitRef::IndexedHeads => {
+            let commit_ids = repo
+                .index()
+                .all_heads_for_gc()
+                .map_err(|err| RevsetResolutionError::Other(err.into()))?
+                .collect();
+            Ok(commit_ids)
+        }
     }
 }
 
@@ -3395,6 +3436,41 @@ pub trait Revset: fmt::Debug {
     where
         Self: 'a;
 
+    /// Like [`Revset::iter()`], but yields a cursor along with each commit.
+    ///
+    /// If `after` is specified, iteration starts right after the commit the
+    /// cursor was yielded with.
+    ///
+    /// The default implementation uses the number of preceding commits as the
+    /// cursor, so resuming has to skip over them.
+    fn iter_with_cursors<'a>(
+        &self,
+        after: Option<RevsetCursor>,
+    ) -> Box<dyn Iterator<Item = Result<(RevsetCursor, CommitId), RevsetEvaluationError>> + 'a>
+    where
+        Self: 'a,
+    {
+        Box::new(with_ordinal_cursors(self.iter(), after))
+    }
+
+    /// Like [`Revset::iter_graph()`], but yields a cursor along with each node.
+    ///
+    /// If `after` is specified, iteration starts right after the node the
+    /// cursor was yielded with. The remaining nodes have the same edges as
+    /// they would have had without the cursor.
+    ///
+    /// The default implementation uses the number of preceding nodes as the
+    /// cursor, so resuming has to skip over them.
+    fn iter_graph_with_cursors<'a>(
+        &self,
+        after: Option<RevsetCursor>,
+    ) -> Box<dyn Iterator<Item = Result<CursorGraphNode, RevsetEvaluationError>> + 'a>
+    where
+        Self: 'a,
+    {
+        Box::new(with_ordinal_cursors(self.iter_graph(), after))
+    }
+
     /// Returns true if iterator will emit no commit nor error.
     fn is_empty(&self) -> bool;
 
@@ -3450,6 +3526,76 @@ impl<I: Iterator<Item = Result<CommitId, RevsetEvaluationError>>> Iterator
     }
 }
 
+/// Position in the iteration order of a [`Revset`], used to resume iteration.
+///
+/// Cursors are only valid for revsets evaluated against the same repo (i.e.
+/// at the same operation) as the one they were obtained from. Clients that
+/// fetch a revset in pages should keep the repo pinned until they are done.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct RevsetCursor(u64);
+
+impl RevsetCursor {
+    /// Creates a cursor from the value previously returned by
+    /// [`RevsetCursor::value()`].
+    pub fn new(value: u64) -> Self {
+        Self(value)
+    }
+
+    /// Returns the value of this cursor, which can be stored by clients.
+    pub fn value(self) -> u64 {
+        self.0
+    }
+}
+
+/// Graph node yielded by [`Revset::iter_graph_with_cursors()`].
+pub type CursorGraphNode = (RevsetCursor, GraphNode<CommitId>);
+
+/// Pairs the items of `iter` with their ordinals as cursors, starting after
+/// the `after` cursor.
+fn with_ordinal_cursors<T>(
+    iter: impl Iterator<Item = Result<T, RevsetEvaluationError>>,
+    after: Option<RevsetCursor>,
+) -> impl Iterator<Item = Result<(RevsetCursor, T), RevsetEvaluationError>> {
+    let skip = after.map_or(0, |cursor| {
+        usize::try_from(cursor.value()).map_or(usize::MAX, |n| n.saturating_add(1))
+    });
+    iter.enumerate()
+        .skip(skip)
+        .map(|(n, item)| Ok((RevsetCursor::new(n.try_into().unwrap()), item?)))
+}
+
+/// Items fetched from an iterator returned by
+/// [`Revset::iter_with_cursors()`] or [`Revset::iter_graph_with_cursors()`].
+#[derive(Clone, Debug)]
+pub struct RevsetPage<T> {
+    pub items: Vec<T>,
+    /// Cursor to fetch the next page with. `None` if the iterator was
+    /// exhausted before the page was filled.
+    pub next_cursor: Option<RevsetCursor>,
+}
+
+impl<T> RevsetPage<T> {
+    /// Takes up to `size` items from `iter`.
+    pub fn take_from(
+        iter: &mut impl Iterator<Item = Result<(RevsetCursor, T), RevsetEvaluationError>>,
+        size: usize,
+    ) -> Result<Self, RevsetEvaluationError> {
+        let mut items = Vec::with_capacity(size);
+        let mut last_cursor = None;
+        for item in iter.take(size) {
+            let (cursor, item) = item?;
+            items.push(item);
+            last_cursor = Some(cursor);
+        }
+        let next_cursor = if items.len() == size {
+            last_cursor
+        } else {
+            None
+        };
+        Ok(Self { items, next_cursor })
+    }
+}
+
 /// A set of extensions for revset evaluation.
 pub struct RevsetExtensions {
     symbol_resolvers: Vec<Box<dyn SymbolResolverExtension>>,
@@ -1931,6 +1931,49 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_expand_builtin_function_alias() {
+        let mut aliases = with_aliases([("F(x)", "x|a"), ("G()", "F(b)")]);
+        aliases.aliases_map.insert_builtin_copies();
+        aliases
+            .aliases_map
+            .insert("F(x)", "builtin::F(x)&c")
+            .unwrap();
+
+        // builtin::F() refers to the definition before override.
+        assert_eq!(
+            aliases.parse_normalized("F(y)"),
+            parse_normalized("(y|a)&c")
+        );
+        // Aliases referred to from the builtin definition aren't resolved to
+        // the builtin ones.
+        assert_eq!(
+            aliases.parse_normalized("builtin::G()"),
+            parse_normalized("(b|a)&c")
+        );
+
+        // "builtin::" is parsed as a part of function name, not as a range.
+        assert_matches!(
+            parse_into_kind("builtin::f()"),
+            Ok(ExpressionKind::FunctionCall(function)) if function.name == "builtin::f"
+        );
+        assert_matches!(
+            parse_into_kind("builtin::(f())"),
+            Ok(ExpressionKind::Binary(BinaryOp::DagRange, _, _))
+        );
+        assert_matches!(
+            parse_into_kind("foo::f()"),
+            Ok(ExpressionKind::Binary(BinaryOp::DagRange, _, _))
+        );
+
+        // Builtin aliases can't be declared.
+        assert!(
+            RevsetAliasesMap::new()
+                .insert("builtin::F(x)", "x")
+                .is_err()
+        );
+    }
+
     #[test]
     fn test_expand_with_locals() {
         // Local variable should precede the symbol alias.
@@ -16,10 +16,15 @@
 
 use std::cell::RefCell;
 use std::fs;
+use std::io;
 use std::io::ErrorKind::NotFound;
 use std::io::Write as _;
 use std::path::Path;
 use std::path::PathBuf;
+use std::process::Command;
+use std::process::ExitStatus;
+use std::process::Stdio;
+use std::thread;
 
 use prost::Message as _;
 use rand::RngExt as _;
@@ -27,6 +32,7 @@ use rand_chacha::ChaCha20Rng;
 use tempfile::NamedTempFile;
 use thiserror::Error;
 
+use crate::config::ConfigFileEncoder;
 use crate::file_util::BadPathEncoding;
 use crate::file_util::IoResultExt as _;
 use crate::file_util::PathError;
@@ -36,6 +42,8 @@ use crate::hex_util::encode_hex;
 use crate::protos::secure_config::ConfigMetadata;
 
 const CONFIG_FILE: &str = "config.toml";
+const ENCRYPTED_CONFIG_FILE: &str = "config.toml.age";
+const AGE_PROGRAM: &str = "age";
 const METADATA_FILE: &str = "metadata.binpb";
 const CONFIG_ID_BYTES: usize = 10;
 #[cfg(not(unix))]
@@ -80,6 +88,26 @@ pub enum SecureConfigError {
     /// The config ID isn't CONFIG_ID_BYTES * 2 hex chars.
     #[error("Found an invalid config ID")]
     BadConfigIdError,
+
+    /// Failed to encrypt or decrypt the config file.
+    #[error(transparent)]
+    AgeError(#[from] AgeError),
+}
+
+/// An error when running `age` to encrypt or decrypt a config file.
+#[derive(Error, Debug)]
+pub enum AgeError {
+    /// `age` exited with an error.
+    #[error("age failed with {exit_status}:\n{stderr}")]
+    Command {
+        /// The exit status of the command.
+        exit_status: ExitStatus,
+        /// The error output of the command.
+        stderr: String,
+    },
+    /// Failed to run `age`.
+    #[error("Failed to run age")]
+    Io(#[from] io::Error),
 }
 
 /// The path to the config file for a secure config.
@@ -96,6 +124,117 @@ pub struct LoadedSecureConfig {
     pub warnings: Vec<String>,
 }
 
+impl LoadedSecureConfig {
+    /// Returns the encryption of the config file, or `None` if it's stored in
+    /// plain text.
+    pub fn encryption(&self) -> Result<Option<AgeEncryption>, SecureConfigError> {
+        let identity = self
+            .metadata
+            .age_identity
+            .as_deref()
+            .map(path_from_bytes)
+            .transpose()?;
+        Ok(identity.map(|identity| AgeEncryption::new(identity.to_owned())))
+    }
+}
+
+/// Encryption of a config file with the `age` command.
+///
+/// The file is encrypted to the recipient of the identity file, so an identity
+/// of an age plugin (e.g. one backed by a hardware key or the OS keychain) can
+/// be used as well.
+#[derive(Clone, Debug)]
+pub struct AgeEncryption {
+    identity_file: PathBuf,
+}
+
+impl AgeEncryption {
+    /// Creates an encryption with the given age identity file.
+    pub fn new(identity_file: PathBuf) -> Self {
+        Self { identity_file }
+    }
+
+    /// Returns the path to the age identity file.
+    pub fn identity_file(&self) -> &Path {
+        &self.identity_file
+    }
+
+    /// Reads and decrypts the file at `path`.
+    pub fn decrypt_file(&self, path: &Path) -> Result<Vec<u8>, SecureConfigError> {
+        // Check the file up front so that a missing file is reported as such.
+        fs::metadata(path).context(path)?;
+        let mut command = Command::new(AGE_PROGRAM);
+        command
+            .arg("--decrypt")
+            .arg("--identity")
+            .arg(&self.identity_file)
+            .arg(path);
+        Ok(run_age_command(&mut command, &[])?)
+    }
+
+    /// Encrypts `content` and writes it to the file at `path`.
+    pub fn encrypt_to_file(&self, path: &Path, content: &[u8]) -> Result<(), SecureConfigError> {
+        let mut command = Command::new(AGE_PROGRAM);
+        command
+            .arg("--encrypt")
+            .arg("--identity")
+            .arg(&self.identity_file);
+        let encrypted = run_age_command(&mut command, content)?;
+        atomic_write(path, &encrypted)
+    }
+}
+
+impl ConfigFileEncoder for AgeEncryption {
+    fn write_file(&self, path: &Path, text: &str) -> Result<(), PathError> {
+        self.encrypt_to_file(path, text.as_bytes())
+            .map_err(|err| match err {
+                SecureConfigError::PathError(err) => err,
+                err => PathError {
+                    path: path.to_owned(),
+                    source: io::Error::other(err),
+                },
+            })
+    }
+}
+
+fn run_age_command(command: &mut Command, input: &[u8]) -> Result<Vec<u8>, AgeError> {
+    tracing::info!(?command, "running age command");
+    let mut process = command
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()?;
+    let mut stdin = process.stdin.take().unwrap();
+    // Write the input from another thread so the pipes can't fill up while
+    // the output isn't being read.
+    let (write_result, output) = thread::scope(|s| {
+        let writer = s.spawn(move || stdin.write_all(input));
+        let output = process.wait_with_output();
+        (writer.join().unwrap(), output)
+    });
+    let output = output?;
+    tracing::info!(?command, ?output.status, "age command exited");
+    if output.status.success() {
+        write_result?;
+        Ok(output.stdout)
+    } else {
+        Err(AgeError::Command {
+            exit_status: output.status,
+            stderr: String::from_utf8_lossy(&output.stderr).trim_end().into(),
+        })
+    }
+}
+
+/// Returns the path to the config file in `config_dir`, which depends on
+/// whether the file is encrypted.
+fn config_file_path(config_dir: &Path, metadata: &ConfigMetadata) -> PathBuf {
+    if metadata.age_identity.is_some() {
+        config_dir.join(ENCRYPTED_CONFIG_FILE)
+    } else {
+        config_dir.join(CONFIG_FILE)
+    }
+}
+
 fn atomic_write(path: &Path, content: &[u8]) -> Result<(), SecureConfigError> {
     let d = path.parent().unwrap();
     let mut temp_file = NamedTempFile::new_in(d).context(d)?;
@@ -146,6 +285,11 @@ impl SecureConfig {
         )
     }
 
+    /// Returns the path to the file that stores the config ID.
+    pub fn config_id_path(&self) -> PathBuf {
+        self.repo_dir.join(self.config_id_name)
+    }
+
     fn generate_config(
         &self,
         root_config_dir: &Path,
@@ -154,7 +298,7 @@ impl SecureConfig {
         metadata: &ConfigMetadata,
     ) -> Result<PathBuf, SecureConfigError> {
         let config_dir = root_config_dir.join(config_id);
-        let config_path = config_dir.join(CONFIG_FILE);
+        let config_path = config_file_path(&config_dir, metadata);
         fs::create_dir_all(&config_dir).context(&config_dir)?;
         update_metadata(&config_dir, metadata)?;
         if let Some(content) = content {
@@ -176,6 +320,7 @@ impl SecureConfig {
     ) -> Result<(PathBuf, ConfigMetadata), SecureConfigError> {
         let metadata = ConfigMetadata {
             path: path_to_bytes(&self.repo_dir).ok().map(|b| b.to_vec()),
+            age_identity: None,
         };
         let path = self.generate_config(root_config_dir, config_id, None, &metadata)?;
         Ok((path, metadata))
@@ -196,7 +341,7 @@ impl SecureConfig {
 
         if got == encoded.is_some().then_some(self.repo_dir.as_path()) {
             return Ok(LoadedSecureConfig {
-                config_file: Some(config_dir.join(CONFIG_FILE)),
+                config_file: Some(config_file_path(&config_dir, &metadata)),
                 metadata,
                 warnings: vec![],
             });
@@ -208,7 +353,7 @@ impl SecureConfig {
                 metadata.path = encoded.map(|b| b.to_vec());
                 update_metadata(&config_dir, &metadata)?;
                 return Ok(LoadedSecureConfig {
-                    config_file: Some(config_dir.join(CONFIG_FILE)),
+                    config_file: Some(config_file_path(&config_dir, &metadata)),
                     metadata,
                     warnings: vec![],
                 });
@@ -224,7 +369,7 @@ impl SecureConfig {
             // We now assume the repo was copied. Since the repo was copied,
             // the config should be copied too, rather than sharing the
             // config with what it copied from.
-            let old_config_path = config_dir.join(CONFIG_FILE);
+            let old_config_path = config_file_path(&config_dir, &metadata);
             metadata.path = encoded.map(|b| b.to_vec());
             let old_config_content = fs::read(&old_config_path).context(&old_config_path)?;
             let config_path = self.generate_config(
@@ -245,7 +390,7 @@ impl SecureConfig {
             });
         }
         Ok(LoadedSecureConfig {
-            config_file: Some(config_dir.join(CONFIG_FILE)),
+            config_file: Some(config_file_path(&config_dir, &metadata)),
             metadata,
             warnings: vec![],
         })
@@ -300,6 +445,7 @@ impl SecureConfig {
         };
         let metadata = ConfigMetadata {
             path: path_to_bytes(&self.repo_dir).ok().map(|b| b.to_vec()),
+            age_identity: None,
         };
         let config_file = self.generate_config(
             root_config_dir,
@@ -389,6 +535,136 @@ impl SecureConfig {
         }
         Ok(loaded)
     }
+
+    /// Generates a new config file with the given `content`, and replaces the
+    /// config ID if any. This is meant for a newly-created repo or workspace,
+    /// whose config shouldn't be shared with an existing one.
+    pub fn init_config(
+        &self,
+        rng: &mut ChaCha20Rng,
+        root_config_dir: &Path,
+        content: &[u8],
+    ) -> Result<LoadedSecureConfig, SecureConfigError> {
+        let metadata = ConfigMetadata {
+            path: path_to_bytes(&self.repo_dir).ok().map(|b| b.to_vec()),
+            age_identity: None,
+        };
+        let config_path = self.generate_config(
+            root_config_dir,
+            &generate_config_id(rng),
+            Some(content),
+            &metadata,
+        )?;
+        *self.cache.borrow_mut() = Some((Some(config_path.clone()), metadata.clone()));
+        Ok(LoadedSecureConfig {
+            config_file: Some(config_path),
+            metadata,
+            warnings: vec![],
+        })
+    }
+
+    /// Encrypts the config file with the given age identity file, or decrypts
+    /// it if `age_identity` is `None`. If the file is already encrypted, it's
+    /// re-encrypted with the new identity.
+    ///
+    /// The config is generated if it doesn't exist.
+    pub fn set_encryption(
+        &self,
+        rng: &mut ChaCha20Rng,
+        root_config_dir: &Path,
+        age_identity: Option<&Path>,
+    ) -> Result<LoadedSecureConfig, SecureConfigError> {
+        let loaded = self.load_config(rng, root_config_dir)?;
+        let old_path = loaded.config_file.clone().unwrap();
+        let config_dir = old_path.parent().unwrap();
+        let content = match loaded.encryption()? {
+            Some(encryption) => encryption.decrypt_file(&old_path),
+            None => fs::read(&old_path).context(&old_path).map_err(Into::into),
+        };
+        let content = match content {
+            Ok(content) => Some(content),
+            Err(SecureConfigError::PathError(e)) if e.source.kind() == NotFound => None,
+            Err(e) => return Err(e),
+        };
+
+        let mut metadata = loaded.metadata;
+        metadata.age_identity = age_identity
+            .map(path_to_bytes)
+            .transpose()?
+            .map(|b| b.to_vec());
+        let new_path = config_file_path(config_dir, &metadata);
+        let new_loaded = LoadedSecureConfig {
+            config_file: Some(new_path.clone()),
+            metadata,
+            warnings: vec![],
+        };
+        if let Some(content) = content {
+            match new_loaded.encryption()? {
+                Some(encryption) => encryption.encrypt_to_file(&new_path, &content)?,
+                None => atomic_write(&new_path, &content)?,
+            }
+        }
+        update_metadata(config_dir, &new_loaded.metadata)?;
+        if new_path != old_path {
+            match fs::remove_file(&old_path).context(&old_path) {
+                Ok(()) => {}
+                Err(e) if e.source.kind() == NotFound => {}
+                Err(e) => return Err(e.into()),
+            }
+        }
+        *self.cache.borrow_mut() =
+            Some((new_loaded.config_file.clone(), new_loaded.metadata.clone()));
+        Ok(new_loaded)
+    }
+}
+
+/// Removes the config directories in `root_config_dir` which are no longer
+/// used.
+///
+/// A config directory is removed if the repo it was created for no longer
+/// exists and its config file is missing or empty. Non-empty configs are kept
+/// since the repo may have been moved, in which case the config is picked up
+/// again the next time the repo is loaded.
+///
+/// Returns the removed directories.
+pub fn gc_stale_configs(root_config_dir: &Path) -> Result<Vec<PathBuf>, SecureConfigError> {
+    let entries = match fs::read_dir(root_config_dir).context(root_config_dir) {
+        Ok(entries) => entries,
+        Err(e) if e.source.kind() == NotFound => return Ok(vec![]),
+        Err(e) => return Err(e.into()),
+    };
+    let mut removed = vec![];
+    for entry in entries {
+        let config_dir = entry.context(root_config_dir)?.path();
+        let metadata_path = config_dir.join(METADATA_FILE);
+        let metadata = match fs::read(&metadata_path).context(&metadata_path) {
+            Ok(buf) => ConfigMetadata::decode(buf.as_slice())?,
+            // Not a config directory, or one being generated.
+            Err(e) if e.source.kind() == NotFound => continue,
+            Err(e) => return Err(e.into()),
+        };
+        let Some(repo_dir) = metadata.path.as_deref().map(path_from_bytes).transpose()? else {
+            continue;
+        };
+        if repo_dir.exists() {
+            continue;
+        }
+        // Encrypted configs are kept even if empty, since we can't tell
+        // without decrypting them.
+        if metadata.age_identity.is_some() {
+            continue;
+        }
+        let config_path = config_dir.join(CONFIG_FILE);
+        match fs::read(&config_path).context(&config_path) {
+            Ok(content) if content.trim_ascii().is_empty() => {}
+            Ok(_) => continue,
+            Err(e) if e.source.kind() == NotFound => {}
+            Err(e) => return Err(e.into()),
+        }
+        fs::remove_dir_all(&config_dir).context(&config_dir)?;
+        removed.push(config_dir);
+    }
+    Ok(removed)
 }
 
 #[cfg(test)]
@@ -584,4 +860,151 @@ mod tests {
         assert!(path.parent().unwrap().is_dir());
         assert!(!loaded2.warnings.is_empty());
     }
+
+    #[test]
+    fn test_gc_stale_configs() {
+        let mut env = TestEnv::new();
+        let loaded = env
+            .config
+            .load_config(&mut env.rng, &env.config_dir)
+            .unwrap();
+        let path = loaded.config_file.unwrap();
+
+        let other_dir = env.repo_dir.parent().unwrap().join("other");
+        fs::create_dir(&other_dir).unwrap();
+        let other_config = env.secure_config_for_dir(other_dir.clone());
+        let other_path = other_config
+            .load_config(&mut env.rng, &env.config_dir)
+            .unwrap()
+            .config_file
+            .unwrap();
+        fs::write(&other_path, "config").unwrap();
+
+        // Configs of existing repos are kept.
+        assert!(gc_stale_configs(&env.config_dir).unwrap().is_empty());
+
+        // Empty configs of removed repos are removed, but non-empty ones are kept.
+        fs::remove_dir_all(&env.repo_dir).unwrap();
+        fs::remove_dir_all(&other_dir).unwrap();
+        assert_eq!(
+            gc_stale_configs(&env.config_dir).unwrap(),
+            vec![path.parent().unwrap().to_owned()]
+        );
+        assert!(!path.parent().unwrap().exists());
+        assert!(other_path.exists());
+
+        // Missing root config dir is fine.
+        assert!(
+            gc_stale_configs(&env.config_dir.join("missing"))
+                .unwrap()
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn test_encryption() {
+        if Command::new("age-keygen")
+            .arg("--version")
+            .output()
+            .is_err()
+        {
+            eprintln!("Skipping test because age is not installed on the system");
+            return;
+        }
+        let mut env = TestEnv::new();
+        let identity = env.repo_dir.parent().unwrap().join("key.txt");
+        let status = Command::new("age-keygen")
+            .arg("--output")
+            .arg(&identity)
+            .stderr(Stdio::null())
+            .status()
+            .unwrap();
+        assert!(status.success());
+        let loaded = env
+            .config
+            .load_config(&mut env.rng, &env.config_dir)
+            .unwrap();
+        let path = loaded.config_file.unwrap();
+        fs::write(&path, "a = 1").unwrap();
+
+        // The plain text file is replaced with the encrypted one.
+        let loaded = env
+            .config
+            .set_encryption(&mut env.rng, &env.config_dir, Some(&identity))
+            .unwrap();
+        let encrypted_path = loaded.config_file.clone().unwrap();
+        assert_eq!(encrypted_path, path.with_file_name("config.toml.age"));
+        assert!(!path.exists());
+        assert_ne!(fs::read(&encrypted_path).unwrap(), b"a = 1");
+        let encryption = loaded.encryption().unwrap().unwrap();
+        assert_eq!(encryption.identity_file(), identity);
+        assert_eq!(encryption.decrypt_file(&encrypted_path).unwrap(), b"a = 1");
+
+        // The encryption is recorded in the metadata.
+        *env.config.cache.borrow_mut() = None;
+        let loaded = env
+            .config
+            .maybe_load_config(&mut env.rng, &env.config_dir)
+            .unwrap();
+        assert_eq!(loaded.config_file.as_ref(), Some(&encrypted_path));
+        assert!(loaded.encryption().unwrap().is_some());
+
+        // Encrypted configs of removed repos are kept.
+        let moved_dir = env.repo_dir.with_file_name("moved");
+        fs::rename(&env.repo_dir, &moved_dir).unwrap();
+        assert!(gc_stale_configs(&env.config_dir).unwrap().is_empty());
+        fs::rename(&moved_dir, &env.repo_dir).unwrap();
+
+        let loaded = env
+            .config
+            .set_encryption(&mut env.rng, &env.config_dir, None)
+            .unwrap();
+        assert_eq!(loaded.config_file.as_ref(), Some(&path));
+        assert!(!encrypted_path.exists());
+        assert_eq!(fs::read_to_string(&path).unwrap(), "a = 1");
+        assert!(loaded.encryption().unwrap().is_none());
+    }
+
+    #[test]
+    fn test_init_config() {
+        let mut env = TestEnv::new();
+        let loaded = env
+            .config
+            .load_config(&mut env.rng, &env.config_dir)
+            .unwrap();
+        let old_path = loaded.config_file.unwrap();
+
+        let loaded = env
+            .config
+            .init_config(&mut env.rng, &env.config_dir, b"a = 1")
+            .unwrap();
+        let path = loaded.config_file.unwrap();
+        assert_ne!(path, old_path);
+        assert_eq!(fs::read_to_string(&path).unwrap(), "a = 1");
+        assert!(loaded.warnings.is_empty());
+
+        // The new config ID should be persisted.
+        let config = env.secure_config_for_dir(env.repo_dir.clone());
+        let loaded2 = config.load_config(&mut env.rng, &env.config_dir).unwrap();
+        assert_eq!(loaded2.config_file.unwrap(), path);
+        assert_eq!(loaded2.metadata, loaded.metadata);
+    }
+
+    #[test]
+    fn test_set_encryption_without_config_file() {
+        let mut env = TestEnv::new();
+        let identity = env.repo_dir.parent().unwrap().join("key.txt");
+        // The config file doesn't exist, so age doesn't have to be run.
+        let loaded = env
+            .config
+            .set_encryption(&mut env.rng, &env.config_dir, Some(&identity))
+            .unwrap();
+        let path = loaded.config_file.unwrap();
+        assert_eq!(path.file_name().unwrap(), "config.toml.age");
+        assert!(!path.exists());
+        assert_eq!(
+            loaded.metadata.age_identity.as_deref(),
+            Some(path_to_bytes(&identity).unwrap())
+        );
+    }
 }
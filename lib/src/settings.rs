@@ -56,6 +56,22 @@ struct UserSettingsData {
     operation_username: String,
     signing_behavior: SignBehavior,
     signing_key: Option<String>,
+    identity: Option<String>,
+}
+
+/// Named identity, which can be selected by `user.identity`.
+#[derive(Debug, Clone, Default, serde::Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct IdentitySettings {
+    /// Overrides `user.name`.
+    #[serde(default)]
+    pub name: Option<String>,
+    /// Overrides `user.email`.
+    #[serde(default)]
+    pub email: Option<String>,
+    /// Overrides `signing.key`.
+    #[serde(default)]
+    pub signing_key: Option<String>,
 }
 
 pub type RemoteSettingsMap = HashMap<RemoteNameBuf, RemoteSettings>;
@@ -70,6 +86,12 @@ pub struct RemoteSettings {
     /// automatically.
     #[serde(default)]
     pub auto_track_created_bookmarks: Option<String>,
+    /// Additional `<src>:<dst>` refspecs to fetch from the remote.
+    #[serde(default)]
+    pub fetch: Vec<String>,
+    /// `<src>:<dst>` refspecs to map pushed bookmarks to refs on the remote.
+    #[serde(default)]
+    pub push: Vec<String>,
 }
 
 impl RemoteSettings {
@@ -132,8 +154,19 @@ impl UserSettings {
     }
 
     fn from_config_and_rng(config: StackedConfig, rng: Arc<JJRng>) -> Result<Self, ConfigGetError> {
-        let user_name = config.get("user.name")?;
-        let user_email = config.get("user.email")?;
+        let identity = config.get::<String>("user.identity").optional()?;
+        let identity_settings: IdentitySettings = match &identity {
+            Some(name) => config.get(["identities", name.as_str()])?,
+            None => IdentitySettings::default(),
+        };
+        let user_name = match identity_settings.name {
+            Some(name) => name,
+            None => config.get("user.name")?,
+        };
+        let user_email = match identity_settings.email {
+            Some(email) => email,
+            None => config.get("user.email")?,
+        };
         let commit_timestamp = config
             .get_value_with("debug.commit-timestamp", to_timestamp)
             .optional()?;
@@ -143,7 +176,10 @@ impl UserSettings {
         let operation_hostname = config.get("operation.hostname")?;
         let operation_username = config.get("operation.username")?;
         let signing_behavior = config.get("signing.behavior")?;
-        let signing_key = config.get("signing.key").optional()?;
+        let signing_key = match identity_settings.signing_key {
+            Some(key) => Some(key),
+            None => config.get("signing.key").optional()?,
+        };
         let data = UserSettingsData {
             user_name,
             user_email,
@@ -153,6 +189,7 @@ impl UserSettings {
             operation_username,
             signing_behavior,
             signing_key,
+            identity,
         };
         Ok(Self {
             config: Arc::new(config),
@@ -181,6 +218,11 @@ impl UserSettings {
         &self.data.user_email
     }
 
+    /// Name of the identity selected by `user.identity`, if any.
+    pub fn identity(&self) -> Option<&str> {
+        self.data.identity.as_deref()
+    }
+
     pub fn commit_timestamp(&self) -> Option<Timestamp> {
         self.data.commit_timestamp
     }
@@ -382,6 +424,50 @@ mod tests {
     use assert_matches::assert_matches;
 
     use super::*;
+    use crate::config::ConfigLayer;
+    use crate::config::ConfigSource;
+
+    fn settings_from_text(text: &str) -> Result<UserSettings, ConfigGetError> {
+        let mut config = StackedConfig::with_defaults();
+        config.add_layer(ConfigLayer::parse(ConfigSource::User, text).unwrap());
+        UserSettings::from_config(config)
+    }
+
+    #[test]
+    fn identity_overrides_user_and_signing_key() {
+        let text = r#"
+            user.name = "Default"
+            user.email = "default@example.com"
+            signing.key = "default-key"
+            identities.work.name = "Worker"
+            identities.work.email = "worker@example.com"
+            identities.work.signing-key = "work-key"
+            identities.oss.email = "oss@example.com"
+        "#;
+        let settings = settings_from_text(text).unwrap();
+        assert_eq!(settings.identity(), None);
+        assert_eq!(settings.user_name(), "Default");
+        assert_eq!(settings.user_email(), "default@example.com");
+        assert_eq!(settings.sign_settings().key.as_deref(), Some("default-key"));
+
+        let settings = settings_from_text(&format!("{text}\nuser.identity = 'work'")).unwrap();
+        assert_eq!(settings.identity(), Some("work"));
+        assert_eq!(settings.user_name(), "Worker");
+        assert_eq!(settings.user_email(), "worker@example.com");
+        assert_eq!(settings.sign_settings().key.as_deref(), Some("work-key"));
+        assert_eq!(settings.sign_settings().user_email, "worker@example.com");
+
+        // Unset fields fall back to the user settings
+        let settings = settings_from_text(&format!("{text}\nuser.identity = 'oss'")).unwrap();
+        assert_eq!(settings.user_name(), "Default");
+        assert_eq!(settings.user_email(), "oss@example.com");
+        assert_eq!(settings.sign_settings().key.as_deref(), Some("default-key"));
+
+        assert_matches!(
+            settings_from_text(&format!("{text}\nuser.identity = 'unknown'")),
+            Err(ConfigGetError::NotFound { name }) if name == "identities.unknown"
+        );
+    }
 
     #[test]
     fn byte_size_parse() {
@@ -58,7 +58,8 @@ use crate::backend::TreeValue;
 use crate::backend::make_root_commit;
 use crate::conflict_labels::ConflictLabels;
 use crate::content_hash::blake2b_hash;
-use crate::file_util::persist_content_addressed_temp_file;
+use crate::file_util::FileSyncer;
+use crate::file_util::FsyncPolicy;
 use crate::index::Index;
 use crate::merge::MergeBuilder;
 use crate::object_id::ObjectId;
@@ -95,6 +96,7 @@ pub struct SimpleBackend {
     root_commit_id: CommitId,
     root_change_id: ChangeId,
     empty_tree_id: TreeId,
+    syncer: FileSyncer,
 }
 
 impl SimpleBackend {
@@ -128,9 +130,16 @@ impl SimpleBackend {
             root_commit_id,
             root_change_id,
             empty_tree_id,
+            syncer: FileSyncer::default(),
         }
     }
 
+    /// Sets when written objects are flushed to disk.
+    pub fn with_fsync_policy(mut self, policy: FsyncPolicy) -> Self {
+        self.syncer = FileSyncer::new(policy);
+        self
+    }
+
     fn file_path(&self, id: &FileId) -> PathBuf {
         self.path.join("files").join(id.hex())
     }
@@ -217,7 +226,8 @@ impl Backend for SimpleBackend {
         file.flush().map_err(to_other_err)?;
         let id = FileId::new(hasher.finalize().to_vec());
 
-        persist_content_addressed_temp_file(temp_file, self.file_path(&id))
+        self.syncer
+            .persist_content_addressed_temp_file(temp_file, self.file_path(&id))
             .map_err(to_other_err)?;
         Ok(id)
     }
@@ -238,7 +248,8 @@ impl Backend for SimpleBackend {
         hasher.update(target.as_bytes());
         let id = SymlinkId::new(hasher.finalize().to_vec());
 
-        persist_content_addressed_temp_file(temp_file, self.symlink_path(&id))
+        self.syncer
+            .persist_content_addressed_temp_file(temp_file, self.symlink_path(&id))
             .map_err(to_other_err)?;
         Ok(id)
     }
@@ -281,7 +292,8 @@ impl Backend for SimpleBackend {
 
         let id = TreeId::new(blake2b_hash(tree).to_vec());
 
-        persist_content_addressed_temp_file(temp_file, self.tree_path(&id))
+        self.syncer
+            .persist_content_addressed_temp_file(temp_file, self.tree_path(&id))
             .map_err(to_other_err)?;
         Ok(id)
     }
@@ -331,7 +343,8 @@ impl Backend for SimpleBackend {
 
         let id = CommitId::new(blake2b_hash(&commit).to_vec());
 
-        persist_content_addressed_temp_file(temp_file, self.commit_path(&id))
+        self.syncer
+            .persist_content_addressed_temp_file(temp_file, self.commit_path(&id))
             .map_err(to_other_err)?;
         Ok((id, commit))
     }
@@ -345,6 +358,10 @@ impl Backend for SimpleBackend {
         Ok(Box::pin(stream::empty()))
     }
 
+    async fn sync(&self) -> BackendResult<()> {
+        self.syncer.sync().map_err(to_other_err)
+    }
+
     fn gc(&self, _index: &dyn Index, _keep_newer: SystemTime) -> BackendResult<()> {
         Ok(())
     }
@@ -29,6 +29,7 @@ use crate::file_util::IoResultExt as _;
 use crate::file_util::PathError;
 use crate::hex_util;
 use crate::lock::FileLock;
+use crate::lock::LockRetryPolicy;
 use crate::object_id::ObjectId as _;
 use crate::op_heads_store::OpHeadsStore;
 use crate::op_heads_store::OpHeadsStoreError;
@@ -48,6 +49,8 @@ impl From<SimpleOpHeadsStoreInitError> for BackendInitError {
 
 pub struct SimpleOpHeadsStore {
     dir: PathBuf,
+    lock_policy: LockRetryPolicy,
+    hostname: String,
 }
 
 impl Debug for SimpleOpHeadsStore {
@@ -66,12 +69,39 @@ impl SimpleOpHeadsStore {
     pub fn init(dir: &Path) -> Result<Self, SimpleOpHeadsStoreInitError> {
         let op_heads_dir = dir.join("heads");
         fs::create_dir(&op_heads_dir).context(&op_heads_dir)?;
-        Ok(Self { dir: op_heads_dir })
+        Ok(Self::with_dir(op_heads_dir))
     }
 
     pub fn load(dir: &Path) -> Self {
         let op_heads_dir = dir.join("heads");
-        Self { dir: op_heads_dir }
+        Self::with_dir(op_heads_dir)
+    }
+
+    fn with_dir(dir: PathBuf) -> Self {
+        Self {
+            dir,
+            lock_policy: LockRetryPolicy::default(),
+            hostname: String::new(),
+        }
+    }
+
+    /// Sets how [`OpHeadsStore::lock()`] waits for the lock held by another
+    /// process. The `hostname` is recorded in the lock file so that waiting
+    /// processes can report who holds the lock.
+    pub fn with_lock_retry_policy(mut self, policy: LockRetryPolicy, hostname: String) -> Self {
+        self.lock_policy = policy;
+        self.hostname = hostname;
+        self
+    }
+
+    /// Removes the given operation from the set of heads without deleting
+    /// anything. The head file is moved to the `quarantine` directory next to
+    /// the heads so it can be inspected or restored manually.
+    pub fn quarantine_op_head(&self, id: &OperationId) -> Result<(), PathError> {
+        let quarantine_dir = self.dir.with_file_name("quarantine");
+        fs::create_dir_all(&quarantine_dir).context(&quarantine_dir)?;
+        let head_path = self.dir.join(id.hex());
+        fs::rename(&head_path, quarantine_dir.join(id.hex())).context(&head_path)
     }
 
     fn add_op_head(&self, id: &OperationId) -> io::Result<()> {
@@ -147,8 +177,9 @@ impl OpHeadsStore for SimpleOpHeadsStore {
     }
 
     async fn lock(&self) -> Result<Box<dyn OpHeadsStoreLock + '_>, OpHeadsStoreError> {
-        let lock = FileLock::lock(self.dir.join("lock"))
-            .map_err(|err| OpHeadsStoreError::Lock(err.into()))?;
+        let lock =
+            FileLock::lock_with_retry(self.dir.join("lock"), &self.lock_policy, &self.hostname)
+                .map_err(|err| OpHeadsStoreError::Lock(err.into()))?;
         Ok(Box::new(SimpleOpHeadsStoreLock { _lock: lock }))
     }
 }
@@ -43,6 +43,7 @@ use crate::dag_walk;
 use crate::file_util::IoResultExt as _;
 use crate::file_util::PathError;
 use crate::file_util::persist_content_addressed_temp_file;
+use crate::file_util::persist_temp_file;
 use crate::merge::Merge;
 use crate::object_id::HexPrefix;
 use crate::object_id::ObjectId;
@@ -134,6 +135,12 @@ impl SimpleOpStore {
     fn operations_dir(&self) -> PathBuf {
         self.path.join("operations")
     }
+
+    /// Directory of operation tags. Unlike the other directories, this is
+    /// created when the first tag is written.
+    fn tags_dir(&self) -> PathBuf {
+        self.path.join("tags")
+    }
 }
 
 #[async_trait]
@@ -273,6 +280,70 @@ impl OpStore for SimpleOpStore {
             .map_err(|err| OpStoreError::Other(err.into()))
     }
 
+    #[tracing::instrument(skip(self))]
+    fn get_operation_tags(&self) -> OpStoreResult<BTreeMap<String, OperationId>> {
+        let tags_dir = self.tags_dir();
+        let entries = match tags_dir.read_dir().context(&tags_dir) {
+            Ok(entries) => entries,
+            Err(err) if err.source.kind() == ErrorKind::NotFound => return Ok(BTreeMap::new()),
+            Err(err) => return Err(OpStoreError::Other(err.into())),
+        };
+        let mut tags = BTreeMap::new();
+        for entry in entries {
+            let entry = entry
+                .context(&tags_dir)
+                .map_err(|err| OpStoreError::Other(err.into()))?;
+            let Ok(name) = entry.file_name().into_string() else {
+                continue;
+            };
+            // Skip temporary files
+            if name.starts_with('.') {
+                continue;
+            }
+            let path = entry.path();
+            let content = fs::read_to_string(&path)
+                .context(&path)
+                .map_err(|err| OpStoreError::Other(err.into()))?;
+            let id = OperationId::try_from_hex(content.trim()).ok_or_else(|| {
+                OpStoreError::Other(format!("Invalid operation id in tag {name}").into())
+            })?;
+            tags.insert(name, id);
+        }
+        Ok(tags)
+    }
+
+    fn set_operation_tag(&self, name: &str, id: Option<&OperationId>) -> OpStoreResult<()> {
+        if name.is_empty() || name.starts_with('.') || name.contains(['/', '\\']) {
+            return Err(OpStoreError::Other(
+                format!("Invalid operation tag name {name:?}").into(),
+            ));
+        }
+        let tags_dir = self.tags_dir();
+        let path = tags_dir.join(name);
+        let Some(id) = id else {
+            return match fs::remove_file(&path).context(&path) {
+                Ok(()) => Ok(()),
+                Err(err) if err.source.kind() == ErrorKind::NotFound => Ok(()),
+                Err(err) => Err(io_to_write_error(err, "operation tag")),
+            };
+        };
+        fs::create_dir_all(&tags_dir)
+            .context(&tags_dir)
+            .map_err(|err| io_to_write_error(err, "operation tag"))?;
+        let temp_file = NamedTempFile::new_in(&tags_dir)
+            .context(&tags_dir)
+            .map_err(|err| io_to_write_error(err, "operation tag"))?;
+        temp_file
+            .as_file()
+            .write_all(id.hex().as_bytes())
+            .context(temp_file.path())
+            .map_err(|err| io_to_write_error(err, "operation tag"))?;
+        persist_temp_file(temp_file, &path)
+            .context(&path)
+            .map_err(|err| io_to_write_error(err, "operation tag"))?;
+        Ok(())
+    }
+
     #[tracing::instrument(skip(self))]
     fn gc(&self, head_ids: &[OperationId], keep_newer: SystemTime) -> OpStoreResult<()> {
         let to_op_id = |entry: &fs::DirEntry| -> Option<OperationId> {
@@ -306,8 +377,9 @@ impl OpStore for SimpleOpStore {
                 .block_on()
                 .map(|data| (id.clone(), data))
         };
+        let tagged_ids = self.get_operation_tags()?.into_values().collect_vec();
         let reachable_ops: HashMap<OperationId, Operation> = dag_walk::dfs_ok(
-            head_ids.iter().map(read_op),
+            head_ids.iter().chain(&tagged_ids).map(read_op),
             |(id, _)| id.clone(),
             |(_, data)| data.parents.iter().map(read_op).collect_vec(),
         )
@@ -1089,6 +1161,40 @@ mod tests {
         assert_eq!(read_operation, operation);
     }
 
+    #[test]
+    fn test_operation_tags() {
+        let temp_dir = new_temp_dir();
+        let root_data = RootOperationData {
+            root_commit_id: CommitId::from_hex("000000"),
+        };
+        let store = SimpleOpStore::init(temp_dir.path(), root_data).unwrap();
+        assert!(store.get_operation_tags().unwrap().is_empty());
+
+        let op_id1 = OperationId::from_hex("0123456789abcdef");
+        let op_id2 = OperationId::from_hex("fedcba9876543210");
+        store.set_operation_tag("foo", Some(&op_id1)).unwrap();
+        store.set_operation_tag("bar", Some(&op_id1)).unwrap();
+        store.set_operation_tag("foo", Some(&op_id2)).unwrap();
+        assert_eq!(
+            store.get_operation_tags().unwrap(),
+            btreemap! {
+                "bar".to_owned() => op_id1.clone(),
+                "foo".to_owned() => op_id2.clone(),
+            }
+        );
+
+        store.set_operation_tag("bar", None).unwrap();
+        store.set_operation_tag("baz", None).unwrap();
+        assert_eq!(
+            store.get_operation_tags().unwrap(),
+            btreemap! { "foo".to_owned() => op_id2 }
+        );
+
+        assert!(store.set_operation_tag("", Some(&op_id1)).is_err());
+        assert!(store.set_operation_tag("a/b", Some(&op_id1)).is_err());
+        assert!(store.set_operation_tag("..", Some(&op_id1)).is_err());
+    }
+
     #[test]
     fn test_remote_views_legacy_roundtrip() {
         let mut view = create_view();
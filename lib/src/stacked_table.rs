@@ -42,9 +42,10 @@ use blake2::Digest as _;
 use tempfile::NamedTempFile;
 use thiserror::Error;
 
+use crate::file_util::FileSyncer;
+use crate::file_util::FsyncPolicy;
 use crate::file_util::IoResultExt as _;
 use crate::file_util::PathError;
-use crate::file_util::persist_content_addressed_temp_file;
 use crate::hex_util;
 use crate::lock::FileLock;
 use crate::lock::FileLockError;
@@ -369,7 +370,10 @@ impl MutableTable {
         let mut temp_file = NamedTempFile::new_in(&store.dir).map_err(to_save_err)?;
         let file = temp_file.as_file_mut();
         file.write_all(&buf).map_err(to_save_err)?;
-        persist_content_addressed_temp_file(temp_file, file_path).map_err(to_save_err)?;
+        store
+            .syncer
+            .persist_content_addressed_temp_file(temp_file, file_path)
+            .map_err(to_save_err)?;
 
         ReadonlyTable::load_from(&mut buf.as_slice(), store, file_id_hex, store.key_size)
     }
@@ -415,6 +419,8 @@ pub enum TableStoreError {
     },
     #[error("Failed to lock table store")]
     Lock(#[source] FileLockError),
+    #[error("Failed to sync table segments")]
+    Sync(#[source] io::Error),
 }
 
 pub type TableStoreResult<T> = Result<T, TableStoreError>;
@@ -423,6 +429,7 @@ pub struct TableStore {
     dir: PathBuf,
     key_size: usize,
     cached_tables: RwLock<HashMap<String, Arc<ReadonlyTable>>>,
+    syncer: FileSyncer,
 }
 
 impl TableStore {
@@ -432,6 +439,7 @@ impl TableStore {
             dir,
             key_size,
             cached_tables: Default::default(),
+            syncer: FileSyncer::default(),
         }
     }
 
@@ -449,9 +457,21 @@ impl TableStore {
             dir,
             key_size,
             cached_tables: Default::default(),
+            syncer: FileSyncer::default(),
         }
     }
 
+    /// Sets when saved table segments are flushed to disk.
+    pub fn with_fsync_policy(mut self, policy: FsyncPolicy) -> Self {
+        self.syncer = FileSyncer::new(policy);
+        self
+    }
+
+    /// Flushes table segments whose flushing was deferred by the fsync policy.
+    pub fn sync(&self) -> TableStoreResult<()> {
+        self.syncer.sync().map_err(TableStoreError::Sync)
+    }
+
     pub fn save_table(&self, mut_table: MutableTable) -> TableStoreResult<Arc<ReadonlyTable>> {
         let maybe_parent_table = mut_table.parent_file.clone();
         let table = mut_table.save_in(self)?;
@@ -14,6 +14,8 @@
 
 //! Parsing trailers from commit messages.
 
+use std::fmt::Write as _;
+
 use itertools::Itertools as _;
 use thiserror::Error;
 
@@ -87,6 +89,29 @@ pub fn parse_trailers(body: &str) -> Result<Vec<Trailer>, TrailerParseError> {
     Ok(trailers)
 }
 
+/// Append the given trailers to the last paragraph of the description.
+///
+/// A new paragraph is created if the description doesn't end with trailers
+/// yet. Trailers which are already in the description are not duplicated.
+pub fn append_trailers(description: &str, new_trailers: &[Trailer]) -> String {
+    let trailers = parse_description_trailers(description);
+    let mut description = description.to_owned();
+    if trailers.is_empty() && !new_trailers.is_empty() {
+        if description.is_empty() {
+            // a first empty line where the user will edit the commit summary
+            description.push('\n');
+        }
+        // create a new paragraph for the trailer
+        description.push('\n');
+    }
+    for new_trailer in new_trailers {
+        if !trailers.contains(new_trailer) {
+            writeln!(description, "{}: {}", new_trailer.key, new_trailer.value).unwrap();
+        }
+    }
+    description
+}
+
 fn parse_trailers_impl(body: &str) -> (Vec<Trailer>, bool, bool, Option<String>) {
     // a trailer always comes at the end of a message; we can split the message
     // by newline, but we need to immediately reverse the order of the lines
@@ -138,6 +163,8 @@ fn parse_trailers_impl(body: &str) -> (Vec<Trailer>, bool, bool, Option<String>)
 
 #[cfg(test)]
 mod tests {
+    use std::slice;
+
     use indoc::indoc;
     use pretty_assertions::assert_eq;
 
@@ -452,4 +479,43 @@ mod tests {
         assert_eq!(trailers[0].key, "foo");
         assert_eq!(trailers[0].value, "bar");
     }
+
+    #[test]
+    fn test_append_trailers() {
+        let signoff = Trailer {
+            key: "Signed-off-by".to_owned(),
+            value: "Alice <alice@example.com>".to_owned(),
+        };
+
+        // a new paragraph is created after the description
+        let description = append_trailers("subject\n", slice::from_ref(&signoff));
+        assert_eq!(
+            description,
+            "subject\n\nSigned-off-by: Alice <alice@example.com>\n"
+        );
+
+        // existing trailers aren't duplicated
+        assert_eq!(
+            append_trailers(&description, slice::from_ref(&signoff)),
+            description
+        );
+
+        // new trailers go to the existing trailer paragraph
+        let reviewed = Trailer {
+            key: "Reviewed-by".to_owned(),
+            value: "Bob <bob@example.com>".to_owned(),
+        };
+        assert_eq!(
+            append_trailers(&description, &[signoff, reviewed]),
+            indoc! {"
+                subject
+
+                Signed-off-by: Alice <alice@example.com>
+                Reviewed-by: Bob <bob@example.com>
+            "}
+        );
+
+        // nothing is changed if there are no new trailers
+        assert_eq!(append_trailers("", &[]), "");
+    }
 }
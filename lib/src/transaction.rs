@@ -19,6 +19,7 @@ use std::sync::Arc;
 use itertools::Itertools as _;
 use thiserror::Error;
 
+use crate::backend::BackendError;
 use crate::backend::Timestamp;
 use crate::dag_walk;
 use crate::index::IndexStoreError;
@@ -42,6 +43,7 @@ use crate::view::View;
 #[derive(Debug, Error)]
 #[error("Failed to commit new operation")]
 pub enum TransactionCommitError {
+    Backend(#[from] BackendError),
     IndexStore(#[from] IndexStoreError),
     OpHeadsStore(#[from] OpHeadsStoreError),
     OpStore(#[from] OpStoreError),
@@ -139,6 +141,9 @@ impl Transaction {
         let base_repo = mut_repo.base_repo().clone();
         let (mut_index, view, predecessors) = mut_repo.consume();
 
+        // Objects must be durable before the operation referring to them is.
+        base_repo.store().backend().sync().await?;
+
         let operation = {
             let view_id = base_repo.op_store().write_view(view.store_view()).await?;
             self.op_metadata.description = description.into();
@@ -17,6 +17,7 @@
 
 use std::any::Any;
 use std::collections::BTreeMap;
+use std::collections::BTreeSet;
 use std::ffi::OsString;
 use std::path::PathBuf;
 use std::sync::Arc;
@@ -216,6 +217,9 @@ pub struct SnapshotOptions<'a> {
     // because the TreeState may be long-lived if the library is used in a
     // long-lived process.
     pub base_ignores: Arc<GitIgnoreFile>,
+    /// New files matching this are ignored in the same way as files matching
+    /// the `.gitignore`s.
+    pub ignore_matcher: &'a dyn Matcher,
     /// A callback for the UI to display progress.
     pub progress: Option<&'a SnapshotProgress<'a>>,
     /// For new files that are not already tracked, start tracking them if they
@@ -240,6 +244,9 @@ pub type SnapshotProgress<'a> = dyn Fn(&RepoPath) + 'a + Sync;
 pub struct SnapshotStats {
     /// List of new (previously untracked) files which are still untracked.
     pub untracked_paths: BTreeMap<RepoPathBuf, UntrackedReason>,
+    /// Files having both LF and CRLF line endings, which were normalized when
+    /// the files were snapshotted.
+    pub mixed_eol_paths: BTreeSet<RepoPathBuf>,
 }
 
 /// Reason why the new path isn't tracked.
@@ -0,0 +1,134 @@
+// Copyright 2026 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Storage of cached working copies, which are used to run commands against
+//! revisions without touching the workspace's own working copy.
+
+use std::any::Any;
+use std::fmt::Debug;
+use std::path::PathBuf;
+
+use thiserror::Error;
+
+use crate::commit::Commit;
+use crate::file_util::PathError;
+use crate::lock::FileLock;
+use crate::lock::FileLockError;
+use crate::merged_tree::MergedTree;
+use crate::op_store::OperationId;
+use crate::working_copy::CheckoutError;
+use crate::working_copy::SnapshotError;
+use crate::working_copy::SnapshotOptions;
+use crate::working_copy::WorkingCopy;
+use crate::working_copy::WorkingCopyStateError;
+
+/// Error that can occur when creating, loading, or removing cached working
+/// copies.
+#[derive(Debug, Error)]
+pub enum WorkingCopyStoreError {
+    /// An I/O error related to a file path.
+    #[error(transparent)]
+    Path(#[from] PathError),
+    /// Failed to lock a cached working copy.
+    #[error("Failed to lock cached working copy")]
+    Lock(#[from] FileLockError),
+    /// Failed to read or write the state of a cached working copy.
+    #[error(transparent)]
+    State(#[from] WorkingCopyStateError),
+    /// Failed to check out the revision.
+    #[error(transparent)]
+    Checkout(#[from] CheckoutError),
+    /// Failed to snapshot the files of a cached working copy.
+    #[error(transparent)]
+    Snapshot(#[from] SnapshotError),
+}
+
+/// Working copy returned by a [`WorkingCopyStore`].
+pub struct StoredWorkingCopy {
+    /// The working copy, with the tree of the requested revision checked out.
+    pub working_copy: Box<dyn WorkingCopy>,
+    /// Directory where the files of the working copy are materialized.
+    pub path: PathBuf,
+    /// Directory outside of the working copy where the outputs of commands run
+    /// in it can be saved.
+    pub output_path: PathBuf,
+    /// Whether an existing working copy was reused instead of a new one being
+    /// checked out.
+    pub reused: bool,
+    /// Lock which keeps other processes from using the same working copy. It's
+    /// released when this is dropped.
+    pub lock: Option<FileLock>,
+}
+
+impl StoredWorkingCopy {
+    /// Snapshots the files of the working copy, and returns the resulting
+    /// tree. The state of the working copy is updated to the new tree.
+    pub async fn snapshot(
+        &mut self,
+        options: &SnapshotOptions<'_>,
+        operation_id: &OperationId,
+    ) -> Result<MergedTree, WorkingCopyStoreError> {
+        let mut locked_wc = self.working_copy.start_mutation()?;
+        let (tree, _stats) = locked_wc.snapshot(options).await?;
+        self.working_copy = locked_wc.finish(operation_id.clone()).await?;
+        Ok(tree)
+    }
+
+    /// Reverts changes made to the files since the tree of the `commit` was
+    /// checked out, e.g. by commands run in a reused working copy. Ignored
+    /// files are kept.
+    pub async fn discard_changes(
+        &mut self,
+        commit: &Commit,
+        options: &SnapshotOptions<'_>,
+        operation_id: &OperationId,
+    ) -> Result<(), WorkingCopyStoreError> {
+        let mut locked_wc = self.working_copy.start_mutation()?;
+        let (tree, _stats) = locked_wc.snapshot(options).await?;
+        if tree.tree_ids() != commit.tree_ids() {
+            locked_wc.check_out(commit).await?;
+        }
+        self.working_copy = locked_wc.finish(operation_id.clone()).await?;
+        Ok(())
+    }
+}
+
+/// Storage of working copies which are kept around to be reused by later
+/// commands, e.g. to benefit from build outputs of earlier runs.
+pub trait WorkingCopyStore: Any + Send + Sync + Debug {
+    /// Returns the name of this working copy store implementation.
+    fn name(&self) -> &str;
+
+    /// Returns a working copy with the tree of the `commit` checked out. An
+    /// existing working copy of the same tree is reused if there's one.
+    ///
+    /// The working copy may be locked until the returned value is dropped, so
+    /// the caller shouldn't request the same tree again in the meantime.
+    fn get_or_create_working_copy(
+        &self,
+        commit: &Commit,
+        operation_id: &OperationId,
+    ) -> Result<StoredWorkingCopy, WorkingCopyStoreError>;
+
+    /// Removes all stored working copies except for the ones in use. Returns
+    /// the number of removed working copies.
+    fn clean(&self) -> Result<usize, WorkingCopyStoreError>;
+}
+
+impl dyn WorkingCopyStore {
+    /// Returns reference of the implementation type.
+    pub fn downcast_ref<T: WorkingCopyStore>(&self) -> Option<&T> {
+        (self as &dyn Any).downcast_ref()
+    }
+}
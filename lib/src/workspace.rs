@@ -32,6 +32,8 @@ use crate::file_util::IoResultExt as _;
 use crate::file_util::PathError;
 use crate::local_working_copy::LocalWorkingCopy;
 use crate::local_working_copy::LocalWorkingCopyFactory;
+use crate::lock::FileLock;
+use crate::lock::FileLockError;
 use crate::merged_tree::MergedTree;
 use crate::op_heads_store::OpHeadsStoreError;
 use crate::op_store::OperationId;
@@ -61,8 +63,7 @@ use crate::working_copy::LockedWorkingCopy;
 use crate::working_copy::WorkingCopy;
 use crate::working_copy::WorkingCopyFactory;
 use crate::working_copy::WorkingCopyStateError;
-use crate::workspace_store::SimpleWorkspaceStore;
-use crate::workspace_store::WorkspaceStore as _;
+use crate::workspace_store::WorkspaceCreation;
 use crate::workspace_store::WorkspaceStoreError;
 
 #[derive(Error, Debug)]
@@ -320,8 +321,8 @@ impl Workspace {
                 RepoInitError::Backend(err) => WorkspaceInitError::Backend(err),
                 RepoInitError::OpHeadsStore(err) => WorkspaceInitError::OpHeadsStore(err),
                 RepoInitError::Path(err) => WorkspaceInitError::Path(err),
+                RepoInitError::WorkspaceStore(err) => WorkspaceInitError::WorkspaceStore(err),
             })?;
-            let workspace_store = SimpleWorkspaceStore::load(&repo_dir)?;
             let (working_copy, repo) = init_working_copy(
                 &repo,
                 workspace_root,
@@ -333,7 +334,13 @@ impl Workspace {
             let repo_loader = repo.loader().clone();
             let repo_dir = dunce::canonicalize(&repo_dir).context(&repo_dir)?;
             let workspace = Self::new(workspace_root, repo_dir, working_copy, repo_loader)?;
-            workspace_store.add(workspace.workspace_name(), workspace.workspace_root())?;
+            let workspace_store = repo.loader().workspace_store();
+            let creation = WorkspaceCreation::from_operation(repo.operation());
+            workspace_store.add(
+                workspace.workspace_name(),
+                workspace.workspace_root(),
+                Some(&creation),
+            )?;
             Ok((workspace, repo))
         }
         .await
@@ -385,7 +392,6 @@ impl Workspace {
         let repo_file_path = jj_dir.join("repo");
         fs::write(&repo_file_path, repo_dir_bytes).context(&repo_file_path)?;
 
-        let workspace_store = SimpleWorkspaceStore::load(repo_path)?;
         let (working_copy, repo) = init_working_copy(
             repo,
             workspace_root,
@@ -400,7 +406,13 @@ impl Workspace {
             working_copy,
             repo.loader().clone(),
         )?;
-        workspace_store.add(workspace.workspace_name(), workspace.workspace_root())?;
+        let workspace_store = repo.loader().workspace_store();
+        let creation = WorkspaceCreation::from_operation(repo.operation());
+        workspace_store.add(
+            workspace.workspace_name(),
+            workspace.workspace_root(),
+            Some(&creation),
+        )?;
         Ok((workspace, repo))
     }
 
@@ -440,6 +452,35 @@ impl Workspace {
         self.working_copy.as_ref()
     }
 
+    /// Path to the lock file `name` in the `.jj` directory of this workspace.
+    /// Unlike the locks in the repo directory, these don't block processes
+    /// working in other workspaces of the same repo.
+    fn lock_path(&self, name: &str) -> PathBuf {
+        self.workspace_root.join(".jj").join(name)
+    }
+
+    /// Acquires the lock that serializes snapshots of this workspace across
+    /// processes, waiting for other processes to release it.
+    pub fn lock_snapshot(&self) -> Result<FileLock, FileLockError> {
+        FileLock::lock(self.lock_path("snapshot.lock"))
+    }
+
+    /// Like [`Self::lock_snapshot()`], but returns `None` instead of waiting
+    /// if another process holds the lock.
+    pub fn try_lock_snapshot(&self) -> Result<Option<FileLock>, FileLockError> {
+        FileLock::try_lock(self.lock_path("snapshot.lock"))
+    }
+
+    /// Acquires the lock that is held by [`Self::check_out()`], or returns
+    /// `None` if another process is checking out a commit in this workspace.
+    ///
+    /// While the returned lock is held, no checkout can start, so a process
+    /// that only reads the working copy can snapshot it without waiting for a
+    /// long checkout.
+    pub fn try_lock_checkout(&self) -> Result<Option<FileLock>, FileLockError> {
+        FileLock::try_lock(self.lock_path("checkout.lock"))
+    }
+
     pub fn start_working_copy_mutation(
         &mut self,
     ) -> Result<LockedWorkspace<'_>, WorkingCopyStateError> {
@@ -456,6 +497,12 @@ impl Workspace {
         old_tree: Option<&MergedTree>,
         commit: &Commit,
     ) -> Result<CheckoutStats, CheckoutError> {
+        let _checkout_lock = FileLock::lock(self.lock_path("checkout.lock")).map_err(|err| {
+            CheckoutError::Other {
+                message: "Failed to take lock for checkout".to_string(),
+                err: err.into(),
+            }
+        })?;
         let mut locked_ws = self.start_working_copy_mutation()?;
         // Check if the current working-copy commit has changed on disk compared to what
         // the caller expected. It's safe to check out another commit
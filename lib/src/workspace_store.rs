@@ -20,9 +20,13 @@ use std::io::Write as _;
 use std::path::Path;
 use std::path::PathBuf;
 
+use jj_lib::backend::MillisSinceEpoch;
+use jj_lib::backend::Timestamp;
+use jj_lib::commit::Commit;
 use jj_lib::file_util::BadPathEncoding;
 use jj_lib::file_util::IoResultExt as _;
 use jj_lib::file_util::PathError;
+use jj_lib::file_util::normalize_path;
 use jj_lib::file_util::path_from_bytes;
 use jj_lib::file_util::path_to_bytes;
 use jj_lib::file_util::persist_temp_file;
@@ -30,8 +34,13 @@ use jj_lib::file_util::relative_path;
 use jj_lib::file_util::slash_path;
 use jj_lib::lock::FileLock;
 use jj_lib::lock::FileLockError;
+use jj_lib::object_id::ObjectId as _;
+use jj_lib::op_store::OperationId;
+use jj_lib::operation::Operation;
 use jj_lib::protos::simple_workspace_store;
 use jj_lib::ref_name::WorkspaceName;
+use jj_lib::working_copy::WorkingCopy;
+use jj_lib::working_copy::WorkingCopyStateError;
 use prost::Message as _;
 use tempfile::NamedTempFile;
 use thiserror::Error;
@@ -49,8 +58,15 @@ pub trait WorkspaceStore: Send + Sync + Debug {
     /// Returns the name of this workspace store implementation.
     fn name(&self) -> &str;
 
-    /// Adds a workspace with the given name and path to the store.
-    fn add(&self, workspace_name: &WorkspaceName, path: &Path) -> Result<(), WorkspaceStoreError>;
+    /// Adds a workspace with the given name and path to the store. If the
+    /// `creation` metadata is `None`, the metadata already recorded for the
+    /// workspace is kept.
+    fn add(
+        &self,
+        workspace_name: &WorkspaceName,
+        path: &Path,
+        creation: Option<&WorkspaceCreation>,
+    ) -> Result<(), WorkspaceStoreError>;
 
     /// Forgets the workspaces with the given names.
     fn forget(&self, workspace_names: &[&WorkspaceName]) -> Result<(), WorkspaceStoreError>;
@@ -67,6 +83,111 @@ pub trait WorkspaceStore: Send + Sync + Debug {
         &self,
         workspace_name: &WorkspaceName,
     ) -> Result<Option<PathBuf>, WorkspaceStoreError>;
+
+    /// Gets the metadata recorded when the workspace with the given name was
+    /// created, if any.
+    fn get_workspace_creation(
+        &self,
+        workspace_name: &WorkspaceName,
+    ) -> Result<Option<WorkspaceCreation>, WorkspaceStoreError>;
+}
+
+/// Metadata recorded when a workspace is created.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct WorkspaceCreation {
+    /// Time when the workspace was created.
+    pub time: Timestamp,
+    /// Name of the user who created the workspace.
+    pub username: String,
+    /// Operation which added the workspace.
+    pub operation_id: OperationId,
+}
+
+impl WorkspaceCreation {
+    /// Creates metadata for a workspace added by the `operation`.
+    pub fn from_operation(operation: &Operation) -> Self {
+        let metadata = operation.metadata();
+        Self {
+            time: metadata.time.end,
+            username: metadata.username.clone(),
+            operation_id: operation.id().clone(),
+        }
+    }
+}
+
+/// Information about a workspace recorded in a workspace store.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct WorkspaceInfo {
+    /// Path recorded in the store. A relative path is relative to the repo
+    /// directory. `None` if the workspace has no recorded path.
+    pub recorded_path: Option<PathBuf>,
+    /// Absolute path of the workspace root. `None` if the workspace has no
+    /// recorded path.
+    pub absolute_path: Option<PathBuf>,
+    /// Whether the workspace root directory exists.
+    pub exists: bool,
+    /// Whether the working copy is stale. `None` if it hasn't been checked.
+    pub stale: Option<bool>,
+    /// Metadata recorded when the workspace was created, if any.
+    pub creation: Option<WorkspaceCreation>,
+}
+
+impl WorkspaceInfo {
+    /// Looks up the workspace in the `store` of the repo at `repo_path`. The
+    /// working copy isn't checked for staleness.
+    pub fn load(
+        store: &dyn WorkspaceStore,
+        repo_path: &Path,
+        workspace_name: &WorkspaceName,
+    ) -> Result<Self, WorkspaceStoreError> {
+        let recorded_path = store.get_workspace_path(workspace_name)?;
+        let absolute_path = recorded_path.as_ref().map(|path| {
+            let path = repo_path.join(path);
+            dunce::canonicalize(&path).unwrap_or_else(|_| normalize_path(&path))
+        });
+        let exists = absolute_path.as_ref().is_some_and(|path| path.is_dir());
+        let creation = store.get_workspace_creation(workspace_name)?;
+        Ok(Self {
+            recorded_path,
+            absolute_path,
+            exists,
+            stale: None,
+            creation,
+        })
+    }
+
+    /// Checks whether the `working_copy` of the workspace is stale, i.e.
+    /// whether its tree differs from the tree of the working-copy commit.
+    pub fn check_stale(
+        &mut self,
+        working_copy: &dyn WorkingCopy,
+        wc_commit: &Commit,
+    ) -> Result<(), WorkingCopyStateError> {
+        let tree = working_copy.tree()?;
+        self.stale = Some(tree.tree_ids_and_labels() != wc_commit.tree().tree_ids_and_labels());
+        Ok(())
+    }
+}
+
+/// Updates the path recorded for the workspace if the workspace appears to
+/// have been moved to `workspace_root`. Returns the previous info of the
+/// workspace if the path was updated.
+///
+/// Like the repo config, the workspace is assumed to have been moved if the
+/// recorded directory no longer exists. If the directory still exists, the
+/// workspace may have been copied, so the recorded path is kept.
+pub fn repair_moved_workspace(
+    store: &dyn WorkspaceStore,
+    repo_path: &Path,
+    workspace_name: &WorkspaceName,
+    workspace_root: &Path,
+) -> Result<Option<WorkspaceInfo>, WorkspaceStoreError> {
+    let info = WorkspaceInfo::load(store, repo_path, workspace_name)?;
+    if info.exists {
+        return Ok(None);
+    }
+    store.add(workspace_name, workspace_root, None)?;
+    Ok(Some(info))
 }
 
 /// Errors specific to the `SimpleWorkspaceStore` implementation.
@@ -116,17 +237,26 @@ impl SimpleWorkspaceStore {
         // for repos that were created before workspace_store was added.
         if !store_dir.exists() {
             fs::create_dir(&store_dir)
-                .context(store_dir)
+                .context(&store_dir)
                 .map_err(SimpleWorkspaceStoreError::Path)?;
 
             let _lock = store.lock()?;
 
             store.write_store(simple_workspace_store::Workspaces::default())?;
+            let type_path = store_dir.join("type");
+            fs::write(&type_path, Self::name())
+                .context(&type_path)
+                .map_err(SimpleWorkspaceStoreError::Path)?;
         }
 
         Ok(store)
     }
 
+    /// Name of this workspace store implementation.
+    pub fn name() -> &'static str {
+        "simple"
+    }
+
     fn lock(&self) -> Result<FileLock, SimpleWorkspaceStoreError> {
         Ok(FileLock::lock(self.lock_file.clone())?)
     }
@@ -159,19 +289,33 @@ impl SimpleWorkspaceStore {
 }
 
 impl WorkspaceStore for SimpleWorkspaceStore {
-    fn name(&self) -> &'static str {
-        "simple"
+    fn name(&self) -> &str {
+        Self::name()
     }
 
-    fn add(&self, workspace_name: &WorkspaceName, path: &Path) -> Result<(), WorkspaceStoreError> {
+    fn add(
+        &self,
+        workspace_name: &WorkspaceName,
+        path: &Path,
+        creation: Option<&WorkspaceCreation>,
+    ) -> Result<(), WorkspaceStoreError> {
         let _lock = self.lock()?;
 
         let mut workspaces_proto = self.read_store()?;
 
         // Delete any existing entry with the same name
-        workspaces_proto
-            .workspaces
-            .retain(|w| w.name.as_str() != workspace_name.as_str());
+        let mut old_creation = None;
+        workspaces_proto.workspaces.retain_mut(|w| {
+            if w.name.as_str() != workspace_name.as_str() {
+                return true;
+            }
+            old_creation = w.creation.take();
+            false
+        });
+        let creation = match creation {
+            Some(creation) => Some(creation_to_proto(creation)),
+            None => old_creation,
+        };
 
         let path_to_store = relative_path(&self.repo_path, path);
         let path_to_store = if path_to_store.is_relative() {
@@ -186,6 +330,7 @@ impl WorkspaceStore for SimpleWorkspaceStore {
                 path: path_to_bytes(&path_to_store)
                     .map_err(SimpleWorkspaceStoreError::BadPathEncoding)?
                     .to_owned(),
+                creation,
             });
 
         self.write_store(workspaces_proto)?;
@@ -248,4 +393,40 @@ impl WorkspaceStore for SimpleWorkspaceStore {
             })
             .transpose()?)
     }
+
+    fn get_workspace_creation(
+        &self,
+        workspace_name: &WorkspaceName,
+    ) -> Result<Option<WorkspaceCreation>, WorkspaceStoreError> {
+        let creation = self
+            .read_store()?
+            .workspaces
+            .into_iter()
+            .find(|w| w.name.as_str() == workspace_name.as_str())
+            .and_then(|w| w.creation);
+        Ok(creation.map(creation_from_proto))
+    }
+}
+
+fn creation_to_proto(creation: &WorkspaceCreation) -> simple_workspace_store::WorkspaceCreation {
+    simple_workspace_store::WorkspaceCreation {
+        time: Some(simple_workspace_store::Timestamp {
+            millis_since_epoch: creation.time.timestamp.0,
+            tz_offset: creation.time.tz_offset,
+        }),
+        username: creation.username.clone(),
+        operation_id: creation.operation_id.to_bytes(),
+    }
+}
+
+fn creation_from_proto(proto: simple_workspace_store::WorkspaceCreation) -> WorkspaceCreation {
+    let time = proto.time.unwrap_or_default();
+    WorkspaceCreation {
+        time: Timestamp {
+            timestamp: MillisSinceEpoch(time.millis_since_epoch),
+            tz_offset: time.tz_offset,
+        },
+        username: proto.username,
+        operation_id: OperationId::new(proto.operation_id),
+    }
 }
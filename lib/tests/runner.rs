@@ -13,6 +13,7 @@ mod test_commit_builder;
 mod test_commit_concurrent;
 mod test_conflicts;
 mod test_default_revset_graph_iterator;
+mod test_default_working_copy_store;
 mod test_eol;
 mod test_evolution_predecessors;
 mod test_fix;
@@ -0,0 +1,135 @@
+// Copyright 2026 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fs;
+
+use jj_lib::default_working_copy_store::DefaultWorkingCopyStore;
+use jj_lib::lock::FileLock;
+use jj_lib::repo::Repo as _;
+use jj_lib::working_copy_store::WorkingCopyStore as _;
+use pollster::FutureExt as _;
+use testutils::TestRepo;
+use testutils::commit_with_tree;
+use testutils::create_tree;
+use testutils::empty_snapshot_options;
+use testutils::new_temp_dir;
+use testutils::repo_path;
+use testutils::user_settings;
+
+#[test]
+fn test_get_or_create_working_copy() {
+    let test_repo = TestRepo::init();
+    let repo = &test_repo.repo;
+    let temp_dir = new_temp_dir();
+    let store_path = temp_dir.path().join("run");
+    let working_copy_store =
+        DefaultWorkingCopyStore::load(repo.store().clone(), store_path.clone(), &user_settings());
+    let path = repo_path("file");
+    let commit1 = commit_with_tree(repo.store(), create_tree(repo, &[(path, "1")]));
+    let commit2 = commit_with_tree(repo.store(), create_tree(repo, &[(path, "2")]));
+    let op_id = repo.op_id();
+
+    // A new working copy is checked out for each tree
+    let stored1 = working_copy_store
+        .get_or_create_working_copy(&commit1, op_id)
+        .unwrap();
+    assert!(!stored1.reused);
+    assert_eq!(
+        stored1.working_copy.tree().unwrap().tree_ids(),
+        commit1.tree_ids()
+    );
+    assert_eq!(fs::read(stored1.path.join("file")).unwrap(), b"1");
+    assert!(stored1.output_path.is_dir());
+    assert!(!stored1.output_path.starts_with(&stored1.path));
+    let stored2 = working_copy_store
+        .get_or_create_working_copy(&commit2, op_id)
+        .unwrap();
+    assert!(!stored2.reused);
+    assert_ne!(stored2.path, stored1.path);
+    assert_eq!(fs::read(stored2.path.join("file")).unwrap(), b"2");
+
+    // The working copy is locked while it's in use
+    let lock_path = stored1.path.parent().unwrap().with_extension("lock");
+    assert!(FileLock::try_lock(lock_path.clone()).unwrap().is_none());
+
+    // The existing working copy is reused along with untracked files
+    fs::write(stored1.path.join("output"), "built").unwrap();
+    drop(stored1.lock);
+    assert!(FileLock::try_lock(lock_path).unwrap().is_some());
+    let stored1_again = working_copy_store
+        .get_or_create_working_copy(&commit1, op_id)
+        .unwrap();
+    assert!(stored1_again.reused);
+    assert_eq!(stored1_again.path, stored1.path);
+    assert!(stored1.path.join("output").exists());
+
+    // A working copy whose state is broken is replaced
+    let state_path = stored2.path.parent().unwrap().join("state");
+    fs::remove_dir_all(&state_path).unwrap();
+    drop(stored2);
+    let stored2_again = working_copy_store
+        .get_or_create_working_copy(&commit2, op_id)
+        .unwrap();
+    assert!(!stored2_again.reused);
+    assert_eq!(fs::read(stored2_again.path.join("file")).unwrap(), b"2");
+
+    // Working copies in use are skipped
+    drop(stored2_again);
+    assert_eq!(working_copy_store.clean().unwrap(), 1);
+    drop(stored1_again);
+    assert_eq!(working_copy_store.clean().unwrap(), 1);
+    assert_eq!(fs::read_dir(&store_path).unwrap().count(), 0);
+    assert_eq!(working_copy_store.clean().unwrap(), 0);
+}
+
+#[test]
+fn test_snapshot_and_discard_changes() {
+    let test_repo = TestRepo::init();
+    let repo = &test_repo.repo;
+    let temp_dir = new_temp_dir();
+    let working_copy_store = DefaultWorkingCopyStore::load(
+        repo.store().clone(),
+        temp_dir.path().join("run"),
+        &user_settings(),
+    );
+    let path = repo_path("file");
+    let commit1 = commit_with_tree(repo.store(), create_tree(repo, &[(path, "1")]));
+    let commit2 = commit_with_tree(repo.store(), create_tree(repo, &[(path, "2")]));
+    let op_id = repo.op_id();
+    let options = empty_snapshot_options();
+
+    // The snapshot records the changes made to the files
+    let mut stored = working_copy_store
+        .get_or_create_working_copy(&commit1, op_id)
+        .unwrap();
+    fs::write(stored.path.join("file"), "2").unwrap();
+    let tree = stored.snapshot(&options, op_id).block_on().unwrap();
+    assert_eq!(tree.tree_ids(), commit2.tree_ids());
+    assert_eq!(
+        stored.working_copy.tree().unwrap().tree_ids(),
+        commit2.tree_ids()
+    );
+
+    // The changes are reverted
+    fs::write(stored.path.join("file"), "3").unwrap();
+    stored
+        .discard_changes(&commit1, &options, op_id)
+        .block_on()
+        .unwrap();
+    assert_eq!(fs::read(stored.path.join("file")).unwrap(), b"1");
+    assert_eq!(
+        stored.working_copy.tree().unwrap().tree_ids(),
+        commit1.tree_ids()
+    );
+}
@@ -42,6 +42,7 @@ use jj_lib::config::ConfigSource;
 use jj_lib::git;
 use jj_lib::git::FailedRefExportReason;
 use jj_lib::git::FetchTagsOverride;
+use jj_lib::git::GitBranchPrefixes;
 use jj_lib::git::GitBranchPushTargets;
 use jj_lib::git::GitFetch;
 use jj_lib::git::GitFetchError;
@@ -50,8 +51,10 @@ use jj_lib::git::GitImportError;
 use jj_lib::git::GitImportOptions;
 use jj_lib::git::GitImportStats;
 use jj_lib::git::GitPushError;
+use jj_lib::git::GitPushRefSpecs;
 use jj_lib::git::GitPushStats;
 use jj_lib::git::GitRefKind;
+use jj_lib::git::GitRefMapping;
 use jj_lib::git::GitRefUpdate;
 use jj_lib::git::GitResetHeadError;
 use jj_lib::git::GitSettings;
@@ -71,6 +74,7 @@ use jj_lib::op_store::LocalRemoteRefTarget;
 use jj_lib::op_store::RefTarget;
 use jj_lib::op_store::RemoteRef;
 use jj_lib::op_store::RemoteRefState;
+use jj_lib::ref_name::GitRefName;
 use jj_lib::ref_name::GitRefNameBuf;
 use jj_lib::ref_name::RefName;
 use jj_lib::ref_name::RemoteName;
@@ -1375,6 +1379,138 @@ fn test_import_refs_reimport_abandoning_disabled() {
     assert!(tx.repo().view().heads().contains(&jj_id(commit2)));
 }
 
+#[test]
+fn test_import_refs_ignored() {
+    let test_repo = TestRepo::init_with_backend(TestRepoBackend::Git);
+    let repo = &test_repo.repo;
+    let git_repo = get_git_repo(repo);
+    let import_options = GitImportOptions {
+        ref_mapping: GitRefMapping {
+            ignored_refs: StringExpression::union_all(vec![
+                StringExpression::pattern(StringPattern::glob("refs/heads/pr/*").unwrap()),
+                StringExpression::pattern(StringPattern::glob("refs/remotes/*/bot/*").unwrap()),
+            ])
+            .to_matcher(),
+            ..GitRefMapping::default()
+        },
+        ..auto_track_import_options()
+    };
+
+    let commit1 = empty_git_commit(&git_repo, "refs/heads/main", &[]);
+    let commit2 = empty_git_commit(&git_repo, "refs/heads/pr/1", &[commit1]);
+    git_ref(&git_repo, "refs/remotes/origin/main", commit1);
+    git_ref(&git_repo, "refs/remotes/origin/bot/update", commit2);
+
+    let mut tx = repo.start_transaction();
+    git::import_refs(tx.repo_mut(), &import_options).unwrap();
+    let repo = tx.commit("test").block_on().unwrap();
+
+    let view = repo.view();
+    assert_eq!(*view.heads(), hashset! {jj_id(commit1)});
+    assert!(view.get_local_bookmark("main".as_ref()).is_present());
+    assert!(view.get_local_bookmark("pr/1".as_ref()).is_absent());
+    assert!(view.get_local_bookmark("bot/update".as_ref()).is_absent());
+    assert!(
+        view.get_remote_bookmark(remote_symbol("main", "origin"))
+            .is_present()
+    );
+    assert!(
+        view.get_remote_bookmark(remote_symbol("bot/update", "origin"))
+            .is_absent()
+    );
+    assert!(view.get_git_ref("refs/heads/pr/1".as_ref()).is_absent());
+}
+
+#[test]
+fn test_import_export_refs_branch_prefixes() {
+    let test_repo = TestRepo::init_with_backend(TestRepoBackend::Git);
+    let repo = &test_repo.repo;
+    let git_repo = get_git_repo(repo);
+    let ref_mapping = || GitRefMapping {
+        branch_prefixes: GitBranchPrefixes::new([("user/alice/".to_owned(), "alice/".to_owned())])
+            .unwrap(),
+        ..GitRefMapping::default()
+    };
+    let import_options = GitImportOptions {
+        ref_mapping: ref_mapping(),
+        ..default_import_options()
+    };
+
+    let commit1 = empty_git_commit(&git_repo, "refs/heads/main", &[]);
+    let commit2 = empty_git_commit(&git_repo, "refs/heads/user/alice/topic", &[commit1]);
+    // Can't be represented because it would clash with the renamed branches
+    empty_git_commit(&git_repo, "refs/heads/alice/clash", &[commit1]);
+
+    let mut tx = repo.start_transaction();
+    git::import_refs(tx.repo_mut(), &import_options).unwrap();
+    let view = tx.repo().view();
+    assert_eq!(
+        view.local_bookmarks()
+            .map(|(name, _)| name.as_str())
+            .collect_vec(),
+        ["alice/topic", "main"]
+    );
+    assert_eq!(
+        *view.get_local_bookmark("alice/topic".as_ref()),
+        RefTarget::normal(jj_id(commit2))
+    );
+    assert!(
+        view.get_remote_bookmark(remote_symbol("alice/topic", "git"))
+            .is_present()
+    );
+    assert!(
+        view.get_git_ref("refs/heads/user/alice/topic".as_ref())
+            .is_present()
+    );
+
+    // Renamed bookmarks are exported back to the original branches
+    let mut_repo = tx.repo_mut();
+    mut_repo.set_local_bookmark_target("alice/topic".as_ref(), RefTarget::normal(jj_id(commit1)));
+    mut_repo.set_local_bookmark_target("alice/new".as_ref(), RefTarget::normal(jj_id(commit1)));
+    // Can't be exported because it would be imported as "alice/other"
+    mut_repo.set_local_bookmark_target(
+        "user/alice/other".as_ref(),
+        RefTarget::normal(jj_id(commit1)),
+    );
+    let stats = git::export_refs(mut_repo, &ref_mapping()).unwrap();
+    assert_eq!(stats.failed_bookmarks.len(), 1);
+    assert_eq!(
+        stats.failed_bookmarks[0].0.as_ref(),
+        remote_symbol("user/alice/other", "git")
+    );
+    assert_matches!(
+        stats.failed_bookmarks[0].1,
+        FailedRefExportReason::InvalidGitName
+    );
+    let git_ref_id = |name: &str| git_repo.find_reference(name).unwrap().id().detach();
+    assert_eq!(git_ref_id("refs/heads/user/alice/topic"), commit1);
+    assert_eq!(git_ref_id("refs/heads/user/alice/new"), commit1);
+    assert!(
+        git_repo
+            .try_find_reference("refs/heads/alice/new")
+            .unwrap()
+            .is_none()
+    );
+
+    // Re-importing the exported refs doesn't change anything
+    let stats = git::import_refs(tx.repo_mut(), &import_options).unwrap();
+    assert!(stats.changed_remote_bookmarks.is_empty());
+}
+
+#[test]
+fn test_git_branch_prefixes_overlap() {
+    let new = |prefixes: &[(&str, &str)]| {
+        GitBranchPrefixes::new(
+            prefixes
+                .iter()
+                .map(|&(git, bookmark)| (git.to_owned(), bookmark.to_owned())),
+        )
+    };
+    assert!(new(&[("a/", "x/"), ("b/", "a/")]).is_ok());
+    assert!(new(&[("a/", "x/"), ("a/b/", "y/")]).is_err());
+    assert!(new(&[("a/", "x/"), ("b/", "x/y/")]).is_err());
+}
+
 #[test]
 fn test_import_refs_reimport_conflicted_remote_bookmark() {
     let test_repo = TestRepo::init_with_backend(TestRepoBackend::Git);
@@ -1451,6 +1587,72 @@ fn test_import_refs_reserved_remote_name() {
     );
 }
 
+#[cfg(unix)]
+#[test]
+fn test_git_refs_fingerprint() {
+    use std::time::Duration;
+    use std::time::SystemTime;
+
+    let test_repo = TestRepo::init_with_backend(TestRepoBackend::Git);
+    let repo = &test_repo.repo;
+    let git_repo = get_git_repo(repo);
+    let backdate_refs = |time: SystemTime| {
+        let refs_dir = git_repo.common_dir().join("refs");
+        let mut dirs = vec![refs_dir];
+        while let Some(dir) = dirs.pop() {
+            for entry in fs::read_dir(&dir).unwrap() {
+                let entry = entry.unwrap();
+                if entry.file_type().unwrap().is_dir() {
+                    dirs.push(entry.path());
+                } else {
+                    fs::File::open(entry.path())
+                        .unwrap()
+                        .set_modified(time)
+                        .unwrap();
+                }
+            }
+            fs::File::open(&dir).unwrap().set_modified(time).unwrap();
+        }
+    };
+    let past = SystemTime::now() - Duration::from_secs(60);
+
+    // Recently modified refs can't be fingerprinted
+    let commit1 = empty_git_commit(&git_repo, "refs/heads/main", &[]);
+    assert_eq!(git::git_refs_fingerprint(repo.store()), None);
+
+    backdate_refs(past);
+    let fingerprint1 = git::git_refs_fingerprint(repo.store()).unwrap();
+    assert_eq!(
+        git::git_refs_fingerprint(repo.store()),
+        Some(fingerprint1.clone())
+    );
+
+    // Adding a ref in a new directory changes the fingerprint
+    git_ref(&git_repo, "refs/heads/feature/1", commit1);
+    backdate_refs(past);
+    let fingerprint2 = git::git_refs_fingerprint(repo.store()).unwrap();
+    assert_ne!(fingerprint1, fingerprint2);
+
+    // Updating a ref bumps the modification time of the directory
+    empty_git_commit(&git_repo, "refs/heads/main", &[commit1]);
+    assert_eq!(git::git_refs_fingerprint(repo.store()), None);
+    backdate_refs(past - Duration::from_secs(60));
+    let fingerprint3 = git::git_refs_fingerprint(repo.store()).unwrap();
+    assert_ne!(fingerprint2, fingerprint3);
+
+    // Editing a loose ref in place doesn't bump the modification time of the
+    // directory, but the file's
+    let main_path = git_repo.common_dir().join("refs/heads/main");
+    fs::write(&main_path, format!("{commit1}\n")).unwrap();
+    backdate_refs(past - Duration::from_secs(60));
+    fs::File::open(&main_path)
+        .unwrap()
+        .set_modified(past - Duration::from_secs(30))
+        .unwrap();
+    let fingerprint4 = git::git_refs_fingerprint(repo.store()).unwrap();
+    assert_ne!(fingerprint3, fingerprint4);
+}
+
 #[test]
 fn test_import_some_refs() {
     let test_workspace = TestRepo::init_with_backend(TestRepoBackend::Git);
@@ -1820,7 +2022,7 @@ fn test_export_refs_no_detach() {
     mut_repo.rebase_descendants().block_on().unwrap();
 
     // Do an initial export to make sure `main` is considered
-    let stats = git::export_refs(mut_repo).unwrap();
+    let stats = git::export_refs(mut_repo, &GitRefMapping::default()).unwrap();
     assert!(stats.failed_bookmarks.is_empty());
     assert!(stats.failed_tags.is_empty());
     assert_eq!(
@@ -1863,7 +2065,7 @@ fn test_export_refs_bookmark_changed() {
     git::import_head(mut_repo).unwrap();
     git::import_refs(mut_repo, &import_options).unwrap();
     mut_repo.rebase_descendants().block_on().unwrap();
-    let stats = git::export_refs(mut_repo).unwrap();
+    let stats = git::export_refs(mut_repo, &GitRefMapping::default()).unwrap();
     assert!(stats.failed_bookmarks.is_empty());
     assert!(stats.failed_tags.is_empty());
 
@@ -1871,7 +2073,7 @@ fn test_export_refs_bookmark_changed() {
         .set_parents(vec![jj_id(commit)])
         .write_unwrap();
     mut_repo.set_local_bookmark_target("main".as_ref(), RefTarget::normal(new_commit.id().clone()));
-    let stats = git::export_refs(mut_repo).unwrap();
+    let stats = git::export_refs(mut_repo, &GitRefMapping::default()).unwrap();
     assert!(stats.failed_bookmarks.is_empty());
     assert!(stats.failed_tags.is_empty());
     assert_eq!(
@@ -1922,7 +2124,7 @@ fn test_export_refs_tag_changed() {
     let stats = git::import_refs(mut_repo, &import_options).unwrap();
     assert_eq!(stats.changed_remote_tags.len(), 4);
     mut_repo.rebase_descendants().block_on().unwrap();
-    let stats = git::export_refs(mut_repo).unwrap();
+    let stats = git::export_refs(mut_repo, &GitRefMapping::default()).unwrap();
     assert!(stats.failed_bookmarks.is_empty());
     assert!(stats.failed_tags.is_empty());
 
@@ -1935,7 +2137,7 @@ fn test_export_refs_tag_changed() {
     mut_repo.set_local_tag_target("annotated-change".as_ref(), new_target.clone());
     mut_repo.set_local_tag_target("annotated-delete".as_ref(), RefTarget::absent());
     mut_repo.set_local_tag_target("new".as_ref(), new_target.clone());
-    let stats = git::export_refs(mut_repo).unwrap();
+    let stats = git::export_refs(mut_repo, &GitRefMapping::default()).unwrap();
     assert!(stats.failed_bookmarks.is_empty());
     assert!(stats.failed_tags.is_empty());
     assert_eq!(
@@ -2010,7 +2212,7 @@ fn test_export_refs_current_bookmark_changed() {
     git::import_head(mut_repo).unwrap();
     git::import_refs(mut_repo, &import_options).unwrap();
     mut_repo.rebase_descendants().block_on().unwrap();
-    let stats = git::export_refs(mut_repo).unwrap();
+    let stats = git::export_refs(mut_repo, &GitRefMapping::default()).unwrap();
     assert!(stats.failed_bookmarks.is_empty());
     assert!(stats.failed_tags.is_empty());
 
@@ -2018,7 +2220,7 @@ fn test_export_refs_current_bookmark_changed() {
         .set_parents(vec![jj_id(commit1)])
         .write_unwrap();
     mut_repo.set_local_bookmark_target("main".as_ref(), RefTarget::normal(new_commit.id().clone()));
-    let stats = git::export_refs(mut_repo).unwrap();
+    let stats = git::export_refs(mut_repo, &GitRefMapping::default()).unwrap();
     assert!(stats.failed_bookmarks.is_empty());
     assert!(stats.failed_tags.is_empty());
     assert_eq!(
@@ -2052,7 +2254,7 @@ fn test_export_refs_current_tag_changed() {
     git::import_head(mut_repo).unwrap();
     git::import_refs(mut_repo, &import_options).unwrap();
     mut_repo.rebase_descendants().block_on().unwrap();
-    let stats = git::export_refs(mut_repo).unwrap();
+    let stats = git::export_refs(mut_repo, &GitRefMapping::default()).unwrap();
     assert!(stats.failed_bookmarks.is_empty());
     assert!(stats.failed_tags.is_empty());
 
@@ -2060,7 +2262,7 @@ fn test_export_refs_current_tag_changed() {
         .set_parents(vec![jj_id(commit1)])
         .write_unwrap();
     mut_repo.set_local_tag_target("v1.0".as_ref(), RefTarget::normal(new_commit.id().clone()));
-    let stats = git::export_refs(mut_repo).unwrap();
+    let stats = git::export_refs(mut_repo, &GitRefMapping::default()).unwrap();
     assert!(stats.failed_bookmarks.is_empty());
     assert!(stats.failed_tags.is_empty());
     assert_eq!(
@@ -2093,7 +2295,7 @@ fn test_export_refs_unborn_git_bookmark(move_placeholder_ref: bool) {
     git::import_head(mut_repo).unwrap();
     git::import_refs(mut_repo, &import_options).unwrap();
     mut_repo.rebase_descendants().block_on().unwrap();
-    let stats = git::export_refs(mut_repo).unwrap();
+    let stats = git::export_refs(mut_repo, &GitRefMapping::default()).unwrap();
     assert!(stats.failed_bookmarks.is_empty());
     assert!(stats.failed_tags.is_empty());
     assert!(git_repo.head().unwrap().is_unborn(), "HEAD is unborn");
@@ -2110,7 +2312,7 @@ fn test_export_refs_unborn_git_bookmark(move_placeholder_ref: bool) {
             )
             .unwrap();
     }
-    let stats = git::export_refs(mut_repo).unwrap();
+    let stats = git::export_refs(mut_repo, &GitRefMapping::default()).unwrap();
     assert!(stats.failed_bookmarks.is_empty());
     assert!(stats.failed_tags.is_empty());
     assert_eq!(
@@ -2165,7 +2367,7 @@ fn test_export_import_sequence() {
     mut_repo.set_local_bookmark_target("main".as_ref(), RefTarget::normal(commit_b.id().clone()));
 
     // Export the bookmark to git
-    let stats = git::export_refs(mut_repo).unwrap();
+    let stats = git::export_refs(mut_repo, &GitRefMapping::default()).unwrap();
     assert!(stats.failed_bookmarks.is_empty());
     assert!(stats.failed_tags.is_empty());
     assert_eq!(
@@ -2229,7 +2431,7 @@ fn test_import_export_non_tracking_bookmark() {
     );
 
     // Export the bookmark to git
-    let stats = git::export_refs(mut_repo).unwrap();
+    let stats = git::export_refs(mut_repo, &GitRefMapping::default()).unwrap();
     assert!(stats.failed_bookmarks.is_empty());
     assert!(stats.failed_tags.is_empty());
     assert_eq!(
@@ -2319,7 +2521,7 @@ fn test_export_conflicts() {
     mut_repo
         .set_local_bookmark_target("feature".as_ref(), RefTarget::normal(commit_a.id().clone()));
     mut_repo.set_local_tag_target("v1.0".as_ref(), RefTarget::normal(commit_a.id().clone()));
-    let stats = git::export_refs(mut_repo).unwrap();
+    let stats = git::export_refs(mut_repo, &GitRefMapping::default()).unwrap();
     assert!(stats.failed_bookmarks.is_empty());
     assert!(stats.failed_tags.is_empty());
 
@@ -2332,7 +2534,7 @@ fn test_export_conflicts() {
     );
     mut_repo.set_local_bookmark_target("feature".as_ref(), conflict_target.clone());
     mut_repo.set_local_tag_target("v1.0".as_ref(), conflict_target.clone());
-    let stats = git::export_refs(mut_repo).unwrap();
+    let stats = git::export_refs(mut_repo, &GitRefMapping::default()).unwrap();
     assert!(stats.failed_bookmarks.is_empty());
     assert!(stats.failed_tags.is_empty());
     assert_eq!(
@@ -2394,7 +2596,7 @@ fn test_export_bookmark_on_root_commit() {
         "on_root".as_ref(),
         RefTarget::normal(mut_repo.store().root_commit_id().clone()),
     );
-    let stats = git::export_refs(mut_repo).unwrap();
+    let stats = git::export_refs(mut_repo, &GitRefMapping::default()).unwrap();
     assert_eq!(stats.failed_bookmarks.len(), 1);
     assert_eq!(
         stats.failed_bookmarks[0].0.as_ref(),
@@ -2433,7 +2635,7 @@ fn test_export_partial_failure() {
             state: RemoteRefState::Tracked,
         },
     );
-    let stats = git::export_refs(mut_repo).unwrap();
+    let stats = git::export_refs(mut_repo, &GitRefMapping::default()).unwrap();
     assert_eq!(stats.failed_bookmarks.len(), 3);
     assert_eq!(
         stats.failed_bookmarks[0].0.as_ref(),
@@ -2508,7 +2710,7 @@ fn test_export_partial_failure() {
     // Now remove the `main` bookmark and make sure that the `main/sub` gets
     // exported even though it didn't change
     mut_repo.set_local_bookmark_target("main".as_ref(), RefTarget::absent());
-    let stats = git::export_refs(mut_repo).unwrap();
+    let stats = git::export_refs(mut_repo, &GitRefMapping::default()).unwrap();
     assert_eq!(stats.failed_bookmarks.len(), 2);
     assert_eq!(
         stats.failed_bookmarks[0].0.as_ref(),
@@ -2608,7 +2810,7 @@ fn test_export_reexport_transitions() {
         mut_repo
             .set_local_bookmark_target(bookmark.as_ref(), RefTarget::normal(commit_a.id().clone()));
     }
-    let stats = git::export_refs(mut_repo).unwrap();
+    let stats = git::export_refs(mut_repo, &GitRefMapping::default()).unwrap();
     assert!(stats.failed_bookmarks.is_empty());
     assert!(stats.failed_tags.is_empty());
 
@@ -2666,7 +2868,7 @@ fn test_export_reexport_transitions() {
     // TODO: The bookmarks that we made conflicting changes to should have failed to
     // export. They should have been unchanged in git and in
     // mut_repo.view().git_refs().
-    let stats = git::export_refs(mut_repo).unwrap();
+    let stats = git::export_refs(mut_repo, &GitRefMapping::default()).unwrap();
     assert_eq!(
         stats
             .failed_bookmarks
@@ -2750,7 +2952,7 @@ fn test_export_undo_reexport() {
     };
     mut_repo.set_local_bookmark_target("main".as_ref(), target_a.clone());
     mut_repo.set_local_tag_target("v1.0".as_ref(), target_a.clone());
-    let stats = git::export_refs(mut_repo).unwrap();
+    let stats = git::export_refs(mut_repo, &GitRefMapping::default()).unwrap();
     assert!(stats.failed_bookmarks.is_empty());
     assert!(stats.failed_tags.is_empty());
     assert_eq!(
@@ -2785,7 +2987,7 @@ fn test_export_undo_reexport() {
     mut_repo.set_remote_tag(remote_symbol("v1.0", "git"), RemoteRef::absent());
 
     // Reexport should update the Git-tracking bookmark/tag
-    let stats = git::export_refs(mut_repo).unwrap();
+    let stats = git::export_refs(mut_repo, &GitRefMapping::default()).unwrap();
     assert!(stats.failed_bookmarks.is_empty());
     assert!(stats.failed_tags.is_empty());
     assert_eq!(
@@ -3780,6 +3982,55 @@ fn test_fetch_no_such_remote() {
     assert!(matches!(result, Err(GitFetchError::NoSuchRemote(_))));
 }
 
+#[test]
+fn test_fetch_commit_ref() {
+    let test_data = GitRepoData::create();
+    let _main_oid = empty_git_commit(&test_data.origin_repo, "refs/heads/main", &[]);
+    let change_oid = empty_git_commit(&test_data.origin_repo, "refs/changes/34/1234/1", &[]);
+    let subprocess_options =
+        GitSubprocessOptions::from_settings(test_data.repo.settings()).unwrap();
+    let import_options = default_import_options();
+
+    let mut tx = test_data.repo.start_transaction();
+    let mut fetcher = GitFetch::new(tx.repo_mut(), subprocess_options, &import_options).unwrap();
+    let refs = fetcher
+        .list_remote_refs("origin".as_ref(), "refs/changes/34/1234/*")
+        .unwrap();
+    assert_eq!(
+        refs,
+        [(
+            GitRefNameBuf::from("refs/changes/34/1234/1"),
+            jj_id(change_oid)
+        )]
+    );
+    let commit_id = fetcher
+        .fetch_commit_ref(
+            "origin".as_ref(),
+            GitRefName::new("refs/changes/34/1234/1"),
+            &mut NullCallback,
+        )
+        .unwrap();
+    assert_eq!(commit_id, Some(jj_id(change_oid)));
+    let result = fetcher.fetch_commit_ref(
+        "origin".as_ref(),
+        GitRefName::new("refs/changes/34/1234/2"),
+        &mut NullCallback,
+    );
+    assert_matches!(result, Ok(None));
+
+    // Neither the temporary ref nor remote bookmarks should be left behind.
+    let stats = fetcher.import_refs().unwrap();
+    assert!(stats.changed_remote_bookmarks.is_empty());
+    assert!(
+        test_data
+            .git_repo
+            .try_find_reference("refs/jj/fetched-commit")
+            .unwrap()
+            .is_none()
+    );
+    assert!(test_data.git_repo.find_object(change_oid).is_ok());
+}
+
 #[test]
 fn test_fetch_multiple_branches() {
     let test_data = GitRepoData::create();
@@ -4074,7 +4325,7 @@ fn test_fetch_export_annotated_tags() {
     // Fetch tags, merge remote tags, and export merged local tags to Git
     let mut tx = test_data.repo.start_transaction();
     fetch_import(tx.repo_mut());
-    git::export_refs(tx.repo_mut()).unwrap();
+    git::export_refs(tx.repo_mut(), &GitRefMapping::default()).unwrap();
     let repo = tx.commit("test").block_on().unwrap();
 
     assert_eq!(repo.view().get_local_tag("tag1".as_ref()), &target1);
@@ -4259,7 +4510,7 @@ fn test_fetch_rejected_tag_updates() {
     let target2 = RefTarget::normal(commit2.id().clone());
     tx.repo_mut()
         .set_local_tag_target("tag".as_ref(), target2.clone());
-    git::export_refs(tx.repo_mut()).unwrap();
+    git::export_refs(tx.repo_mut(), &GitRefMapping::default()).unwrap();
     let repo = tx.commit("test").block_on().unwrap();
 
     // Tags shouldn't be "force" updated. (#7528)
@@ -4396,6 +4647,7 @@ fn test_push_bookmarks_success() {
         subprocess_options,
         "origin".as_ref(),
         &targets,
+        &GitPushRefSpecs::default(),
         &mut NullCallback,
     )
     .unwrap();
@@ -4447,6 +4699,99 @@ fn test_push_bookmarks_success() {
     assert!(!tx.repo().has_changes());
 }
 
+#[test]
+fn test_push_bookmarks_with_refspecs() {
+    let settings = testutils::user_settings();
+    let temp_dir = testutils::new_temp_dir();
+    let setup = set_up_push_repos(&settings, &temp_dir);
+    let mut tx = setup.jj_repo.start_transaction();
+    let subprocess_options = GitSubprocessOptions::from_settings(&settings).unwrap();
+
+    let targets = GitBranchPushTargets {
+        branch_updates: vec![(
+            "topic".into(),
+            BookmarkPushUpdate {
+                old_target: None,
+                new_target: Some(setup.child_of_main_commit.id().clone()),
+            },
+        )],
+    };
+    let refspecs =
+        GitPushRefSpecs::new(&["refs/heads/*:refs/heads/users/me/*".to_owned()]).unwrap();
+    let stats = git::push_branches(
+        tx.repo_mut(),
+        subprocess_options,
+        "origin".as_ref(),
+        &targets,
+        &refspecs,
+        &mut NullCallback,
+    )
+    .unwrap();
+    insta::assert_debug_snapshot!(stats, @r#"
+    GitPushStats {
+        pushed: [
+            GitRefNameBuf(
+                "refs/heads/users/me/topic",
+            ),
+        ],
+        rejected: [],
+        remote_rejected: [],
+        unexported_bookmarks: [],
+    }
+    "#);
+
+    // Check that the renamed ref got created in the source repo
+    let source_repo = testutils::git::open(&setup.source_repo_dir);
+    let new_target = source_repo
+        .find_reference("refs/heads/users/me/topic")
+        .unwrap();
+    assert_eq!(
+        new_target.target().id(),
+        git_id(&setup.child_of_main_commit)
+    );
+    assert!(
+        source_repo
+            .try_find_reference("refs/heads/topic")
+            .unwrap()
+            .is_none()
+    );
+
+    // Check that the remote bookmark is recorded under the bookmark name
+    let view = tx.repo().view();
+    assert_eq!(
+        *view.get_remote_bookmark(remote_symbol("topic", "origin")),
+        RemoteRef {
+            target: RefTarget::normal(setup.child_of_main_commit.id().clone()),
+            state: RemoteRefState::Tracked,
+        },
+    );
+}
+
+#[test]
+fn test_push_refspecs_mapping() {
+    let refspecs = GitPushRefSpecs::new(&[
+        "+refs/heads/main:refs/heads/trunk".to_owned(),
+        "refs/heads/feature/*:refs/for/main/*".to_owned(),
+    ])
+    .unwrap();
+    let remote_ref = |name: &str| refspecs.to_remote_ref_name(name.as_ref());
+    assert_eq!(remote_ref("main").as_str(), "refs/heads/trunk");
+    assert_eq!(remote_ref("feature/foo").as_str(), "refs/for/main/foo");
+    assert_eq!(remote_ref("feature/").as_str(), "refs/heads/feature/");
+    assert_eq!(remote_ref("mainline").as_str(), "refs/heads/mainline");
+
+    let parse_err = |refspec: &str| {
+        GitPushRefSpecs::new(&[refspec.to_owned()])
+            .unwrap_err()
+            .to_string()
+    };
+    insta::assert_snapshot!(parse_err("refs/heads/main"), @"Invalid push refspec `refs/heads/main`: expected `<src>:<dst>`");
+    insta::assert_snapshot!(parse_err("refs/tags/*:refs/tags/*"), @"Invalid push refspec `refs/tags/*:refs/tags/*`: source must be in `refs/heads/`");
+    insta::assert_snapshot!(parse_err("refs/heads/*:main"), @"Invalid push refspec `refs/heads/*:main`: destination must be a full ref name");
+    insta::assert_snapshot!(parse_err("refs/heads/*:refs/heads/main"), @"Invalid push refspec `refs/heads/*:refs/heads/main`: source and destination must both contain one `*`, or neither");
+    insta::assert_snapshot!(parse_err("refs/heads/*/*:refs/heads/*/*"), @"Invalid push refspec `refs/heads/*/*:refs/heads/*/*`: source and destination must both contain one `*`, or neither");
+}
+
 #[test]
 fn test_push_bookmarks_deletion() {
     let settings = testutils::user_settings();
@@ -4475,6 +4820,7 @@ fn test_push_bookmarks_deletion() {
         subprocess_options,
         "origin".as_ref(),
         &targets,
+        &GitPushRefSpecs::default(),
         &mut NullCallback,
     )
     .unwrap();
@@ -4553,6 +4899,7 @@ fn test_push_bookmarks_mixed_deletion_and_addition() {
         subprocess_options,
         "origin".as_ref(),
         &targets,
+        &GitPushRefSpecs::default(),
         &mut NullCallback,
     )
     .unwrap();
@@ -4634,6 +4981,7 @@ fn test_push_bookmarks_not_fast_forward() {
         subprocess_options,
         "origin".as_ref(),
         &targets,
+        &GitPushRefSpecs::default(),
         &mut NullCallback,
     )
     .unwrap();
@@ -4687,6 +5035,7 @@ fn test_push_bookmarks_partial_success() {
         subprocess_options,
         "origin".as_ref(),
         &targets,
+        &GitPushRefSpecs::default(),
         &mut NullCallback,
     )
     .unwrap();
@@ -4796,6 +5145,7 @@ fn test_push_bookmarks_unmapped_refs() {
         subprocess_options,
         "origin".as_ref(),
         &targets,
+        &GitPushRefSpecs::default(),
         &mut NullCallback,
     )
     .unwrap();
@@ -5182,6 +5532,7 @@ fn test_push_environment_options() {
         subprocess_options,
         "origin".as_ref(),
         &targets,
+        &GitPushRefSpecs::default(),
         &mut NullCallback,
     )
     .unwrap();
@@ -5752,6 +6103,51 @@ fn test_remote_rename_refs() {
     );
 }
 
+#[test]
+fn test_export_import_change_id_notes() {
+    let mut config = base_user_config();
+    let mut layer = ConfigLayer::empty(ConfigSource::Default);
+    layer
+        .set_value("git.write-change-id-header", false)
+        .unwrap();
+    layer.set_value("git.change-id-notes", true).unwrap();
+    config.add_layer(layer);
+    let settings = UserSettings::from_config(config).unwrap();
+    let temp_dir = testutils::new_temp_dir();
+    let git_repo_path = temp_dir.path().join("git");
+    let git_repo = testutils::git::init_bare(&git_repo_path);
+
+    let workspace_root1 = temp_dir.path().join("repo1");
+    fs::create_dir(&workspace_root1).unwrap();
+    let (_workspace1, repo1) =
+        Workspace::init_external_git(&settings, &workspace_root1, &git_repo_path)
+            .block_on()
+            .unwrap();
+    let mut tx = repo1.start_transaction();
+    let commit1 = write_random_commit(tx.repo_mut());
+    let commit2 = write_random_commit_with_parents(tx.repo_mut(), &[&commit1]);
+    tx.repo_mut()
+        .set_local_bookmark_target("main".as_ref(), RefTarget::normal(commit2.id().clone()));
+    git::export_refs(tx.repo_mut(), &GitRefMapping::default()).unwrap();
+    tx.commit("test").block_on().unwrap();
+    assert!(git_repo.find_reference("refs/notes/jj-change-id").is_ok());
+
+    // Another repo backed by the same Git repo picks up the change ids
+    let workspace_root2 = temp_dir.path().join("repo2");
+    fs::create_dir(&workspace_root2).unwrap();
+    let (_workspace2, repo2) =
+        Workspace::init_external_git(&settings, &workspace_root2, &git_repo_path)
+            .block_on()
+            .unwrap();
+    let mut tx = repo2.start_transaction();
+    git::import_refs(tx.repo_mut(), &default_import_options()).unwrap();
+    let repo2 = tx.commit("test").block_on().unwrap();
+    let imported_commit1 = repo2.store().get_commit(commit1.id()).unwrap();
+    let imported_commit2 = repo2.store().get_commit(commit2.id()).unwrap();
+    assert_eq!(imported_commit1.change_id(), commit1.change_id());
+    assert_eq!(imported_commit2.change_id(), commit2.change_id());
+}
+
 fn user_settings_without_change_id() -> UserSettings {
     let mut config = base_user_config();
     let mut layer = ConfigLayer::empty(ConfigSource::Default);
@@ -5875,6 +6271,7 @@ fn default_import_options() -> GitImportOptions {
         auto_local_bookmark: false,
         abandon_unreachable_commits: true,
         remote_auto_track_bookmarks: HashMap::new(),
+        ref_mapping: GitRefMapping::default(),
     }
 }
 
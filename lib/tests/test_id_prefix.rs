@@ -244,6 +244,74 @@ fn test_id_prefix() {
         SingleMatch(vec![root_commit_id.clone()])
     );
 
+    // Disambiguate within multiple revsets in order of priority
+    // ---------------------------------------------------------------------------------------------
+    let context = context.disambiguate_within_scopes([
+        RevsetExpression::commit(commits[7].id().clone()),
+        RevsetExpression::commits(vec![commits[10].id().clone(), commits[5].id().clone()]),
+    ]);
+    let index = context.populate(repo.as_ref()).unwrap();
+    assert_eq!(shortest_commit_prefix_len(&index, commits[7].id()), 1);
+    assert_eq!(
+        resolve_commit_prefix(&index, prefix("1")),
+        SingleMatch(commits[7].id().clone())
+    );
+    // The prefix must not match commits in the first set
+    assert_eq!(shortest_commit_prefix_len(&index, commits[10].id()), 2);
+    assert_eq!(
+        resolve_commit_prefix(&index, prefix("19")),
+        SingleMatch(commits[10].id().clone())
+    );
+    assert_eq!(shortest_commit_prefix_len(&index, commits[5].id()), 1);
+    assert_eq!(
+        resolve_commit_prefix(&index, prefix("b")),
+        SingleMatch(commits[5].id().clone())
+    );
+    // The prefix must not match commits in any set
+    assert_eq!(shortest_commit_prefix_len(&index, commits[22].id()), 3);
+    assert_eq!(
+        resolve_commit_prefix(&index, prefix("b97")),
+        SingleMatch(commits[22].id().clone())
+    );
+
+    // List candidates of ambiguous prefixes
+    // ---------------------------------------------------------------------------------------------
+    let index = IdPrefixIndex::empty();
+    assert_eq!(
+        index
+            .commit_prefix_candidates(repo.as_ref(), &prefix("1"))
+            .unwrap(),
+        vec![commits[10].id().clone(), commits[7].id().clone()]
+    );
+    assert_eq!(
+        index
+            .change_prefix_candidates(repo.as_ref(), &prefix("4"))
+            .unwrap(),
+        vec![
+            commits[17].id().clone(),
+            commits[16].id().clone(),
+            commits[2].id().clone(),
+        ]
+    );
+    // Candidates in the disambiguation sets take precedence
+    let context = context.disambiguate_within(RevsetExpression::commits(vec![
+        commits[17].id().clone(),
+        commits[2].id().clone(),
+    ]));
+    let index = context.populate(repo.as_ref()).unwrap();
+    assert_eq!(
+        index
+            .change_prefix_candidates(repo.as_ref(), &prefix("4"))
+            .unwrap(),
+        vec![commits[17].id().clone(), commits[2].id().clone()]
+    );
+    assert_eq!(
+        index
+            .commit_prefix_candidates(repo.as_ref(), &prefix("1"))
+            .unwrap(),
+        vec![commits[10].id().clone(), commits[7].id().clone()]
+    );
+
     // Disambiguate within revset that fails to evaluate
     // ---------------------------------------------------------------------------------------------
     let expression = RevsetExpression::symbol("nonexistent".to_string());
@@ -20,6 +20,7 @@ use itertools::Itertools as _;
 use jj_lib::backend::ChangeId;
 use jj_lib::backend::CommitId;
 use jj_lib::commit::Commit;
+use jj_lib::default_index::DefaultIndexProblem;
 use jj_lib::default_index::DefaultIndexStore;
 use jj_lib::default_index::DefaultIndexStoreError;
 use jj_lib::default_index::DefaultMutableIndex;
@@ -702,6 +703,50 @@ fn test_reindex_corrupt_segment_files() {
     assert!(index_has_id(repo.index(), commit_a.id()));
 }
 
+#[test]
+fn test_reindex_segment_checksum_mismatch() {
+    let settings = testutils::user_settings();
+    let test_repo = TestRepo::init();
+    let test_env = &test_repo.env;
+    let repo = &test_repo.repo;
+
+    let mut tx = repo.start_transaction();
+    let commit_a = write_random_commit(tx.repo_mut());
+    let repo = tx.commit("test").block_on().unwrap();
+    let default_index_store: &DefaultIndexStore = repo.index_store().downcast_ref().unwrap();
+    let problems = default_index_store
+        .verify_index_at_operation(repo.operation(), repo.store())
+        .block_on()
+        .unwrap();
+    assert!(problems.is_empty(), "{problems:?}");
+
+    // Flip a bit in the last entry, which keeps the file structurally valid
+    let segments_dir = test_repo.repo_path().join("index").join("segments");
+    for entry in segments_dir.read_dir().unwrap() {
+        let entry = entry.unwrap();
+        let mut data = fs::read(entry.path()).unwrap();
+        *data.last_mut().unwrap() ^= 1;
+        fs::write(entry.path(), data).unwrap();
+    }
+    let problems = default_index_store
+        .verify_index_at_operation(repo.operation(), repo.store())
+        .block_on()
+        .unwrap();
+    assert_matches!(problems.as_slice(), [DefaultIndexProblem::Corrupt(_)]);
+
+    // The checksums aren't verified when loading, so the index has to be
+    // rebuilt explicitly
+    default_index_store.reinit().unwrap();
+    let repo = test_env.load_repo_at_head(&settings, test_repo.repo_path());
+    assert!(index_has_id(repo.index(), commit_a.id()));
+    let default_index_store: &DefaultIndexStore = repo.index_store().downcast_ref().unwrap();
+    let problems = default_index_store
+        .verify_index_at_operation(repo.operation(), repo.store())
+        .block_on()
+        .unwrap();
+    assert!(problems.is_empty(), "{problems:?}");
+}
+
 #[test]
 fn test_reindex_from_merged_operation() {
     let test_repo = TestRepo::init();
@@ -892,6 +937,45 @@ fn test_changed_path_segments() {
     );
 }
 
+#[test]
+fn test_changed_path_segments_corrupt() {
+    let settings = testutils::user_settings();
+    let test_repo = TestRepo::init();
+    let test_env = &test_repo.env;
+    let repo = enable_changed_path_index(&test_repo.repo);
+    let root_commit_id = repo.store().root_commit_id();
+
+    let tree = create_tree(&repo, &[(repo_path("a"), "")]);
+    let mut tx = repo.start_transaction();
+    let commit = tx
+        .repo_mut()
+        .new_commit(vec![root_commit_id.clone()], tree)
+        .write_unwrap();
+    tx.commit("test").block_on().unwrap();
+
+    // Truncate the changed-path segment files
+    let segments_dir = test_repo.repo_path().join("index").join("changed_paths");
+    for entry in segments_dir.read_dir().unwrap() {
+        let entry = entry.unwrap();
+        let mut data = fs::read(entry.path()).unwrap();
+        data.pop();
+        fs::write(entry.path(), data).unwrap();
+    }
+
+    // The changed-path index is loaded on first access, so the commit index
+    // is still usable, but path queries report the corrupt changed-path index
+    let repo = test_env.load_repo_at_head(&settings, test_repo.repo_path());
+    assert!(index_has_id(repo.index(), commit.id()));
+    assert!(repo.index().changed_paths_in_commit(commit.id()).is_err());
+
+    let default_index_store: &DefaultIndexStore = repo.index_store().downcast_ref().unwrap();
+    let problems = default_index_store
+        .verify_index_at_operation(repo.operation(), repo.store())
+        .block_on()
+        .unwrap();
+    assert_matches!(problems.as_slice(), [DefaultIndexProblem::Corrupt(_)]);
+}
+
 #[test]
 fn test_build_changed_path_segments() {
     let test_repo = TestRepo::init();
@@ -41,17 +41,21 @@ use jj_lib::file_util::symlink_file;
 use jj_lib::files::FileMergeHunkLevel;
 use jj_lib::fsmonitor::FsmonitorSettings;
 use jj_lib::git::get_git_backend;
+use jj_lib::git_backend::FileWriteStats;
 use jj_lib::gitignore::GitIgnoreFile;
 use jj_lib::local_working_copy::LocalWorkingCopy;
 use jj_lib::local_working_copy::TreeState;
 use jj_lib::local_working_copy::TreeStateSettings;
 use jj_lib::matchers::FilesMatcher;
+use jj_lib::matchers::PrefixMatcher;
+use jj_lib::matchers::UnionMatcher;
 use jj_lib::merge::Merge;
 use jj_lib::merge::SameChange;
 use jj_lib::merged_tree::MergedTree;
 use jj_lib::merged_tree_builder::MergedTreeBuilder;
 use jj_lib::op_store::OperationId;
 use jj_lib::ref_name::WorkspaceName;
+use jj_lib::ref_name::WorkspaceNameBuf;
 use jj_lib::repo::ReadonlyRepo;
 use jj_lib::repo::Repo as _;
 use jj_lib::repo_path::RepoPath;
@@ -67,6 +71,7 @@ use jj_lib::working_copy::UntrackedReason;
 use jj_lib::working_copy::WorkingCopy as _;
 use jj_lib::workspace::Workspace;
 use jj_lib::workspace::default_working_copy_factories;
+use jj_lib::workspace::default_working_copy_factory;
 use pollster::FutureExt as _;
 use test_case::test_case;
 use testutils::CommitBuilderExt as _;
@@ -838,6 +843,74 @@ fn test_checkout_discard() {
     assert!(!reloaded_wc.file_states().unwrap().contains_path(file2_path));
 }
 
+#[test]
+fn test_checkout_interrupted() {
+    // Start a checkout, and abandon it before the new state is saved as if the
+    // process crashed. The checkout should be completed the next time the
+    // working copy is locked.
+    let mut test_workspace = TestWorkspace::init();
+    let repo = test_workspace.repo.clone();
+    let workspace_root = test_workspace.workspace.workspace_root().to_owned();
+
+    let file1_path = repo_path("file1");
+    let file2_path = repo_path("file2");
+
+    let store = repo.store();
+    let tree1 = create_tree(&repo, &[(file1_path, "contents")]);
+    let tree2 = create_tree(&repo, &[(file2_path, "contents")]);
+    let commit1 = commit_with_tree(repo.store(), tree1);
+    let commit2 = commit_with_tree(repo.store(), tree2.clone());
+
+    let ws = &mut test_workspace.workspace;
+    ws.check_out(repo.op_id().clone(), None, &commit1)
+        .block_on()
+        .unwrap();
+    let wc: &LocalWorkingCopy = ws.working_copy().downcast_ref().unwrap();
+    let state_path = wc.state_path().to_path_buf();
+
+    // Start a checkout and abandon it. The journal is left behind.
+    let mut locked_ws = ws.start_working_copy_mutation().unwrap();
+    locked_ws
+        .locked_wc()
+        .check_out(&commit2)
+        .block_on()
+        .unwrap();
+    drop(locked_ws);
+    assert!(state_path.join("pending_checkout").is_file());
+    let reloaded_wc = LocalWorkingCopy::load(
+        store.clone(),
+        workspace_root.clone(),
+        state_path.clone(),
+        repo.settings(),
+    )
+    .unwrap();
+    assert!(reloaded_wc.file_states().unwrap().contains_path(file1_path));
+
+    // Locking the working copy again completes the checkout
+    let mut locked_ws = ws.start_working_copy_mutation().unwrap();
+    assert_eq!(
+        locked_ws.locked_wc().old_tree().tree_ids_and_labels(),
+        tree2.tree_ids_and_labels()
+    );
+    assert!(!state_path.join("pending_checkout").exists());
+    let reloaded_wc = LocalWorkingCopy::load(
+        store.clone(),
+        workspace_root.clone(),
+        state_path.clone(),
+        repo.settings(),
+    )
+    .unwrap();
+    assert!(!reloaded_wc.file_states().unwrap().contains_path(file1_path));
+    assert!(reloaded_wc.file_states().unwrap().contains_path(file2_path));
+    assert!(!file1_path.to_fs_path_unchecked(&workspace_root).is_file());
+    assert!(file2_path.to_fs_path_unchecked(&workspace_root).is_file());
+    drop(locked_ws);
+
+    // The resumed working copy is clean
+    let new_tree = test_workspace.snapshot().unwrap();
+    assert_tree_eq!(new_tree, tree2);
+}
+
 #[test]
 fn test_snapshot_file_directory_transition() {
     let mut test_workspace = TestWorkspace::init();
@@ -1454,6 +1527,69 @@ fn test_snapshot_racy_timestamps() {
     }
 }
 
+#[test]
+fn test_snapshot_dedup_across_workspaces() {
+    // Tests that identical files snapshotted by workspaces are written to the
+    // backend only once, even if the workspaces are loaded separately like in
+    // different processes.
+    let settings = testutils::user_settings();
+    let mut test_workspace = TestWorkspace::init_with_backend(TestRepoBackend::Git);
+    let repo = test_workspace.repo.clone();
+    let ws1_root = test_workspace.workspace.workspace_root().to_owned();
+    let ws2_root = test_workspace.root_dir().join("ws2_root");
+    std::fs::create_dir(&ws2_root).unwrap();
+    Workspace::init_workspace_with_existing_repo(
+        &ws2_root,
+        test_workspace.repo_path(),
+        &repo,
+        &*default_working_copy_factory(),
+        WorkspaceNameBuf::from("ws2"),
+    )
+    .block_on()
+    .unwrap();
+    let mut ws2 = Workspace::load(
+        &settings,
+        &ws2_root,
+        &test_workspace.env.default_store_factories(),
+        &default_working_copy_factories(),
+    )
+    .unwrap();
+    let ws2_repo = ws2.repo_loader().load_at_head().block_on().unwrap();
+
+    let git_backend1 = get_git_backend(repo.store()).unwrap();
+    let git_backend2 = get_git_backend(ws2_repo.store()).unwrap();
+    git_backend1.take_file_write_stats();
+    let file_path = repo_path("generated");
+    std::fs::write(file_path.to_fs_path_unchecked(&ws1_root), "output").unwrap();
+    std::fs::write(file_path.to_fs_path_unchecked(&ws2_root), "output").unwrap();
+    let tree1 = test_workspace.snapshot().unwrap();
+    let mut locked_ws = ws2.start_working_copy_mutation().unwrap();
+    let (tree2, _stats) = locked_ws
+        .locked_wc()
+        .snapshot(&empty_snapshot_options())
+        .block_on()
+        .unwrap();
+    locked_ws.finish(ws2_repo.op_id().clone()).unwrap();
+
+    assert_eq!(tree1.tree_ids(), tree2.tree_ids());
+    assert_eq!(
+        git_backend1.take_file_write_stats(),
+        FileWriteStats {
+            written_files: 1,
+            reused_files: 0,
+            reused_bytes: 0,
+        }
+    );
+    assert_eq!(
+        git_backend2.take_file_write_stats(),
+        FileWriteStats {
+            written_files: 0,
+            reused_files: 1,
+            reused_bytes: 6,
+        }
+    );
+}
+
 #[cfg(unix)]
 #[test]
 fn test_snapshot_special_file() {
@@ -1569,6 +1705,42 @@ fn test_gitignores() {
     );
 }
 
+#[test]
+fn test_snapshot_ignore_matcher() {
+    // Tests that new files matching the ignore_matcher are ignored, but tracked
+    // files are still snapshotted.
+
+    let mut test_workspace = TestWorkspace::init();
+    let workspace_root = test_workspace.workspace.workspace_root().to_owned();
+
+    let tracked_path = repo_path("tracked");
+    let added_path = repo_path("added");
+    let ignored_path = repo_path("ignored");
+    let subdir_ignored_path = repo_path("dir/ignored");
+
+    testutils::write_working_copy_file(&workspace_root, tracked_path, "1");
+    test_workspace.snapshot().unwrap();
+
+    testutils::write_working_copy_file(&workspace_root, tracked_path, "2");
+    testutils::write_working_copy_file(&workspace_root, added_path, "2");
+    testutils::write_working_copy_file(&workspace_root, ignored_path, "2");
+    std::fs::create_dir(workspace_root.join("dir")).unwrap();
+    testutils::write_working_copy_file(&workspace_root, subdir_ignored_path, "2");
+
+    let ignore_matcher = UnionMatcher::new(
+        FilesMatcher::new([tracked_path, ignored_path]),
+        PrefixMatcher::new([repo_path("dir")]),
+    );
+    let options = SnapshotOptions {
+        ignore_matcher: &ignore_matcher,
+        ..empty_snapshot_options()
+    };
+    let (tree, stats) = test_workspace.snapshot_with_options(&options).unwrap();
+    let files = tree.entries().map(|(name, _value)| name).collect_vec();
+    assert_eq!(files, to_owned_path_vec(&[added_path, tracked_path]));
+    assert!(stats.untracked_paths.is_empty());
+}
+
 #[test]
 fn test_gitignores_in_ignored_dir() {
     // Tests that .gitignore files in an ignored directory are ignored, i.e. that
@@ -730,6 +730,48 @@ fn test_resolve_op_id() {
     );
 }
 
+#[test]
+fn test_resolve_op_tag() {
+    let test_repo = TestRepo::init();
+    let repo_0 = test_repo.repo;
+    let repo_1 = repo_0
+        .start_transaction()
+        .commit("op 1")
+        .block_on()
+        .unwrap();
+    let repo_2 = repo_1
+        .start_transaction()
+        .commit("op 2")
+        .block_on()
+        .unwrap();
+    let op_store = repo_2.op_store();
+    op_store
+        .set_operation_tag("saved", Some(repo_1.op_id()))
+        .unwrap();
+
+    assert_eq!(
+        op_walk::resolve_op_with_repo(&repo_2, "saved").unwrap(),
+        *repo_1.operation()
+    );
+    assert_eq!(
+        op_walk::resolve_op_with_repo(&repo_2, "saved+").unwrap(),
+        *repo_2.operation()
+    );
+    assert_eq!(
+        op_walk::resolve_op_for_load(repo_2.loader(), "saved-").unwrap(),
+        *repo_0.operation()
+    );
+
+    // Deleted tag
+    op_store.set_operation_tag("saved", None).unwrap();
+    assert_matches!(
+        op_walk::resolve_op_with_repo(&repo_2, "saved"),
+        Err(OpsetEvaluationError::OpsetResolution(
+            OpsetResolutionError::InvalidIdPrefix(_)
+        ))
+    );
+}
+
 #[test]
 fn test_resolve_current_op() {
     let settings = stable_op_id_settings();
@@ -1072,6 +1114,44 @@ fn test_gc() {
     assert_eq!(expected_view_entries.len(), 1);
 }
 
+#[test]
+fn test_gc_tagged_operation() {
+    let test_repo = TestRepo::init();
+    let op_dir = test_repo.repo_path().join("op_store").join("operations");
+    let repo_0 = test_repo.repo;
+    let op_store = repo_0.op_store();
+
+    let repo_a = repo_0
+        .start_transaction()
+        .commit("op A")
+        .block_on()
+        .unwrap();
+    let repo_b = repo_0
+        .start_transaction()
+        .commit("op B")
+        .block_on()
+        .unwrap();
+    op_store
+        .set_operation_tag("saved", Some(repo_b.op_id()))
+        .unwrap();
+
+    // B is unreachable from the heads, but kept by the tag
+    let now = SystemTime::now();
+    op_store.gc(slice::from_ref(repo_a.op_id()), now).unwrap();
+    assert_eq!(
+        list_dir(&op_dir),
+        [repo_a.op_id().hex(), repo_b.op_id().hex()]
+            .into_iter()
+            .sorted()
+            .collect_vec()
+    );
+
+    // B is no longer reachable
+    op_store.set_operation_tag("saved", None).unwrap();
+    op_store.gc(slice::from_ref(repo_a.op_id()), now).unwrap();
+    assert_eq!(list_dir(&op_dir), [repo_a.op_id().hex()]);
+}
+
 #[track_caller]
 fn extract_multiple_operations_error(
     error: &OpsetEvaluationError,
@@ -51,11 +51,13 @@ use jj_lib::repo_path::RepoPathUiConverter;
 use jj_lib::revset::ResolvedRevsetExpression;
 use jj_lib::revset::Revset;
 use jj_lib::revset::RevsetAliasesMap;
+use jj_lib::revset::RevsetCursor;
 use jj_lib::revset::RevsetDiagnostics;
 use jj_lib::revset::RevsetEvaluationError;
 use jj_lib::revset::RevsetExpression;
 use jj_lib::revset::RevsetExtensions;
 use jj_lib::revset::RevsetFilterPredicate;
+use jj_lib::revset::RevsetPage;
 use jj_lib::revset::RevsetParseContext;
 use jj_lib::revset::RevsetResolutionError;
 use jj_lib::revset::RevsetWorkspaceContext;
@@ -521,6 +523,56 @@ fn test_resolve_symbol_hidden_change_id() {
     );
 }
 
+#[test]
+fn test_resolve_symbol_hidden_change_id_include_hidden() {
+    let test_repo = TestRepo::init();
+    let repo = &test_repo.repo;
+
+    let mut tx = repo.start_transaction();
+    let commit1 = write_random_commit(tx.repo_mut());
+    let commit2 = write_random_commit(tx.repo_mut());
+    let commit3 = tx
+        .repo_mut()
+        .rewrite_commit(&commit2)
+        .set_description("updated commit")
+        .write_unwrap();
+    tx.repo_mut().rebase_descendants().block_on().unwrap();
+    let repo = tx.commit("test").block_on().unwrap();
+
+    let mut tx = repo.start_transaction();
+    tx.repo_mut().record_abandoned_commit(&commit1);
+    tx.repo_mut().record_abandoned_commit(&commit3);
+    tx.repo_mut().rebase_descendants().block_on().unwrap();
+    let repo = tx.commit("abandon commits").block_on().unwrap();
+
+    let id_prefix_context =
+        IdPrefixContext::new(Arc::new(RevsetExtensions::default())).include_hidden(true);
+    let symbol_resolver =
+        default_symbol_resolver(repo.as_ref()).with_id_prefix_context(&id_prefix_context);
+
+    // Hidden commit is resolved if it's the only commit of the change
+    assert_eq!(
+        symbol_resolver
+            .resolve_symbol(repo.as_ref(), &commit1.change_id().reverse_hex())
+            .unwrap(),
+        commit1.id().clone()
+    );
+    // Ambiguous if the change has multiple hidden commits
+    assert_matches!(
+        symbol_resolver.resolve_symbol(repo.as_ref(), &commit2.change_id().reverse_hex()),
+        Err(RevsetResolutionError::NoSuchRevision { .. })
+    );
+
+    // Hidden commits aren't resolved by default
+    let id_prefix_context = IdPrefixContext::new(Arc::new(RevsetExtensions::default()));
+    let symbol_resolver =
+        default_symbol_resolver(repo.as_ref()).with_id_prefix_context(&id_prefix_context);
+    assert_matches!(
+        symbol_resolver.resolve_symbol(repo.as_ref(), &commit1.change_id().reverse_hex()),
+        Err(RevsetResolutionError::NoSuchRevision { .. })
+    );
+}
+
 #[test]
 fn test_resolve_symbol_in_different_disambiguation_context() {
     let test_repo = TestRepo::init();
@@ -2521,6 +2573,35 @@ fn test_evaluate_expression_visible_heads() {
     );
 }
 
+#[test]
+fn test_evaluate_expression_hidden() {
+    let test_repo = TestRepo::init();
+    let repo = &test_repo.repo;
+
+    let mut tx = repo.start_transaction();
+    let commit1 = write_random_commit(tx.repo_mut());
+    let commit2 = write_random_commit_with_parents(tx.repo_mut(), &[&commit1]);
+    let commit3 = write_random_commit_with_parents(tx.repo_mut(), &[&commit2]);
+    let repo = tx.commit("test").block_on().unwrap();
+
+    assert_eq!(resolve_commit_ids(repo.as_ref(), "hidden()"), vec![]);
+
+    let mut tx = repo.start_transaction();
+    tx.repo_mut().record_abandoned_commit(&commit3);
+    tx.repo_mut().record_abandoned_commit(&commit2);
+    tx.repo_mut().rebase_descendants().block_on().unwrap();
+    let repo = tx.commit("abandon commits").block_on().unwrap();
+
+    assert_eq!(
+        resolve_commit_ids(repo.as_ref(), "hidden()"),
+        vec![commit3.id().clone(), commit2.id().clone()]
+    );
+    assert_eq!(
+        resolve_commit_ids(repo.as_ref(), "hidden() & roots(hidden())"),
+        vec![commit2.id().clone()]
+    );
+}
+
 #[test]
 fn test_evaluate_expression_git_refs() {
     let test_repo = TestRepo::init();
@@ -4216,6 +4297,22 @@ fn test_evaluate_expression_at_operation() {
         ),
         vec![]
     );
+    // visible_at_operation() is the shorthand for that.
+    assert_eq!(
+        resolve_commit_ids(
+            repo2.as_ref(),
+            "visible_at_operation(@-, subject('commit*'))"
+        ),
+        vec![commit2_op1.id().clone()]
+    );
+    assert_eq!(
+        resolve_commit_ids(repo2.as_ref(), "visible_at_operation(@-, commit1_ref)"),
+        vec![]
+    );
+    assert_eq!(
+        resolve_commit_ids(repo2.as_ref(), "visible_at_operation(@, commit1_ref)"),
+        vec![commit1_op2.id().clone()]
+    );
 
     // Bad operation:
     // TODO: should we suppress NoSuchOperation error by present()?
@@ -5045,6 +5142,111 @@ fn test_evaluate_expression_divergent() {
     );
 }
 
+#[test]
+fn test_revset_pagination() {
+    let test_repo = TestRepo::init();
+    let repo = &test_repo.repo;
+
+    //  F
+    //  |\
+    //  D E
+    //  |/
+    //  C
+    //  |
+    //  b
+    //  |
+    //  A
+    let mut tx = repo.start_transaction();
+    let mut_repo = tx.repo_mut();
+    let commit_a = write_random_commit(mut_repo);
+    let commit_b = write_random_commit_with_parents(mut_repo, &[&commit_a]);
+    let commit_c = write_random_commit_with_parents(mut_repo, &[&commit_b]);
+    let commit_d = write_random_commit_with_parents(mut_repo, &[&commit_c]);
+    let commit_e = write_random_commit_with_parents(mut_repo, &[&commit_c]);
+    let commit_f = write_random_commit_with_parents(mut_repo, &[&commit_d, &commit_e]);
+    let repo = tx.commit("test").block_on().unwrap();
+
+    let revset = revset_for_commits(
+        repo.as_ref(),
+        &[&commit_a, &commit_c, &commit_d, &commit_e, &commit_f],
+    );
+    let all_ids = revset.iter().try_collect::<_, Vec<_>, _>().unwrap();
+
+    // Fetch pages, re-creating the iterator from the cursor every time
+    let mut ids = vec![];
+    let mut cursor = None;
+    loop {
+        let mut iter = revset.iter_with_cursors(cursor);
+        let page = RevsetPage::take_from(&mut iter, 2).unwrap();
+        assert!(page.items.len() <= 2);
+        ids.extend(page.items);
+        // Cursors can be stored by clients as plain values
+        cursor = page.next_cursor.map(|c| RevsetCursor::new(c.value()));
+        if cursor.is_none() {
+            break;
+        }
+    }
+    assert_eq!(ids, all_ids);
+
+    // Combined revsets are resumed at the same position
+    let revset_str = format!(
+        "(::{} ~ ::{}) | {} | (::{} & root()..)",
+        commit_f.id(),
+        commit_c.id(),
+        commit_b.id(),
+        commit_b.id(),
+    );
+    let combined_revset = try_evaluate_expression(repo.as_ref(), &revset_str).unwrap();
+    let combined_ids = combined_revset
+        .iter()
+        .try_collect::<_, Vec<_>, _>()
+        .unwrap();
+    assert_eq!(combined_ids.len(), 5);
+    let mut ids = vec![];
+    let mut cursor = None;
+    loop {
+        let mut iter = combined_revset.iter_with_cursors(cursor);
+        let page = RevsetPage::take_from(&mut iter, 1).unwrap();
+        ids.extend(page.items);
+        cursor = page.next_cursor;
+        if cursor.is_none() {
+            break;
+        }
+    }
+    assert_eq!(ids, combined_ids);
+
+    // Fetch pages from a single iterator
+    let mut iter = revset.iter_with_cursors(None);
+    let page = RevsetPage::take_from(&mut iter, 5).unwrap();
+    assert_eq!(page.items, all_ids);
+    assert!(page.next_cursor.is_some());
+    let page = RevsetPage::take_from(&mut iter, 5).unwrap();
+    assert!(page.items.is_empty());
+    assert!(page.next_cursor.is_none());
+
+    // Resumed graph has the same edges as the full graph
+    let all_nodes = revset.iter_graph().try_collect::<_, Vec<_>, _>().unwrap();
+    let mut nodes = vec![];
+    let mut cursor = None;
+    loop {
+        let mut iter = revset.iter_graph_with_cursors(cursor);
+        let page = RevsetPage::take_from(&mut iter, 2).unwrap();
+        nodes.extend(page.items);
+        cursor = page.next_cursor;
+        if cursor.is_none() {
+            break;
+        }
+    }
+    assert_eq!(nodes, all_nodes);
+    assert_eq!(
+        nodes[3],
+        (
+            commit_c.id().clone(),
+            vec![GraphEdge::indirect(commit_a.id().clone())]
+        )
+    );
+}
+
 #[test]
 fn test_reverse_graph() {
     let test_repo = TestRepo::init();
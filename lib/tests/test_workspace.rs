@@ -12,15 +12,25 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::path::Path;
+use std::path::PathBuf;
 use std::thread;
 
 use assert_matches::assert_matches;
+use jj_lib::ref_name::WorkspaceName;
 use jj_lib::ref_name::WorkspaceNameBuf;
 use jj_lib::repo::Repo as _;
+use jj_lib::repo::StoreLoadError;
 use jj_lib::workspace::Workspace;
 use jj_lib::workspace::WorkspaceLoadError;
 use jj_lib::workspace::default_working_copy_factories;
 use jj_lib::workspace::default_working_copy_factory;
+use jj_lib::workspace_store::SimpleWorkspaceStore;
+use jj_lib::workspace_store::WorkspaceCreation;
+use jj_lib::workspace_store::WorkspaceInfo;
+use jj_lib::workspace_store::WorkspaceStore;
+use jj_lib::workspace_store::WorkspaceStoreError;
+use jj_lib::workspace_store::repair_moved_workspace;
 use pollster::FutureExt as _;
 use testutils::TestEnvironment;
 use testutils::TestWorkspace;
@@ -140,6 +150,150 @@ fn test_init_additional_workspace_absolute_path_compat() {
     assert_eq!(same_workspace.workspace_root(), ws2.workspace_root());
 }
 
+#[test]
+fn test_repair_moved_workspace() {
+    let settings = testutils::user_settings();
+    let test_workspace = TestWorkspace::init_with_settings(&settings);
+    let repo_path = dunce::canonicalize(test_workspace.repo_path()).unwrap();
+
+    let ws2_name = WorkspaceNameBuf::from("ws2");
+    let ws2_root = test_workspace.root_dir().join("ws2_root");
+    std::fs::create_dir(&ws2_root).unwrap();
+    let ws2_root = dunce::canonicalize(&ws2_root).unwrap();
+    let (_ws2, repo) = Workspace::init_workspace_with_existing_repo(
+        &ws2_root,
+        &repo_path,
+        &test_workspace.repo,
+        &*default_working_copy_factory(),
+        ws2_name.clone(),
+    )
+    .block_on()
+    .unwrap();
+    let store = SimpleWorkspaceStore::load(&repo_path).unwrap();
+    let creation = store.get_workspace_creation(&ws2_name).unwrap().unwrap();
+    assert_eq!(
+        creation,
+        WorkspaceCreation::from_operation(repo.operation())
+    );
+
+    // The workspace isn't moved.
+    assert_eq!(
+        repair_moved_workspace(&store, &repo_path, &ws2_name, &ws2_root).unwrap(),
+        None
+    );
+
+    // The workspace is moved, so the recorded path is updated.
+    let moved_root = test_workspace.root_dir().join("moved_root");
+    std::fs::rename(&ws2_root, &moved_root).unwrap();
+    let moved_root = dunce::canonicalize(&moved_root).unwrap();
+    let old_info = repair_moved_workspace(&store, &repo_path, &ws2_name, &moved_root)
+        .unwrap()
+        .unwrap();
+    assert_eq!(old_info.absolute_path, Some(ws2_root.clone()));
+    assert!(!old_info.exists);
+    let info = WorkspaceInfo::load(&store, &repo_path, &ws2_name).unwrap();
+    assert_eq!(info.absolute_path, Some(moved_root.clone()));
+    assert!(info.exists);
+    // The creation metadata is kept.
+    assert_eq!(info.creation, Some(creation));
+
+    // The workspace may be copied if the recorded directory exists.
+    let copied_root = test_workspace.root_dir().join("copied_root");
+    std::fs::create_dir(&copied_root).unwrap();
+    assert_eq!(
+        repair_moved_workspace(&store, &repo_path, &ws2_name, &copied_root).unwrap(),
+        None
+    );
+    let info = WorkspaceInfo::load(&store, &repo_path, &ws2_name).unwrap();
+    assert_eq!(info.absolute_path, Some(moved_root));
+}
+
+#[test]
+fn test_load_custom_workspace_store() {
+    #[derive(Debug)]
+    struct TestWorkspaceStore;
+
+    impl WorkspaceStore for TestWorkspaceStore {
+        fn name(&self) -> &'static str {
+            "test"
+        }
+
+        fn add(
+            &self,
+            _name: &WorkspaceName,
+            _path: &Path,
+            _creation: Option<&WorkspaceCreation>,
+        ) -> Result<(), WorkspaceStoreError> {
+            Ok(())
+        }
+
+        fn forget(&self, _names: &[&WorkspaceName]) -> Result<(), WorkspaceStoreError> {
+            Ok(())
+        }
+
+        fn rename(
+            &self,
+            _old_name: &WorkspaceName,
+            _new_name: &WorkspaceName,
+        ) -> Result<(), WorkspaceStoreError> {
+            Ok(())
+        }
+
+        fn get_workspace_path(
+            &self,
+            _name: &WorkspaceName,
+        ) -> Result<Option<PathBuf>, WorkspaceStoreError> {
+            Ok(None)
+        }
+
+        fn get_workspace_creation(
+            &self,
+            _name: &WorkspaceName,
+        ) -> Result<Option<WorkspaceCreation>, WorkspaceStoreError> {
+            Ok(None)
+        }
+    }
+
+    let settings = testutils::user_settings();
+    let test_workspace = TestWorkspace::init_with_settings(&settings);
+    let workspace_root = test_workspace.workspace.workspace_root();
+    let type_path = test_workspace
+        .repo_path()
+        .join("workspace_store")
+        .join("type");
+    assert_eq!(std::fs::read_to_string(&type_path).unwrap(), "simple");
+    std::fs::write(&type_path, "test").unwrap();
+
+    // The store type isn't registered.
+    let result = Workspace::load(
+        &settings,
+        workspace_root,
+        &test_workspace.env.default_store_factories(),
+        &default_working_copy_factories(),
+    );
+    assert_matches!(
+        result.err(),
+        Some(WorkspaceLoadError::StoreLoadError(StoreLoadError::UnsupportedType {
+            store: "workspace",
+            store_type,
+        })) if store_type == "test"
+    );
+
+    let mut store_factories = test_workspace.env.default_store_factories();
+    store_factories.add_workspace_store(
+        "test",
+        Box::new(|_settings, _store_path| Ok(Box::new(TestWorkspaceStore))),
+    );
+    let workspace = Workspace::load(
+        &settings,
+        workspace_root,
+        &store_factories,
+        &default_working_copy_factories(),
+    )
+    .unwrap();
+    assert_eq!(workspace.repo_loader().workspace_store().name(), "test");
+}
+
 #[cfg(unix)]
 #[test]
 fn test_init_additional_workspace_non_utf8_path() {
@@ -163,6 +163,29 @@ pub fn write_commit(
     .detach()
 }
 
+/// Creates or replaces the annotated tag `refs/tags/<name>`, created at
+/// `seconds` since the epoch.
+pub fn write_annotated_tag(
+    repo: &gix::Repository,
+    name: &str,
+    target: gix::ObjectId,
+    seconds: i64,
+) -> gix::ObjectId {
+    let mut tagger = signature();
+    tagger.time = gix::date::Time::new(seconds, 0);
+    repo.tag(
+        name,
+        target,
+        gix::object::Kind::Commit,
+        Some(tagger.to_ref(&mut TimeBuf::default())),
+        "",
+        gix::refs::transaction::PreviousValue::Any,
+    )
+    .unwrap()
+    .id()
+    .detach()
+}
+
 pub fn set_head_to_id(repo: &gix::Repository, target: gix::ObjectId) {
     repo.edit_reference(gix::refs::transaction::RefEdit {
         change: gix::refs::transaction::Change::Update {
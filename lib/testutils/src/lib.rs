@@ -149,6 +149,7 @@ pub fn user_settings() -> UserSettings {
 pub fn empty_snapshot_options() -> SnapshotOptions<'static> {
     SnapshotOptions {
         base_ignores: GitIgnoreFile::empty(),
+        ignore_matcher: &NothingMatcher,
         progress: None,
         start_tracking_matcher: &EverythingMatcher,
         force_tracking_matcher: &NothingMatcher,